@@ -64,6 +64,7 @@ pub(crate) enum Command {
         payer_note: Option<String>,
 
         /// Whether or not this is a drain operation. If true, all available funds will be used.
+        /// If an asset id is provided without an amount, all funds of that asset will be used.
         #[clap(short, long, action = ArgAction::SetTrue)]
         drain: Option<bool>,
 
@@ -75,6 +76,15 @@ pub(crate) enum Command {
         /// If it differs from the provided `asset_id`, it will execute a SideSwap payment.
         #[clap(long)]
         from_asset: Option<String>,
+
+        /// The asset id to pay the swap lockup fees with when paying a Lightning destination.
+        /// Requires `use_asset_fees` to be set.
+        #[clap(long)]
+        fee_asset: Option<String>,
+
+        /// The id of the sub-account the payment is debited from
+        #[clap(long)]
+        sub_account: Option<String>,
     },
     /// Fetch the current limits for Send and Receive payments
     FetchLightningLimits,
@@ -95,6 +105,10 @@ pub(crate) enum Command {
         /// The optional fee rate to use, in sat/vbyte
         #[clap(short = 'f', long = "fee_rate")]
         fee_rate_sat_per_vbyte: Option<u32>,
+
+        /// The asset id to pay the swap lockup fees with
+        #[clap(long)]
+        fee_asset: Option<String>,
     },
     /// Receive a payment directly or via a swap
     ReceivePayment {
@@ -214,6 +228,56 @@ pub(crate) enum Command {
     RescanOnchainSwaps,
     /// Get the balance and general info of the current instance
     GetInfo,
+    /// List the accounts of the wallet seed that have a tx history
+    ListAccounts,
+    /// Create a virtual sub-account in the ledger
+    CreateSubAccount {
+        /// The name of the sub-account
+        name: String,
+    },
+    /// List the sub-accounts of the ledger
+    ListSubAccounts,
+    /// Credit the incoming payments to a destination to a sub-account
+    AttributeToSubAccount {
+        /// The id of the sub-account
+        sub_account_id: String,
+
+        /// The invoice, offer or address receiving the payments
+        destination: String,
+    },
+    /// Move funds between sub-accounts, or from/to the unallocated wallet balance
+    TransferSubAccountFunds {
+        /// The amount to transfer, in satoshi
+        amount_sat: u64,
+
+        /// The source sub-account. If not set, the unallocated wallet balance is used.
+        #[clap(short = 'f', long = "from")]
+        from_sub_account_id: Option<String>,
+
+        /// The target sub-account. If not set, the funds become unallocated.
+        #[clap(short = 't', long = "to")]
+        to_sub_account_id: Option<String>,
+
+        /// Optional description of the transfer
+        #[clap(short = 'd', long = "description")]
+        description: Option<String>,
+    },
+    /// List the ledger entries of the sub-accounts
+    ListLedgerEntries {
+        /// Optional id of the sub-account
+        #[clap(short = 's', long = "sub-account")]
+        sub_account_id: Option<String>,
+
+        /// Optional limit of listed entries
+        #[clap(short = 'l', long = "limit")]
+        limit: Option<u32>,
+
+        /// Optional offset in entries
+        #[clap(short = 'o', long = "offset")]
+        offset: Option<u32>,
+    },
+    /// Get how much of the wallet balance is held by the sub-accounts
+    GetLedgerInfo,
     /// Sign a message using the wallet private key
     SignMessage {
         /// The message to sign
@@ -403,6 +467,7 @@ pub(crate) async fn handle_command(
                 .prepare_receive_payment(&PrepareReceiveRequest {
                     payment_method,
                     amount: amount.clone(),
+                    swap_provider: None,
                 })
                 .await?;
 
@@ -433,6 +498,7 @@ pub(crate) async fn handle_command(
                         }
                     }),
                     payer_note,
+                    settle_as_asset: None,
                 })
                 .await?;
 
@@ -471,6 +537,8 @@ pub(crate) async fn handle_command(
             drain,
             delay,
             from_asset,
+            fee_asset,
+            sub_account,
         } => {
             let destination = invoice.or(offer.or(address.clone())).ok_or(anyhow!(
                 "Must specify either a BOLT11 invoice, a BOLT12 offer or a direct/BIP21 address."
@@ -498,6 +566,7 @@ pub(crate) async fn handle_command(
                     estimate_asset_fees: use_asset_fees,
                     from_asset,
                 }),
+                (Some(asset_id), None, _, true) => Some(PayAmount::DrainAsset { asset_id }),
                 (None, None, Some(receiver_amount_sat), _) => Some(PayAmount::Bitcoin {
                     receiver_amount_sat,
                 }),
@@ -511,6 +580,10 @@ pub(crate) async fn handle_command(
                     amount,
                     disable_mrh: None,
                     payment_timeout_sec: None,
+                    swap_provider: None,
+                    from_asset: None,
+                    fee_asset,
+                    export_pset: None,
                 })
                 .await?;
 
@@ -543,6 +616,7 @@ pub(crate) async fn handle_command(
                 prepare_response: prepare_response.clone(),
                 use_asset_fees,
                 payer_note,
+                sub_account_id: sub_account,
             };
 
             if let Some(delay) = delay {
@@ -563,6 +637,7 @@ pub(crate) async fn handle_command(
             receiver_amount_sat,
             drain,
             fee_rate_sat_per_vbyte,
+            fee_asset,
         } => {
             let amount = match drain.unwrap_or(false) {
                 true => PayAmount::Drain,
@@ -576,21 +651,29 @@ pub(crate) async fn handle_command(
                 .prepare_pay_onchain(&PreparePayOnchainRequest {
                     amount,
                     fee_rate_sat_per_vbyte,
+                    swap_provider: None,
+                    fee_asset,
                 })
                 .await?;
 
+            let asset_fees_msg = prepare_response
+                .estimated_asset_fees
+                .map(|asset_fees| format!(" Lockup fees: approx {asset_fees}."))
+                .unwrap_or_default();
             wait_confirmation!(
                 format!(
-                    "Fees: {} sat (incl claim fee: {} sat). Receiver amount: {} sat. Are the fees acceptable? (y/N) ",
+                    "Fees: {} sat (incl claim fee: {} sat).{asset_fees_msg} Receiver amount: {} sat. Are the fees acceptable? (y/N) ",
                     prepare_response.total_fees_sat, prepare_response.claim_fees_sat, prepare_response.receiver_amount_sat
                 ),
                 "Payment send halted"
             );
 
+            let use_asset_fees = prepare_response.fee_asset.is_some().then_some(true);
             let response = sdk
                 .pay_onchain(&PayOnchainRequest {
                     address,
                     prepare_response,
+                    use_asset_fees,
                 })
                 .await?;
             command_result!(response)
@@ -628,9 +711,65 @@ pub(crate) async fn handle_command(
         Command::GetInfo => {
             command_result!(sdk.get_info().await?)
         }
+        Command::ListAccounts => {
+            command_result!(sdk.list_accounts().await?)
+        }
+        Command::CreateSubAccount { name } => {
+            command_result!(
+                sdk.create_sub_account(&CreateSubAccountRequest { name })
+                    .await?
+            )
+        }
+        Command::ListSubAccounts => {
+            command_result!(sdk.list_sub_accounts().await?)
+        }
+        Command::AttributeToSubAccount {
+            sub_account_id,
+            destination,
+        } => {
+            sdk.attribute_to_sub_account(&AttributeToSubAccountRequest {
+                sub_account_id,
+                destination,
+            })
+            .await?;
+            command_result!("Destination attributed to the sub-account")
+        }
+        Command::TransferSubAccountFunds {
+            amount_sat,
+            from_sub_account_id,
+            to_sub_account_id,
+            description,
+        } => {
+            command_result!(
+                sdk.transfer_sub_account_funds(&TransferSubAccountFundsRequest {
+                    from_sub_account_id,
+                    to_sub_account_id,
+                    amount_sat,
+                    description,
+                })
+                .await?
+            )
+        }
+        Command::ListLedgerEntries {
+            sub_account_id,
+            limit,
+            offset,
+        } => {
+            command_result!(
+                sdk.list_ledger_entries(&ListLedgerEntriesRequest {
+                    sub_account_id,
+                    offset,
+                    limit,
+                })
+                .await?
+            )
+        }
+        Command::GetLedgerInfo => {
+            command_result!(sdk.get_ledger_info().await?)
+        }
         Command::SignMessage { message } => {
             let req = SignMessageRequest { message };
-            let res = sdk.sign_message(&req)?;
+            let res = sdk.sign_message(&req).await?;
             command_result!(format!("Message signature: {}", res.signature))
         }
         Command::CheckMessage {
@@ -41,6 +41,9 @@ pub(crate) struct Args {
     #[clap(long)]
     pub(crate) passphrase: Option<String>,
 
+    #[clap(long)]
+    pub(crate) account_index: Option<u32>,
+
     #[clap(long, default_value = "false")]
     pub(crate) no_qrs: bool,
 
@@ -120,6 +123,7 @@ async fn main() -> Result<()> {
         mnemonic: Some(mnemonic.to_string()),
         passphrase,
         seed: None,
+        account_index: args.account_index,
     })
     .await?;
     if args.nwc {
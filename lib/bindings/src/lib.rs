@@ -94,6 +94,20 @@ pub fn connect_with_signer(
     })
 }
 
+pub fn connect_watch_only(req: ConnectWatchOnlyRequest) -> Result<Arc<BindingLiquidSdk>, SdkError> {
+    rt().block_on(async {
+        let sdk = LiquidSdk::connect_watch_only(req).await?;
+        Ok(Arc::new(BindingLiquidSdk { sdk }))
+    })
+}
+
+pub fn connect_multisig(req: ConnectMultisigRequest) -> Result<Arc<BindingLiquidSdk>, SdkError> {
+    rt().block_on(async {
+        let sdk = LiquidSdk::connect_multisig(req).await?;
+        Ok(Arc::new(BindingLiquidSdk { sdk }))
+    })
+}
+
 pub fn default_config(
     network: LiquidNetwork,
     breez_api_key: Option<String>,
@@ -124,8 +138,12 @@ impl BindingLiquidSdk {
         rt().block_on(self.sdk.get_info())
     }
 
+    pub fn list_accounts(&self) -> SdkResult<Vec<AccountInfo>> {
+        rt().block_on(self.sdk.list_accounts())
+    }
+
     pub fn sign_message(&self, req: SignMessageRequest) -> SdkResult<SignMessageResponse> {
-        self.sdk.sign_message(&req)
+        rt().block_on(self.sdk.sign_message(&req))
     }
 
     pub fn check_message(&self, req: CheckMessageRequest) -> SdkResult<CheckMessageResponse> {
@@ -150,6 +168,24 @@ impl BindingLiquidSdk {
         rt().block_on(self.sdk.send_payment(&req))
     }
 
+    pub fn finalize_send_payment(
+        &self,
+        req: FinalizeSendPaymentRequest,
+    ) -> Result<SendPaymentResponse, PaymentError> {
+        rt().block_on(self.sdk.finalize_send_payment(&req))
+    }
+
+    pub fn sign_pset(&self, req: SignPsetRequest) -> Result<SignPsetResponse, PaymentError> {
+        rt().block_on(self.sdk.sign_pset(&req))
+    }
+
+    pub fn combine_psets(
+        &self,
+        req: CombinePsetsRequest,
+    ) -> Result<CombinePsetsResponse, PaymentError> {
+        self.sdk.combine_psets(&req)
+    }
+
     pub fn prepare_receive_payment(
         &self,
         req: PrepareReceiveRequest,
@@ -190,6 +226,56 @@ impl BindingLiquidSdk {
         rt().block_on(self.sdk.pay_onchain(&req))
     }
 
+    pub fn receive_peg_in(
+        &self,
+        req: ReceivePegInRequest,
+    ) -> Result<ReceivePegInResponse, PaymentError> {
+        rt().block_on(self.sdk.receive_peg_in(&req))
+    }
+
+    pub fn prepare_peg_out(
+        &self,
+        req: PreparePegOutRequest,
+    ) -> Result<PreparePegOutResponse, PaymentError> {
+        rt().block_on(self.sdk.prepare_peg_out(&req))
+    }
+
+    pub fn peg_out(&self, req: PegOutRequest) -> Result<SendPaymentResponse, PaymentError> {
+        rt().block_on(self.sdk.peg_out(&req))
+    }
+
+    pub fn issue_asset(&self, req: IssueAssetRequest) -> Result<IssueAssetResponse, PaymentError> {
+        rt().block_on(self.sdk.issue_asset(&req))
+    }
+
+    pub fn reissue_asset(
+        &self,
+        req: ReissueAssetRequest,
+    ) -> Result<AssetOperationResponse, PaymentError> {
+        rt().block_on(self.sdk.reissue_asset(&req))
+    }
+
+    pub fn burn_asset(
+        &self,
+        req: BurnAssetRequest,
+    ) -> Result<AssetOperationResponse, PaymentError> {
+        rt().block_on(self.sdk.burn_asset(&req))
+    }
+
+    pub fn prepare_asset_swap(
+        &self,
+        req: PrepareAssetSwapRequest,
+    ) -> Result<PrepareAssetSwapResponse, PaymentError> {
+        rt().block_on(self.sdk.prepare_asset_swap(&req))
+    }
+
+    pub fn asset_swap(
+        &self,
+        req: AssetSwapRequest,
+    ) -> Result<AssetOperationResponse, PaymentError> {
+        rt().block_on(self.sdk.asset_swap(&req))
+    }
+
     pub fn prepare_buy_bitcoin(
         &self,
         req: PrepareBuyBitcoinRequest,
@@ -209,6 +295,36 @@ impl BindingLiquidSdk {
         rt().block_on(self.sdk.get_payment(&req))
     }
 
+    pub fn create_sub_account(&self, req: CreateSubAccountRequest) -> SdkResult<SubAccount> {
+        rt().block_on(self.sdk.create_sub_account(&req))
+    }
+
+    pub fn list_sub_accounts(&self) -> SdkResult<Vec<SubAccount>> {
+        rt().block_on(self.sdk.list_sub_accounts())
+    }
+
+    pub fn attribute_to_sub_account(&self, req: AttributeToSubAccountRequest) -> SdkResult<()> {
+        rt().block_on(self.sdk.attribute_to_sub_account(&req))
+    }
+
+    pub fn transfer_sub_account_funds(
+        &self,
+        req: TransferSubAccountFundsRequest,
+    ) -> SdkResult<Vec<LedgerEntry>> {
+        rt().block_on(self.sdk.transfer_sub_account_funds(&req))
+    }
+
+    pub fn list_ledger_entries(
+        &self,
+        req: ListLedgerEntriesRequest,
+    ) -> SdkResult<Vec<LedgerEntry>> {
+        rt().block_on(self.sdk.list_ledger_entries(&req))
+    }
+
+    pub fn get_ledger_info(&self) -> SdkResult<LedgerInfo> {
+        rt().block_on(self.sdk.get_ledger_info())
+    }
+
     pub fn fetch_payment_proposed_fees(
         &self,
         req: FetchPaymentProposedFeesRequest,
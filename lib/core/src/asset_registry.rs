@@ -0,0 +1,416 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{anyhow, ensure, Result};
+use futures::future::join_all;
+use log::{debug, info, warn};
+use lwk_wollet::elements::{AssetId, OutPoint, Txid};
+use sdk_common::prelude::{parse_json, RestClient};
+use serde::Deserialize;
+use tokio::sync::broadcast;
+use tokio_with_wasm::alias as tokio;
+
+use crate::{model::AssetMetadata, persist::Persister, utils};
+
+/// How long an asset that could not be resolved is skipped before being looked up again
+const UNRESOLVED_RETRY_SECS: u32 = 60 * 60;
+
+/// How long the lookup of a single asset may take before it is considered failed
+const LOOKUP_TIMEOUT_SECS: u64 = 10;
+
+/// An asset entry, in the format served by the
+/// [Liquid Asset Registry](https://docs.liquid.net/docs/blockstream-liquid-asset-registry)
+#[derive(Debug, Deserialize)]
+struct RegistryAsset {
+    asset_id: String,
+    contract: serde_json::Value,
+    issuance_prevout: RegistryPrevout,
+}
+
+#[derive(Debug, Deserialize)]
+struct RegistryPrevout {
+    txid: String,
+    vout: u32,
+}
+
+impl RegistryAsset {
+    /// Validates that the asset id commits to the contract and returns the asset metadata
+    fn validate(self) -> Result<AssetMetadata> {
+        let contract: lwk_wollet::Contract = serde_json::from_value(self.contract)?;
+        contract.validate()?;
+        let prevout = OutPoint::new(
+            Txid::from_str(&self.issuance_prevout.txid)?,
+            self.issuance_prevout.vout,
+        );
+        let entropy = AssetId::generate_asset_entropy(prevout, contract.contract_hash()?);
+        let asset_id = AssetId::from_entropy(entropy).to_string();
+        ensure!(
+            asset_id == self.asset_id,
+            "Contract does not match asset {}",
+            self.asset_id
+        );
+
+        Ok(AssetMetadata {
+            asset_id,
+            name: contract.name,
+            ticker: contract.ticker,
+            precision: contract.precision,
+            fiat_id: None,
+        })
+    }
+}
+
+/// The assets being looked up and the ones that could not be resolved
+#[derive(Default)]
+struct Lookups {
+    in_progress: HashSet<String>,
+    /// The time of the last failed lookup of the assets that could not be resolved, which are
+    /// not looked up again until [UNRESOLVED_RETRY_SECS] have passed
+    unresolved: HashMap<String, u32>,
+}
+
+/// Looks up the metadata of assets unknown to the SDK in an asset registry and caches it
+pub(crate) struct AssetRegistry {
+    registry_url: Option<String>,
+    persister: Arc<Persister>,
+    rest_client: Arc<dyn RestClient>,
+    lookups: Mutex<Lookups>,
+    subscription_notifier: broadcast::Sender<String>,
+}
+
+impl AssetRegistry {
+    pub(crate) fn new(
+        registry_url: Option<String>,
+        persister: Arc<Persister>,
+        rest_client: Arc<dyn RestClient>,
+    ) -> Self {
+        let (subscription_notifier, _) = broadcast::channel::<String>(30);
+        Self {
+            registry_url,
+            persister,
+            rest_client,
+            lookups: Default::default(),
+            subscription_notifier,
+        }
+    }
+
+    /// Notifies the id of the payments whose asset metadata was resolved
+    pub(crate) fn subscribe_payment_updates(&self) -> broadcast::Receiver<String> {
+        self.subscription_notifier.subscribe()
+    }
+
+    /// Resolves the assets of the given payments in a background task, notifying an update of
+    /// the payments holding an asset once it is resolved.
+    ///
+    /// `payment_assets` maps each asset id to the tx ids of the payments holding it.
+    pub(crate) fn resolve_assets_in_background(
+        self: &Arc<Self>,
+        payment_assets: HashMap<String, HashSet<String>>,
+    ) {
+        if self.registry_url.is_none() || payment_assets.is_empty() {
+            return;
+        }
+        let registry = self.clone();
+        tokio::spawn(async move {
+            let asset_ids = payment_assets.keys().cloned().collect();
+            for asset_id in registry.resolve_assets(asset_ids).await {
+                for tx_id in payment_assets.get(&asset_id).into_iter().flatten() {
+                    let _ = registry.subscription_notifier.send(tx_id.clone());
+                }
+            }
+        });
+    }
+
+    /// Fetches and caches the metadata of the given assets, skipping the ones already known or
+    /// being looked up. Returns the assets that were resolved.
+    ///
+    /// The assets are looked up concurrently, each within [LOOKUP_TIMEOUT_SECS].
+    pub(crate) async fn resolve_assets(&self, asset_ids: HashSet<String>) -> Vec<String> {
+        let Some(registry_url) = &self.registry_url else {
+            return vec![];
+        };
+
+        let asset_ids: Vec<String> = asset_ids
+            .into_iter()
+            .filter(
+                |asset_id| match self.persister.get_asset_metadata(asset_id) {
+                    Ok(maybe_asset_metadata) => maybe_asset_metadata.is_none(),
+                    Err(e) => {
+                        warn!("Failed to get asset metadata for {asset_id}: {e:?}");
+                        false
+                    }
+                },
+            )
+            .collect();
+        let asset_ids: Vec<String> = {
+            let mut lookups = self.lookups.lock().unwrap();
+            let now = utils::now();
+            lookups
+                .unresolved
+                .retain(|_, failed_at| now.saturating_sub(*failed_at) < UNRESOLVED_RETRY_SECS);
+            let asset_ids: Vec<String> = asset_ids
+                .into_iter()
+                .filter(|asset_id| {
+                    !lookups.unresolved.contains_key(asset_id)
+                        && !lookups.in_progress.contains(asset_id)
+                })
+                .collect();
+            lookups.in_progress.extend(asset_ids.iter().cloned());
+            asset_ids
+        };
+
+        let results = join_all(asset_ids.into_iter().map(|asset_id| async move {
+            let res = tokio::time::timeout(
+                Duration::from_secs(LOOKUP_TIMEOUT_SECS),
+                self.fetch_asset_metadata(registry_url, &asset_id),
+            )
+            .await
+            .unwrap_or_else(|_| Err(anyhow!("Lookup timed out")));
+            (asset_id, res)
+        }))
+        .await;
+
+        let mut resolved_asset_ids = vec![];
+        for (asset_id, res) in results {
+            match res {
+                Ok(asset_metadata) => {
+                    info!(
+                        "Resolved asset {asset_id} as {} ({})",
+                        asset_metadata.name, asset_metadata.ticker
+                    );
+                    match self
+                        .persister
+                        .insert_registry_asset_metadata(&asset_metadata)
+                    {
+                        Ok(()) => resolved_asset_ids.push(asset_id.clone()),
+                        Err(e) => warn!("Failed to cache asset metadata for {asset_id}: {e:?}"),
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to resolve asset {asset_id}: {e:?}");
+                    let mut lookups = self.lookups.lock().unwrap();
+                    lookups.unresolved.insert(asset_id.clone(), utils::now());
+                }
+            }
+            self.lookups.lock().unwrap().in_progress.remove(&asset_id);
+        }
+        resolved_asset_ids
+    }
+
+    async fn fetch_asset_metadata(
+        &self,
+        registry_url: &str,
+        asset_id: &str,
+    ) -> Result<AssetMetadata> {
+        let url = format!("{}/{asset_id}", registry_url.trim_end_matches('/'));
+        debug!("Fetching asset metadata from {url}");
+        let (response, status_code) = self.rest_client.get(&url).await?;
+        ensure!(
+            status_code == 200,
+            "Asset registry returned status code {status_code}"
+        );
+        let asset: RegistryAsset = parse_json(&response)?;
+        ensure!(
+            asset.asset_id == asset_id,
+            "Asset registry returned asset {}",
+            asset.asset_id
+        );
+        asset
+            .validate()
+            .map_err(|e| anyhow!("Invalid asset registry entry: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{HashMap, HashSet},
+        sync::Arc,
+        time::Duration,
+    };
+
+    use anyhow::Result;
+    use sdk_common::prelude::{MockResponse, MockRestClient};
+    use serde_json::json;
+    use tokio_with_wasm::alias as tokio;
+
+    use crate::test_utils::{clock::MockClock, persist::create_persister};
+
+    use super::{AssetRegistry, UNRESOLVED_RETRY_SECS};
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    const USDT_ASSET_ID: &str = "ce091c998b83c78bb71a632313ba3760f1763d9cfcffae02258ffa9865a37bd2";
+
+    fn usdt_registry_entry(name: &str) -> String {
+        json!({
+            "asset_id": USDT_ASSET_ID,
+            "contract": {
+                "entity": { "domain": "tether.to" },
+                "issuer_pubkey": "0337cceec0beea0232ebe14cba0197a9fbd45fcf2ec946749de920e71434c2b904",
+                "name": name,
+                "precision": 8,
+                "ticker": "USDt",
+                "version": 0
+            },
+            "issuance_txin": {
+                "txid": "abb4080d91849e933ee2ed65da6b436f7c385cf363fb4aa08399f1e27c58ff3d",
+                "vin": 0
+            },
+            "issuance_prevout": {
+                "txid": "9596d259270ef5bac0020435e6d859aea633409483ba64e232b8ba04ce288668",
+                "vout": 0
+            },
+            "name": name,
+            "ticker": "USDt",
+            "precision": 8
+        })
+        .to_string()
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_resolve_assets() -> Result<()> {
+        create_persister!(persister);
+        let rest_client = Arc::new(MockRestClient::new());
+        let registry = AssetRegistry::new(
+            Some("http://localhost:3000/".to_string()),
+            persister.clone(),
+            rest_client.clone(),
+        );
+
+        rest_client.add_response(MockResponse::new(200, usdt_registry_entry("Tether USD")));
+        registry
+            .resolve_assets(HashSet::from([USDT_ASSET_ID.to_string()]))
+            .await;
+        let asset_metadata = persister
+            .get_asset_metadata(USDT_ASSET_ID)?
+            .expect("Asset metadata should be cached");
+        assert_eq!(asset_metadata.name, "Tether USD");
+        assert_eq!(asset_metadata.ticker, "USDt");
+        assert_eq!(asset_metadata.precision, 8);
+
+        // The cached metadata is kept when the configured metadata is replaced
+        persister.replace_asset_metadata(None)?;
+        assert!(persister.get_asset_metadata(USDT_ASSET_ID)?.is_some());
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_resolve_assets_in_background() -> Result<()> {
+        create_persister!(persister);
+        let rest_client = Arc::new(MockRestClient::new());
+        let registry = Arc::new(AssetRegistry::new(
+            Some("http://localhost:3000".to_string()),
+            persister.clone(),
+            rest_client.clone(),
+        ));
+        let mut payment_updates = registry.subscribe_payment_updates();
+
+        rest_client.add_response(MockResponse::new(200, usdt_registry_entry("Tether USD")));
+        let tx_ids = HashSet::from(["tx_1".to_string(), "tx_2".to_string()]);
+        registry.resolve_assets_in_background(HashMap::from([(
+            USDT_ASSET_ID.to_string(),
+            tx_ids.clone(),
+        )]));
+
+        // Each payment holding the resolved asset is updated
+        let mut updated_tx_ids = HashSet::new();
+        for _ in 0..tx_ids.len() {
+            let tx_id =
+                tokio::time::timeout(Duration::from_secs(5), payment_updates.recv()).await??;
+            updated_tx_ids.insert(tx_id);
+        }
+        assert_eq!(updated_tx_ids, tx_ids);
+        assert!(persister.get_asset_metadata(USDT_ASSET_ID)?.is_some());
+
+        // Known assets are not looked up again
+        assert!(registry
+            .resolve_assets(HashSet::from([USDT_ASSET_ID.to_string()]))
+            .await
+            .is_empty());
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_resolve_assets_rejects_invalid_contract() -> Result<()> {
+        create_persister!(persister);
+        let rest_client = Arc::new(MockRestClient::new());
+        let registry = AssetRegistry::new(
+            Some("http://localhost:3000".to_string()),
+            persister.clone(),
+            rest_client.clone(),
+        );
+
+        // The contract hash no longer matches the asset id
+        rest_client.add_response(MockResponse::new(200, usdt_registry_entry("Fake USD")));
+        registry
+            .resolve_assets(HashSet::from([USDT_ASSET_ID.to_string()]))
+            .await;
+        assert!(persister.get_asset_metadata(USDT_ASSET_ID)?.is_none());
+
+        // Unresolved assets are not looked up again
+        registry
+            .resolve_assets(HashSet::from([USDT_ASSET_ID.to_string()]))
+            .await;
+        assert!(persister.get_asset_metadata(USDT_ASSET_ID)?.is_none());
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_resolve_assets_retries_after_expiry() -> Result<()> {
+        create_persister!(persister);
+        let clock = MockClock::install(1_700_000_000);
+        let rest_client = Arc::new(MockRestClient::new());
+        let registry = AssetRegistry::new(
+            Some("http://localhost:3000".to_string()),
+            persister.clone(),
+            rest_client.clone(),
+        );
+
+        rest_client.add_response(MockResponse::new(503, "Unavailable".to_string()));
+        registry
+            .resolve_assets(HashSet::from([USDT_ASSET_ID.to_string()]))
+            .await;
+        assert!(persister.get_asset_metadata(USDT_ASSET_ID)?.is_none());
+
+        // The failed lookup is skipped until it expires
+        rest_client.add_response(MockResponse::new(200, usdt_registry_entry("Tether USD")));
+        registry
+            .resolve_assets(HashSet::from([USDT_ASSET_ID.to_string()]))
+            .await;
+        assert!(persister.get_asset_metadata(USDT_ASSET_ID)?.is_none());
+
+        clock.advance(Duration::from_secs(UNRESOLVED_RETRY_SECS as u64));
+        registry
+            .resolve_assets(HashSet::from([USDT_ASSET_ID.to_string()]))
+            .await;
+        assert!(persister.get_asset_metadata(USDT_ASSET_ID)?.is_some());
+
+        MockClock::uninstall();
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_resolve_assets_not_found() -> Result<()> {
+        create_persister!(persister);
+        let rest_client = Arc::new(MockRestClient::new());
+        let registry = AssetRegistry::new(
+            Some("http://localhost:3000".to_string()),
+            persister.clone(),
+            rest_client.clone(),
+        );
+        let asset_id = "0000000000000000000000000000000000000000000000000000000000000001";
+
+        rest_client.add_response(MockResponse::new(404, "Not Found".to_string()));
+        registry
+            .resolve_assets(HashSet::from([asset_id.to_string()]))
+            .await;
+        assert!(persister.get_asset_metadata(asset_id)?.is_none());
+        Ok(())
+    }
+}
@@ -7,9 +7,11 @@ use tokio::sync::Mutex;
 
 use crate::{
     bitcoin::{
+        block::Header,
         consensus::{deserialize, serialize},
-        hashes::{sha256, Hash},
-        Address, OutPoint, Script, ScriptBuf, Transaction, Txid,
+        hashes::{sha256, sha256d, Hash, HashEngine},
+        merkle_tree::{MerkleBlock, PartialMerkleTree},
+        Address, BlockHash, OutPoint, Script, ScriptBuf, Transaction, TxMerkleNode, Txid,
     },
     chain::{with_empty_retry, with_error_retry},
     model::{BlockchainExplorer, Config, RecommendedFees, Utxo},
@@ -107,6 +109,11 @@ impl BitcoinChainService for ElectrumBitcoinChainService {
         }
     }
 
+    async fn get_block_hash(&self, height: u32) -> Result<BlockHash> {
+        let header = self.get_client()?.block_header(height as usize)?;
+        Ok(header.block_hash())
+    }
+
     async fn broadcast(&self, tx: &Transaction) -> Result<Txid> {
         let txid = self
             .get_client()?
@@ -312,6 +319,13 @@ impl BitcoinChainService for ElectrumBitcoinChainService {
         }
     }
 
+    async fn get_txout_proof(&self, txid: &Txid, height: u32) -> Result<MerkleBlock> {
+        let client = self.get_client()?;
+        let merkle = client.transaction_get_merkle(txid, height as usize)?;
+        let header = client.block_header(height as usize)?;
+        merkle_block_from_branch(header, *txid, merkle.pos, &merkle.merkle)
+    }
+
     async fn recommended_fees(&self) -> Result<RecommendedFees> {
         let fees: Vec<u64> = self
             .get_client()?
@@ -328,3 +342,184 @@ impl BitcoinChainService for ElectrumBitcoinChainService {
         })
     }
 }
+
+/// Builds the proof that a tx is included in a block from its Electrum merkle branch.
+///
+/// Electrum servers don't return the number of txs in the block, which the proof commits to.
+/// It is instead derived from the branch: a node whose sibling is itself is the last node of
+/// its level.
+fn merkle_block_from_branch(
+    header: Header,
+    txid: Txid,
+    pos: usize,
+    branch: &[[u8; 32]],
+) -> Result<MerkleBlock> {
+    // The branch hashes can be encoded either in internal or in display byte order
+    for reversed in [false, true] {
+        let branch: Vec<sha256d::Hash> = branch
+            .iter()
+            .map(|hash| {
+                let mut hash = *hash;
+                if reversed {
+                    hash.reverse();
+                }
+                sha256d::Hash::from_byte_array(hash)
+            })
+            .collect();
+        let Some(num_tx) = num_tx_from_branch(&header, txid, pos, &branch) else {
+            continue;
+        };
+
+        let mut traversal = BranchTraversal {
+            txid,
+            pos,
+            num_tx,
+            branch: &branch,
+            hashes: vec![],
+            bits: vec![],
+        };
+        traversal.traverse(branch.len(), 0);
+
+        let mut flags = vec![0u8; traversal.bits.len().div_ceil(8)];
+        for (i, bit) in traversal.bits.iter().enumerate() {
+            flags[i / 8] |= (*bit as u8) << (i % 8);
+        }
+        let mut txn = serialize(&(num_tx as u32));
+        txn.extend(serialize(&traversal.hashes));
+        txn.extend(serialize(&flags));
+        let txn: PartialMerkleTree = deserialize(&txn)?;
+        return Ok(MerkleBlock { header, txn });
+    }
+    bail!("Merkle branch of tx {txid} does not match the block merkle root")
+}
+
+/// Returns the smallest number of txs consistent with the branch, or `None` if the branch
+/// does not lead to the block merkle root
+fn num_tx_from_branch(
+    header: &Header,
+    txid: Txid,
+    pos: usize,
+    branch: &[sha256d::Hash],
+) -> Option<usize> {
+    let mut num_tx = 1 << branch.len();
+    let mut node = txid.to_raw_hash();
+    for (height, sibling) in branch.iter().enumerate() {
+        let index = pos >> height;
+        let mut engine = sha256d::Hash::engine();
+        if index % 2 == 0 {
+            if *sibling == node {
+                num_tx = num_tx.min((index + 1) << height);
+            }
+            engine.input(node.as_byte_array());
+            engine.input(sibling.as_byte_array());
+        } else {
+            engine.input(sibling.as_byte_array());
+            engine.input(node.as_byte_array());
+        }
+        node = sha256d::Hash::from_engine(engine);
+    }
+    (node == header.merkle_root.to_raw_hash()).then_some(num_tx)
+}
+
+/// Walks the partial merkle tree depth-first, as defined in BIP37, along the branch of a tx
+struct BranchTraversal<'a> {
+    txid: Txid,
+    pos: usize,
+    num_tx: usize,
+    branch: &'a [sha256d::Hash],
+    hashes: Vec<TxMerkleNode>,
+    bits: Vec<bool>,
+}
+
+impl BranchTraversal<'_> {
+    fn traverse(&mut self, height: usize, index: usize) {
+        let is_parent_of_match = self.pos >> height == index;
+        self.bits.push(is_parent_of_match);
+        if !is_parent_of_match {
+            // Nodes outside of the path are always siblings of a node in the path
+            self.hashes
+                .push(TxMerkleNode::from_raw_hash(self.branch[height]));
+            return;
+        }
+        if height == 0 {
+            self.hashes
+                .push(TxMerkleNode::from_raw_hash(self.txid.to_raw_hash()));
+            return;
+        }
+        self.traverse(height - 1, index * 2);
+        let width = (self.num_tx + (1 << (height - 1)) - 1) >> (height - 1);
+        if index * 2 + 1 < width {
+            self.traverse(height - 1, index * 2 + 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use crate::bitcoin::{
+        block::{Header, Version},
+        hashes::{sha256d, Hash, HashEngine},
+        merkle_tree, BlockHash, CompactTarget, TxMerkleNode, Txid,
+    };
+
+    use super::merkle_block_from_branch;
+
+    /// Computes the merkle branch of the tx at `pos`, as returned by Electrum servers
+    fn electrum_branch(txids: &[Txid], mut pos: usize) -> Vec<[u8; 32]> {
+        let mut level: Vec<sha256d::Hash> = txids.iter().map(|txid| txid.to_raw_hash()).collect();
+        let mut branch = vec![];
+        while level.len() > 1 {
+            if level.len() % 2 == 1 {
+                level.push(*level.last().unwrap());
+            }
+            let mut sibling = level[pos ^ 1].to_byte_array();
+            sibling.reverse();
+            branch.push(sibling);
+            level = level
+                .chunks(2)
+                .map(|pair| {
+                    let mut engine = sha256d::Hash::engine();
+                    engine.input(pair[0].as_byte_array());
+                    engine.input(pair[1].as_byte_array());
+                    sha256d::Hash::from_engine(engine)
+                })
+                .collect();
+            pos /= 2;
+        }
+        branch
+    }
+
+    #[sdk_macros::test_all]
+    fn test_merkle_block_from_branch() -> Result<()> {
+        for num_tx in 1..=9u8 {
+            let txids: Vec<Txid> = (0..num_tx)
+                .map(|i| Txid::from_raw_hash(sha256d::Hash::hash(&[i])))
+                .collect();
+            let header = Header {
+                version: Version::ONE,
+                prev_blockhash: BlockHash::all_zeros(),
+                merkle_root: TxMerkleNode::from_raw_hash(
+                    merkle_tree::calculate_root(txids.iter().cloned())
+                        .unwrap()
+                        .to_raw_hash(),
+                ),
+                time: 0,
+                bits: CompactTarget::from_consensus(0),
+                nonce: 0,
+            };
+
+            for (pos, txid) in txids.iter().enumerate() {
+                let branch = electrum_branch(&txids, pos);
+                let merkle_block = merkle_block_from_branch(header, *txid, pos, &branch)?;
+
+                let (mut matches, mut indexes) = (vec![], vec![]);
+                merkle_block.extract_matches(&mut matches, &mut indexes)?;
+                assert_eq!(matches, vec![*txid]);
+                assert_eq!(indexes, vec![pos as u32]);
+            }
+        }
+        Ok(())
+    }
+}
@@ -8,7 +8,8 @@ use crate::{
     bitcoin::{
         consensus::deserialize,
         hashes::{sha256, Hash},
-        Address, OutPoint, Script, ScriptBuf, Transaction, Txid,
+        merkle_tree::MerkleBlock,
+        Address, BlockHash, OutPoint, Script, ScriptBuf, Transaction, Txid,
     },
     chain::{with_empty_retry, with_error_retry},
     model::{BlockchainExplorer, Config},
@@ -110,6 +111,10 @@ impl BitcoinChainService for EsploraBitcoinChainService {
         res
     }
 
+    async fn get_block_hash(&self, height: u32) -> Result<BlockHash> {
+        Ok(self.get_client()?.get_block_hash(height).await?)
+    }
+
     async fn broadcast(&self, tx: &Transaction) -> Result<Txid> {
         debug!("BitcoinChainService::broadcast: start");
         self.get_client()?.broadcast(tx).await?;
@@ -368,6 +373,13 @@ impl BitcoinChainService for EsploraBitcoinChainService {
         res
     }
 
+    async fn get_txout_proof(&self, txid: &Txid, _height: u32) -> Result<MerkleBlock> {
+        self.get_client()?
+            .get_merkle_block(txid)
+            .await?
+            .ok_or_else(|| anyhow!("Transaction is not confirmed, txid={txid}"))
+    }
+
     async fn recommended_fees(&self) -> Result<RecommendedFees> {
         debug!("BitcoinChainService::recommended_fees: start");
         let client = self.get_client()?;
@@ -2,7 +2,7 @@ pub(crate) mod electrum;
 pub(crate) mod esplora;
 
 use anyhow::Result;
-use bitcoin::{Address, Script, Transaction, Txid};
+use bitcoin::{merkle_tree::MerkleBlock, Address, BlockHash, Script, Transaction, Txid};
 
 use crate::{
     bitcoin,
@@ -17,6 +17,9 @@ pub trait BitcoinChainService: Send + Sync {
     /// Get the blockchain latest block
     async fn tip(&self) -> Result<u32>;
 
+    /// Get the hash of the block at the given height in the current best chain
+    async fn get_block_hash(&self, height: u32) -> Result<BlockHash>;
+
     /// Broadcast a transaction
     async fn broadcast(&self, tx: &Transaction) -> Result<Txid>;
 
@@ -72,6 +75,9 @@ pub trait BitcoinChainService: Send + Sync {
         verify_confirmation: bool,
     ) -> Result<Transaction>;
 
+    /// Get the proof that a transaction is included in the block at the given height
+    async fn get_txout_proof(&self, txid: &Txid, height: u32) -> Result<MerkleBlock>;
+
     /// Get the recommended fees, in sat/vbyte
     async fn recommended_fees(&self) -> Result<RecommendedFees>;
 }
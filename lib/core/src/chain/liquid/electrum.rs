@@ -1,13 +1,13 @@
 #![cfg(not(all(target_family = "wasm", target_os = "unknown")))]
 
-use std::sync::OnceLock;
+use std::{collections::HashMap, sync::OnceLock};
 
 use anyhow::{anyhow, bail, Context as _, Result};
 use tokio::sync::RwLock;
 
 use crate::{
     chain::{with_empty_retry, with_error_retry},
-    elements::{Address, OutPoint, Script, Transaction, Txid},
+    elements::{Address, BlockHash, OutPoint, Script, Transaction, Txid},
     model::{BlockchainExplorer, Config, Utxo},
     utils,
 };
@@ -70,6 +70,16 @@ impl LiquidChainService for ElectrumLiquidChainService {
             .map(|header| header.height)?)
     }
 
+    async fn get_block_hash(&self, height: u32) -> Result<BlockHash> {
+        self.get_client()?
+            .read()
+            .await
+            .get_headers(&[height], &HashMap::new())?
+            .first()
+            .map(|header| header.block_hash())
+            .context(format!("Block header not found at height {height}"))
+    }
+
     async fn broadcast(&self, tx: &Transaction) -> Result<Txid> {
         Ok(self.get_client()?.read().await.broadcast(tx)?)
     }
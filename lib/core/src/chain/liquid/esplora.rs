@@ -1,4 +1,4 @@
-use std::sync::OnceLock;
+use std::{collections::HashMap, sync::OnceLock};
 
 use anyhow::{anyhow, bail, Context as _, Result};
 use tokio::sync::RwLock;
@@ -6,7 +6,7 @@ use tokio_with_wasm::alias as tokio;
 
 use crate::{
     chain::{with_empty_retry, with_error_retry},
-    elements::{Address, OutPoint, Script, Transaction, Txid},
+    elements::{Address, BlockHash, OutPoint, Script, Transaction, Txid},
     model::{BlockchainExplorer, Config, Utxo, BREEZ_LIQUID_ESPLORA_URL},
     utils,
 };
@@ -94,6 +94,17 @@ impl LiquidChainService for EsploraLiquidChainService {
             .map(|header| header.height)?)
     }
 
+    async fn get_block_hash(&self, height: u32) -> Result<BlockHash> {
+        self.get_client()?
+            .read()
+            .await
+            .get_headers(&[height], &HashMap::new())
+            .await?
+            .first()
+            .map(|header| header.block_hash())
+            .context(format!("Block header not found at height {height}"))
+    }
+
     async fn broadcast(&self, tx: &Transaction) -> Result<Txid> {
         Ok(self.get_client()?.read().await.broadcast(tx).await?)
     }
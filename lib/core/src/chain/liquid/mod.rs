@@ -2,7 +2,7 @@ pub(crate) mod electrum;
 pub(crate) mod esplora;
 
 use anyhow::Result;
-use elements::{Address, Script, Transaction, Txid};
+use elements::{Address, BlockHash, Script, Transaction, Txid};
 use mockall::automock;
 
 use crate::{
@@ -18,6 +18,9 @@ pub trait LiquidChainService: Send + Sync {
     /// Get the blockchain latest block
     async fn tip(&self) -> Result<u32>;
 
+    /// Get the hash of the block at the given height in the current best chain
+    async fn get_block_hash(&self, height: u32) -> Result<BlockHash>;
+
     /// Broadcast a transaction
     async fn broadcast(&self, tx: &Transaction) -> Result<Txid>;
 
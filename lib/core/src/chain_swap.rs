@@ -4,8 +4,7 @@ use std::{collections::HashSet, sync::Arc};
 
 use anyhow::{anyhow, bail, Context, Result};
 use boltz_client::{
-    boltz::{self},
-    swaps::boltz::{ChainSwapStates, CreateChainResponse, TransactionInfo},
+    swaps::boltz::{ChainSwapStates, CreateChainResponse},
     ElementsLockTime, Secp256k1, Serialize, ToHex,
 };
 use elements::{hex::FromHex, Script, Transaction};
@@ -20,13 +19,17 @@ use crate::{
     elements, ensure_sdk,
     error::{PaymentError, SdkError, SdkResult},
     model::{
-        BlockListener, BtcHistory, ChainSwap, ChainSwapUpdate, Config, Direction, LBtcHistory,
+        BlockListener, Blockchain, BtcHistory, ChainSwap, ChainSwapUpdate, Config, Direction,
+        LBtcHistory,
         PaymentState::{self, *},
         PaymentTxData, PaymentType, Swap, SwapScriptV2, Transaction as SdkTransaction,
         LIQUID_FEE_RATE_MSAT_PER_VBYTE,
     },
     persist::Persister,
-    swapper::Swapper,
+    swapper::{
+        model::{SwapStatus, TransactionInfo},
+        Swapper,
+    },
     utils,
     wallet::OnchainWallet,
 };
@@ -94,7 +97,7 @@ impl ChainSwapHandler {
     }
 
     /// Handles status updates from Boltz for Chain swaps
-    pub(crate) async fn on_new_status(&self, update: &boltz::SwapStatus) -> Result<()> {
+    pub(crate) async fn on_new_status(&self, update: &SwapStatus) -> Result<()> {
         let id = &update.id;
         let swap = self.fetch_chain_swap_by_id(id)?;
 
@@ -189,6 +192,12 @@ impl ChainSwapHandler {
                 ))?;
         if *tx_height > 0 {
             info!("Chain Swap {swap_id} server lockup tx is confirmed");
+            if swap.direction == Direction::Incoming
+                && !self.has_required_lockup_confirmations(swap).await?
+            {
+                info!("Chain Swap {swap_id} lockup txs do not have the required confirmations yet");
+                return Ok(());
+            }
             self.claim(swap_id)
                 .await
                 .map_err(|e| anyhow!("Could not claim Chain Swap {swap_id}: {e:?}"))?;
@@ -196,11 +205,7 @@ impl ChainSwapHandler {
         Ok(())
     }
 
-    async fn on_new_incoming_status(
-        &self,
-        swap: &ChainSwap,
-        update: &boltz::SwapStatus,
-    ) -> Result<()> {
+    async fn on_new_incoming_status(&self, swap: &ChainSwap, update: &SwapStatus) -> Result<()> {
         let id = update.id.clone();
         let status = &update.status;
         let swap_state = ChainSwapStates::from_str(status)
@@ -241,6 +246,18 @@ impl ChainSwapHandler {
                             return Err(anyhow!("Unexpected payload from Boltz status stream"));
                         };
 
+                        // Do not claim a replacement of a server lockup that was previously double-spent
+                        if let Some(server_lockup_tx_id) = &swap.server_lockup_tx_id {
+                            if server_lockup_tx_id != &transaction.id
+                                && self
+                                    .persister
+                                    .is_watched_tx_double_spent(&id, server_lockup_tx_id)?
+                            {
+                                warn!("Server lockup tx {server_lockup_tx_id} for incoming Chain Swap {id} was replaced by {}. Waiting for confirmation...", transaction.id);
+                                return Ok(());
+                            }
+                        }
+
                         if let Err(e) = self.verify_user_lockup_tx(swap).await {
                             warn!("User lockup transaction for incoming Chain Swap {} could not be verified. err: {}", swap.id, e);
                             return Err(anyhow!("Could not verify user lockup transaction: {e}",));
@@ -268,7 +285,16 @@ impl ChainSwapHandler {
                             ..Default::default()
                         })?;
 
-                        if swap.accept_zero_conf {
+                        if swap.accept_zero_conf
+                            && self.has_required_lockup_confirmations(swap).await?
+                        {
+                            // Watch the server lockup inputs, so a double-spend can be detected
+                            // before it confirms
+                            if let Some(tx_hex) = &transaction.hex {
+                                let server_lockup_tx = utils::deserialize_tx_hex(tx_hex)?;
+                                self.persister
+                                    .insert_watched_outpoints(&id, &server_lockup_tx)?;
+                            }
                             maybe_delay_before_claim(swap.metadata.is_local).await;
                             self.claim(&id).await.map_err(|e| {
                                 error!("Could not cooperate Chain Swap {id} claim: {e}");
@@ -314,6 +340,13 @@ impl ChainSwapHandler {
                             Ok(_) => {
                                 info!("Server lockup transaction was verified for incoming Chain Swap {}", swap.id);
 
+                                // If the lockups are not deep enough yet, it will be claimed
+                                // later in claim_incoming()
+                                if !self.has_required_lockup_confirmations(swap).await? {
+                                    info!("Lockup txs for incoming Chain Swap {} do not have the required confirmations yet", swap.id);
+                                    return Ok(());
+                                }
+
                                 maybe_delay_before_claim(swap.metadata.is_local).await;
                                 self.claim(&id).await.map_err(|e| {
                                     error!("Could not cooperate Chain Swap {id} claim: {e}");
@@ -518,11 +551,7 @@ impl ChainSwapHandler {
         }
     }
 
-    async fn on_new_outgoing_status(
-        &self,
-        swap: &ChainSwap,
-        update: &boltz::SwapStatus,
-    ) -> Result<()> {
+    async fn on_new_outgoing_status(&self, swap: &ChainSwap, update: &SwapStatus) -> Result<()> {
         let id = update.id.clone();
         let status = &update.status;
         let swap_state = ChainSwapStates::from_str(status)
@@ -540,32 +569,7 @@ impl ChainSwapHandler {
                     // Create the user lockup tx
                     (_, None) => {
                         let create_response = swap.get_boltz_create_response()?;
-                        let user_lockup_tx = self.lockup_funds(&id, &create_response).await?;
-                        let lockup_tx_id = user_lockup_tx.txid().to_string();
-                        let lockup_tx_fees_sat: u64 = user_lockup_tx.all_fees().values().sum();
-
-                        // We insert a pseudo-lockup-tx in case LWK fails to pick up the new mempool tx for a while
-                        // This makes the tx known to the SDK (get_info, list_payments) instantly
-                        self.persister.insert_or_update_payment(PaymentTxData {
-                            tx_id: lockup_tx_id.clone(),
-                            timestamp: Some(utils::now()),
-                            fees_sat: lockup_tx_fees_sat,
-                            is_confirmed: false,
-                            unblinding_data: None,
-                        },
-                        &[PaymentTxBalance {
-                            asset_id: self.config.lbtc_asset_id().to_string(),
-                            amount: create_response.lockup_details.amount,
-                            payment_type: PaymentType::Send,
-                        }],
-                        None, false)?;
-
-                        self.update_swap_info(&ChainSwapUpdate {
-                            swap_id: id,
-                            to_state: Pending,
-                            user_lockup_tx_id: Some(lockup_tx_id),
-                            ..Default::default()
-                        })?;
+                        self.lockup_funds(&id, &create_response).await?;
                     },
 
                     // Lockup tx already exists
@@ -774,7 +778,17 @@ impl ChainSwapHandler {
                 lockup_details.amount,
             )
             .await?;
+        self.broadcast_user_lockup_tx(swap_id, create_response, lockup_tx)
+            .await
+    }
 
+    /// Broadcasts an already built user lockup tx for the outgoing Chain Swap and marks the swap as pending
+    pub(crate) async fn broadcast_user_lockup_tx(
+        &self,
+        swap_id: &str,
+        create_response: &CreateChainResponse,
+        lockup_tx: Transaction,
+    ) -> Result<Transaction, PaymentError> {
         let lockup_tx_id = self
             .liquid_chain_service
             .broadcast(&lockup_tx)
@@ -784,6 +798,34 @@ impl ChainSwapHandler {
         debug!(
           "Successfully broadcast lockup transaction for Chain Swap {swap_id}. Lockup tx id: {lockup_tx_id}"
         );
+
+        // We insert a pseudo-lockup-tx in case LWK fails to pick up the new mempool tx for a while
+        // This makes the tx known to the SDK (get_info, list_payments) instantly
+        let lockup_tx_fees_sat: u64 = lockup_tx.all_fees().values().sum();
+        self.persister.insert_or_update_payment(
+            PaymentTxData {
+                tx_id: lockup_tx_id.clone(),
+                timestamp: Some(utils::now()),
+                fees_sat: lockup_tx_fees_sat,
+                is_confirmed: false,
+                unblinding_data: None,
+                blocks_remaining: None,
+            },
+            &[PaymentTxBalance {
+                asset_id: self.config.lbtc_asset_id().to_string(),
+                amount: create_response.lockup_details.amount,
+                payment_type: PaymentType::Send,
+            }],
+            None,
+            false,
+        )?;
+
+        self.update_swap_info(&ChainSwapUpdate {
+            swap_id: swap_id.to_string(),
+            to_state: Pending,
+            user_lockup_tx_id: Some(lockup_tx_id),
+            ..Default::default()
+        })?;
         Ok(lockup_tx)
     }
 
@@ -945,6 +987,7 @@ impl ChainSwapHandler {
                                         fees_sat: 0,
                                         is_confirmed: false,
                                         unblinding_data: None,
+                                        blocks_remaining: None,
                                     },
                                     &[PaymentTxBalance {
                                         asset_id: self.config.lbtc_asset_id().to_string(),
@@ -1479,6 +1522,70 @@ impl ChainSwapHandler {
         Ok(true)
     }
 
+    /// Checks whether the lockups of an incoming swap have the confirmations required by the
+    /// configured [ConfirmationPolicy](crate::model::ConfirmationPolicy): the Bitcoin
+    /// confirmations on the user lockup tx and, if more than one is required, the Liquid
+    /// confirmations on the server lockup tx.
+    async fn has_required_lockup_confirmations(&self, chain_swap: &ChainSwap) -> Result<bool> {
+        let Some(confirmation_policy) = &self.config.confirmation_policy else {
+            return Ok(true);
+        };
+        let bitcoin_confirmations = confirmation_policy
+            .required_confirmations(Blockchain::Bitcoin, chain_swap.receiver_amount_sat);
+        let liquid_confirmations = confirmation_policy
+            .required_confirmations(Blockchain::Liquid, chain_swap.receiver_amount_sat);
+
+        // The lockup tx ids may have been set while verifying the lockups
+        let chain_swap = self.fetch_chain_swap_by_id(&chain_swap.id)?;
+        if bitcoin_confirmations > 0
+            && !self
+                .has_confirmations(
+                    &chain_swap.get_lockup_swap_script()?,
+                    &chain_swap.user_lockup_tx_id,
+                    bitcoin_confirmations,
+                )
+                .await?
+        {
+            return Ok(false);
+        }
+        if liquid_confirmations > 1
+            && !self
+                .has_confirmations(
+                    &chain_swap.get_claim_swap_script()?,
+                    &chain_swap.server_lockup_tx_id,
+                    liquid_confirmations,
+                )
+                .await?
+        {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Checks whether `tx_id` has at least `required_confirmations` on the chain of `swap_script`
+    async fn has_confirmations(
+        &self,
+        swap_script: &SwapScriptV2,
+        tx_id: &Option<String>,
+        required_confirmations: u32,
+    ) -> Result<bool> {
+        let Some(tx_id) = tx_id else {
+            return Ok(false);
+        };
+        let script_history = self.fetch_script_history(swap_script).await?;
+        let Some((_, height)) = script_history.iter().find(|h| &h.0 == tx_id) else {
+            return Ok(false);
+        };
+        if *height <= 0 {
+            return Ok(false);
+        }
+        let tip = match swap_script {
+            SwapScriptV2::Liquid(_) => self.liquid_chain_service.tip().await?,
+            SwapScriptV2::Bitcoin(_) => self.bitcoin_chain_service.tip().await?,
+        };
+        Ok(tip.saturating_sub(*height as u32) + 1 >= required_confirmations)
+    }
+
     async fn verify_user_lockup_tx(&self, chain_swap: &ChainSwap) -> Result<()> {
         if !self.user_lockup_tx_exists(chain_swap).await? {
             bail!("User lockup tx not found in script history");
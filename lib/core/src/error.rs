@@ -35,6 +35,9 @@ pub enum SdkError {
 
     #[error("Service connectivity: {err}")]
     ServiceConnectivity { err: String },
+
+    #[error("Liquid SDK instance is watch-only and cannot sign")]
+    WatchOnly,
 }
 impl SdkError {
     pub fn generic<T: AsRef<str>>(err: T) -> Self {
@@ -137,6 +140,9 @@ pub enum PaymentError {
 
     #[error("Could not sign the transaction: {err}")]
     SignerError { err: String },
+
+    #[error("Liquid SDK instance is watch-only and cannot sign")]
+    WatchOnly,
 }
 impl PaymentError {
     pub(crate) fn asset_error<S: AsRef<str>>(err: S) -> Self {
@@ -261,8 +267,11 @@ impl From<rusqlite::Error> for PaymentError {
 
 impl From<SdkError> for PaymentError {
     fn from(err: SdkError) -> Self {
-        Self::Generic {
-            err: err.to_string(),
+        match err {
+            SdkError::WatchOnly => Self::WatchOnly,
+            err => Self::Generic {
+                err: err.to_string(),
+            },
         }
     }
 }
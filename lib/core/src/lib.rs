@@ -161,6 +161,7 @@
 //! ## Support
 //!
 //! Join this [telegram group](https://t.me/breezsdk).
+pub(crate) mod asset_registry;
 pub(crate) mod buy;
 pub(crate) mod chain;
 pub(crate) mod chain_swap;
@@ -174,10 +175,13 @@ pub mod logger;
 #[allow(deprecated)]
 pub mod model;
 pub(crate) mod payjoin;
+pub(crate) mod peg;
 pub mod persist;
 pub mod plugin;
 pub mod receive_swap;
 pub(crate) mod recover;
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub mod runtime;
 pub mod sdk;
 pub(crate) mod send_swap;
 pub(crate) mod side_swap;
@@ -197,11 +201,22 @@ pub use lwk_wollet::bitcoin;
 pub use lwk_wollet::elements;
 pub use sdk_common::prelude::*;
 
+/// The provider-neutral swap model, used to register a custom swap provider with
+/// [LiquidSdkBuilder::swap_provider](crate::sdk::LiquidSdkBuilder::swap_provider)
+pub mod swap {
+    pub use crate::swapper::model::*;
+    pub use crate::swapper::{
+        SubscriptionHandler, SwapProvider, Swapper, SwapperStatusStream, DEFAULT_SWAP_PROVIDER,
+    };
+}
+
 #[allow(ambiguous_glob_reexports)]
 #[rustfmt::skip]
 pub mod prelude {
     pub use crate::*;
     pub use crate::model::*;
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    pub use crate::runtime::SdkRuntime;
     pub use crate::sdk::*;
-    pub use crate::signer::SdkSigner;
+    pub use crate::signer::{SdkSigner, SyncSignerAdapter};
 }
@@ -5,14 +5,14 @@ use sdk_common::{
     prelude::{LnUrlResult, LnurlAuthSigner},
 };
 
-use crate::model::Signer;
+use crate::model::AsyncSigner;
 
 pub(crate) struct SdkLnurlAuthSigner {
-    signer: Arc<Box<dyn Signer>>,
+    signer: Arc<dyn AsyncSigner>,
 }
 
 impl SdkLnurlAuthSigner {
-    pub fn new(signer: Arc<Box<dyn Signer>>) -> Self {
+    pub fn new(signer: Arc<dyn AsyncSigner>) -> Self {
         Self { signer }
     }
 }
@@ -23,6 +23,7 @@ impl LnurlAuthSigner for SdkLnurlAuthSigner {
         let derivation: DerivationPath = derivation_path.to_vec().into();
         self.signer
             .derive_xpub(derivation.to_string())
+            .await
             .map_err(|e| sdk_common::prelude::LnUrlError::Generic(e.to_string()))
             .map(|xpub| xpub.to_vec())
     }
@@ -35,6 +36,7 @@ impl LnurlAuthSigner for SdkLnurlAuthSigner {
         let derivation: DerivationPath = derivation_path.to_vec().into();
         self.signer
             .sign_ecdsa(msg.to_vec(), derivation.to_string())
+            .await
             .map_err(|e| sdk_common::prelude::LnUrlError::Generic(e.to_string()))
             .map(|s: Vec<u8>| s.to_vec())
     }
@@ -47,6 +49,7 @@ impl LnurlAuthSigner for SdkLnurlAuthSigner {
         let derivation: DerivationPath = key_derivation_path.to_vec().into();
         self.signer
             .hmac_sha256(input.to_vec(), derivation.to_string())
+            .await
             .map_err(|e| sdk_common::prelude::LnUrlError::Generic(e.to_string()))
     }
 }
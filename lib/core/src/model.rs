@@ -43,7 +43,9 @@ pub const LIQUID_FEE_RATE_MSAT_PER_VBYTE: f32 = (LIQUID_FEE_RATE_SAT_PER_VBYTE *
 pub const BREEZ_SYNC_SERVICE_URL: &str = "https://datasync.breez.technology";
 pub const BREEZ_LIQUID_ESPLORA_URL: &str = "https://lq1.breez.technology/liquid/api";
 pub const BREEZ_SWAP_PROXY_URL: &str = "https://swap.breez.technology/v2";
+pub const BLOCKSTREAM_ASSET_REGISTRY_URL: &str = "https://assets.blockstream.info";
 pub const DEFAULT_ONCHAIN_FEE_RATE_LEEWAY_SAT: u64 = 500;
+pub const DEFAULT_SETTLEMENT_MAX_SLIPPAGE_BPS: u32 = 100;
 const DEFAULT_ONCHAIN_SYNC_PERIOD_SEC: u32 = 10;
 const DEFAULT_ONCHAIN_SYNC_REQUEST_TIMEOUT_SEC: u32 = 7;
 
@@ -102,12 +104,45 @@ pub struct Config {
     pub asset_metadata: Option<Vec<AssetMetadata>>,
     /// The SideSwap API key used for making requests to the SideSwap payjoin service
     pub sideswap_api_key: Option<String>,
+    /// The base url of a SideSwap-compatible service (e.g. `https://api.sideswap.io`), used for
+    /// asset swaps and payjoins. Defaults to the SideSwap servers on mainnet and testnet.
+    ///
+    /// SideSwap is only available on regtest when this field is set.
+    pub sideswap_url: Option<String>,
     /// Set this to false to disable the use of Magic Routing Hints (MRH) to send payments. Enabled by default.
     pub use_magic_routing_hints: bool,
     /// The default period between onchain syncs in seconds. Defaults to 10 seconds.
     pub onchain_sync_period_sec: u32,
     /// The default onchain sync request timeout in seconds. Defaults to 7 seconds.
     pub onchain_sync_request_timeout_sec: u32,
+    /// The number of confirmations incoming payments need before they are considered complete.
+    /// See [ConfirmationPolicy] for more details.
+    ///
+    /// Defaults to a single confirmation on both chains.
+    pub confirmation_policy: Option<ConfirmationPolicy>,
+    /// Boltz-compatible swapper API URLs (e.g. `https://api.boltz.exchange/v2`) to fail over to,
+    /// in order, when the default swapper instance cannot be reached while preparing a new swap.
+    ///
+    /// Each swap stays pinned to the instance it was created with, which is identified by its
+    /// URL.
+    pub swapper_fallback_urls: Option<Vec<String>>,
+    /// The Liquid federation parameters, needed to receive peg-ins.
+    /// See [LiquidSdk::receive_peg_in](crate::sdk::LiquidSdk::receive_peg_in).
+    ///
+    /// Peg-ins are disabled when not set.
+    pub federation_config: Option<FederationConfig>,
+    /// The url of an asset registry serving the
+    /// [Liquid Asset Registry](https://docs.liquid.net/docs/blockstream-liquid-asset-registry)
+    /// format, used to look up the metadata of assets received by the wallet that are not
+    /// set in [Config::asset_metadata], e.g. [BLOCKSTREAM_ASSET_REGISTRY_URL].
+    ///
+    /// The lookups are opt-in, as they reveal the received assets to the registry. They are
+    /// disabled when not set. They run in the background of the sync, and the payments holding
+    /// an asset are updated once it is resolved.
+    pub asset_registry_url: Option<String>,
+    /// The url of the Breez server, used for fiat rates. Defaults to
+    /// [PRODUCTION_BREEZSERVER_URL].
+    pub breez_server_url: Option<String>,
 }
 
 impl Config {
@@ -131,9 +166,15 @@ impl Config {
             onchain_fee_rate_leeway_sat: None,
             asset_metadata: None,
             sideswap_api_key: Some(SIDESWAP_API_KEY.to_string()),
+            sideswap_url: None,
             use_magic_routing_hints: true,
             onchain_sync_period_sec: DEFAULT_ONCHAIN_SYNC_PERIOD_SEC,
             onchain_sync_request_timeout_sec: DEFAULT_ONCHAIN_SYNC_REQUEST_TIMEOUT_SEC,
+            confirmation_policy: None,
+            swapper_fallback_urls: None,
+            federation_config: None,
+            asset_registry_url: None,
+            breez_server_url: None,
         }
     }
 
@@ -158,9 +199,15 @@ impl Config {
             onchain_fee_rate_leeway_sat: None,
             asset_metadata: None,
             sideswap_api_key: Some(SIDESWAP_API_KEY.to_string()),
+            sideswap_url: None,
             use_magic_routing_hints: true,
             onchain_sync_period_sec: DEFAULT_ONCHAIN_SYNC_PERIOD_SEC,
             onchain_sync_request_timeout_sec: DEFAULT_ONCHAIN_SYNC_REQUEST_TIMEOUT_SEC,
+            confirmation_policy: None,
+            swapper_fallback_urls: None,
+            federation_config: None,
+            asset_registry_url: None,
+            breez_server_url: None,
         }
     }
 
@@ -184,9 +231,15 @@ impl Config {
             onchain_fee_rate_leeway_sat: None,
             asset_metadata: None,
             sideswap_api_key: None,
+            sideswap_url: None,
             use_magic_routing_hints: true,
             onchain_sync_period_sec: DEFAULT_ONCHAIN_SYNC_PERIOD_SEC,
             onchain_sync_request_timeout_sec: DEFAULT_ONCHAIN_SYNC_REQUEST_TIMEOUT_SEC,
+            confirmation_policy: None,
+            swapper_fallback_urls: None,
+            federation_config: None,
+            asset_registry_url: None,
+            breez_server_url: None,
         }
     }
 
@@ -211,9 +264,15 @@ impl Config {
             onchain_fee_rate_leeway_sat: None,
             asset_metadata: None,
             sideswap_api_key: None,
+            sideswap_url: None,
             use_magic_routing_hints: true,
             onchain_sync_period_sec: DEFAULT_ONCHAIN_SYNC_PERIOD_SEC,
             onchain_sync_request_timeout_sec: DEFAULT_ONCHAIN_SYNC_REQUEST_TIMEOUT_SEC,
+            confirmation_policy: None,
+            swapper_fallback_urls: None,
+            federation_config: None,
+            asset_registry_url: None,
+            breez_server_url: None,
         }
     }
 
@@ -232,6 +291,10 @@ impl Config {
             .to_string())
     }
 
+    pub(crate) fn confirmation_policy(&self) -> ConfirmationPolicy {
+        self.confirmation_policy.clone().unwrap_or_default()
+    }
+
     pub fn zero_conf_max_amount_sat(&self) -> u64 {
         self.zero_conf_max_amount_sat
             .unwrap_or(DEFAULT_ZERO_CONF_MAX_SAT)
@@ -328,13 +391,36 @@ impl Config {
         )
     }
 
-    pub(crate) fn sideswap_url(&self) -> &'static str {
+    /// The url of the SideSwap websocket API
+    pub(crate) fn sideswap_ws_url(&self) -> Result<String> {
+        if let Some(base_url) = self.sideswap_base_url() {
+            // The websocket API is served on the same host, http(s) becoming ws(s)
+            let ws_url = match base_url.strip_prefix("http") {
+                Some(rest) => format!("ws{rest}"),
+                None => base_url.to_string(),
+            };
+            return Ok(format!("{ws_url}/json-rpc-ws"));
+        }
         match self.network {
-            LiquidNetwork::Mainnet => SIDESWAP_MAINNET_URL,
-            LiquidNetwork::Testnet => SIDESWAP_TESTNET_URL,
-            LiquidNetwork::Regtest => unimplemented!(),
+            LiquidNetwork::Mainnet => Ok(SIDESWAP_MAINNET_URL.to_string()),
+            LiquidNetwork::Testnet => Ok(SIDESWAP_TESTNET_URL.to_string()),
+            network => bail!("SideSwap is not available on {network} without a sideswap_url"),
         }
     }
+
+    /// The url of the Breez server, which defaults to [PRODUCTION_BREEZSERVER_URL]
+    pub(crate) fn breez_server_url(&self) -> String {
+        self.breez_server_url
+            .clone()
+            .unwrap_or(PRODUCTION_BREEZSERVER_URL.to_string())
+    }
+
+    /// The base url of the SideSwap API, if overridden
+    pub(crate) fn sideswap_base_url(&self) -> Option<&str> {
+        self.sideswap_url
+            .as_deref()
+            .map(|url| url.trim_end_matches('/'))
+    }
 }
 
 /// Network chosen for this Liquid SDK instance. Note that it represents both the Liquid and the
@@ -469,6 +555,12 @@ pub enum SdkEvent {
     PaymentWaitingFeeAcceptance {
         details: Payment,
     },
+    /// An unconfirmed tx accepted with zero-conf was double-spent. The payment is either
+    /// [Failed](PaymentState::Failed), or left [Pending](PaymentState::Pending) if it can still
+    /// be completed once a replacement tx confirms.
+    PaymentDoubleSpent {
+        details: Payment,
+    },
     /// Synced with mempool and onchain data
     Synced,
     /// Failed to sync with mempool and onchain data
@@ -480,6 +572,13 @@ pub enum SdkEvent {
         /// Indicates new data was pulled from other instances.
         did_pull_new_records: bool,
     },
+    /// A chain reorganization was detected. Payments and swaps confirmed in the
+    /// disconnected blocks are marked as unconfirmed again until they are re-confirmed.
+    Reorg {
+        chain: Blockchain,
+        /// The number of blocks that were disconnected from the previously known tip
+        depth: u32,
+    },
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -538,6 +637,45 @@ pub trait Signer: Send + Sync {
     fn ecies_decrypt(&self, msg: Vec<u8>) -> Result<Vec<u8>, SignerError>;
 }
 
+/// The asynchronous counterpart of [Signer], for signers that cannot answer without waiting,
+/// like hardware wallets over USB/BLE or remote signers.
+/// A [Signer] can be used wherever an [AsyncSigner] is expected by wrapping it in a
+/// [crate::signer::SyncSignerAdapter].
+#[sdk_macros::async_trait]
+pub trait AsyncSigner: Send + Sync {
+    /// See [Signer::xpub]
+    async fn xpub(&self) -> Result<Vec<u8>, SignerError>;
+
+    /// See [Signer::derive_xpub]
+    async fn derive_xpub(&self, derivation_path: String) -> Result<Vec<u8>, SignerError>;
+
+    /// See [Signer::sign_ecdsa]
+    async fn sign_ecdsa(
+        &self,
+        msg: Vec<u8>,
+        derivation_path: String,
+    ) -> Result<Vec<u8>, SignerError>;
+
+    /// See [Signer::sign_ecdsa_recoverable]
+    async fn sign_ecdsa_recoverable(&self, msg: Vec<u8>) -> Result<Vec<u8>, SignerError>;
+
+    /// See [Signer::slip77_master_blinding_key]
+    async fn slip77_master_blinding_key(&self) -> Result<Vec<u8>, SignerError>;
+
+    /// See [Signer::hmac_sha256]
+    async fn hmac_sha256(
+        &self,
+        msg: Vec<u8>,
+        derivation_path: String,
+    ) -> Result<Vec<u8>, SignerError>;
+
+    /// See [Signer::ecies_encrypt]
+    async fn ecies_encrypt(&self, msg: Vec<u8>) -> Result<Vec<u8>, SignerError>;
+
+    /// See [Signer::ecies_decrypt]
+    async fn ecies_decrypt(&self, msg: Vec<u8>) -> Result<Vec<u8>, SignerError>;
+}
+
 /// An argument when calling [crate::sdk::LiquidSdk::connect].
 /// The resquest takes either a `mnemonic` and `passphrase`, or a `seed`.
 pub struct ConnectRequest {
@@ -549,10 +687,36 @@ pub struct ConnectRequest {
     pub passphrase: Option<String>,
     /// The optional Liquid wallet seed
     pub seed: Option<Vec<u8>>,
+    /// The optional BIP44 account of the wallet. Defaults to the first account, `0`.
+    pub account_index: Option<u32>,
 }
 
 pub struct ConnectWithSignerRequest {
     pub config: Config,
+    /// The optional BIP44 account of the wallet. Defaults to the first account, `0`.
+    pub account_index: Option<u32>,
+}
+
+/// An argument when calling [crate::sdk::LiquidSdk::connect_watch_only].
+pub struct ConnectWatchOnlyRequest {
+    /// The SDK [Config]
+    pub config: Config,
+    /// The CT descriptor of the wallet to watch, e.g. `ct(slip77(...),elwpkh([fingerprint/84'/1776'/0']xpub.../<0;1>/*))`
+    pub ct_descriptor: String,
+}
+
+/// An argument when calling [crate::sdk::LiquidSdk::connect_multisig].
+/// The optional `mnemonic` and `passphrase` are those of the hot key.
+pub struct ConnectMultisigRequest {
+    /// The SDK [Config]
+    pub config: Config,
+    /// The k-of-n multisig CT descriptor of the wallet, e.g. `ct(slip77(...),elwsh(multi(2,[fingerprint/87'/1776'/0']xpub.../<0;1>/*,...)))`
+    pub ct_descriptor: String,
+    /// The optional mnemonic of the hot key, which signs the swap claims and refunds and
+    /// cosigns the wallet txs if it is one of the cosigners. Without it, the SDK is watch-only.
+    pub mnemonic: Option<String>,
+    /// The optional passphrase for the mnemonic
+    pub passphrase: Option<String>,
 }
 
 /// A reserved address. Once an address is reserved, it can only be
@@ -592,6 +756,9 @@ pub struct PrepareReceiveRequest {
     pub payment_method: PaymentMethod,
     /// The amount to be paid in either Bitcoin or another asset
     pub amount: Option<ReceiveAmount>,
+    /// The swap provider to receive the payment with, if it requires a swap.
+    /// Defaults to the [default swap provider](crate::swap::DEFAULT_SWAP_PROVIDER).
+    pub swap_provider: Option<String>,
 }
 
 /// Returned when calling [crate::sdk::LiquidSdk::prepare_receive_payment].
@@ -621,6 +788,12 @@ pub struct PrepareReceiveResponse {
     ///
     /// When the method is [PaymentMethod::LiquidAddress], this is empty.
     pub swapper_feerate: Option<f64>,
+    /// The swap provider the fees were estimated with.
+    ///
+    /// When the method is [PaymentMethod::LiquidAddress] or [PaymentMethod::Bolt12Offer], this is empty.
+    pub swap_provider: Option<String>,
+    /// The quotes of all registered swap providers for this payment
+    pub swap_quotes: Vec<SwapQuote>,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -641,6 +814,20 @@ pub struct ReceivePaymentRequest {
     pub description_hash: Option<DescriptionHash>,
     /// An optional payer note, typically included in a LNURL-Pay request
     pub payer_note: Option<String>,
+    /// If set, the L-BTC received through a Lightning invoice is swapped to the given asset
+    /// via SideSwap once the payment is complete
+    pub settle_as_asset: Option<SettleAsAsset>,
+}
+
+/// An option of [ReceivePaymentRequest] to convert a Lightning payment into an asset on arrival.
+#[derive(Clone, Debug, Serialize)]
+pub struct SettleAsAsset {
+    /// The asset id to convert the received L-BTC to
+    pub asset_id: String,
+    /// The maximum allowed decrease of the received asset amount, in basis points, between the
+    /// quote at invoice creation and the one at settlement. If exceeded, the L-BTC is kept.
+    /// Defaults to [DEFAULT_SETTLEMENT_MAX_SLIPPAGE_BPS].
+    pub max_slippage_bps: Option<u32>,
 }
 
 /// Returned when calling [crate::sdk::LiquidSdk::receive_payment].
@@ -672,13 +859,28 @@ pub struct CreateBolt12InvoiceResponse {
 }
 
 /// The minimum and maximum in satoshis of a Lightning or onchain payment.
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Limits {
     pub min_sat: u64,
     pub max_sat: u64,
     pub max_zero_conf_sat: u64,
 }
 
+/// The limits and fees quoted by a swap provider for a type of swap.
+#[derive(Clone, Debug, Serialize)]
+pub struct SwapQuote {
+    /// The id the swap provider is registered with
+    pub swap_provider: String,
+    pub limits: Limits,
+    /// The percentage of the swap amount charged as service fee
+    pub fees_percentage: f64,
+    /// The fixed part of the fees, e.g. covering the provider's onchain fees
+    pub fixed_fees_sat: u64,
+    /// The total swap fees for the requested amount. Only set when the amount is known and is
+    /// within the limits.
+    pub fees_sat: Option<u64>,
+}
+
 /// Returned when calling [crate::sdk::LiquidSdk::fetch_lightning_limits].
 #[derive(Debug, Serialize)]
 pub struct LightningPaymentLimitsResponse {
@@ -686,6 +888,10 @@ pub struct LightningPaymentLimitsResponse {
     pub send: Limits,
     /// Amount limits for a Receive Payment to be valid
     pub receive: Limits,
+    /// The Send Payment quotes of all registered swap providers
+    pub send_quotes: Vec<SwapQuote>,
+    /// The Receive Payment quotes of all registered swap providers
+    pub receive_quotes: Vec<SwapQuote>,
 }
 
 /// Returned when calling [crate::sdk::LiquidSdk::fetch_onchain_limits].
@@ -695,6 +901,10 @@ pub struct OnchainPaymentLimitsResponse {
     pub send: Limits,
     /// Amount limits for a Receive Onchain Payment to be valid
     pub receive: Limits,
+    /// The Send Onchain Payment quotes of all registered swap providers
+    pub send_quotes: Vec<SwapQuote>,
+    /// The Receive Onchain Payment quotes of all registered swap providers
+    pub receive_quotes: Vec<SwapQuote>,
 }
 
 /// An argument when calling [crate::sdk::LiquidSdk::prepare_send_payment].
@@ -711,6 +921,24 @@ pub struct PrepareSendRequest {
     /// Custom payment timeout in seconds. If not set, uses the (configuration
     /// timeout)[Config::payment_timeout_sec]
     pub payment_timeout_sec: Option<u64>,
+    /// The swap provider to pay with, if the payment requires a swap.
+    /// Defaults to the [default swap provider](crate::swap::DEFAULT_SWAP_PROVIDER).
+    pub swap_provider: Option<String>,
+    /// The asset id whose balance we want to fund a Lightning payment with. The asset is
+    /// converted to L-BTC via SideSwap before locking up the swap. Defaults to L-BTC.
+    pub from_asset: Option<String>,
+    /// The asset id to pay the network fees of a Lightning payment's swap lockup with, using the
+    /// SideSwap payjoin service. When set, the lockup network fees are excluded from
+    /// [PrepareSendResponse::fees_sat] and estimated in [PrepareSendResponse::estimated_asset_fees].
+    pub fee_asset: Option<String>,
+    /// If set to true, the payment transaction is returned unsigned in
+    /// [PrepareSendResponse::unsigned_pset] to be signed externally, and the payment is then
+    /// executed with [crate::sdk::LiquidSdk::finalize_send_payment]. Supported for Liquid
+    /// payments and BOLT11 invoices, which are always paid via a swap created when preparing.
+    /// BOLT12 offers are not supported: their invoice is only fetched when sending, so the swap
+    /// lockup is not known when preparing. The chain swap lockups of
+    /// [crate::sdk::LiquidSdk::pay_onchain] cannot be exported either.
+    pub export_pset: Option<bool>,
 }
 
 /// Specifies the supported destinations which can be payed by the SDK
@@ -745,25 +973,52 @@ pub struct PrepareSendResponse {
     pub fees_sat: Option<u64>,
     /// The optional estimated fee in the asset. Is set when [PayAmount::Asset::estimate_asset_fees]
     /// is set to `true`, the Payjoin service accepts this asset to pay fees and there
-    /// are funds available in this asset to pay fees.
+    /// are funds available in this asset to pay fees. Also set when paying the swap lockup
+    /// fees in [PrepareSendRequest::fee_asset].
     pub estimated_asset_fees: Option<f64>,
     /// The amount of funds required (in satoshi) to execute a SideSwap payment, excluding fees.
     /// Only present when [PayAmount::Asset::pay_with_bitcoin] is set to `true`.
+    /// When funding a Lightning payment from an asset, it is the amount of the asset exchanged.
     pub exchange_amount_sat: Option<u64>,
+    /// The asset funding a Lightning payment, see [PrepareSendRequest::from_asset]
+    pub from_asset: Option<String>,
+    /// The asset paying the swap lockup network fees, see [PrepareSendRequest::fee_asset]
+    pub fee_asset: Option<String>,
     /// If set to true, the payment will be sent without magic routing hints
     pub disable_mrh: Option<bool>,
     /// Custom timeout in seconds. If not set, uses the default config timeout.
     pub payment_timeout_sec: Option<u64>,
+    /// The swap provider the fees were estimated with. Only set if the payment requires a swap.
+    pub swap_provider: Option<String>,
+    /// The quotes of all registered swap providers, if the payment requires a swap
+    pub swap_quotes: Vec<SwapQuote>,
+    /// The unsigned payment transaction as a base64 encoded PSET, when
+    /// [PrepareSendRequest::export_pset] is set. For a BOLT11 invoice, it is the swap lockup.
+    pub unsigned_pset: Option<String>,
 }
 
 /// An argument when calling [crate::sdk::LiquidSdk::send_payment].
 #[derive(Debug, Serialize)]
 pub struct SendPaymentRequest {
     pub prepare_response: PrepareSendResponse,
-    /// If set to true, the payment will be sent using the SideSwap payjoin service
+    /// If set to true, the payment will be sent using the SideSwap payjoin service.
+    /// Must be set to true when [PrepareSendResponse::fee_asset] is set.
     pub use_asset_fees: Option<bool>,
     /// An optional payer note, which is to be included in a BOLT12 invoice request
     pub payer_note: Option<String>,
+    /// The sub-account the payment and its fees are debited from, see [SubAccount]
+    pub sub_account_id: Option<String>,
+}
+
+/// An argument when calling [crate::sdk::LiquidSdk::finalize_send_payment].
+#[derive(Debug, Serialize)]
+pub struct FinalizeSendPaymentRequest {
+    /// The [PrepareSendResponse] returned with an [PrepareSendResponse::unsigned_pset]
+    pub prepare_response: PrepareSendResponse,
+    /// The base64 encoded PSET, once signed externally
+    pub signed_pset: String,
+    /// The sub-account the payment and its fees are debited from, see [SubAccount]
+    pub sub_account_id: Option<String>,
 }
 
 /// Returned when calling [crate::sdk::LiquidSdk::send_payment].
@@ -772,6 +1027,7 @@ pub struct SendPaymentResponse {
     pub payment: Payment,
 }
 
+#[derive(Clone)]
 pub(crate) struct SendPaymentViaSwapRequest {
     pub(crate) invoice: String,
     pub(crate) bolt12_offer: Option<String>,
@@ -779,6 +1035,19 @@ pub(crate) struct SendPaymentViaSwapRequest {
     pub(crate) description: Option<String>,
     pub(crate) receiver_amount_sat: u64,
     pub(crate) fees_sat: u64,
+    pub(crate) swap_provider: Option<String>,
+    /// The asset paying the lockup network fees via the SideSwap payjoin service
+    pub(crate) fee_asset: Option<String>,
+    /// The asset converted to L-BTC to fund the lockup, once the swap is created
+    pub(crate) asset_funding: Option<AssetFunding>,
+}
+
+/// An asset converted to L-BTC via SideSwap to fund a Lightning payment
+#[derive(Clone, Debug)]
+pub(crate) struct AssetFunding {
+    pub(crate) from_asset: String,
+    /// The most of the asset that can be converted, as quoted when preparing the payment
+    pub(crate) max_payer_amount_sat: Option<u64>,
 }
 
 pub(crate) struct PayLiquidRequest {
@@ -816,6 +1085,14 @@ pub enum PayAmount {
 
     /// Indicates that all available Bitcoin funds should be sent
     Drain,
+
+    /// Indicates that all available funds of a non-Bitcoin asset should be sent.
+    /// Fees are paid in Bitcoin or, if there is no Bitcoin available, in the asset itself
+    /// and deducted from the drained amount.
+    DrainAsset {
+        /// The asset id whose entire balance will be sent
+        asset_id: String,
+    },
 }
 
 impl PayAmount {
@@ -838,6 +1115,14 @@ pub struct PreparePayOnchainRequest {
     pub amount: PayAmount,
     /// The optional fee rate of the Bitcoin claim transaction in sat/vB. Defaults to the swapper estimated claim fee.
     pub fee_rate_sat_per_vbyte: Option<u32>,
+    /// The swap provider to pay with.
+    /// Defaults to the [default swap provider](crate::swap::DEFAULT_SWAP_PROVIDER).
+    pub swap_provider: Option<String>,
+    /// The asset id to pay the network fees of the swap lockup with, using the SideSwap
+    /// payjoin service. When set, the lockup network fees are excluded from
+    /// [PreparePayOnchainResponse::total_fees_sat] and estimated in
+    /// [PreparePayOnchainResponse::estimated_asset_fees].
+    pub fee_asset: Option<String>,
 }
 
 /// Returned when calling [crate::sdk::LiquidSdk::prepare_pay_onchain].
@@ -846,6 +1131,14 @@ pub struct PreparePayOnchainResponse {
     pub receiver_amount_sat: u64,
     pub claim_fees_sat: u64,
     pub total_fees_sat: u64,
+    /// The swap provider the fees were estimated with
+    pub swap_provider: Option<String>,
+    /// The quotes of all registered swap providers
+    pub swap_quotes: Vec<SwapQuote>,
+    /// The asset paying the swap lockup network fees, see [PreparePayOnchainRequest::fee_asset]
+    pub fee_asset: Option<String>,
+    /// The estimated swap lockup network fees in the [PreparePayOnchainResponse::fee_asset]
+    pub estimated_asset_fees: Option<f64>,
 }
 
 /// An argument when calling [crate::sdk::LiquidSdk::pay_onchain].
@@ -853,6 +1146,263 @@ pub struct PreparePayOnchainResponse {
 pub struct PayOnchainRequest {
     pub address: String,
     pub prepare_response: PreparePayOnchainResponse,
+    /// If set to true, the swap lockup fees will be paid using the SideSwap payjoin service.
+    /// Must be set to true when [PreparePayOnchainResponse::fee_asset] is set.
+    pub use_asset_fees: Option<bool>,
+}
+
+/// An argument when calling [crate::sdk::LiquidSdk::receive_peg_in].
+#[derive(Debug, Serialize)]
+pub struct ReceivePegInRequest {
+    /// The optional description of the peg-in
+    pub description: Option<String>,
+}
+
+/// Returned when calling [crate::sdk::LiquidSdk::receive_peg_in].
+#[derive(Debug, Serialize)]
+pub struct ReceivePegInResponse {
+    pub peg_id: String,
+    /// The Bitcoin address to deposit the funds to
+    pub bitcoin_address: String,
+    /// The Bitcoin confirmations the deposit needs before it is claimed on Liquid
+    pub required_confirmations: u32,
+}
+
+/// An argument when calling [crate::sdk::LiquidSdk::prepare_peg_out].
+#[derive(Debug, Serialize, Clone)]
+pub struct PreparePegOutRequest {
+    /// The amount to peg out
+    pub receiver_amount_sat: u64,
+}
+
+/// Returned when calling [crate::sdk::LiquidSdk::prepare_peg_out].
+#[derive(Debug, Serialize, Clone)]
+pub struct PreparePegOutResponse {
+    pub receiver_amount_sat: u64,
+    /// The Liquid tx fees
+    pub fees_sat: u64,
+}
+
+/// An argument when calling [crate::sdk::LiquidSdk::peg_out].
+#[derive(Debug, Serialize)]
+pub struct PegOutRequest {
+    /// The Bitcoin address receiving the funds
+    pub bitcoin_address: String,
+    pub prepare_response: PreparePegOutResponse,
+}
+
+/// An argument when calling [crate::sdk::LiquidSdk::prepare_asset_swap].
+#[derive(Debug, Serialize)]
+pub struct PrepareAssetSwapRequest {
+    /// The asset id to swap from
+    pub from_asset: String,
+    /// The asset id to swap to
+    pub to_asset: String,
+    /// The amount to receive, having its decimal shifted to the left by the
+    /// [precision](AssetMetadata::precision) of the received asset
+    pub receiver_amount: f64,
+}
+
+/// Returned when calling [crate::sdk::LiquidSdk::prepare_asset_swap].
+#[derive(Clone, Debug, Serialize)]
+pub struct PrepareAssetSwapResponse {
+    pub from_asset: String,
+    pub to_asset: String,
+    /// The amount sent, in the base units of the sent asset
+    pub payer_amount_sat: u64,
+    /// The amount received, in the base units of the received asset
+    pub receiver_amount_sat: u64,
+    /// The SideSwap fees, in satoshi
+    pub fees_sat: u64,
+    /// The amount of the non L-BTC asset that can be traded for one L-BTC
+    pub exchange_rate: f64,
+}
+
+/// An argument when calling [crate::sdk::LiquidSdk::asset_swap].
+#[derive(Debug, Serialize)]
+pub struct AssetSwapRequest {
+    pub prepare_response: PrepareAssetSwapResponse,
+    /// The optional description of the asset swap
+    pub description: Option<String>,
+}
+
+/// The issuer-attested contract of an issued asset. Its hash is committed to in the issuance
+/// and it can be published to the
+/// [Liquid Asset Registry](https://docs.liquid.net/docs/blockstream-liquid-asset-registry).
+#[derive(Clone, Debug, Serialize)]
+pub struct AssetContract {
+    /// The domain of the issuing entity, used to verify the asset in the registry
+    pub domain: String,
+    /// The name of the asset
+    pub name: String,
+    /// The ticker of the asset
+    pub ticker: String,
+    /// The precision used to display the asset amount
+    pub precision: u8,
+    /// The hex encoded public key of the issuer. Defaults to the wallet public key.
+    pub issuer_pubkey: Option<String>,
+}
+
+/// An argument when calling [crate::sdk::LiquidSdk::issue_asset].
+#[derive(Debug, Serialize)]
+pub struct IssueAssetRequest {
+    /// The amount to issue, having its decimal shifted to the left by the contract precision
+    pub amount: f64,
+    /// The optional amount of reissuance tokens to issue, in base units. If not set, the asset
+    /// cannot be reissued.
+    pub reissuance_token_amount_sat: Option<u64>,
+    pub contract: AssetContract,
+}
+
+/// Returned when calling [crate::sdk::LiquidSdk::issue_asset].
+#[derive(Debug, Serialize)]
+pub struct IssueAssetResponse {
+    /// The id of the issued asset
+    pub asset_id: String,
+    /// The id of the reissuance token, if any was issued
+    pub reissuance_token_id: Option<String>,
+    /// The hash of the asset contract
+    pub contract_hash: String,
+    pub payment: Payment,
+}
+
+/// An argument when calling [crate::sdk::LiquidSdk::reissue_asset].
+#[derive(Debug, Serialize)]
+pub struct ReissueAssetRequest {
+    /// The id of the asset to reissue. Its reissuance token must be held by the wallet.
+    pub asset_id: String,
+    /// The amount to reissue, having its decimal shifted to the left by the asset precision
+    pub amount: f64,
+}
+
+/// An argument when calling [crate::sdk::LiquidSdk::burn_asset].
+#[derive(Debug, Serialize)]
+pub struct BurnAssetRequest {
+    /// The id of the asset to burn
+    pub asset_id: String,
+    /// The amount to burn, having its decimal shifted to the left by the asset precision
+    pub amount: f64,
+}
+
+/// Returned when calling [crate::sdk::LiquidSdk::reissue_asset],
+/// [crate::sdk::LiquidSdk::burn_asset] or [crate::sdk::LiquidSdk::asset_swap].
+#[derive(Debug, Serialize)]
+pub struct AssetOperationResponse {
+    pub payment: Payment,
+}
+
+/// An argument when calling [crate::sdk::LiquidSdk::create_sub_account].
+#[derive(Debug, Serialize)]
+pub struct CreateSubAccountRequest {
+    /// The name of the sub-account, e.g. the id of the end user it belongs to
+    pub name: String,
+}
+
+/// A virtual sub-account of the wallet, holding part of its L-BTC balance.
+///
+/// Sub-accounts only exist in the local ledger: they are credited with the incoming payments
+/// attributed to them, debited with the payments sent from them and can transfer funds between
+/// each other, without any onchain movement. The ledger is not synced to other instances.
+#[derive(Clone, Debug, Serialize)]
+pub struct SubAccount {
+    pub id: String,
+    pub name: String,
+    /// The balance of the sub-account, which is part of [WalletInfo::balance_sat]
+    pub balance_sat: u64,
+    /// Epoch time, in seconds
+    pub created_at: u32,
+}
+
+/// An argument when calling [crate::sdk::LiquidSdk::attribute_to_sub_account].
+#[derive(Debug, Serialize)]
+pub struct AttributeToSubAccountRequest {
+    pub sub_account_id: String,
+    /// The BOLT11 invoice, BOLT12 offer, Liquid or Bitcoin address (or BIP21 URI) whose
+    /// incoming payments are credited to the sub-account
+    pub destination: String,
+}
+
+/// An argument when calling [crate::sdk::LiquidSdk::transfer_sub_account_funds].
+///
+/// Funds can also be moved from or to the part of the wallet balance not held by any
+/// sub-account, by leaving `from_sub_account_id` or `to_sub_account_id` unset.
+#[derive(Debug, Serialize)]
+pub struct TransferSubAccountFundsRequest {
+    pub from_sub_account_id: Option<String>,
+    pub to_sub_account_id: Option<String>,
+    pub amount_sat: u64,
+    pub description: Option<String>,
+}
+
+/// The type of a [LedgerEntry]
+#[derive(Clone, Copy, Debug, EnumString, Eq, PartialEq, Serialize)]
+#[strum(serialize_all = "lowercase")]
+pub enum LedgerEntryType {
+    /// An incoming payment attributed to the sub-account
+    Receive = 0,
+    /// A payment sent from the sub-account, including its fees
+    Send = 1,
+    /// A payment sent from the sub-account that failed, crediting back the refunded amount
+    Refund = 2,
+    /// An internal transfer from or to the sub-account
+    Transfer = 3,
+}
+impl ToSql for LedgerEntryType {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(*self as i8))
+    }
+}
+impl FromSql for LedgerEntryType {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Integer(i) => match i as u8 {
+                0 => Ok(LedgerEntryType::Receive),
+                1 => Ok(LedgerEntryType::Send),
+                2 => Ok(LedgerEntryType::Refund),
+                3 => Ok(LedgerEntryType::Transfer),
+                _ => Err(FromSqlError::OutOfRange(i)),
+            },
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+/// A change of a sub-account balance, as recorded in the ledger audit trail
+#[derive(Clone, Debug, Serialize)]
+pub struct LedgerEntry {
+    pub id: i64,
+    pub sub_account_id: String,
+    pub entry_type: LedgerEntryType,
+    /// The balance change, which is negative for debits
+    pub amount_sat: i64,
+    /// The balance of the sub-account after the entry
+    pub balance_sat: u64,
+    /// The id of the payment, for [LedgerEntryType::Receive], [LedgerEntryType::Send] and
+    /// [LedgerEntryType::Refund] entries. It is the swap id for swaps and the tx id otherwise.
+    pub payment_id: Option<String>,
+    /// The id shared by both entries of a transfer between two sub-accounts
+    pub transfer_id: Option<String>,
+    pub description: Option<String>,
+    /// Epoch time, in seconds
+    pub created_at: u32,
+}
+
+/// An argument when calling [crate::sdk::LiquidSdk::list_ledger_entries].
+#[derive(Debug, Default, Serialize)]
+pub struct ListLedgerEntriesRequest {
+    /// If set, only the entries of this sub-account are listed
+    pub sub_account_id: Option<String>,
+    pub offset: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+/// Returned when calling [crate::sdk::LiquidSdk::get_ledger_info].
+#[derive(Clone, Debug, Serialize)]
+pub struct LedgerInfo {
+    /// The sum of all sub-account balances
+    pub allocated_sat: u64,
+    /// The part of [WalletInfo::balance_sat] not held by any sub-account
+    pub unallocated_sat: u64,
 }
 
 /// An argument when calling [crate::sdk::LiquidSdk::prepare_refund].
@@ -908,6 +1458,137 @@ pub struct BlockchainInfo {
     pub bitcoin_tip: u32,
 }
 
+/// The blockchain a block or transaction belongs to.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum Blockchain {
+    Liquid = 0,
+    Bitcoin = 1,
+}
+impl ToSql for Blockchain {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(*self as i8))
+    }
+}
+impl FromSql for Blockchain {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Integer(i) => match i as u8 {
+                0 => Ok(Blockchain::Liquid),
+                1 => Ok(Blockchain::Bitcoin),
+                _ => Err(FromSqlError::OutOfRange(i)),
+            },
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+/// Configures how many confirmations the txs funding an incoming payment need before the
+/// payment is considered [Complete](PaymentState::Complete).
+///
+/// For direct Liquid payments this applies to the Liquid tx paying into the wallet. Swaps are
+/// only claimed once their lockups reach the required confirmations: the swapper's Liquid lockup
+/// tx for Lightning receives and incoming chain swaps, and also the user's Bitcoin lockup tx
+/// for incoming chain swaps. A single Liquid confirmation keeps claiming zero-conf lockups.
+#[derive(Clone, Debug, Serialize)]
+pub struct ConfirmationPolicy {
+    /// Confirmations required on Liquid. Values below 1 are treated as 1.
+    pub liquid_confirmations: u32,
+    /// Confirmations required on Bitcoin.
+    pub bitcoin_confirmations: u32,
+    /// Optional tiers requiring a different number of confirmations for larger amounts
+    pub tiers: Vec<ConfirmationTier>,
+}
+
+impl Default for ConfirmationPolicy {
+    fn default() -> Self {
+        Self {
+            liquid_confirmations: 1,
+            bitcoin_confirmations: 1,
+            tiers: vec![],
+        }
+    }
+}
+
+impl ConfirmationPolicy {
+    /// Returns the confirmations required on `chain` for a payment of `amount_sat`.
+    ///
+    /// The tier with the highest [ConfirmationTier::min_amount_sat] not above `amount_sat` applies.
+    pub fn required_confirmations(&self, chain: Blockchain, amount_sat: u64) -> u32 {
+        let tier = self
+            .tiers
+            .iter()
+            .filter(|tier| amount_sat >= tier.min_amount_sat)
+            .max_by_key(|tier| tier.min_amount_sat);
+        let confirmations = match chain {
+            Blockchain::Liquid => {
+                tier.map_or(self.liquid_confirmations, |t| t.liquid_confirmations)
+            }
+            Blockchain::Bitcoin => {
+                tier.map_or(self.bitcoin_confirmations, |t| t.bitcoin_confirmations)
+            }
+        };
+        match chain {
+            Blockchain::Liquid => confirmations.max(1),
+            Blockchain::Bitcoin => confirmations,
+        }
+    }
+
+    /// Returns how many more blocks a tx confirmed at `height` needs to reach the required
+    /// confirmations, given the current `tip`. Unconfirmed txs have a `height` of `None`.
+    pub(crate) fn blocks_remaining(
+        &self,
+        chain: Blockchain,
+        amount_sat: u64,
+        tip: u32,
+        height: Option<u32>,
+    ) -> u32 {
+        let confirmations = height.map_or(0, |height| tip.saturating_sub(height) + 1);
+        self.required_confirmations(chain, amount_sat)
+            .saturating_sub(confirmations)
+    }
+}
+
+/// A tier of a [ConfirmationPolicy]
+#[derive(Clone, Debug, Serialize)]
+pub struct ConfirmationTier {
+    /// The minimum payment amount from which this tier applies
+    pub min_amount_sat: u64,
+    pub liquid_confirmations: u32,
+    pub bitcoin_confirmations: u32,
+}
+
+/// The parameters of the Liquid federation used for peg-ins, as returned by the
+/// `getsidechaininfo` RPC of an Elements node
+#[derive(Clone, Debug, Serialize)]
+pub struct FederationConfig {
+    /// The hex encoded federation peg script (`fedpegscript`)
+    pub fedpeg_script: String,
+    /// The Bitcoin confirmations a peg-in deposit needs before it can be claimed
+    /// (`pegin_confirmation_depth`)
+    pub pegin_confirmations: u32,
+}
+
+/// An outpoint spent by an unconfirmed tx that was accepted with zero-conf. It is watched
+/// until the tx confirms, in order to detect double-spends.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct WatchedOutpoint {
+    /// The outpoint, formatted as `txid:vout`
+    pub(crate) outpoint: String,
+    /// The accepted unconfirmed tx spending the outpoint
+    pub(crate) tx_id: String,
+    /// The swap id, or the tx id in case of a direct Liquid payment
+    pub(crate) payment_id: String,
+    pub(crate) created_at: u32,
+}
+
+/// A block hash persisted to detect chain reorganizations.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct BlockHeader {
+    pub(crate) chain: Blockchain,
+    pub(crate) height: u32,
+    pub(crate) hash: String,
+}
+
 #[derive(Copy, Clone)]
 pub(crate) struct ChainTips {
     pub liquid_tip: u32,
@@ -962,6 +1643,15 @@ impl WalletInfo {
     }
 }
 
+/// An account of the wallet seed, as returned by [crate::sdk::LiquidSdk::list_accounts].
+#[derive(Clone, Debug, Serialize)]
+pub struct AccountInfo {
+    /// The BIP44 account index, to be set in [ConnectRequest::account_index]
+    pub account_index: u32,
+    /// The number of txs in the account history
+    pub tx_count: u32,
+}
+
 /// Returned when calling [crate::sdk::LiquidSdk::get_info].
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GetInfoResponse {
@@ -984,6 +1674,34 @@ pub struct SignMessageResponse {
     pub signature: String,
 }
 
+/// An argument when calling [crate::sdk::LiquidSdk::sign_pset].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignPsetRequest {
+    /// The base64 encoded PSET spending multisig wallet inputs
+    pub pset: String,
+}
+
+/// Returned when calling [crate::sdk::LiquidSdk::sign_pset].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignPsetResponse {
+    /// The base64 encoded PSET, including the hot key signatures
+    pub pset: String,
+}
+
+/// An argument when calling [crate::sdk::LiquidSdk::combine_psets].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CombinePsetsRequest {
+    /// The base64 encoded PSETs of the same tx, each signed by some of the cosigners
+    pub psets: Vec<String>,
+}
+
+/// Returned when calling [crate::sdk::LiquidSdk::combine_psets].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CombinePsetsResponse {
+    /// The base64 encoded PSET, including the signatures of all the combined PSETs
+    pub pset: String,
+}
+
 /// An argument when calling [crate::sdk::LiquidSdk::check_message].
 #[derive(Clone, Debug, PartialEq)]
 pub struct CheckMessageRequest {
@@ -1115,6 +1833,14 @@ impl Swap {
             | Swap::Receive(ReceiveSwap { metadata, .. }) => metadata.last_updated_at,
         }
     }
+
+    pub(crate) fn swap_provider(&self) -> Option<&str> {
+        match self {
+            Swap::Chain(ChainSwap { swap_provider, .. })
+            | Swap::Send(SendSwap { swap_provider, .. })
+            | Swap::Receive(ReceiveSwap { swap_provider, .. }) => swap_provider.as_deref(),
+        }
+    }
 }
 impl From<ChainSwap> for Swap {
     fn from(swap: ChainSwap) -> Self {
@@ -1184,6 +1910,61 @@ pub(crate) struct SwapMetadata {
     pub(crate) is_local: bool,
 }
 
+/// A peg-in or peg-out through the Liquid federation
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Peg {
+    pub(crate) id: String,
+    pub(crate) direction: Direction,
+    /// For an incoming peg, the Bitcoin deposit address. For an outgoing one, the Bitcoin
+    /// address receiving the funds.
+    pub(crate) bitcoin_address: String,
+    /// The script the deposit is committed to, only set for incoming pegs
+    pub(crate) claim_script: Option<String>,
+    /// The index of the signer key spending the claim script, only set for incoming pegs.
+    /// See [crate::peg::peg_in_key_path].
+    pub(crate) key_index: Option<u32>,
+    /// For an incoming peg, the deposited amount, once detected
+    pub(crate) amount_sat: u64,
+    pub(crate) bitcoin_tx_id: Option<String>,
+    pub(crate) bitcoin_vout: Option<u32>,
+    /// For an incoming peg, the claim tx. For an outgoing one, the peg-out tx.
+    pub(crate) liquid_tx_id: Option<String>,
+    pub(crate) description: Option<String>,
+    pub(crate) state: PaymentState,
+    pub(crate) created_at: u32,
+}
+
+/// A swap between two Liquid assets through SideSwap, received by the wallet
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct AssetSwapData {
+    pub(crate) id: String,
+    pub(crate) tx_id: String,
+    pub(crate) from_asset: String,
+    pub(crate) to_asset: String,
+    pub(crate) payer_amount_sat: u64,
+    pub(crate) receiver_amount_sat: u64,
+    pub(crate) fees_sat: u64,
+    pub(crate) exchange_rate: f64,
+    pub(crate) description: Option<String>,
+    /// The Receive swap settled by this asset swap, see [SettleAsAsset]
+    pub(crate) receive_swap_id: Option<String>,
+    pub(crate) created_at: u32,
+}
+
+/// A pending conversion of a Receive swap into an asset, see [SettleAsAsset]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct AssetSettlement {
+    /// The id of the Receive swap being settled
+    pub(crate) swap_id: String,
+    pub(crate) asset_id: String,
+    /// The minimum asset amount to receive for the quote to be accepted
+    pub(crate) min_receiver_amount_sat: u64,
+    /// Either [PaymentState::Created] until settled, [PaymentState::Complete] once converted
+    /// or [PaymentState::Failed] if the L-BTC was kept
+    pub(crate) state: PaymentState,
+    pub(crate) created_at: u32,
+}
+
 /// A chain swap
 ///
 /// See <https://docs.boltz.exchange/v/api/lifecycle#chain-swaps>
@@ -1235,6 +2016,8 @@ pub struct ChainSwap {
     /// Whether the user lockup has been spent (server claimed). If true, we must claim regardless
     /// of timeout proximity since the preimage has been revealed.
     pub(crate) user_lockup_spent: bool,
+    /// The provider the swap was created with. If not set, it's the default swap provider
+    pub(crate) swap_provider: Option<String>,
     /// Swap metadata that is only valid when reading one from the local database
     #[derivative(PartialEq = "ignore")]
     pub(crate) metadata: SwapMetadata,
@@ -1391,6 +2174,8 @@ pub struct SendSwap {
     pub(crate) timeout_block_height: u64,
     pub(crate) state: PaymentState,
     pub(crate) refund_private_key: String,
+    /// The provider the swap was created with. If not set, it's the default swap provider
+    pub(crate) swap_provider: Option<String>,
     /// Swap metadata that is only valid when reading one from the local database
     #[derivative(PartialEq = "ignore")]
     pub(crate) metadata: SwapMetadata,
@@ -1494,6 +2279,8 @@ pub struct ReceiveSwap {
     pub(crate) created_at: u32,
     pub(crate) timeout_block_height: u32,
     pub(crate) state: PaymentState,
+    /// The provider the swap was created with. If not set, it's the default swap provider
+    pub(crate) swap_provider: Option<String>,
     /// Swap metadata that is only valid when reading one from the local database
     #[derivative(PartialEq = "ignore")]
     pub(crate) metadata: SwapMetadata,
@@ -1806,6 +2593,10 @@ pub struct PaymentTxData {
     /// Data to use in the `blinded` param when unblinding the transaction in an explorer.
     /// See: <https://docs.liquid.net/docs/unblinding-transactions>
     pub unblinding_data: Option<String>,
+
+    /// The number of blocks left until an incoming tx reaches the confirmations required by the
+    /// [ConfirmationPolicy]. Only set for incoming wallet txs.
+    pub blocks_remaining: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -1965,6 +2756,10 @@ pub enum PaymentDetails {
 
         /// The invoice settlement time, if present
         settled_at: Option<u32>,
+
+        /// For a Receive payment settled as an asset, this is the id of the asset swap
+        /// converting the received L-BTC
+        asset_swap_id: Option<String>,
     },
     /// Direct onchain payment to a Liquid address
     Liquid {
@@ -2022,6 +2817,39 @@ pub enum PaymentDetails {
         /// For a Send swap which was refunded, this is the refund amount
         refund_tx_amount_sat: Option<u64>,
     },
+    /// Moving funds between the Bitcoin chain and Liquid through the Liquid federation
+    Peg {
+        peg_id: String,
+        /// For a peg-in, this is the Bitcoin deposit address.
+        /// For a peg-out, this is the Bitcoin address receiving the funds.
+        bitcoin_address: String,
+        /// Represents the peg description
+        description: String,
+        /// For a peg-in, this is the Bitcoin deposit tx id
+        bitcoin_tx_id: Option<String>,
+        /// For a peg-in, this is the Liquid tx id claiming the deposit
+        claim_tx_id: Option<String>,
+    },
+    /// Swapping between two Liquid assets held by the wallet through SideSwap
+    AssetSwap {
+        swap_id: String,
+        /// Represents the asset swap description
+        description: String,
+        /// The asset id sent to SideSwap
+        from_asset_id: String,
+        /// The asset id received by the wallet
+        to_asset_id: String,
+        /// The amount sent, in the base units of the sent asset
+        payer_amount_sat: u64,
+        /// The amount received, in the base units of the received asset
+        receiver_amount_sat: u64,
+        /// The sent asset info derived from the [AssetMetadata]
+        from_asset_info: Option<AssetInfo>,
+        /// The received asset info derived from the [AssetMetadata]
+        to_asset_info: Option<AssetInfo>,
+        /// For an asset swap settling a Lightning payment, this is the Receive swap id
+        receive_swap_id: Option<String>,
+    },
 }
 
 impl PaymentDetails {
@@ -2030,7 +2858,7 @@ impl PaymentDetails {
             Self::Lightning { swap_id, .. } | Self::Bitcoin { swap_id, .. } => {
                 Some(swap_id.clone())
             }
-            Self::Liquid { .. } => None,
+            Self::Liquid { .. } | Self::Peg { .. } | Self::AssetSwap { .. } => None,
         }
     }
 
@@ -2044,7 +2872,7 @@ impl PaymentDetails {
                 refund_tx_amount_sat,
                 ..
             } => *refund_tx_amount_sat,
-            Self::Liquid { .. } => None,
+            Self::Liquid { .. } | Self::Peg { .. } | Self::AssetSwap { .. } => None,
         }
     }
 
@@ -2052,15 +2880,19 @@ impl PaymentDetails {
         match self {
             Self::Lightning { description, .. }
             | Self::Bitcoin { description, .. }
-            | Self::Liquid { description, .. } => Some(description.clone()),
+            | Self::Liquid { description, .. }
+            | Self::Peg { description, .. }
+            | Self::AssetSwap { description, .. } => Some(description.clone()),
         }
     }
 
     pub(crate) fn is_lbtc_asset_id(&self, network: LiquidNetwork) -> bool {
         match self {
-            Self::Liquid { asset_id, .. } => {
-                asset_id.eq(&utils::lbtc_asset_id(network).to_string())
-            }
+            Self::Liquid { asset_id, .. }
+            | Self::AssetSwap {
+                from_asset_id: asset_id,
+                ..
+            } => asset_id.eq(&utils::lbtc_asset_id(network).to_string()),
             _ => true,
         }
     }
@@ -2122,6 +2954,10 @@ pub struct Payment {
     /// If the tx has an associated swap, this is determined by the swap status (pending or complete).
     pub status: PaymentState,
 
+    /// The number of blocks left until the incoming tx reaches the confirmations required by the
+    /// [ConfirmationPolicy]. Only set for payments with an incoming tx that is not yet settled.
+    pub blocks_remaining: Option<u32>,
+
     /// The details of a payment, depending on its [destination](Payment::destination) and
     /// [type](Payment::payment_type)
     pub details: PaymentDetails,
@@ -2149,6 +2985,7 @@ impl Payment {
             swapper_fees_sat: Some(swap.swapper_fees_sat),
             payment_type,
             status: swap.status,
+            blocks_remaining: None,
             details: payment_details,
         }
     }
@@ -2185,6 +3022,11 @@ impl Payment {
                         asset_info: Some(ref asset_info),
                         ..
                     } if asset_info.ticker != "BTC" => (0, asset_info.fees.map_or(fees_sat, |_| 0)),
+                    // Likewise, an asset swap only shows the amount if L-BTC was sent
+                    PaymentDetails::AssetSwap {
+                        from_asset_info: Some(ref asset_info),
+                        ..
+                    } if asset_info.ticker != "BTC" => (0, fees_sat),
                     _ => (amount_sat, fees_sat),
                 }
             }
@@ -2214,6 +3056,9 @@ impl Payment {
                 }) => bitcoin_address.clone(),
                 _ => match &details {
                     PaymentDetails::Liquid { destination, .. } => Some(destination.clone()),
+                    PaymentDetails::Peg {
+                        bitcoin_address, ..
+                    } => Some(bitcoin_address.clone()),
                     _ => None,
                 },
             },
@@ -2227,11 +3072,12 @@ impl Payment {
             payment_type: balance.payment_type,
             status: match &swap {
                 Some(swap) => swap.status,
-                None => match tx.is_confirmed {
+                None => match tx.is_confirmed && tx.blocks_remaining.is_none_or(|b| b == 0) {
                     true => PaymentState::Complete,
                     false => PaymentState::Pending,
                 },
             },
+            blocks_remaining: None,
             details,
         }
     }
@@ -2240,7 +3086,9 @@ impl Payment {
         match self.details.clone() {
             PaymentDetails::Lightning { refund_tx_id, .. } => Some(refund_tx_id),
             PaymentDetails::Bitcoin { refund_tx_id, .. } => Some(refund_tx_id),
-            PaymentDetails::Liquid { .. } => None,
+            PaymentDetails::Liquid { .. }
+            | PaymentDetails::Peg { .. }
+            | PaymentDetails::AssetSwap { .. } => None,
         }
         .flatten()
     }
@@ -2586,3 +3434,58 @@ macro_rules! get_updated_fields {
         }
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Blockchain, ConfirmationPolicy, ConfirmationTier};
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[sdk_macros::test_all]
+    fn test_confirmation_policy() {
+        let policy = ConfirmationPolicy {
+            liquid_confirmations: 0,
+            bitcoin_confirmations: 0,
+            tiers: vec![
+                ConfirmationTier {
+                    min_amount_sat: 100_000,
+                    liquid_confirmations: 2,
+                    bitcoin_confirmations: 1,
+                },
+                ConfirmationTier {
+                    min_amount_sat: 1_000_000,
+                    liquid_confirmations: 10,
+                    bitcoin_confirmations: 3,
+                },
+            ],
+        };
+
+        // Liquid requires at least one confirmation
+        assert_eq!(policy.required_confirmations(Blockchain::Liquid, 1_000), 1);
+        assert_eq!(policy.required_confirmations(Blockchain::Bitcoin, 1_000), 0);
+        assert_eq!(
+            policy.required_confirmations(Blockchain::Liquid, 100_000),
+            2
+        );
+        assert_eq!(
+            policy.required_confirmations(Blockchain::Bitcoin, 5_000_000),
+            3
+        );
+
+        // Unconfirmed
+        assert_eq!(
+            policy.blocks_remaining(Blockchain::Liquid, 1_000_000, 100, None),
+            10
+        );
+        // Confirmed at the tip
+        assert_eq!(
+            policy.blocks_remaining(Blockchain::Liquid, 1_000_000, 100, Some(100)),
+            9
+        );
+        assert_eq!(
+            policy.blocks_remaining(Blockchain::Liquid, 1_000_000, 109, Some(100)),
+            0
+        );
+    }
+}
@@ -1,6 +1,6 @@
 pub(crate) mod error;
 pub(crate) mod model;
-mod pset;
+pub(crate) mod pset;
 pub(crate) mod side_swap;
 mod utxo_select;
 
@@ -16,6 +16,18 @@ pub trait PayjoinService: Send + Sync {
     /// Estimate the fee for a payjoin transaction
     async fn estimate_payjoin_tx_fee(&self, asset_id: &str, amount_sat: u64) -> PayjoinResult<f64>;
 
+    /// Estimate the fee for a payjoin transaction sending all funds of the asset, which spends
+    /// every utxo of the asset. The fee is deducted from the amount sent.
+    async fn estimate_payjoin_drain_tx_fee(&self, asset_id: &str) -> PayjoinResult<f64>;
+
+    /// Estimate the fee for a payjoin transaction sending `amount_sat` of L-BTC, as built by
+    /// [PayjoinService::build_payjoin_lbtc_tx]
+    async fn estimate_payjoin_lbtc_tx_fee(
+        &self,
+        fee_asset_id: &str,
+        amount_sat: u64,
+    ) -> PayjoinResult<f64>;
+
     /// Build a payjoin transaction to send funds to a recipient using the asset to pay fees.
     /// Returns the transaction and the service fee paid in satoshi units.
     async fn build_payjoin_tx(
@@ -24,4 +36,13 @@ pub trait PayjoinService: Send + Sync {
         asset_id: &str,
         amount_sat: u64,
     ) -> PayjoinResult<(Transaction, u64)>;
+
+    /// Build a payjoin transaction to send L-BTC to a recipient using the fee asset to pay fees.
+    /// Returns the transaction and the service fee paid in satoshi units of the fee asset.
+    async fn build_payjoin_lbtc_tx(
+        &self,
+        recipient_address: &str,
+        fee_asset_id: &str,
+        amount_sat: u64,
+    ) -> PayjoinResult<(Transaction, u64)>;
 }
@@ -29,7 +29,7 @@ use sdk_common::{
 use serde::{de::DeserializeOwned, Serialize};
 use tokio::sync::OnceCell;
 
-use crate::model::{Config, LiquidNetwork};
+use crate::model::{AssetMetadata, Config, LiquidNetwork};
 use crate::payjoin::{
     model::Recipient,
     pset::{blind::remove_explicit_values, construct_pset, ConstructPsetRequest, PsetOutput},
@@ -74,10 +74,13 @@ impl SideSwapPayjoinService {
         }
     }
 
-    fn get_url(&self) -> PayjoinResult<&str> {
+    fn get_url(&self) -> PayjoinResult<String> {
+        if let Some(base_url) = self.config.sideswap_base_url() {
+            return Ok(format!("{base_url}/payjoin"));
+        }
         match self.config.network {
-            LiquidNetwork::Mainnet => Ok(PRODUCTION_SIDESWAP_URL),
-            LiquidNetwork::Testnet => Ok(TESTNET_SIDESWAP_URL),
+            LiquidNetwork::Mainnet => Ok(PRODUCTION_SIDESWAP_URL.to_string()),
+            LiquidNetwork::Testnet => Ok(TESTNET_SIDESWAP_URL.to_string()),
             network => Err(PayjoinError::generic(format!(
                 "Payjoin not supported on {network}"
             ))),
@@ -90,7 +93,7 @@ impl SideSwapPayjoinService {
         debug!("Posting request to SideSwap: {body}");
         let (response, status_code) = self
             .rest_client
-            .post(self.get_url()?, Some(headers), Some(body))
+            .post(&self.get_url()?, Some(headers), Some(body))
             .await?;
         if status_code != 200 {
             error!("Received status code {status_code} response from SideSwap");
@@ -125,15 +128,7 @@ impl PayjoinService for SideSwapPayjoinService {
     }
 
     async fn estimate_payjoin_tx_fee(&self, asset_id: &str, amount_sat: u64) -> PayjoinResult<f64> {
-        // Check the asset is accepted
-        let fee_asset = AssetId::from_str(asset_id)?;
-        let accepted_assets = self.fetch_accepted_assets().await?;
-        ensure_sdk!(
-            accepted_assets
-                .iter()
-                .any(|asset| asset.asset_id == asset_id),
-            PayjoinError::generic("Asset not accepted by SideSwap")
-        );
+        let fee_asset = self.ensure_accepted_asset(asset_id).await?;
 
         // Get and check the wallet asset balance
         let wallet_asset_balance: u64 = self
@@ -148,6 +143,131 @@ impl PayjoinService for SideSwapPayjoinService {
             PayjoinError::InsufficientFunds
         );
 
+        // The user spends an asset input to the recipient and the asset change
+        let (asset_metadata, fee_sat) = self
+            .estimate_fee_sat(asset_id, Self::payjoin_tx_fee(1, 2))
+            .await?;
+        ensure_sdk!(
+            wallet_asset_balance >= amount_sat + fee_sat,
+            PayjoinError::InsufficientFunds
+        );
+
+        Ok(Self::fee_amount(&asset_metadata, fee_sat))
+    }
+
+    async fn estimate_payjoin_drain_tx_fee(&self, asset_id: &str) -> PayjoinResult<f64> {
+        let fee_asset = self.ensure_accepted_asset(asset_id).await?;
+
+        let wallet_utxos = self.onchain_wallet.asset_utxos(&fee_asset).await?;
+        let drain_amount_sat: u64 = wallet_utxos.iter().map(|utxo| utxo.unblinded.value).sum();
+        ensure_sdk!(drain_amount_sat > 0, PayjoinError::InsufficientFunds);
+
+        // The user spends every asset utxo to the recipient
+        let (asset_metadata, fee_sat) = self
+            .estimate_fee_sat(asset_id, Self::payjoin_tx_fee(wallet_utxos.len(), 1))
+            .await?;
+        ensure_sdk!(drain_amount_sat > fee_sat, PayjoinError::InsufficientFunds);
+
+        Ok(Self::fee_amount(&asset_metadata, fee_sat))
+    }
+
+    async fn estimate_payjoin_lbtc_tx_fee(
+        &self,
+        fee_asset_id: &str,
+        amount_sat: u64,
+    ) -> PayjoinResult<f64> {
+        let fee_asset = self.ensure_accepted_asset(fee_asset_id).await?;
+
+        // Get and check the wallet L-BTC balance
+        let policy_asset = utils::lbtc_asset_id(self.config.network);
+        let wallet_lbtc_balance: u64 = self
+            .onchain_wallet
+            .asset_utxos(&policy_asset)
+            .await?
+            .iter()
+            .map(|utxo| utxo.unblinded.value)
+            .sum();
+        ensure_sdk!(
+            wallet_lbtc_balance >= amount_sat,
+            PayjoinError::InsufficientFunds
+        );
+
+        // The user spends an L-BTC and an asset input to the recipient, the L-BTC change and the
+        // asset change
+        let (asset_metadata, fee_sat) = self
+            .estimate_fee_sat(fee_asset_id, Self::payjoin_tx_fee(2, 3))
+            .await?;
+        let wallet_asset_balance: u64 = self
+            .onchain_wallet
+            .asset_utxos(&fee_asset)
+            .await?
+            .iter()
+            .map(|utxo| utxo.unblinded.value)
+            .sum();
+        ensure_sdk!(
+            wallet_asset_balance >= fee_sat,
+            PayjoinError::InsufficientFunds
+        );
+
+        Ok(Self::fee_amount(&asset_metadata, fee_sat))
+    }
+
+    async fn build_payjoin_tx(
+        &self,
+        recipient_address: &str,
+        asset_id: &str,
+        amount_sat: u64,
+    ) -> PayjoinResult<(Transaction, u64)> {
+        let fee_asset = AssetId::from_str(asset_id)?;
+        self.build_tx(recipient_address, fee_asset, amount_sat, fee_asset)
+            .await
+    }
+
+    async fn build_payjoin_lbtc_tx(
+        &self,
+        recipient_address: &str,
+        fee_asset_id: &str,
+        amount_sat: u64,
+    ) -> PayjoinResult<(Transaction, u64)> {
+        let fee_asset = AssetId::from_str(fee_asset_id)?;
+        let policy_asset = utils::lbtc_asset_id(self.config.network);
+        self.build_tx(recipient_address, policy_asset, amount_sat, fee_asset)
+            .await
+    }
+}
+
+impl SideSwapPayjoinService {
+    async fn ensure_accepted_asset(&self, asset_id: &str) -> PayjoinResult<AssetId> {
+        let fee_asset = AssetId::from_str(asset_id)?;
+        let accepted_assets = self.fetch_accepted_assets().await?;
+        ensure_sdk!(
+            accepted_assets
+                .iter()
+                .any(|asset| asset.asset_id == asset_id),
+            PayjoinError::generic("Asset not accepted by SideSwap")
+        );
+        Ok(fee_asset)
+    }
+
+    /// The shape of a payjoin tx with `user_inputs` and `user_outputs`, to which the server adds:
+    /// - 1 input for the server (lbtc)
+    /// - 1 output for the server (asset fee)
+    /// - 1 output for the server (lbtc change)
+    fn payjoin_tx_fee(user_inputs: usize, user_outputs: usize) -> TxFee {
+        TxFee {
+            native_inputs: 1,
+            nested_inputs: user_inputs,
+            outputs: user_outputs + 2,
+        }
+    }
+
+    /// Estimates the server fee of a payjoin tx in satoshi units of the fee asset, which covers
+    /// the network fee of the tx and the server fixed fee
+    async fn estimate_fee_sat(
+        &self,
+        asset_id: &str,
+        tx_fee: TxFee,
+    ) -> PayjoinResult<(AssetMetadata, u64)> {
         // Fetch the fiat rates
         let asset_metadata =
             self.persister
@@ -175,42 +295,29 @@ impl PayjoinService for SideSwapPayjoinService {
             )))?;
 
         let fixed_fee = (SIDESWAP_BASE_USD_FEE_SAT / usd_index_price * asset_index_price) as u64;
-        // Fees assuming we have:
-        // - 1 input for the server (lbtc)
-        // - 1 input for the user (asset)
-        // - 1 output for the user (asset change)
-        // - 1 output for the recipient (asset)
-        // - 1 output for the server (asset fee)
-        // - 1 output for the server (lbtc change)
-        let network_fee = TxFee {
-            native_inputs: 1,
-            nested_inputs: 1,
-            outputs: 4,
-        }
-        .fee(None);
+        let network_fee = tx_fee.fee(None);
         let fee_sat = (network_fee as f64 * asset_index_price) as u64 + fixed_fee;
-        ensure_sdk!(
-            wallet_asset_balance >= amount_sat + fee_sat,
-            PayjoinError::InsufficientFunds
-        );
+        Ok((asset_metadata, fee_sat))
+    }
 
+    fn fee_amount(asset_metadata: &AssetMetadata, fee_sat: u64) -> f64 {
         // The estimation accuracy gives a fee to two decimal places
         let mut fee = asset_metadata.amount_from_sat(fee_sat);
         fee = (fee * 100.0).ceil() / 100.0;
 
         debug!("Estimated payjoin server fee: {fee} ({fee_sat} satoshi units)");
 
-        Ok(fee)
+        fee
     }
 
-    async fn build_payjoin_tx(
+    async fn build_tx(
         &self,
         recipient_address: &str,
-        asset_id: &str,
+        recipient_asset: AssetId,
         amount_sat: u64,
+        fee_asset: AssetId,
     ) -> PayjoinResult<(Transaction, u64)> {
-        let fee_asset = AssetId::from_str(asset_id)?;
-        let wallet_utxos = self
+        let mut wallet_utxos = self
             .onchain_wallet
             .asset_utxos(&fee_asset)
             .await?
@@ -218,6 +325,16 @@ impl PayjoinService for SideSwapPayjoinService {
             .map(Utxo::from)
             .collect::<Vec<_>>();
         ensure_sdk!(!wallet_utxos.is_empty(), PayjoinError::InsufficientFunds);
+        if recipient_asset != fee_asset {
+            // The recipient amount is funded from the wallet utxos of the recipient asset
+            wallet_utxos.extend(
+                self.onchain_wallet
+                    .asset_utxos(&recipient_asset)
+                    .await?
+                    .iter()
+                    .map(Utxo::from),
+            );
+        }
 
         let address = Address::from_str(recipient_address).map_err(|e| {
             PayjoinError::generic(format!(
@@ -226,12 +343,12 @@ impl PayjoinService for SideSwapPayjoinService {
         })?;
         let recipients = vec![Recipient {
             address,
-            asset_id: fee_asset,
+            asset_id: recipient_asset,
             amount: amount_sat,
         }];
 
         let start_request = Request::Start(StartRequest {
-            asset_id: asset_id.to_string(),
+            asset_id: fee_asset.to_string(),
             user_agent: "breezsdk".to_string(),
             api_key: self.config.sideswap_api_key.clone(),
         });
@@ -0,0 +1,892 @@
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+use anyhow::{anyhow, Context, Result};
+use boltz_client::Secp256k1;
+use elements::{confidential, hex::FromHex, Script, Txid};
+use log::{debug, error, info};
+use lwk_wollet::{
+    elements_miniscript::elements::bitcoin::bip32::Xpub, hashes::hex::DisplayHex, WalletTx,
+};
+use tokio::sync::broadcast;
+use tokio_with_wasm::alias as tokio;
+
+use crate::{
+    bitcoin::{
+        self,
+        hashes::{sha256, Hash, HashEngine, Hmac, HmacEngine},
+        opcodes::all::{OP_DEPTH, OP_ELSE},
+        script::{Builder, Instruction},
+        secp256k1::{PublicKey, Scalar},
+        ScriptBuf,
+    },
+    chain::{
+        bitcoin::{BitcoinChainService, History},
+        liquid::LiquidChainService,
+    },
+    elements,
+    error::PaymentError,
+    model::{
+        AsyncSigner, BlockListener, Config, Direction, FederationConfig, LiquidNetwork,
+        PaymentState::*, PaymentType, Peg, LIQUID_FEE_RATE_MSAT_PER_VBYTE,
+        LIQUID_FEE_RATE_SAT_PER_VBYTE,
+    },
+    persist::{model::PaymentTxBalance, Persister},
+    utils,
+    wallet::OnchainWallet,
+};
+
+/// The derivation path of the key spending the claim script of the peg-in with the given index,
+/// in the given BIP44 account of the wallet.
+///
+/// The keys live under a purpose of their own, so they never collide with the wallet keys. As
+/// each account counts its peg-in indexes separately, the account is part of the path too.
+pub(crate) fn peg_in_key_path(network: LiquidNetwork, account_index: u32, index: u32) -> String {
+    let coin_type = match network {
+        LiquidNetwork::Mainnet => 1776,
+        _ => 1,
+    };
+    format!("m/1000'/{coin_type}'/{account_index}'/0/{index}")
+}
+
+pub(crate) struct PegHandler {
+    config: Config,
+    signer: Arc<dyn AsyncSigner>,
+    /// The BIP44 account of the wallet, see [peg_in_key_path]
+    account_index: u32,
+    onchain_wallet: Arc<dyn OnchainWallet>,
+    persister: std::sync::Arc<Persister>,
+    liquid_chain_service: Arc<dyn LiquidChainService>,
+    bitcoin_chain_service: Arc<dyn BitcoinChainService>,
+    subscription_notifier: broadcast::Sender<String>,
+}
+
+#[sdk_macros::async_trait]
+impl BlockListener for PegHandler {
+    async fn on_bitcoin_block(&self, height: u32) {
+        if let Err(e) = self.claim_peg_ins(height).await {
+            error!("Error claiming peg-ins: {e:?}");
+        }
+    }
+
+    async fn on_liquid_block(&self, _height: u32) {
+        if let Err(e) = self.complete_confirmed_pegs().await {
+            error!("Error completing confirmed pegs: {e:?}");
+        }
+    }
+}
+
+impl PegHandler {
+    pub(crate) fn new(
+        config: Config,
+        signer: Arc<dyn AsyncSigner>,
+        account_index: u32,
+        onchain_wallet: Arc<dyn OnchainWallet>,
+        persister: std::sync::Arc<Persister>,
+        liquid_chain_service: Arc<dyn LiquidChainService>,
+        bitcoin_chain_service: Arc<dyn BitcoinChainService>,
+    ) -> Self {
+        let (subscription_notifier, _) = broadcast::channel::<String>(30);
+        Self {
+            config,
+            signer,
+            account_index,
+            onchain_wallet,
+            persister,
+            liquid_chain_service,
+            bitcoin_chain_service,
+            subscription_notifier,
+        }
+    }
+
+    pub(crate) fn subscribe_payment_updates(&self) -> broadcast::Receiver<String> {
+        self.subscription_notifier.subscribe()
+    }
+
+    fn federation_config(&self) -> Result<&FederationConfig> {
+        self.config
+            .federation_config
+            .as_ref()
+            .ok_or(anyhow!("No federation config is set"))
+    }
+
+    /// The public key at the derivation path of a peg-in claim key
+    async fn claim_public_key(&self, key_path: &str) -> Result<bitcoin::PublicKey> {
+        let xpub = Xpub::decode(&self.signer.derive_xpub(key_path.to_string()).await?)?;
+        Ok(bitcoin::PublicKey::new(xpub.public_key))
+    }
+
+    /// Creates a peg-in with a new Bitcoin deposit address
+    pub(crate) async fn create_peg_in(&self, description: Option<String>) -> Result<Peg> {
+        let fedpeg_script = ScriptBuf::from_hex(&self.federation_config()?.fedpeg_script)
+            .context("Invalid fedpeg script")?;
+
+        let key_index = self.persister.next_peg_in_key_index()?;
+        let public_key = self
+            .claim_public_key(&peg_in_key_path(
+                self.config.network,
+                self.account_index,
+                key_index,
+            ))
+            .await?;
+        let claim_script =
+            Script::new_v0_wpkh(&elements::WPubkeyHash::hash(&public_key.to_bytes()));
+        let contract = calculate_contract(&fedpeg_script, claim_script.as_bytes())?;
+        let bitcoin_address = bitcoin::Address::p2shwsh(&contract, self.config.network.into());
+
+        let peg = Peg {
+            id: uuid::Uuid::new_v4().to_string(),
+            direction: Direction::Incoming,
+            bitcoin_address: bitcoin_address.to_string(),
+            claim_script: Some(claim_script.as_bytes().to_lower_hex_string()),
+            key_index: Some(key_index),
+            amount_sat: 0,
+            bitcoin_tx_id: None,
+            bitcoin_vout: None,
+            liquid_tx_id: None,
+            description,
+            state: Created,
+            created_at: utils::now(),
+        };
+        self.persister.insert_or_update_peg(&peg)?;
+        info!("Created peg-in {} with address {bitcoin_address}", peg.id);
+        Ok(peg)
+    }
+
+    /// Builds a signed peg-out tx sending `amount_sat` to the Bitcoin script
+    pub(crate) async fn build_peg_out_tx(
+        &self,
+        bitcoin_script: &bitcoin::Script,
+        amount_sat: u64,
+    ) -> Result<elements::Transaction, PaymentError> {
+        let genesis_hash = self.bitcoin_chain_service.get_block_hash(0).await?;
+        self.onchain_wallet
+            .build_peg_out_tx(
+                Some(LIQUID_FEE_RATE_MSAT_PER_VBYTE),
+                &peg_out_script(&genesis_hash, bitcoin_script),
+                amount_sat,
+            )
+            .await
+    }
+
+    /// Stores a broadcast peg-out, which completes once its tx confirms
+    pub(crate) fn insert_peg_out(
+        &self,
+        bitcoin_address: &str,
+        amount_sat: u64,
+        liquid_tx_id: &str,
+    ) -> Result<Peg> {
+        let peg = Peg {
+            id: uuid::Uuid::new_v4().to_string(),
+            direction: Direction::Outgoing,
+            bitcoin_address: bitcoin_address.to_string(),
+            claim_script: None,
+            key_index: None,
+            amount_sat,
+            bitcoin_tx_id: None,
+            bitcoin_vout: None,
+            liquid_tx_id: Some(liquid_tx_id.to_string()),
+            description: None,
+            state: Pending,
+            created_at: utils::now(),
+        };
+        self.persister.insert_or_update_peg(&peg)?;
+        Ok(peg)
+    }
+
+    fn update_peg(&self, peg: &Peg) -> Result<()> {
+        self.persister.insert_or_update_peg(peg)?;
+        let _ = self.subscription_notifier.send(peg.id.clone());
+        Ok(())
+    }
+
+    async fn claim_peg_ins(&self, height: u32) -> Result<()> {
+        let pegs = self.persister.list_unclaimed_peg_ins()?;
+        if pegs.is_empty() {
+            return Ok(());
+        }
+        let required_confirmations = self.federation_config()?.pegin_confirmations;
+        info!(
+            "Rescanning {} unclaimed peg-in(s) at height {height}",
+            pegs.len()
+        );
+        for peg in pegs {
+            if let Err(e) = self
+                .check_peg_in(peg.clone(), height, required_confirmations)
+                .await
+            {
+                error!("Error checking peg-in {}: {e:?}", peg.id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Finds the deposit to the peg-in address, recording it on the peg when first found.
+    ///
+    /// Only the first deposit to the peg-in address is claimed.
+    async fn find_deposit(&self, peg: &mut Peg) -> Result<Option<(History, bitcoin::Transaction)>> {
+        let script_pubkey = bitcoin::Address::from_str(&peg.bitcoin_address)?
+            .assume_checked()
+            .script_pubkey();
+        let history = self
+            .bitcoin_chain_service
+            .get_script_history(&script_pubkey)
+            .await?;
+        let maybe_deposit = match &peg.bitcoin_tx_id {
+            Some(tx_id) => history.into_iter().find(|h| h.txid.to_string() == *tx_id),
+            None => history.into_iter().next(),
+        };
+        let Some(deposit) = maybe_deposit else {
+            return Ok(None);
+        };
+        let deposit_tx = self
+            .bitcoin_chain_service
+            .get_transactions_with_retry(&[deposit.txid], 3)
+            .await?
+            .into_iter()
+            .next()
+            .context("Deposit tx not found")?;
+
+        if peg.bitcoin_tx_id.is_none() {
+            let (vout, tx_out) = deposit_tx
+                .output
+                .iter()
+                .enumerate()
+                .find(|(_, tx_out)| tx_out.script_pubkey == script_pubkey)
+                .context("Deposit tx does not pay to the peg-in address")?;
+            info!("Found deposit tx {} for peg-in {}", deposit.txid, peg.id);
+            peg.bitcoin_tx_id = Some(deposit.txid.to_string());
+            peg.bitcoin_vout = Some(vout as u32);
+            peg.amount_sat = tx_out.value.to_sat();
+            peg.state = Pending;
+            self.update_peg(peg)?;
+        }
+        Ok(Some((deposit, deposit_tx)))
+    }
+
+    async fn check_peg_in(
+        &self,
+        mut peg: Peg,
+        height: u32,
+        required_confirmations: u32,
+    ) -> Result<()> {
+        let Some((deposit, deposit_tx)) = self.find_deposit(&mut peg).await? else {
+            return Ok(());
+        };
+
+        let confirmations = match deposit.confirmed() {
+            true => height.saturating_sub(deposit.height as u32) + 1,
+            false => 0,
+        };
+        if confirmations < required_confirmations {
+            debug!(
+                "Peg-in {} deposit has {confirmations} of {required_confirmations} confirmations",
+                peg.id
+            );
+            return Ok(());
+        }
+
+        let claim_tx = self
+            .build_claim_tx(&peg, &deposit_tx, deposit.height as u32)
+            .await?;
+        let claim_tx_id = self
+            .liquid_chain_service
+            .broadcast(&claim_tx)
+            .await?
+            .to_string();
+        info!("Broadcast claim tx {claim_tx_id} for peg-in {}", peg.id);
+
+        self.persister.insert_broadcast_tx(
+            &claim_tx_id,
+            0,
+            &[PaymentTxBalance {
+                asset_id: self.config.lbtc_asset_id(),
+                amount: claim_tx.output[0].value.explicit().unwrap_or_default(),
+                payment_type: PaymentType::Receive,
+            }],
+            None,
+        )?;
+        peg.liquid_tx_id = Some(claim_tx_id);
+        self.update_peg(&peg)
+    }
+
+    async fn build_claim_tx(
+        &self,
+        peg: &Peg,
+        deposit_tx: &bitcoin::Transaction,
+        deposit_height: u32,
+    ) -> Result<elements::Transaction> {
+        let claim_script = Script::from_hex(
+            peg.claim_script
+                .as_ref()
+                .context("Peg-in has no claim script")?,
+        )?;
+        let key_path = peg_in_key_path(
+            self.config.network,
+            self.account_index,
+            peg.key_index.context("Peg-in has no claim key")?,
+        );
+        let deposit_tx_id = deposit_tx.compute_txid();
+        let txout_proof = self
+            .bitcoin_chain_service
+            .get_txout_proof(&deposit_tx_id, deposit_height)
+            .await?;
+        let genesis_hash = self.bitcoin_chain_service.get_block_hash(0).await?;
+
+        // The mainchain tx is committed to without its witnesses
+        let mut mainchain_tx = deposit_tx.clone();
+        for input in mainchain_tx.input.iter_mut() {
+            input.witness.clear();
+        }
+        let pegin_witness = vec![
+            peg.amount_sat.to_le_bytes().to_vec(),
+            elements::encode::serialize(&utils::lbtc_asset_id(self.config.network)),
+            genesis_hash.to_byte_array().to_vec(),
+            claim_script.to_bytes(),
+            bitcoin::consensus::serialize(&mainchain_tx),
+            bitcoin::consensus::serialize(&txout_proof),
+        ];
+        let previous_output = elements::OutPoint::new(
+            elements::Txid::from_byte_array(deposit_tx_id.to_byte_array()),
+            peg.bitcoin_vout.context("Peg-in has no deposit output")?,
+        );
+        let claim_address = self.onchain_wallet.next_unused_address().await?;
+
+        // Sign once without fees to get the size of the claim tx
+        let unfunded_tx = self
+            .sign_claim_tx(
+                peg.amount_sat,
+                &key_path,
+                previous_output,
+                pegin_witness.clone(),
+                claim_address.script_pubkey(),
+                0,
+            )
+            .await?;
+        let fees_sat = (unfunded_tx.vsize() as f64 * LIQUID_FEE_RATE_SAT_PER_VBYTE).ceil() as u64;
+        self.sign_claim_tx(
+            peg.amount_sat,
+            &key_path,
+            previous_output,
+            pegin_witness,
+            claim_address.script_pubkey(),
+            fees_sat,
+        )
+        .await
+    }
+
+    async fn sign_claim_tx(
+        &self,
+        amount_sat: u64,
+        key_path: &str,
+        previous_output: elements::OutPoint,
+        pegin_witness: Vec<Vec<u8>>,
+        script_pubkey: Script,
+        fees_sat: u64,
+    ) -> Result<elements::Transaction> {
+        let asset_id = utils::lbtc_asset_id(self.config.network);
+        let receiver_amount_sat = amount_sat
+            .checked_sub(fees_sat)
+            .context("Peg-in amount does not cover the claim fees")?;
+        let mut tx = elements::Transaction {
+            version: 2,
+            lock_time: elements::LockTime::ZERO,
+            input: vec![elements::TxIn {
+                previous_output,
+                is_pegin: true,
+                script_sig: Script::new(),
+                sequence: elements::Sequence::MAX,
+                asset_issuance: Default::default(),
+                witness: elements::TxInWitness {
+                    pegin_witness,
+                    ..Default::default()
+                },
+            }],
+            output: vec![
+                elements::TxOut {
+                    asset: confidential::Asset::Explicit(asset_id),
+                    value: confidential::Value::Explicit(receiver_amount_sat),
+                    nonce: confidential::Nonce::Null,
+                    script_pubkey,
+                    witness: Default::default(),
+                },
+                elements::TxOut::new_fee(fees_sat, asset_id),
+            ],
+        };
+
+        let public_key = self.claim_public_key(key_path).await?;
+        let script_code = Script::new_p2pkh(&elements::PubkeyHash::hash(&public_key.to_bytes()));
+        let sighash = elements::sighash::SighashCache::new(&tx).segwitv0_sighash(
+            0,
+            &script_code,
+            confidential::Value::Explicit(amount_sat),
+            elements::EcdsaSighashType::All,
+        );
+        let mut signature = self
+            .signer
+            .sign_ecdsa(sighash.to_byte_array().to_vec(), key_path.to_string())
+            .await?;
+        signature.push(elements::EcdsaSighashType::All as u8);
+        tx.input[0].witness.script_witness = vec![signature, public_key.to_bytes()];
+        Ok(tx)
+    }
+
+    /// Completes the peg if its Liquid tx is confirmed in the wallet
+    fn complete_if_confirmed(
+        &self,
+        peg: &mut Peg,
+        wallet_txs: &HashMap<Txid, WalletTx>,
+    ) -> Result<()> {
+        let Some(tx_id) = peg
+            .liquid_tx_id
+            .as_ref()
+            .and_then(|tx_id| Txid::from_str(tx_id).ok())
+        else {
+            return Ok(());
+        };
+        if wallet_txs.get(&tx_id).is_some_and(|tx| tx.height.is_some()) {
+            info!("Peg {} tx {tx_id} is confirmed", peg.id);
+            peg.state = Complete;
+            self.update_peg(peg)?;
+        }
+        Ok(())
+    }
+
+    async fn complete_confirmed_pegs(&self) -> Result<()> {
+        let pegs = self.persister.list_pending_pegs_with_liquid_tx()?;
+        if pegs.is_empty() {
+            return Ok(());
+        }
+        let wallet_txs = self.onchain_wallet.transactions_by_tx_id().await?;
+        let mut unconfirmed_peg_ins = vec![];
+        for mut peg in pegs {
+            self.complete_if_confirmed(&mut peg, &wallet_txs)?;
+            if peg.state == Pending && peg.direction == Direction::Incoming {
+                unconfirmed_peg_ins.push(peg);
+            }
+        }
+        self.retry_dropped_claims(unconfirmed_peg_ins, &wallet_txs)
+            .await
+    }
+
+    /// Claims the peg-ins again whose claim tx is neither in the wallet nor found by the chain
+    /// service, e.g. because it was dropped from the mempool.
+    ///
+    /// The claim tx is rebuilt from the deposit, so it is broadcast again even if it was evicted.
+    async fn retry_dropped_claims(
+        &self,
+        peg_ins: Vec<Peg>,
+        wallet_txs: &HashMap<Txid, WalletTx>,
+    ) -> Result<()> {
+        let mut dropped_peg_ins = vec![];
+        for peg in peg_ins {
+            let Some(tx_id) = peg
+                .liquid_tx_id
+                .as_ref()
+                .and_then(|tx_id| Txid::from_str(tx_id).ok())
+            else {
+                continue;
+            };
+            if wallet_txs.contains_key(&tx_id) {
+                continue;
+            }
+            match self.liquid_chain_service.get_transaction_hex(&tx_id).await {
+                Ok(Some(_)) => continue,
+                Ok(None) => {}
+                // Some backends fail instead of returning nothing for an unknown tx
+                Err(e) => debug!("Could not fetch claim tx {tx_id}: {e:?}"),
+            }
+            info!("Claim tx {tx_id} of peg-in {} was not found", peg.id);
+            dropped_peg_ins.push(peg);
+        }
+        if dropped_peg_ins.is_empty() {
+            return Ok(());
+        }
+
+        let required_confirmations = self.federation_config()?.pegin_confirmations;
+        let height = self.bitcoin_chain_service.tip().await?;
+        for peg in dropped_peg_ins {
+            if let Err(e) = self
+                .check_peg_in(peg.clone(), height, required_confirmations)
+                .await
+            {
+                error!("Error claiming peg-in {} again: {e:?}", peg.id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Recovers the deposits, claims and completion of the ongoing pegs from the chain data,
+    /// e.g. for pegs created by another instance and received through sync
+    pub(crate) async fn recover_pegs(&self, wallet_txs: &HashMap<Txid, WalletTx>) -> Result<()> {
+        for mut peg in self.persister.list_ongoing_pegs()? {
+            if peg.direction == Direction::Incoming {
+                if let Err(e) = self.recover_peg_in_claim(&mut peg, wallet_txs).await {
+                    error!("Error recovering peg-in {}: {e:?}", peg.id);
+                    continue;
+                }
+            }
+            self.complete_if_confirmed(&mut peg, wallet_txs)?;
+        }
+        Ok(())
+    }
+
+    async fn recover_peg_in_claim(
+        &self,
+        peg: &mut Peg,
+        wallet_txs: &HashMap<Txid, WalletTx>,
+    ) -> Result<()> {
+        if peg.liquid_tx_id.is_some() || self.find_deposit(peg).await?.is_none() {
+            return Ok(());
+        }
+        let (Some(deposit_tx_id), Some(vout)) = (&peg.bitcoin_tx_id, peg.bitcoin_vout) else {
+            return Ok(());
+        };
+        let deposit_outpoint = elements::OutPoint::new(Txid::from_str(deposit_tx_id)?, vout);
+        let maybe_claim_tx = wallet_txs.values().find(|tx| {
+            tx.tx
+                .input
+                .iter()
+                .any(|input| input.is_pegin && input.previous_output == deposit_outpoint)
+        });
+        if let Some(claim_tx) = maybe_claim_tx {
+            info!("Recovered claim tx {} for peg-in {}", claim_tx.txid, peg.id);
+            peg.liquid_tx_id = Some(claim_tx.txid.to_string());
+            self.update_peg(peg)?;
+        }
+        Ok(())
+    }
+}
+
+/// Tweaks the federation keys of the fedpeg script with the claim script, as the federation
+/// does when validating a peg-in.
+///
+/// For a Liquid-style fedpeg script starting with `OP_DEPTH`, the emergency keys following
+/// `OP_ELSE` are left untweaked.
+pub(crate) fn calculate_contract(
+    fedpeg_script: &bitcoin::Script,
+    claim_script: &[u8],
+) -> Result<ScriptBuf> {
+    let secp = Secp256k1::new();
+    let is_liquid_fedpeg = fedpeg_script.as_bytes().first() == Some(&OP_DEPTH.to_u8());
+    let mut is_after_else = false;
+    let mut builder = Builder::new();
+    for instruction in fedpeg_script.instructions() {
+        builder = match instruction? {
+            Instruction::Op(opcode) => {
+                if is_liquid_fedpeg && opcode == OP_ELSE {
+                    is_after_else = true;
+                }
+                builder.push_opcode(opcode)
+            }
+            Instruction::PushBytes(bytes) if bytes.len() == 33 && !is_after_else => {
+                let mut engine = HmacEngine::<sha256::Hash>::new(bytes.as_bytes());
+                engine.input(claim_script);
+                let tweak = Hmac::<sha256::Hash>::from_engine(engine);
+                let tweaked_key = PublicKey::from_slice(bytes.as_bytes())?
+                    .add_exp_tweak(&secp, &Scalar::from_be_bytes(tweak.to_byte_array())?)?;
+                builder.push_slice(tweaked_key.serialize())
+            }
+            Instruction::PushBytes(bytes) => builder.push_slice(bytes),
+        };
+    }
+    Ok(builder.into_script())
+}
+
+/// The script of a peg-out output, committing to the Bitcoin chain and destination script
+pub(crate) fn peg_out_script(
+    genesis_hash: &bitcoin::BlockHash,
+    bitcoin_script: &bitcoin::Script,
+) -> Script {
+    elements::script::Builder::new()
+        .push_opcode(elements::opcodes::all::OP_RETURN)
+        .push_slice(genesis_hash.as_byte_array())
+        .push_slice(bitcoin_script.as_bytes())
+        .into_script()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use anyhow::Result;
+    use lwk_wollet::hashes::hex::DisplayHex;
+
+    use crate::bitcoin::{
+        self,
+        hashes::Hash,
+        merkle_tree::MerkleBlock,
+        opcodes::all::{OP_CHECKMULTISIG, OP_DEPTH, OP_ELSE, OP_ENDIF, OP_IF, OP_PUSHNUM_1},
+        script::{Builder, Instruction},
+        BlockHash, ScriptBuf,
+    };
+    use crate::{
+        model::{BlockListener, Config, FederationConfig, LiquidNetwork, PaymentState, Signer},
+        signer::{SdkSigner, SyncSignerAdapter},
+        test_utils::{
+            chain::{MockBitcoinChainService, MockLiquidChainService},
+            persist::create_persister,
+            wallet::MockWallet,
+        },
+        utils,
+    };
+
+    use super::{calculate_contract, peg_in_key_path, peg_out_script, PegHandler};
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn pushed_keys(script: &ScriptBuf) -> Vec<Vec<u8>> {
+        script
+            .instructions()
+            .filter_map(|i| match i.unwrap() {
+                Instruction::PushBytes(bytes) if bytes.len() == 33 => {
+                    Some(bytes.as_bytes().to_vec())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[sdk_macros::test_all]
+    fn test_calculate_contract() -> Result<()> {
+        let federation_key = utils::generate_keypair().public_key().serialize();
+        let emergency_key = utils::generate_keypair().public_key().serialize();
+        let fedpeg_script = Builder::new()
+            .push_opcode(OP_DEPTH)
+            .push_opcode(OP_IF)
+            .push_opcode(OP_PUSHNUM_1)
+            .push_slice(federation_key)
+            .push_opcode(OP_PUSHNUM_1)
+            .push_opcode(OP_CHECKMULTISIG)
+            .push_opcode(OP_ELSE)
+            .push_opcode(OP_PUSHNUM_1)
+            .push_slice(emergency_key)
+            .push_opcode(OP_PUSHNUM_1)
+            .push_opcode(OP_CHECKMULTISIG)
+            .push_opcode(OP_ENDIF)
+            .into_script();
+
+        let contract = calculate_contract(&fedpeg_script, &[0x00, 0x14, 0x01])?;
+        let keys = pushed_keys(&contract);
+        assert_eq!(contract.len(), fedpeg_script.len());
+        assert_ne!(keys[0], federation_key.to_vec());
+        assert_eq!(keys[1], emergency_key.to_vec());
+
+        // The contract is deterministic and depends on the claim script
+        assert_eq!(
+            calculate_contract(&fedpeg_script, &[0x00, 0x14, 0x01])?,
+            contract
+        );
+        assert_ne!(
+            calculate_contract(&fedpeg_script, &[0x00, 0x14, 0x02])?,
+            contract
+        );
+        Ok(())
+    }
+
+    #[sdk_macros::test_all]
+    fn test_peg_in_key_path() {
+        assert_eq!(
+            peg_in_key_path(LiquidNetwork::Mainnet, 0, 0),
+            "m/1000'/1776'/0'/0/0"
+        );
+        assert_eq!(
+            peg_in_key_path(LiquidNetwork::Regtest, 0, 5),
+            "m/1000'/1'/0'/0/5"
+        );
+        assert_eq!(
+            peg_in_key_path(LiquidNetwork::Mainnet, 3, 5),
+            "m/1000'/1776'/3'/0/5"
+        );
+    }
+
+    #[sdk_macros::test_all]
+    fn test_peg_out_script() {
+        let genesis_hash = BlockHash::from_byte_array([7; 32]);
+        let bitcoin_script = ScriptBuf::from_bytes(vec![0x00, 0x14, 0x01, 0x02]);
+        let script = peg_out_script(&genesis_hash, &bitcoin_script);
+
+        let mut expected = vec![0x6a, 0x20];
+        expected.extend([7; 32]);
+        expected.extend([0x04, 0x00, 0x14, 0x01, 0x02]);
+        assert_eq!(script.as_bytes(), expected);
+        assert!(script.is_op_return());
+    }
+
+    /// A deposit of `amount_sat` to the address, with the proof of its inclusion in a block
+    fn deposit_tx(address: &str, amount_sat: u64) -> (bitcoin::Transaction, MerkleBlock) {
+        let script_pubkey = bitcoin::Address::from_str(address)
+            .unwrap()
+            .assume_checked()
+            .script_pubkey();
+        let tx = bitcoin::Transaction {
+            version: bitcoin::transaction::Version::TWO,
+            lock_time: bitcoin::absolute::LockTime::ZERO,
+            input: vec![bitcoin::TxIn::default()],
+            output: vec![bitcoin::TxOut {
+                value: bitcoin::Amount::from_sat(amount_sat),
+                script_pubkey,
+            }],
+        };
+        let txid = tx.compute_txid();
+        let header = bitcoin::block::Header {
+            version: bitcoin::block::Version::ONE,
+            prev_blockhash: BlockHash::all_zeros(),
+            merkle_root: bitcoin::TxMerkleNode::from_byte_array(txid.to_byte_array()),
+            time: 0,
+            bits: bitcoin::CompactTarget::from_consensus(0x207fffff),
+            nonce: 0,
+        };
+        let txout_proof =
+            MerkleBlock::from_header_txids_with_predicate(&header, &[txid], |t| *t == txid);
+        (tx, txout_proof)
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_claim_peg_in() -> Result<()> {
+        create_persister!(persister);
+
+        let fedpeg_script = Builder::new()
+            .push_opcode(OP_PUSHNUM_1)
+            .push_slice(utils::generate_keypair().public_key().serialize())
+            .push_opcode(OP_PUSHNUM_1)
+            .push_opcode(OP_CHECKMULTISIG)
+            .into_script();
+        let mut config = Config::regtest_esplora();
+        config.federation_config = Some(FederationConfig {
+            fedpeg_script: fedpeg_script.as_bytes().to_lower_hex_string(),
+            pegin_confirmations: 2,
+        });
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let signer: Box<dyn Signer> = Box::new(SdkSigner::new(mnemonic, "", false)?);
+        let signer = Arc::new(signer);
+        let liquid_chain_service = Arc::new(MockLiquidChainService::new());
+        let bitcoin_chain_service = Arc::new(MockBitcoinChainService::new());
+        let peg_handler = PegHandler::new(
+            config,
+            Arc::new(SyncSignerAdapter::new(signer.clone())),
+            0,
+            Arc::new(MockWallet::new(signer)?),
+            persister.clone(),
+            liquid_chain_service.clone(),
+            bitcoin_chain_service.clone(),
+        );
+
+        let peg = peg_handler.create_peg_in(None).await?;
+        let (deposit_tx, txout_proof) = deposit_tx(&peg.bitcoin_address, 100_000);
+        let deposit_tx_id = deposit_tx.compute_txid();
+        bitcoin_chain_service
+            .set_history(vec![BtcHistory {
+                txid: deposit_tx_id,
+                height: 100,
+            }])
+            .set_transactions(&[&bitcoin::consensus::encode::serialize_hex(&deposit_tx)])
+            .set_txout_proof(txout_proof.clone())
+            .set_tip(100);
+
+        // The deposit is found, but is not claimed before it has the required confirmations
+        peg_handler.on_bitcoin_block(100).await;
+        let peg = persister.fetch_peg_by_id(&peg.id)?.unwrap();
+        assert_eq!(peg.state, PaymentState::Pending);
+        assert_eq!(peg.bitcoin_tx_id, Some(deposit_tx_id.to_string()));
+        assert_eq!(peg.amount_sat, 100_000);
+        assert!(peg.liquid_tx_id.is_none());
+        assert!(liquid_chain_service.get_broadcast_txs().is_empty());
+
+        bitcoin_chain_service.set_tip(101);
+        peg_handler.on_bitcoin_block(101).await;
+        let peg = persister.fetch_peg_by_id(&peg.id)?.unwrap();
+        let broadcast_txs = liquid_chain_service.get_broadcast_txs();
+        assert_eq!(broadcast_txs.len(), 1);
+        let claim_tx = &broadcast_txs[0];
+        assert_eq!(peg.liquid_tx_id, Some(claim_tx.txid().to_string()));
+
+        let input = &claim_tx.input[0];
+        assert!(input.is_pegin);
+        assert_eq!(input.previous_output.vout, 0);
+        assert_eq!(
+            input.previous_output.txid.to_byte_array(),
+            deposit_tx_id.to_byte_array()
+        );
+        assert_eq!(
+            input.witness.pegin_witness.last(),
+            Some(&bitcoin::consensus::serialize(&txout_proof))
+        );
+        assert_eq!(input.witness.script_witness.len(), 2);
+        assert_eq!(
+            claim_tx.output[0].script_pubkey,
+            peg_handler
+                .onchain_wallet
+                .next_unused_address()
+                .await?
+                .script_pubkey()
+        );
+        let claimed_sat = claim_tx.output[0].value.explicit().unwrap();
+        let fees_sat = claim_tx.output[1].value.explicit().unwrap();
+        assert!(fees_sat > 0);
+        assert_eq!(claimed_sat + fees_sat, 100_000);
+
+        // A claim tx that is still known is not broadcast again
+        liquid_chain_service.set_transactions(vec![claim_tx.clone()]);
+        peg_handler.on_liquid_block(1).await;
+        assert_eq!(liquid_chain_service.get_broadcast_txs().len(), 1);
+
+        // A claim tx dropped from the mempool is claimed again
+        liquid_chain_service.set_transactions(vec![]);
+        peg_handler.on_liquid_block(2).await;
+        let broadcast_txs = liquid_chain_service.get_broadcast_txs();
+        assert_eq!(broadcast_txs.len(), 2);
+        assert_eq!(
+            broadcast_txs[1].input[0].previous_output,
+            input.previous_output
+        );
+        let peg = persister.fetch_peg_by_id(&peg.id)?.unwrap();
+        assert_eq!(peg.liquid_tx_id, Some(broadcast_txs[1].txid().to_string()));
+        assert_eq!(peg.state, PaymentState::Pending);
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_peg_in_address_per_account() -> Result<()> {
+        // Each account of a seed has its own database, so its peg-in key indexes start over
+        create_persister!(account_0_persister);
+        create_persister!(account_1_persister);
+
+        let fedpeg_script = Builder::new()
+            .push_opcode(OP_PUSHNUM_1)
+            .push_slice(utils::generate_keypair().public_key().serialize())
+            .push_opcode(OP_PUSHNUM_1)
+            .push_opcode(OP_CHECKMULTISIG)
+            .into_script();
+        let mut config = Config::regtest_esplora();
+        config.federation_config = Some(FederationConfig {
+            fedpeg_script: fedpeg_script.as_bytes().to_lower_hex_string(),
+            pegin_confirmations: 2,
+        });
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let signer: Box<dyn Signer> = Box::new(SdkSigner::new(mnemonic, "", false)?);
+        let signer = Arc::new(signer);
+        let peg_handler = |account_index, persister| -> Result<PegHandler> {
+            Ok(PegHandler::new(
+                config.clone(),
+                Arc::new(SyncSignerAdapter::new(signer.clone())),
+                account_index,
+                Arc::new(MockWallet::new(signer.clone())?),
+                persister,
+                Arc::new(MockLiquidChainService::new()),
+                Arc::new(MockBitcoinChainService::new()),
+            ))
+        };
+
+        let account_0_peg = peg_handler(0, account_0_persister)?
+            .create_peg_in(None)
+            .await?;
+        let account_1_peg = peg_handler(1, account_1_persister)?
+            .create_peg_in(None)
+            .await?;
+        assert_eq!(account_0_peg.key_index, account_1_peg.key_index);
+        assert_ne!(account_0_peg.claim_script, account_1_peg.claim_script);
+        assert_ne!(account_0_peg.bitcoin_address, account_1_peg.bitcoin_address);
+        Ok(())
+    }
+}
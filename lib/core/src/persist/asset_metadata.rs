@@ -9,11 +9,14 @@ impl Persister {
         asset_metadata: Option<Vec<AssetMetadata>>,
     ) -> Result<()> {
         let con = self.get_connection()?;
-        con.execute("DELETE FROM asset_metadata WHERE is_default = 0", [])?;
+        con.execute(
+            "DELETE FROM asset_metadata WHERE is_default = 0 AND is_issued = 0 AND is_registry = 0",
+            [],
+        )?;
         if let Some(asset_metadata) = asset_metadata {
             for am in asset_metadata {
                 con.execute(
-                    "INSERT INTO asset_metadata (asset_id, name, ticker, precision, fiat_id) VALUES (?, ?, ?, ?, ?)",
+                    "INSERT OR REPLACE INTO asset_metadata (asset_id, name, ticker, precision, fiat_id) VALUES (?, ?, ?, ?, ?)",
                     (am.asset_id, am.name, am.ticker, am.precision, am.fiat_id),
                 )?;
             }
@@ -22,6 +25,27 @@ impl Persister {
         Ok(())
     }
 
+    /// Registers the metadata of an asset issued by the wallet. Unlike the metadata set in the
+    /// [Config](crate::model::Config), it is kept across restarts.
+    pub(crate) fn insert_issued_asset_metadata(&self, am: &AssetMetadata) -> Result<()> {
+        let con = self.get_connection()?;
+        con.execute(
+            "INSERT OR REPLACE INTO asset_metadata (asset_id, name, ticker, precision, fiat_id, is_issued) VALUES (?, ?, ?, ?, ?, 1)",
+            (&am.asset_id, &am.name, &am.ticker, am.precision, &am.fiat_id),
+        )?;
+        Ok(())
+    }
+
+    /// Caches the metadata of an asset resolved from the asset registry
+    pub(crate) fn insert_registry_asset_metadata(&self, am: &AssetMetadata) -> Result<()> {
+        let con = self.get_connection()?;
+        con.execute(
+            "INSERT OR REPLACE INTO asset_metadata (asset_id, name, ticker, precision, fiat_id, is_registry) VALUES (?, ?, ?, ?, ?, 1)",
+            (&am.asset_id, &am.name, &am.ticker, am.precision, &am.fiat_id),
+        )?;
+        Ok(())
+    }
+
     pub(crate) fn list_asset_metadata(&self) -> Result<Vec<AssetMetadata>> {
         let con = self.get_connection()?;
         let mut stmt = con.prepare(
@@ -65,3 +89,43 @@ impl Persister {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use crate::{model::AssetMetadata, test_utils::persist::create_persister};
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[sdk_macros::test_all]
+    fn test_issued_asset_metadata_is_kept() -> Result<()> {
+        create_persister!(storage);
+
+        let issued = AssetMetadata {
+            asset_id: "issued".to_string(),
+            name: "Loyalty Points".to_string(),
+            ticker: "PTS".to_string(),
+            precision: 0,
+            fiat_id: None,
+        };
+        storage.insert_issued_asset_metadata(&issued)?;
+        storage.replace_asset_metadata(Some(vec![AssetMetadata {
+            asset_id: "configured".to_string(),
+            name: "Configured".to_string(),
+            ticker: "CFG".to_string(),
+            precision: 2,
+            fiat_id: None,
+        }]))?;
+        storage.replace_asset_metadata(None)?;
+
+        assert!(storage.get_asset_metadata("configured")?.is_none());
+        let fetched = storage
+            .get_asset_metadata(&issued.asset_id)?
+            .expect("Issued asset metadata should be kept");
+        assert_eq!(fetched.name, issued.name);
+        assert_eq!(fetched.precision, issued.precision);
+        Ok(())
+    }
+}
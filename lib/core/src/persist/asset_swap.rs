@@ -0,0 +1,249 @@
+use anyhow::Result;
+use rusqlite::{named_params, Row};
+
+use super::Persister;
+use crate::model::{AssetSettlement, AssetSwapData, PaymentState};
+use crate::utils::{from_row_to_u64, from_u64_to_row};
+
+impl Persister {
+    pub(crate) fn insert_asset_swap(&self, asset_swap: &AssetSwapData) -> Result<()> {
+        let con = self.get_connection()?;
+        con.execute(
+            "INSERT INTO asset_swaps (
+                id,
+                tx_id,
+                from_asset,
+                to_asset,
+                payer_amount_sat,
+                receiver_amount_sat,
+                fees_sat,
+                exchange_rate,
+                description,
+                receive_swap_id,
+                created_at
+            )
+            VALUES (
+                :id,
+                :tx_id,
+                :from_asset,
+                :to_asset,
+                :payer_amount_sat,
+                :receiver_amount_sat,
+                :fees_sat,
+                :exchange_rate,
+                :description,
+                :receive_swap_id,
+                :created_at
+            )",
+            named_params! {
+                ":id": &asset_swap.id,
+                ":tx_id": &asset_swap.tx_id,
+                ":from_asset": &asset_swap.from_asset,
+                ":to_asset": &asset_swap.to_asset,
+                ":payer_amount_sat": from_u64_to_row(asset_swap.payer_amount_sat)?,
+                ":receiver_amount_sat": from_u64_to_row(asset_swap.receiver_amount_sat)?,
+                ":fees_sat": from_u64_to_row(asset_swap.fees_sat)?,
+                ":exchange_rate": &asset_swap.exchange_rate,
+                ":description": &asset_swap.description,
+                ":receive_swap_id": &asset_swap.receive_swap_id,
+                ":created_at": &asset_swap.created_at,
+            },
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn insert_or_update_asset_settlement(
+        &self,
+        settlement: &AssetSettlement,
+    ) -> Result<()> {
+        let con = self.get_connection()?;
+        con.execute(
+            "INSERT INTO asset_settlements (
+                swap_id,
+                asset_id,
+                min_receiver_amount_sat,
+                state,
+                created_at
+            )
+            VALUES (
+                :swap_id,
+                :asset_id,
+                :min_receiver_amount_sat,
+                :state,
+                :created_at
+            )
+            ON CONFLICT(swap_id) DO UPDATE SET
+                state = excluded.state",
+            named_params! {
+                ":swap_id": &settlement.swap_id,
+                ":asset_id": &settlement.asset_id,
+                ":min_receiver_amount_sat": from_u64_to_row(settlement.min_receiver_amount_sat)?,
+                ":state": &settlement.state,
+                ":created_at": &settlement.created_at,
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Lists the settlements not yet converted whose Receive swap is complete
+    pub(crate) fn list_pending_asset_settlements(&self) -> Result<Vec<AssetSettlement>> {
+        let con = self.get_connection()?;
+        let mut stmt = con.prepare(
+            "SELECT
+                st.swap_id,
+                st.asset_id,
+                st.min_receiver_amount_sat,
+                st.state,
+                st.created_at
+            FROM asset_settlements AS st
+            JOIN receive_swaps AS rs ON rs.id = st.swap_id
+            WHERE st.state = ?1 AND rs.state = ?2
+            ORDER BY st.created_at",
+        )?;
+        let settlements = stmt
+            .query_map(
+                (PaymentState::Created, PaymentState::Complete),
+                Self::sql_row_to_asset_settlement,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(settlements)
+    }
+
+    fn sql_row_to_asset_settlement(row: &Row) -> rusqlite::Result<AssetSettlement> {
+        Ok(AssetSettlement {
+            swap_id: row.get(0)?,
+            asset_id: row.get(1)?,
+            min_receiver_amount_sat: from_row_to_u64(row, 2)?,
+            state: row.get(3)?,
+            created_at: row.get(4)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{anyhow, Result};
+
+    use crate::{
+        model::{
+            AssetSettlement, AssetSwapData, ListPaymentsRequest, PaymentDetails, PaymentState,
+            PaymentTxData, PaymentType,
+        },
+        persist::model::PaymentTxBalance,
+        test_utils::persist::{create_persister, new_receive_swap},
+        utils,
+    };
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    const USDT_ASSET_ID: &str = "ce091c998b83c78bb71a632313ba3760f1763d9cfcffae02258ffa9865a37bd2";
+
+    #[sdk_macros::test_all]
+    fn test_asset_swap_is_a_single_payment() -> Result<()> {
+        create_persister!(storage);
+        let lbtc_asset_id = utils::lbtc_asset_id(storage.network).to_string();
+        let tx_id = "asset-swap-tx".to_string();
+
+        storage.insert_or_update_payment(
+            PaymentTxData {
+                tx_id: tx_id.clone(),
+                timestamp: Some(utils::now()),
+                fees_sat: 100,
+                is_confirmed: false,
+                unblinding_data: None,
+                blocks_remaining: None,
+            },
+            &[
+                PaymentTxBalance {
+                    asset_id: lbtc_asset_id.clone(),
+                    amount: 10_000,
+                    payment_type: PaymentType::Send,
+                },
+                PaymentTxBalance {
+                    asset_id: USDT_ASSET_ID.to_string(),
+                    amount: 1_000_000_000,
+                    payment_type: PaymentType::Receive,
+                },
+            ],
+            None,
+            false,
+        )?;
+        storage.insert_asset_swap(&AssetSwapData {
+            id: "asset-swap".to_string(),
+            tx_id: tx_id.clone(),
+            from_asset: lbtc_asset_id.clone(),
+            to_asset: USDT_ASSET_ID.to_string(),
+            payer_amount_sat: 10_000,
+            receiver_amount_sat: 1_000_000_000,
+            fees_sat: 100,
+            exchange_rate: 100_000.0,
+            description: None,
+            receive_swap_id: None,
+            created_at: utils::now(),
+        })?;
+
+        let payments = storage.get_payments(&ListPaymentsRequest::default())?;
+        assert_eq!(payments.len(), 1);
+        let payment = storage
+            .get_payment("asset-swap")?
+            .ok_or(anyhow!("Payment not found"))?;
+        assert_eq!(payment.tx_id, Some(tx_id));
+        assert_eq!(payment.payment_type, PaymentType::Send);
+        assert_eq!(payment.amount_sat, 10_000);
+        match payment.details {
+            PaymentDetails::AssetSwap {
+                swap_id,
+                from_asset_id,
+                to_asset_id,
+                payer_amount_sat,
+                receiver_amount_sat,
+                ..
+            } => {
+                assert_eq!(swap_id, "asset-swap");
+                assert_eq!(from_asset_id, lbtc_asset_id);
+                assert_eq!(to_asset_id, USDT_ASSET_ID);
+                assert_eq!(payer_amount_sat, 10_000);
+                assert_eq!(receiver_amount_sat, 1_000_000_000);
+            }
+            _ => return Err(anyhow!("Unexpected payment details")),
+        }
+        Ok(())
+    }
+
+    #[sdk_macros::test_all]
+    fn test_list_pending_asset_settlements() -> Result<()> {
+        create_persister!(storage);
+
+        let receive_swap = new_receive_swap(Some(PaymentState::Pending), None);
+        storage.insert_or_update_receive_swap(&receive_swap)?;
+        let mut settlement = AssetSettlement {
+            swap_id: receive_swap.id.clone(),
+            asset_id: USDT_ASSET_ID.to_string(),
+            min_receiver_amount_sat: 1_000_000_000,
+            state: PaymentState::Created,
+            created_at: utils::now(),
+        };
+        storage.insert_or_update_asset_settlement(&settlement)?;
+
+        // Only settlements of complete Receive swaps are pending
+        assert!(storage.list_pending_asset_settlements()?.is_empty());
+        storage.try_handle_receive_swap_update(
+            &receive_swap.id,
+            PaymentState::Complete,
+            None,
+            None,
+            None,
+            None,
+        )?;
+        assert_eq!(
+            storage.list_pending_asset_settlements()?,
+            vec![settlement.clone()]
+        );
+
+        settlement.state = PaymentState::Failed;
+        storage.insert_or_update_asset_settlement(&settlement)?;
+        assert!(storage.list_pending_asset_settlements()?.is_empty());
+        Ok(())
+    }
+}
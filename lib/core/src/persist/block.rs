@@ -0,0 +1,110 @@
+use anyhow::Result;
+use rusqlite::{params, Row};
+
+use super::Persister;
+use crate::model::{BlockHeader, Blockchain};
+
+impl Persister {
+    /// Lists the tracked block hashes of a chain, ordered from the highest block down
+    pub(crate) fn list_block_headers(&self, chain: Blockchain) -> Result<Vec<BlockHeader>> {
+        let con = self.get_connection()?;
+        let mut stmt = con.prepare(
+            "SELECT chain, height, hash
+            FROM block_hashes
+            WHERE chain = ?
+            ORDER BY height DESC",
+        )?;
+        let headers: Vec<BlockHeader> = stmt
+            .query_map([chain], Self::sql_row_to_block_header)?
+            .map(|i| i.unwrap())
+            .collect();
+        Ok(headers)
+    }
+
+    pub(crate) fn insert_or_update_block_header(&self, header: &BlockHeader) -> Result<()> {
+        let con = self.get_connection()?;
+        con.execute(
+            "INSERT OR REPLACE INTO block_hashes (chain, height, hash) VALUES (?, ?, ?)",
+            params![header.chain, header.height, header.hash],
+        )?;
+        Ok(())
+    }
+
+    /// Removes the tracked block hashes of a chain above `height`, e.g. once they are
+    /// known to have been disconnected
+    pub(crate) fn delete_block_headers_above(&self, chain: Blockchain, height: u32) -> Result<()> {
+        let con = self.get_connection()?;
+        con.execute(
+            "DELETE FROM block_hashes WHERE chain = ? AND height > ?",
+            params![chain, height],
+        )?;
+        Ok(())
+    }
+
+    /// Removes the tracked block hashes of a chain below `height`
+    pub(crate) fn delete_block_headers_below(&self, chain: Blockchain, height: u32) -> Result<()> {
+        let con = self.get_connection()?;
+        con.execute(
+            "DELETE FROM block_hashes WHERE chain = ? AND height < ?",
+            params![chain, height],
+        )?;
+        Ok(())
+    }
+
+    fn sql_row_to_block_header(row: &Row) -> rusqlite::Result<BlockHeader> {
+        Ok(BlockHeader {
+            chain: row.get(0)?,
+            height: row.get(1)?,
+            hash: row.get(2)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use crate::{
+        model::{BlockHeader, Blockchain},
+        test_utils::persist::create_persister,
+    };
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[sdk_macros::test_all]
+    fn test_block_headers() -> Result<()> {
+        create_persister!(storage);
+
+        for height in 100..105 {
+            storage.insert_or_update_block_header(&BlockHeader {
+                chain: Blockchain::Liquid,
+                height,
+                hash: format!("liquid-{height}"),
+            })?;
+        }
+        storage.insert_or_update_block_header(&BlockHeader {
+            chain: Blockchain::Bitcoin,
+            height: 200,
+            hash: "bitcoin-200".to_string(),
+        })?;
+
+        let headers = storage.list_block_headers(Blockchain::Liquid)?;
+        assert_eq!(headers.len(), 5);
+        assert_eq!(headers[0].height, 104);
+        assert_eq!(headers[4].height, 100);
+
+        storage.delete_block_headers_above(Blockchain::Liquid, 102)?;
+        storage.delete_block_headers_below(Blockchain::Liquid, 101)?;
+        let headers = storage.list_block_headers(Blockchain::Liquid)?;
+        assert_eq!(
+            headers.iter().map(|h| h.height).collect::<Vec<u32>>(),
+            vec![102, 101]
+        );
+
+        // Other chains are not affected
+        assert_eq!(storage.list_block_headers(Blockchain::Bitcoin)?.len(), 1);
+
+        Ok(())
+    }
+}
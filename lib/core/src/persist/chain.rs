@@ -79,7 +79,8 @@ impl Persister {
                 state = :state,
                 actual_payer_amount_sat = :actual_payer_amount_sat,
                 accepted_receiver_amount_sat = :accepted_receiver_amount_sat,
-                user_lockup_spent = :user_lockup_spent
+                user_lockup_spent = :user_lockup_spent,
+                swap_provider = :swap_provider
             WHERE
                 id = :id AND
                 version = :version",
@@ -98,6 +99,7 @@ impl Persister {
                 ":actual_payer_amount_sat": from_optional_u64_to_row(&chain_swap.actual_payer_amount_sat)?,
                 ":accepted_receiver_amount_sat": from_optional_u64_to_row(&chain_swap.accepted_receiver_amount_sat)?,
                 ":user_lockup_spent": &chain_swap.user_lockup_spent,
+                ":swap_provider": &chain_swap.swap_provider,
                 ":version": from_u64_to_row(chain_swap.metadata.version)?,
             },
         )?;
@@ -110,6 +112,7 @@ impl Persister {
     }
 
     pub(crate) fn insert_or_update_chain_swap(&self, chain_swap: &ChainSwap) -> Result<()> {
+        self.check_fault("insert_or_update_chain_swap")?;
         let maybe_swap = self.fetch_chain_swap_by_id(&chain_swap.id)?;
         let updated_fields = ChainSyncData::updated_fields(maybe_swap, chain_swap);
 
@@ -171,6 +174,7 @@ impl Persister {
                 user_lockup_spent,
                 version,
                 last_updated_at,
+                swap_provider,
 
                 sync_state.is_local
             FROM chain_swaps
@@ -229,10 +233,11 @@ impl Persister {
             accepted_receiver_amount_sat: from_row_to_optional_u64(row, 24)?,
             auto_accepted_fees: row.get(25)?,
             user_lockup_spent: row.get(26)?,
+            swap_provider: row.get(29)?,
             metadata: SwapMetadata {
                 version: from_row_to_u64(row, 27)?,
                 last_updated_at: row.get(28)?,
-                is_local: row.get::<usize, Option<bool>>(29)?.unwrap_or(true),
+                is_local: row.get::<usize, Option<bool>>(30)?.unwrap_or(true),
             },
         })
     }
@@ -476,6 +481,7 @@ impl Persister {
         &self,
         swap_update: &ChainSwapUpdate,
     ) -> Result<(), PaymentError> {
+        self.check_fault("try_handle_chain_swap_update")?;
         // Do not overwrite server_lockup_tx_id, user_lockup_tx_id, claim_address, claim_tx_id
         // Overwrite refund_tx_id if provided (refund tx fee bump)
         let mut con = self.get_connection()?;
@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Result};
+use rusqlite::{named_params, params, OptionalExtension, Row, TransactionBehavior};
+
+use super::Persister;
+use crate::model::{LedgerEntry, LedgerEntryType, SubAccount};
+use crate::utils::{self, from_row_to_u64, from_u64_to_row};
+
+/// A sub-account balance change to be recorded in the ledger
+#[derive(Clone, Debug)]
+pub(crate) struct LedgerChange {
+    pub(crate) sub_account_id: String,
+    pub(crate) entry_type: LedgerEntryType,
+    /// The balance change, which is negative for debits
+    pub(crate) amount_sat: i64,
+    pub(crate) payment_id: Option<String>,
+    pub(crate) transfer_id: Option<String>,
+    pub(crate) description: Option<String>,
+}
+
+impl Persister {
+    pub(crate) fn insert_sub_account(&self, sub_account: &SubAccount) -> Result<()> {
+        let con = self.get_connection()?;
+        con.execute(
+            "INSERT INTO sub_accounts (id, name, balance_sat, created_at)
+            VALUES (:id, :name, :balance_sat, :created_at)",
+            named_params! {
+                ":id": &sub_account.id,
+                ":name": &sub_account.name,
+                ":balance_sat": from_u64_to_row(sub_account.balance_sat)?,
+                ":created_at": &sub_account.created_at,
+            },
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn fetch_sub_account(&self, id: &str) -> Result<Option<SubAccount>> {
+        let con = self.get_connection()?;
+        Ok(con
+            .query_row(
+                "SELECT id, name, balance_sat, created_at FROM sub_accounts WHERE id = ?",
+                [id],
+                Self::sql_row_to_sub_account,
+            )
+            .optional()?)
+    }
+
+    pub(crate) fn list_sub_accounts(&self) -> Result<Vec<SubAccount>> {
+        let con = self.get_connection()?;
+        let mut stmt = con.prepare(
+            "SELECT id, name, balance_sat, created_at
+            FROM sub_accounts
+            ORDER BY created_at, id",
+        )?;
+        let sub_accounts = stmt
+            .query_map([], Self::sql_row_to_sub_account)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(sub_accounts)
+    }
+
+    pub(crate) fn has_sub_accounts(&self) -> Result<bool> {
+        let con = self.get_connection()?;
+        Ok(
+            con.query_row("SELECT EXISTS(SELECT 1 FROM sub_accounts)", [], |row| {
+                row.get(0)
+            })?,
+        )
+    }
+
+    /// The sum of all sub-account balances
+    pub(crate) fn get_allocated_balance_sat(&self) -> Result<u64> {
+        let con = self.get_connection()?;
+        Ok(con.query_row(
+            "SELECT COALESCE(SUM(balance_sat), 0) FROM sub_accounts",
+            [],
+            |row| from_row_to_u64(row, 0),
+        )?)
+    }
+
+    /// Credits the incoming payments to `destination` to the sub-account. A destination can
+    /// only be attributed to one sub-account.
+    pub(crate) fn insert_ledger_attribution(
+        &self,
+        destination: &str,
+        sub_account_id: &str,
+    ) -> Result<()> {
+        let con = self.get_connection()?;
+        let maybe_owner: Option<String> = con
+            .query_row(
+                "SELECT sub_account_id FROM ledger_attributions WHERE destination = ?",
+                [destination],
+                |row| row.get(0),
+            )
+            .optional()?;
+        match maybe_owner {
+            Some(owner) if owner == sub_account_id => Ok(()),
+            Some(owner) => bail!("Destination is already attributed to sub-account {owner}"),
+            None => {
+                con.execute(
+                    "INSERT INTO ledger_attributions (destination, sub_account_id, created_at)
+                    VALUES (?, ?, ?)",
+                    params![destination, sub_account_id, utils::now()],
+                )?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the sub-account id of each attributed destination
+    pub(crate) fn list_ledger_attributions(&self) -> Result<HashMap<String, String>> {
+        let con = self.get_connection()?;
+        let mut stmt =
+            con.prepare("SELECT destination, sub_account_id FROM ledger_attributions")?;
+        let attributions = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(attributions)
+    }
+
+    /// Records the changes and updates the sub-account balances atomically.
+    ///
+    /// Changes of a payment that were already recorded are skipped, so the incoming payments
+    /// and refunds can be credited again safely. Fails without recording anything if a
+    /// sub-account balance would become negative.
+    pub(crate) fn apply_ledger_changes(
+        &self,
+        changes: &[LedgerChange],
+    ) -> Result<Vec<LedgerEntry>> {
+        let mut con = self.get_connection()?;
+        let db_tx = con.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let created_at = utils::now();
+        let mut entries = vec![];
+        for change in changes {
+            if let Some(payment_id) = &change.payment_id {
+                let is_recorded: bool = db_tx.query_row(
+                    "SELECT EXISTS(
+                        SELECT 1 FROM ledger_entries WHERE payment_id = ? AND entry_type = ?
+                    )",
+                    params![payment_id, change.entry_type],
+                    |row| row.get(0),
+                )?;
+                if is_recorded {
+                    continue;
+                }
+            }
+
+            let balance_sat: i64 = db_tx
+                .query_row(
+                    "SELECT balance_sat FROM sub_accounts WHERE id = ?",
+                    [&change.sub_account_id],
+                    |row| row.get(0),
+                )
+                .optional()?
+                .ok_or(anyhow!("Unknown sub-account {}", change.sub_account_id))?;
+            let new_balance_sat = balance_sat
+                .checked_add(change.amount_sat)
+                .filter(|balance| *balance >= 0)
+                .ok_or(anyhow!(
+                    "Insufficient balance in sub-account {}",
+                    change.sub_account_id
+                ))?;
+
+            db_tx.execute(
+                "UPDATE sub_accounts SET balance_sat = ? WHERE id = ?",
+                params![new_balance_sat, change.sub_account_id],
+            )?;
+            db_tx.execute(
+                "INSERT INTO ledger_entries (
+                    sub_account_id,
+                    entry_type,
+                    amount_sat,
+                    balance_sat,
+                    payment_id,
+                    transfer_id,
+                    description,
+                    created_at
+                )
+                VALUES (
+                    :sub_account_id,
+                    :entry_type,
+                    :amount_sat,
+                    :balance_sat,
+                    :payment_id,
+                    :transfer_id,
+                    :description,
+                    :created_at
+                )",
+                named_params! {
+                    ":sub_account_id": &change.sub_account_id,
+                    ":entry_type": &change.entry_type,
+                    ":amount_sat": &change.amount_sat,
+                    ":balance_sat": &new_balance_sat,
+                    ":payment_id": &change.payment_id,
+                    ":transfer_id": &change.transfer_id,
+                    ":description": &change.description,
+                    ":created_at": &created_at,
+                },
+            )?;
+            entries.push(LedgerEntry {
+                id: db_tx.last_insert_rowid(),
+                sub_account_id: change.sub_account_id.clone(),
+                entry_type: change.entry_type,
+                amount_sat: change.amount_sat,
+                balance_sat: new_balance_sat as u64,
+                payment_id: change.payment_id.clone(),
+                transfer_id: change.transfer_id.clone(),
+                description: change.description.clone(),
+                created_at,
+            });
+        }
+        db_tx.commit()?;
+        Ok(entries)
+    }
+
+    /// Keys the pending debit of a send by the id of its payment, once it is known. Returns
+    /// false if the payment was already debited, in which case the pending debit is unchanged.
+    pub(crate) fn set_ledger_send_payment_id(
+        &self,
+        pending_id: &str,
+        payment_id: &str,
+    ) -> Result<bool> {
+        let con = self.get_connection()?;
+        let updated = con.execute(
+            "UPDATE OR IGNORE ledger_entries SET payment_id = ?1
+            WHERE payment_id = ?2 AND entry_type = ?3",
+            params![payment_id, pending_id, LedgerEntryType::Send],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Lists the ledger entries in the order they were recorded
+    pub(crate) fn list_ledger_entries(
+        &self,
+        sub_account_id: Option<&str>,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<Vec<LedgerEntry>> {
+        let con = self.get_connection()?;
+        let mut stmt = con.prepare(&format!(
+            "{} WHERE (?1 IS NULL OR sub_account_id = ?1)
+            ORDER BY id
+            LIMIT ?2 OFFSET ?3",
+            Self::list_ledger_entries_query()
+        ))?;
+        let entries = stmt
+            .query_map(
+                params![
+                    sub_account_id,
+                    limit.map(i64::from).unwrap_or(-1),
+                    offset.unwrap_or_default()
+                ],
+                Self::sql_row_to_ledger_entry,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }
+
+    /// Lists the sends debited from a sub-account that were not refunded
+    pub(crate) fn list_unrefunded_ledger_sends(&self) -> Result<Vec<LedgerEntry>> {
+        let con = self.get_connection()?;
+        let mut stmt = con.prepare(&format!(
+            "{} WHERE entry_type = ?1 AND NOT EXISTS (
+                SELECT 1 FROM ledger_entries r
+                WHERE r.payment_id = ledger_entries.payment_id AND r.entry_type = ?2
+            )
+            ORDER BY id",
+            Self::list_ledger_entries_query()
+        ))?;
+        let entries = stmt
+            .query_map(
+                params![LedgerEntryType::Send, LedgerEntryType::Refund],
+                Self::sql_row_to_ledger_entry,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }
+
+    fn list_ledger_entries_query() -> &'static str {
+        "SELECT
+            id,
+            sub_account_id,
+            entry_type,
+            amount_sat,
+            balance_sat,
+            payment_id,
+            transfer_id,
+            description,
+            created_at
+        FROM ledger_entries"
+    }
+
+    fn sql_row_to_sub_account(row: &Row) -> rusqlite::Result<SubAccount> {
+        Ok(SubAccount {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            balance_sat: from_row_to_u64(row, 2)?,
+            created_at: row.get(3)?,
+        })
+    }
+
+    fn sql_row_to_ledger_entry(row: &Row) -> rusqlite::Result<LedgerEntry> {
+        Ok(LedgerEntry {
+            id: row.get(0)?,
+            sub_account_id: row.get(1)?,
+            entry_type: row.get(2)?,
+            amount_sat: row.get(3)?,
+            balance_sat: from_row_to_u64(row, 4)?,
+            payment_id: row.get(5)?,
+            transfer_id: row.get(6)?,
+            description: row.get(7)?,
+            created_at: row.get(8)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::LedgerChange;
+    use crate::model::{LedgerEntryType, SubAccount};
+    use crate::test_utils::persist::create_persister;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn change(
+        sub_account_id: &str,
+        entry_type: LedgerEntryType,
+        amount_sat: i64,
+        payment_id: Option<&str>,
+    ) -> LedgerChange {
+        LedgerChange {
+            sub_account_id: sub_account_id.to_string(),
+            entry_type,
+            amount_sat,
+            payment_id: payment_id.map(ToString::to_string),
+            transfer_id: None,
+            description: None,
+        }
+    }
+
+    #[sdk_macros::test_all]
+    fn test_ledger_changes() -> Result<()> {
+        create_persister!(storage);
+        for id in ["alice", "bob"] {
+            storage.insert_sub_account(&SubAccount {
+                id: id.to_string(),
+                name: id.to_string(),
+                balance_sat: 0,
+                created_at: 0,
+            })?;
+        }
+        assert!(storage.has_sub_accounts()?);
+
+        // Incoming payments are only credited once
+        let receive = change("alice", LedgerEntryType::Receive, 10_000, Some("payment-1"));
+        assert_eq!(storage.apply_ledger_changes(&[receive.clone()])?.len(), 1);
+        assert!(storage.apply_ledger_changes(&[receive])?.is_empty());
+
+        // A transfer exceeding the balance is not recorded at all
+        assert!(storage
+            .apply_ledger_changes(&[
+                change("alice", LedgerEntryType::Transfer, -12_000, None),
+                change("bob", LedgerEntryType::Transfer, 12_000, None),
+            ])
+            .is_err());
+        let entries = storage.apply_ledger_changes(&[
+            change("alice", LedgerEntryType::Transfer, -4_000, None),
+            change("bob", LedgerEntryType::Transfer, 4_000, None),
+        ])?;
+        assert_eq!(entries[0].balance_sat, 6_000);
+        assert_eq!(entries[1].balance_sat, 4_000);
+
+        storage.apply_ledger_changes(&[change(
+            "bob",
+            LedgerEntryType::Send,
+            -3_000,
+            Some("payment-2"),
+        )])?;
+        let sends = storage.list_unrefunded_ledger_sends()?;
+        assert_eq!(sends.len(), 1);
+        storage.apply_ledger_changes(&[change(
+            "bob",
+            LedgerEntryType::Refund,
+            3_000,
+            Some("payment-2"),
+        )])?;
+        assert!(storage.list_unrefunded_ledger_sends()?.is_empty());
+
+        // A pending debit is keyed by its payment, unless the payment was already debited
+        for pending_id in ["pending-1", "pending-2"] {
+            storage.apply_ledger_changes(&[change(
+                "alice",
+                LedgerEntryType::Send,
+                -1_000,
+                Some(pending_id),
+            )])?;
+        }
+        assert!(storage.set_ledger_send_payment_id("pending-1", "payment-3")?);
+        assert!(!storage.set_ledger_send_payment_id("pending-2", "payment-3")?);
+        storage.apply_ledger_changes(&[change(
+            "alice",
+            LedgerEntryType::Refund,
+            1_000,
+            Some("pending-2"),
+        )])?;
+
+        assert_eq!(storage.get_allocated_balance_sat()?, 9_000);
+        assert_eq!(storage.list_ledger_entries(None, None, None)?.len(), 8);
+        let bob_entries = storage.list_ledger_entries(Some("bob"), Some(1), Some(1))?;
+        assert_eq!(bob_entries.len(), 1);
+        assert_eq!(bob_entries[0].entry_type, LedgerEntryType::Send);
+
+        storage.insert_ledger_attribution("address", "alice")?;
+        storage.insert_ledger_attribution("address", "alice")?;
+        assert!(storage.insert_ledger_attribution("address", "bob").is_err());
+        assert_eq!(
+            storage.list_ledger_attributions()?.get("address"),
+            Some(&"alice".to_string())
+        );
+
+        Ok(())
+    }
+}
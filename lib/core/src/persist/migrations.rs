@@ -376,6 +376,102 @@ pub(crate) fn current_migrations(network: LiquidNetwork) -> Vec<&'static str> {
         ",
         "ALTER TABLE chain_swaps ADD COLUMN user_lockup_spent INTEGER NOT NULL DEFAULT 0;",
         "ALTER TABLE payment_details ADD COLUMN settled_at INTEGER;",
+        "
+        CREATE TABLE IF NOT EXISTS block_hashes (
+            chain INTEGER NOT NULL,
+            height INTEGER NOT NULL,
+            hash TEXT NOT NULL,
+            PRIMARY KEY (chain, height)
+        ) STRICT;
+        ",
+        "ALTER TABLE payment_tx_data ADD COLUMN blocks_remaining INTEGER;",
+        "
+        CREATE TABLE IF NOT EXISTS watched_outpoints (
+            outpoint TEXT NOT NULL PRIMARY KEY,
+            tx_id TEXT NOT NULL,
+            payment_id TEXT NOT NULL,
+            created_at INTEGER NOT NULL
+        ) STRICT;
+        ",
+        "ALTER TABLE payment_tx_data ADD COLUMN is_double_spent INTEGER NOT NULL DEFAULT 0;",
+        "
+        ALTER TABLE receive_swaps ADD COLUMN swap_provider TEXT;
+        ALTER TABLE send_swaps ADD COLUMN swap_provider TEXT;
+        ALTER TABLE chain_swaps ADD COLUMN swap_provider TEXT;
+        ",
+        "
+        CREATE TABLE IF NOT EXISTS pegs (
+            id TEXT NOT NULL PRIMARY KEY,
+            direction INTEGER NOT NULL,
+            bitcoin_address TEXT NOT NULL,
+            claim_script TEXT,
+            claim_private_key TEXT,
+            amount_sat INTEGER NOT NULL,
+            bitcoin_tx_id TEXT,
+            bitcoin_vout INTEGER,
+            liquid_tx_id TEXT,
+            description TEXT,
+            state INTEGER NOT NULL,
+            created_at INTEGER NOT NULL
+        ) STRICT;
+        ",
+        "ALTER TABLE asset_metadata ADD COLUMN is_issued INTEGER NOT NULL DEFAULT 0;",
+        "ALTER TABLE asset_metadata ADD COLUMN is_registry INTEGER NOT NULL DEFAULT 0;",
+        "
+        CREATE TABLE IF NOT EXISTS asset_swaps (
+            id TEXT NOT NULL PRIMARY KEY,
+            tx_id TEXT NOT NULL,
+            from_asset TEXT NOT NULL,
+            to_asset TEXT NOT NULL,
+            payer_amount_sat INTEGER NOT NULL,
+            receiver_amount_sat INTEGER NOT NULL,
+            fees_sat INTEGER NOT NULL,
+            exchange_rate REAL NOT NULL,
+            description TEXT,
+            created_at INTEGER NOT NULL
+        ) STRICT;
+        ",
+        "
+        ALTER TABLE asset_swaps ADD COLUMN receive_swap_id TEXT;
+        CREATE TABLE IF NOT EXISTS asset_settlements (
+            swap_id TEXT NOT NULL PRIMARY KEY,
+            asset_id TEXT NOT NULL,
+            min_receiver_amount_sat INTEGER NOT NULL,
+            state INTEGER NOT NULL,
+            created_at INTEGER NOT NULL
+        ) STRICT;
+        ",
+        "
+        CREATE TABLE IF NOT EXISTS sub_accounts (
+            id TEXT NOT NULL PRIMARY KEY,
+            name TEXT NOT NULL,
+            balance_sat INTEGER NOT NULL DEFAULT 0,
+            created_at INTEGER NOT NULL
+        ) STRICT;
+        CREATE TABLE IF NOT EXISTS ledger_attributions (
+            destination TEXT NOT NULL PRIMARY KEY,
+            sub_account_id TEXT NOT NULL REFERENCES sub_accounts(id),
+            created_at INTEGER NOT NULL
+        ) STRICT;
+        CREATE TABLE IF NOT EXISTS ledger_entries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            sub_account_id TEXT NOT NULL REFERENCES sub_accounts(id),
+            entry_type INTEGER NOT NULL,
+            amount_sat INTEGER NOT NULL,
+            balance_sat INTEGER NOT NULL,
+            payment_id TEXT,
+            transfer_id TEXT,
+            description TEXT,
+            created_at INTEGER NOT NULL
+        ) STRICT;
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_ledger_entries_payment
+            ON ledger_entries(payment_id, entry_type) WHERE payment_id IS NOT NULL;
+        ",
+        "ALTER TABLE watched_outpoints ADD COLUMN conflicting_tx_id TEXT;",
+        "
+        ALTER TABLE pegs DROP COLUMN claim_private_key;
+        ALTER TABLE pegs ADD COLUMN key_index INTEGER;
+        ",
     ]
 }
 
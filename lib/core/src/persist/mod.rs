@@ -1,15 +1,20 @@
 mod address;
 pub(crate) mod asset_metadata;
+mod asset_swap;
 mod backup;
+mod block;
 pub(crate) mod bolt12_offer;
 pub(crate) mod cache;
 pub(crate) mod chain;
+pub(crate) mod ledger;
 mod migrations;
 pub(crate) mod model;
+mod peg;
 pub(crate) mod receive;
 pub(crate) mod send;
 pub(crate) mod sync;
 pub(crate) mod wallet_updates;
+mod watched_outpoint;
 
 use std::collections::{HashMap, HashSet};
 use std::ops::Not;
@@ -40,6 +45,8 @@ pub struct Persister {
     main_db_dir: PathBuf,
     network: LiquidNetwork,
     pub(crate) sync_trigger: Option<Sender<()>>,
+    #[cfg(any(test, feature = "test-utils"))]
+    faults: std::sync::Mutex<std::sync::Arc<crate::test_utils::faults::FaultInjector>>,
 }
 
 /// Builds a WHERE clause that checks if `state` is any of the given arguments
@@ -131,6 +138,8 @@ impl Persister {
             main_db_dir,
             network,
             sync_trigger,
+            #[cfg(any(test, feature = "test-utils"))]
+            faults: Default::default(),
         };
 
         if let Some(backup_con) = backup_con {
@@ -168,7 +177,32 @@ impl Persister {
         Ok(())
     }
 
+    /// Makes the database accesses subject to the faults injected for
+    /// [FaultTarget::Persister](crate::test_utils::faults::FaultTarget::Persister)
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn set_fault_injector(
+        &self,
+        faults: std::sync::Arc<crate::test_utils::faults::FaultInjector>,
+    ) {
+        *self.faults.lock().unwrap() = faults;
+    }
+
+    /// Applies the fault injected for the named persister operation, if any
+    #[cfg(any(test, feature = "test-utils"))]
+    fn check_fault(&self, method: &str) -> Result<()> {
+        self.faults
+            .lock()
+            .unwrap()
+            .check_sync(crate::test_utils::faults::FaultTarget::Persister, method)
+    }
+
+    #[cfg(not(any(test, feature = "test-utils")))]
+    fn check_fault(&self, _method: &str) -> Result<()> {
+        Ok(())
+    }
+
     pub(crate) fn get_connection(&self) -> Result<Connection> {
+        self.check_fault("get_connection")?;
         Ok(Connection::open(self.get_db_path())?)
     }
 
@@ -214,9 +248,16 @@ impl Persister {
         }
     }
 
-    pub(crate) fn insert_or_update_payment_with_wallet_tx(&self, tx: &WalletTx) -> Result<()> {
+    /// Persists the payment of a wallet tx. For incoming txs, this also persists how many blocks
+    /// are left until they reach the confirmations required by the `confirmation_policy` at the
+    /// `liquid_tip`.
+    pub(crate) fn insert_or_update_payment_with_wallet_tx(
+        &self,
+        tx: &WalletTx,
+        liquid_tip: u32,
+        confirmation_policy: &ConfirmationPolicy,
+    ) -> Result<()> {
         let tx_id = tx.txid.to_string();
-        let is_tx_confirmed = tx.height.is_some();
 
         let mut tx_balances: HashMap<AssetId, i64> = HashMap::new();
         for input in &tx.inputs {
@@ -266,6 +307,26 @@ impl Persister {
             })
             .collect();
 
+        // The policy tiers are in sats, so only the received L-BTC amount selects a tier. Txs
+        // only receiving other assets need the confirmations of the base policy.
+        let is_incoming = payment_balances
+            .iter()
+            .any(|b| b.payment_type == PaymentType::Receive);
+        let lbtc_received_sat = payment_balances
+            .iter()
+            .find(|b| {
+                b.payment_type == PaymentType::Receive && b.asset_id == lbtc_asset_id.to_string()
+            })
+            .map_or(0, |b| b.amount);
+        let blocks_remaining = is_incoming.then(|| {
+            confirmation_policy.blocks_remaining(
+                Blockchain::Liquid,
+                lbtc_received_sat,
+                liquid_tip,
+                tx.height,
+            )
+        });
+
         let maybe_address = tx
             .outputs
             .iter()
@@ -285,8 +346,9 @@ impl Persister {
                 tx_id: tx_id.clone(),
                 timestamp: tx.timestamp,
                 fees_sat: tx.fee,
-                is_confirmed: is_tx_confirmed,
+                is_confirmed: tx.height.is_some(),
                 unblinding_data: Some(unblinding_data),
+                blocks_remaining,
             },
             &payment_balances,
             maybe_address.map(|destination| PaymentTxDetails {
@@ -305,9 +367,10 @@ impl Persister {
                         timestamp,
                         fees_sat,
                         is_confirmed,
-                        unblinding_data
+                        unblinding_data,
+                        blocks_remaining
             FROM payment_tx_data
-            WHERE is_confirmed = 0",
+            WHERE is_confirmed = 0 AND is_double_spent = 0",
         )?;
         let payments: Vec<PaymentTxData> = stmt
             .query_map([], |row| {
@@ -317,6 +380,7 @@ impl Persister {
                     fees_sat: from_row_to_u64(row, 2)?,
                     is_confirmed: row.get(3)?,
                     unblinding_data: row.get(4)?,
+                    blocks_remaining: row.get(5)?,
                 })
             })?
             .map(|i| i.unwrap())
@@ -324,6 +388,30 @@ impl Persister {
         Ok(payments)
     }
 
+    pub(crate) fn list_confirmed_payment_tx_ids(&self) -> Result<Vec<String>> {
+        let con = self.get_connection()?;
+        let mut stmt = con.prepare("SELECT tx_id FROM payment_tx_data WHERE is_confirmed = 1")?;
+        let tx_ids: Vec<String> = stmt
+            .query_map([], |row| row.get(0))?
+            .map(|i| i.unwrap())
+            .collect();
+        Ok(tx_ids)
+    }
+
+    /// Marks the given payment txs as unconfirmed, e.g. after their block was reorged out
+    pub(crate) fn mark_payment_txs_unconfirmed(&self, tx_ids: &[String]) -> Result<()> {
+        let mut con = self.get_connection()?;
+        let tx = con.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        for tx_id in tx_ids {
+            tx.execute(
+                "UPDATE payment_tx_data SET is_confirmed = 0 WHERE tx_id = ?",
+                [tx_id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
     fn insert_or_update_payment_balance(
         con: &Connection,
         tx_id: &str,
@@ -347,6 +435,32 @@ impl Persister {
         Ok(())
     }
 
+    /// Inserts an unconfirmed payment for a tx the SDK just broadcast.
+    ///
+    /// LWK may take a while to pick up a new mempool tx, so inserting it right away makes the
+    /// tx known to the SDK (get_info, list_payments) instantly.
+    pub(crate) fn insert_broadcast_tx(
+        &self,
+        tx_id: &str,
+        fees_sat: u64,
+        balances: &[PaymentTxBalance],
+        payment_tx_details: Option<PaymentTxDetails>,
+    ) -> Result<()> {
+        self.insert_or_update_payment(
+            PaymentTxData {
+                tx_id: tx_id.to_string(),
+                timestamp: Some(utils::now()),
+                fees_sat,
+                is_confirmed: false,
+                unblinding_data: None,
+                blocks_remaining: None,
+            },
+            balances,
+            payment_tx_details,
+            false,
+        )
+    }
+
     pub(crate) fn insert_or_update_payment(
         &self,
         ptx: PaymentTxData,
@@ -354,6 +468,7 @@ impl Persister {
         payment_tx_details: Option<PaymentTxDetails>,
         from_wallet_tx_data: bool,
     ) -> Result<()> {
+        self.check_fault("insert_or_update_payment")?;
         let mut con = self.get_connection()?;
         let tx = con.transaction_with_behavior(TransactionBehavior::Immediate)?;
         tx.execute(
@@ -362,14 +477,16 @@ impl Persister {
            timestamp,
            fees_sat,
            is_confirmed,
-           unblinding_data
+           unblinding_data,
+           blocks_remaining
         )
-        VALUES (?, ?, ?, ?, ?)
+        VALUES (?, ?, ?, ?, ?, ?)
         ON CONFLICT (tx_id)
         DO UPDATE SET timestamp = CASE WHEN excluded.is_confirmed = 1 THEN excluded.timestamp ELSE timestamp END,
                       fees_sat = excluded.fees_sat,
                       is_confirmed = excluded.is_confirmed,
-                      unblinding_data = excluded.unblinding_data
+                      unblinding_data = excluded.unblinding_data,
+                      blocks_remaining = COALESCE(excluded.blocks_remaining, blocks_remaining)
         ",
             (
                 &ptx.tx_id,
@@ -377,6 +494,7 @@ impl Persister {
                 from_u64_to_row(ptx.fees_sat)?,
                 ptx.is_confirmed,
                 ptx.unblinding_data,
+                ptx.blocks_remaining,
             ),
         )?;
 
@@ -564,18 +682,20 @@ impl Persister {
         sort_ascending: Option<bool>,
         include_all_states: Option<bool>,
     ) -> String {
-        let (where_receive_swap_clause, where_chain_swap_clause) = if include_all_states
-            .unwrap_or_default()
-        {
-            ("true", "true")
-        } else {
-            (
-                // Receive Swap has a tx id and state not in Created, Failed, TimedOut
-                "COALESCE(claim_tx_id, lockup_tx_id, mrh_tx_id) IS NOT NULL AND state NOT IN (0, 3, 4)",
-                // Chain Swap has a tx id and state not in Created, TimedOut
-                "COALESCE(user_lockup_tx_id, claim_tx_id) IS NOT NULL AND state NOT IN (0, 4)",
-            )
-        };
+        let (where_receive_swap_clause, where_chain_swap_clause, where_peg_clause) =
+            if include_all_states.unwrap_or_default() {
+                ("true", "true", "true")
+            } else {
+                (
+                    // Receive Swap has a tx id and state not in Created, Failed, TimedOut
+                    "COALESCE(claim_tx_id, lockup_tx_id, mrh_tx_id) IS NOT NULL AND state NOT IN (0, 3, 4)",
+                    // Chain Swap has a tx id and state not in Created, TimedOut
+                    "COALESCE(user_lockup_tx_id, claim_tx_id) IS NOT NULL AND state NOT IN (0, 4)",
+                    // Peg has a Bitcoin or Liquid tx id
+                    "COALESCE(bitcoin_tx_id, liquid_tx_id) IS NOT NULL",
+                )
+            };
+        let lbtc_asset_id = utils::lbtc_asset_id(self.network);
 
         format!(
             "
@@ -646,10 +766,36 @@ impl Persister {
                 pd.settled_at,
                 am.name,
                 am.ticker,
-                am.precision
+                am.precision,
+                ptx.blocks_remaining,
+                ptx.is_double_spent,
+                pg.id,
+                pg.direction,
+                pg.bitcoin_address,
+                pg.description,
+                pg.bitcoin_tx_id,
+                pg.amount_sat,
+                pg.state,
+                pg.created_at,
+                asw.id,
+                asw.to_asset,
+                asw.payer_amount_sat,
+                asw.receiver_amount_sat,
+                asw.description,
+                tam.name,
+                tam.ticker,
+                tam.precision,
+                asw.receive_swap_id,
+                sas.id
             FROM payment_tx_data AS ptx          -- Payment tx (each tx results in a Payment)
+            LEFT JOIN asset_swaps AS asw         -- Asset Swap data
+                ON asw.tx_id = ptx.tx_id
             LEFT JOIN payment_balance AS pb
                 ON pb.tx_id = ptx.tx_id          -- Payment tx balances, split by asset
+                AND (asw.id IS NULL OR pb.asset_id = asw.from_asset) -- Asset Swaps result in a single Payment
+                AND (pb.asset_id = '{lbtc_asset_id}'  -- Swap lockups paying fees in an asset result in a single Payment
+                    OR ptx.tx_id NOT IN (SELECT lockup_tx_id FROM send_swaps WHERE lockup_tx_id NOT NULL)
+                    AND ptx.tx_id NOT IN (SELECT user_lockup_tx_id FROM chain_swaps WHERE user_lockup_tx_id NOT NULL))
             FULL JOIN (
                 SELECT * FROM receive_swaps WHERE {}
             ) rs                                 -- Receive Swap data
@@ -658,6 +804,12 @@ impl Persister {
                 SELECT * FROM chain_swaps WHERE {}
             ) cs                                 -- Chain Swap data
                 ON ptx.tx_id in (cs.user_lockup_tx_id, cs.claim_tx_id)
+            FULL JOIN (
+                SELECT * FROM pegs WHERE {}
+            ) pg                                 -- Peg data
+                ON ptx.tx_id = pg.liquid_tx_id
+            LEFT JOIN asset_swaps AS sas         -- Asset Swap settling a Receive Swap
+                ON sas.receive_swap_id = rs.id
             LEFT JOIN send_swaps AS ss           -- Send Swap data
                 ON ptx.tx_id = ss.lockup_tx_id
             LEFT JOIN payment_balance AS rb      -- Refund tx balance
@@ -666,18 +818,21 @@ impl Persister {
                 ON pd.tx_id = ptx.tx_id
             LEFT JOIN asset_metadata AS am       -- Asset metadata
                 ON am.asset_id = pb.asset_id
+            LEFT JOIN asset_metadata AS tam      -- Asset Swap received asset metadata
+                ON tam.asset_id = asw.to_asset
             WHERE
                 (ptx.tx_id IS NULL               -- Filter out refund txs from Chain/Send Swaps
                     OR ptx.tx_id NOT IN (SELECT refund_tx_id FROM send_swaps WHERE refund_tx_id NOT NULL)
                     AND ptx.tx_id NOT IN (SELECT refund_tx_id FROM chain_swaps WHERE refund_tx_id NOT NULL))
             AND {}
             ORDER BY                             -- Order by swap creation time or tx timestamp (in case of direct tx)
-                COALESCE(rs.created_at, ss.created_at, cs.created_at, ptx.timestamp) {}
+                COALESCE(rs.created_at, ss.created_at, cs.created_at, pg.created_at, asw.created_at, ptx.timestamp) {}
             LIMIT {}
             OFFSET {}
             ",
             where_receive_swap_clause,
             where_chain_swap_clause,
+            where_peg_clause,
             where_clause.unwrap_or("true"),
             match sort_ascending.unwrap_or(false) {
                 true => "ASC",
@@ -698,6 +853,7 @@ impl Persister {
                     fees_sat: from_row_to_u64(row, 2)?,
                     is_confirmed: row.get(3)?,
                     unblinding_data: row.get(4)?,
+                    blocks_remaining: row.get(67)?,
                 },
                 PaymentTxBalance {
                     amount: from_row_to_u64(row, 5)?,
@@ -781,6 +937,29 @@ impl Persister {
         let maybe_asset_metadata_ticker: Option<String> = row.get(65)?;
         let maybe_asset_metadata_precision: Option<u8> = row.get(66)?;
 
+        let is_double_spent = row.get::<usize, Option<bool>>(68)?.unwrap_or(false);
+
+        let maybe_peg_id: Option<String> = row.get(69)?;
+        let maybe_peg_direction: Option<Direction> = row.get(70)?;
+        let maybe_peg_bitcoin_address: Option<String> = row.get(71)?;
+        let maybe_peg_description: Option<String> = row.get(72)?;
+        let maybe_peg_bitcoin_tx_id: Option<String> = row.get(73)?;
+        let maybe_peg_amount_sat = from_row_to_optional_u64(row, 74)?;
+        let maybe_peg_state: Option<PaymentState> = row.get(75)?;
+        let maybe_peg_created_at: Option<u32> = row.get(76)?;
+
+        let maybe_asset_swap_id: Option<String> = row.get(77)?;
+        let maybe_asset_swap_to_asset: Option<String> = row.get(78)?;
+        let maybe_asset_swap_payer_amount_sat = from_row_to_optional_u64(row, 79)?;
+        let maybe_asset_swap_receiver_amount_sat = from_row_to_optional_u64(row, 80)?;
+        let maybe_asset_swap_description: Option<String> = row.get(81)?;
+        let maybe_to_asset_metadata_name: Option<String> = row.get(82)?;
+        let maybe_to_asset_metadata_ticker: Option<String> = row.get(83)?;
+        let maybe_to_asset_metadata_precision: Option<u8> = row.get(84)?;
+        let maybe_asset_swap_receive_swap_id: Option<String> = row.get(85)?;
+
+        let maybe_settlement_asset_swap_id: Option<String> = row.get(86)?;
+
         let bitcoin_address = match maybe_chain_swap_direction {
             Some(Direction::Incoming) => maybe_chain_swap_lockup_address,
             Some(Direction::Outgoing) => maybe_chain_swap_claim_address,
@@ -973,6 +1152,7 @@ impl Persister {
                     .unwrap_or(description.unwrap_or("Lightning transfer".to_string())),
                 liquid_expiration_blockheight: expiration_blockheight,
                 settled_at: maybe_payment_details_settled_at,
+                asset_swap_id: maybe_settlement_asset_swap_id,
             },
             Some(PaymentSwapData {
                 swap_type: PaymentSwapType::Chain,
@@ -1009,6 +1189,77 @@ impl Persister {
                     auto_accepted_fees,
                 }
             }
+            None if maybe_peg_id.is_some() => {
+                let direction = maybe_peg_direction.unwrap_or(Direction::Incoming);
+                PaymentDetails::Peg {
+                    peg_id: maybe_peg_id.clone().unwrap_or_default(),
+                    bitcoin_address: maybe_peg_bitcoin_address.clone().unwrap_or_default(),
+                    description: maybe_peg_description.unwrap_or_else(|| match direction {
+                        Direction::Incoming => "Bitcoin peg-in".to_string(),
+                        Direction::Outgoing => "Bitcoin peg-out".to_string(),
+                    }),
+                    bitcoin_tx_id: maybe_peg_bitcoin_tx_id,
+                    claim_tx_id: match direction {
+                        Direction::Incoming => maybe_tx_tx_id.as_ref().ok().cloned(),
+                        Direction::Outgoing => None,
+                    },
+                }
+            }
+            None if maybe_asset_swap_id.is_some() => {
+                let from_asset_id = tx_with_balance
+                    .as_ref()
+                    .map_or(utils::lbtc_asset_id(self.network).to_string(), |(_, b)| {
+                        b.asset_id.clone()
+                    });
+                let to_asset_id = maybe_asset_swap_to_asset.unwrap_or_default();
+                let payer_amount_sat = maybe_asset_swap_payer_amount_sat.unwrap_or(0);
+                let receiver_amount_sat = maybe_asset_swap_receiver_amount_sat.unwrap_or(0);
+                let asset_info =
+                    |asset_id: &str, name: Option<String>, ticker, precision, amount_sat| match (
+                        name, ticker, precision,
+                    ) {
+                        (Some(name), Some(ticker), Some(precision)) => {
+                            let asset_metadata = AssetMetadata {
+                                asset_id: asset_id.to_string(),
+                                name: name.clone(),
+                                ticker: ticker.clone(),
+                                precision,
+                                fiat_id: None,
+                            };
+                            Some(AssetInfo {
+                                name,
+                                ticker,
+                                amount: asset_metadata.amount_from_sat(amount_sat),
+                                fees: None,
+                            })
+                        }
+                        _ => None,
+                    };
+
+                PaymentDetails::AssetSwap {
+                    swap_id: maybe_asset_swap_id.clone().unwrap_or_default(),
+                    description: maybe_asset_swap_description.unwrap_or("Asset swap".to_string()),
+                    from_asset_info: asset_info(
+                        &from_asset_id,
+                        maybe_asset_metadata_name,
+                        maybe_asset_metadata_ticker,
+                        maybe_asset_metadata_precision,
+                        payer_amount_sat,
+                    ),
+                    to_asset_info: asset_info(
+                        &to_asset_id,
+                        maybe_to_asset_metadata_name,
+                        maybe_to_asset_metadata_ticker,
+                        maybe_to_asset_metadata_precision,
+                        receiver_amount_sat,
+                    ),
+                    from_asset_id,
+                    to_asset_id,
+                    payer_amount_sat,
+                    receiver_amount_sat,
+                    receive_swap_id: maybe_asset_swap_receive_swap_id,
+                }
+            }
             _ => {
                 let (amount, asset_id) = tx_with_balance.clone().map_or(
                     (0, utils::lbtc_asset_id(self.network).to_string()),
@@ -1060,21 +1311,38 @@ impl Persister {
         };
 
         match (tx_with_balance, swap.clone()) {
+            // A peg-in whose deposit was detected but not yet claimed
+            (None, None) if maybe_peg_id.is_some() => Ok(Payment {
+                destination: maybe_peg_bitcoin_address,
+                tx_id: None,
+                unblinding_data: None,
+                timestamp: maybe_peg_created_at.unwrap_or(utils::now()),
+                amount_sat: maybe_peg_amount_sat.unwrap_or(0),
+                fees_sat: 0,
+                swapper_fees_sat: None,
+                payment_type: maybe_peg_direction.unwrap_or(Direction::Incoming).into(),
+                status: maybe_peg_state.unwrap_or(PaymentState::Created),
+                blocks_remaining: None,
+                details: payment_details,
+            }),
             (None, None) => Err(maybe_tx_tx_id.err().unwrap()),
             (None, Some(swap)) => Ok(Payment::from_pending_swap(
                 swap,
                 payment_type,
                 payment_details,
             )),
-            (Some((tx, balance)), None) => {
-                Ok(Payment::from_tx_data(tx, balance, None, payment_details))
+            (Some((tx, balance)), swap) => {
+                let is_direct_payment = swap.is_none();
+                let blocks_remaining = tx.blocks_remaining.filter(|b| *b > 0);
+                let mut payment = Payment::from_tx_data(tx, balance, swap, payment_details);
+                if is_direct_payment && is_double_spent {
+                    payment.status = PaymentState::Failed;
+                }
+                if payment.status == PaymentState::Pending {
+                    payment.blocks_remaining = blocks_remaining;
+                }
+                Ok(payment)
             }
-            (Some((tx, balance)), Some(swap)) => Ok(Payment::from_tx_data(
-                tx,
-                balance,
-                Some(swap),
-                payment_details,
-            )),
         }
     }
 
@@ -1083,7 +1351,7 @@ impl Persister {
             .get_connection()?
             .query_row(
                 &self.select_payment_query(
-                    Some("(ptx.tx_id = ?1 OR COALESCE(rs.id, ss.id, cs.id) = ?1)"),
+                    Some("(ptx.tx_id = ?1 OR COALESCE(rs.id, ss.id, cs.id, pg.id, asw.id) = ?1)"),
                     None,
                     None,
                     None,
@@ -1221,8 +1489,8 @@ fn filter_to_where_clause(req: &ListPaymentsRequest) -> (String, Vec<Box<dyn ToS
                 .collect::<Vec<_>>()
                 .join(", ");
             let states_query = match tx_comfirmed_param.is_empty() {
-                true => format!("COALESCE(rs.state, ss.state, cs.state) in ({states_param})"),
-                false => format!("(COALESCE(rs.id, ss.id, cs.id) IS NULL AND ptx.is_confirmed in ({tx_comfirmed_param}) OR COALESCE(rs.state, ss.state, cs.state) in ({states_param}))"),
+                true => format!("COALESCE(rs.state, ss.state, cs.state, pg.state) in ({states_param})"),
+                false => format!("(COALESCE(rs.id, ss.id, cs.id, pg.id) IS NULL AND ptx.is_confirmed in ({tx_comfirmed_param}) OR COALESCE(rs.state, ss.state, cs.state, pg.state) in ({states_param}))"),
             };
             where_clause.push(states_query);
         }
@@ -1246,7 +1514,8 @@ fn filter_to_where_clause(req: &ListPaymentsRequest) -> (String, Vec<Box<dyn ToS
                 asset_id,
                 destination,
             } => {
-                where_clause.push("COALESCE(rs.id, ss.id, cs.id) IS NULL".to_string());
+                where_clause
+                    .push("COALESCE(rs.id, ss.id, cs.id, pg.id, asw.id) IS NULL".to_string());
                 if let Some(asset_id) = asset_id {
                     where_clause.push("pb.asset_id = ?".to_string());
                     where_params.push(Box::new(asset_id));
@@ -1264,15 +1533,27 @@ fn filter_to_where_clause(req: &ListPaymentsRequest) -> (String, Vec<Box<dyn ToS
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+    use std::str::FromStr;
+
     use anyhow::Result;
+    use lwk_wollet::{
+        elements::{
+            confidential::{AssetBlindingFactor, ValueBlindingFactor},
+            hashes::Hash as _,
+            Address, AssetId, LockTime, OutPoint, Script, Transaction, TxOutSecrets, Txid,
+        },
+        Chain, WalletTx, WalletTxOut,
+    };
 
     use crate::{
-        model::LiquidNetwork,
+        model::{ConfirmationPolicy, ConfirmationTier, LiquidNetwork},
         persist::PaymentTxDetails,
         prelude::ListPaymentsRequest,
         test_utils::persist::{
             create_persister, new_payment_tx_data, new_receive_swap, new_send_swap,
         },
+        utils,
     };
 
     use super::{PaymentState, PaymentType};
@@ -1306,6 +1587,92 @@ mod tests {
         Ok(())
     }
 
+    fn wallet_tx_receiving(height: u32, received: Vec<(AssetId, u64)>) -> WalletTx {
+        let txid = Txid::from_byte_array([1; 32]);
+        let outputs = received
+            .into_iter()
+            .enumerate()
+            .map(|(vout, (asset, value))| {
+                Some(WalletTxOut {
+                    outpoint: OutPoint::new(txid, vout as u32),
+                    script_pubkey: Script::new(),
+                    height: Some(height),
+                    unblinded: TxOutSecrets {
+                        asset,
+                        value,
+                        asset_bf: AssetBlindingFactor::zero(),
+                        value_bf: ValueBlindingFactor::zero(),
+                    },
+                    wildcard_index: 0,
+                    ext_int: Chain::External,
+                    is_spent: false,
+                    address: Address::from_str("lq1pqw8ct25kd47dejyesyvk3g2kaf8s9uhq4se7r2kj9y9hhvu9ug5thxlpn9y63s78kc2mcp6nujavckvr42q7hwkhqq9hfz46nth22hfp3em0ulm4nsuf").unwrap(),
+                })
+            })
+            .collect();
+        WalletTx {
+            txid,
+            tx: Transaction {
+                version: 2,
+                lock_time: LockTime::ZERO,
+                input: vec![],
+                output: vec![],
+            },
+            height: Some(height),
+            fee: 0,
+            timestamp: Some(utils::now()),
+            balance: BTreeMap::new(),
+            outputs,
+            inputs: vec![],
+            type_: "".to_string(),
+        }
+    }
+
+    #[sdk_macros::test_all]
+    fn test_wallet_tx_blocks_remaining() -> Result<()> {
+        create_persister!(storage);
+
+        let policy = ConfirmationPolicy {
+            liquid_confirmations: 2,
+            bitcoin_confirmations: 1,
+            tiers: vec![ConfirmationTier {
+                min_amount_sat: 1_000_000,
+                liquid_confirmations: 10,
+                bitcoin_confirmations: 3,
+            }],
+        };
+        // The large asset amount does not select the L-BTC tier
+        let lbtc_asset_id = utils::lbtc_asset_id(LiquidNetwork::Regtest);
+        let asset_id = AssetId::from_slice(&[2; 32])?;
+        let tx = wallet_tx_receiving(100, vec![(lbtc_asset_id, 2_000), (asset_id, 5_000_000)]);
+        let tx_id = tx.txid.to_string();
+
+        storage.insert_or_update_payment_with_wallet_tx(&tx, 100, &policy)?;
+        let payment = storage.get_payment(&tx_id)?.unwrap();
+        assert_eq!(payment.status, PaymentState::Pending);
+        assert_eq!(payment.blocks_remaining, Some(1));
+        // The tx itself is confirmed, even if the policy is not satisfied yet
+        assert!(storage.list_unconfirmed_payment_txs_data()?.is_empty());
+
+        // An update without policy data keeps the remaining blocks
+        let (mut tx_data, balance) =
+            new_payment_tx_data(LiquidNetwork::Regtest, PaymentType::Receive);
+        tx_data.tx_id = tx_id.clone();
+        tx_data.is_confirmed = true;
+        storage.insert_or_update_payment(tx_data, &[balance], None, false)?;
+        assert_eq!(
+            storage.get_payment(&tx_id)?.unwrap().blocks_remaining,
+            Some(1)
+        );
+
+        storage.insert_or_update_payment_with_wallet_tx(&tx, 101, &policy)?;
+        let payment = storage.get_payment(&tx_id)?.unwrap();
+        assert_eq!(payment.status, PaymentState::Complete);
+        assert_eq!(payment.blocks_remaining, None);
+
+        Ok(())
+    }
+
     #[sdk_macros::test_all]
     fn test_list_ongoing_swaps() -> Result<()> {
         create_persister!(storage);
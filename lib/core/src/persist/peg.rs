@@ -0,0 +1,238 @@
+use anyhow::Result;
+use rusqlite::{named_params, Connection, OptionalExtension, Row, TransactionBehavior};
+
+use super::Persister;
+use crate::model::{Direction, PaymentState, Peg};
+use crate::sync::model::RecordType;
+use crate::utils::{from_row_to_u64, from_u64_to_row};
+
+impl Persister {
+    pub(crate) fn insert_or_update_peg_inner(con: &Connection, peg: &Peg) -> Result<()> {
+        con.execute(
+            "INSERT INTO pegs (
+                id,
+                direction,
+                bitcoin_address,
+                claim_script,
+                key_index,
+                amount_sat,
+                bitcoin_tx_id,
+                bitcoin_vout,
+                liquid_tx_id,
+                description,
+                state,
+                created_at
+            )
+            VALUES (
+                :id,
+                :direction,
+                :bitcoin_address,
+                :claim_script,
+                :key_index,
+                :amount_sat,
+                :bitcoin_tx_id,
+                :bitcoin_vout,
+                :liquid_tx_id,
+                :description,
+                :state,
+                :created_at
+            )
+            ON CONFLICT(id) DO UPDATE SET
+                amount_sat = excluded.amount_sat,
+                bitcoin_tx_id = excluded.bitcoin_tx_id,
+                bitcoin_vout = excluded.bitcoin_vout,
+                liquid_tx_id = excluded.liquid_tx_id,
+                state = excluded.state",
+            named_params! {
+                ":id": &peg.id,
+                ":direction": &peg.direction,
+                ":bitcoin_address": &peg.bitcoin_address,
+                ":claim_script": &peg.claim_script,
+                ":key_index": &peg.key_index,
+                ":amount_sat": from_u64_to_row(peg.amount_sat)?,
+                ":bitcoin_tx_id": &peg.bitcoin_tx_id,
+                ":bitcoin_vout": &peg.bitcoin_vout,
+                ":liquid_tx_id": &peg.liquid_tx_id,
+                ":description": &peg.description,
+                ":state": &peg.state,
+                ":created_at": &peg.created_at,
+            },
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn insert_or_update_peg(&self, peg: &Peg) -> Result<()> {
+        let is_new = self.fetch_peg_by_id(&peg.id)?.is_none();
+
+        let mut con = self.get_connection()?;
+        let tx = con.transaction_with_behavior(TransactionBehavior::Immediate)?;
+
+        Self::insert_or_update_peg_inner(&tx, peg)?;
+
+        // Only the creation data of a peg is synced, the rest is recovered from the chains
+        match is_new {
+            true => {
+                self.commit_outgoing(&tx, &peg.id, RecordType::Peg, None)?;
+                tx.commit()?;
+                self.trigger_sync();
+            }
+            false => {
+                tx.commit()?;
+            }
+        };
+
+        Ok(())
+    }
+
+    /// The claim key index of the next peg-in, following the highest one in use
+    pub(crate) fn next_peg_in_key_index(&self) -> Result<u32> {
+        let con = self.get_connection()?;
+        let max_key_index: Option<u32> =
+            con.query_row("SELECT MAX(key_index) FROM pegs", [], |row| row.get(0))?;
+        Ok(max_key_index.map_or(0, |key_index| key_index + 1))
+    }
+
+    pub(crate) fn fetch_peg_by_id(&self, id: &str) -> Result<Option<Peg>> {
+        let con = self.get_connection()?;
+        Ok(con
+            .query_row(
+                &Self::list_pegs_query("id = ?1"),
+                [id],
+                Self::sql_row_to_peg,
+            )
+            .optional()?)
+    }
+
+    /// Lists the incoming pegs whose deposit has not been claimed yet
+    pub(crate) fn list_unclaimed_peg_ins(&self) -> Result<Vec<Peg>> {
+        let con = self.get_connection()?;
+        let mut stmt = con.prepare(&Self::list_pegs_query(
+            "direction = ?1 AND liquid_tx_id IS NULL AND state IN (?2, ?3)",
+        ))?;
+        let pegs = stmt
+            .query_map(
+                (
+                    Direction::Incoming,
+                    PaymentState::Created,
+                    PaymentState::Pending,
+                ),
+                Self::sql_row_to_peg,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(pegs)
+    }
+
+    /// Lists the pegs that are not yet settled
+    pub(crate) fn list_ongoing_pegs(&self) -> Result<Vec<Peg>> {
+        let con = self.get_connection()?;
+        let mut stmt = con.prepare(&Self::list_pegs_query("state IN (?1, ?2)"))?;
+        let pegs = stmt
+            .query_map(
+                (PaymentState::Created, PaymentState::Pending),
+                Self::sql_row_to_peg,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(pegs)
+    }
+
+    /// Lists the pegs with a broadcast Liquid tx that is not yet settled
+    pub(crate) fn list_pending_pegs_with_liquid_tx(&self) -> Result<Vec<Peg>> {
+        let con = self.get_connection()?;
+        let mut stmt = con.prepare(&Self::list_pegs_query(
+            "liquid_tx_id IS NOT NULL AND state = ?1",
+        ))?;
+        let pegs = stmt
+            .query_map([PaymentState::Pending], Self::sql_row_to_peg)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(pegs)
+    }
+
+    fn list_pegs_query(where_clause: &str) -> String {
+        format!(
+            "SELECT
+                id,
+                direction,
+                bitcoin_address,
+                claim_script,
+                key_index,
+                amount_sat,
+                bitcoin_tx_id,
+                bitcoin_vout,
+                liquid_tx_id,
+                description,
+                state,
+                created_at
+            FROM pegs
+            WHERE {where_clause}
+            ORDER BY created_at"
+        )
+    }
+
+    fn sql_row_to_peg(row: &Row) -> rusqlite::Result<Peg> {
+        Ok(Peg {
+            id: row.get(0)?,
+            direction: row.get(1)?,
+            bitcoin_address: row.get(2)?,
+            claim_script: row.get(3)?,
+            key_index: row.get(4)?,
+            amount_sat: from_row_to_u64(row, 5)?,
+            bitcoin_tx_id: row.get(6)?,
+            bitcoin_vout: row.get(7)?,
+            liquid_tx_id: row.get(8)?,
+            description: row.get(9)?,
+            state: row.get(10)?,
+            created_at: row.get(11)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{anyhow, Result};
+
+    use crate::{
+        model::{Direction, PaymentState, Peg},
+        test_utils::persist::create_persister,
+    };
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[sdk_macros::test_all]
+    fn test_insert_and_update_peg() -> Result<()> {
+        create_persister!(storage);
+
+        let mut peg = Peg {
+            id: "peg-in".to_string(),
+            direction: Direction::Incoming,
+            bitcoin_address: "bcrt1qaddress".to_string(),
+            claim_script: Some("0014".to_string()),
+            key_index: Some(0),
+            amount_sat: 0,
+            bitcoin_tx_id: None,
+            bitcoin_vout: None,
+            liquid_tx_id: None,
+            description: None,
+            state: PaymentState::Created,
+            created_at: 0,
+        };
+        storage.insert_or_update_peg(&peg)?;
+        assert_eq!(storage.list_unclaimed_peg_ins()?, vec![peg.clone()]);
+        assert_eq!(storage.next_peg_in_key_index()?, 1);
+
+        peg.amount_sat = 100_000;
+        peg.bitcoin_tx_id = Some("btc-tx".to_string());
+        peg.bitcoin_vout = Some(1);
+        peg.liquid_tx_id = Some("claim-tx".to_string());
+        peg.state = PaymentState::Pending;
+        storage.insert_or_update_peg(&peg)?;
+
+        let fetched = storage
+            .fetch_peg_by_id(&peg.id)?
+            .ok_or(anyhow!("Peg not found"))?;
+        assert_eq!(fetched, peg);
+        assert!(storage.list_unclaimed_peg_ins()?.is_empty());
+        assert_eq!(storage.list_pending_pegs_with_liquid_tx()?, vec![peg]);
+        Ok(())
+    }
+}
@@ -36,9 +36,10 @@ impl Persister {
                 claim_fees_sat,
                 mrh_address,
                 state,
-                pair_fees_json
+                pair_fees_json,
+                swap_provider
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT DO NOTHING
             ",
             (
@@ -58,6 +59,7 @@ impl Persister {
                 &receive_swap.mrh_address,
                 &receive_swap.state,
                 &receive_swap.pair_fees_json,
+                &receive_swap.swap_provider,
             ),
         )?;
 
@@ -108,6 +110,7 @@ impl Persister {
     }
 
     pub(crate) fn insert_or_update_receive_swap(&self, receive_swap: &ReceiveSwap) -> Result<()> {
+        self.check_fault("insert_or_update_receive_swap")?;
         let maybe_swap = self.fetch_receive_swap_by_id(&receive_swap.id)?;
         let updated_fields = ReceiveSyncData::updated_fields(maybe_swap, receive_swap);
 
@@ -164,6 +167,7 @@ impl Persister {
                 rs.pair_fees_json,
                 rs.version,
                 rs.last_updated_at,
+                rs.swap_provider,
 
                 sync_state.is_local
             FROM receive_swaps AS rs
@@ -217,10 +221,11 @@ impl Persister {
             created_at: row.get(19)?,
             state: row.get(20)?,
             pair_fees_json: row.get(21)?,
+            swap_provider: row.get(24)?,
             metadata: SwapMetadata {
                 version: from_row_to_u64(row, 22)?,
                 last_updated_at: row.get(23)?,
-                is_local: row.get::<usize, Option<bool>>(24)?.unwrap_or(true),
+                is_local: row.get::<usize, Option<bool>>(25)?.unwrap_or(true),
             },
         })
     }
@@ -239,6 +244,15 @@ impl Persister {
         Ok(ongoing_receive)
     }
 
+    pub(crate) fn list_receive_swaps_by_state(
+        &self,
+        states: Vec<PaymentState>,
+    ) -> Result<Vec<ReceiveSwap>> {
+        let con = self.get_connection()?;
+        let where_clause = vec![get_where_clause_state_in(&states)];
+        self.list_receive_swaps_where(&con, where_clause)
+    }
+
     pub(crate) fn list_ongoing_receive_swaps(&self) -> Result<Vec<ReceiveSwap>> {
         let con = self.get_connection()?;
         let where_clauses = vec![get_where_clause_state_in(&[
@@ -336,6 +350,7 @@ impl Persister {
         mrh_tx_id: Option<&str>,
         mrh_amount_sat: Option<u64>,
     ) -> Result<(), PaymentError> {
+        self.check_fault("try_handle_receive_swap_update")?;
         // Do not overwrite claim_tx_id, lockup_tx_id, mrh_tx_id
         let mut con = self.get_connection()?;
         let tx = con.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
@@ -36,9 +36,10 @@ impl Persister {
                 refund_private_key,
                 created_at,
                 state,
-                pair_fees_json
+                pair_fees_json,
+                swap_provider
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT DO NOTHING
             ",
             (
@@ -56,6 +57,7 @@ impl Persister {
                 &send_swap.created_at,
                 &send_swap.state,
                 &send_swap.pair_fees_json,
+                &send_swap.swap_provider,
             ),
         )?;
 
@@ -91,6 +93,7 @@ impl Persister {
     }
 
     pub(crate) fn insert_or_update_send_swap(&self, send_swap: &SendSwap) -> Result<()> {
+        self.check_fault("insert_or_update_send_swap")?;
         let maybe_swap = self.fetch_send_swap_by_id(&send_swap.id)?;
         let updated_fields = SendSyncData::updated_fields(maybe_swap, send_swap);
 
@@ -189,6 +192,7 @@ impl Persister {
                 pair_fees_json,
                 version,
                 last_updated_at,
+                swap_provider,
 
                 sync_state.is_local
             FROM send_swaps AS ss
@@ -217,6 +221,20 @@ impl Persister {
         Ok(res.ok())
     }
 
+    /// The latest Send Swap paying the BOLT12 offer
+    pub(crate) fn fetch_latest_send_swap_by_bolt12_offer(
+        &self,
+        offer: &str,
+    ) -> Result<Option<SendSwap>> {
+        let con: Connection = self.get_connection()?;
+        let query = Self::list_send_swaps_query(vec!["bolt12_offer = ?1".to_string()]);
+        let swaps = con
+            .prepare(&query)?
+            .query_map([offer], Self::sql_row_to_send_swap)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(swaps.into_iter().last())
+    }
+
     fn sql_row_to_send_swap(row: &Row) -> rusqlite::Result<SendSwap> {
         Ok(SendSwap {
             id: row.get(0)?,
@@ -237,10 +255,11 @@ impl Persister {
             created_at: row.get(15)?,
             state: row.get(16)?,
             pair_fees_json: row.get(17)?,
+            swap_provider: row.get(20)?,
             metadata: SwapMetadata {
                 version: from_row_to_u64(row, 18)?,
                 last_updated_at: row.get(19)?,
-                is_local: row.get::<usize, Option<bool>>(20)?.unwrap_or(true),
+                is_local: row.get::<usize, Option<bool>>(21)?.unwrap_or(true),
             },
         })
     }
@@ -292,6 +311,7 @@ impl Persister {
         lockup_tx_id: Option<&str>,
         refund_tx_id: Option<&str>,
     ) -> Result<(), PaymentError> {
+        self.check_fault("try_handle_send_swap_update")?;
         // Do not overwrite preimage, lockup_tx_id, refund_tx_id
         let mut con = self.get_connection()?;
         let tx = con.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
@@ -433,7 +453,10 @@ impl InternalCreateSubmarineResponse {
 
 #[cfg(test)]
 mod tests {
+    use crate::model::{ListPaymentsRequest, PaymentTxData, PaymentType};
+    use crate::persist::model::PaymentTxBalance;
     use crate::test_utils::persist::{create_persister, new_send_swap};
+    use crate::utils;
     use anyhow::{anyhow, Result};
 
     use super::PaymentState;
@@ -551,4 +574,48 @@ mod tests {
 
         Ok(())
     }
+
+    #[sdk_macros::test_all]
+    fn test_payjoin_lockup_is_a_single_payment() -> Result<()> {
+        create_persister!(storage);
+        let lbtc_asset_id = utils::lbtc_asset_id(storage.network).to_string();
+        let lockup_tx_id = "payjoin-lockup-tx";
+
+        let send_swap = new_send_swap(Some(PaymentState::Pending), None);
+        storage.insert_or_update_send_swap(&send_swap)?;
+        storage.set_send_swap_lockup_tx_id(&send_swap.id, lockup_tx_id)?;
+
+        // The lockup network fees are paid in an asset via the payjoin service
+        storage.insert_or_update_payment(
+            PaymentTxData {
+                tx_id: lockup_tx_id.to_string(),
+                timestamp: Some(utils::now()),
+                fees_sat: 0,
+                is_confirmed: false,
+                unblinding_data: None,
+                blocks_remaining: None,
+            },
+            &[
+                PaymentTxBalance {
+                    asset_id: lbtc_asset_id,
+                    amount: send_swap.payer_amount_sat,
+                    payment_type: PaymentType::Send,
+                },
+                PaymentTxBalance {
+                    asset_id: "ce091c998b83c78bb71a632313ba3760f1763d9cfcffae02258ffa9865a37bd2"
+                        .to_string(),
+                    amount: 5_000_000,
+                    payment_type: PaymentType::Send,
+                },
+            ],
+            None,
+            false,
+        )?;
+
+        let payments = storage.get_payments(&ListPaymentsRequest::default())?;
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0].tx_id.as_deref(), Some(lockup_tx_id));
+
+        Ok(())
+    }
 }
@@ -9,7 +9,7 @@ use tokio::sync::broadcast;
 
 use super::{cache::KEY_LAST_DERIVATION_INDEX, PaymentTxDetails, Persister, Swap};
 use crate::{
-    model::Bolt12Offer,
+    model::{Bolt12Offer, Peg},
     persist::where_clauses_to_string,
     sync::model::{
         data::LAST_DERIVATION_INDEX_DATA_ID, Record, RecordType, SyncOutgoingChanges, SyncSettings,
@@ -524,6 +524,42 @@ impl Persister {
         Ok(())
     }
 
+    pub(crate) fn commit_incoming_peg(
+        &self,
+        peg: Peg,
+        sync_state: &SyncState,
+        last_commit_time: Option<u32>,
+    ) -> Result<()> {
+        // Keep the chain data of a known peg, which is not part of the record
+        let peg = match self.fetch_peg_by_id(&peg.id)? {
+            Some(local_peg) => Peg {
+                description: peg.description,
+                ..local_peg
+            },
+            None => peg,
+        };
+
+        let mut con = self.get_connection()?;
+        let tx = con.transaction_with_behavior(TransactionBehavior::Immediate)?;
+
+        if let Some(last_commit_time) = last_commit_time {
+            Self::check_commit_update(&tx, &sync_state.record_id, last_commit_time)?;
+        }
+
+        Self::insert_or_update_peg_inner(&tx, &peg)?;
+
+        Self::set_sync_state_stmt(&tx)?.execute(named_params! {
+            ":data_id": &sync_state.data_id,
+            ":record_id": &sync_state.record_id,
+            ":record_revision": from_u64_to_row(sync_state.record_revision)?,
+            ":is_local": &sync_state.is_local,
+        })?;
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
     pub(crate) fn subscribe_sync_trigger(&self) -> Result<broadcast::Receiver<()>> {
         match self.sync_trigger {
             Some(ref sender) => Ok(sender.subscribe()),
@@ -0,0 +1,140 @@
+use anyhow::Result;
+use rusqlite::{params, Row, TransactionBehavior};
+
+use super::Persister;
+use crate::{elements::Transaction, model::WatchedOutpoint, utils};
+
+impl Persister {
+    /// Watches the outpoints spent by an unconfirmed `tx` that was accepted with zero-conf
+    pub(crate) fn insert_watched_outpoints(
+        &self,
+        payment_id: &str,
+        tx: &Transaction,
+    ) -> Result<()> {
+        let mut con = self.get_connection()?;
+        let db_tx = con.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let tx_id = tx.txid().to_string();
+        for input in &tx.input {
+            db_tx.execute(
+                "INSERT OR REPLACE INTO watched_outpoints (outpoint, tx_id, payment_id, created_at)
+                VALUES (?, ?, ?, ?)",
+                params![
+                    input.previous_output.to_string(),
+                    tx_id,
+                    payment_id,
+                    utils::now()
+                ],
+            )?;
+        }
+        db_tx.commit()?;
+        Ok(())
+    }
+
+    /// Lists the outpoints that are still watched, i.e. not double-spent yet
+    pub(crate) fn list_watched_outpoints(&self) -> Result<Vec<WatchedOutpoint>> {
+        let con = self.get_connection()?;
+        let mut stmt = con.prepare(
+            "SELECT outpoint, tx_id, payment_id, created_at
+            FROM watched_outpoints
+            WHERE conflicting_tx_id IS NULL
+            ORDER BY created_at",
+        )?;
+        let outpoints: Vec<WatchedOutpoint> = stmt
+            .query_map([], Self::sql_row_to_watched_outpoint)?
+            .map(|i| i.unwrap())
+            .collect();
+        Ok(outpoints)
+    }
+
+    /// Stops watching the outpoints spent by `tx_id`
+    pub(crate) fn delete_watched_outpoints(&self, tx_id: &str) -> Result<()> {
+        let con = self.get_connection()?;
+        con.execute("DELETE FROM watched_outpoints WHERE tx_id = ?", [tx_id])?;
+        Ok(())
+    }
+
+    /// Stops watching the outpoints spent by `tx_id`, recording that they were double-spent
+    /// by `conflicting_tx_id`
+    pub(crate) fn set_watched_outpoints_conflict(
+        &self,
+        tx_id: &str,
+        conflicting_tx_id: &str,
+    ) -> Result<()> {
+        let con = self.get_connection()?;
+        con.execute(
+            "UPDATE watched_outpoints SET conflicting_tx_id = ? WHERE tx_id = ?",
+            [conflicting_tx_id, tx_id],
+        )?;
+        Ok(())
+    }
+
+    /// Whether the outpoints watched for `tx_id` of the payment `payment_id` were double-spent
+    pub(crate) fn is_watched_tx_double_spent(&self, payment_id: &str, tx_id: &str) -> Result<bool> {
+        let con = self.get_connection()?;
+        Ok(con.query_row(
+            "SELECT EXISTS(
+                SELECT 1 FROM watched_outpoints
+                WHERE payment_id = ? AND tx_id = ? AND conflicting_tx_id IS NOT NULL
+            )",
+            [payment_id, tx_id],
+            |row| row.get(0),
+        )?)
+    }
+
+    /// Marks the payment tx as double-spent, so the payment is reported as failed
+    pub(crate) fn set_payment_tx_double_spent(&self, tx_id: &str) -> Result<()> {
+        let con = self.get_connection()?;
+        con.execute(
+            "UPDATE payment_tx_data SET is_double_spent = 1 WHERE tx_id = ?",
+            [tx_id],
+        )?;
+        Ok(())
+    }
+
+    fn sql_row_to_watched_outpoint(row: &Row) -> rusqlite::Result<WatchedOutpoint> {
+        Ok(WatchedOutpoint {
+            outpoint: row.get(0)?,
+            tx_id: row.get(1)?,
+            payment_id: row.get(2)?,
+            created_at: row.get(3)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use crate::test_utils::{persist::create_persister, wallet::TEST_LIQUID_TX};
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[sdk_macros::test_all]
+    fn test_watched_outpoints() -> Result<()> {
+        create_persister!(storage);
+        let tx = TEST_LIQUID_TX.clone();
+        let tx_id = tx.txid().to_string();
+
+        storage.insert_watched_outpoints("payment-id", &tx)?;
+        let outpoints = storage.list_watched_outpoints()?;
+        assert_eq!(outpoints.len(), tx.input.len());
+        assert!(outpoints
+            .iter()
+            .all(|o| o.tx_id == tx_id && o.payment_id == "payment-id"));
+        assert_eq!(
+            outpoints[0].outpoint,
+            tx.input[0].previous_output.to_string()
+        );
+
+        storage.set_watched_outpoints_conflict(&tx_id, "conflicting-tx-id")?;
+        assert!(storage.list_watched_outpoints()?.is_empty());
+        assert!(storage.is_watched_tx_double_spent("payment-id", &tx_id)?);
+        assert!(!storage.is_watched_tx_double_spent("other-payment-id", &tx_id)?);
+
+        storage.delete_watched_outpoints(&tx_id)?;
+        assert!(!storage.is_watched_tx_double_spent("payment-id", &tx_id)?);
+
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use anyhow::{anyhow, bail, Context, Result};
 use boltz_client::swaps::boltz::RevSwapStates;
-use boltz_client::{boltz, Serialize, ToHex};
+use boltz_client::{Serialize, ToHex};
 use log::{debug, error, info, warn};
 use lwk_wollet::elements::secp256k1_zkp::Secp256k1;
 use lwk_wollet::elements::{Transaction, Txid};
@@ -14,13 +14,16 @@ use tokio::sync::{broadcast, Mutex};
 
 use crate::chain::liquid::LiquidChainService;
 use crate::error::is_txn_mempool_conflict_error;
-use crate::model::{BlockListener, PaymentState::*};
+use crate::model::{BlockListener, Blockchain, PaymentState::*};
 use crate::model::{Config, PaymentTxData, PaymentType, ReceiveSwap};
 use crate::persist::model::{PaymentTxBalance, PaymentTxDetails};
 use crate::prelude::Swap;
 use crate::{ensure_sdk, utils};
 use crate::{
-    error::PaymentError, model::PaymentState, persist::Persister, swapper::Swapper,
+    error::PaymentError,
+    model::PaymentState,
+    persist::Persister,
+    swapper::{model::SwapStatus, Swapper},
     wallet::OnchainWallet,
 };
 
@@ -77,7 +80,7 @@ impl ReceiveSwapHandler {
     }
 
     /// Handles status updates from Boltz for Receive swaps
-    pub(crate) async fn on_new_status(&self, update: &boltz::SwapStatus) -> Result<()> {
+    pub(crate) async fn on_new_status(&self, update: &SwapStatus) -> Result<()> {
         let id = &update.id;
         let status = &update.status;
         let swap_state = RevSwapStates::from_str(status)
@@ -122,6 +125,18 @@ impl ReceiveSwapHandler {
                     ));
                 }
 
+                // Do not claim a replacement of a lockup that was previously double-spent
+                if let Some(lockup_tx_id) = &receive_swap.lockup_tx_id {
+                    if lockup_tx_id != &transaction.id
+                        && self
+                            .persister
+                            .is_watched_tx_double_spent(id, lockup_tx_id)?
+                    {
+                        warn!("[Receive Swap {id}] Lockup tx {lockup_tx_id} was replaced by {}. Waiting for confirmation...", transaction.id);
+                        return Ok(());
+                    }
+                }
+
                 // Looking for lockup script history to verify lockup was broadcasted
                 let tx_hex = transaction.hex.ok_or(anyhow!(
                     "Missing lockup transaction hex in swap status update"
@@ -176,6 +191,14 @@ impl ReceiveSwapHandler {
 
                 debug!("[Receive Swap {id}] Amount is within valid range for zero-conf ({receiver_amount_sat} < {max_amount_sat} sat)");
 
+                if !self
+                    .has_required_lockup_confirmations(&receive_swap, lockup_tx_id)
+                    .await?
+                {
+                    warn!("[Receive Swap {id}] The confirmation policy requires a confirmed lockup. Waiting for confirmation...");
+                    return Ok(());
+                }
+
                 // If the transaction has RBF, see https://github.com/bitcoin/bips/blob/master/bip-0125.mediawiki
                 // TODO: Check for inherent RBF by ensuring all tx ancestors are confirmed
                 let rbf_explicit = lockup_tx.input.iter().any(|input| input.sequence.is_rbf());
@@ -187,6 +210,9 @@ impl ReceiveSwapHandler {
                 }
                 debug!("[Receive Swap {id}] Lockup tx does not signal RBF. Proceeding...");
 
+                // Watch the lockup inputs, so a double-spend can be detected before it confirms
+                self.persister.insert_watched_outpoints(id, &lockup_tx)?;
+
                 if let Err(err) = self.claim(id).await {
                     match err {
                         PaymentError::AlreadyClaimed => {
@@ -247,7 +273,24 @@ impl ReceiveSwapHandler {
                         warn!("Claim tx for Receive Swap {id} was already broadcast: txid {claim_tx_id}")
                     }
                     None => {
-                        self.update_swap_info(&receive_swap.id, Pending, None, None, None, None)?;
+                        self.update_swap_info(
+                            &receive_swap.id,
+                            Pending,
+                            None,
+                            Some(&transaction.id),
+                            None,
+                            None,
+                        )?;
+
+                        // If the lockup is not deep enough yet, it will be claimed later in
+                        // claim_confirmed_lockups()
+                        if !self
+                            .has_required_lockup_confirmations(&receive_swap, &transaction.id)
+                            .await?
+                        {
+                            info!("Lockup tx for Receive Swap {id} does not have the required confirmations yet");
+                            return Ok(());
+                        }
 
                         if let Err(err) = self.claim(id).await {
                             match err {
@@ -458,6 +501,7 @@ impl ReceiveSwapHandler {
                                 fees_sat: 0,
                                 is_confirmed: false,
                                 unblinding_data: None,
+                                blocks_remaining: None,
                             },
                             &[PaymentTxBalance {
                                 amount: swap.receiver_amount_sat,
@@ -535,6 +579,13 @@ impl ReceiveSwapHandler {
             return Err(e);
         }
         info!("Receive Swap {swap_id} lockup tx is confirmed");
+        if !self
+            .has_required_lockup_confirmations(receive_swap, &tx_id)
+            .await?
+        {
+            info!("Receive Swap {swap_id} lockup tx does not have the required confirmations yet");
+            return Ok(());
+        }
         self.claim(swap_id)
             .await
             .map_err(|e| anyhow!("Could not claim Receive Swap {swap_id}: {e:?}"))
@@ -600,6 +651,43 @@ impl ReceiveSwapHandler {
             .await
     }
 
+    /// Checks whether the lockup tx has the Liquid confirmations required by the configured
+    /// [ConfirmationPolicy](crate::model::ConfirmationPolicy) for the swap amount.
+    ///
+    /// A policy requiring a single confirmation accepts any lockup, which keeps claiming
+    /// zero-conf lockups within the zero-conf limit.
+    async fn has_required_lockup_confirmations(
+        &self,
+        receive_swap: &ReceiveSwap,
+        lockup_tx_id: &str,
+    ) -> Result<bool> {
+        let Some(confirmation_policy) = &self.config.confirmation_policy else {
+            return Ok(true);
+        };
+        let required_confirmations = confirmation_policy
+            .required_confirmations(Blockchain::Liquid, receive_swap.receiver_amount_sat);
+        if required_confirmations <= 1 {
+            return Ok(true);
+        }
+
+        let script = receive_swap.get_swap_script()?;
+        let address = script
+            .to_address(self.config.network.into())
+            .map_err(|e| anyhow!("Failed to get swap script address {e:?}"))?;
+        let script_history = self
+            .liquid_chain_service
+            .get_script_history(&address.script_pubkey())
+            .await?;
+        let Some(lockup_history) = script_history
+            .iter()
+            .find(|h| h.txid.to_string() == lockup_tx_id && h.confirmed())
+        else {
+            return Ok(false);
+        };
+        let tip = self.liquid_chain_service.tip().await?;
+        Ok(tip.saturating_sub(lockup_history.height as u32) + 1 >= required_confirmations)
+    }
+
     async fn verify_lockup_tx_amount(
         &self,
         receive_swap: &ReceiveSwap,
@@ -334,6 +334,7 @@ mod test {
             timeout_block_height: 10000,
             claim_timeout_block_height: 1000,
             state: PaymentState::Created,
+            swap_provider: None,
             metadata: SwapMetadata {
                 version: 1,
                 last_updated_at: 1000,
@@ -443,6 +443,7 @@ mod test {
             created_at: 1000,
             timeout_block_height: 1000,
             state: PaymentState::Created,
+            swap_provider: None,
             metadata: SwapMetadata {
                 version: 1,
                 last_updated_at: 1000,
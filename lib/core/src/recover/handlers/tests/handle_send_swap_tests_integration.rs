@@ -338,6 +338,7 @@ mod test {
             timeout_block_height: 1000,
             state: PaymentState::Created,
             refund_private_key: "0000000000000000000000000000000000000000000000000000000000000001".to_string(),
+            swap_provider: None,
             metadata: SwapMetadata {
                 version: 1,
                 last_updated_at: 1000,
@@ -0,0 +1,208 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::{anyhow, Result};
+use log::{error, info};
+use sdk_common::prelude::BreezServer;
+use tokio::sync::watch;
+
+use crate::chain::{bitcoin::BitcoinChainService, liquid::LiquidChainService};
+use crate::error::SdkError;
+use crate::model::{AsyncSigner, Config, ConnectRequest, ConnectWithSignerRequest, LiquidNetwork};
+use crate::persist::Persister;
+use crate::sdk::{LiquidSdk, LiquidSdkBuilder};
+use crate::signer::SyncSignerAdapter;
+use crate::swapper::{
+    boltz::{proxy::BoltzProxyFetcher, BoltzSwapper},
+    router::StatusStreamRouter,
+    Swapper,
+};
+use crate::sync::client::{BreezSyncerClient, SharedSyncerClient};
+use crate::utils;
+
+/// The name of the directory holding the data of the runtime itself, next to the wallet
+/// directories
+const RUNTIME_DIR: &str = "runtime";
+
+/// Hosts many [LiquidSdk] instances in one process.
+///
+/// The SDKs connected through a runtime share its connections instead of opening their own:
+/// the Liquid and Bitcoin chain services, the Boltz swapper and its status stream, the swapper
+/// fallback instances of [Config::swapper_fallback_urls] and their status streams, and the
+/// real-time sync service client. Each SDK still has its own wallet, persister and background
+/// tasks, and the swap updates received on the shared status stream are only routed to the SDK
+/// that tracks the swap.
+///
+/// The chain, swapper and sync settings of the runtime [Config] apply to all its SDKs. The
+/// runtime has to outlive them, as dropping it closes the shared connections.
+pub struct SdkRuntime {
+    config: Config,
+    breez_server: Arc<BreezServer>,
+    bitcoin_chain_service: Arc<dyn BitcoinChainService>,
+    liquid_chain_service: Arc<dyn LiquidChainService>,
+    swapper: Arc<dyn Swapper>,
+    status_stream_router: Arc<StatusStreamRouter>,
+    /// The swapper fallback instances, with their shared status streams, by API URL
+    swapper_fallbacks: Vec<(String, Arc<dyn Swapper>, Arc<StatusStreamRouter>)>,
+    syncer_client: SharedSyncerClient,
+    shutdown_sender: watch::Sender<()>,
+}
+
+impl SdkRuntime {
+    /// Creates the shared services from the given [Config]. No connection is opened until the
+    /// first SDK is connected.
+    pub fn new(config: Config) -> Result<Self> {
+        // Testnet is not currently supported
+        if config.network == LiquidNetwork::Testnet {
+            return Err(SdkError::network_not_supported(config.network).into());
+        }
+
+        let runtime_dir = config.get_wallet_dir(&config.working_dir, RUNTIME_DIR)?;
+        // Only caches the Boltz proxy urls
+        let persister = Arc::new(Persister::new_using_fs(
+            &runtime_dir,
+            config.network,
+            false,
+            config.asset_metadata.clone(),
+        )?);
+        let proxy_url_fetcher = Arc::new(BoltzProxyFetcher::new(persister));
+        let boltz_swapper = Arc::new(BoltzSwapper::new(
+            config.clone(),
+            proxy_url_fetcher.clone(),
+        )?);
+
+        let (shutdown_sender, shutdown_receiver) = watch::channel::<()>(());
+        let status_stream_router = Arc::new(StatusStreamRouter::new(
+            boltz_swapper.clone(),
+            shutdown_receiver.clone(),
+        ));
+        let mut swapper_fallbacks: Vec<(String, Arc<dyn Swapper>, Arc<StatusStreamRouter>)> =
+            vec![];
+        for url in config.swapper_fallback_urls.clone().unwrap_or_default() {
+            let fallback_swapper = Arc::new(
+                BoltzSwapper::new(config.clone(), proxy_url_fetcher.clone())?
+                    .with_boltz_url(url.clone()),
+            );
+            let fallback_router = Arc::new(StatusStreamRouter::new(
+                fallback_swapper.clone(),
+                shutdown_receiver.clone(),
+            ));
+            swapper_fallbacks.push((url, fallback_swapper, fallback_router));
+        }
+        let syncer_client = SharedSyncerClient::new(Arc::new(BreezSyncerClient::new(
+            config.breez_api_key.clone(),
+        )));
+
+        Ok(Self {
+            breez_server: Arc::new(BreezServer::new(config.breez_server_url(), None)?),
+            bitcoin_chain_service: config.bitcoin_chain_service(),
+            liquid_chain_service: config.liquid_chain_service()?,
+            swapper: boltz_swapper,
+            status_stream_router,
+            swapper_fallbacks,
+            syncer_client,
+            shutdown_sender,
+            config,
+        })
+    }
+
+    /// Creates a builder for an SDK using the shared services of the runtime. The builder
+    /// can be further customized, e.g. with a persister or BIP44 account, before being built.
+    ///
+    /// # Arguments
+    ///
+    /// * `working_dir` - the base directory of the wallet data, overriding the one of the
+    ///   runtime [Config]
+    /// * `signer` - the [AsyncSigner] holding the wallet keys
+    pub fn builder(
+        &self,
+        working_dir: Option<String>,
+        signer: Arc<dyn AsyncSigner>,
+    ) -> Result<LiquidSdkBuilder> {
+        let mut config = self.config.clone();
+        if let Some(working_dir) = working_dir {
+            config.working_dir = working_dir;
+        }
+
+        let server_url = self.config.breez_server_url();
+        let mut builder = LiquidSdkBuilder::new_with_async_signer(config, server_url, signer)?;
+        builder
+            .bitcoin_chain_service(self.bitcoin_chain_service.clone())
+            .liquid_chain_service(self.liquid_chain_service.clone())
+            .fiat_api(self.breez_server.clone())
+            .swapper(self.swapper.clone())
+            .status_stream(Arc::new(self.status_stream_router.wallet_stream()))
+            .syncer_client(self.syncer_client.clone());
+        for (url, fallback_swapper, fallback_router) in &self.swapper_fallbacks {
+            builder.swapper_fallback(
+                url,
+                fallback_swapper.clone(),
+                Arc::new(fallback_router.wallet_stream()),
+            );
+        }
+        Ok(builder)
+    }
+
+    /// Connects an SDK through the runtime, like [LiquidSdk::connect].
+    ///
+    /// The request [Config] must be for the network of the runtime. Only its working directory
+    /// is used, the other settings are those of the runtime.
+    pub async fn connect(&self, req: ConnectRequest) -> Result<Arc<LiquidSdk>> {
+        let signer = LiquidSdk::default_signer(&req)?;
+
+        self.connect_with_async_signer(
+            ConnectWithSignerRequest {
+                config: req.config,
+                account_index: req.account_index,
+            },
+            Arc::new(SyncSignerAdapter::new(Arc::new(Box::new(signer)))),
+        )
+        .await
+        .inspect_err(|e| error!("Failed to connect through the runtime: {e:?}"))
+    }
+
+    /// Connects an SDK using an [AsyncSigner] through the runtime, like
+    /// [LiquidSdk::connect_with_async_signer].
+    ///
+    /// The request [Config] must be for the network of the runtime. Only its working directory
+    /// is used, the other settings are those of the runtime.
+    pub async fn connect_with_async_signer(
+        &self,
+        req: ConnectWithSignerRequest,
+        signer: Arc<dyn AsyncSigner>,
+    ) -> Result<Arc<LiquidSdk>> {
+        let start_ts = Instant::now();
+
+        if req.config.network != self.config.network {
+            return Err(anyhow!(
+                "Cannot connect a {} wallet to a {} runtime",
+                req.config.network,
+                self.config.network
+            ));
+        }
+        std::fs::create_dir_all(&req.config.working_dir)?;
+
+        let mut sdk_builder = self.builder(Some(req.config.working_dir), signer)?;
+        sdk_builder.account_index(req.account_index.unwrap_or_default());
+        let sdk = sdk_builder.build().await?;
+        sdk.start().await?;
+
+        let init_time = Instant::now().duration_since(start_ts);
+        utils::log_print_header(init_time);
+
+        Ok(sdk)
+    }
+
+    /// The number of connected SDKs using the shared swap status stream
+    pub fn wallet_count(&self) -> usize {
+        self.status_stream_router.wallet_count()
+    }
+
+    /// Closes the shared connections. The SDKs connected through the runtime should be
+    /// disconnected first.
+    pub async fn shutdown(&self) -> Result<()> {
+        info!("Shutting down the SDK runtime");
+        let _ = self.shutdown_sender.send(());
+        self.syncer_client.shutdown().await
+    }
+}
@@ -18,6 +18,7 @@ use lwk_wollet::bitcoin::base64::Engine as _;
 use lwk_wollet::elements::AssetId;
 use lwk_wollet::elements_miniscript::elements::bitcoin::bip32::Xpub;
 use lwk_wollet::hashes::{sha256, Hash};
+use lwk_wollet::WolletDescriptor;
 use persist::model::{PaymentTxBalance, PaymentTxDetails};
 use recover::recoverer::Recoverer;
 use sdk_common::bitcoin::hashes::hex::ToHex;
@@ -40,29 +41,44 @@ use sdk_common::lightning_with_bolt12::types::payment::PaymentHash;
 use sdk_common::lightning_with_bolt12::util::string::UntrustedString;
 use sdk_common::liquid::LiquidAddressData;
 use sdk_common::prelude::{FiatAPI, FiatCurrency, LnUrlPayError, LnUrlWithdrawError, Rate};
-use side_swap::api::SideSwapService;
-use signer::SdkSigner;
+#[cfg(any(test, feature = "simulation"))]
+use sdk_common::prelude::{MockRestClient, STAGING_BREEZSERVER_URL};
+use side_swap::api::{model::AssetSwap, SideSwapService};
+use signer::{
+    descriptor_keys, AccountScopedSigner, SdkLwkSigner, SdkSigner, SyncSignerAdapter,
+    WatchOnlySigner,
+};
 use swapper::boltz::proxy::BoltzProxyFetcher;
 use tokio::sync::{watch, Mutex, RwLock};
 use tokio_stream::wrappers::BroadcastStream;
 use tokio_with_wasm::alias as tokio;
-use web_time::{Instant, SystemTime, UNIX_EPOCH};
+use web_time::{Instant, UNIX_EPOCH};
 use x509_parser::parse_x509_certificate;
 
+use crate::asset_registry::AssetRegistry;
 use crate::chain_swap::ChainSwapHandler;
 use crate::ensure_sdk;
 use crate::error::SdkError;
 use crate::lightning_invoice::{Bolt11Invoice, Bolt11InvoiceDescription};
 use crate::model::PaymentState::*;
-use crate::model::Signer;
+use crate::model::{AsyncSigner, Signer};
 use crate::payjoin::{side_swap::SideSwapPayjoinService, PayjoinService};
+use crate::peg::PegHandler;
 use crate::plugin::{Plugin, PluginSdk, PluginStorage};
 use crate::receive_swap::ReceiveSwapHandler;
 use crate::send_swap::SendSwapHandler;
 use crate::swapper::SubscriptionHandler;
 use crate::swapper::{
-    boltz::BoltzSwapper, Swapper, SwapperStatusStream, SwapperSubscriptionHandler,
+    boltz::BoltzSwapper,
+    model::{
+        CreateBolt12OfferRequest, CreateChainRequest, CreateReverseRequest, CreateSubmarineRequest,
+        GetBolt12FetchRequest, GetBolt12FetchResponse, InvoiceRequest, MagicRoutingHint,
+        UpdateBolt12OfferRequest, Webhook,
+    },
+    SwapProvider, Swapper, SwapperRegistry, SwapperStatusStream, SwapperSubscriptionHandler,
 };
+#[cfg(any(test, feature = "simulation"))]
+use crate::test_utils::simulation::Simulator;
 use crate::utils::bolt12::encode_invoice;
 use crate::utils::run_with_shutdown;
 use crate::wallet::{LiquidOnchainWallet, OnchainWallet};
@@ -75,8 +91,9 @@ use crate::{
 };
 use sdk_common::lightning_with_bolt12::offers::invoice::{Bolt12Invoice, UnsignedBolt12Invoice};
 
-use self::sync::client::BreezSyncerClient;
+use self::sync::client::{BreezSyncerClient, SharedSyncerClient, SyncerClient};
 use self::sync::SyncService;
+use crate::persist::ledger::LedgerChange;
 
 pub const DEFAULT_DATA_DIR: &str = ".data";
 /// Number of blocks to monitor a swap after its timeout block height (~14 days)
@@ -99,12 +116,19 @@ pub const DEFAULT_EXTERNAL_INPUT_PARSERS: &[(&str, &str, &str)] = &[
 ];
 
 pub(crate) const NETWORK_PROPAGATION_GRACE_PERIOD: Duration = Duration::from_secs(120);
+/// Number of blocks below the tip for which block hashes are tracked to detect reorgs
+pub(crate) const REORG_TRACKING_DEPTH: u32 = 100;
+/// Estimated vsize of a lockup tx spending the L-BTC output of a SideSwap swap
+const ESTIMATED_LOCKUP_TX_VSIZE: u64 = 300;
+/// Number of consecutive accounts without history after which account discovery stops
+const ACCOUNT_DISCOVERY_GAP_LIMIT: u32 = 5;
 
 pub struct LiquidSdkBuilder {
     config: Config,
-    signer: Arc<Box<dyn Signer>>,
+    signer: Arc<dyn AsyncSigner>,
     breez_server: Arc<BreezServer>,
     bitcoin_chain_service: Option<Arc<dyn BitcoinChainService>>,
+    fiat_api: Option<Arc<dyn FiatAPI>>,
     liquid_chain_service: Option<Arc<dyn LiquidChainService>>,
     onchain_wallet: Option<Arc<dyn OnchainWallet>>,
     payjoin_service: Option<Arc<dyn PayjoinService>>,
@@ -113,8 +137,16 @@ pub struct LiquidSdkBuilder {
     rest_client: Option<Arc<dyn RestClient>>,
     status_stream: Option<Arc<dyn SwapperStatusStream>>,
     swapper: Option<Arc<dyn Swapper>>,
+    swap_providers: Vec<(String, SwapProvider)>,
+    /// The swapper fallback instances set by URL, instead of being created from the config
+    swapper_fallbacks: HashMap<String, SwapProvider>,
     sync_service: Option<Arc<SyncService>>,
+    syncer_client: Option<SharedSyncerClient>,
     plugins: Option<HashMap<String, Arc<dyn Plugin>>>,
+    wallet_descriptor: Option<WolletDescriptor>,
+    watch_only: bool,
+    multisig: bool,
+    account_index: u32,
 }
 
 #[allow(dead_code)]
@@ -123,6 +155,16 @@ impl LiquidSdkBuilder {
         config: Config,
         server_url: String,
         signer: Arc<Box<dyn Signer>>,
+    ) -> Result<LiquidSdkBuilder> {
+        Self::new_with_async_signer(config, server_url, Arc::new(SyncSignerAdapter::new(signer)))
+    }
+
+    /// Creates a builder for an SDK using an [AsyncSigner], for signers that cannot answer
+    /// synchronously, like hardware wallets or remote signing services.
+    pub fn new_with_async_signer(
+        config: Config,
+        server_url: String,
+        signer: Arc<dyn AsyncSigner>,
     ) -> Result<LiquidSdkBuilder> {
         let breez_server = Arc::new(BreezServer::new(server_url, None)?);
         Ok(LiquidSdkBuilder {
@@ -130,6 +172,7 @@ impl LiquidSdkBuilder {
             signer,
             breez_server,
             bitcoin_chain_service: None,
+            fiat_api: None,
             liquid_chain_service: None,
             onchain_wallet: None,
             payjoin_service: None,
@@ -138,11 +181,64 @@ impl LiquidSdkBuilder {
             rest_client: None,
             status_stream: None,
             swapper: None,
+            swap_providers: vec![],
+            swapper_fallbacks: HashMap::new(),
             sync_service: None,
+            syncer_client: None,
             plugins: None,
+            wallet_descriptor: None,
+            watch_only: false,
+            multisig: false,
+            account_index: 0,
         })
     }
 
+    /// Creates a builder for a watch-only SDK tracking the wallet of the given CT descriptor.
+    /// The SDK holds no private keys, so any operation that needs to sign fails with
+    /// [SdkError::WatchOnly].
+    pub fn new_watch_only(
+        config: Config,
+        server_url: String,
+        ct_descriptor: &str,
+    ) -> Result<LiquidSdkBuilder> {
+        let descriptor: WolletDescriptor = ct_descriptor
+            .parse()
+            .map_err(|e| anyhow!("Invalid CT descriptor: {e}"))?;
+        let signer: Box<dyn Signer> = Box::new(WatchOnlySigner::new(&descriptor)?);
+        let mut builder = Self::new(config, server_url, Arc::new(signer))?;
+        builder.wallet_descriptor = Some(descriptor);
+        builder.watch_only = true;
+        Ok(builder)
+    }
+
+    /// Creates a builder for an SDK using the k-of-n multisig wallet of the given CT descriptor.
+    /// The `hot_signer` signs the swap claims and refunds and cosigns the wallet txs, which
+    /// otherwise need the signatures of the other cosigners. Without a `hot_signer`, the SDK is
+    /// watch-only.
+    pub fn new_multisig(
+        config: Config,
+        server_url: String,
+        ct_descriptor: &str,
+        hot_signer: Option<Arc<Box<dyn Signer>>>,
+    ) -> Result<LiquidSdkBuilder> {
+        let descriptor: WolletDescriptor = ct_descriptor
+            .parse()
+            .map_err(|e| anyhow!("Invalid CT descriptor: {e}"))?;
+        let watch_only = hot_signer.is_none();
+        let signer = match hot_signer {
+            Some(hot_signer) => hot_signer,
+            None => {
+                let signer: Box<dyn Signer> = Box::new(WatchOnlySigner::new_multisig(&descriptor)?);
+                Arc::new(signer)
+            }
+        };
+        let mut builder = Self::new(config, server_url, signer)?;
+        builder.wallet_descriptor = Some(descriptor);
+        builder.watch_only = watch_only;
+        builder.multisig = true;
+        Ok(builder)
+    }
+
     pub fn bitcoin_chain_service(
         &mut self,
         bitcoin_chain_service: Arc<dyn BitcoinChainService>,
@@ -151,6 +247,34 @@ impl LiquidSdkBuilder {
         self
     }
 
+    /// Creates a builder for an SDK running against an in-memory network, which is controlled
+    /// via the returned [Simulator]. No funds or backend services are needed.
+    #[cfg(any(test, feature = "simulation"))]
+    pub fn simulated(
+        mut config: Config,
+        signer: Arc<Box<dyn Signer>>,
+    ) -> Result<(LiquidSdkBuilder, Simulator)> {
+        config.breez_api_key = None;
+        config.sync_service_url = None;
+        let simulator = Simulator::new(config.clone(), signer.clone())?;
+
+        let mut builder = LiquidSdkBuilder::new(config, STAGING_BREEZSERVER_URL.into(), signer)?;
+        builder
+            .bitcoin_chain_service(simulator.bitcoin_chain_service.clone())
+            .fiat_api(simulator.fiat_api.clone())
+            .liquid_chain_service(simulator.liquid_chain_service.clone())
+            .onchain_wallet(simulator.onchain_wallet.clone())
+            .rest_client(Arc::new(MockRestClient::new()))
+            .status_stream(simulator.status_stream.clone())
+            .swapper(simulator.swapper.clone());
+        Ok((builder, simulator))
+    }
+
+    pub fn fiat_api(&mut self, fiat_api: Arc<dyn FiatAPI>) -> &mut Self {
+        self.fiat_api = Some(fiat_api.clone());
+        self
+    }
+
     pub fn liquid_chain_service(
         &mut self,
         liquid_chain_service: Arc<dyn LiquidChainService>,
@@ -194,20 +318,93 @@ impl LiquidSdkBuilder {
         self
     }
 
+    /// Registers an additional swap provider under the given id, next to the default one.
+    /// It can then be selected when preparing a payment via its `swap_provider` field.
+    pub fn swap_provider(
+        &mut self,
+        id: &str,
+        swapper: Arc<dyn Swapper>,
+        status_stream: Arc<dyn SwapperStatusStream>,
+    ) -> &mut Self {
+        self.swap_providers.push((
+            id.to_string(),
+            SwapProvider {
+                swapper,
+                status_stream,
+            },
+        ));
+        self
+    }
+
+    /// Sets the instance used as the swapper fallback with the given URL of
+    /// [Config::swapper_fallback_urls], instead of creating a new one
+    pub fn swapper_fallback(
+        &mut self,
+        url: &str,
+        swapper: Arc<dyn Swapper>,
+        status_stream: Arc<dyn SwapperStatusStream>,
+    ) -> &mut Self {
+        self.swapper_fallbacks.insert(
+            url.to_string(),
+            SwapProvider {
+                swapper,
+                status_stream,
+            },
+        );
+        self
+    }
+
     pub fn sync_service(&mut self, sync_service: Arc<SyncService>) -> &mut Self {
         self.sync_service = Some(sync_service.clone());
         self
     }
 
+    /// Sets the client used to connect to the real-time sync service, which is shared with the
+    /// other SDKs of a [SdkRuntime](crate::runtime::SdkRuntime)
+    pub(crate) fn syncer_client(&mut self, syncer_client: SharedSyncerClient) -> &mut Self {
+        self.syncer_client = Some(syncer_client);
+        self
+    }
+
+    /// Sets the BIP44 account of the signer seed used by the wallet. Each account has its own
+    /// working directory and real-time sync namespace. Ignored for watch-only and multisig
+    /// builders, whose wallet is given by the descriptor.
+    pub fn account_index(&mut self, account_index: u32) -> &mut Self {
+        self.account_index = account_index;
+        self
+    }
+
     pub fn use_plugin(&mut self, plugin: Arc<dyn Plugin>) -> &mut Self {
         let plugins = self.plugins.get_or_insert(HashMap::new());
         plugins.insert(plugin.id(), plugin);
         self
     }
 
-    fn get_working_dir(&self) -> Result<String> {
-        let fingerprint_hex: String =
-            Xpub::decode(self.signer.xpub()?.as_slice())?.identifier()[0..4].to_hex();
+    /// The wallet fingerprint, used to build the working directory in [Config::get_wallet_dir].
+    /// For a watch-only builder, this is the fingerprint of the descriptor's account xpub.
+    /// For a multisig builder, it is derived from the descriptor, as the hot key may also be
+    /// used by a singlesig wallet.
+    /// For accounts other than the first, the account index is appended to the fingerprint.
+    pub async fn fingerprint(&self) -> Result<String> {
+        match (&self.wallet_descriptor, self.multisig) {
+            (Some(descriptor), true) => {
+                Ok(sha256::Hash::hash(descriptor.to_string().as_bytes())[0..4].to_hex())
+            }
+            (maybe_descriptor, _) => {
+                let fingerprint =
+                    Xpub::decode(self.signer.xpub().await?.as_slice())?.identifier()[0..4].to_hex();
+                match (maybe_descriptor, self.account_index) {
+                    (None, account_index) if account_index > 0 => {
+                        Ok(format!("{fingerprint}-{account_index}"))
+                    }
+                    _ => Ok(fingerprint),
+                }
+            }
+        }
+    }
+
+    async fn get_working_dir(&self) -> Result<String> {
+        let fingerprint_hex = self.fingerprint().await?;
         self.config
             .get_wallet_dir(&self.config.working_dir, &fingerprint_hex)
     }
@@ -226,7 +423,7 @@ impl LiquidSdkBuilder {
                 ));
                 #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
                 std::sync::Arc::new(Persister::new_using_fs(
-                    &self.get_working_dir()?,
+                    &self.get_working_dir().await?,
                     self.config.network,
                     self.config.sync_enabled(),
                     self.config.asset_metadata.clone(),
@@ -253,14 +450,34 @@ impl LiquidSdkBuilder {
 
         let onchain_wallet: Arc<dyn OnchainWallet> = match self.onchain_wallet.clone() {
             Some(onchain_wallet) => onchain_wallet,
-            None => Arc::new(
-                LiquidOnchainWallet::new(
-                    self.config.clone(),
-                    persister.clone(),
-                    self.signer.clone(),
-                )
-                .await?,
-            ),
+            None => match self.wallet_descriptor.clone() {
+                Some(descriptor) if self.multisig => Arc::new(
+                    LiquidOnchainWallet::new_multisig(
+                        self.config.clone(),
+                        persister.clone(),
+                        (!self.watch_only).then(|| self.signer.clone()),
+                        descriptor,
+                    )
+                    .await?,
+                ),
+                Some(descriptor) => Arc::new(
+                    LiquidOnchainWallet::new_watch_only(
+                        self.config.clone(),
+                        persister.clone(),
+                        descriptor,
+                    )
+                    .await?,
+                ),
+                None => Arc::new(
+                    LiquidOnchainWallet::new(
+                        self.config.clone(),
+                        persister.clone(),
+                        self.signer.clone(),
+                        self.account_index,
+                    )
+                    .await?,
+                ),
+            },
         };
 
         let event_manager = Arc::new(EventManager::new());
@@ -280,10 +497,51 @@ impl LiquidSdkBuilder {
                 }
             };
 
+        let mut swapper_registry = SwapperRegistry::new(
+            persister.clone(),
+            SwapProvider {
+                swapper,
+                status_stream,
+            },
+        );
+        for (id, provider) in self.swap_providers.clone() {
+            swapper_registry.register(&id, provider)?;
+        }
+        for url in self
+            .config
+            .swapper_fallback_urls
+            .clone()
+            .unwrap_or_default()
+        {
+            let provider = match self.swapper_fallbacks.get(&url) {
+                Some(provider) => provider.clone(),
+                None => {
+                    let proxy_url_fetcher = Arc::new(BoltzProxyFetcher::new(persister.clone()));
+                    let fallback_swapper = Arc::new(
+                        BoltzSwapper::new(self.config.clone(), proxy_url_fetcher)?
+                            .with_boltz_url(url.clone()),
+                    );
+                    SwapProvider {
+                        swapper: fallback_swapper.clone(),
+                        status_stream: fallback_swapper,
+                    }
+                }
+            };
+            swapper_registry.register_fallback(&url, provider)?;
+        }
+        let swapper_registry = Arc::new(swapper_registry);
+        let swapper: Arc<dyn Swapper> = swapper_registry.clone();
+        let status_stream: Arc<dyn SwapperStatusStream> = swapper_registry.clone();
+
+        // The wallet of a descriptor is blinded with its key, which a multisig hot key lacks
+        let slip77_master_blinding_key = match &self.wallet_descriptor {
+            Some(descriptor) => descriptor_keys(descriptor)?.1.as_bytes().to_vec(),
+            None => self.signer.slip77_master_blinding_key().await?,
+        };
         let recoverer = match self.recoverer.clone() {
             Some(recoverer) => recoverer,
             None => Arc::new(Recoverer::new(
-                self.signer.slip77_master_blinding_key()?,
+                slip77_master_blinding_key,
                 utils::lbtc_asset_id(self.config.network),
                 swapper.clone(),
                 onchain_wallet.clone(),
@@ -295,6 +553,9 @@ impl LiquidSdkBuilder {
 
         let sync_service = match self.sync_service.clone() {
             Some(sync_service) => Some(sync_service),
+            // Sync records are encrypted with the signer keys, which a watch-only SDK lacks.
+            // A multisig hot key may also sync its own singlesig wallet, so it is not used.
+            None if self.watch_only || self.multisig => None,
             None => match self.config.sync_service_url.clone() {
                 Some(sync_service_url) => {
                     if BREEZ_SYNC_SERVICE_URL == sync_service_url
@@ -305,13 +566,22 @@ impl LiquidSdkBuilder {
                         );
                     }
 
-                    let syncer_client =
-                        Box::new(BreezSyncerClient::new(self.config.breez_api_key.clone()));
+                    let syncer_client: Box<dyn SyncerClient> = match self.syncer_client.clone() {
+                        Some(syncer_client) => Box::new(syncer_client),
+                        None => Box::new(BreezSyncerClient::new(self.config.breez_api_key.clone())),
+                    };
+                    // The first account keeps syncing with the master key
+                    let sync_signer: Arc<dyn AsyncSigner> = match self.account_index {
+                        0 => self.signer.clone(),
+                        account_index => {
+                            Arc::new(AccountScopedSigner::new(self.signer.clone(), account_index))
+                        }
+                    };
                     Some(Arc::new(SyncService::new(
                         sync_service_url,
                         persister.clone(),
                         recoverer.clone(),
-                        self.signer.clone(),
+                        sync_signer,
                         syncer_client,
                     )))
                 }
@@ -345,11 +615,32 @@ impl LiquidSdkBuilder {
             bitcoin_chain_service.clone(),
         )?);
 
+        let peg_handler = PegHandler::new(
+            self.config.clone(),
+            self.signer.clone(),
+            self.account_index,
+            onchain_wallet.clone(),
+            persister.clone(),
+            liquid_chain_service.clone(),
+            bitcoin_chain_service.clone(),
+        );
+
+        let asset_registry = Arc::new(AssetRegistry::new(
+            self.config.asset_registry_url.clone(),
+            persister.clone(),
+            rest_client.clone(),
+        ));
+
+        let fiat_api: Arc<dyn FiatAPI> = match self.fiat_api.clone() {
+            Some(fiat_api) => fiat_api,
+            None => self.breez_server.clone(),
+        };
+
         let payjoin_service = match self.payjoin_service.clone() {
             Some(payjoin_service) => payjoin_service,
             None => Arc::new(SideSwapPayjoinService::new(
                 self.config.clone(),
-                self.breez_server.clone(),
+                fiat_api.clone(),
                 persister.clone(),
                 onchain_wallet.clone(),
                 rest_client.clone(),
@@ -367,15 +658,18 @@ impl LiquidSdkBuilder {
             config: self.config.clone(),
             onchain_wallet,
             signer: self.signer.clone(),
+            watch_only: self.watch_only,
+            multisig: self.multisig,
             persister: persister.clone(),
             rest_client,
             event_manager,
             status_stream: status_stream.clone(),
             swapper,
+            swapper_registry,
             recoverer,
             bitcoin_chain_service,
             liquid_chain_service,
-            fiat_api: self.breez_server.clone(),
+            fiat_api,
             is_started: RwLock::new(false),
             shutdown_sender,
             shutdown_receiver,
@@ -383,11 +677,14 @@ impl LiquidSdkBuilder {
             receive_swap_handler,
             sync_service,
             chain_swap_handler,
+            peg_handler,
+            asset_registry,
             payjoin_service,
             buy_bitcoin_service,
             external_input_parsers,
             background_task_handles: Mutex::new(vec![]),
             plugins: Mutex::new(self.plugins.unwrap_or_default()),
+            ledger_lock: Mutex::new(()),
         });
         Ok(sdk)
     }
@@ -396,12 +693,15 @@ impl LiquidSdkBuilder {
 pub struct LiquidSdk {
     pub(crate) config: Config,
     pub(crate) onchain_wallet: Arc<dyn OnchainWallet>,
-    pub(crate) signer: Arc<Box<dyn Signer>>,
+    pub(crate) signer: Arc<dyn AsyncSigner>,
+    pub(crate) watch_only: bool,
+    pub(crate) multisig: bool,
     pub(crate) persister: std::sync::Arc<Persister>,
     pub(crate) rest_client: Arc<dyn RestClient>,
     pub(crate) event_manager: Arc<EventManager>,
     pub(crate) status_stream: Arc<dyn SwapperStatusStream>,
     pub(crate) swapper: Arc<dyn Swapper>,
+    pub(crate) swapper_registry: Arc<SwapperRegistry>,
     pub(crate) recoverer: Arc<Recoverer>,
     pub(crate) liquid_chain_service: Arc<dyn LiquidChainService>,
     pub(crate) bitcoin_chain_service: Arc<dyn BitcoinChainService>,
@@ -413,11 +713,16 @@ pub struct LiquidSdk {
     pub(crate) sync_service: Option<Arc<SyncService>>,
     pub(crate) receive_swap_handler: ReceiveSwapHandler,
     pub(crate) chain_swap_handler: Arc<ChainSwapHandler>,
+    pub(crate) peg_handler: PegHandler,
+    pub(crate) asset_registry: Arc<AssetRegistry>,
     pub(crate) payjoin_service: Arc<dyn PayjoinService>,
     pub(crate) buy_bitcoin_service: Arc<dyn BuyBitcoinApi>,
     pub(crate) external_input_parsers: Vec<ExternalInputParser>,
     pub(crate) background_task_handles: Mutex<Vec<TaskHandle>>,
     pub(crate) plugins: Mutex<HashMap<String, Arc<dyn Plugin>>>,
+    /// Held while checking a sub-account or the unallocated balance and updating the ledger,
+    /// so concurrent sends, transfers and reconciliations cannot overdraw them
+    pub(crate) ledger_lock: Mutex<()>,
 }
 
 impl LiquidSdk {
@@ -431,12 +736,16 @@ impl LiquidSdk {
     ///     * `mnemonic` - the optional Liquid wallet mnemonic
     ///     * `passphrase` - the optional passphrase for the mnemonic
     ///     * `seed` - the optional Liquid wallet seed
+    ///     * `account_index` - the optional BIP44 account of the wallet
     /// * `plugins` - the [Plugin]s which should be loaded by the SDK at startup
     pub async fn connect(req: ConnectRequest) -> Result<Arc<LiquidSdk>> {
         let signer = Self::default_signer(&req)?;
 
         Self::connect_with_signer(
-            ConnectWithSignerRequest { config: req.config },
+            ConnectWithSignerRequest {
+                config: req.config,
+                account_index: req.account_index,
+            },
             Box::new(signer),
         )
         .inspect_err(|e| error!("Failed to connect: {e:?}"))
@@ -459,6 +768,25 @@ impl LiquidSdk {
     pub async fn connect_with_signer(
         req: ConnectWithSignerRequest,
         signer: Box<dyn Signer>,
+    ) -> Result<Arc<LiquidSdk>> {
+        Self::connect_with_async_signer(req, Arc::new(SyncSignerAdapter::new(Arc::new(signer))))
+            .await
+    }
+
+    /// Initializes the SDK services with an [AsyncSigner] and starts the background tasks.
+    ///
+    /// Unlike [LiquidSdk::connect_with_signer], the signer may take its time to answer, e.g.
+    /// while waiting for the user to confirm on a hardware wallet.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - the [ConnectWithSignerRequest] containing:
+    ///     * `config` - the SDK [Config]
+    ///     * `account_index` - the optional BIP44 account of the wallet
+    /// * `signer` - the [AsyncSigner] holding the wallet keys
+    pub async fn connect_with_async_signer(
+        req: ConnectWithSignerRequest,
+        signer: Arc<dyn AsyncSigner>,
     ) -> Result<Arc<LiquidSdk>> {
         let start_ts = Instant::now();
 
@@ -470,13 +798,97 @@ impl LiquidSdk {
         #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
         std::fs::create_dir_all(&req.config.working_dir)?;
 
-        let sdk = LiquidSdkBuilder::new(
-            req.config,
-            PRODUCTION_BREEZSERVER_URL.into(),
-            Arc::new(signer),
-        )?
-        .build()
-        .await?;
+        let server_url = req.config.breez_server_url();
+        let mut sdk_builder =
+            LiquidSdkBuilder::new_with_async_signer(req.config, server_url, signer)?;
+        sdk_builder.account_index(req.account_index.unwrap_or_default());
+        let sdk = sdk_builder.build().await?;
+        sdk.start().await?;
+
+        let init_time = Instant::now().duration_since(start_ts);
+        utils::log_print_header(init_time);
+
+        Ok(sdk)
+    }
+
+    /// Initializes a watch-only SDK from a CT descriptor and starts the background tasks.
+    ///
+    /// The SDK holds no private keys: balances, payments and Liquid receive addresses are
+    /// available, while sending, claiming and refunding fail with [SdkError::WatchOnly].
+    /// The real-time sync service is not used, and the wallet fingerprint is that of the
+    /// descriptor's account xpub.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - the [ConnectWatchOnlyRequest] containing:
+    ///     * `config` - the SDK [Config]
+    ///     * `ct_descriptor` - the CT descriptor of the wallet to watch
+    pub async fn connect_watch_only(req: ConnectWatchOnlyRequest) -> Result<Arc<LiquidSdk>> {
+        let start_ts = Instant::now();
+
+        // Testnet is not currently supported
+        if req.config.network == LiquidNetwork::Testnet {
+            return Err(SdkError::network_not_supported(req.config.network).into());
+        }
+
+        #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+        std::fs::create_dir_all(&req.config.working_dir)?;
+
+        let server_url = req.config.breez_server_url();
+        let sdk = LiquidSdkBuilder::new_watch_only(req.config, server_url, &req.ct_descriptor)?
+            .build()
+            .await?;
+        sdk.start().await?;
+
+        let init_time = Instant::now().duration_since(start_ts);
+        utils::log_print_header(init_time);
+
+        Ok(sdk)
+    }
+
+    /// Initializes an SDK using a k-of-n multisig wallet and starts the background tasks.
+    ///
+    /// Wallet txs are never fully signed by the SDK: payments are prepared with
+    /// [PrepareSendRequest::export_pset], cosigned with [LiquidSdk::sign_pset] and by the other
+    /// cosigners, combined with [LiquidSdk::combine_psets] and then executed with
+    /// [LiquidSdk::finalize_send_payment]. Swap claims and refunds are signed by the hot key.
+    /// Without a hot key, the SDK is watch-only and swaps cannot be created.
+    /// Real-time sync is not available for multisig wallets.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - the [ConnectMultisigRequest] containing:
+    ///     * `config` - the SDK [Config]
+    ///     * `ct_descriptor` - the multisig CT descriptor of the wallet
+    ///     * `mnemonic` - the optional mnemonic of the hot key
+    ///     * `passphrase` - the optional passphrase for the mnemonic
+    pub async fn connect_multisig(req: ConnectMultisigRequest) -> Result<Arc<LiquidSdk>> {
+        let start_ts = Instant::now();
+
+        // Testnet is not currently supported
+        if req.config.network == LiquidNetwork::Testnet {
+            return Err(SdkError::network_not_supported(req.config.network).into());
+        }
+
+        #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+        std::fs::create_dir_all(&req.config.working_dir)?;
+
+        let hot_signer = match &req.mnemonic {
+            Some(mnemonic) => {
+                let signer: Box<dyn Signer> = Box::new(SdkSigner::new(
+                    mnemonic,
+                    req.passphrase.as_deref().unwrap_or_default(),
+                    req.config.network == LiquidNetwork::Mainnet,
+                )?);
+                Some(Arc::new(signer))
+            }
+            None => None,
+        };
+        let server_url = req.config.breez_server_url();
+        let sdk =
+            LiquidSdkBuilder::new_multisig(req.config, server_url, &req.ct_descriptor, hot_signer)?
+                .build()
+                .await?;
         sdk.start().await?;
 
         let init_time = Instant::now().duration_since(start_ts);
@@ -575,6 +987,11 @@ impl LiquidSdk {
         Ok(())
     }
 
+    fn ensure_not_watch_only(&self) -> SdkResult<()> {
+        ensure_sdk!(!self.watch_only, SdkError::WatchOnly);
+        Ok(())
+    }
+
     /// Disconnects the [LiquidSdk] instance and stops the background tasks.
     pub async fn disconnect(&self) -> SdkResult<()> {
         self.ensure_is_started().await?;
@@ -699,14 +1116,24 @@ impl LiquidSdk {
                 .update_blockchain_info(liquid_tip, sync_context.maybe_bitcoin_tip)
                 .unwrap_or_else(|err| warn!("Could not update local tips: {err:?}"));
 
+            let chain_tips = ChainTips {
+                liquid_tip,
+                bitcoin_tip: sync_context.maybe_bitcoin_tip,
+            };
+            let mut recoverable_swaps = sync_context.recoverable_swaps;
+            let reorged_chains = self.handle_reorgs(chain_tips).await;
+            if !reorged_chains.is_empty() {
+                // Swaps rolled back by the reorg are monitored again, so include them
+                // in this sync to recover their state from the new best chain
+                match self.get_monitored_swaps_list(false, true, chain_tips).await {
+                    Ok(swaps) => recoverable_swaps = swaps,
+                    Err(e) => warn!("Could not list monitored swaps after reorg: {e:?}"),
+                }
+            }
+
+            let is_wallet_scanned = reorged_chains.contains(&Blockchain::Liquid);
             if let Err(e) = self
-                .sync_inner(
-                    sync_context.recoverable_swaps,
-                    ChainTips {
-                        liquid_tip,
-                        bitcoin_tip: sync_context.maybe_bitcoin_tip,
-                    },
-                )
+                .sync_inner(recoverable_swaps, chain_tips, is_wallet_scanned)
                 .await
             {
                 error!("Failed to sync while tracking new blocks: {e}");
@@ -729,6 +1156,10 @@ impl LiquidSdk {
             self.send_swap_handler
                 .on_liquid_block(*current_liquid_block)
                 .await;
+            self.peg_handler
+                .on_liquid_block(*current_liquid_block)
+                .await;
+            self.settle_received_assets().await;
         }
         if sync_context.is_new_bitcoin_block {
             self.chain_swap_handler
@@ -740,82 +1171,479 @@ impl LiquidSdk {
             self.send_swap_handler
                 .on_bitcoin_block(*current_bitcoin_block)
                 .await;
+            self.peg_handler
+                .on_bitcoin_block(*current_bitcoin_block)
+                .await;
         }
     }
 
-    fn start_track_new_blocks_task(self: &Arc<LiquidSdk>) -> tokio::task::JoinHandle<()> {
-        let cloned = self.clone();
-
-        let track_new_blocks_future = async move {
-            let last_blockchain_info = cloned
-                .get_info()
-                .await
-                .map(|i| i.blockchain_info)
-                .unwrap_or_default();
+    /// Checks both chains for reorganizations, rolling back the confirmations that were
+    /// part of the disconnected blocks. Returns the chains whose confirmations were rolled back.
+    ///
+    /// Rolling back a Liquid reorg rescans the wallet, so the rest of the sync can skip its own scan.
+    async fn handle_reorgs(&self, chain_tips: ChainTips) -> HashSet<Blockchain> {
+        let mut tips = vec![(Blockchain::Liquid, chain_tips.liquid_tip)];
+        if let Some(bitcoin_tip) = chain_tips.bitcoin_tip {
+            tips.push((Blockchain::Bitcoin, bitcoin_tip));
+        }
 
-            let mut current_liquid_block: u32 = last_blockchain_info.liquid_tip;
-            let mut current_bitcoin_block: u32 = last_blockchain_info.bitcoin_tip;
-            cloned
-                .track_new_blocks(&mut current_liquid_block, &mut current_bitcoin_block)
-                .await;
-            loop {
-                tokio::time::sleep(Duration::from_secs(
-                    cloned.config.onchain_sync_period_sec as u64,
-                ))
-                .await;
-                cloned
-                    .track_new_blocks(&mut current_liquid_block, &mut current_bitcoin_block)
-                    .await;
+        let mut reorged_chains = HashSet::new();
+        for (chain, tip) in tips {
+            let (fork_height, depth) = match self.detect_reorg(chain, tip).await {
+                Ok(Some(reorg)) => reorg,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!("Could not check for {chain:?} reorg: {e:?}");
+                    continue;
+                }
+            };
+            warn!("Detected {chain:?} reorg of depth {depth} (fork height {fork_height})");
+            let res = match chain {
+                Blockchain::Liquid => self.rollback_liquid_confirmations().await,
+                Blockchain::Bitcoin => self.rollback_bitcoin_confirmations().await,
+            };
+            match res {
+                Ok(()) => {
+                    reorged_chains.insert(chain);
+                }
+                Err(e) => {
+                    error!("Failed to roll back {chain:?} confirmations after reorg: {e:?}")
+                }
             }
-        };
+            self.notify_event_listeners(SdkEvent::Reorg { chain, depth })
+                .await;
+        }
+        reorged_chains
+    }
 
-        let shutdown_receiver = self.shutdown_receiver.clone();
-        info!("Starting track-new-blocks task");
-        tokio::spawn(async move {
-            run_with_shutdown(
-                shutdown_receiver,
-                "Received shutdown signal, exiting track blocks loop",
-                track_new_blocks_future,
-            )
-            .await
+    async fn get_block_hash(&self, chain: Blockchain, height: u32) -> Result<String> {
+        Ok(match chain {
+            Blockchain::Liquid => self
+                .liquid_chain_service
+                .get_block_hash(height)
+                .await?
+                .to_string(),
+            Blockchain::Bitcoin => self
+                .bitcoin_chain_service
+                .get_block_hash(height)
+                .await?
+                .to_string(),
         })
     }
 
-    fn track_swap_updates(self: &Arc<LiquidSdk>) -> tokio::task::JoinHandle<()> {
-        let cloned = self.clone();
-        let track_swap_updates_future = async move {
-            let mut updates_stream = cloned.status_stream.subscribe_swap_updates();
-            let mut invoice_request_stream = cloned.status_stream.subscribe_invoice_requests();
-            let swaps_streams = vec![
-                cloned.send_swap_handler.subscribe_payment_updates(),
-                cloned.receive_swap_handler.subscribe_payment_updates(),
-                cloned.chain_swap_handler.subscribe_payment_updates(),
-            ];
-            let mut combined_swap_streams =
-                select_all(swaps_streams.into_iter().map(BroadcastStream::new));
-            loop {
-                tokio::select! {
-                    payment_id = combined_swap_streams.next() => {
-                      if let Some(payment_id) = payment_id {
-                        match payment_id {
-                            Ok(payment_id) => {
-                              if let Err(e) = cloned.emit_payment_updated(Some(payment_id)).await {
-                                error!("Failed to emit payment update: {e:?}");
-                              }
-                            }
-                            Err(e) => error!("Failed to receive swap state change: {e:?}")
-                        }
-                      }
-                    }
-                    update = updates_stream.recv() => match update {
-                        Ok(update) => {
-                            let id = &update.id;
-                            match cloned.persister.fetch_swap_by_id(id) {
-                                Ok(Swap::Send(_)) => match cloned.send_swap_handler.on_new_status(&update).await {
-                                    Ok(_) => info!("Successfully handled Send Swap {id} update"),
-                                    Err(e) => error!("Failed to handle Send Swap {id} update: {e}")
-                                },
-                                Ok(Swap::Receive(_)) => match cloned.receive_swap_handler.on_new_status(&update).await {
+    /// Compares the tracked block hashes of a chain against its current best chain, then
+    /// tracks the hash of the current tip.
+    ///
+    /// Returns the fork height and the depth of the reorg, if any tracked block was disconnected.
+    async fn detect_reorg(&self, chain: Blockchain, tip: u32) -> Result<Option<(u32, u32)>> {
+        let tracked_headers = self.persister.list_block_headers(chain)?;
+
+        let mut reorg = None;
+        if let (Some(highest), Some(lowest)) = (tracked_headers.first(), tracked_headers.last()) {
+            // If none of the tracked blocks is still in the best chain, the fork is below them
+            let mut fork_height = lowest.height.saturating_sub(1);
+            for header in &tracked_headers {
+                // Blocks above the tip are no longer part of the best chain
+                if header.height > tip {
+                    continue;
+                }
+                if self.get_block_hash(chain, header.height).await? == header.hash {
+                    fork_height = header.height;
+                    break;
+                }
+            }
+            if fork_height < highest.height {
+                self.persister
+                    .delete_block_headers_above(chain, fork_height)?;
+                reorg = Some((fork_height, highest.height - fork_height));
+            }
+        }
+
+        let hash = self.get_block_hash(chain, tip).await?;
+        self.persister.insert_or_update_block_header(&BlockHeader {
+            chain,
+            height: tip,
+            hash,
+        })?;
+        self.persister
+            .delete_block_headers_below(chain, tip.saturating_sub(REORG_TRACKING_DEPTH))?;
+
+        Ok(reorg)
+    }
+
+    /// Marks the Liquid payment txs that are no longer confirmed as unconfirmed, and moves the
+    /// swaps that were finalized by any of them back to a pending state.
+    async fn rollback_liquid_confirmations(&self) -> Result<()> {
+        self.onchain_wallet.full_scan().await?;
+        let wallet_txs = self.onchain_wallet.transactions_by_tx_id().await?;
+
+        let reorged_tx_ids: HashSet<String> = self
+            .persister
+            .list_confirmed_payment_tx_ids()?
+            .into_iter()
+            .filter(|tx_id| {
+                lwk_wollet::elements::Txid::from_str(tx_id)
+                    .ok()
+                    .and_then(|txid| wallet_txs.get(&txid))
+                    .and_then(|tx| tx.height)
+                    .is_none()
+            })
+            .collect();
+        if reorged_tx_ids.is_empty() {
+            return Ok(());
+        }
+        info!("Rolling back confirmation of txs: {reorged_tx_ids:?}");
+        self.persister
+            .mark_payment_txs_unconfirmed(&reorged_tx_ids.iter().cloned().collect::<Vec<_>>())?;
+
+        let is_reorged =
+            |tx_id: &Option<String>| tx_id.as_ref().is_some_and(|id| reorged_tx_ids.contains(id));
+        let mut swap_tx_ids = HashSet::new();
+
+        for swap in self.persister.list_receive_swaps_by_state(vec![Complete])? {
+            if is_reorged(&swap.claim_tx_id) || is_reorged(&swap.mrh_tx_id) {
+                swap_tx_ids.extend([swap.claim_tx_id.clone(), swap.mrh_tx_id.clone()]);
+                self.receive_swap_handler.update_swap(ReceiveSwap {
+                    state: Pending,
+                    ..swap
+                })?;
+            }
+        }
+        for swap in self.persister.list_send_swaps_by_state(vec![Failed])? {
+            if is_reorged(&swap.refund_tx_id) {
+                swap_tx_ids.insert(swap.refund_tx_id.clone());
+                self.send_swap_handler.update_swap(SendSwap {
+                    state: RefundPending,
+                    ..swap
+                })?;
+            }
+        }
+        for swap in self
+            .persister
+            .list_chain_swaps_by_state(vec![Complete, Failed])?
+        {
+            let state = match (swap.direction, swap.state) {
+                (Direction::Incoming, Complete) if is_reorged(&swap.claim_tx_id) => {
+                    swap_tx_ids.insert(swap.claim_tx_id.clone());
+                    Pending
+                }
+                (Direction::Outgoing, Failed) if is_reorged(&swap.refund_tx_id) => {
+                    swap_tx_ids.insert(swap.refund_tx_id.clone());
+                    RefundPending
+                }
+                _ => continue,
+            };
+            self.chain_swap_handler
+                .update_swap(ChainSwap { state, ..swap })?;
+        }
+
+        // Swap payment updates are emitted by the swap handlers
+        for tx_id in reorged_tx_ids {
+            if !swap_tx_ids.contains(&Some(tx_id.clone())) {
+                self.emit_payment_updated(Some(tx_id)).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves the outgoing chain swaps whose BTC claim tx is no longer confirmed back to pending.
+    ///
+    /// Only the states derived from a BTC confirmation are rolled back. Incoming swaps become
+    /// refundable because of a swapper update (lockup failed, expired), which a reorg does not undo.
+    async fn rollback_bitcoin_confirmations(&self) -> Result<()> {
+        for swap in self.persister.list_chain_swaps_by_state(vec![Complete])? {
+            let (Direction::Outgoing, Some(claim_tx_id)) = (swap.direction, &swap.claim_tx_id)
+            else {
+                continue;
+            };
+            let Some(claim_script) = swap
+                .get_claim_swap_script()
+                .ok()
+                .and_then(|script| script.as_bitcoin_script().ok())
+                .and_then(|script| script.funding_addrs.map(|addr| addr.script_pubkey()))
+            else {
+                warn!(
+                    "BTC claim script not found for Onchain Send Swap {}",
+                    swap.id
+                );
+                continue;
+            };
+
+            // The tx may have been mined again in a block of the new best chain
+            let history = self
+                .bitcoin_chain_service
+                .get_script_history(&claim_script)
+                .await?;
+            let is_still_confirmed = history
+                .iter()
+                .any(|h| h.txid.to_string() == *claim_tx_id && h.confirmed());
+            if !is_still_confirmed {
+                info!("Rolling back Chain Swap {} after Bitcoin reorg", swap.id);
+                self.chain_swap_handler.update_swap(ChainSwap {
+                    state: Pending,
+                    ..swap
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks whether the outpoints spent by accepted zero-conf txs were spent by a
+    /// conflicting tx. Watches are dropped once the watched tx confirms.
+    ///
+    /// The chain data of all watched outpoints is fetched in batches: the txs funding them,
+    /// the history of the scripts they fund, then the txs of these histories.
+    async fn check_double_spends(&self) -> Result<()> {
+        let watched_outpoints = self.persister.list_watched_outpoints()?;
+        if watched_outpoints.is_empty() {
+            return Ok(());
+        }
+        let outpoints = watched_outpoints
+            .iter()
+            .map(|watched| lwk_wollet::elements::OutPoint::from_str(&watched.outpoint))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let prev_txids: Vec<_> = outpoints
+            .iter()
+            .map(|outpoint| outpoint.txid)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let prev_txs: HashMap<_, _> = self
+            .liquid_chain_service
+            .get_transactions(&prev_txids)
+            .await?
+            .into_iter()
+            .map(|tx| (tx.txid(), tx))
+            .collect();
+
+        let scripts: Vec<_> = outpoints
+            .iter()
+            .filter_map(|outpoint| {
+                prev_txs
+                    .get(&outpoint.txid)
+                    .and_then(|tx| tx.output.get(outpoint.vout as usize))
+                    .map(|output| output.script_pubkey.clone())
+            })
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        if scripts.is_empty() {
+            return Ok(());
+        }
+        let history: Vec<LBtcHistory> = self
+            .liquid_chain_service
+            .get_scripts_history_with_retry(&scripts, 3)
+            .await?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        let candidate_txids: Vec<_> = history
+            .iter()
+            .map(|h| h.txid)
+            .filter(|txid| !prev_txs.contains_key(txid))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let candidate_txs = self
+            .liquid_chain_service
+            .get_transactions(&candidate_txids)
+            .await?;
+
+        let mut handled_tx_ids = HashSet::new();
+        for (watched, outpoint) in watched_outpoints.iter().zip(outpoints) {
+            if handled_tx_ids.contains(&watched.tx_id) {
+                continue;
+            }
+            let Some(spender) = candidate_txs
+                .iter()
+                .chain(prev_txs.values())
+                .find(|tx| tx.input.iter().any(|i| i.previous_output == outpoint))
+            else {
+                continue;
+            };
+
+            let spender_tx_id = spender.txid();
+            if spender_tx_id.to_string() == watched.tx_id {
+                let is_confirmed = history
+                    .iter()
+                    .any(|h| h.txid == spender_tx_id && h.confirmed());
+                if is_confirmed {
+                    self.persister.delete_watched_outpoints(&watched.tx_id)?;
+                    handled_tx_ids.insert(watched.tx_id.clone());
+                }
+                continue;
+            }
+
+            warn!(
+                "Outpoint {} of tx {} was double-spent by tx {spender_tx_id}",
+                watched.outpoint, watched.tx_id
+            );
+            self.persister
+                .set_watched_outpoints_conflict(&watched.tx_id, &spender_tx_id.to_string())?;
+            self.handle_double_spend(watched, spender).await?;
+            handled_tx_ids.insert(watched.tx_id.clone());
+        }
+        Ok(())
+    }
+
+    /// Fails the payment of a double-spent tx. Swaps are instead held if the conflicting tx
+    /// still funds the swap script, so they can be claimed once the replacement confirms.
+    ///
+    /// Swaps are only affected if the double-spent tx is their current lockup.
+    async fn handle_double_spend(
+        &self,
+        watched: &WatchedOutpoint,
+        conflicting_tx: &lwk_wollet::elements::Transaction,
+    ) -> Result<()> {
+        let pays_to = |script: &lwk_wollet::elements::Script| {
+            conflicting_tx
+                .output
+                .iter()
+                .any(|out| &out.script_pubkey == script)
+        };
+
+        if let Some(swap) = self
+            .persister
+            .fetch_receive_swap_by_id(&watched.payment_id)?
+        {
+            if swap.lockup_tx_id.as_ref() != Some(&watched.tx_id) {
+                info!(
+                    "Double-spent tx {} is not the lockup of Receive Swap {}",
+                    watched.tx_id, swap.id
+                );
+                return Ok(());
+            }
+            let state = match swap.claim_script() {
+                Ok(claim_script) if pays_to(&claim_script) => {
+                    info!(
+                        "Holding Receive Swap {} until the replaced lockup confirms",
+                        swap.id
+                    );
+                    Pending
+                }
+                _ => Failed,
+            };
+            self.receive_swap_handler.update_swap(ReceiveSwap {
+                state,
+                claim_tx_id: None,
+                ..swap
+            })?;
+        } else if let Some(swap) = self.persister.fetch_chain_swap_by_id(&watched.payment_id)? {
+            if swap.server_lockup_tx_id.as_ref() != Some(&watched.tx_id) {
+                info!(
+                    "Double-spent tx {} is not the server lockup of Chain Swap {}",
+                    watched.tx_id, swap.id
+                );
+                return Ok(());
+            }
+            let claim_script = swap
+                .get_claim_swap_script()
+                .ok()
+                .and_then(|script| script.as_liquid_script().ok())
+                .and_then(|script| script.funding_addrs.map(|addr| addr.script_pubkey()));
+            let state = match claim_script {
+                Some(claim_script) if pays_to(&claim_script) => {
+                    info!(
+                        "Holding Chain Swap {} until the replaced server lockup confirms",
+                        swap.id
+                    );
+                    Pending
+                }
+                _ => Failed,
+            };
+            self.chain_swap_handler.update_swap(ChainSwap {
+                state,
+                claim_tx_id: None,
+                ..swap
+            })?;
+        } else {
+            self.persister.set_payment_tx_double_spent(&watched.tx_id)?;
+        }
+
+        if let Some(payment) = self.persister.get_payment(&watched.payment_id)? {
+            self.notify_event_listeners(SdkEvent::PaymentDoubleSpent { details: payment })
+                .await;
+        }
+        Ok(())
+    }
+
+    fn start_track_new_blocks_task(self: &Arc<LiquidSdk>) -> tokio::task::JoinHandle<()> {
+        let cloned = self.clone();
+
+        let track_new_blocks_future = async move {
+            let last_blockchain_info = cloned
+                .get_info()
+                .await
+                .map(|i| i.blockchain_info)
+                .unwrap_or_default();
+
+            let mut current_liquid_block: u32 = last_blockchain_info.liquid_tip;
+            let mut current_bitcoin_block: u32 = last_blockchain_info.bitcoin_tip;
+            cloned
+                .track_new_blocks(&mut current_liquid_block, &mut current_bitcoin_block)
+                .await;
+            loop {
+                tokio::time::sleep(Duration::from_secs(
+                    cloned.config.onchain_sync_period_sec as u64,
+                ))
+                .await;
+                cloned
+                    .track_new_blocks(&mut current_liquid_block, &mut current_bitcoin_block)
+                    .await;
+            }
+        };
+
+        let shutdown_receiver = self.shutdown_receiver.clone();
+        info!("Starting track-new-blocks task");
+        tokio::spawn(async move {
+            run_with_shutdown(
+                shutdown_receiver,
+                "Received shutdown signal, exiting track blocks loop",
+                track_new_blocks_future,
+            )
+            .await
+        })
+    }
+
+    fn track_swap_updates(self: &Arc<LiquidSdk>) -> tokio::task::JoinHandle<()> {
+        let cloned = self.clone();
+        let track_swap_updates_future = async move {
+            let mut updates_stream = cloned.status_stream.subscribe_swap_updates();
+            let mut invoice_request_stream = cloned.status_stream.subscribe_invoice_requests();
+            let swaps_streams = vec![
+                cloned.send_swap_handler.subscribe_payment_updates(),
+                cloned.receive_swap_handler.subscribe_payment_updates(),
+                cloned.chain_swap_handler.subscribe_payment_updates(),
+                cloned.peg_handler.subscribe_payment_updates(),
+                cloned.asset_registry.subscribe_payment_updates(),
+            ];
+            let mut combined_swap_streams =
+                select_all(swaps_streams.into_iter().map(BroadcastStream::new));
+            loop {
+                tokio::select! {
+                    payment_id = combined_swap_streams.next() => {
+                      if let Some(payment_id) = payment_id {
+                        match payment_id {
+                            Ok(payment_id) => {
+                              if let Err(e) = cloned.emit_payment_updated(Some(payment_id)).await {
+                                error!("Failed to emit payment update: {e:?}");
+                              }
+                            }
+                            Err(e) => error!("Failed to receive swap state change: {e:?}")
+                        }
+                      }
+                    }
+                    update = updates_stream.recv() => match update {
+                        Ok(update) => {
+                            let id = &update.id;
+                            match cloned.persister.fetch_swap_by_id(id) {
+                                Ok(Swap::Send(_)) => match cloned.send_swap_handler.on_new_status(&update).await {
+                                    Ok(_) => info!("Successfully handled Send Swap {id} update"),
+                                    Err(e) => error!("Failed to handle Send Swap {id} update: {e}")
+                                },
+                                Ok(Swap::Receive(_)) => match cloned.receive_swap_handler.on_new_status(&update).await {
                                     Ok(_) => info!("Successfully handled Receive Swap {id} update"),
                                     Err(e) => error!("Failed to handle Receive Swap {id} update: {e}")
                                 },
@@ -831,7 +1659,7 @@ impl LiquidSdk {
                         Err(e) => error!("Received update stream error: {e:?}"),
                     },
                     invoice_request_res = invoice_request_stream.recv() => match invoice_request_res {
-                        Ok(boltz_client::boltz::InvoiceRequest{id, offer, invoice_request}) => {
+                        Ok(InvoiceRequest{id, offer, invoice_request}) => {
                             match cloned.create_bolt12_invoice(&CreateBolt12InvoiceRequest { offer, invoice_request }).await {
                                 Ok(response) => {
                                     match cloned.status_stream.send_invoice_created(&id, &response.invoice) {
@@ -899,6 +1727,11 @@ impl LiquidSdk {
             match self.persister.get_payment(&id)? {
                 Some(payment) => {
                     self.update_wallet_info().await?;
+                    if matches!(payment.status, Complete | Failed | RefundPending) {
+                        if let Err(e) = self.reconcile_ledger().await {
+                            warn!("Failed to reconcile the sub-account ledger: {e:?}");
+                        }
+                    }
                     match payment.status {
                         Complete => {
                             self.notify_event_listeners(SdkEvent::PaymentSucceeded {
@@ -1035,9 +1868,27 @@ impl LiquidSdk {
         }
     }
 
+    /// Lists the BIP44 accounts of the wallet seed that have a tx history. Any of them can be
+    /// used by connecting with its [ConnectRequest::account_index].
+    ///
+    /// The accounts are discovered in order, stopping once several consecutive accounts have
+    /// no history, so an account left unused does not hide the ones after it.
+    pub async fn list_accounts(&self) -> SdkResult<Vec<AccountInfo>> {
+        self.ensure_not_watch_only()?;
+        let signer = SdkLwkSigner::new(self.signer.clone())
+            .await
+            .map_err(|e| SdkError::generic(format!("Could not load the signer keys: {e}")))?;
+        discover_accounts(|account_index| {
+            crate::wallet::account_tx_count(&self.config, &signer, account_index)
+        })
+        .await
+        .map_err(|err| SdkError::generic(format!("Failed to scan account: {err}")))
+    }
+
     /// Sign given message with the private key. Returns a zbase encoded signature.
-    pub fn sign_message(&self, req: &SignMessageRequest) -> SdkResult<SignMessageResponse> {
-        let signature = self.onchain_wallet.sign_message(&req.message)?;
+    pub async fn sign_message(&self, req: &SignMessageRequest) -> SdkResult<SignMessageResponse> {
+        self.ensure_not_watch_only()?;
+        let signature = self.onchain_wallet.sign_message(&req.message).await?;
         Ok(SignMessageResponse { signature })
     }
 
@@ -1093,9 +1944,7 @@ impl LiquidSdk {
                 .timestamp()
                 .duration_since(std::time::SystemTime::UNIX_EPOCH)
                 .map_err(|_| PaymentError::invalid_invoice("Invalid invoice timestamp"))?;
-        if let Ok(elapsed_web_time) =
-            web_time::SystemTime::now().duration_since(invoice_ts_web_time)
-        {
+        if let Ok(elapsed_web_time) = utils::system_time().duration_since(invoice_ts_web_time) {
             ensure_sdk!(
                 elapsed_web_time <= invoice.expiry_time(),
                 PaymentError::invalid_invoice("Invoice has expired")
@@ -1147,26 +1996,32 @@ impl LiquidSdk {
 
     /// For submarine swaps (Liquid -> LN), the output amount (invoice amount) is checked if it fits
     /// the pair limits. This is unlike all the other swap types, where the input amount is checked.
+    ///
+    /// Returns the pair along with the id of the swap provider it was fetched from
     async fn validate_submarine_pairs(
         &self,
         receiver_amount_sat: u64,
-    ) -> Result<SubmarinePair, PaymentError> {
-        let lbtc_pair = self
-            .swapper
-            .get_submarine_pairs()
-            .await?
-            .ok_or(PaymentError::PairsNotFound)?;
+        swap_provider: Option<&str>,
+    ) -> Result<(String, SubmarinePair), PaymentError> {
+        let (swap_provider, lbtc_pair) = self
+            .swapper_registry
+            .get_submarine_pair(swap_provider)
+            .await?;
 
         lbtc_pair.limits.within(receiver_amount_sat)?;
 
-        Ok(lbtc_pair)
+        Ok((swap_provider, lbtc_pair))
     }
 
-    async fn get_chain_pair(&self, direction: Direction) -> Result<ChainPair, PaymentError> {
-        self.swapper
-            .get_chain_pair(direction)
-            .await?
-            .ok_or(PaymentError::PairsNotFound)
+    /// Returns the pair along with the id of the swap provider it was fetched from
+    async fn get_chain_pair(
+        &self,
+        direction: Direction,
+        swap_provider: Option<&str>,
+    ) -> Result<(String, ChainPair), PaymentError> {
+        self.swapper_registry
+            .get_chain_pair(swap_provider, direction)
+            .await
     }
 
     /// Validates if the `user_lockup_amount_sat` fits within the limits of this pair
@@ -1184,12 +2039,13 @@ impl LiquidSdk {
         &self,
         direction: Direction,
         user_lockup_amount_sat: Option<u64>,
-    ) -> Result<ChainPair, PaymentError> {
-        let pair = self.get_chain_pair(direction).await?;
+        swap_provider: Option<&str>,
+    ) -> Result<(String, ChainPair), PaymentError> {
+        let (swap_provider, pair) = self.get_chain_pair(direction, swap_provider).await?;
         if let Some(user_lockup_amount_sat) = user_lockup_amount_sat {
             self.validate_user_lockup_amount_for_chain_pair(&pair, user_lockup_amount_sat)?;
         }
-        Ok(pair)
+        Ok((swap_provider, pair))
     }
 
     /// Estimate the onchain fee for sending the given amount to the given destination address
@@ -1199,18 +2055,33 @@ impl LiquidSdk {
         address: &str,
         asset_id: &str,
     ) -> Result<u64, PaymentError> {
-        let fee_sat = self
-            .onchain_wallet
-            .build_tx(
-                Some(LIQUID_FEE_RATE_MSAT_PER_VBYTE),
-                address,
-                asset_id,
-                amount_sat,
-            )
-            .await?
-            .all_fees()
-            .values()
-            .sum::<u64>();
+        let fee_sat = match self.multisig {
+            // The hot key alone cannot sign a multisig tx, but the fees of the unsigned tx
+            // already account for the weight of the cosigner signatures
+            true => Self::pset_fees_sat(
+                &self
+                    .onchain_wallet
+                    .build_unsigned_tx(
+                        Some(LIQUID_FEE_RATE_MSAT_PER_VBYTE),
+                        address,
+                        asset_id,
+                        amount_sat,
+                    )
+                    .await?,
+            ),
+            false => self
+                .onchain_wallet
+                .build_tx(
+                    Some(LIQUID_FEE_RATE_MSAT_PER_VBYTE),
+                    address,
+                    asset_id,
+                    amount_sat,
+                )
+                .await?
+                .all_fees()
+                .values()
+                .sum::<u64>(),
+        };
         info!("Estimated tx fee: {fee_sat} sat");
         Ok(fee_sat)
     }
@@ -1294,6 +2165,38 @@ impl LiquidSdk {
         .await
     }
 
+    /// Estimates the lockup tx fee. If the lockup is funded from an asset, the wallet may not
+    /// have the L-BTC to build the tx yet, so its size is estimated instead.
+    async fn estimate_asset_funded_lockup_tx_fee(
+        &self,
+        user_lockup_amount_sat: u64,
+        is_asset_funded: bool,
+    ) -> Result<u64, PaymentError> {
+        match self
+            .estimate_lockup_tx_or_drain_tx_fee(user_lockup_amount_sat)
+            .await
+        {
+            Err(PaymentError::InsufficientFunds) if is_asset_funded => Ok(
+                (ESTIMATED_LOCKUP_TX_VSIZE as f64 * LIQUID_FEE_RATE_SAT_PER_VBYTE).ceil() as u64,
+            ),
+            res => res,
+        }
+    }
+
+    /// Returns the L-BTC network fee of a swap lockup tx, computed by `estimate_lbtc_fee`.
+    ///
+    /// When the lockup fee is paid in `fee_asset` via the payjoin service, no L-BTC is spent on
+    /// it: the fee is then estimated separately, in the asset, by the payjoin service.
+    async fn lockup_tx_fee_in_lbtc(
+        fee_asset: &Option<String>,
+        estimate_lbtc_fee: impl std::future::Future<Output = Result<u64, PaymentError>>,
+    ) -> Result<u64, PaymentError> {
+        match fee_asset {
+            Some(_) => Ok(0),
+            None => estimate_lbtc_fee.await,
+        }
+    }
+
     /// Prepares to pay a Lightning invoice via a submarine swap.
     ///
     /// # Arguments
@@ -1305,6 +2208,12 @@ impl LiquidSdk {
     ///        - [PayAmount::Drain] which uses all Bitcoin funds
     ///        - [PayAmount::Bitcoin] which sets the amount in satoshi that will be received
     ///        - [PayAmount::Asset] which sets the amount of an asset that will be received
+    ///        - [PayAmount::DrainAsset] which uses all funds of a non-Bitcoin asset
+    ///     * `fee_asset` - The optional asset to pay the swap lockup network fees of a Lightning
+    ///       payment with via the SideSwap payjoin service
+    ///     * `export_pset` - If set to true, the unsigned payment transaction is returned to be
+    ///       signed externally. Not supported for BOLT12 offers, whose invoice and swap are only
+    ///       created when sending, so there is no swap lockup to export when preparing.
     ///
     /// # Returns
     /// Returns a [PrepareSendResponse] containing:
@@ -1315,34 +2224,91 @@ impl LiquidSdk {
     ///     * `estimated_asset_fees` - the optional estimated fee in the asset. Is set when
     ///        [PayAmount::Asset::estimate_asset_fees] is set to `true`, the Payjoin service accepts
     ///        this asset to pay fees and there are funds available in this asset to pay fees.
+    ///        Also set when draining an asset with [PayAmount::DrainAsset] and no Bitcoin is
+    ///        available to pay fees.
     pub async fn prepare_send_payment(
         &self,
         req: &PrepareSendRequest,
     ) -> Result<PrepareSendResponse, PaymentError> {
         self.ensure_is_started().await?;
 
-        let use_mrh = match req.disable_mrh {
-            Some(disable_mrh) => !disable_mrh,
-            None => self.config.use_magic_routing_hints,
-        };
-
-        let timeout_sec = req
-            .payment_timeout_sec
-            .unwrap_or(self.config.payment_timeout_sec);
-
+        let from_asset = req
+            .from_asset
+            .clone()
+            .filter(|asset_id| asset_id.ne(&self.config.lbtc_asset_id()));
+        ensure_sdk!(
+            from_asset.is_none()
+                || !matches!(
+                    req.amount,
+                    Some(PayAmount::Drain | PayAmount::DrainAsset { .. })
+                ),
+            PaymentError::generic("Cannot drain when funding a payment from an asset")
+        );
+        let fee_asset = req
+            .fee_asset
+            .clone()
+            .filter(|asset_id| asset_id.ne(&self.config.lbtc_asset_id()));
+        ensure_sdk!(
+            fee_asset.is_none() || from_asset.is_none(),
+            PaymentError::generic("Cannot pay asset fees when funding a payment from an asset")
+        );
+        ensure_sdk!(
+            fee_asset.is_none() || !matches!(req.amount, Some(PayAmount::Drain)),
+            PaymentError::generic("Cannot drain when paying fees in an asset")
+        );
+        let export_pset = req.export_pset.unwrap_or_default();
+        ensure_sdk!(
+            !export_pset
+                || (from_asset.is_none()
+                    && fee_asset.is_none()
+                    && !matches!(
+                        req.amount,
+                        Some(PayAmount::Drain | PayAmount::DrainAsset { .. })
+                    )),
+            PaymentError::generic("Cannot export a PSET when draining or paying with an asset")
+        );
+        // A payment funded from an asset, paying its fees in an asset or exporting its PSET
+        // is always paid via a swap
+        let disable_mrh = match from_asset.is_some() || fee_asset.is_some() || export_pset {
+            true => Some(true),
+            false => req.disable_mrh,
+        };
+        let use_mrh = match disable_mrh {
+            Some(disable_mrh) => !disable_mrh,
+            None => self.config.use_magic_routing_hints,
+        };
+
+        let timeout_sec = req
+            .payment_timeout_sec
+            .unwrap_or(self.config.payment_timeout_sec);
+
         let get_info_res = self.get_info().await?;
         let fees_sat;
-        let estimated_asset_fees;
+        let mut estimated_asset_fees;
         let receiver_amount_sat;
         let asset_id;
         let payment_destination;
         let mut validate_funds = true;
         let mut exchange_amount_sat = None;
+        let mut swap_provider = None;
+        let mut swap_quotes = vec![];
 
         match self.parse(&req.destination).await {
             Ok(InputType::LiquidAddress {
                 address: mut liquid_address_data,
             }) => {
+                ensure_sdk!(
+                    from_asset.is_none(),
+                    PaymentError::generic(
+                        "Use PayAmount::Asset to pay a Liquid address from another asset"
+                    )
+                );
+                ensure_sdk!(
+                    fee_asset.is_none(),
+                    PaymentError::generic(
+                        "Use PayAmount::Asset to pay the fees of a Liquid payment in an asset"
+                    )
+                );
                 let amount = match (
                     liquid_address_data.amount,
                     liquid_address_data.amount_sat,
@@ -1413,6 +2379,57 @@ impl LiquidSdk {
                             None,
                         )
                     }
+                    PayAmount::DrainAsset { asset_id } => {
+                        ensure_sdk!(
+                            asset_id != self.config.lbtc_asset_id(),
+                            PaymentError::asset_error(
+                                "Use PayAmount::Drain to send all Bitcoin funds"
+                            )
+                        );
+                        let asset_metadata = self.persister.get_asset_metadata(&asset_id)?.ok_or(
+                            PaymentError::asset_error(format!("Asset {asset_id} is not supported")),
+                        )?;
+                        let drain_amount_sat: u64 = self
+                            .onchain_wallet
+                            .asset_utxos(&AssetId::from_str(&asset_id)?)
+                            .await?
+                            .iter()
+                            .map(|utxo| utxo.unblinded.value)
+                            .sum();
+                        ensure_sdk!(drain_amount_sat > 0, PaymentError::InsufficientFunds);
+
+                        // Sending the full asset amount spends every UTXO of the asset
+                        match self
+                            .estimate_onchain_tx_fee(
+                                drain_amount_sat,
+                                &liquid_address_data.address,
+                                &asset_id,
+                            )
+                            .await
+                        {
+                            Ok(fees_sat) => {
+                                info!("Drain asset amount: {drain_amount_sat} sat");
+                                (asset_id, drain_amount_sat, Some(fees_sat), None)
+                            }
+                            Err(PaymentError::InsufficientFunds) => {
+                                // There is no Bitcoin to pay the fees, so pay them in the
+                                // asset and deduct them from the drained amount
+                                let asset_fees = self
+                                    .payjoin_service
+                                    .estimate_payjoin_drain_tx_fee(&asset_id)
+                                    .await?;
+                                let asset_fees_sat = asset_metadata.amount_to_sat(asset_fees);
+                                ensure_sdk!(
+                                    drain_amount_sat > asset_fees_sat,
+                                    PaymentError::InsufficientFunds
+                                );
+                                let receiver_amount_sat = drain_amount_sat - asset_fees_sat;
+                                info!("Drain asset amount: {receiver_amount_sat} sat");
+                                (asset_id, receiver_amount_sat, None, Some(asset_fees))
+                            }
+                            Err(e) => return Err(e),
+                        }
+                    }
                     PayAmount::Bitcoin {
                         receiver_amount_sat,
                     } => {
@@ -1510,6 +2527,10 @@ impl LiquidSdk {
                 self.ensure_send_is_not_self_transfer(&invoice.bolt11)?;
                 self.validate_bolt11_invoice(&invoice.bolt11)?;
 
+                ensure_sdk!(
+                    !matches!(req.amount, Some(PayAmount::DrainAsset { .. })),
+                    PaymentError::generic("Cannot drain an asset balance to a Bolt11 invoice")
+                );
                 let invoice_amount_sat = invoice.amount_msat.ok_or(
                     PaymentError::amount_missing("Expected invoice with an amount"),
                 )? / 1000;
@@ -1526,7 +2547,9 @@ impl LiquidSdk {
                     );
                 }
 
-                let lbtc_pair = self.validate_submarine_pairs(invoice_amount_sat).await?;
+                let (pair_provider, lbtc_pair) = self
+                    .validate_submarine_pairs(invoice_amount_sat, req.swap_provider.as_deref())
+                    .await?;
                 let mrh_address = if use_mrh {
                     self.swapper
                         .check_for_mrh(&invoice.bolt11)
@@ -1565,10 +2588,20 @@ impl LiquidSdk {
                         // The BOLT11 invoice has no MRH (or MRH is disabled), so we calculate the fees using a swap
                         let boltz_fees_total = lbtc_pair.fees.total(invoice_amount_sat);
                         let user_lockup_amount_sat = invoice_amount_sat + boltz_fees_total;
-                        let lockup_fees_sat = self
-                            .estimate_lockup_tx_or_drain_tx_fee(user_lockup_amount_sat)
-                            .await?;
+                        let lockup_fees_sat = Self::lockup_tx_fee_in_lbtc(
+                            &fee_asset,
+                            self.estimate_asset_funded_lockup_tx_fee(
+                                user_lockup_amount_sat,
+                                from_asset.is_some(),
+                            ),
+                        )
+                        .await?;
                         let fees_sat = boltz_fees_total + lockup_fees_sat;
+                        swap_provider = Some(pair_provider);
+                        swap_quotes = self
+                            .swapper_registry
+                            .fetch_submarine_quotes(Some(invoice_amount_sat))
+                            .await;
                         (invoice_amount_sat, Some(fees_sat))
                     }
                 };
@@ -1582,9 +2615,14 @@ impl LiquidSdk {
                 offer,
                 bip353_address,
             }) => {
+                ensure_sdk!(
+                    !export_pset,
+                    PaymentError::generic("Cannot export a PSET to pay a Bolt12 offer")
+                );
                 asset_id = self.config.lbtc_asset_id();
                 estimated_asset_fees = None;
-                (receiver_amount_sat, fees_sat) = match req.amount {
+                let pair_provider;
+                (receiver_amount_sat, fees_sat, pair_provider) = match req.amount {
                     Some(PayAmount::Drain) => {
                         ensure_sdk!(
                             get_info_res.wallet_info.pending_receive_sat == 0
@@ -1593,11 +2631,10 @@ impl LiquidSdk {
                                 err: "Cannot drain while there are pending payments".to_string(),
                             }
                         );
-                        let lbtc_pair = self
-                            .swapper
-                            .get_submarine_pairs()
-                            .await?
-                            .ok_or(PaymentError::PairsNotFound)?;
+                        let (pair_provider, lbtc_pair) = self
+                            .swapper_registry
+                            .get_submarine_pair(req.swap_provider.as_deref())
+                            .await?;
                         let drain_fees_sat = self.estimate_drain_tx_fee(None, None).await?;
                         let drain_amount_sat =
                             get_info_res.wallet_info.balance_sat - drain_fees_sat;
@@ -1621,20 +2658,28 @@ impl LiquidSdk {
                         );
                         let fees_sat = Some(boltz_fees_total + drain_fees_sat);
                         info!("Drain amount: {receiver_amount_sat} sat");
-                        Ok((receiver_amount_sat, fees_sat))
+                        Ok((receiver_amount_sat, fees_sat, pair_provider))
                     }
                     Some(PayAmount::Bitcoin {
                         receiver_amount_sat,
                     }) => {
-                        let lbtc_pair = self.validate_submarine_pairs(receiver_amount_sat).await?;
-                        let boltz_fees_total = lbtc_pair.fees.total(receiver_amount_sat);
-                        let lockup_fees_sat = self
-                            .estimate_lockup_tx_or_drain_tx_fee(
-                                receiver_amount_sat + boltz_fees_total,
+                        let (pair_provider, lbtc_pair) = self
+                            .validate_submarine_pairs(
+                                receiver_amount_sat,
+                                req.swap_provider.as_deref(),
                             )
                             .await?;
+                        let boltz_fees_total = lbtc_pair.fees.total(receiver_amount_sat);
+                        let lockup_fees_sat = Self::lockup_tx_fee_in_lbtc(
+                            &fee_asset,
+                            self.estimate_asset_funded_lockup_tx_fee(
+                                receiver_amount_sat + boltz_fees_total,
+                                from_asset.is_some(),
+                            ),
+                        )
+                        .await?;
                         let fees_sat = Some(boltz_fees_total + lockup_fees_sat);
-                        Ok((receiver_amount_sat, fees_sat))
+                        Ok((receiver_amount_sat, fees_sat, pair_provider))
                     }
                     _ => Err(PaymentError::amount_missing(
                         "Expected PayAmount of type Receiver when processing a Bolt12 offer",
@@ -1648,6 +2693,11 @@ impl LiquidSdk {
                         )
                     );
                 }
+                swap_provider = Some(pair_provider);
+                swap_quotes = self
+                    .swapper_registry
+                    .fetch_submarine_quotes(Some(receiver_amount_sat))
+                    .await;
 
                 payment_destination = SendDestination::Bolt12 {
                     offer,
@@ -1660,6 +2710,39 @@ impl LiquidSdk {
             }
         };
 
+        if let Some(from_asset) = &from_asset {
+            // Quote the L-BTC missing from the balance to fund the swap lockup, including its fees
+            let shortfall_sat = (receiver_amount_sat + fees_sat.unwrap_or_default())
+                .saturating_sub(get_info_res.wallet_info.balance_sat);
+            exchange_amount_sat = Some(0);
+            if shortfall_sat > 0 {
+                let swap = SideSwapService::from_sdk(self)
+                    .await
+                    .get_asset_swap(
+                        AssetId::from_str(from_asset)?,
+                        utils::lbtc_asset_id(self.config.network),
+                        shortfall_sat,
+                    )
+                    .await?;
+                swap.check_sufficient_asset_balance(&get_info_res.wallet_info)?;
+                validate_funds = false;
+                exchange_amount_sat = Some(swap.payer_amount_sat);
+                // The SideSwap fees in the asset, as the exchange rate is the asset amount per L-BTC
+                estimated_asset_fees =
+                    Some(swap.fees_sat as f64 / 100_000_000.0 * swap.exchange_rate);
+            }
+        }
+
+        if let Some(fee_asset) = &fee_asset {
+            // The lockup is built via the payjoin service, which pays the network fees in the asset
+            let lockup_amount_sat = receiver_amount_sat + fees_sat.unwrap_or_default();
+            estimated_asset_fees = Some(
+                self.payjoin_service
+                    .estimate_payjoin_lbtc_tx_fee(fee_asset, lockup_amount_sat)
+                    .await?,
+            );
+        }
+
         if validate_funds {
             get_info_res.wallet_info.validate_sufficient_funds(
                 self.config.network,
@@ -1669,14 +2752,123 @@ impl LiquidSdk {
             )?;
         }
 
-        Ok(PrepareSendResponse {
+        let mut response = PrepareSendResponse {
             destination: payment_destination,
             fees_sat,
             estimated_asset_fees,
             amount: req.amount.clone(),
             exchange_amount_sat,
-            disable_mrh: req.disable_mrh,
+            from_asset,
+            fee_asset,
+            disable_mrh,
             payment_timeout_sec: Some(timeout_sec),
+            swap_provider,
+            swap_quotes,
+            unsigned_pset: None,
+        };
+        if export_pset {
+            response.unsigned_pset = Some(self.export_send_pset(&response).await?);
+        }
+        Ok(response)
+    }
+
+    /// Builds the unsigned payment tx of a prepared payment, creating its swap if needed
+    async fn export_send_pset(
+        &self,
+        prepare_response: &PrepareSendResponse,
+    ) -> Result<String, PaymentError> {
+        let fees_sat = prepare_response
+            .fees_sat
+            .ok_or(PaymentError::InsufficientFunds)?;
+        let (address, asset_id, amount_sat) = match &prepare_response.destination {
+            SendDestination::LiquidAddress { address_data, .. } => {
+                ensure_sdk!(
+                    !prepare_response
+                        .amount
+                        .as_ref()
+                        .is_some_and(|a| a.is_sideswap_payment()),
+                    PaymentError::generic(
+                        "Cannot export a PSET for a payment between two separate assets"
+                    )
+                );
+                (
+                    address_data.address.clone(),
+                    address_data
+                        .asset_id
+                        .clone()
+                        .unwrap_or(self.config.lbtc_asset_id()),
+                    address_data
+                        .amount_sat
+                        .ok_or(PaymentError::amount_missing("Receiver amount must be set"))?,
+                )
+            }
+            SendDestination::Bolt11 { invoice, .. } => {
+                let swap_req = self.bolt11_send_swap_request(
+                    &invoice.bolt11,
+                    fees_sat,
+                    prepare_response.swap_provider.clone(),
+                )?;
+                let (swap_provider, lbtc_pair) = self
+                    .validate_submarine_pairs(
+                        swap_req.receiver_amount_sat,
+                        swap_req.swap_provider.as_deref(),
+                    )
+                    .await?;
+                let swap = self
+                    .get_or_create_send_swap(&swap_req, swap_provider, &lbtc_pair)
+                    .await?;
+                ensure_sdk!(swap.lockup_tx_id.is_none(), PaymentError::PaymentInProgress);
+                let create_response = swap.get_boltz_create_response()?;
+                (
+                    create_response.address,
+                    self.config.lbtc_asset_id(),
+                    create_response.expected_amount,
+                )
+            }
+            SendDestination::Bolt12 { .. } => {
+                return Err(PaymentError::generic(
+                    "Cannot export a PSET to pay a Bolt12 offer",
+                ))
+            }
+        };
+
+        let pset = self
+            .onchain_wallet
+            .build_unsigned_tx(
+                Some(LIQUID_FEE_RATE_MSAT_PER_VBYTE),
+                &address,
+                &asset_id,
+                amount_sat,
+            )
+            .await?;
+        Ok(utils::encode_pset(&pset))
+    }
+
+    fn bolt11_send_swap_request(
+        &self,
+        invoice: &str,
+        fees_sat: u64,
+        swap_provider: Option<String>,
+    ) -> Result<SendPaymentViaSwapRequest, PaymentError> {
+        let bolt11_invoice = self.validate_bolt11_invoice(invoice)?;
+        let receiver_amount_sat = bolt11_invoice
+            .amount_milli_satoshis()
+            .map(|msat| msat / 1_000)
+            .ok_or(PaymentError::amount_missing("Invoice amount is missing"))?;
+        let description = match bolt11_invoice.description() {
+            Bolt11InvoiceDescription::Direct(msg) => Some(msg.to_string()),
+            Bolt11InvoiceDescription::Hash(_) => None,
+        };
+        Ok(SendPaymentViaSwapRequest {
+            invoice: invoice.to_string(),
+            bolt12_offer: None,
+            payment_hash: bolt11_invoice.payment_hash().to_string(),
+            description,
+            receiver_amount_sat,
+            fees_sat,
+            swap_provider,
+            fee_asset: None,
+            asset_funding: None,
         })
     }
 
@@ -1700,16 +2892,301 @@ impl LiquidSdk {
     ///     * `prepare_response` - the [PrepareSendResponse] returned by [LiquidSdk::prepare_send_payment]
     ///     * `use_asset_fees` - if set to true, the payment will be sent using the SideSwap payjoin service
     ///     * `payer_note` - the optional payer note, which is to be included in a BOLT12 invoice request
+    ///     * `sub_account_id` - the optional [SubAccount] the payment and its fees are debited from.
+    ///       The debit is recorded before sending and reversed if the payment fails.
     ///
     /// # Errors
     ///
     /// * [PaymentError::PaymentTimeout] - if the payment could not be initiated in this time
+    /// * [PaymentError::InsufficientFunds] - if the sub-account balance does not cover the
+    ///   payment and its fees, or without a sub-account, if the wallet balance not allocated to
+    ///   sub-accounts does not cover them
     pub async fn send_payment(
         &self,
         req: &SendPaymentRequest,
     ) -> Result<SendPaymentResponse, PaymentError> {
         self.ensure_is_started().await?;
+        self.ensure_not_watch_only()?;
+
+        match &req.sub_account_id {
+            Some(sub_account_id) => {
+                let debited_sat =
+                    self.sub_account_send_amount_sat(&req.prepare_response, req.use_asset_fees)?;
+                self.send_from_sub_account(
+                    sub_account_id,
+                    debited_sat,
+                    &req.prepare_response.destination,
+                    self.send_payment_inner(req),
+                )
+                .await
+            }
+            None => {
+                self.ensure_unallocated_funds(
+                    self.unattributed_send_amount_sat(&req.prepare_response, req.use_asset_fees),
+                )
+                .await?;
+                self.send_payment_inner(req).await
+            }
+        }
+    }
+
+    /// Ensures a send that is not debited from a sub-account only spends the unallocated
+    /// wallet balance, see [LedgerInfo::unallocated_sat]. A `None` amount drains the wallet.
+    async fn ensure_unallocated_funds(&self, amount_sat: Option<u64>) -> Result<(), PaymentError> {
+        let _ledger_guard = self.ledger_lock.lock().await;
+        if !self.persister.has_sub_accounts()? {
+            return Ok(());
+        }
+        let ledger_info = self.ledger_info().await?;
+        ensure_sdk!(
+            match amount_sat {
+                Some(amount_sat) => ledger_info.unallocated_sat >= amount_sat,
+                None => ledger_info.allocated_sat == 0,
+            },
+            PaymentError::InsufficientFunds
+        );
+        Ok(())
+    }
+
+    /// The L-BTC amount, including fees, that a send not debited from a sub-account spends
+    /// from the wallet balance. `None` if the send drains the wallet.
+    fn unattributed_send_amount_sat(
+        &self,
+        prepare_response: &PrepareSendResponse,
+        use_asset_fees: Option<bool>,
+    ) -> Option<u64> {
+        // A payment funded from an asset converts what the unallocated balance lacks
+        if prepare_response.from_asset.is_some() {
+            return Some(0);
+        }
+        let fees_sat = match use_asset_fees.unwrap_or_default() {
+            true => 0,
+            false => prepare_response.fees_sat.unwrap_or_default(),
+        };
+        let receiver_amount_sat = match (&prepare_response.amount, &prepare_response.destination) {
+            (Some(PayAmount::Drain), _) => return None,
+            (
+                Some(PayAmount::Bitcoin {
+                    receiver_amount_sat,
+                }),
+                _,
+            ) => *receiver_amount_sat,
+            (Some(PayAmount::Asset { .. } | PayAmount::DrainAsset { .. }), _) => 0,
+            (None, SendDestination::LiquidAddress { address_data, .. }) => {
+                match address_data
+                    .asset_id
+                    .as_ref()
+                    .is_none_or(|asset_id| *asset_id == self.config.lbtc_asset_id())
+                {
+                    true => address_data.amount_sat.unwrap_or_default(),
+                    false => 0,
+                }
+            }
+            (None, SendDestination::Bolt11 { invoice, .. }) => {
+                invoice.amount_msat.unwrap_or_default() / 1000
+            }
+            (
+                None,
+                SendDestination::Bolt12 {
+                    receiver_amount_sat,
+                    ..
+                },
+            ) => *receiver_amount_sat,
+        };
+        Some(receiver_amount_sat + fees_sat)
+    }
+
+    /// Sends a payment with `send`, debiting `debited_sat` from the sub-account before it is
+    /// sent. The debit is reversed if the payment fails without spending any funds.
+    async fn send_from_sub_account(
+        &self,
+        sub_account_id: &str,
+        debited_sat: u64,
+        destination: &SendDestination,
+        send: impl std::future::Future<Output = Result<SendPaymentResponse, PaymentError>>,
+    ) -> Result<SendPaymentResponse, PaymentError> {
+        let sent_at = utils::now();
+        let pending_id = self
+            .reserve_sub_account_funds(sub_account_id, debited_sat)
+            .await?;
+        let res = send.await;
+        let _ledger_guard = self.ledger_lock.lock().await;
+        let settle_res = match &res {
+            Ok(response) => match ledger_payment_id(&response.payment) {
+                Some(payment_id) => self.key_sub_account_debit(
+                    sub_account_id,
+                    &pending_id,
+                    debited_sat,
+                    &payment_id,
+                ),
+                None => Ok(()),
+            },
+            // A swap may have locked up the funds before failing, e.g. when timing out
+            Err(_) => self
+                .locked_up_send_swap_id(destination, sent_at)
+                .and_then(|maybe_swap_id| match maybe_swap_id {
+                    Some(swap_id) => self.key_sub_account_debit(
+                        sub_account_id,
+                        &pending_id,
+                        debited_sat,
+                        &swap_id,
+                    ),
+                    None => {
+                        self.reverse_sub_account_debit(sub_account_id, &pending_id, debited_sat)
+                    }
+                }),
+        };
+        // The pending debit is kept, so the sub-account never holds funds that were spent
+        if let Err(e) = settle_res {
+            error!("Failed to settle the pending debit {pending_id} of sub-account {sub_account_id}: {e:?}");
+        }
+        res
+    }
+
+    /// Debits the funds of a send from the sub-account before it is sent, keyed by a pending
+    /// id until the id of its payment is known. Returns the pending id.
+    async fn reserve_sub_account_funds(
+        &self,
+        sub_account_id: &str,
+        debited_sat: u64,
+    ) -> Result<String, PaymentError> {
+        let _ledger_guard = self.ledger_lock.lock().await;
+        let sub_account =
+            self.persister
+                .fetch_sub_account(sub_account_id)?
+                .ok_or(PaymentError::generic(format!(
+                    "Unknown sub-account {sub_account_id}"
+                )))?;
+        ensure_sdk!(
+            sub_account.balance_sat >= debited_sat,
+            PaymentError::InsufficientFunds
+        );
+
+        let pending_id = uuid::Uuid::new_v4().to_string();
+        self.persister.apply_ledger_changes(&[LedgerChange {
+            sub_account_id: sub_account_id.to_string(),
+            entry_type: LedgerEntryType::Send,
+            amount_sat: -utils::from_u64_to_row(debited_sat)?,
+            payment_id: Some(pending_id.clone()),
+            transfer_id: None,
+            description: None,
+        }])?;
+        Ok(pending_id)
+    }
+
+    /// Keys the pending debit of a send by the id of its payment, so it is credited back by
+    /// [LiquidSdk::reconcile_ledger] if the payment fails. If the payment was already debited,
+    /// e.g. when resuming a swap, the pending debit is reversed instead.
+    fn key_sub_account_debit(
+        &self,
+        sub_account_id: &str,
+        pending_id: &str,
+        debited_sat: u64,
+        payment_id: &str,
+    ) -> Result<()> {
+        match self
+            .persister
+            .set_ledger_send_payment_id(pending_id, payment_id)?
+        {
+            true => Ok(()),
+            false => self.reverse_sub_account_debit(sub_account_id, pending_id, debited_sat),
+        }
+    }
+
+    /// Credits back the pending debit of a send that spent no funds
+    fn reverse_sub_account_debit(
+        &self,
+        sub_account_id: &str,
+        pending_id: &str,
+        debited_sat: u64,
+    ) -> Result<()> {
+        self.persister.apply_ledger_changes(&[LedgerChange {
+            sub_account_id: sub_account_id.to_string(),
+            entry_type: LedgerEntryType::Refund,
+            amount_sat: utils::from_u64_to_row(debited_sat)?,
+            payment_id: Some(pending_id.to_string()),
+            transfer_id: None,
+            description: None,
+        }])?;
+        Ok(())
+    }
+
+    /// The id of the Send Swap of a failed send, if it already locked up the funds, which are
+    /// only credited back once the swap is refunded. As an offer can be paid more than once,
+    /// the swap of a BOLT12 offer must have been created after `sent_at`.
+    fn locked_up_send_swap_id(
+        &self,
+        destination: &SendDestination,
+        sent_at: u32,
+    ) -> Result<Option<String>> {
+        let maybe_swap = match destination {
+            SendDestination::LiquidAddress { .. } => None,
+            SendDestination::Bolt11 { invoice, .. } => self
+                .persister
+                .fetch_send_swap_by_payment_hash(&invoice.payment_hash)?,
+            SendDestination::Bolt12 { offer, .. } => self
+                .persister
+                .fetch_latest_send_swap_by_bolt12_offer(&offer.offer)?
+                .filter(|swap| swap.created_at >= sent_at),
+        };
+        Ok(maybe_swap
+            .filter(|swap| swap.lockup_tx_id.is_some())
+            .map(|swap| swap.id))
+    }
+
+    /// The amount debited from a sub-account when sending the prepared payment, including fees.
+    /// Only L-BTC payments of a fixed amount can be sent from a sub-account.
+    fn sub_account_send_amount_sat(
+        &self,
+        prepare_response: &PrepareSendResponse,
+        use_asset_fees: Option<bool>,
+    ) -> Result<u64, PaymentError> {
+        ensure_sdk!(
+            !use_asset_fees.unwrap_or_default()
+                && prepare_response.from_asset.is_none()
+                && prepare_response.fee_asset.is_none(),
+            PaymentError::generic("Payments sent from a sub-account must pay fees in L-BTC")
+        );
+        let receiver_amount_sat = match (&prepare_response.amount, &prepare_response.destination) {
+            (
+                Some(PayAmount::Bitcoin {
+                    receiver_amount_sat,
+                }),
+                _,
+            ) => *receiver_amount_sat,
+            (Some(_), _) => {
+                return Err(PaymentError::generic(
+                    "Only fixed L-BTC amounts can be sent from a sub-account",
+                ))
+            }
+            (None, SendDestination::LiquidAddress { address_data, .. }) => {
+                ensure_sdk!(
+                    address_data
+                        .asset_id
+                        .as_ref()
+                        .is_none_or(|asset_id| *asset_id == self.config.lbtc_asset_id()),
+                    PaymentError::generic("Only L-BTC can be sent from a sub-account")
+                );
+                address_data.amount_sat.unwrap_or_default()
+            }
+            (None, SendDestination::Bolt11 { invoice, .. }) => {
+                invoice.amount_msat.unwrap_or_default() / 1000
+            }
+            (
+                None,
+                SendDestination::Bolt12 {
+                    receiver_amount_sat,
+                    ..
+                },
+            ) => *receiver_amount_sat,
+        };
+        Ok(receiver_amount_sat + prepare_response.fees_sat.unwrap_or_default())
+    }
 
+    async fn send_payment_inner(
+        &self,
+        req: &SendPaymentRequest,
+    ) -> Result<SendPaymentResponse, PaymentError> {
         let use_mrh = match req.prepare_response.disable_mrh {
             Some(disable_mrh) => !disable_mrh,
             None => self.config.use_magic_routing_hints,
@@ -1720,9 +3197,17 @@ impl LiquidSdk {
             destination: payment_destination,
             amount,
             payment_timeout_sec,
+            swap_provider,
+            from_asset,
+            fee_asset,
+            exchange_amount_sat,
             ..
         } = &req.prepare_response;
         let is_drain = matches!(amount, Some(PayAmount::Drain));
+        let asset_funding = from_asset.clone().map(|from_asset| AssetFunding {
+            from_asset,
+            max_payer_amount_sat: *exchange_amount_sat,
+        });
 
         let timeout_sec = payment_timeout_sec.unwrap_or(self.config.payment_timeout_sec);
 
@@ -1792,8 +3277,18 @@ impl LiquidSdk {
                 bip353_address,
             } => {
                 let fees_sat = fees_sat.ok_or(PaymentError::InsufficientFunds)?;
+                let fee_asset = Self::get_lockup_fee_asset(fee_asset, req.use_asset_fees)?;
                 let mut response = self
-                    .pay_bolt11_invoice(&invoice.bolt11, fees_sat, is_drain, use_mrh, timeout_sec)
+                    .pay_bolt11_invoice(
+                        &invoice.bolt11,
+                        fees_sat,
+                        is_drain,
+                        use_mrh,
+                        timeout_sec,
+                        swap_provider.as_deref(),
+                        fee_asset,
+                        asset_funding,
+                    )
                     .await?;
                 self.insert_payment_details(&req.payer_note, bip353_address, &mut response)?;
                 Ok(response)
@@ -1804,6 +3299,7 @@ impl LiquidSdk {
                 bip353_address,
             } => {
                 let fees_sat = fees_sat.ok_or(PaymentError::InsufficientFunds)?;
+                let fee_asset = Self::get_lockup_fee_asset(fee_asset, req.use_asset_fees)?;
                 let bolt12_info = self
                     .swapper
                     .get_bolt12_info(GetBolt12FetchRequest {
@@ -1821,6 +3317,9 @@ impl LiquidSdk {
                         is_drain,
                         use_mrh,
                         timeout_sec,
+                        swap_provider.as_deref(),
+                        fee_asset,
+                        asset_funding,
                     )
                     .await?;
                 self.insert_payment_details(&req.payer_note, bip353_address, &mut response)?;
@@ -1829,6 +3328,71 @@ impl LiquidSdk {
         }
     }
 
+    /// Returns the asset paying the swap lockup fees, ensuring the asset fees were
+    /// estimated if and only if they are used
+    fn get_lockup_fee_asset(
+        fee_asset: &Option<String>,
+        use_asset_fees: Option<bool>,
+    ) -> Result<Option<String>, PaymentError> {
+        match (fee_asset, use_asset_fees.unwrap_or_default()) {
+            (Some(fee_asset), true) => Ok(Some(fee_asset.clone())),
+            (None, false) => Ok(None),
+            (Some(_), false) => Err(PaymentError::generic(
+                "The fees were prepared to be paid in an asset, asset fees must be used",
+            )),
+            (None, true) => Err(PaymentError::generic(
+                "The fees were not prepared to be paid in an asset",
+            )),
+        }
+    }
+
+    /// Converts just enough of an asset to L-BTC via SideSwap to fund a Lightning payment of
+    /// `lbtc_amount_sat`, taking the L-BTC balance into account
+    async fn fund_payment_from_asset(
+        &self,
+        asset_funding: &AssetFunding,
+        lbtc_amount_sat: u64,
+    ) -> Result<(), PaymentError> {
+        let wallet_info = self.get_info().await?.wallet_info;
+        // The L-BTC held by sub-accounts is not spent
+        let unallocated_sat = match self.persister.has_sub_accounts()? {
+            true => self.ledger_info().await?.unallocated_sat,
+            false => wallet_info.balance_sat,
+        };
+        let shortfall_sat = lbtc_amount_sat.saturating_sub(unallocated_sat);
+        if shortfall_sat == 0 {
+            return Ok(());
+        }
+
+        let sideswap_service = SideSwapService::from_sdk(self).await;
+        let swap = sideswap_service
+            .get_asset_swap(
+                AssetId::from_str(&asset_funding.from_asset)?,
+                utils::lbtc_asset_id(self.config.network),
+                shortfall_sat,
+            )
+            .await?;
+        ensure_sdk!(
+            asset_funding
+                .max_payer_amount_sat
+                .is_none_or(|max| swap.payer_amount_sat <= max),
+            PaymentError::InvalidOrExpiredFees
+        );
+        swap.check_sufficient_asset_balance(&wallet_info)?;
+
+        self.execute_asset_swap(
+            &sideswap_service,
+            &swap,
+            Some("Lightning payment funding".to_string()),
+            None,
+        )
+        .await?;
+        // Pick up the swapped L-BTC so it can be spent by the lockup tx
+        self.onchain_wallet.full_scan().await?;
+        self.update_wallet_info().await?;
+        Ok(())
+    }
+
     fn insert_payment_details(
         &self,
         payer_note: &Option<String>,
@@ -1856,6 +3420,7 @@ impl LiquidSdk {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn pay_bolt11_invoice(
         &self,
         invoice: &str,
@@ -1863,6 +3428,9 @@ impl LiquidSdk {
         is_drain: bool,
         use_mrh: bool,
         timeout_sec: u64,
+        swap_provider: Option<&str>,
+        fee_asset: Option<String>,
+        asset_funding: Option<AssetFunding>,
     ) -> Result<SendPaymentResponse, PaymentError> {
         self.ensure_send_is_not_self_transfer(invoice)?;
         let bolt11_invoice = self.validate_bolt11_invoice(invoice)?;
@@ -1875,8 +3443,10 @@ impl LiquidSdk {
             })?;
         let payer_amount_sat = amount_sat + fees_sat;
         let get_info_response = self.get_info().await?;
+        // A payment funded from an asset is only funded once its swap is created
         ensure_sdk!(
-            payer_amount_sat <= get_info_response.wallet_info.balance_sat,
+            asset_funding.is_some()
+                || payer_amount_sat <= get_info_response.wallet_info.balance_sat,
             PaymentError::InsufficientFunds
         );
 
@@ -1935,6 +3505,9 @@ impl LiquidSdk {
                         description,
                         receiver_amount_sat: amount_sat,
                         fees_sat,
+                        swap_provider: swap_provider.map(str::to_string),
+                        fee_asset,
+                        asset_funding,
                     },
                     timeout_sec,
                 )
@@ -1953,6 +3526,9 @@ impl LiquidSdk {
         is_drain: bool,
         use_mrh: bool,
         timeout_sec: u64,
+        swap_provider: Option<&str>,
+        fee_asset: Option<String>,
+        asset_funding: Option<AssetFunding>,
     ) -> Result<SendPaymentResponse, PaymentError> {
         let invoice = self.validate_bolt12_invoice(
             offer,
@@ -1963,8 +3539,10 @@ impl LiquidSdk {
         let receiver_amount_sat = invoice.amount_msats() / 1_000;
         let payer_amount_sat = receiver_amount_sat + fees_sat;
         let get_info_response = self.get_info().await?;
+        // A payment funded from an asset is only funded once its swap is created
         ensure_sdk!(
-            payer_amount_sat <= get_info_response.wallet_info.balance_sat,
+            asset_funding.is_some()
+                || payer_amount_sat <= get_info_response.wallet_info.balance_sat,
             PaymentError::InsufficientFunds
         );
 
@@ -2013,6 +3591,9 @@ impl LiquidSdk {
                         description: invoice.description().map(|desc| desc.to_string()),
                         receiver_amount_sat,
                         fees_sat,
+                        swap_provider: swap_provider.map(str::to_string),
+                        fee_asset,
+                        asset_funding,
                     },
                     timeout_sec,
                 )
@@ -2063,7 +3644,10 @@ impl LiquidSdk {
         let destination = address_data
             .to_uri()
             .unwrap_or(address_data.address.clone());
-        let asset_id = address_data.asset_id.unwrap_or(self.config.lbtc_asset_id());
+        let asset_id = address_data
+            .asset_id
+            .clone()
+            .unwrap_or(self.config.lbtc_asset_id());
         let payments = self.persister.get_payments(&ListPaymentsRequest {
             details: Some(ListPaymentDetails::Liquid {
                 asset_id: Some(asset_id.clone()),
@@ -2093,16 +3677,37 @@ impl LiquidSdk {
             "Built onchain Liquid tx with receiver_amount_sat = {receiver_amount_sat}, fees_sat = {fees_sat} and txid = {tx_id}"
         );
 
-        let tx_id = self.liquid_chain_service.broadcast(&tx).await?.to_string();
+        self.broadcast_liquid_payment(address_data, receiver_amount_sat, fees_sat, tx)
+            .await
+    }
 
-        // We insert a pseudo-tx in case LWK fails to pick up the new mempool tx for a while
-        // This makes the tx known to the SDK (get_info, list_payments) instantly
-        let tx_data = PaymentTxData {
+    /// Broadcasts the tx of a Liquid payment and stores it as a pending payment
+    async fn broadcast_liquid_payment(
+        &self,
+        address_data: LiquidAddressData,
+        receiver_amount_sat: u64,
+        fees_sat: u64,
+        tx: elements::Transaction,
+    ) -> Result<SendPaymentResponse, PaymentError> {
+        let destination = address_data
+            .to_uri()
+            .unwrap_or(address_data.address.clone());
+        let asset_id = address_data
+            .asset_id
+            .clone()
+            .unwrap_or(self.config.lbtc_asset_id());
+
+        let tx_id = self.liquid_chain_service.broadcast(&tx).await?.to_string();
+
+        // We insert a pseudo-tx in case LWK fails to pick up the new mempool tx for a while
+        // This makes the tx known to the SDK (get_info, list_payments) instantly
+        let tx_data = PaymentTxData {
             tx_id: tx_id.clone(),
             timestamp: Some(utils::now()),
             is_confirmed: false,
             fees_sat,
             unblinding_data: None,
+            blocks_remaining: None,
         };
         let tx_balance = PaymentTxBalance {
             amount: receiver_amount_sat,
@@ -2199,6 +3804,7 @@ impl LiquidSdk {
                 fees_sat: swap.fees_sat,
                 is_confirmed: false,
                 unblinding_data: None,
+                blocks_remaining: None,
             },
             &[PaymentTxBalance {
                 asset_id: swap.from_asset.to_string(),
@@ -2259,6 +3865,7 @@ impl LiquidSdk {
             timestamp: Some(utils::now()),
             is_confirmed: false,
             unblinding_data: None,
+            blocks_remaining: None,
         };
         let tx_balance = PaymentTxBalance {
             asset_id: asset_id.clone(),
@@ -2306,6 +3913,25 @@ impl LiquidSdk {
         })
     }
 
+    /// Builds a swap lockup tx via the payjoin service, paying its network fees in the asset
+    async fn build_payjoin_lockup_tx(
+        &self,
+        lockup_address: &str,
+        fee_asset: &str,
+        lockup_amount_sat: u64,
+    ) -> Result<elements::Transaction, PaymentError> {
+        let (lockup_tx, asset_fees) = self
+            .payjoin_service
+            .build_payjoin_lbtc_tx(lockup_address, fee_asset, lockup_amount_sat)
+            .await
+            .inspect_err(|e| error!("Error building payjoin lockup tx: {e}"))?;
+        info!(
+            "Built payjoin lockup tx with lockup_amount_sat = {lockup_amount_sat}, asset_fees = {asset_fees} and txid = {}",
+            lockup_tx.txid()
+        );
+        Ok(lockup_tx)
+    }
+
     /// Performs a Send Payment by doing a swap (create it, fund it, track it, etc).
     ///
     /// If `bolt12_offer` is set, `invoice` refers to a Bolt12 invoice, otherwise it's a Bolt11 one.
@@ -2315,54 +3941,351 @@ impl LiquidSdk {
         timeout_sec: u64,
     ) -> Result<SendPaymentResponse, PaymentError> {
         let SendPaymentViaSwapRequest {
-            invoice,
-            bolt12_offer,
-            payment_hash,
-            description,
             receiver_amount_sat,
             fees_sat,
-        } = req;
-        let lbtc_pair = self.validate_submarine_pairs(receiver_amount_sat).await?;
+            swap_provider,
+            fee_asset,
+            asset_funding,
+            ..
+        } = req.clone();
+        let (swap_provider, lbtc_pair) = self
+            .validate_submarine_pairs(receiver_amount_sat, swap_provider.as_deref())
+            .await?;
         let boltz_fees_total = lbtc_pair.fees.total(receiver_amount_sat);
         let user_lockup_amount_sat = receiver_amount_sat + boltz_fees_total;
-        let lockup_tx_fees_sat = self
-            .estimate_lockup_tx_or_drain_tx_fee(user_lockup_amount_sat)
-            .await?;
+        let lockup_tx_fees_sat = Self::lockup_tx_fee_in_lbtc(
+            &fee_asset,
+            self.estimate_lockup_tx_or_drain_tx_fee(user_lockup_amount_sat),
+        )
+        .await?;
         ensure_sdk!(
             fees_sat == boltz_fees_total + lockup_tx_fees_sat,
             PaymentError::InvalidOrExpiredFees
         );
 
+        let swap = self
+            .get_or_create_send_swap(&req, swap_provider, &lbtc_pair)
+            .await?;
+
+        let create_response = swap.get_boltz_create_response()?;
+        if let Some(asset_funding) = &asset_funding {
+            ensure_sdk!(swap.lockup_tx_id.is_none(), PaymentError::PaymentInProgress);
+            self.fund_payment_from_asset(asset_funding, swap.payer_amount_sat)
+                .await?;
+        }
+        match fee_asset {
+            Some(fee_asset) => {
+                ensure_sdk!(swap.lockup_tx_id.is_none(), PaymentError::PaymentInProgress);
+                let lockup_tx = self
+                    .build_payjoin_lockup_tx(
+                        &create_response.address,
+                        &fee_asset,
+                        create_response.expected_amount,
+                    )
+                    .await?;
+                self.send_swap_handler
+                    .broadcast_lockup_tx(&swap, &create_response, lockup_tx)
+                    .await?;
+            }
+            None => {
+                self.send_swap_handler
+                    .try_lockup(&swap, &create_response)
+                    .await?;
+            }
+        }
+
+        self.wait_for_payment_with_timeout(
+            Swap::Send(swap),
+            create_response.accept_zero_conf,
+            timeout_sec,
+        )
+        .await
+        .map(|payment| SendPaymentResponse { payment })
+    }
+
+    /// Checks that an existing Send Swap can be paid, resetting it if it timed out
+    fn resume_send_swap(&self, swap: SendSwap) -> Result<SendSwap, PaymentError> {
+        match swap.state {
+            Created => Ok(swap),
+            TimedOut => {
+                self.send_swap_handler.update_swap_info(
+                    &swap.id,
+                    PaymentState::Created,
+                    None,
+                    None,
+                    None,
+                )?;
+                Ok(swap)
+            }
+            Pending => Err(PaymentError::PaymentInProgress),
+            Complete => Err(PaymentError::AlreadyPaid),
+            RefundPending | Refundable | Failed => Err(PaymentError::invalid_invoice(
+                "Payment has already failed. Please try with another invoice",
+            )),
+            WaitingFeeAcceptance => Err(PaymentError::Generic {
+                err: "Send swap payment cannot be in state WaitingFeeAcceptance".to_string(),
+            }),
+        }
+    }
+
+    /// Executes a payment prepared with [PrepareSendRequest::export_pset], once its PSET has
+    /// been signed externally. The signed PSET must match the exported one and pay the prepared
+    /// amount to the destination, within the prepared fees.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - the [FinalizeSendPaymentRequest] containing:
+    ///     * `prepare_response` - the [PrepareSendResponse] returned by [LiquidSdk::prepare_send_payment]
+    ///     * `signed_pset` - the base64 encoded signed PSET
+    ///     * `sub_account_id` - the optional [SubAccount] the payment and its fees are debited from,
+    ///       as in [LiquidSdk::send_payment]
+    ///
+    /// Only Liquid payments and BOLT11 invoices can be finalized, as the PSET of a BOLT12 offer
+    /// cannot be exported, see [PrepareSendRequest::export_pset].
+    ///
+    /// # Errors
+    ///
+    /// * [PaymentError::InvalidOrExpiredFees] - the PSET pays more fees than prepared
+    /// * [PaymentError::PaymentInProgress] - the swap lockup was already broadcast
+    /// * [PaymentError::PaymentTimeout] - the payment could not be initiated in this time
+    pub async fn finalize_send_payment(
+        &self,
+        req: &FinalizeSendPaymentRequest,
+    ) -> Result<SendPaymentResponse, PaymentError> {
+        self.ensure_is_started().await?;
+
+        match &req.sub_account_id {
+            Some(sub_account_id) => {
+                let debited_sat = self.sub_account_send_amount_sat(&req.prepare_response, None)?;
+                self.send_from_sub_account(
+                    sub_account_id,
+                    debited_sat,
+                    &req.prepare_response.destination,
+                    self.finalize_send_payment_inner(req),
+                )
+                .await
+            }
+            None => {
+                self.ensure_unallocated_funds(
+                    self.unattributed_send_amount_sat(&req.prepare_response, None),
+                )
+                .await?;
+                self.finalize_send_payment_inner(req).await
+            }
+        }
+    }
+
+    async fn finalize_send_payment_inner(
+        &self,
+        req: &FinalizeSendPaymentRequest,
+    ) -> Result<SendPaymentResponse, PaymentError> {
+        let PrepareSendResponse {
+            destination,
+            fees_sat,
+            payment_timeout_sec,
+            swap_provider,
+            unsigned_pset,
+            ..
+        } = &req.prepare_response;
+        let fees_sat = fees_sat.ok_or(PaymentError::InsufficientFunds)?;
+        let unsigned_pset = utils::decode_pset(unsigned_pset.as_deref().ok_or(
+            PaymentError::generic("The payment was not prepared with an exported PSET"),
+        )?)?;
+        let mut signed_pset = utils::decode_pset(&req.signed_pset)?;
+        let extract_txid = |pset: &elements::pset::PartiallySignedTransaction| {
+            pset.extract_tx()
+                .map(|tx| tx.txid())
+                .map_err(|e| PaymentError::generic(format!("Invalid PSET: {e:?}")))
+        };
+        ensure_sdk!(
+            extract_txid(&signed_pset)? == extract_txid(&unsigned_pset)?,
+            PaymentError::generic("The signed PSET does not match the exported PSET")
+        );
+
+        match destination {
+            SendDestination::LiquidAddress {
+                address_data,
+                bip353_address,
+            } => {
+                let receiver_amount_sat = address_data
+                    .amount_sat
+                    .ok_or(PaymentError::amount_missing("Receiver amount must be set"))?;
+                let asset_id = address_data
+                    .asset_id
+                    .clone()
+                    .unwrap_or(self.config.lbtc_asset_id());
+                Self::validate_payment_pset(
+                    &signed_pset,
+                    &address_data.address,
+                    &asset_id,
+                    receiver_amount_sat,
+                    fees_sat,
+                )?;
+
+                let tx = self.onchain_wallet.finalize_pset(&mut signed_pset).await?;
+                let mut response = self
+                    .broadcast_liquid_payment(
+                        address_data.clone(),
+                        receiver_amount_sat,
+                        fees_sat,
+                        tx,
+                    )
+                    .await?;
+                self.insert_payment_details(&None, bip353_address, &mut response)?;
+                Ok(response)
+            }
+            SendDestination::Bolt11 {
+                invoice,
+                bip353_address,
+            } => {
+                let swap_req = self.bolt11_send_swap_request(
+                    &invoice.bolt11,
+                    fees_sat,
+                    swap_provider.clone(),
+                )?;
+                let swap = self
+                    .persister
+                    .fetch_send_swap_by_payment_hash(&swap_req.payment_hash)?
+                    .ok_or(PaymentError::generic("Send Swap not found for the invoice"))?;
+                let swap = self.resume_send_swap(swap)?;
+                ensure_sdk!(swap.lockup_tx_id.is_none(), PaymentError::PaymentInProgress);
+                let create_response = swap.get_boltz_create_response()?;
+                // The payer amount covers the lockup amount and the lockup network fees
+                let lockup_fees_sat = swap
+                    .payer_amount_sat
+                    .saturating_sub(create_response.expected_amount);
+                Self::validate_payment_pset(
+                    &signed_pset,
+                    &create_response.address,
+                    &self.config.lbtc_asset_id(),
+                    create_response.expected_amount,
+                    lockup_fees_sat,
+                )?;
+
+                let lockup_tx = self.onchain_wallet.finalize_pset(&mut signed_pset).await?;
+                self.status_stream.track_swap_id(&swap.id)?;
+                self.send_swap_handler
+                    .broadcast_lockup_tx(&swap, &create_response, lockup_tx)
+                    .await?;
+                let mut response = self
+                    .wait_for_payment_with_timeout(
+                        Swap::Send(swap),
+                        create_response.accept_zero_conf,
+                        payment_timeout_sec.unwrap_or(self.config.payment_timeout_sec),
+                    )
+                    .await
+                    .map(|payment| SendPaymentResponse { payment })?;
+                self.insert_payment_details(&None, bip353_address, &mut response)?;
+                Ok(response)
+            }
+            SendDestination::Bolt12 { .. } => Err(PaymentError::generic(
+                "Cannot export a PSET to pay a Bolt12 offer",
+            )),
+        }
+    }
+
+    /// Adds the hot key signatures to a PSET spending the inputs of a multisig wallet, without
+    /// finalizing it.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - the [SignPsetRequest] containing:
+    ///     * `pset` - the base64 encoded PSET, e.g. [PrepareSendResponse::unsigned_pset]
+    pub async fn sign_pset(&self, req: &SignPsetRequest) -> Result<SignPsetResponse, PaymentError> {
+        self.ensure_is_started().await?;
+        self.ensure_not_watch_only()?;
+
+        let mut pset = utils::decode_pset(&req.pset)?;
+        self.onchain_wallet.cosign_pset(&mut pset).await?;
+        Ok(SignPsetResponse {
+            pset: utils::encode_pset(&pset),
+        })
+    }
+
+    /// Combines the partial signatures of the cosigners of a multisig wallet into a single PSET,
+    /// which can be passed to [LiquidSdk::finalize_send_payment] once enough cosigners signed.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - the [CombinePsetsRequest] containing:
+    ///     * `psets` - the base64 encoded PSETs of the same tx
+    pub fn combine_psets(
+        &self,
+        req: &CombinePsetsRequest,
+    ) -> Result<CombinePsetsResponse, PaymentError> {
+        let mut psets = req.psets.iter().map(|pset| utils::decode_pset(pset));
+        let mut combined = psets
+            .next()
+            .ok_or(PaymentError::generic("No PSET to combine"))??;
+        for pset in psets {
+            combined.merge(pset?).map_err(|e| {
+                PaymentError::generic(format!("Failed to combine the PSETs: {e:?}"))
+            })?;
+        }
+        Ok(CombinePsetsResponse {
+            pset: utils::encode_pset(&combined),
+        })
+    }
+
+    /// Checks that a PSET pays `amount_sat` of the asset to the address, with at most
+    /// `max_fees_sat` of network fees
+    fn validate_payment_pset(
+        pset: &elements::pset::PartiallySignedTransaction,
+        address: &str,
+        asset_id: &str,
+        amount_sat: u64,
+        max_fees_sat: u64,
+    ) -> Result<(), PaymentError> {
+        let script_pubkey = elements::Address::from_str(address)
+            .map_err(|err| PaymentError::generic(format!("Invalid address {address}: {err}")))?
+            .script_pubkey();
+        let asset_id = AssetId::from_str(asset_id)?;
+        ensure_sdk!(
+            pset.outputs().iter().any(|output| {
+                output.script_pubkey == script_pubkey
+                    && output.amount == Some(amount_sat)
+                    && output.asset == Some(asset_id)
+            }),
+            PaymentError::generic("The PSET does not pay the prepared amount to the destination")
+        );
+        ensure_sdk!(
+            Self::pset_fees_sat(pset) <= max_fees_sat,
+            PaymentError::InvalidOrExpiredFees
+        );
+        Ok(())
+    }
+
+    /// The sum of the explicit fee outputs of a PSET
+    fn pset_fees_sat(pset: &elements::pset::PartiallySignedTransaction) -> u64 {
+        pset.outputs()
+            .iter()
+            .filter(|output| output.script_pubkey.is_empty())
+            .filter_map(|output| output.amount)
+            .sum()
+    }
+
+    /// Fetches the Send Swap paying the invoice, or creates it if there is none, and tracks it
+    async fn get_or_create_send_swap(
+        &self,
+        req: &SendPaymentViaSwapRequest,
+        swap_provider: String,
+        lbtc_pair: &SubmarinePair,
+    ) -> Result<SendSwap, PaymentError> {
+        let SendPaymentViaSwapRequest {
+            invoice,
+            bolt12_offer,
+            payment_hash,
+            description,
+            receiver_amount_sat,
+            fees_sat,
+            ..
+        } = req.clone();
+        let swapper = self.swapper_registry.swapper(Some(&swap_provider))?;
         let swap = match self
             .persister
             .fetch_send_swap_by_payment_hash(&payment_hash)?
         {
-            Some(swap) => match swap.state {
-                Created => swap,
-                TimedOut => {
-                    self.send_swap_handler.update_swap_info(
-                        &swap.id,
-                        PaymentState::Created,
-                        None,
-                        None,
-                        None,
-                    )?;
-                    swap
-                }
-                Pending => return Err(PaymentError::PaymentInProgress),
-                Complete => return Err(PaymentError::AlreadyPaid),
-                RefundPending | Refundable | Failed => {
-                    return Err(PaymentError::invalid_invoice(
-                        "Payment has already failed. Please try with another invoice",
-                    ))
-                }
-                WaitingFeeAcceptance => {
-                    return Err(PaymentError::Generic {
-                        err: "Send swap payment cannot be in state WaitingFeeAcceptance"
-                            .to_string(),
-                    })
-                }
-            },
+            Some(swap) => self.resume_send_swap(swap)?,
             None => {
                 let keypair = utils::generate_keypair();
                 let refund_public_key = boltz_client::PublicKey {
@@ -2379,8 +4302,7 @@ impl LiquidSdk {
                         SubSwapStates::TransactionLockupFailed,
                     ]),
                 });
-                let create_response = self
-                    .swapper
+                let create_response = swapper
                     .create_send_swap(CreateSubmarineRequest {
                         from: "L-BTC".to_string(),
                         to: "BTC".to_string(),
@@ -2420,6 +4342,7 @@ impl LiquidSdk {
                     created_at: utils::now(),
                     state: PaymentState::Created,
                     refund_private_key: keypair.display_secret().to_string(),
+                    swap_provider: Some(swap_provider),
                     metadata: Default::default(),
                 };
                 self.persister.insert_or_update_send_swap(&swap)?;
@@ -2427,19 +4350,7 @@ impl LiquidSdk {
             }
         };
         self.status_stream.track_swap_id(&swap.id)?;
-
-        let create_response = swap.get_boltz_create_response()?;
-        self.send_swap_handler
-            .try_lockup(&swap, &create_response)
-            .await?;
-
-        self.wait_for_payment_with_timeout(
-            Swap::Send(swap),
-            create_response.accept_zero_conf,
-            timeout_sec,
-        )
-        .await
-        .map(|payment| SendPaymentResponse { payment })
+        Ok(swap)
     }
 
     /// Fetch the current payment limits for [LiquidSdk::send_payment] and [LiquidSdk::receive_payment].
@@ -2473,6 +4384,11 @@ impl LiquidSdk {
                 max_sat: receive_limits.maximal,
                 max_zero_conf_sat: self.config.zero_conf_max_amount_sat(),
             },
+            send_quotes: self.swapper_registry.fetch_submarine_quotes(None).await,
+            receive_quotes: self
+                .swapper_registry
+                .fetch_reverse_quotes(None, self.config.zero_conf_max_amount_sat())
+                .await,
         };
         debug!("fetch_lightning_limits returned: {res:?}");
         Ok(res)
@@ -2501,6 +4417,14 @@ impl LiquidSdk {
                 max_sat: receive_limits.maximal,
                 max_zero_conf_sat: receive_limits.maximal_zero_conf,
             },
+            send_quotes: self
+                .swapper_registry
+                .fetch_chain_quotes(Direction::Outgoing, None)
+                .await,
+            receive_quotes: self
+                .swapper_registry
+                .fetch_chain_quotes(Direction::Incoming, None)
+                .await,
         })
     }
 
@@ -2512,6 +4436,7 @@ impl LiquidSdk {
     ///     * `amount` - which can be of two types: [PayAmount::Drain], which uses all funds,
     ///       and [PayAmount::Bitcoin], which sets the amount the receiver should receive
     ///     * `fee_rate_sat_per_vbyte` - the optional fee rate of the Bitcoin claim transaction. Defaults to the swapper estimated claim fee
+    ///     * `fee_asset` - the optional asset to pay the swap lockup network fees with via the SideSwap payjoin service
     pub async fn prepare_pay_onchain(
         &self,
         req: &PreparePayOnchainRequest,
@@ -2519,12 +4444,22 @@ impl LiquidSdk {
         self.ensure_is_started().await?;
 
         let get_info_res = self.get_info().await?;
-        let pair = self.get_chain_pair(Direction::Outgoing).await?;
+        let (swap_provider, pair) = self
+            .get_chain_pair(Direction::Outgoing, req.swap_provider.as_deref())
+            .await?;
         let claim_fees_sat = match req.fee_rate_sat_per_vbyte {
             Some(sat_per_vbyte) => ESTIMATED_BTC_CLAIM_TX_VSIZE * sat_per_vbyte as u64,
             None => pair.clone().fees.claim_estimate(),
         };
         let server_fees_sat = pair.fees.server();
+        let fee_asset = req
+            .fee_asset
+            .clone()
+            .filter(|asset_id| asset_id.ne(&self.config.lbtc_asset_id()));
+        ensure_sdk!(
+            fee_asset.is_none() || !matches!(req.amount, PayAmount::Drain),
+            PaymentError::generic("Cannot drain when paying fees in an asset")
+        );
 
         info!("Preparing for onchain payment of kind: {:?}", req.amount);
         let (payer_amount_sat, receiver_amount_sat, total_fees_sat) = match req.amount {
@@ -2544,7 +4479,11 @@ impl LiquidSdk {
                     .ceil() as u64;
                 self.validate_user_lockup_amount_for_chain_pair(&pair, user_lockup_amount_sat)?;
 
-                let lockup_fees_sat = self.estimate_lockup_tx_fee(user_lockup_amount_sat).await?;
+                let lockup_fees_sat = Self::lockup_tx_fee_in_lbtc(
+                    &fee_asset,
+                    self.estimate_lockup_tx_fee(user_lockup_amount_sat),
+                )
+                .await?;
 
                 let boltz_fees_sat =
                     user_lockup_amount_sat - user_lockup_amount_sat_without_service_fee;
@@ -2575,17 +4514,35 @@ impl LiquidSdk {
 
                 (payer_amount_sat, receiver_amount_sat, total_fees_sat)
             }
-            PayAmount::Asset { .. } => {
+            PayAmount::Asset { .. } | PayAmount::DrainAsset { .. } => {
                 return Err(PaymentError::asset_error(
                     "Cannot send an asset to a Bitcoin address",
                 ))
             }
         };
 
+        let estimated_asset_fees = match &fee_asset {
+            // The lockup is built via the payjoin service, which pays the network fees in the asset
+            // As the lockup network fees are not paid in L-BTC, the whole payer amount is locked up
+            Some(fee_asset) => Some(
+                self.payjoin_service
+                    .estimate_payjoin_lbtc_tx_fee(fee_asset, payer_amount_sat)
+                    .await?,
+            ),
+            None => None,
+        };
+
         let res = PreparePayOnchainResponse {
             receiver_amount_sat,
             claim_fees_sat,
             total_fees_sat,
+            swap_provider: Some(swap_provider),
+            swap_quotes: self
+                .swapper_registry
+                .fetch_chain_quotes(Direction::Outgoing, Some(receiver_amount_sat))
+                .await,
+            fee_asset,
+            estimated_asset_fees,
         };
 
         ensure_sdk!(
@@ -2609,15 +4566,25 @@ impl LiquidSdk {
     /// * `req` - the [PayOnchainRequest] containing:
     ///     * `address` - the Bitcoin address to pay to
     ///     * `prepare_response` - the [PreparePayOnchainResponse] from calling [LiquidSdk::prepare_pay_onchain]
+    ///     * `use_asset_fees` - if set to true, the swap lockup is sent using the SideSwap payjoin service
     ///
     /// # Errors
     ///
     /// * [PaymentError::PaymentTimeout] - if the payment could not be initiated in this time
+    /// * [PaymentError::Generic] - if the wallet is multisig, as the swap lockup cannot be
+    ///   exported to be signed by the cosigners
     pub async fn pay_onchain(
         &self,
         req: &PayOnchainRequest,
     ) -> Result<SendPaymentResponse, PaymentError> {
         self.ensure_is_started().await?;
+        self.ensure_not_watch_only()?;
+        ensure_sdk!(
+            !self.multisig,
+            PaymentError::generic(
+                "Cannot pay onchain from a multisig wallet, as the swap lockup PSET cannot be exported"
+            )
+        );
         info!("Paying onchain, request = {req:?}");
 
         let timeout_sec = self.config.payment_timeout_sec;
@@ -2625,7 +4592,13 @@ impl LiquidSdk {
         let claim_address = self.validate_bitcoin_address(&req.address).await?;
         let balance_sat = self.get_info().await?.wallet_info.balance_sat;
         let receiver_amount_sat = req.prepare_response.receiver_amount_sat;
-        let pair = self.get_chain_pair(Direction::Outgoing).await?;
+        let (swap_provider, pair) = self
+            .get_chain_pair(
+                Direction::Outgoing,
+                req.prepare_response.swap_provider.as_deref(),
+            )
+            .await?;
+        let swapper = self.swapper_registry.swapper(Some(&swap_provider))?;
         let claim_fees_sat = req.prepare_response.claim_fees_sat;
         let server_fees_sat = pair.fees.server();
         let server_lockup_amount_sat = receiver_amount_sat + claim_fees_sat;
@@ -2643,10 +4616,15 @@ impl LiquidSdk {
 
         let payer_amount_sat = req.prepare_response.total_fees_sat + receiver_amount_sat;
 
-        let lockup_fees_sat = match payer_amount_sat == balance_sat {
-            true => self.estimate_drain_tx_fee(None, None).await?,
-            false => self.estimate_lockup_tx_fee(user_lockup_amount_sat).await?,
-        };
+        let fee_asset =
+            Self::get_lockup_fee_asset(&req.prepare_response.fee_asset, req.use_asset_fees)?;
+        let lockup_fees_sat = Self::lockup_tx_fee_in_lbtc(&fee_asset, async {
+            match payer_amount_sat == balance_sat {
+                true => self.estimate_drain_tx_fee(None, None).await,
+                false => self.estimate_lockup_tx_fee(user_lockup_amount_sat).await,
+            }
+        })
+        .await?;
 
         ensure_sdk!(
             req.prepare_response.total_fees_sat
@@ -2658,6 +4636,8 @@ impl LiquidSdk {
             payer_amount_sat <= balance_sat,
             PaymentError::InsufficientFunds
         );
+        self.ensure_unallocated_funds(Some(payer_amount_sat))
+            .await?;
 
         let preimage = Preimage::new();
         let preimage_str = preimage.to_string().ok_or(PaymentError::InvalidPreimage)?;
@@ -2681,8 +4661,7 @@ impl LiquidSdk {
                 ChainSwapStates::TransactionServerConfirmed,
             ]),
         });
-        let create_response = self
-            .swapper
+        let create_response = swapper
             .create_chain_swap(CreateChainRequest {
                 from: "L-BTC".to_string(),
                 to: "BTC".to_string(),
@@ -2734,9 +4713,25 @@ impl LiquidSdk {
             state: PaymentState::Created,
             auto_accepted_fees: false,
             user_lockup_spent: false,
+            swap_provider: Some(swap_provider),
             metadata: Default::default(),
         };
         self.persister.insert_or_update_chain_swap(&swap)?;
+        if let Some(fee_asset) = fee_asset {
+            // Lock up the funds before tracking the swap, so the swap handler does not
+            // build its own lockup tx when the swap is created
+            let create_response = swap.get_boltz_create_response()?;
+            let lockup_tx = self
+                .build_payjoin_lockup_tx(
+                    &create_response.lockup_details.lockup_address,
+                    &fee_asset,
+                    create_response.lockup_details.amount,
+                )
+                .await?;
+            self.chain_swap_handler
+                .broadcast_user_lockup_tx(&swap_id, &create_response, lockup_tx)
+                .await?;
+        }
         self.status_stream.track_swap_id(&swap_id)?;
 
         self.wait_for_payment_with_timeout(Swap::Chain(swap), accept_zero_conf, timeout_sec)
@@ -2847,11 +4842,10 @@ impl LiquidSdk {
                         return Err(err);
                     }
                 };
-                let reverse_pair = self
-                    .swapper
-                    .get_reverse_swap_pairs()
-                    .await?
-                    .ok_or(PaymentError::PairsNotFound)?;
+                let (swap_provider, reverse_pair) = self
+                    .swapper_registry
+                    .get_reverse_pair(req.swap_provider.as_deref())
+                    .await?;
 
                 let fees_sat = reverse_pair.fees.total(payer_amount_sat);
 
@@ -2877,6 +4871,14 @@ impl LiquidSdk {
                     min_payer_amount_sat,
                     max_payer_amount_sat,
                     swapper_feerate,
+                    swap_provider: Some(swap_provider),
+                    swap_quotes: self
+                        .swapper_registry
+                        .fetch_reverse_quotes(
+                            Some(payer_amount_sat),
+                            self.config.zero_conf_max_amount_sat(),
+                        )
+                        .await,
                 })
             }
             PaymentMethod::Bolt12Offer => {
@@ -2902,6 +4904,8 @@ impl LiquidSdk {
                     min_payer_amount_sat: Some(reverse_pair.limits.minimal),
                     max_payer_amount_sat: Some(reverse_pair.limits.maximal),
                     swapper_feerate: Some(reverse_pair.fees.percentage),
+                    swap_provider: None,
+                    swap_quotes: vec![],
                 })
             }
             PaymentMethod::BitcoinAddress => {
@@ -2916,8 +4920,12 @@ impl LiquidSdk {
                     Some(ReceiveAmount::Bitcoin { payer_amount_sat }) => Some(payer_amount_sat),
                     None => None,
                 };
-                let pair = self
-                    .get_and_validate_chain_pair(Direction::Incoming, payer_amount_sat)
+                let (swap_provider, pair) = self
+                    .get_and_validate_chain_pair(
+                        Direction::Incoming,
+                        payer_amount_sat,
+                        req.swap_provider.as_deref(),
+                    )
                     .await?;
                 let claim_fees_sat = pair.fees.claim_estimate();
                 let server_fees_sat = pair.fees.server();
@@ -2935,6 +4943,11 @@ impl LiquidSdk {
                     min_payer_amount_sat: Some(pair.limits.minimal),
                     max_payer_amount_sat: Some(pair.limits.maximal),
                     swapper_feerate: Some(pair.fees.percentage),
+                    swap_provider: Some(swap_provider),
+                    swap_quotes: self
+                        .swapper_registry
+                        .fetch_chain_quotes(Direction::Incoming, payer_amount_sat)
+                        .await,
                 })
             }
             PaymentMethod::LiquidAddress => {
@@ -2958,6 +4971,8 @@ impl LiquidSdk {
                     min_payer_amount_sat: None,
                     max_payer_amount_sat: None,
                     swapper_feerate: None,
+                    swap_provider: None,
+                    swap_quotes: vec![],
                 })
             }
         };
@@ -2996,9 +5011,19 @@ impl LiquidSdk {
             payment_method,
             amount,
             fees_sat,
+            swap_provider,
             ..
         } = req.prepare_response.clone();
 
+        // Swaps are claimed by the SDK, so only Liquid addresses can be used when watch-only
+        if !matches!(payment_method, PaymentMethod::LiquidAddress) {
+            self.ensure_not_watch_only()?;
+        }
+        ensure_sdk!(
+            req.settle_as_asset.is_none() || matches!(payment_method, PaymentMethod::Bolt11Invoice),
+            PaymentError::generic("Settling as an asset is only supported for Bolt11 invoices")
+        );
+
         let result = match payment_method {
             #[allow(deprecated)]
             PaymentMethod::Bolt11Invoice => {
@@ -3049,14 +5074,37 @@ impl LiquidSdk {
                     }
                     (description, None) => (description, None),
                 };
-                self.create_bolt11_receive_swap(
-                    amount_sat,
-                    fees_sat,
-                    description,
-                    description_hash,
-                    req.payer_note.clone(),
-                )
-                .await
+                // Quote the conversion upfront, so the invoice is not created if it would fail
+                let settlement_quote = match &req.settle_as_asset {
+                    Some(settle_as_asset) => Some(
+                        self.quote_asset_settlement(
+                            settle_as_asset,
+                            amount_sat.saturating_sub(fees_sat),
+                        )
+                        .await?,
+                    ),
+                    None => None,
+                };
+                let response = self
+                    .create_bolt11_receive_swap(
+                        amount_sat,
+                        fees_sat,
+                        description,
+                        description_hash,
+                        req.payer_note.clone(),
+                        swap_provider.as_deref(),
+                    )
+                    .await?;
+                if let Some(mut settlement) = settlement_quote {
+                    let receive_swap = self
+                        .persister
+                        .fetch_receive_swap_by_invoice(&response.destination)?
+                        .ok_or(PaymentError::PersistError)?;
+                    settlement.swap_id = receive_swap.id;
+                    self.persister
+                        .insert_or_update_asset_settlement(&settlement)?;
+                }
+                Ok(response)
             }
             PaymentMethod::Bolt12Offer => {
                 let description = req.description.clone().unwrap_or("".to_string());
@@ -3084,7 +5132,8 @@ impl LiquidSdk {
                     Some(ReceiveAmount::Bitcoin { payer_amount_sat }) => Some(payer_amount_sat),
                     None => None,
                 };
-                self.receive_onchain(amount_sat, fees_sat).await
+                self.receive_onchain(amount_sat, fees_sat, swap_provider.as_deref())
+                    .await
             }
             PaymentMethod::LiquidAddress => {
                 let lbtc_asset_id = self.config.lbtc_asset_id();
@@ -3131,19 +5180,150 @@ impl LiquidSdk {
             .inspect_err(|e| error!("receive_payment returned error: {e:?}"))
     }
 
-    async fn create_bolt11_receive_swap(
+    /// Quotes the conversion of the L-BTC received through a Lightning payment into an asset,
+    /// returning the [AssetSettlement] to track once the Receive swap is created
+    async fn quote_asset_settlement(
         &self,
-        payer_amount_sat: u64,
+        settle_as_asset: &SettleAsAsset,
+        receiver_amount_sat: u64,
+    ) -> Result<AssetSettlement, PaymentError> {
+        let SettleAsAsset {
+            asset_id,
+            max_slippage_bps,
+        } = settle_as_asset;
+        let max_slippage_bps = max_slippage_bps.unwrap_or(DEFAULT_SETTLEMENT_MAX_SLIPPAGE_BPS);
+        ensure_sdk!(
+            max_slippage_bps <= 10_000,
+            PaymentError::generic("Maximum slippage cannot exceed 10000 basis points")
+        );
+        ensure_sdk!(
+            asset_id.ne(&self.config.lbtc_asset_id()),
+            PaymentError::asset_error("The settlement asset cannot be L-BTC")
+        );
+        ensure_sdk!(
+            self.persister.get_asset_metadata(asset_id)?.is_some(),
+            PaymentError::asset_error(format!("Asset {asset_id} is not supported"))
+        );
+
+        let swap = SideSwapService::from_sdk(self)
+            .await
+            .get_asset_swap_for_payer_amount(
+                utils::lbtc_asset_id(self.config.network),
+                AssetId::from_str(asset_id)?,
+                receiver_amount_sat,
+            )
+            .await?;
+        let min_receiver_amount_sat = (swap.receiver_amount_sat as u128
+            * (10_000 - max_slippage_bps) as u128
+            / 10_000) as u64;
+        Ok(AssetSettlement {
+            swap_id: String::new(),
+            asset_id: asset_id.clone(),
+            min_receiver_amount_sat,
+            state: PaymentState::Created,
+            created_at: utils::now(),
+        })
+    }
+
+    /// Converts the L-BTC of complete Receive swaps opted into [SettleAsAsset].
+    /// If the conversion quote is worse than the allowed slippage, the L-BTC is kept.
+    async fn settle_received_assets(&self) {
+        let settlements = match self.persister.list_pending_asset_settlements() {
+            Ok(settlements) => settlements,
+            Err(e) => {
+                warn!("Could not list pending asset settlements: {e:?}");
+                return;
+            }
+        };
+        if settlements.is_empty() {
+            return;
+        }
+
+        let sideswap_service = SideSwapService::from_sdk(self).await;
+        for mut settlement in settlements {
+            let swap_id = settlement.swap_id.clone();
+            match self
+                .settle_received_asset(&sideswap_service, &settlement)
+                .await
+            {
+                Ok(Some(payment)) => {
+                    info!(
+                        "Settled Receive swap {swap_id} as asset {} in tx {:?}",
+                        settlement.asset_id, payment.tx_id
+                    );
+                    settlement.state = PaymentState::Complete;
+                }
+                Ok(None) => {
+                    warn!("Keeping L-BTC of Receive swap {swap_id}: the asset quote exceeds the allowed slippage");
+                    settlement.state = PaymentState::Failed;
+                }
+                Err(PaymentError::InsufficientFunds) => {
+                    warn!("Keeping L-BTC of Receive swap {swap_id}: insufficient funds to settle as asset");
+                    settlement.state = PaymentState::Failed;
+                }
+                Err(e) => {
+                    // Retried on the next block
+                    warn!("Could not settle Receive swap {swap_id} as asset: {e:?}");
+                    continue;
+                }
+            }
+            if let Err(e) = self
+                .persister
+                .insert_or_update_asset_settlement(&settlement)
+            {
+                error!("Could not update asset settlement of Receive swap {swap_id}: {e:?}");
+            }
+        }
+        sideswap_service.stop();
+    }
+
+    /// Executes the conversion of a Receive swap, returning `None` if the quote is too low
+    async fn settle_received_asset(
+        &self,
+        sideswap_service: &SideSwapService,
+        settlement: &AssetSettlement,
+    ) -> Result<Option<Payment>, PaymentError> {
+        let receive_swap = self
+            .persister
+            .fetch_receive_swap_by_id(&settlement.swap_id)?
+            .ok_or(PaymentError::PersistError)?;
+        let swap = sideswap_service
+            .get_asset_swap_for_payer_amount(
+                utils::lbtc_asset_id(self.config.network),
+                AssetId::from_str(&settlement.asset_id)?,
+                receive_swap.receiver_amount_sat,
+            )
+            .await?;
+        if swap.receiver_amount_sat < settlement.min_receiver_amount_sat {
+            return Ok(None);
+        }
+        swap.check_sufficient_balance(&self.get_info().await?.wallet_info)?;
+
+        let payment = self
+            .execute_asset_swap(
+                sideswap_service,
+                &swap,
+                receive_swap.description,
+                Some(receive_swap.id),
+            )
+            .await?;
+        Ok(Some(payment))
+    }
+
+    async fn create_bolt11_receive_swap(
+        &self,
+        payer_amount_sat: u64,
         fees_sat: u64,
         description: Option<String>,
         description_hash: Option<String>,
         payer_note: Option<String>,
+        swap_provider: Option<&str>,
     ) -> Result<ReceivePaymentResponse, PaymentError> {
-        let reverse_pair = self
-            .swapper
-            .get_reverse_swap_pairs()
-            .await?
-            .ok_or(PaymentError::PairsNotFound)?;
+        let (swap_provider, reverse_pair) = self
+            .swapper_registry
+            .get_reverse_pair(swap_provider)
+            .await?;
+        let swapper = self.swapper_registry.swapper(Some(&swap_provider))?;
         let new_fees_sat = reverse_pair.fees.total(payer_amount_sat);
         ensure_sdk!(fees_sat == new_fees_sat, PaymentError::InvalidOrExpiredFees);
 
@@ -3187,7 +5367,7 @@ impl LiquidSdk {
             referral_id: None,
             webhook,
         };
-        let create_response = self.swapper.create_receive_swap(v2_req).await?;
+        let create_response = swapper.create_receive_swap(v2_req).await?;
         let invoice_str = create_response
             .invoice
             .clone()
@@ -3200,8 +5380,7 @@ impl LiquidSdk {
         )?;
 
         // Check if correct MRH was added to the invoice by Boltz
-        let (bip21_lbtc_address, _bip21_amount_btc) = self
-            .swapper
+        let (bip21_lbtc_address, _bip21_amount_btc) = swapper
             .check_for_mrh(&invoice_str)
             .await?
             .ok_or(PaymentError::receive_error("Invoice has no MRH"))?;
@@ -3265,6 +5444,7 @@ impl LiquidSdk {
                 mrh_tx_id: None,
                 created_at: utils::now(),
                 state: PaymentState::Created,
+                swap_provider: Some(swap_provider),
                 metadata: Default::default(),
             })
             .map_err(|e| {
@@ -3393,9 +5573,11 @@ impl LiquidSdk {
             .respond_with_no_std(
                 vec![payment_path],
                 PaymentHash(preimage_hash),
-                SystemTime::now().duration_since(UNIX_EPOCH).map_err(|e| {
-                    PaymentError::generic(format!("Failed to create BOLT12 invoice: {e:?}"))
-                })?,
+                utils::system_time()
+                    .duration_since(UNIX_EPOCH)
+                    .map_err(|e| {
+                        PaymentError::generic(format!("Failed to create BOLT12 invoice: {e:?}"))
+                    })?,
             )?
             .build()?
             .sign(|unsigned_invoice: &UnsignedBolt12Invoice| {
@@ -3478,6 +5660,7 @@ impl LiquidSdk {
                 mrh_tx_id: None,
                 created_at: utils::now(),
                 state: PaymentState::Created,
+                swap_provider: None,
                 metadata: Default::default(),
             })
             .map_err(|e| {
@@ -3566,10 +5749,12 @@ impl LiquidSdk {
         &self,
         user_lockup_amount_sat: Option<u64>,
         fees_sat: u64,
+        swap_provider: Option<&str>,
     ) -> Result<ChainSwap, PaymentError> {
-        let pair = self
-            .get_and_validate_chain_pair(Direction::Incoming, user_lockup_amount_sat)
+        let (swap_provider, pair) = self
+            .get_and_validate_chain_pair(Direction::Incoming, user_lockup_amount_sat, swap_provider)
             .await?;
+        let swapper = self.swapper_registry.swapper(Some(&swap_provider))?;
         let claim_fees_sat = pair.fees.claim_estimate();
         let server_fees_sat = pair.fees.server();
         // Service fees are 0 if this is a zero-amount swap
@@ -3604,8 +5789,7 @@ impl LiquidSdk {
                 ChainSwapStates::TransactionServerConfirmed,
             ]),
         });
-        let create_response = self
-            .swapper
+        let create_response = swapper
             .create_chain_swap(CreateChainRequest {
                 from: "BTC".to_string(),
                 to: "L-BTC".to_string(),
@@ -3661,6 +5845,7 @@ impl LiquidSdk {
             state: PaymentState::Created,
             auto_accepted_fees: false,
             user_lockup_spent: false,
+            swap_provider: Some(swap_provider),
             metadata: Default::default(),
         };
         self.persister.insert_or_update_chain_swap(&swap)?;
@@ -3676,11 +5861,12 @@ impl LiquidSdk {
         &self,
         user_lockup_amount_sat: Option<u64>,
         fees_sat: u64,
+        swap_provider: Option<&str>,
     ) -> Result<ReceivePaymentResponse, PaymentError> {
         self.ensure_is_started().await?;
 
         let swap = self
-            .create_receive_chain_swap(user_lockup_amount_sat, fees_sat)
+            .create_receive_chain_swap(user_lockup_amount_sat, fees_sat, swap_provider)
             .await?;
         let create_response = swap.get_boltz_create_response()?;
         let address = create_response.lockup_details.lockup_address;
@@ -3697,134 +5883,676 @@ impl LiquidSdk {
         })
     }
 
-    /// List all failed chain swaps that need to be refunded.
-    /// They can be refunded by calling [LiquidSdk::prepare_refund] then [LiquidSdk::refund].
-    pub async fn list_refundables(&self) -> SdkResult<Vec<RefundableSwap>> {
-        let chain_swaps = self.persister.list_refundable_chain_swaps()?;
+    /// Generates a Bitcoin address to peg-in funds through the Liquid federation, without
+    /// going through a swap. Requires [Config::federation_config] to be set.
+    ///
+    /// Once the deposit reaches [ReceivePegInResponse::required_confirmations] on the Bitcoin
+    /// chain, it is claimed to the wallet.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - the [ReceivePegInRequest] containing:
+    ///     * `description` - the optional description of the peg-in
+    pub async fn receive_peg_in(
+        &self,
+        req: &ReceivePegInRequest,
+    ) -> Result<ReceivePegInResponse, PaymentError> {
+        self.ensure_is_started().await?;
+        self.ensure_not_watch_only()?;
+
+        let federation_config =
+            self.config
+                .federation_config
+                .as_ref()
+                .ok_or(PaymentError::generic(
+                    "Peg-ins are not supported without a federation config",
+                ))?;
+        let peg = self
+            .peg_handler
+            .create_peg_in(req.description.clone())
+            .await?;
+        Ok(ReceivePegInResponse {
+            peg_id: peg.id,
+            bitcoin_address: peg.bitcoin_address,
+            required_confirmations: federation_config.pegin_confirmations,
+        })
+    }
 
-        let mut chain_swaps_with_scripts = vec![];
-        for swap in &chain_swaps {
-            let script_pubkey = swap.get_receive_lockup_swap_script_pubkey(self.config.network)?;
-            chain_swaps_with_scripts.push((swap, script_pubkey));
-        }
+    fn ensure_peg_out_supported(&self) -> Result<(), PaymentError> {
+        ensure_sdk!(
+            self.config.network != LiquidNetwork::Mainnet,
+            PaymentError::generic(
+                "Peg-outs are not supported on mainnet, as they require a PAK proof"
+            )
+        );
+        Ok(())
+    }
 
-        let lockup_scripts: Vec<&boltz_client::bitcoin::Script> = chain_swaps_with_scripts
-            .iter()
-            .map(|(_, script_pubkey)| script_pubkey.as_script())
-            .collect();
-        let scripts_utxos = self
-            .bitcoin_chain_service
-            .get_scripts_utxos(&lockup_scripts)
-            .await?;
+    /// Prepares to peg-out funds to a Bitcoin address through the Liquid federation, by
+    /// estimating the fees of the peg-out tx.
+    ///
+    /// Peg-outs are not supported on Liquid mainnet, where the federation only processes
+    /// peg-outs proven to pay its whitelisted addresses with a PAK proof.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - the [PreparePegOutRequest] containing:
+    ///     * `receiver_amount_sat` - the amount to peg-out
+    pub async fn prepare_peg_out(
+        &self,
+        req: &PreparePegOutRequest,
+    ) -> Result<PreparePegOutResponse, PaymentError> {
+        self.ensure_is_started().await?;
+        self.ensure_peg_out_supported()?;
 
-        let mut script_to_utxos_map = std::collections::HashMap::new();
-        for script_utxos in scripts_utxos {
-            if let Some(first_utxo) = script_utxos.first() {
-                if let Some((_, txo)) = first_utxo.as_bitcoin() {
-                    let script_pubkey: boltz_client::bitcoin::ScriptBuf = txo.script_pubkey.clone();
-                    script_to_utxos_map.insert(script_pubkey, script_utxos);
-                }
+        let receiver_amount_sat = req.receiver_amount_sat;
+        ensure_sdk!(
+            receiver_amount_sat > 0,
+            PaymentError::AmountMissing {
+                err: "Peg-out amount must be greater than zero".to_string()
             }
-        }
+        );
+        let lbtc_asset_id = self.config.lbtc_asset_id();
+        let wallet_info = self.get_info().await?.wallet_info;
+        wallet_info.validate_sufficient_funds(
+            self.config.network,
+            receiver_amount_sat,
+            None,
+            &lbtc_asset_id,
+        )?;
 
-        let mut refundables = vec![];
+        // The fees are estimated with a placeholder P2WSH script, the largest standard output
+        let placeholder_script = bitcoin::ScriptBuf::new_p2wsh(&bitcoin::WScriptHash::all_zeros());
+        let tx = self
+            .peg_handler
+            .build_peg_out_tx(&placeholder_script, receiver_amount_sat)
+            .await?;
+        let fees_sat = tx.all_fees().values().sum::<u64>();
+        wallet_info.validate_sufficient_funds(
+            self.config.network,
+            receiver_amount_sat,
+            Some(fees_sat),
+            &lbtc_asset_id,
+        )?;
 
-        for (chain_swap, script_pubkey) in chain_swaps_with_scripts {
-            if let Some(script_utxos) = script_to_utxos_map.get(&script_pubkey) {
-                let swap_id = &chain_swap.id;
-                let amount_sat: u64 = script_utxos
-                    .iter()
-                    .filter_map(|utxo| utxo.as_bitcoin().cloned())
-                    .map(|(_, txo)| txo.value.to_sat())
-                    .sum();
-                info!("Incoming Chain Swap {swap_id} is refundable with {amount_sat} sats");
+        Ok(PreparePegOutResponse {
+            receiver_amount_sat,
+            fees_sat,
+        })
+    }
 
-                refundables.push(chain_swap.to_refundable(amount_sat));
-            }
-        }
+    /// Pegs-out funds to a Bitcoin address through the Liquid federation.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - the [PegOutRequest] containing:
+    ///     * `bitcoin_address` - the Bitcoin address receiving the funds
+    ///     * `prepare_response` - the [PreparePegOutResponse] from calling [LiquidSdk::prepare_peg_out]
+    pub async fn peg_out(&self, req: &PegOutRequest) -> Result<SendPaymentResponse, PaymentError> {
+        self.ensure_is_started().await?;
+        self.ensure_not_watch_only()?;
+        self.ensure_peg_out_supported()?;
 
-        Ok(refundables)
+        let bitcoin_address = self.validate_bitcoin_address(&req.bitcoin_address).await?;
+        let PreparePegOutResponse {
+            receiver_amount_sat,
+            fees_sat,
+        } = req.prepare_response;
+        let bitcoin_script = bitcoin::Address::from_str(&bitcoin_address)
+            .map_err(|e| PaymentError::generic(format!("Invalid Bitcoin address: {e}")))?
+            .assume_checked()
+            .script_pubkey();
+
+        let tx = self
+            .peg_handler
+            .build_peg_out_tx(&bitcoin_script, receiver_amount_sat)
+            .await?;
+        let tx_fees_sat = tx.all_fees().values().sum::<u64>();
+        ensure_sdk!(tx_fees_sat <= fees_sat, PaymentError::InvalidOrExpiredFees);
+        self.ensure_unallocated_funds(Some(receiver_amount_sat + tx_fees_sat))
+            .await?;
+
+        let tx_id = self.liquid_chain_service.broadcast(&tx).await?.to_string();
+        info!("Broadcast peg-out tx {tx_id} of {receiver_amount_sat} sat to {bitcoin_address}");
+
+        self.persister.insert_broadcast_tx(
+            &tx_id,
+            tx_fees_sat,
+            &[PaymentTxBalance {
+                amount: receiver_amount_sat,
+                asset_id: self.config.lbtc_asset_id(),
+                payment_type: PaymentType::Send,
+            }],
+            None,
+        )?;
+        let peg = self
+            .peg_handler
+            .insert_peg_out(&bitcoin_address, receiver_amount_sat, &tx_id)?;
+        self.emit_payment_updated(Some(peg.id.clone())).await?; // Emit Pending event
+
+        let payment = self
+            .persister
+            .get_payment(&peg.id)?
+            .ok_or(PaymentError::PersistError)?;
+        Ok(SendPaymentResponse { payment })
     }
 
-    /// Prepares to refund a failed chain swap by calculating the refund transaction size and absolute fee.
+    /// Issues a new Liquid asset to the wallet, committing to the given contract. The asset
+    /// metadata is registered so that the asset is listed in the wallet balances.
     ///
     /// # Arguments
     ///
-    /// * `req` - the [PrepareRefundRequest] containing:
-    ///     * `swap_address` - the swap address to refund from [RefundableSwap::swap_address]
-    ///     * `refund_address` - the Bitcoin address to refund to
-    ///     * `fee_rate_sat_per_vbyte` - the fee rate at which to broadcast the refund transaction
-    pub async fn prepare_refund(
+    /// * `req` - the [IssueAssetRequest] containing:
+    ///     * `amount` - the amount to issue, shifted by the contract precision
+    ///     * `reissuance_token_amount_sat` - the optional amount of reissuance tokens to issue
+    ///     * `contract` - the [AssetContract] of the asset
+    pub async fn issue_asset(
         &self,
-        req: &PrepareRefundRequest,
-    ) -> SdkResult<PrepareRefundResponse> {
-        let refund_address = self
-            .validate_bitcoin_address(&req.refund_address)
-            .await
-            .map_err(|e| SdkError::Generic {
-                err: format!("Failed to validate refund address: {e}"),
-            })?;
+        req: &IssueAssetRequest,
+    ) -> Result<IssueAssetResponse, PaymentError> {
+        self.ensure_is_started().await?;
+        self.ensure_not_watch_only()?;
+
+        let AssetContract {
+            domain,
+            name,
+            ticker,
+            precision,
+            issuer_pubkey,
+        } = req.contract.clone();
+        let mut asset_metadata = AssetMetadata {
+            asset_id: String::new(),
+            name: name.clone(),
+            ticker: ticker.clone(),
+            precision,
+            fiat_id: None,
+        };
+        let asset_sat = asset_metadata.amount_to_sat(req.amount);
+        ensure_sdk!(
+            asset_sat > 0,
+            PaymentError::AmountMissing {
+                err: "Issued amount must be greater than zero".to_string()
+            }
+        );
 
-        let (tx_vsize, tx_fee_sat, refund_tx_id) = self
-            .chain_swap_handler
-            .prepare_refund(
-                &req.swap_address,
-                &refund_address,
-                req.fee_rate_sat_per_vbyte,
+        let issuer_pubkey = match issuer_pubkey {
+            Some(issuer_pubkey) => issuer_pubkey,
+            None => self.onchain_wallet.pubkey()?,
+        };
+        let issuer_pubkey = bitcoin::secp256k1::PublicKey::from_str(&issuer_pubkey)
+            .map_err(|e| PaymentError::generic(format!("Invalid issuer public key: {e}")))?;
+        let contract = lwk_wollet::Contract {
+            entity: lwk_wollet::Entity::Domain(domain),
+            issuer_pubkey: issuer_pubkey.serialize().to_vec(),
+            name,
+            precision,
+            ticker,
+            version: 0,
+        };
+        contract.validate().map_err(|e| PaymentError::AssetError {
+            err: format!("Invalid asset contract: {e}"),
+        })?;
+        let contract_hash = contract
+            .contract_hash()
+            .map_err(|e| PaymentError::AssetError {
+                err: format!("Invalid asset contract: {e}"),
+            })?
+            .to_string();
+
+        let tx = self
+            .onchain_wallet
+            .build_issuance_tx(
+                Some(LIQUID_FEE_RATE_MSAT_PER_VBYTE),
+                asset_sat,
+                req.reissuance_token_amount_sat.unwrap_or(0),
+                Some(contract),
             )
             .await?;
-        Ok(PrepareRefundResponse {
-            tx_vsize,
-            tx_fee_sat,
-            last_refund_tx_id: refund_tx_id,
+        let (asset_id, token_id) = tx
+            .input
+            .iter()
+            .find(|input| input.has_issuance())
+            .map(|input| input.issuance_ids())
+            .ok_or(PaymentError::generic("Issuance input not found"))?;
+
+        asset_metadata.asset_id = asset_id.to_string();
+        info!("Issuing {asset_sat} of asset {asset_id} with contract hash {contract_hash}");
+
+        let payment = self
+            .broadcast_asset_tx(
+                &tx,
+                &asset_id,
+                asset_sat,
+                PaymentType::Receive,
+                Some(&asset_metadata),
+            )
+            .await?;
+        Ok(IssueAssetResponse {
+            asset_id: asset_id.to_string(),
+            reissuance_token_id: req
+                .reissuance_token_amount_sat
+                .filter(|amount_sat| *amount_sat > 0)
+                .map(|_| token_id.to_string()),
+            contract_hash,
+            payment,
         })
     }
 
-    /// Refund a failed chain swap.
+    /// Reissues an asset previously issued with a reissuance token held by the wallet.
     ///
     /// # Arguments
     ///
-    /// * `req` - the [RefundRequest] containing:
-    ///     * `swap_address` - the swap address to refund from [RefundableSwap::swap_address]
-    ///     * `refund_address` - the Bitcoin address to refund to
-    ///     * `fee_rate_sat_per_vbyte` - the fee rate at which to broadcast the refund transaction
-    pub async fn refund(&self, req: &RefundRequest) -> Result<RefundResponse, PaymentError> {
-        let refund_address = self
-            .validate_bitcoin_address(&req.refund_address)
-            .await
-            .map_err(|e| SdkError::Generic {
-                err: format!("Failed to validate refund address: {e}"),
-            })?;
+    /// * `req` - the [ReissueAssetRequest] containing:
+    ///     * `asset_id` - the id of the asset to reissue
+    ///     * `amount` - the amount to reissue, shifted by the asset precision
+    pub async fn reissue_asset(
+        &self,
+        req: &ReissueAssetRequest,
+    ) -> Result<AssetOperationResponse, PaymentError> {
+        self.ensure_is_started().await?;
+        self.ensure_not_watch_only()?;
 
-        let refund_tx_id = self
-            .chain_swap_handler
-            .refund_incoming_swap(
-                &req.swap_address,
-                &refund_address,
-                req.fee_rate_sat_per_vbyte,
-                true,
-            )
-            .or_else(|e| {
-                warn!("Failed to initiate cooperative refund, switching to non-cooperative: {e:?}");
-                self.chain_swap_handler.refund_incoming_swap(
-                    &req.swap_address,
-                    &refund_address,
-                    req.fee_rate_sat_per_vbyte,
-                    false,
-                )
-            })
+        let (asset_id, amount_sat) = self.validate_asset_amount(&req.asset_id, req.amount)?;
+        let tx = self
+            .onchain_wallet
+            .build_reissuance_tx(Some(LIQUID_FEE_RATE_MSAT_PER_VBYTE), &asset_id, amount_sat)
             .await?;
+        info!("Reissuing {amount_sat} of asset {asset_id}");
 
-        Ok(RefundResponse { refund_tx_id })
+        let payment = self
+            .broadcast_asset_tx(&tx, &asset_id, amount_sat, PaymentType::Receive, None)
+            .await?;
+        Ok(AssetOperationResponse { payment })
     }
 
-    /// Rescans all expired chain swaps created from calling [LiquidSdk::receive_onchain] to check
-    /// if there are any confirmed funds available to refund.
+    /// Provably burns an amount of an asset held by the wallet.
     ///
-    /// Since it bypasses the monitoring period, this should be called rarely or when the caller
-    /// expects there is a very old refundable chain swap. Otherwise, for relatively recent swaps
-    /// (within last [CHAIN_SWAP_MONITORING_PERIOD_BITCOIN_BLOCKS] blocks = ~14 days), calling this
-    /// is not necessary as it happens automatically in the background.
+    /// # Arguments
+    ///
+    /// * `req` - the [BurnAssetRequest] containing:
+    ///     * `asset_id` - the id of the asset to burn
+    ///     * `amount` - the amount to burn, shifted by the asset precision
+    pub async fn burn_asset(
+        &self,
+        req: &BurnAssetRequest,
+    ) -> Result<AssetOperationResponse, PaymentError> {
+        self.ensure_is_started().await?;
+        self.ensure_not_watch_only()?;
+
+        let (asset_id, amount_sat) = self.validate_asset_amount(&req.asset_id, req.amount)?;
+        self.get_info()
+            .await?
+            .wallet_info
+            .validate_sufficient_funds(self.config.network, amount_sat, None, &req.asset_id)?;
+        let tx = self
+            .onchain_wallet
+            .build_burn_tx(Some(LIQUID_FEE_RATE_MSAT_PER_VBYTE), &asset_id, amount_sat)
+            .await?;
+        info!("Burning {amount_sat} of asset {asset_id}");
+
+        let payment = self
+            .broadcast_asset_tx(&tx, &asset_id, amount_sat, PaymentType::Send, None)
+            .await?;
+        Ok(AssetOperationResponse { payment })
+    }
+
+    /// Prepares to swap between two assets held by the wallet through SideSwap.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - the [PrepareAssetSwapRequest] containing:
+    ///     * `from_asset` - the id of the asset to send
+    ///     * `to_asset` - the id of the asset to receive
+    ///     * `receiver_amount` - the amount to receive, shifted by the received asset precision
+    ///
+    /// # Returns
+    /// Returns a [PrepareAssetSwapResponse] containing the quoted amounts and fees, to be
+    /// passed to [LiquidSdk::asset_swap]
+    pub async fn prepare_asset_swap(
+        &self,
+        req: &PrepareAssetSwapRequest,
+    ) -> Result<PrepareAssetSwapResponse, PaymentError> {
+        self.ensure_is_started().await?;
+
+        ensure_sdk!(
+            req.from_asset != req.to_asset,
+            PaymentError::AssetError {
+                err: "Cannot swap an asset for itself".to_string()
+            }
+        );
+        ensure_sdk!(
+            self.persister
+                .get_asset_metadata(&req.from_asset)?
+                .is_some(),
+            PaymentError::AssetError {
+                err: format!("Asset {} is not supported", req.from_asset)
+            }
+        );
+        let to_asset_metadata =
+            self.persister
+                .get_asset_metadata(&req.to_asset)?
+                .ok_or(PaymentError::AssetError {
+                    err: format!("Asset {} is not supported", req.to_asset),
+                })?;
+        let receiver_amount_sat = to_asset_metadata.amount_to_sat(req.receiver_amount);
+        ensure_sdk!(
+            receiver_amount_sat > 0,
+            PaymentError::AmountMissing {
+                err: "Amount must be greater than zero".to_string()
+            }
+        );
+
+        let swap = SideSwapService::from_sdk(self)
+            .await
+            .get_asset_swap(
+                AssetId::from_str(&req.from_asset)?,
+                AssetId::from_str(&req.to_asset)?,
+                receiver_amount_sat,
+            )
+            .await?;
+        swap.check_sufficient_balance(&self.get_info().await?.wallet_info)?;
+
+        Ok(PrepareAssetSwapResponse {
+            from_asset: req.from_asset.clone(),
+            to_asset: req.to_asset.clone(),
+            payer_amount_sat: swap.payer_amount_sat,
+            receiver_amount_sat: swap.receiver_amount_sat,
+            fees_sat: swap.fees_sat,
+            exchange_rate: swap.exchange_rate,
+        })
+    }
+
+    /// Swaps between two assets held by the wallet through SideSwap, receiving the swapped
+    /// asset into the wallet.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - the [AssetSwapRequest] containing:
+    ///     * `prepare_response` - the [PrepareAssetSwapResponse] from calling [LiquidSdk::prepare_asset_swap]
+    ///     * `description` - the optional description of the asset swap
+    ///
+    /// # Errors
+    ///
+    /// * [PaymentError::InvalidOrExpiredFees] - if the requoted swap is more expensive than prepared
+    pub async fn asset_swap(
+        &self,
+        req: &AssetSwapRequest,
+    ) -> Result<AssetOperationResponse, PaymentError> {
+        self.ensure_is_started().await?;
+        self.ensure_not_watch_only()?;
+
+        let prepare_response = &req.prepare_response;
+        let sideswap_service = SideSwapService::from_sdk(self).await;
+        let swap = sideswap_service
+            .get_asset_swap(
+                AssetId::from_str(&prepare_response.from_asset)?,
+                AssetId::from_str(&prepare_response.to_asset)?,
+                prepare_response.receiver_amount_sat,
+            )
+            .await?;
+        ensure_sdk!(
+            swap.fees_sat <= prepare_response.fees_sat
+                && swap.payer_amount_sat <= prepare_response.payer_amount_sat,
+            PaymentError::InvalidOrExpiredFees
+        );
+        swap.check_sufficient_balance(&self.get_info().await?.wallet_info)?;
+        if swap.from_asset.to_string() == self.config.lbtc_asset_id() {
+            self.ensure_unallocated_funds(Some(swap.payer_amount_sat))
+                .await?;
+        }
+
+        let payment = self
+            .execute_asset_swap(&sideswap_service, &swap, req.description.clone(), None)
+            .await?;
+        Ok(AssetOperationResponse { payment })
+    }
+
+    /// Executes an asset swap into the wallet, recording it as a single payment
+    async fn execute_asset_swap(
+        &self,
+        sideswap_service: &SideSwapService,
+        swap: &AssetSwap,
+        description: Option<String>,
+        receive_swap_id: Option<String>,
+    ) -> Result<Payment, PaymentError> {
+        let receive_address = self.onchain_wallet.next_unused_address().await?;
+        let tx_id = sideswap_service.execute_swap(receive_address, swap).await?;
+        info!(
+            "Swapped {} of asset {} for {} of asset {} in tx {tx_id}",
+            swap.payer_amount_sat, swap.from_asset, swap.receiver_amount_sat, swap.to_asset
+        );
+
+        self.persister.insert_broadcast_tx(
+            &tx_id,
+            swap.fees_sat,
+            &[
+                PaymentTxBalance {
+                    asset_id: swap.from_asset.to_string(),
+                    amount: swap.payer_amount_sat,
+                    payment_type: PaymentType::Send,
+                },
+                PaymentTxBalance {
+                    asset_id: swap.to_asset.to_string(),
+                    amount: swap.receiver_amount_sat,
+                    payment_type: PaymentType::Receive,
+                },
+            ],
+            None,
+        )?;
+        let asset_swap_id = uuid::Uuid::new_v4().to_string();
+        self.persister.insert_asset_swap(&AssetSwapData {
+            id: asset_swap_id.clone(),
+            tx_id: tx_id.clone(),
+            from_asset: swap.from_asset.to_string(),
+            to_asset: swap.to_asset.to_string(),
+            payer_amount_sat: swap.payer_amount_sat,
+            receiver_amount_sat: swap.receiver_amount_sat,
+            fees_sat: swap.fees_sat,
+            exchange_rate: swap.exchange_rate,
+            description,
+            receive_swap_id,
+            created_at: utils::now(),
+        })?;
+        self.emit_payment_updated(Some(tx_id.clone())).await?; // Emit Pending event
+
+        self.persister
+            .get_payment(&asset_swap_id)?
+            .ok_or(PaymentError::PersistError)
+    }
+
+    /// Converts an asset amount to its base units, failing if the asset metadata is unknown
+    fn validate_asset_amount(
+        &self,
+        asset_id: &str,
+        amount: f64,
+    ) -> Result<(AssetId, u64), PaymentError> {
+        ensure_sdk!(
+            asset_id.ne(&self.config.lbtc_asset_id()),
+            PaymentError::AssetError {
+                err: "The asset cannot be L-BTC".to_string()
+            }
+        );
+        let asset_metadata =
+            self.persister
+                .get_asset_metadata(asset_id)?
+                .ok_or(PaymentError::AssetError {
+                    err: format!("Asset {asset_id} is not supported"),
+                })?;
+        let amount_sat = asset_metadata.amount_to_sat(amount);
+        ensure_sdk!(
+            amount_sat > 0,
+            PaymentError::AmountMissing {
+                err: "Amount must be greater than zero".to_string()
+            }
+        );
+        Ok((AssetId::from_str(asset_id)?, amount_sat))
+    }
+
+    /// Broadcasts an asset issuance, reissuance or burn tx and inserts its pseudo-tx.
+    ///
+    /// The metadata of a newly issued asset is only stored once its issuance is broadcast.
+    async fn broadcast_asset_tx(
+        &self,
+        tx: &elements::Transaction,
+        asset_id: &AssetId,
+        amount_sat: u64,
+        payment_type: PaymentType,
+        issued_asset_metadata: Option<&AssetMetadata>,
+    ) -> Result<Payment, PaymentError> {
+        let tx_fees_sat = tx.all_fees().values().sum::<u64>();
+        let tx_id = self.liquid_chain_service.broadcast(tx).await?.to_string();
+
+        if let Some(asset_metadata) = issued_asset_metadata {
+            self.persister
+                .insert_issued_asset_metadata(asset_metadata)?;
+        }
+
+        self.persister.insert_broadcast_tx(
+            &tx_id,
+            tx_fees_sat,
+            &[PaymentTxBalance {
+                amount: amount_sat,
+                asset_id: asset_id.to_string(),
+                payment_type,
+            }],
+            None,
+        )?;
+        self.emit_payment_updated(Some(tx_id.clone())).await?; // Emit Pending event
+
+        self.persister
+            .get_payment(&tx_id)?
+            .ok_or(PaymentError::PersistError)
+    }
+
+    /// List all failed chain swaps that need to be refunded.
+    /// They can be refunded by calling [LiquidSdk::prepare_refund] then [LiquidSdk::refund].
+    pub async fn list_refundables(&self) -> SdkResult<Vec<RefundableSwap>> {
+        let chain_swaps = self.persister.list_refundable_chain_swaps()?;
+
+        let mut chain_swaps_with_scripts = vec![];
+        for swap in &chain_swaps {
+            let script_pubkey = swap.get_receive_lockup_swap_script_pubkey(self.config.network)?;
+            chain_swaps_with_scripts.push((swap, script_pubkey));
+        }
+
+        let lockup_scripts: Vec<&boltz_client::bitcoin::Script> = chain_swaps_with_scripts
+            .iter()
+            .map(|(_, script_pubkey)| script_pubkey.as_script())
+            .collect();
+        let scripts_utxos = self
+            .bitcoin_chain_service
+            .get_scripts_utxos(&lockup_scripts)
+            .await?;
+
+        let mut script_to_utxos_map = std::collections::HashMap::new();
+        for script_utxos in scripts_utxos {
+            if let Some(first_utxo) = script_utxos.first() {
+                if let Some((_, txo)) = first_utxo.as_bitcoin() {
+                    let script_pubkey: boltz_client::bitcoin::ScriptBuf = txo.script_pubkey.clone();
+                    script_to_utxos_map.insert(script_pubkey, script_utxos);
+                }
+            }
+        }
+
+        let mut refundables = vec![];
+
+        for (chain_swap, script_pubkey) in chain_swaps_with_scripts {
+            if let Some(script_utxos) = script_to_utxos_map.get(&script_pubkey) {
+                let swap_id = &chain_swap.id;
+                let amount_sat: u64 = script_utxos
+                    .iter()
+                    .filter_map(|utxo| utxo.as_bitcoin().cloned())
+                    .map(|(_, txo)| txo.value.to_sat())
+                    .sum();
+                info!("Incoming Chain Swap {swap_id} is refundable with {amount_sat} sats");
+
+                refundables.push(chain_swap.to_refundable(amount_sat));
+            }
+        }
+
+        Ok(refundables)
+    }
+
+    /// Prepares to refund a failed chain swap by calculating the refund transaction size and absolute fee.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - the [PrepareRefundRequest] containing:
+    ///     * `swap_address` - the swap address to refund from [RefundableSwap::swap_address]
+    ///     * `refund_address` - the Bitcoin address to refund to
+    ///     * `fee_rate_sat_per_vbyte` - the fee rate at which to broadcast the refund transaction
+    pub async fn prepare_refund(
+        &self,
+        req: &PrepareRefundRequest,
+    ) -> SdkResult<PrepareRefundResponse> {
+        let refund_address = self
+            .validate_bitcoin_address(&req.refund_address)
+            .await
+            .map_err(|e| SdkError::Generic {
+                err: format!("Failed to validate refund address: {e}"),
+            })?;
+
+        let (tx_vsize, tx_fee_sat, refund_tx_id) = self
+            .chain_swap_handler
+            .prepare_refund(
+                &req.swap_address,
+                &refund_address,
+                req.fee_rate_sat_per_vbyte,
+            )
+            .await?;
+        Ok(PrepareRefundResponse {
+            tx_vsize,
+            tx_fee_sat,
+            last_refund_tx_id: refund_tx_id,
+        })
+    }
+
+    /// Refund a failed chain swap.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - the [RefundRequest] containing:
+    ///     * `swap_address` - the swap address to refund from [RefundableSwap::swap_address]
+    ///     * `refund_address` - the Bitcoin address to refund to
+    ///     * `fee_rate_sat_per_vbyte` - the fee rate at which to broadcast the refund transaction
+    pub async fn refund(&self, req: &RefundRequest) -> Result<RefundResponse, PaymentError> {
+        self.ensure_not_watch_only()?;
+        let refund_address = self
+            .validate_bitcoin_address(&req.refund_address)
+            .await
+            .map_err(|e| SdkError::Generic {
+                err: format!("Failed to validate refund address: {e}"),
+            })?;
+
+        let refund_tx_id = self
+            .chain_swap_handler
+            .refund_incoming_swap(
+                &req.swap_address,
+                &refund_address,
+                req.fee_rate_sat_per_vbyte,
+                true,
+            )
+            .or_else(|e| {
+                warn!("Failed to initiate cooperative refund, switching to non-cooperative: {e:?}");
+                self.chain_swap_handler.refund_incoming_swap(
+                    &req.swap_address,
+                    &refund_address,
+                    req.fee_rate_sat_per_vbyte,
+                    false,
+                )
+            })
+            .await?;
+
+        Ok(RefundResponse { refund_tx_id })
+    }
+
+    /// Rescans all expired chain swaps created from calling [LiquidSdk::receive_onchain] to check
+    /// if there are any confirmed funds available to refund.
+    ///
+    /// Since it bypasses the monitoring period, this should be called rarely or when the caller
+    /// expects there is a very old refundable chain swap. Otherwise, for relatively recent swaps
+    /// (within last [CHAIN_SWAP_MONITORING_PERIOD_BITCOIN_BLOCKS] blocks = ~14 days), calling this
+    /// is not necessary as it happens automatically in the background.
     pub async fn rescan_onchain_swaps(&self) -> SdkResult<()> {
         let t0 = Instant::now();
         let mut rescannable_swaps: Vec<Swap> = self
@@ -3885,6 +6613,7 @@ impl LiquidSdk {
                 amount: Some(ReceiveAmount::Bitcoin {
                     payer_amount_sat: req.amount_sat,
                 }),
+                swap_provider: None,
             })
             .await?;
 
@@ -3915,12 +6644,14 @@ impl LiquidSdk {
     ///     * `prepare_response` - the [PrepareBuyBitcoinResponse] from calling [LiquidSdk::prepare_buy_bitcoin]
     ///     * `redirect_url` - the optional redirect URL the provider should redirect to after purchase
     pub async fn buy_bitcoin(&self, req: &BuyBitcoinRequest) -> Result<String, PaymentError> {
+        self.ensure_not_watch_only()?;
         self.validate_buy_bitcoin(req.prepare_response.amount_sat)?;
 
         let swap = self
             .create_receive_chain_swap(
                 Some(req.prepare_response.amount_sat),
                 req.prepare_response.fees_sat,
+                None,
             )
             .await?;
 
@@ -4016,6 +6747,8 @@ impl LiquidSdk {
 
         let all_wallet_tx_ids: HashSet<String> =
             wallet_tx_map.keys().map(|txid| txid.to_string()).collect();
+        let liquid_tip = chain_tips.liquid_tip;
+        let confirmation_policy = self.config.confirmation_policy();
 
         for swap in recoverable_swaps {
             let swap_id = &swap.id();
@@ -4032,8 +6765,11 @@ impl LiquidSdk {
                         if let Some(tx) =
                             wallet_tx_map.remove(&lwk_wollet::elements::Txid::from_str(tx_id)?)
                         {
-                            self.persister
-                                .insert_or_update_payment_with_wallet_tx(&tx)?;
+                            self.persister.insert_or_update_payment_with_wallet_tx(
+                                &tx,
+                                liquid_tip,
+                                &confirmation_policy,
+                            )?;
                         }
                     }
                     if let Err(e) = self.receive_swap_handler.update_swap(receive_swap) {
@@ -4050,8 +6786,11 @@ impl LiquidSdk {
                         if let Some(tx) =
                             wallet_tx_map.remove(&lwk_wollet::elements::Txid::from_str(tx_id)?)
                         {
-                            self.persister
-                                .insert_or_update_payment_with_wallet_tx(&tx)?;
+                            self.persister.insert_or_update_payment_with_wallet_tx(
+                                &tx,
+                                liquid_tip,
+                                &confirmation_policy,
+                            )?;
                         }
                     }
                     if let Err(e) = self.send_swap_handler.update_swap(send_swap) {
@@ -4073,8 +6812,11 @@ impl LiquidSdk {
                         if let Some(tx) =
                             wallet_tx_map.remove(&lwk_wollet::elements::Txid::from_str(tx_id)?)
                         {
-                            self.persister
-                                .insert_or_update_payment_with_wallet_tx(&tx)?;
+                            self.persister.insert_or_update_payment_with_wallet_tx(
+                                &tx,
+                                liquid_tip,
+                                &confirmation_policy,
+                            )?;
                         }
                     }
                     if let Err(e) = self.chain_swap_handler.update_swap(chain_swap) {
@@ -4084,6 +6826,10 @@ impl LiquidSdk {
             };
         }
 
+        if let Err(e) = self.peg_handler.recover_pegs(&wallet_tx_map).await {
+            error!("Error recovering pegs: {e:?}");
+        }
+
         let non_swap_wallet_tx_map = wallet_tx_map;
 
         let payments = self
@@ -4101,15 +6847,42 @@ impl LiquidSdk {
             "Found {} unconfirmed payment txs",
             unconfirmed_txs_by_id.len()
         );
+        let lbtc_asset_id = AssetId::from_str(&self.config.lbtc_asset_id())?;
+        let zero_conf_max_amount_sat = self.config.zero_conf_max_amount_sat();
+
+        // Look up the metadata of any unknown asset without holding up the sync. The payments
+        // holding an asset are updated once it is resolved.
+        let mut payment_assets: HashMap<String, HashSet<String>> = HashMap::new();
+        for tx in non_swap_wallet_tx_map.values() {
+            for asset_id in tx.balance.keys().filter(|id| **id != lbtc_asset_id) {
+                payment_assets
+                    .entry(asset_id.to_string())
+                    .or_default()
+                    .insert(tx.txid.to_string());
+            }
+        }
+        self.asset_registry
+            .resolve_assets_in_background(payment_assets);
+
         for tx in non_swap_wallet_tx_map.values() {
             let tx_id = tx.txid.to_string();
             let maybe_payment = payments.get(&tx_id);
             let mut updated = false;
+
+            // Watch new unconfirmed receives within the zero-conf limit for double-spends
+            let lbtc_received_sat = tx.balance.get(&lbtc_asset_id).copied().unwrap_or_default();
+            if maybe_payment.is_none()
+                && tx.height.is_none()
+                && lbtc_received_sat > 0
+                && lbtc_received_sat as u64 <= zero_conf_max_amount_sat
+            {
+                self.persister.insert_watched_outpoints(&tx_id, &tx.tx)?;
+            }
             match maybe_payment {
-                // When no payment is found or its a Liquid payment
+                // When no payment is found or its a Liquid payment or asset swap
                 None
                 | Some(Payment {
-                    details: PaymentDetails::Liquid { .. },
+                    details: PaymentDetails::Liquid { .. } | PaymentDetails::AssetSwap { .. },
                     ..
                 }) => {
                     let updated_needed = maybe_payment
@@ -4117,8 +6890,25 @@ impl LiquidSdk {
                     if updated_needed {
                         // An unknown tx which needs inserting or a known Liquid payment tx
                         // that was in the mempool, but is now confirmed
-                        self.persister.insert_or_update_payment_with_wallet_tx(tx)?;
-                        self.emit_payment_updated(Some(tx_id.clone())).await?;
+                        self.persister.insert_or_update_payment_with_wallet_tx(
+                            tx,
+                            liquid_tip,
+                            &confirmation_policy,
+                        )?;
+                        // A confirmed tx may still be short of the confirmation policy, so only
+                        // notify when its state or remaining confirmations changed
+                        let is_changed = match maybe_payment {
+                            None => true,
+                            Some(payment) => {
+                                self.persister.get_payment(&tx_id)?.is_none_or(|updated| {
+                                    updated.status != payment.status
+                                        || updated.blocks_remaining != payment.blocks_remaining
+                                })
+                            }
+                        };
+                        if is_changed {
+                            self.emit_payment_updated(Some(tx_id.clone())).await?;
+                        }
                         updated = true
                     }
                 }
@@ -4127,7 +6917,11 @@ impl LiquidSdk {
             }
             if !updated && unconfirmed_txs_by_id.contains_key(&tx_id) && tx.height.is_some() {
                 // An unconfirmed tx that was not found in the payments table
-                self.persister.insert_or_update_payment_with_wallet_tx(tx)?;
+                self.persister.insert_or_update_payment_with_wallet_tx(
+                    tx,
+                    liquid_tip,
+                    &confirmation_policy,
+                )?;
             }
         }
 
@@ -4160,6 +6954,10 @@ impl LiquidSdk {
             }
         }
 
+        if let Err(e) = self.check_double_spends().await {
+            warn!("Failed to check watched outpoints for double-spends: {e:?}");
+        }
+
         self.update_wallet_info().await?;
         debug!("LiquidSdk::sync_payments_with_chain_data: end");
         Ok(())
@@ -4289,6 +7087,249 @@ impl LiquidSdk {
         Ok(self.persister.get_payment_by_request(req)?)
     }
 
+    /// Creates a virtual [SubAccount] in the local ledger, to hold part of the wallet L-BTC
+    /// balance for an end user.
+    ///
+    /// A new sub-account has no funds. It is credited with the incoming payments attributed to
+    /// it via [LiquidSdk::attribute_to_sub_account] or with a transfer via
+    /// [LiquidSdk::transfer_sub_account_funds], and debited when setting
+    /// [SendPaymentRequest::sub_account_id].
+    pub async fn create_sub_account(&self, req: &CreateSubAccountRequest) -> SdkResult<SubAccount> {
+        self.ensure_is_started().await?;
+        ensure_sdk!(
+            !req.name.trim().is_empty(),
+            SdkError::generic("Sub-account name cannot be empty")
+        );
+
+        let sub_account = SubAccount {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: req.name.clone(),
+            balance_sat: 0,
+            created_at: utils::now(),
+        };
+        self.persister.insert_sub_account(&sub_account)?;
+        Ok(sub_account)
+    }
+
+    /// Lists the sub-accounts of the ledger, in the order they were created
+    pub async fn list_sub_accounts(&self) -> SdkResult<Vec<SubAccount>> {
+        self.ensure_is_started().await?;
+
+        Ok(self.persister.list_sub_accounts()?)
+    }
+
+    /// Credits the incoming payments to a destination to a sub-account, once they are
+    /// [PaymentState::Complete]. Payments already received to the destination are credited
+    /// right away.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - the [AttributeToSubAccountRequest] containing:
+    ///     * `sub_account_id` - the id of the credited sub-account
+    ///     * `destination` - the BOLT11 invoice, BOLT12 offer, Liquid or Bitcoin address from
+    ///       [ReceivePaymentResponse::destination]
+    pub async fn attribute_to_sub_account(
+        &self,
+        req: &AttributeToSubAccountRequest,
+    ) -> SdkResult<()> {
+        self.ensure_is_started().await?;
+        self.persister
+            .fetch_sub_account(&req.sub_account_id)?
+            .ok_or(SdkError::generic(format!(
+                "Unknown sub-account {}",
+                req.sub_account_id
+            )))?;
+
+        self.persister
+            .insert_ledger_attribution(ledger_destination(&req.destination), &req.sub_account_id)?;
+        self.reconcile_ledger().await
+    }
+
+    /// Moves funds between two sub-accounts, or between a sub-account and the part of the
+    /// wallet balance not held by any sub-account. No onchain tx is made.
+    ///
+    /// Returns the recorded [LedgerEntry] of each sub-account involved.
+    pub async fn transfer_sub_account_funds(
+        &self,
+        req: &TransferSubAccountFundsRequest,
+    ) -> SdkResult<Vec<LedgerEntry>> {
+        self.ensure_is_started().await?;
+        ensure_sdk!(
+            req.from_sub_account_id.is_some() || req.to_sub_account_id.is_some(),
+            SdkError::generic("Either the source or the target sub-account must be set")
+        );
+        ensure_sdk!(
+            req.from_sub_account_id != req.to_sub_account_id,
+            SdkError::generic("Cannot transfer funds to the same sub-account")
+        );
+        ensure_sdk!(
+            req.amount_sat > 0,
+            SdkError::generic("Transfer amount must be greater than zero")
+        );
+        let amount_sat = utils::from_u64_to_row(req.amount_sat)?;
+
+        let _ledger_guard = self.ledger_lock.lock().await;
+        if req.from_sub_account_id.is_none() {
+            ensure_sdk!(
+                self.ledger_info().await?.unallocated_sat >= req.amount_sat,
+                SdkError::generic("Insufficient unallocated wallet balance")
+            );
+        }
+
+        let transfer_id = uuid::Uuid::new_v4().to_string();
+        let changes: Vec<LedgerChange> = [
+            (&req.from_sub_account_id, -amount_sat),
+            (&req.to_sub_account_id, amount_sat),
+        ]
+        .into_iter()
+        .filter_map(|(maybe_sub_account_id, amount_sat)| {
+            maybe_sub_account_id
+                .as_ref()
+                .map(|sub_account_id| LedgerChange {
+                    sub_account_id: sub_account_id.clone(),
+                    entry_type: LedgerEntryType::Transfer,
+                    amount_sat,
+                    payment_id: None,
+                    transfer_id: Some(transfer_id.clone()),
+                    description: req.description.clone(),
+                })
+        })
+        .collect();
+        Ok(self.persister.apply_ledger_changes(&changes)?)
+    }
+
+    /// Lists the audit trail of the sub-account balance changes, in the order they were
+    /// recorded
+    pub async fn list_ledger_entries(
+        &self,
+        req: &ListLedgerEntriesRequest,
+    ) -> SdkResult<Vec<LedgerEntry>> {
+        self.ensure_is_started().await?;
+
+        Ok(self.persister.list_ledger_entries(
+            req.sub_account_id.as_deref(),
+            req.offset,
+            req.limit,
+        )?)
+    }
+
+    /// Returns how much of the wallet balance is held by the sub-accounts
+    pub async fn get_ledger_info(&self) -> SdkResult<LedgerInfo> {
+        self.ensure_is_started().await?;
+
+        self.ledger_info().await
+    }
+
+    async fn ledger_info(&self) -> SdkResult<LedgerInfo> {
+        let allocated_sat = self.persister.get_allocated_balance_sat()?;
+        let balance_sat = self.get_info().await?.wallet_info.balance_sat;
+        Ok(LedgerInfo {
+            allocated_sat,
+            unallocated_sat: balance_sat.saturating_sub(allocated_sat),
+        })
+    }
+
+    /// Credits the completed incoming payments to the sub-accounts their destinations are
+    /// attributed to, and credits back the payments sent from a sub-account that failed
+    async fn reconcile_ledger(&self) -> SdkResult<()> {
+        let _ledger_guard = self.ledger_lock.lock().await;
+        if !self.persister.has_sub_accounts()? {
+            return Ok(());
+        }
+
+        let mut changes = vec![];
+        let attributions = self.persister.list_ledger_attributions()?;
+        if !attributions.is_empty() {
+            let receives = self.persister.get_payments(&ListPaymentsRequest {
+                filters: Some(vec![PaymentType::Receive]),
+                states: Some(vec![Complete]),
+                ..Default::default()
+            })?;
+            changes.extend(
+                receives
+                    .iter()
+                    .filter_map(|payment| self.ledger_receive_change(payment, &attributions)),
+            );
+        }
+
+        for send in self.persister.list_unrefunded_ledger_sends()? {
+            let Some(payment_id) = send.payment_id else {
+                continue;
+            };
+            let Some(payment) = self.persister.get_payment(&payment_id)? else {
+                continue;
+            };
+            // A timed out swap can still be resumed, so a send is only credited back with the
+            // amount of its refund tx, as it does not return the fees of the swap txs, or in
+            // full if it failed without its funds ever leaving the wallet
+            let debited_sat = send.amount_sat.unsigned_abs();
+            let is_locked_up = payment.details.get_swap_id().is_some() && payment.tx_id.is_some();
+            let refunded_sat = match (payment.details.get_refund_tx_amount_sat(), payment.status) {
+                (Some(refund_amount_sat), _) => refund_amount_sat.min(debited_sat),
+                (None, Failed) if !is_locked_up => debited_sat,
+                _ => continue,
+            };
+            changes.push(LedgerChange {
+                sub_account_id: send.sub_account_id,
+                entry_type: LedgerEntryType::Refund,
+                amount_sat: utils::from_u64_to_row(refunded_sat)?,
+                payment_id: Some(payment_id),
+                transfer_id: None,
+                description: None,
+            });
+        }
+
+        if !changes.is_empty() {
+            let entries = self.persister.apply_ledger_changes(&changes)?;
+            debug!("Recorded {} ledger entries", entries.len());
+        }
+        Ok(())
+    }
+
+    /// The credit of an incoming L-BTC payment to the sub-account its destination is
+    /// attributed to, if any
+    fn ledger_receive_change(
+        &self,
+        payment: &Payment,
+        attributions: &HashMap<String, String>,
+    ) -> Option<LedgerChange> {
+        let destinations = match &payment.details {
+            PaymentDetails::Lightning {
+                invoice,
+                bolt12_offer,
+                asset_swap_id: None,
+                ..
+            } => vec![invoice.clone(), bolt12_offer.clone()],
+            PaymentDetails::Liquid {
+                destination,
+                asset_id,
+                ..
+            } if *asset_id == self.config.lbtc_asset_id() => vec![Some(destination.clone())],
+            PaymentDetails::Bitcoin {
+                bitcoin_address, ..
+            }
+            | PaymentDetails::Peg {
+                bitcoin_address, ..
+            } => vec![Some(bitcoin_address.clone())],
+            _ => return None,
+        };
+        let sub_account_id = destinations
+            .into_iter()
+            .chain([payment.destination.clone()])
+            .flatten()
+            .find_map(|destination| attributions.get(ledger_destination(&destination)))?;
+
+        Some(LedgerChange {
+            sub_account_id: sub_account_id.clone(),
+            entry_type: LedgerEntryType::Receive,
+            amount_sat: utils::from_u64_to_row(payment.amount_sat).ok()?,
+            // Without an id, the payment could be credited more than once
+            payment_id: Some(ledger_payment_id(payment)?),
+            transfer_id: None,
+            description: None,
+        })
+    }
+
     /// Fetches an up-to-date fees proposal for a [Payment] that is [WaitingFeeAcceptance].
     ///
     /// Use [LiquidSdk::accept_payment_proposed_fees] to accept the proposed fees and proceed
@@ -4341,6 +7382,8 @@ impl LiquidSdk {
         &self,
         req: &AcceptPaymentProposedFeesRequest,
     ) -> Result<(), PaymentError> {
+        self.ensure_not_watch_only()?;
+
         let FetchPaymentProposedFeesResponse {
             swap_id,
             fees_sat,
@@ -4421,6 +7464,7 @@ impl LiquidSdk {
                 })?,
                 bitcoin_tip: sync_context.maybe_bitcoin_tip,
             },
+            false,
         )
         .await
     }
@@ -4533,10 +7577,13 @@ impl LiquidSdk {
         })
     }
 
+    /// Syncs the payments with the chain data. The wallet scan is skipped if `is_wallet_scanned`
+    /// is set, e.g. because the wallet was already rescanned while rolling back a reorg.
     async fn sync_inner(
         &self,
         recoverable_swaps: Vec<Swap>,
         chain_tips: ChainTips,
+        is_wallet_scanned: bool,
     ) -> SdkResult<()> {
         debug!(
             "LiquidSdk::sync_inner called with {} recoverable swaps",
@@ -4546,10 +7593,12 @@ impl LiquidSdk {
 
         let t0 = Instant::now();
 
-        self.onchain_wallet.full_scan().await.map_err(|err| {
-            error!("Failed to scan wallet: {err:?}");
-            SdkError::generic(err.to_string())
-        })?;
+        if !is_wallet_scanned {
+            self.onchain_wallet.full_scan().await.map_err(|err| {
+                error!("Failed to scan wallet: {err:?}");
+                SdkError::generic(err.to_string())
+            })?;
+        }
 
         let is_first_sync = !self
             .persister
@@ -4571,6 +7620,10 @@ impl LiquidSdk {
         let duration_ms = Instant::now().duration_since(t0).as_millis();
         info!("Synchronized with mempool and onchain data ({duration_ms} ms)");
 
+        if let Err(e) = self.reconcile_ledger().await {
+            warn!("Failed to reconcile the sub-account ledger: {e:?}");
+        }
+
         self.notify_event_listeners(SdkEvent::Synced).await;
         Ok(())
     }
@@ -4689,7 +7742,7 @@ impl LiquidSdk {
             PayAmount::Bitcoin {
                 receiver_amount_sat,
             } => receiver_amount_sat * 1000,
-            PayAmount::Asset { .. } => {
+            PayAmount::Asset { .. } | PayAmount::DrainAsset { .. } => {
                 return Err(LnUrlPayError::Generic {
                     err: "Cannot send an asset to a Bitcoin address".to_string(),
                 })
@@ -4716,6 +7769,10 @@ impl LiquidSdk {
                         amount: Some(req.amount.clone()),
                         disable_mrh: None,
                         payment_timeout_sec: None,
+                        swap_provider: None,
+                        from_asset: None,
+                        fee_asset: None,
+                        export_pset: None,
                     })
                     .await?;
 
@@ -4772,12 +7829,18 @@ impl LiquidSdk {
                     fees_sat: Some(prepare_response.fees_sat),
                     estimated_asset_fees: None,
                     exchange_amount_sat: None,
+                    from_asset: None,
+                    fee_asset: None,
                     amount: Some(prepare_response.amount),
                     disable_mrh: None,
                     payment_timeout_sec: None,
+                    swap_provider: None,
+                    swap_quotes: vec![],
+                    unsigned_pset: None,
                 },
                 use_asset_fees: None,
                 payer_note: prepare_response.comment.clone(),
+                sub_account_id: None,
             })
             .await?
             .payment;
@@ -4891,6 +7954,7 @@ impl LiquidSdk {
                     amount: Some(ReceiveAmount::Bitcoin {
                         payer_amount_sat: req.amount_msat / 1_000,
                     }),
+                    swap_provider: None,
                 }
             })
             .await?;
@@ -4900,6 +7964,7 @@ impl LiquidSdk {
                 description: req.description.clone(),
                 description_hash: None,
                 payer_note: None,
+                settle_as_asset: None,
             })
             .await?;
 
@@ -5129,6 +8194,7 @@ impl LiquidSdk {
         let plugin_passphrase = self
             .signer
             .hmac_sha256(plugin_id.as_bytes().to_vec(), "m/49'/1'/0'/0/0".to_string())
+            .await
             .map_err(|err| {
                 SdkError::generic(format!("Could not generate plugin passphrase: {err}"))
             })?;
@@ -5157,6 +8223,48 @@ impl LiquidSdk {
     }
 }
 
+/// Lists the accounts with a tx history, scanning them in order with `tx_count` until
+/// [ACCOUNT_DISCOVERY_GAP_LIMIT] consecutive accounts have none
+async fn discover_accounts<F, Fut>(tx_count: F) -> Result<Vec<AccountInfo>, PaymentError>
+where
+    F: Fn(u32) -> Fut,
+    Fut: std::future::Future<Output = Result<usize, PaymentError>>,
+{
+    let mut accounts = vec![];
+    let mut empty_accounts = 0;
+    let mut account_index = 0;
+    while empty_accounts < ACCOUNT_DISCOVERY_GAP_LIMIT {
+        match tx_count(account_index).await? {
+            0 => empty_accounts += 1,
+            tx_count => {
+                empty_accounts = 0;
+                accounts.push(AccountInfo {
+                    account_index,
+                    tx_count: tx_count as u32,
+                });
+            }
+        }
+        account_index += 1;
+    }
+    Ok(accounts)
+}
+
+/// The id of a payment in the ledger, which is its swap id for swaps and its tx id otherwise
+fn ledger_payment_id(payment: &Payment) -> Option<String> {
+    match &payment.details {
+        PaymentDetails::Peg { peg_id, .. } => Some(peg_id.clone()),
+        details => details.get_swap_id().or(payment.tx_id.clone()),
+    }
+}
+
+/// The address of a BIP21 URI, or the destination itself
+fn ledger_destination(destination: &str) -> &str {
+    let without_scheme = destination
+        .split_once(':')
+        .map_or(destination, |(_, rest)| rest);
+    without_scheme.split('?').next().unwrap_or(without_scheme)
+}
+
 /// Extracts `description` from `metadata_str`
 fn extract_description_from_metadata(request_data: &LnUrlPayRequestData) -> Option<String> {
     let metadata = request_data.metadata_vec().ok()?;
@@ -5172,15 +8280,17 @@ fn extract_description_from_metadata(request_data: &LnUrlPayRequestData) -> Opti
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
-    use std::{str::FromStr, sync::Arc};
+    use std::{collections::HashSet, str::FromStr, sync::Arc};
 
     use anyhow::{anyhow, Result};
     use boltz_client::{
-        boltz::{self, TransactionInfo},
         swaps::boltz::{ChainSwapStates, RevSwapStates, SubSwapStates},
         Secp256k1,
     };
-    use lwk_wollet::{bitcoin::Network, hashes::hex::DisplayHex as _};
+    use lwk_wollet::{
+        bitcoin::Network,
+        hashes::{hex::DisplayHex as _, Hash as _},
+    };
     use sdk_common::{
         bitcoin::hashes::hex::ToHex,
         lightning_with_bolt12::{
@@ -5192,17 +8302,21 @@ mod tests {
     };
     use tokio_with_wasm::alias as tokio;
 
+    use crate::swapper::model::{SwapStatus, TransactionInfo};
     use crate::test_utils::swapper::ZeroAmountSwapMockConfig;
     use crate::test_utils::wallet::TEST_LIQUID_RECEIVE_LOCKUP_TX;
     use crate::utils;
     use crate::{
         bitcoin, elements,
-        model::{BtcHistory, Direction, LBtcHistory, PaymentState, Swap},
+        model::{
+            Blockchain, BtcHistory, ChainSwap, ChainTips, Direction, LBtcHistory, LiquidNetwork,
+            PaymentState, PaymentType, ReceiveSwap, Swap,
+        },
         sdk::LiquidSdk,
         test_utils::{
             chain::{MockBitcoinChainService, MockLiquidChainService},
             chain_swap::{new_chain_swap, TEST_BITCOIN_INCOMING_USER_LOCKUP_TX},
-            persist::{create_persister, new_receive_swap, new_send_swap},
+            persist::{create_persister, new_payment_tx_data, new_receive_swap, new_send_swap},
             sdk::{new_liquid_sdk, new_liquid_sdk_with_chain_services},
             status_stream::MockStatusStream,
             swapper::MockSwapper,
@@ -5322,7 +8436,7 @@ mod tests {
 
             $status_stream
                 .clone()
-                .send_mock_update(boltz::SwapStatus {
+                .send_mock_update(SwapStatus {
                     id: swap.id(),
                     status: $status.to_string(),
                     transaction: $transaction,
@@ -5411,7 +8525,6 @@ mod tests {
                         hex: Some(
                             lwk_wollet::elements::encode::serialize(&mock_tx).to_lower_hex_string()
                         ),
-                        eta: None,
                     }),
                     None
                 );
@@ -5445,7 +8558,6 @@ mod tests {
                         hex: Some(
                             lwk_wollet::elements::encode::serialize(&mock_tx).to_lower_hex_string()
                         ),
-                        eta: None
                     }),
                     None
                 );
@@ -5655,7 +8767,6 @@ mod tests {
                         Some(TransactionInfo {
                             id: mock_user_lockup_tx_id.clone(),
                             hex: Some(mock_user_lockup_tx_hex.clone()),
-                            eta: None
                         }), // sets `update.transaction`
                         Some(true) // sets `update.zero_conf_rejected`
                     );
@@ -5684,7 +8795,6 @@ mod tests {
                         Some(TransactionInfo {
                             id: mock_server_lockup_tx_id.clone(),
                             hex: Some(mock_server_lockup_tx_hex.clone()),
-                            eta: None,
                         }),
                         None
                     );
@@ -5713,7 +8823,6 @@ mod tests {
                     Some(TransactionInfo {
                         id: mock_server_lockup_tx_id,
                         hex: Some(mock_server_lockup_tx_hex),
-                        eta: None,
                     }),
                     None
                 );
@@ -5864,6 +8973,409 @@ mod tests {
         Ok(())
     }
 
+    #[sdk_macros::async_test_all]
+    async fn test_handle_reorgs() -> Result<()> {
+        create_persister!(persister);
+        let swapper = Arc::new(MockSwapper::default());
+        let status_stream = Arc::new(MockStatusStream::new());
+        let liquid_chain_service = Arc::new(MockLiquidChainService::new());
+        let bitcoin_chain_service = Arc::new(MockBitcoinChainService::new());
+
+        let sdk = new_liquid_sdk_with_chain_services(
+            persister.clone(),
+            swapper.clone(),
+            status_stream.clone(),
+            liquid_chain_service.clone(),
+            bitcoin_chain_service.clone(),
+            None,
+        )
+        .await?;
+
+        // The first tips are only tracked
+        liquid_chain_service.set_block_hash(100, elements::BlockHash::from_byte_array([1; 32]));
+        bitcoin_chain_service.set_block_hash(200, bitcoin::BlockHash::from_byte_array([1; 32]));
+        let chain_tips = ChainTips {
+            liquid_tip: 100,
+            bitcoin_tip: Some(200),
+        };
+        assert!(sdk.handle_reorgs(chain_tips).await.is_empty());
+        assert!(sdk.handle_reorgs(chain_tips).await.is_empty());
+
+        // A receive swap claimed by a confirmed Liquid tx, which is not in the wallet anymore
+        let (mut claim_tx, claim_balance) =
+            new_payment_tx_data(LiquidNetwork::Regtest, PaymentType::Receive);
+        claim_tx.is_confirmed = true;
+        let receive_swap = ReceiveSwap {
+            claim_tx_id: Some(claim_tx.tx_id.clone()),
+            ..new_receive_swap(Some(PaymentState::Complete), None)
+        };
+        persister.insert_or_update_receive_swap(&receive_swap)?;
+        persister.insert_or_update_payment(claim_tx, &[claim_balance], None, false)?;
+
+        // Two chain swaps claimed on Bitcoin: one claim is mined again above the fork, the
+        // other one is no longer confirmed
+        let remined_claim_tx_id = bitcoin::Txid::from_byte_array([2; 32]);
+        let remined_swap = ChainSwap {
+            claim_tx_id: Some(remined_claim_tx_id.to_string()),
+            ..new_chain_swap(
+                Direction::Outgoing,
+                Some(PaymentState::Complete),
+                false,
+                None,
+                false,
+                false,
+                None,
+            )
+        };
+        let reorged_swap = ChainSwap {
+            claim_tx_id: Some(bitcoin::Txid::from_byte_array([3; 32]).to_string()),
+            ..new_chain_swap(
+                Direction::Outgoing,
+                Some(PaymentState::Complete),
+                false,
+                None,
+                false,
+                false,
+                None,
+            )
+        };
+        // An incoming chain swap that became refundable, whose user lockup is disconnected
+        let refundable_swap = new_chain_swap(
+            Direction::Incoming,
+            Some(PaymentState::Refundable),
+            false,
+            None,
+            false,
+            false,
+            None,
+        );
+        persister.insert_or_update_chain_swap(&remined_swap)?;
+        persister.insert_or_update_chain_swap(&reorged_swap)?;
+        persister.insert_or_update_chain_swap(&refundable_swap)?;
+        bitcoin_chain_service.set_history(vec![BtcHistory {
+            txid: remined_claim_tx_id,
+            height: 201,
+        }]);
+
+        // Both tracked blocks are replaced
+        liquid_chain_service
+            .set_block_hash(100, elements::BlockHash::from_byte_array([2; 32]))
+            .set_block_hash(101, elements::BlockHash::from_byte_array([3; 32]));
+        bitcoin_chain_service
+            .set_block_hash(200, bitcoin::BlockHash::from_byte_array([2; 32]))
+            .set_block_hash(201, bitcoin::BlockHash::from_byte_array([3; 32]));
+        let chain_tips = ChainTips {
+            liquid_tip: 101,
+            bitcoin_tip: Some(201),
+        };
+        assert_eq!(
+            sdk.handle_reorgs(chain_tips).await,
+            HashSet::from([Blockchain::Liquid, Blockchain::Bitcoin])
+        );
+
+        let receive_swap = persister
+            .fetch_receive_swap_by_id(&receive_swap.id)?
+            .ok_or(anyhow!("Could not retrieve receive swap"))?;
+        assert_eq!(receive_swap.state, PaymentState::Pending);
+        assert!(persister.list_confirmed_payment_tx_ids()?.is_empty());
+
+        let remined_swap = persister
+            .fetch_chain_swap_by_id(&remined_swap.id)?
+            .ok_or(anyhow!("Could not retrieve chain swap"))?;
+        assert_eq!(remined_swap.state, PaymentState::Complete);
+        let reorged_swap = persister
+            .fetch_chain_swap_by_id(&reorged_swap.id)?
+            .ok_or(anyhow!("Could not retrieve chain swap"))?;
+        assert_eq!(reorged_swap.state, PaymentState::Pending);
+
+        // The refundable state was not derived from a confirmation, so it is kept
+        let refundable_swap = persister
+            .fetch_chain_swap_by_id(&refundable_swap.id)?
+            .ok_or(anyhow!("Could not retrieve chain swap"))?;
+        assert_eq!(refundable_swap.state, PaymentState::Refundable);
+
+        // The new tips are tracked, so the same chain is not detected as a reorg again
+        assert!(sdk.handle_reorgs(chain_tips).await.is_empty());
+
+        Ok(())
+    }
+
+    fn new_test_tx(
+        inputs: Vec<elements::OutPoint>,
+        outputs: Vec<(elements::Script, u64)>,
+    ) -> elements::Transaction {
+        let asset_id = utils::lbtc_asset_id(LiquidNetwork::Regtest);
+        elements::Transaction {
+            version: 2,
+            lock_time: elements::LockTime::ZERO,
+            input: inputs
+                .into_iter()
+                .map(|previous_output| elements::TxIn {
+                    previous_output,
+                    is_pegin: false,
+                    script_sig: elements::Script::new(),
+                    sequence: elements::Sequence::MAX,
+                    asset_issuance: Default::default(),
+                    witness: Default::default(),
+                })
+                .collect(),
+            output: outputs
+                .into_iter()
+                .map(|(script_pubkey, value)| elements::TxOut {
+                    asset: elements::confidential::Asset::Explicit(asset_id),
+                    value: elements::confidential::Value::Explicit(value),
+                    nonce: elements::confidential::Nonce::Null,
+                    script_pubkey,
+                    witness: Default::default(),
+                })
+                .collect(),
+        }
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_check_double_spends() -> Result<()> {
+        create_persister!(persister);
+        let swapper = Arc::new(MockSwapper::default());
+        let status_stream = Arc::new(MockStatusStream::new());
+        let liquid_chain_service = Arc::new(MockLiquidChainService::new());
+        let bitcoin_chain_service = Arc::new(MockBitcoinChainService::new());
+
+        let sdk = new_liquid_sdk_with_chain_services(
+            persister.clone(),
+            swapper.clone(),
+            status_stream.clone(),
+            liquid_chain_service.clone(),
+            bitcoin_chain_service.clone(),
+            None,
+        )
+        .await?;
+
+        // Four swaps are locked up by txs spending outputs of the same funding tx
+        let funding_script = elements::Script::from(vec![0x51]);
+        let other_script = elements::Script::from(vec![0x52]);
+        let funding_tx = new_test_tx(vec![], vec![(funding_script.clone(), 10_000); 4]);
+        let funding_outpoint = |vout| elements::OutPoint::new(funding_tx.txid(), vout);
+        let new_watched_swap = |vout| -> Result<(ReceiveSwap, elements::Transaction)> {
+            let swap = new_receive_swap(Some(PaymentState::Pending), None);
+            let lockup_tx = new_test_tx(
+                vec![funding_outpoint(vout)],
+                vec![(swap.claim_script()?, 1_000 + vout as u64)],
+            );
+            let swap = ReceiveSwap {
+                lockup_tx_id: Some(lockup_tx.txid().to_string()),
+                ..swap
+            };
+            persister.insert_or_update_receive_swap(&swap)?;
+            persister.insert_watched_outpoints(&swap.id, &lockup_tx)?;
+            Ok((swap, lockup_tx))
+        };
+        let (failed_swap, failed_lockup_tx) = new_watched_swap(0)?;
+        let (held_swap, held_lockup_tx) = new_watched_swap(1)?;
+        let (stale_swap, stale_lockup_tx) = new_watched_swap(2)?;
+        let (confirmed_swap, confirmed_lockup_tx) = new_watched_swap(3)?;
+
+        // The stale swap was locked up again by another tx since the watch was added
+        let stale_swap = ReceiveSwap {
+            lockup_tx_id: Some(elements::Txid::from_byte_array([1; 32]).to_string()),
+            ..stale_swap
+        };
+        persister.insert_or_update_receive_swap(&stale_swap)?;
+
+        // A direct payment spends an output of the failed lockup tx
+        let direct_tx = new_test_tx(
+            vec![elements::OutPoint::new(failed_lockup_tx.txid(), 0)],
+            vec![(other_script.clone(), 500)],
+        );
+        let (mut direct_tx_data, direct_balance) =
+            new_payment_tx_data(LiquidNetwork::Regtest, PaymentType::Receive);
+        direct_tx_data.tx_id = direct_tx.txid().to_string();
+        persister.insert_or_update_payment(direct_tx_data, &[direct_balance], None, false)?;
+        persister.insert_watched_outpoints(&direct_tx.txid().to_string(), &direct_tx)?;
+
+        // Nothing happens while the watched txs are the only spenders
+        let txs = vec![
+            funding_tx.clone(),
+            failed_lockup_tx.clone(),
+            held_lockup_tx.clone(),
+            stale_lockup_tx.clone(),
+            confirmed_lockup_tx.clone(),
+        ];
+        let history = |txs: &[&elements::Transaction], height| {
+            txs.iter()
+                .map(|tx| LBtcHistory {
+                    txid: tx.txid(),
+                    height,
+                })
+                .collect::<Vec<_>>()
+        };
+        liquid_chain_service.set_transactions(txs.clone());
+        liquid_chain_service.set_script_history(
+            funding_script.clone(),
+            history(
+                &[
+                    &failed_lockup_tx,
+                    &held_lockup_tx,
+                    &stale_lockup_tx,
+                    &confirmed_lockup_tx,
+                ],
+                0,
+            ),
+        );
+        sdk.check_double_spends().await?;
+        assert_eq!(persister.list_watched_outpoints()?.len(), 5);
+
+        // The watched txs are replaced, except for the one that confirmed
+        let failed_conflict_tx = new_test_tx(
+            vec![funding_outpoint(0)],
+            vec![(other_script.clone(), 1_000)],
+        );
+        let held_conflict_tx = new_test_tx(
+            vec![funding_outpoint(1)],
+            vec![(held_swap.claim_script()?, 2_000)],
+        );
+        let stale_conflict_tx = new_test_tx(
+            vec![funding_outpoint(2)],
+            vec![(other_script.clone(), 3_000)],
+        );
+        let direct_conflict_tx = new_test_tx(
+            vec![elements::OutPoint::new(failed_lockup_tx.txid(), 0)],
+            vec![(other_script.clone(), 600)],
+        );
+        liquid_chain_service.set_transactions(
+            [
+                txs,
+                vec![
+                    failed_conflict_tx.clone(),
+                    held_conflict_tx.clone(),
+                    stale_conflict_tx.clone(),
+                    direct_conflict_tx.clone(),
+                ],
+            ]
+            .concat(),
+        );
+        liquid_chain_service.set_script_history(
+            funding_script.clone(),
+            [
+                history(
+                    &[&failed_conflict_tx, &held_conflict_tx, &stale_conflict_tx],
+                    0,
+                ),
+                history(&[&confirmed_lockup_tx], 10),
+            ]
+            .concat(),
+        );
+        liquid_chain_service.set_script_history(
+            failed_swap.claim_script()?,
+            history(&[&direct_conflict_tx], 0),
+        );
+        sdk.check_double_spends().await?;
+
+        let fetch_state = |id: &str| -> Result<PaymentState> {
+            Ok(persister
+                .fetch_receive_swap_by_id(id)?
+                .ok_or(anyhow!("Could not retrieve receive swap"))?
+                .state)
+        };
+        assert_eq!(fetch_state(&failed_swap.id)?, PaymentState::Failed);
+        assert_eq!(fetch_state(&held_swap.id)?, PaymentState::Pending);
+        assert_eq!(fetch_state(&stale_swap.id)?, PaymentState::Pending);
+        assert_eq!(fetch_state(&confirmed_swap.id)?, PaymentState::Pending);
+        assert!(persister
+            .is_watched_tx_double_spent(&failed_swap.id, &failed_lockup_tx.txid().to_string())?);
+        assert!(persister
+            .is_watched_tx_double_spent(&held_swap.id, &held_lockup_tx.txid().to_string())?);
+        assert!(!persister.is_watched_tx_double_spent(
+            &confirmed_swap.id,
+            &confirmed_lockup_tx.txid().to_string()
+        )?);
+
+        let direct_payment = persister
+            .get_payment(&direct_tx.txid().to_string())?
+            .ok_or(anyhow!("Could not retrieve payment"))?;
+        assert_eq!(direct_payment.status, PaymentState::Failed);
+
+        // Double-spent and confirmed txs are no longer watched
+        assert!(persister.list_watched_outpoints()?.is_empty());
+
+        Ok(())
+    }
+
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    #[sdk_macros::async_test_all]
+    async fn test_receive_with_mock_boltz_server() -> Result<()> {
+        use crate::{
+            model::{
+                Config, PaymentMethod, PrepareReceiveRequest, ReceiveAmount, ReceivePaymentRequest,
+            },
+            swapper::boltz::BoltzSwapper,
+            test_utils::{boltz_server::MockBoltzServer, swapper::MockProxyUrlFetcher},
+        };
+
+        create_persister!(persister);
+        let server = MockBoltzServer::start().await?;
+        let swapper = Arc::new(
+            BoltzSwapper::new(
+                Config::regtest_esplora(),
+                Arc::new(MockProxyUrlFetcher::new()),
+            )?
+            .with_boltz_url(server.url()),
+        );
+        let sdk = new_liquid_sdk_with_chain_services(
+            persister.clone(),
+            swapper.clone(),
+            swapper,
+            Arc::new(MockLiquidChainService::new()),
+            Arc::new(MockBitcoinChainService::new()),
+            None,
+        )
+        .await?;
+        sdk.start().await?;
+
+        // The swap is created through the server's REST API
+        let prepare_response = sdk
+            .prepare_receive_payment(&PrepareReceiveRequest {
+                payment_method: PaymentMethod::Bolt11Invoice,
+                amount: Some(ReceiveAmount::Bitcoin {
+                    payer_amount_sat: 50_000,
+                }),
+                swap_provider: None,
+            })
+            .await?;
+        let receive_response = sdk
+            .receive_payment(&ReceivePaymentRequest {
+                prepare_response,
+                description: None,
+                description_hash: None,
+                payer_note: None,
+                settle_as_asset: None,
+            })
+            .await?;
+        let swap = persister
+            .fetch_receive_swap_by_invoice(&receive_response.destination)?
+            .ok_or(anyhow!("Could not retrieve receive swap"))?;
+        assert_eq!(swap.state, PaymentState::Created);
+
+        // Its updates are received over the server's websocket
+        server
+            .script_swap_statuses(&swap.id, vec!["swap.expired"], Duration::from_millis(500))
+            .await?;
+        let mut state = swap.state;
+        for _ in 0..50 {
+            state = persister
+                .fetch_receive_swap_by_id(&swap.id)?
+                .ok_or(anyhow!("Could not retrieve receive swap"))?
+                .state;
+            if state == PaymentState::Failed {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert_eq!(state, PaymentState::Failed);
+
+        sdk.disconnect().await?;
+        Ok(())
+    }
+
     #[sdk_macros::async_test_all]
     async fn test_background_tasks() -> Result<()> {
         create_persister!(persister);
@@ -5975,4 +9487,1513 @@ mod tests {
 
         Ok(())
     }
+
+    /// Builds a started SDK on a simulated network, with a config adjusted by `configure` and
+    /// services overridden by `configure_builder`
+    async fn new_simulated_sdk(
+        persister: Arc<crate::persist::Persister>,
+        configure: impl FnOnce(&mut crate::model::Config),
+        configure_builder: impl FnOnce(&mut crate::sdk::LiquidSdkBuilder),
+    ) -> Result<(Arc<LiquidSdk>, crate::test_utils::simulation::Simulator)> {
+        use crate::{
+            model::{Config, Signer},
+            sdk::LiquidSdkBuilder,
+            test_utils::wallet::MockSigner,
+        };
+
+        let signer: Arc<Box<dyn Signer>> = Arc::new(Box::new(MockSigner::new()?));
+        let mut config = Config::regtest_esplora();
+        config.working_dir = persister
+            .get_database_dir()
+            .to_str()
+            .ok_or(anyhow!("An invalid SDK directory was specified"))?
+            .to_string();
+        configure(&mut config);
+
+        let (mut builder, simulator) = LiquidSdkBuilder::simulated(config, signer)?;
+        builder.persister(persister);
+        configure_builder(&mut builder);
+        let sdk = builder.build().await?;
+        sdk.start().await?;
+        Ok((sdk, simulator))
+    }
+
+    /// Builds a started SDK on a simulated network, using the given SideSwap server
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    async fn new_sideswap_sdk(
+        persister: Arc<crate::persist::Persister>,
+        server: &crate::test_utils::sideswap_server::MockSideSwapServer,
+        configure: impl FnOnce(&mut crate::model::Config),
+    ) -> Result<(Arc<LiquidSdk>, crate::test_utils::simulation::Simulator)> {
+        // The payjoins and asset swaps are executed with HTTP requests to the server
+        let rest_client = Arc::new(sdk_common::prelude::ReqwestRestClient::new()?);
+        new_simulated_sdk(
+            persister,
+            |config| {
+                config.sideswap_url = Some(server.url());
+                configure(config);
+            },
+            |builder| {
+                builder.rest_client(rest_client);
+            },
+        )
+        .await
+    }
+
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    #[sdk_macros::async_test_all]
+    async fn test_send_payment_from_asset_failure() -> Result<()> {
+        use crate::{
+            error::PaymentError,
+            model::{PrepareSendRequest, SendPaymentRequest},
+            test_utils::{
+                faults::{Fault, FaultTarget},
+                sideswap_server::MockSideSwapServer,
+            },
+        };
+
+        create_persister!(persister);
+        let server = MockSideSwapServer::start().await?;
+        let (sdk, simulator) = new_sideswap_sdk(persister.clone(), &server, |_| {}).await?;
+        let asset_id = elements::AssetId::from_slice(&[2; 32])?.to_string();
+        simulator.fund_wallet(&sdk, 10_000).await?;
+        simulator
+            .fund_wallet_with_asset(&sdk, &asset_id, 1_000_000)
+            .await?;
+
+        let payment_hash = "01".repeat(32);
+        let invoice = MockSwapper::mock_invoice(&payment_hash, 50_000, String::new())?;
+        let prepare_response = sdk
+            .prepare_send_payment(&PrepareSendRequest {
+                destination: invoice,
+                amount: None,
+                disable_mrh: None,
+                payment_timeout_sec: None,
+                swap_provider: None,
+                from_asset: Some(asset_id),
+                fee_asset: None,
+                export_pset: None,
+            })
+            .await?;
+        // Only the L-BTC missing from the balance is converted
+        let exchange_amount_sat = prepare_response
+            .exchange_amount_sat
+            .ok_or(anyhow!("Expected an exchange amount"))?;
+        let fees_sat = prepare_response.fees_sat.ok_or(anyhow!("Expected fees"))?;
+        assert_eq!(exchange_amount_sat, 50_000 + fees_sat - 10_000 + 1_000);
+        let send_request = SendPaymentRequest {
+            prepare_response,
+            use_asset_fees: None,
+            payer_note: None,
+            sub_account_id: None,
+        };
+
+        // Nothing is converted when the swap cannot be created
+        simulator.faults().inject_times(
+            FaultTarget::Swapper,
+            "create_send_swap",
+            Fault::Error("Swap creation failed".to_string()),
+            1,
+        );
+        assert!(sdk.send_payment(&send_request).await.is_err());
+        assert!(persister
+            .fetch_send_swap_by_payment_hash(&payment_hash)?
+            .is_none());
+        assert!(server.swap_txs().is_empty());
+
+        // Nor when the conversion costs more of the asset than quoted when preparing
+        server.set_price(2.0);
+        assert!(matches!(
+            sdk.send_payment(&send_request).await,
+            Err(PaymentError::InvalidOrExpiredFees)
+        ));
+        let swap = persister
+            .fetch_send_swap_by_payment_hash(&payment_hash)?
+            .ok_or(anyhow!("Expected a send swap"))?;
+        assert!(swap.lockup_tx_id.is_none());
+        assert!(server.swap_txs().is_empty());
+
+        sdk.disconnect().await?;
+        Ok(())
+    }
+
+    /// A regtest asset with two decimal places, and a regtest address to send it to
+    fn test_asset_metadata() -> Result<(crate::model::AssetMetadata, String)> {
+        Ok((
+            crate::model::AssetMetadata {
+                asset_id: elements::AssetId::from_slice(&[2; 32])?.to_string(),
+                name: "Test Asset".to_string(),
+                ticker: "TST".to_string(),
+                precision: 2,
+                fiat_id: None,
+            },
+            "el1pqtjufhhy2se6lj2t7wufvpqqhnw66v57x2s0uu5dxs4fqlzlvh3hqe87vn83z3qreh8kxn49xe0h0fpe4kjkhl4gv99tdppupk0tdd485q8zegdag97r".to_string(),
+        ))
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_prepare_drain_asset() -> Result<()> {
+        use crate::{
+            model::{PayAmount, PrepareSendRequest, SendDestination},
+            test_utils::{
+                payjoin::{MockPayjoinService, PayjoinCall},
+                wallet::TEST_LIQUID_TX,
+            },
+        };
+
+        create_persister!(persister);
+        let (asset_metadata, address) = test_asset_metadata()?;
+        let asset_id = asset_metadata.asset_id.clone();
+        let payjoin_service = Arc::new(MockPayjoinService::new(&asset_id, 0.25, 25));
+        let (sdk, simulator) = new_simulated_sdk(
+            persister,
+            |config| config.asset_metadata = Some(vec![asset_metadata]),
+            |builder| {
+                builder.payjoin_service(payjoin_service.clone());
+            },
+        )
+        .await?;
+        simulator.fund_wallet(&sdk, 10_000).await?;
+        simulator
+            .fund_wallet_with_asset(&sdk, &asset_id, 6_000)
+            .await?;
+        simulator
+            .fund_wallet_with_asset(&sdk, &asset_id, 4_000)
+            .await?;
+
+        let prepare_request = PrepareSendRequest {
+            destination: address,
+            amount: Some(PayAmount::DrainAsset {
+                asset_id: asset_id.clone(),
+            }),
+            disable_mrh: None,
+            payment_timeout_sec: None,
+            swap_provider: None,
+            from_asset: None,
+            fee_asset: None,
+            export_pset: None,
+        };
+        let receiver_amount_sat = |destination: &SendDestination| match destination {
+            SendDestination::LiquidAddress { address_data, .. } => address_data.amount_sat,
+            _ => None,
+        };
+
+        // The fees are paid in L-BTC when there is enough of it
+        let response = sdk.prepare_send_payment(&prepare_request).await?;
+        let lbtc_fees_sat = TEST_LIQUID_TX.all_fees().values().sum::<u64>();
+        assert_eq!(receiver_amount_sat(&response.destination), Some(10_000));
+        assert_eq!(response.fees_sat, Some(lbtc_fees_sat));
+        assert_eq!(response.estimated_asset_fees, None);
+        assert!(payjoin_service.calls().is_empty());
+
+        // Otherwise they are paid in the asset and deducted from the drained amount, which
+        // spends all of its utxos
+        simulator.onchain_wallet.set_insufficient_funds(true);
+        let response = sdk.prepare_send_payment(&prepare_request).await?;
+        assert_eq!(receiver_amount_sat(&response.destination), Some(9_975));
+        assert_eq!(response.fees_sat, None);
+        assert_eq!(response.estimated_asset_fees, Some(0.25));
+        assert_eq!(
+            payjoin_service.calls(),
+            vec![PayjoinCall::EstimateDrainTxFee { asset_id }]
+        );
+
+        sdk.disconnect().await?;
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_send_payment_with_payjoin_lockup() -> Result<()> {
+        use crate::{
+            error::PaymentError,
+            model::{PrepareSendRequest, SendPaymentRequest},
+            test_utils::{
+                payjoin::{MockPayjoinService, PayjoinCall},
+                wallet::TEST_LIQUID_TX,
+            },
+        };
+
+        create_persister!(persister);
+        let (asset_metadata, _) = test_asset_metadata()?;
+        let asset_id = asset_metadata.asset_id.clone();
+        let payjoin_service = Arc::new(MockPayjoinService::new(&asset_id, 0.25, 25));
+        let (sdk, simulator) = new_simulated_sdk(
+            persister.clone(),
+            |config| config.asset_metadata = Some(vec![asset_metadata]),
+            |builder| {
+                builder.payjoin_service(payjoin_service.clone());
+            },
+        )
+        .await?;
+        simulator.fund_wallet(&sdk, 100_000).await?;
+
+        let payment_hash = "02".repeat(32);
+        let invoice = MockSwapper::mock_invoice(&payment_hash, 50_000, String::new())?;
+        let prepare_request = |fee_asset: Option<String>| PrepareSendRequest {
+            destination: invoice.clone(),
+            amount: None,
+            disable_mrh: None,
+            payment_timeout_sec: Some(1),
+            swap_provider: None,
+            from_asset: None,
+            fee_asset,
+            export_pset: None,
+        };
+
+        // The lockup network fees are estimated in the asset, for the amount locked up
+        let prepare_response = sdk
+            .prepare_send_payment(&prepare_request(Some(asset_id.clone())))
+            .await?;
+        let fees_sat = prepare_response.fees_sat.ok_or(anyhow!("Expected fees"))?;
+        let lockup_amount_sat = 50_000 + fees_sat;
+        assert_eq!(prepare_response.estimated_asset_fees, Some(0.25));
+        assert_eq!(
+            payjoin_service.calls(),
+            vec![PayjoinCall::EstimateLbtcTxFee {
+                fee_asset_id: asset_id.clone(),
+                amount_sat: lockup_amount_sat,
+            }]
+        );
+
+        // The asset fees must be used if and only if they were prepared
+        let send_request = |prepare_response, use_asset_fees| SendPaymentRequest {
+            prepare_response,
+            use_asset_fees,
+            payer_note: None,
+            sub_account_id: None,
+        };
+        assert!(matches!(
+            sdk.send_payment(&send_request(prepare_response.clone(), None)).await,
+            Err(PaymentError::Generic { err }) if err.contains("asset fees must be used")
+        ));
+        let lbtc_prepare_response = sdk.prepare_send_payment(&prepare_request(None)).await?;
+        assert!(matches!(
+            sdk.send_payment(&send_request(lbtc_prepare_response, Some(true))).await,
+            Err(PaymentError::Generic { err }) if err.contains("not prepared to be paid in an asset")
+        ));
+        assert!(persister
+            .fetch_send_swap_by_payment_hash(&payment_hash)?
+            .is_none());
+
+        // The lockup tx built via the payjoin service is broadcast
+        sdk.send_payment(&send_request(prepare_response, Some(true)))
+            .await?;
+        let swap = persister
+            .fetch_send_swap_by_payment_hash(&payment_hash)?
+            .ok_or(anyhow!("Expected a send swap"))?;
+        assert_eq!(swap.state, PaymentState::Pending);
+        assert_eq!(swap.lockup_tx_id, Some(TEST_LIQUID_TX.txid().to_string()));
+        let create_response = swap.get_boltz_create_response()?;
+        assert_eq!(create_response.expected_amount, lockup_amount_sat);
+        assert_eq!(
+            payjoin_service.calls().last(),
+            Some(&PayjoinCall::BuildLbtcTx {
+                recipient_address: create_response.address,
+                fee_asset_id: asset_id,
+                amount_sat: lockup_amount_sat,
+            })
+        );
+
+        sdk.disconnect().await?;
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_pay_onchain_with_payjoin_lockup() -> Result<()> {
+        use crate::{
+            error::PaymentError,
+            model::{PayAmount, PayOnchainRequest, PreparePayOnchainRequest},
+            test_utils::{
+                payjoin::{MockPayjoinService, PayjoinCall},
+                wallet::TEST_LIQUID_TX,
+            },
+        };
+
+        create_persister!(persister);
+        let (asset_metadata, _) = test_asset_metadata()?;
+        let asset_id = asset_metadata.asset_id.clone();
+        let payjoin_service = Arc::new(MockPayjoinService::new(&asset_id, 0.25, 25));
+        let (sdk, simulator) = new_simulated_sdk(
+            persister.clone(),
+            |config| {
+                config.asset_metadata = Some(vec![asset_metadata]);
+                config.payment_timeout_sec = 1;
+            },
+            |builder| {
+                builder.payjoin_service(payjoin_service.clone());
+            },
+        )
+        .await?;
+        simulator.fund_wallet(&sdk, 100_000).await?;
+
+        // The lockup network fees are estimated in the asset, for the amount locked up
+        let prepare_response = sdk
+            .prepare_pay_onchain(&PreparePayOnchainRequest {
+                amount: PayAmount::Bitcoin {
+                    receiver_amount_sat: 50_000,
+                },
+                fee_rate_sat_per_vbyte: None,
+                swap_provider: None,
+                fee_asset: Some(asset_id.clone()),
+            })
+            .await?;
+        let lockup_amount_sat =
+            prepare_response.receiver_amount_sat + prepare_response.total_fees_sat;
+        assert_eq!(prepare_response.estimated_asset_fees, Some(0.25));
+        assert_eq!(
+            payjoin_service.calls(),
+            vec![PayjoinCall::EstimateLbtcTxFee {
+                fee_asset_id: asset_id.clone(),
+                amount_sat: lockup_amount_sat,
+            }]
+        );
+
+        let address =
+            bitcoin::Address::p2wsh(&bitcoin::ScriptBuf::new(), Network::Regtest).to_string();
+        let pay_request = |use_asset_fees| PayOnchainRequest {
+            address: address.clone(),
+            prepare_response: prepare_response.clone(),
+            use_asset_fees,
+        };
+        assert!(matches!(
+            sdk.pay_onchain(&pay_request(None)).await,
+            Err(PaymentError::Generic { err }) if err.contains("asset fees must be used")
+        ));
+
+        // The lockup tx built via the payjoin service is broadcast
+        sdk.pay_onchain(&pay_request(Some(true))).await?;
+        let swaps = persister.list_chain_swaps()?;
+        assert_eq!(swaps.len(), 1);
+        assert_eq!(
+            swaps[0].user_lockup_tx_id,
+            Some(TEST_LIQUID_TX.txid().to_string())
+        );
+        let create_response = swaps[0].get_boltz_create_response()?;
+        assert_eq!(create_response.lockup_details.amount, lockup_amount_sat);
+        assert_eq!(
+            payjoin_service.calls().last(),
+            Some(&PayjoinCall::BuildLbtcTx {
+                recipient_address: create_response.lockup_details.lockup_address,
+                fee_asset_id: asset_id,
+                amount_sat: lockup_amount_sat,
+            })
+        );
+
+        sdk.disconnect().await?;
+        Ok(())
+    }
+
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    #[sdk_macros::async_test_all]
+    async fn test_send_payment_via_sideswap() -> Result<()> {
+        use crate::{
+            model::{PayAmount, PrepareSendRequest, SendPaymentRequest},
+            test_utils::sideswap_server::MockSideSwapServer,
+        };
+
+        create_persister!(persister);
+        let (asset_metadata, address) = test_asset_metadata()?;
+        let asset_id = elements::AssetId::from_str(&asset_metadata.asset_id)?;
+        let lbtc_asset_id = utils::lbtc_asset_id(LiquidNetwork::Regtest);
+        let server = MockSideSwapServer::start().await?;
+        let server_txid = elements::Txid::from_str(&"03".repeat(32))?;
+        server.set_price(0.5);
+        server.add_utxo(server_txid, 0, asset_id, 100_000);
+        server.add_utxo(server_txid, 1, lbtc_asset_id, 10_000);
+
+        let (sdk, simulator) = new_sideswap_sdk(persister, &server, |config| {
+            config.asset_metadata = Some(vec![asset_metadata])
+        })
+        .await?;
+        let funding_tx_id = simulator
+            .fund_wallet_with_asset(&sdk, &lbtc_asset_id.to_string(), 200_000)
+            .await?;
+
+        // The asset is bought with L-BTC at the server price, plus its fixed fee
+        let prepare_response = sdk
+            .prepare_send_payment(&PrepareSendRequest {
+                destination: address,
+                amount: Some(PayAmount::Asset {
+                    to_asset: asset_id.to_string(),
+                    from_asset: Some(lbtc_asset_id.to_string()),
+                    receiver_amount: 500.0,
+                    estimate_asset_fees: None,
+                }),
+                disable_mrh: None,
+                payment_timeout_sec: None,
+                swap_provider: None,
+                from_asset: None,
+                fee_asset: None,
+                export_pset: None,
+            })
+            .await?;
+        assert_eq!(prepare_response.exchange_amount_sat, Some(100_000));
+        assert_eq!(prepare_response.fees_sat, Some(1_000));
+
+        // The swap tx spends the wallet L-BTC and is the payment tx
+        let payment = sdk
+            .send_payment(&SendPaymentRequest {
+                prepare_response,
+                use_asset_fees: None,
+                payer_note: None,
+                sub_account_id: None,
+            })
+            .await?
+            .payment;
+        let swap_txs = server.swap_txs();
+        assert_eq!(swap_txs.len(), 1);
+        assert_eq!(payment.tx_id, Some(swap_txs[0].txid().to_string()));
+        assert_eq!(payment.status, PaymentState::Pending);
+        assert!(swap_txs[0]
+            .input
+            .iter()
+            .any(|input| input.previous_output.txid.to_string() == funding_tx_id));
+
+        sdk.disconnect().await?;
+        Ok(())
+    }
+
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    #[sdk_macros::async_test_all]
+    async fn test_asset_swap() -> Result<()> {
+        use crate::{
+            error::PaymentError,
+            model::{AssetSwapRequest, PaymentDetails, PrepareAssetSwapRequest},
+            test_utils::sideswap_server::MockSideSwapServer,
+        };
+
+        create_persister!(persister);
+        let (asset_metadata, _) = test_asset_metadata()?;
+        let asset_id = elements::AssetId::from_str(&asset_metadata.asset_id)?;
+        let lbtc_asset_id = utils::lbtc_asset_id(LiquidNetwork::Regtest);
+        let server = MockSideSwapServer::start().await?;
+        let server_txid = elements::Txid::from_str(&"03".repeat(32))?;
+        server.set_price(0.5);
+        server.add_utxo(server_txid, 0, asset_id, 100_000);
+        server.add_utxo(server_txid, 1, lbtc_asset_id, 10_000);
+
+        let (sdk, simulator) = new_sideswap_sdk(persister, &server, |config| {
+            config.asset_metadata = Some(vec![asset_metadata])
+        })
+        .await?;
+        simulator
+            .fund_wallet_with_asset(&sdk, &lbtc_asset_id.to_string(), 200_000)
+            .await?;
+
+        // The asset is quoted at the server price, plus its fixed fee
+        let prepare_response = sdk
+            .prepare_asset_swap(&PrepareAssetSwapRequest {
+                from_asset: lbtc_asset_id.to_string(),
+                to_asset: asset_id.to_string(),
+                receiver_amount: 500.0,
+            })
+            .await?;
+        assert_eq!(prepare_response.receiver_amount_sat, 50_000);
+        assert_eq!(prepare_response.payer_amount_sat, 101_000);
+        assert_eq!(prepare_response.fees_sat, 1_000);
+        assert_eq!(prepare_response.exchange_rate, 0.5);
+        let swap_request = |prepare_response| AssetSwapRequest {
+            prepare_response,
+            description: Some("Asset swap".to_string()),
+        };
+
+        // The swap is rejected when the price moves against the quote
+        server.set_price(0.4);
+        assert!(matches!(
+            sdk.asset_swap(&swap_request(prepare_response.clone()))
+                .await,
+            Err(PaymentError::InvalidOrExpiredFees)
+        ));
+
+        // Or when the quoted fees are no longer valid
+        server.set_price(0.5);
+        server.set_fixed_fee(2_000);
+        assert!(matches!(
+            sdk.asset_swap(&swap_request(prepare_response.clone()))
+                .await,
+            Err(PaymentError::InvalidOrExpiredFees)
+        ));
+        assert!(server.swap_txs().is_empty());
+
+        // A price moving in favor of the quote is accepted
+        server.set_fixed_fee(1_000);
+        server.set_price(0.6);
+        let payment = sdk
+            .asset_swap(&swap_request(prepare_response))
+            .await?
+            .payment;
+        let swap_txs = server.swap_txs();
+        assert_eq!(swap_txs.len(), 1);
+        assert_eq!(payment.tx_id, Some(swap_txs[0].txid().to_string()));
+        assert_eq!(payment.status, PaymentState::Pending);
+        let PaymentDetails::AssetSwap {
+            description,
+            from_asset_id,
+            to_asset_id,
+            payer_amount_sat,
+            receiver_amount_sat,
+            to_asset_info,
+            ..
+        } = payment.details
+        else {
+            panic!("Expected an asset swap payment");
+        };
+        assert_eq!(description, "Asset swap");
+        assert_eq!(from_asset_id, lbtc_asset_id.to_string());
+        assert_eq!(to_asset_id, asset_id.to_string());
+        assert!(payer_amount_sat < 101_000);
+        assert_eq!(receiver_amount_sat, 50_000);
+        assert_eq!(
+            to_asset_info.map(|info| info.ticker),
+            Some("TST".to_string())
+        );
+
+        sdk.disconnect().await?;
+        Ok(())
+    }
+
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    #[sdk_macros::async_test_all]
+    async fn test_receive_payment_settled_as_asset() -> Result<()> {
+        use crate::{
+            model::{
+                ListPaymentsRequest, PaymentDetails, PaymentMethod, PrepareReceiveRequest,
+                ReceiveAmount, ReceivePaymentRequest, SettleAsAsset,
+            },
+            test_utils::sideswap_server::MockSideSwapServer,
+        };
+
+        create_persister!(persister);
+        let (asset_metadata, _) = test_asset_metadata()?;
+        let asset_id = elements::AssetId::from_str(&asset_metadata.asset_id)?;
+        let lbtc_asset_id = utils::lbtc_asset_id(LiquidNetwork::Regtest);
+        let server = MockSideSwapServer::start().await?;
+        let server_txid = elements::Txid::from_str(&"03".repeat(32))?;
+        server.set_price(0.5);
+        server.add_utxo(server_txid, 0, asset_id, 100_000);
+        server.add_utxo(server_txid, 1, lbtc_asset_id, 10_000);
+
+        // The settlements are run explicitly rather than on new blocks
+        let (sdk, simulator) = new_sideswap_sdk(persister.clone(), &server, |config| {
+            config.asset_metadata = Some(vec![asset_metadata]);
+            config.onchain_sync_period_sec = 3_600;
+        })
+        .await?;
+        simulator
+            .fund_wallet_with_asset(&sdk, &lbtc_asset_id.to_string(), 100_000)
+            .await?;
+
+        async fn receive_settled_as_asset(sdk: &LiquidSdk, asset_id: &str) -> Result<String> {
+            let prepare_response = sdk
+                .prepare_receive_payment(&PrepareReceiveRequest {
+                    payment_method: PaymentMethod::Bolt11Invoice,
+                    amount: Some(ReceiveAmount::Bitcoin {
+                        payer_amount_sat: 50_000,
+                    }),
+                    swap_provider: None,
+                })
+                .await?;
+            Ok(sdk
+                .receive_payment(&ReceivePaymentRequest {
+                    prepare_response,
+                    description: None,
+                    description_hash: None,
+                    payer_note: None,
+                    settle_as_asset: Some(SettleAsAsset {
+                        asset_id: asset_id.to_string(),
+                        max_slippage_bps: Some(100),
+                    }),
+                })
+                .await?
+                .destination)
+        }
+        let settlement_swap_ids = |sdk: &LiquidSdk| -> Result<Vec<String>> {
+            Ok(sdk
+                .persister
+                .get_payments(&ListPaymentsRequest::default())?
+                .into_iter()
+                .filter_map(|payment| match payment.details {
+                    PaymentDetails::AssetSwap {
+                        receive_swap_id, ..
+                    } => receive_swap_id,
+                    _ => None,
+                })
+                .collect())
+        };
+
+        // The received L-BTC is converted once the Receive swap completes
+        let invoice = receive_settled_as_asset(&sdk, &asset_id.to_string()).await?;
+        simulator.pay_invoice(&sdk, &invoice).await?;
+        let receive_swap = persister
+            .fetch_receive_swap_by_invoice(&invoice)?
+            .ok_or(anyhow!("Expected a receive swap"))?;
+        assert_eq!(persister.list_pending_asset_settlements()?.len(), 1);
+        sdk.settle_received_assets().await;
+        assert!(persister.list_pending_asset_settlements()?.is_empty());
+        let swap_txs = server.swap_txs();
+        assert_eq!(swap_txs.len(), 1);
+        assert_eq!(settlement_swap_ids(&sdk)?, vec![receive_swap.id]);
+
+        // The L-BTC is kept when the quote drops by more than the allowed slippage
+        let invoice = receive_settled_as_asset(&sdk, &asset_id.to_string()).await?;
+        simulator.pay_invoice(&sdk, &invoice).await?;
+        server.set_price(0.45);
+        sdk.settle_received_assets().await;
+        assert!(persister.list_pending_asset_settlements()?.is_empty());
+        assert_eq!(server.swap_txs().len(), 1);
+        assert_eq!(settlement_swap_ids(&sdk)?.len(), 1);
+
+        sdk.disconnect().await?;
+        Ok(())
+    }
+
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    #[sdk_macros::async_test_all]
+    async fn test_send_payment_with_sideswap_payjoin() -> Result<()> {
+        use crate::{
+            error::PaymentError,
+            model::{PayAmount, PaymentDetails, PrepareSendRequest, SendPaymentRequest},
+            test_utils::sideswap_server::MockSideSwapServer,
+        };
+
+        create_persister!(persister);
+        // The payjoin fees are estimated from the fiat rate of the asset
+        let (mut asset_metadata, address) = test_asset_metadata()?;
+        asset_metadata.precision = 8;
+        asset_metadata.fiat_id = Some("USD".to_string());
+        let asset_id = elements::AssetId::from_str(&asset_metadata.asset_id)?;
+        let lbtc_asset_id = utils::lbtc_asset_id(LiquidNetwork::Regtest);
+        let server = MockSideSwapServer::start().await?;
+        // Priced at the simulated USD rate, with the fixed fee SideSwap charges
+        server.set_price(100_000.0);
+        server.set_fixed_fee(4_000_000);
+        server.set_accepted_assets(vec![asset_id]);
+        server.add_utxo(
+            elements::Txid::from_str(&"03".repeat(32))?,
+            0,
+            lbtc_asset_id,
+            10_000,
+        );
+
+        let (sdk, simulator) = new_sideswap_sdk(persister, &server, |config| {
+            config.asset_metadata = Some(vec![asset_metadata])
+        })
+        .await?;
+        simulator.fund_wallet(&sdk, 10_000).await?;
+        simulator
+            .fund_wallet_with_asset(&sdk, &asset_id.to_string(), 100_000_000)
+            .await?;
+
+        let prepare_response = sdk
+            .prepare_send_payment(&PrepareSendRequest {
+                destination: address,
+                amount: Some(PayAmount::Asset {
+                    to_asset: asset_id.to_string(),
+                    from_asset: None,
+                    receiver_amount: 0.5,
+                    estimate_asset_fees: Some(true),
+                }),
+                disable_mrh: None,
+                payment_timeout_sec: None,
+                swap_provider: None,
+                from_asset: None,
+                fee_asset: None,
+                export_pset: None,
+            })
+            .await?;
+        assert!(prepare_response
+            .estimated_asset_fees
+            .is_some_and(|fees| fees > 0.0));
+        let send_request = SendPaymentRequest {
+            prepare_response,
+            use_asset_fees: Some(true),
+            payer_note: None,
+            sub_account_id: None,
+        };
+
+        // The payjoin tx pays the server fee in the asset
+        let payment = sdk.send_payment(&send_request).await?.payment;
+        assert!(payment.tx_id.is_some());
+        assert_eq!(payment.status, PaymentState::Pending);
+        let PaymentDetails::Liquid {
+            asset_info: Some(asset_info),
+            ..
+        } = payment.details
+        else {
+            return Err(anyhow!("Expected the asset info of a Liquid payment"));
+        };
+        assert_eq!(asset_info.amount, 0.5);
+        assert!(asset_info.fees.is_some_and(|fees| fees > 0.0));
+
+        // The server L-BTC utxo was spent by the payjoin tx
+        assert!(matches!(
+            sdk.send_payment(&send_request).await,
+            Err(PaymentError::Generic { err }) if err.contains("Server utxos are empty")
+        ));
+
+        sdk.disconnect().await?;
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_watch_only_sdk() -> Result<()> {
+        use crate::{
+            error::PaymentError,
+            model::{
+                PayAmount, PaymentMethod, PrepareReceiveRequest, PrepareSendRequest,
+                ReceivePaymentRequest, ReceivePaymentResponse, RefundRequest, SendPaymentRequest,
+            },
+        };
+
+        create_persister!(persister);
+        let (_, address) = test_asset_metadata()?;
+        let (sdk, simulator) = new_simulated_sdk(
+            persister,
+            |_| {},
+            |builder| {
+                builder.watch_only = true;
+            },
+        )
+        .await?;
+        simulator.fund_wallet(&sdk, 10_000).await?;
+
+        // Payments can be prepared, but not sent
+        let prepare_response = sdk
+            .prepare_send_payment(&PrepareSendRequest {
+                destination: address,
+                amount: Some(PayAmount::Bitcoin {
+                    receiver_amount_sat: 1_000,
+                }),
+                disable_mrh: None,
+                payment_timeout_sec: None,
+                swap_provider: None,
+                from_asset: None,
+                fee_asset: None,
+                export_pset: None,
+            })
+            .await?;
+        assert!(matches!(
+            sdk.send_payment(&SendPaymentRequest {
+                prepare_response,
+                use_asset_fees: None,
+                payer_note: None,
+                sub_account_id: None,
+            })
+            .await,
+            Err(PaymentError::WatchOnly)
+        ));
+
+        // Nor can swaps be refunded
+        assert!(matches!(
+            sdk.refund(&RefundRequest {
+                swap_address: "bcrt1qxqhtjd3k4u9gv0lwxwfs9cx5fu7f9mzhqcxkdp".to_string(),
+                refund_address: "bcrt1qxqhtjd3k4u9gv0lwxwfs9cx5fu7f9mzhqcxkdp".to_string(),
+                fee_rate_sat_per_vbyte: 1,
+            })
+            .await,
+            Err(PaymentError::WatchOnly)
+        ));
+
+        // Only Liquid payments can be received, as swaps would need to be claimed
+        async fn receive(
+            sdk: &LiquidSdk,
+            payment_method: PaymentMethod,
+        ) -> Result<ReceivePaymentResponse, PaymentError> {
+            let prepare_response = sdk
+                .prepare_receive_payment(&PrepareReceiveRequest {
+                    payment_method,
+                    amount: None,
+                    swap_provider: None,
+                })
+                .await?;
+            sdk.receive_payment(&ReceivePaymentRequest {
+                prepare_response,
+                description: None,
+                description_hash: None,
+                payer_note: None,
+                settle_as_asset: None,
+            })
+            .await
+        }
+        assert!(matches!(
+            receive(&sdk, PaymentMethod::BitcoinAddress).await,
+            Err(PaymentError::WatchOnly)
+        ));
+        let receive_response = receive(&sdk, PaymentMethod::LiquidAddress).await?;
+        assert!(elements::Address::from_str(&receive_response.destination).is_ok());
+
+        sdk.disconnect().await?;
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_finalize_send_payment() -> Result<()> {
+        use crate::{
+            elements::pset::PartiallySignedTransaction,
+            error::PaymentError,
+            model::{
+                CreateSubAccountRequest, FinalizeSendPaymentRequest, LedgerEntryType,
+                ListLedgerEntriesRequest, PayAmount, PrepareSendRequest, PrepareSendResponse,
+                SendPaymentResponse, TransferSubAccountFundsRequest,
+            },
+        };
+
+        create_persister!(persister);
+        let (_, address) = test_asset_metadata()?;
+        let (sdk, simulator) = new_simulated_sdk(persister, |_| {}, |_| {}).await?;
+        simulator.fund_wallet(&sdk, 10_000).await?;
+
+        let prepare_response = sdk
+            .prepare_send_payment(&PrepareSendRequest {
+                destination: address,
+                amount: Some(PayAmount::Bitcoin {
+                    receiver_amount_sat: 1_000,
+                }),
+                disable_mrh: None,
+                payment_timeout_sec: None,
+                swap_provider: None,
+                from_asset: None,
+                fee_asset: None,
+                export_pset: Some(true),
+            })
+            .await?;
+        let pset = utils::decode_pset(
+            prepare_response
+                .unsigned_pset
+                .as_deref()
+                .ok_or(anyhow!("Expected an unsigned PSET"))?,
+        )?;
+
+        // Finalizes the payment with the exported and the signed PSET
+        async fn finalize(
+            sdk: &LiquidSdk,
+            prepare_response: &PrepareSendResponse,
+            unsigned_pset: &PartiallySignedTransaction,
+            signed_pset: &PartiallySignedTransaction,
+        ) -> Result<SendPaymentResponse, PaymentError> {
+            let mut prepare_response = prepare_response.clone();
+            prepare_response.unsigned_pset = Some(utils::encode_pset(unsigned_pset));
+            sdk.finalize_send_payment(&FinalizeSendPaymentRequest {
+                prepare_response,
+                signed_pset: utils::encode_pset(signed_pset),
+                sub_account_id: None,
+            })
+            .await
+        }
+
+        // A signed PSET whose outputs were tampered with does not match the exported one
+        let mut tampered_pset = pset.clone();
+        tampered_pset.outputs_mut()[0].amount = Some(999);
+        assert!(matches!(
+            finalize(&sdk, &prepare_response, &pset, &tampered_pset).await,
+            Err(PaymentError::Generic { err }) if err.contains("does not match")
+        ));
+
+        // Neither do PSETs whose tx cannot be extracted
+        let mut invalid_pset = pset.clone();
+        invalid_pset.outputs_mut()[0].amount = None;
+        assert!(matches!(
+            finalize(&sdk, &prepare_response, &invalid_pset, &invalid_pset).await,
+            Err(PaymentError::Generic { err }) if err.contains("Invalid PSET")
+        ));
+
+        // Exported PSETs not matching the prepared payment are rejected too
+        let mut fee_pset = pset.clone();
+        let fees_sat = fee_pset.outputs()[1].amount.unwrap_or_default();
+        fee_pset.outputs_mut()[1].amount = Some(fees_sat + 100);
+        assert!(matches!(
+            finalize(&sdk, &prepare_response, &fee_pset, &fee_pset).await,
+            Err(PaymentError::InvalidOrExpiredFees)
+        ));
+        let mut destination_pset = pset.clone();
+        destination_pset.outputs_mut()[0].script_pubkey = elements::Script::from(vec![0x51]);
+        assert!(matches!(
+            finalize(&sdk, &prepare_response, &destination_pset, &destination_pset).await,
+            Err(PaymentError::Generic { err }) if err.contains("does not pay the prepared amount")
+        ));
+
+        // The exported PSET is broadcast once signed, debited from the sub-account paying it
+        let sub_account_id = sdk
+            .create_sub_account(&CreateSubAccountRequest {
+                name: "Treasury".to_string(),
+            })
+            .await?
+            .id;
+        sdk.transfer_sub_account_funds(&TransferSubAccountFundsRequest {
+            from_sub_account_id: None,
+            to_sub_account_id: Some(sub_account_id.clone()),
+            amount_sat: 5_000,
+            description: None,
+        })
+        .await?;
+        let tx_id = pset.extract_tx().map_err(|e| anyhow!("{e:?}"))?.txid();
+        let payment = sdk
+            .finalize_send_payment(&FinalizeSendPaymentRequest {
+                prepare_response: prepare_response.clone(),
+                signed_pset: utils::encode_pset(&pset),
+                sub_account_id: Some(sub_account_id.clone()),
+            })
+            .await?
+            .payment;
+        assert_eq!(payment.tx_id, Some(tx_id.to_string()));
+
+        let debited_sat = 1_000 + prepare_response.fees_sat.ok_or(anyhow!("Expected fees"))?;
+        let sub_account = sdk
+            .list_sub_accounts()
+            .await?
+            .into_iter()
+            .find(|sub_account| sub_account.id == sub_account_id)
+            .ok_or(anyhow!("Sub-account not found"))?;
+        assert_eq!(sub_account.balance_sat, 5_000 - debited_sat);
+        let debit = sdk
+            .list_ledger_entries(&ListLedgerEntriesRequest {
+                sub_account_id: Some(sub_account_id),
+                ..Default::default()
+            })
+            .await?
+            .pop()
+            .ok_or(anyhow!("Expected a debit"))?;
+        assert_eq!(debit.entry_type, LedgerEntryType::Send);
+        assert_eq!(debit.amount_sat, -(debited_sat as i64));
+        assert_eq!(debit.payment_id, payment.tx_id);
+
+        sdk.disconnect().await?;
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_combine_psets() -> Result<()> {
+        use crate::{
+            elements::pset::PartiallySignedTransaction, model::CombinePsetsRequest,
+            test_utils::wallet::TEST_LIQUID_TX,
+        };
+
+        create_persister!(persister);
+        let (sdk, _) = new_simulated_sdk(persister, |_| {}, |_| {}).await?;
+
+        // Each cosigner adds its own partial signature to the same PSET
+        let cosigner_pset = |pubkey: &str| -> Result<String> {
+            let mut pset = PartiallySignedTransaction::from_tx(TEST_LIQUID_TX.clone());
+            pset.inputs_mut()[0]
+                .partial_sigs
+                .insert(elements::bitcoin::PublicKey::from_str(pubkey)?, vec![1; 71]);
+            Ok(utils::encode_pset(&pset))
+        };
+        let first_pset =
+            cosigner_pset("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")?;
+        let second_pset =
+            cosigner_pset("02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5")?;
+
+        let combined_pset = utils::decode_pset(
+            &sdk.combine_psets(&CombinePsetsRequest {
+                psets: vec![first_pset.clone(), second_pset],
+            })?
+            .pset,
+        )?;
+        assert_eq!(combined_pset.inputs()[0].partial_sigs.len(), 2);
+
+        // The PSETs of different txs cannot be combined
+        let mut other_pset = PartiallySignedTransaction::from_tx(TEST_LIQUID_TX.clone());
+        other_pset.add_output(elements::pset::Output::from_txout(
+            TEST_LIQUID_TX.output[0].clone(),
+        ));
+        assert!(sdk
+            .combine_psets(&CombinePsetsRequest {
+                psets: vec![first_pset, utils::encode_pset(&other_pset)],
+            })
+            .is_err());
+
+        // There must be at least one PSET to combine
+        assert!(sdk
+            .combine_psets(&CombinePsetsRequest { psets: vec![] })
+            .is_err());
+
+        sdk.disconnect().await?;
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_confirmation_policy_payment_events() -> Result<()> {
+        use crate::model::{ConfirmationPolicy, SdkEvent};
+
+        fn count_payment_events(
+            events: &mut tokio::sync::broadcast::Receiver<SdkEvent>,
+            tx_id: &str,
+        ) -> usize {
+            let mut count = 0;
+            while let Ok(event) = events.try_recv() {
+                match event {
+                    SdkEvent::PaymentWaitingConfirmation { details }
+                    | SdkEvent::PaymentSucceeded { details }
+                        if details.tx_id.as_deref() == Some(tx_id) =>
+                    {
+                        count += 1
+                    }
+                    _ => {}
+                }
+            }
+            count
+        }
+
+        create_persister!(persister);
+        let (sdk, simulator) = new_simulated_sdk(
+            persister,
+            |config| {
+                config.confirmation_policy = Some(ConfirmationPolicy {
+                    liquid_confirmations: 3,
+                    ..Default::default()
+                })
+            },
+            |_| {},
+        )
+        .await?;
+        simulator.mine_block();
+        sdk.sync(false).await?;
+        let mut events = sdk.event_manager.subscribe();
+
+        // The payment is confirmed, but short of the required confirmations
+        let tx_id = simulator.receive_liquid_payment(&sdk, 10_000).await?;
+        let payment = sdk
+            .persister
+            .get_payment(&tx_id)?
+            .ok_or(anyhow!("Expected a Liquid payment"))?;
+        assert_eq!(payment.status, PaymentState::Pending);
+        assert_eq!(payment.blocks_remaining, Some(2));
+        assert_eq!(count_payment_events(&mut events, &tx_id), 1);
+
+        // Syncing again without a new block changes nothing, so nothing is emitted
+        sdk.sync(false).await?;
+        assert_eq!(count_payment_events(&mut events, &tx_id), 0);
+
+        // Each new block changes the remaining confirmations, until the payment completes
+        simulator.mine_block();
+        sdk.sync(false).await?;
+        assert_eq!(count_payment_events(&mut events, &tx_id), 1);
+        simulator.mine_block();
+        sdk.sync(false).await?;
+        assert_eq!(count_payment_events(&mut events, &tx_id), 1);
+        let payment = sdk
+            .persister
+            .get_payment(&tx_id)?
+            .ok_or(anyhow!("Expected a Liquid payment"))?;
+        assert_eq!(payment.status, PaymentState::Complete);
+        sdk.sync(false).await?;
+        assert_eq!(count_payment_events(&mut events, &tx_id), 0);
+
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_persister_fault_during_receive() -> Result<()> {
+        use crate::{
+            model::{
+                ListPaymentsRequest, PaymentMethod, PrepareReceiveRequest, ReceiveAmount,
+                ReceivePaymentRequest,
+            },
+            test_utils::faults::{Fault, FaultTarget},
+        };
+
+        create_persister!(persister);
+        let (sdk, simulator) = new_simulated_sdk(persister, |_| {}, |_| {}).await?;
+        sdk.persister.set_fault_injector(simulator.faults());
+        let prepare_response = sdk
+            .prepare_receive_payment(&PrepareReceiveRequest {
+                payment_method: PaymentMethod::Bolt11Invoice,
+                amount: Some(ReceiveAmount::Bitcoin {
+                    payer_amount_sat: 50_000,
+                }),
+                swap_provider: None,
+            })
+            .await?;
+        let invoice = sdk
+            .receive_payment(&ReceivePaymentRequest {
+                prepare_response,
+                description: None,
+                description_hash: None,
+                payer_note: None,
+                settle_as_asset: None,
+            })
+            .await?
+            .destination;
+
+        // Persisting the mempool lockup fails, so the swap is only claimed once the lockup
+        // is confirmed
+        simulator.faults().inject_times(
+            FaultTarget::Persister,
+            "try_handle_receive_swap_update",
+            Fault::Error("disk full".to_string()),
+            1,
+        );
+        simulator.pay_invoice(&sdk, &invoice).await?;
+
+        let payment = sdk
+            .list_payments(&ListPaymentsRequest::default())
+            .await?
+            .into_iter()
+            .find(|payment| payment.payment_type == PaymentType::Receive)
+            .ok_or(anyhow!("Expected a receive payment"))?;
+        assert_eq!(payment.status, PaymentState::Complete);
+
+        sdk.disconnect().await?;
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_sub_account_ledger() -> Result<()> {
+        use crate::{
+            error::PaymentError,
+            model::{
+                AttributeToSubAccountRequest, CreateSubAccountRequest, LedgerEntry,
+                LedgerEntryType, ListLedgerEntriesRequest, ListPaymentsRequest, PayAmount,
+                PaymentMethod, PrepareReceiveRequest, PrepareSendRequest, ReceiveAmount,
+                ReceivePaymentRequest, SendPaymentRequest, TransferSubAccountFundsRequest,
+            },
+            test_utils::faults::{Fault, FaultTarget},
+        };
+
+        async fn balance_sat(sdk: &LiquidSdk, sub_account_id: &str) -> Result<u64> {
+            Ok(sdk
+                .list_sub_accounts()
+                .await?
+                .into_iter()
+                .find(|sub_account| sub_account.id == sub_account_id)
+                .ok_or(anyhow!("Sub-account not found"))?
+                .balance_sat)
+        }
+
+        async fn list_entries(sdk: &LiquidSdk, sub_account_id: &str) -> Result<Vec<LedgerEntry>> {
+            Ok(sdk
+                .list_ledger_entries(&ListLedgerEntriesRequest {
+                    sub_account_id: Some(sub_account_id.to_string()),
+                    ..Default::default()
+                })
+                .await?)
+        }
+
+        create_persister!(persister);
+        let (_, address) = test_asset_metadata()?;
+        let (sdk, simulator) = new_simulated_sdk(persister, |_| {}, |_| {}).await?;
+        let sub_account_id = sdk
+            .create_sub_account(&CreateSubAccountRequest {
+                name: "Alice".to_string(),
+            })
+            .await?
+            .id;
+
+        // An incoming payment to an attributed destination is credited once complete
+        let prepare_response = sdk
+            .prepare_receive_payment(&PrepareReceiveRequest {
+                payment_method: PaymentMethod::Bolt11Invoice,
+                amount: Some(ReceiveAmount::Bitcoin {
+                    payer_amount_sat: 50_000,
+                }),
+                swap_provider: None,
+            })
+            .await?;
+        let invoice = sdk
+            .receive_payment(&ReceivePaymentRequest {
+                prepare_response,
+                description: None,
+                description_hash: None,
+                payer_note: None,
+                settle_as_asset: None,
+            })
+            .await?
+            .destination;
+        sdk.attribute_to_sub_account(&AttributeToSubAccountRequest {
+            sub_account_id: sub_account_id.clone(),
+            destination: invoice.clone(),
+        })
+        .await?;
+        assert_eq!(balance_sat(&sdk, &sub_account_id).await?, 0);
+
+        simulator.pay_invoice(&sdk, &invoice).await?;
+        let received_sat = sdk
+            .list_payments(&ListPaymentsRequest::default())
+            .await?
+            .into_iter()
+            .find(|payment| payment.payment_type == PaymentType::Receive)
+            .ok_or(anyhow!("Expected a receive payment"))?
+            .amount_sat;
+        assert_eq!(balance_sat(&sdk, &sub_account_id).await?, received_sat);
+
+        let prepare_response = sdk
+            .prepare_send_payment(&PrepareSendRequest {
+                destination: address,
+                amount: Some(PayAmount::Bitcoin {
+                    receiver_amount_sat: 1_000,
+                }),
+                disable_mrh: None,
+                payment_timeout_sec: None,
+                swap_provider: None,
+                from_asset: None,
+                fee_asset: None,
+                export_pset: None,
+            })
+            .await?;
+        let debited_sat = 1_000 + prepare_response.fees_sat.ok_or(anyhow!("Expected fees"))?;
+        let send_request = SendPaymentRequest {
+            prepare_response,
+            use_asset_fees: None,
+            payer_note: None,
+            sub_account_id: Some(sub_account_id.clone()),
+        };
+        // The debit of a send that fails before spending any funds is reversed
+        simulator.faults().inject_times(
+            FaultTarget::ChainService,
+            "broadcast",
+            Fault::Error("Broadcast failed".to_string()),
+            1,
+        );
+        assert!(sdk.send_payment(&send_request).await.is_err());
+        assert_eq!(balance_sat(&sdk, &sub_account_id).await?, received_sat);
+        let entries = list_entries(&sdk, &sub_account_id).await?;
+        let [debit, reversal] = &entries[entries.len() - 2..] else {
+            return Err(anyhow!("Expected a debit and its reversal"));
+        };
+        assert_eq!(debit.entry_type, LedgerEntryType::Send);
+        assert_eq!(debit.amount_sat, -(debited_sat as i64));
+        assert_eq!(reversal.entry_type, LedgerEntryType::Refund);
+        assert_eq!(reversal.amount_sat, debited_sat as i64);
+        assert_eq!(reversal.payment_id, debit.payment_id);
+
+        // A sent payment is debited with its fees, keyed by its tx id
+        let payment = sdk.send_payment(&send_request).await?.payment;
+        assert_eq!(
+            balance_sat(&sdk, &sub_account_id).await?,
+            received_sat - debited_sat
+        );
+        let debit = list_entries(&sdk, &sub_account_id)
+            .await?
+            .pop()
+            .ok_or(anyhow!("Expected a debit"))?;
+        assert_eq!(debit.entry_type, LedgerEntryType::Send);
+        assert_eq!(debit.payment_id, payment.tx_id);
+
+        // A send exceeding the sub-account balance is rejected
+        sdk.transfer_sub_account_funds(&TransferSubAccountFundsRequest {
+            from_sub_account_id: Some(sub_account_id.clone()),
+            to_sub_account_id: None,
+            amount_sat: received_sat - debited_sat - 100,
+            description: None,
+        })
+        .await?;
+        let entry_count = list_entries(&sdk, &sub_account_id).await?.len();
+        assert!(matches!(
+            sdk.send_payment(&send_request).await,
+            Err(PaymentError::InsufficientFunds)
+        ));
+        assert_eq!(
+            list_entries(&sdk, &sub_account_id).await?.len(),
+            entry_count
+        );
+        assert_eq!(balance_sat(&sdk, &sub_account_id).await?, 100);
+
+        sdk.disconnect().await?;
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_unattributed_send_keeps_allocated_funds() -> Result<()> {
+        use crate::{
+            error::PaymentError,
+            model::{
+                CreateSubAccountRequest, PayAmount, PrepareSendRequest, SendPaymentRequest,
+                TransferSubAccountFundsRequest,
+            },
+        };
+
+        async fn transfer(
+            sdk: &LiquidSdk,
+            from_sub_account_id: Option<String>,
+            to_sub_account_id: Option<String>,
+            amount_sat: u64,
+        ) -> Result<()> {
+            sdk.transfer_sub_account_funds(&TransferSubAccountFundsRequest {
+                from_sub_account_id,
+                to_sub_account_id,
+                amount_sat,
+                description: None,
+            })
+            .await?;
+            Ok(())
+        }
+
+        create_persister!(persister);
+        let (_, address) = test_asset_metadata()?;
+        let (sdk, simulator) = new_simulated_sdk(persister, |_| {}, |_| {}).await?;
+        simulator.fund_wallet(&sdk, 10_000).await?;
+        let sub_account_id = sdk
+            .create_sub_account(&CreateSubAccountRequest {
+                name: "Alice".to_string(),
+            })
+            .await?
+            .id;
+        transfer(&sdk, None, Some(sub_account_id.clone()), 9_000).await?;
+
+        // The unallocated balance cannot be transferred twice
+        assert!(transfer(&sdk, None, Some(sub_account_id.clone()), 2_000)
+            .await
+            .is_err());
+
+        let prepare_response = sdk
+            .prepare_send_payment(&PrepareSendRequest {
+                destination: address,
+                amount: Some(PayAmount::Bitcoin {
+                    receiver_amount_sat: 1_000,
+                }),
+                disable_mrh: None,
+                payment_timeout_sec: None,
+                swap_provider: None,
+                from_asset: None,
+                fee_asset: None,
+                export_pset: None,
+            })
+            .await?;
+        let send_request = SendPaymentRequest {
+            prepare_response,
+            use_asset_fees: None,
+            payer_note: None,
+            sub_account_id: None,
+        };
+
+        // A send that is not debited from a sub-account cannot spend the allocated funds
+        assert!(matches!(
+            sdk.send_payment(&send_request).await,
+            Err(PaymentError::InsufficientFunds)
+        ));
+        assert_eq!(sdk.get_ledger_info().await?.allocated_sat, 9_000);
+
+        // It can once enough funds are unallocated
+        transfer(&sdk, Some(sub_account_id.clone()), None, 5_000).await?;
+        sdk.send_payment(&send_request).await?;
+        assert_eq!(sdk.get_ledger_info().await?.allocated_sat, 4_000);
+
+        sdk.disconnect().await?;
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_sub_account_refund_reconciliation() -> Result<()> {
+        use crate::{
+            model::{
+                CreateSubAccountRequest, LedgerEntryType, ListLedgerEntriesRequest,
+                TransferSubAccountFundsRequest,
+            },
+            persist::ledger::LedgerChange,
+        };
+
+        create_persister!(persister);
+        let (sdk, simulator) = new_simulated_sdk(persister.clone(), |_| {}, |_| {}).await?;
+        simulator.fund_wallet(&sdk, 10_000).await?;
+        let sub_account_id = sdk
+            .create_sub_account(&CreateSubAccountRequest {
+                name: "Alice".to_string(),
+            })
+            .await?
+            .id;
+        sdk.transfer_sub_account_funds(&TransferSubAccountFundsRequest {
+            from_sub_account_id: None,
+            to_sub_account_id: Some(sub_account_id.clone()),
+            amount_sat: 10_000,
+            description: None,
+        })
+        .await?;
+
+        // Records a send swap debited from the sub-account, whose funds were locked up
+        let debit_send_swap = |state: PaymentState, refund_amount_sat: Option<u64>| {
+            let mut swap = new_send_swap(Some(state), None);
+            let payment_hash =
+                lwk_wollet::hashes::sha256::Hash::hash(swap.id.as_bytes()).to_string();
+            swap.invoice =
+                MockSwapper::mock_invoice(&payment_hash, swap.receiver_amount_sat, String::new())?;
+            swap.payment_hash = Some(payment_hash);
+            let (lockup_tx, mut lockup_balance) =
+                new_payment_tx_data(LiquidNetwork::Regtest, PaymentType::Send);
+            lockup_balance.amount = swap.payer_amount_sat;
+            swap.lockup_tx_id = Some(lockup_tx.tx_id.clone());
+            persister.insert_or_update_payment(lockup_tx, &[lockup_balance], None, false)?;
+            if let Some(refund_amount_sat) = refund_amount_sat {
+                let (refund_tx, mut refund_balance) =
+                    new_payment_tx_data(LiquidNetwork::Regtest, PaymentType::Receive);
+                refund_balance.amount = refund_amount_sat;
+                swap.refund_tx_id = Some(refund_tx.tx_id.clone());
+                persister.insert_or_update_payment(refund_tx, &[refund_balance], None, false)?;
+            }
+            persister.insert_or_update_send_swap(&swap)?;
+            persister.apply_ledger_changes(&[LedgerChange {
+                sub_account_id: sub_account_id.clone(),
+                entry_type: LedgerEntryType::Send,
+                amount_sat: -(swap.payer_amount_sat as i64),
+                payment_id: Some(swap.id.clone()),
+                transfer_id: None,
+                description: None,
+            }])?;
+            anyhow::Ok((swap.id, swap.payer_amount_sat))
+        };
+        let (refunded_id, debited_sat) = debit_send_swap(PaymentState::Failed, Some(1_000))?;
+        let (refund_pending_id, _) = debit_send_swap(PaymentState::RefundPending, Some(900))?;
+        // Without a refund tx, the locked up funds are not credited back
+        debit_send_swap(PaymentState::TimedOut, None)?;
+        debit_send_swap(PaymentState::RefundPending, None)?;
+
+        sdk.reconcile_ledger().await?;
+        // A refund does not return the fees of the swap txs
+        assert_eq!(
+            sdk.list_sub_accounts().await?[0].balance_sat,
+            10_000 - 4 * debited_sat + 1_000 + 900
+        );
+        let refunds: Vec<(Option<String>, i64)> = sdk
+            .list_ledger_entries(&ListLedgerEntriesRequest {
+                sub_account_id: Some(sub_account_id.clone()),
+                ..Default::default()
+            })
+            .await?
+            .into_iter()
+            .filter(|entry| entry.entry_type == LedgerEntryType::Refund)
+            .map(|entry| (entry.payment_id, entry.amount_sat))
+            .collect();
+        assert_eq!(
+            refunds,
+            vec![(Some(refunded_id), 1_000), (Some(refund_pending_id), 900)]
+        );
+
+        // The refunds are only credited once
+        sdk.reconcile_ledger().await?;
+        assert_eq!(
+            sdk.list_sub_accounts().await?[0].balance_sat,
+            10_000 - 4 * debited_sat + 1_900
+        );
+
+        sdk.disconnect().await?;
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_discover_accounts() -> Result<()> {
+        use super::{discover_accounts, ACCOUNT_DISCOVERY_GAP_LIMIT};
+        use crate::error::PaymentError;
+
+        // Accounts 0 and 2 have history, separated by an unused account
+        let last_account = 2 + ACCOUNT_DISCOVERY_GAP_LIMIT;
+        let accounts = discover_accounts(|account_index| async move {
+            match account_index {
+                0 => Ok(3),
+                2 => Ok(1),
+                // Found only when the gap limit is not honored
+                account_index if account_index == last_account => Ok(1),
+                account_index if account_index > last_account => {
+                    Err(PaymentError::generic("Scanned past the gap limit"))
+                }
+                _ => Ok(0),
+            }
+        })
+        .await?;
+        let found: Vec<(u32, u32)> = accounts
+            .iter()
+            .map(|account| (account.account_index, account.tx_count))
+            .collect();
+        assert_eq!(found, vec![(0, 3), (2, 1)]);
+        Ok(())
+    }
 }
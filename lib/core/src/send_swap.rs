@@ -3,8 +3,6 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{anyhow, bail, Result};
-use boltz_client::boltz::SubmarineClaimTxResponse;
-use boltz_client::swaps::boltz;
 use boltz_client::swaps::{boltz::CreateSubmarineResponse, boltz::SubSwapStates};
 use futures_util::TryFutureExt;
 use log::{debug, error, info, warn};
@@ -12,7 +10,7 @@ use lwk_wollet::elements::{LockTime, Transaction};
 use lwk_wollet::hashes::{sha256, Hash};
 use sdk_common::prelude::{AesSuccessActionDataResult, SuccessAction, SuccessActionProcessed};
 use tokio::sync::broadcast;
-use web_time::{SystemTime, UNIX_EPOCH};
+use web_time::UNIX_EPOCH;
 
 use crate::chain::liquid::LiquidChainService;
 use crate::model::{
@@ -21,7 +19,10 @@ use crate::model::{
 use crate::persist::model::{PaymentTxBalance, PaymentTxDetails};
 use crate::prelude::{PaymentTxData, PaymentType, Swap};
 use crate::recover::recoverer::Recoverer;
-use crate::swapper::Swapper;
+use crate::swapper::{
+    model::{SubmarineClaimTxResponse, SwapStatus},
+    Swapper,
+};
 use crate::utils;
 use crate::wallet::OnchainWallet;
 use crate::{
@@ -78,7 +79,7 @@ impl SendSwapHandler {
     }
 
     /// Handles status updates from Boltz for Send swaps
-    pub(crate) async fn on_new_status(&self, update: &boltz::SwapStatus) -> Result<()> {
+    pub(crate) async fn on_new_status(&self, update: &SwapStatus) -> Result<()> {
         let id = &update.id;
         let status = &update.status;
         let swap_state = SubSwapStates::from_str(status)
@@ -238,7 +239,6 @@ impl SendSwapHandler {
             return Err(PaymentError::PaymentInProgress);
         }
 
-        let swap_id = &swap.id;
         debug!(
             "Initiated Send Swap: send {} sats to liquid address {}",
             create_response.expected_amount, create_response.address
@@ -253,6 +253,18 @@ impl SendSwapHandler {
                 create_response.expected_amount,
             )
             .await?;
+        self.broadcast_lockup_tx(swap, create_response, lockup_tx)
+            .await
+    }
+
+    /// Broadcasts an already built lockup tx for the Send Swap and marks the swap as pending
+    pub(crate) async fn broadcast_lockup_tx(
+        &self,
+        swap: &SendSwap,
+        create_response: &CreateSubmarineResponse,
+        lockup_tx: Transaction,
+    ) -> Result<Transaction, PaymentError> {
+        let swap_id = &swap.id;
         let lockup_tx_id = lockup_tx.txid().to_string();
 
         self.persister
@@ -281,6 +293,7 @@ impl SendSwapHandler {
                 fees_sat: lockup_tx_fees_sat,
                 is_confirmed: false,
                 unblinding_data: None,
+                blocks_remaining: None,
             },
             &[PaymentTxBalance {
                 asset_id: self.config.lbtc_asset_id(),
@@ -503,7 +516,8 @@ impl SendSwapHandler {
 
     async fn check_swap_expiry(&self, swap: &SendSwap) -> Result<bool> {
         let swap_creation_time = UNIX_EPOCH + Duration::from_secs(swap.created_at as u64);
-        let duration_since_creation_time = SystemTime::now().duration_since(swap_creation_time)?;
+        let duration_since_creation_time =
+            utils::system_time().duration_since(swap_creation_time)?;
         if duration_since_creation_time.as_secs() < 60 * 10 {
             return Ok(false);
         }
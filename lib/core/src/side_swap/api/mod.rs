@@ -77,9 +77,16 @@ impl SideSwapService {
         };
 
         let side_swap_service_future = async move {
+            let sideswap_url = match config.sideswap_ws_url() {
+                Ok(url) => url,
+                Err(err) => {
+                    error!("Could not start SideSwap service: {err}");
+                    return;
+                }
+            };
             info!("Starting SideSwap service event loop");
             loop {
-                let ws = match connect(config.sideswap_url()).await {
+                let ws = match connect(&sideswap_url).await {
                     Ok(ws) => ws,
                     Err(err) => {
                         error!("Could not connect to SideSwap websocket: {err}");
@@ -220,14 +227,36 @@ impl SideSwapService {
         from_asset: AssetId,
         to_asset: AssetId,
         receiver_amount_sat: u64,
+    ) -> Result<AssetSwap> {
+        self.quote_asset_swap(from_asset, to_asset, None, Some(receiver_amount_sat))
+            .await
+    }
+
+    /// Quotes a swap spending a fixed amount of the `from_asset`
+    pub(crate) async fn get_asset_swap_for_payer_amount(
+        &self,
+        from_asset: AssetId,
+        to_asset: AssetId,
+        payer_amount_sat: u64,
+    ) -> Result<AssetSwap> {
+        self.quote_asset_swap(from_asset, to_asset, Some(payer_amount_sat), None)
+            .await
+    }
+
+    async fn quote_asset_swap(
+        &self,
+        from_asset: AssetId,
+        to_asset: AssetId,
+        payer_amount_sat: Option<u64>,
+        receiver_amount_sat: Option<u64>,
     ) -> Result<AssetSwap> {
         let payload_details =
             AssetPayloadDetails::try_from_assets(self.config.network, &from_asset, &to_asset)?;
         let req = Request::SubscribePriceStream(SubscribePriceStreamRequest {
             subscribe_id: None,
             asset: payload_details.asset,
-            send_amount: None,
-            recv_amount: Some(receiver_amount_sat as i64),
+            send_amount: payer_amount_sat.map(|amount| amount as i64),
+            recv_amount: receiver_amount_sat.map(|amount| amount as i64),
             send_bitcoins: payload_details.send_bitcoins,
         });
         let request_id = self.request_handler.send(req).await?;
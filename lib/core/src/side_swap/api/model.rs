@@ -144,6 +144,24 @@ impl AssetSwap {
 
         Ok(())
     }
+
+    /// Checks the balance of the asset we are trading from, for swaps to L-BTC whose
+    /// fees are deducted from the L-BTC received
+    pub(crate) fn check_sufficient_asset_balance(
+        &self,
+        wallet_info: &WalletInfo,
+    ) -> Result<(), PaymentError> {
+        let asset_balance_sat = wallet_info
+            .asset_balances
+            .iter()
+            .find(|b| b.asset_id.eq(&self.from_asset.to_string()))
+            .map(|b| b.balance_sat)
+            .unwrap_or_default();
+        if asset_balance_sat < self.payer_amount_sat {
+            return Err(PaymentError::InsufficientFunds);
+        }
+        Ok(())
+    }
 }
 
 pub(crate) struct AssetPayloadDetails {
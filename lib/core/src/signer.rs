@@ -3,10 +3,10 @@ use std::sync::Arc;
 use anyhow::anyhow;
 use bip39::Mnemonic;
 use boltz_client::PublicKey;
-use lwk_common::Signer as LwkSigner;
 use lwk_wollet::bitcoin::bip32::Xpriv;
 use lwk_wollet::bitcoin::Network;
-use lwk_wollet::elements_miniscript::{self, ToPublicKey as _};
+use lwk_wollet::elements_miniscript::confidential::{Descriptor as ConfidentialDescriptor, Key};
+use lwk_wollet::elements_miniscript::{self, DescriptorPublicKey, ForEachKey, ToPublicKey as _};
 use lwk_wollet::elements_miniscript::{
     bitcoin::{self, bip32::DerivationPath},
     elements::{
@@ -22,9 +22,10 @@ use lwk_wollet::elements_miniscript::{
 };
 use lwk_wollet::hashes::{sha256, HashEngine, Hmac, HmacEngine};
 use lwk_wollet::secp256k1::ecdsa::Signature;
-use lwk_wollet::secp256k1::Message;
+use lwk_wollet::secp256k1::{Message, SecretKey};
+use lwk_wollet::WolletDescriptor;
 
-use crate::model::{Signer, SignerError};
+use crate::model::{AsyncSigner, Signer, SignerError};
 
 #[derive(thiserror::Error, Debug)]
 pub enum SignError {
@@ -61,25 +62,185 @@ pub enum NewError {
 
     #[error(transparent)]
     Seed(#[from] anyhow::Error),
+
+    #[error("Invalid descriptor: {0}")]
+    Descriptor(String),
+
+    #[error(transparent)]
+    Signer(#[from] SignerError),
+}
+
+/// Adapts a synchronous [Signer] to the [AsyncSigner] trait, so that existing signers keep
+/// working. Every call is answered inline.
+pub struct SyncSignerAdapter {
+    signer: Arc<Box<dyn Signer>>,
+}
+
+impl SyncSignerAdapter {
+    pub fn new(signer: Arc<Box<dyn Signer>>) -> Self {
+        Self { signer }
+    }
 }
 
-/// A software signer
+#[sdk_macros::async_trait]
+impl AsyncSigner for SyncSignerAdapter {
+    async fn xpub(&self) -> Result<Vec<u8>, SignerError> {
+        self.signer.xpub()
+    }
+
+    async fn derive_xpub(&self, derivation_path: String) -> Result<Vec<u8>, SignerError> {
+        self.signer.derive_xpub(derivation_path)
+    }
+
+    async fn sign_ecdsa(
+        &self,
+        msg: Vec<u8>,
+        derivation_path: String,
+    ) -> Result<Vec<u8>, SignerError> {
+        self.signer.sign_ecdsa(msg, derivation_path)
+    }
+
+    async fn sign_ecdsa_recoverable(&self, msg: Vec<u8>) -> Result<Vec<u8>, SignerError> {
+        self.signer.sign_ecdsa_recoverable(msg)
+    }
+
+    async fn slip77_master_blinding_key(&self) -> Result<Vec<u8>, SignerError> {
+        self.signer.slip77_master_blinding_key()
+    }
+
+    async fn hmac_sha256(
+        &self,
+        msg: Vec<u8>,
+        derivation_path: String,
+    ) -> Result<Vec<u8>, SignerError> {
+        self.signer.hmac_sha256(msg, derivation_path)
+    }
+
+    async fn ecies_encrypt(&self, msg: Vec<u8>) -> Result<Vec<u8>, SignerError> {
+        self.signer.ecies_encrypt(msg)
+    }
+
+    async fn ecies_decrypt(&self, msg: Vec<u8>) -> Result<Vec<u8>, SignerError> {
+        self.signer.ecies_decrypt(msg)
+    }
+}
+
+/// The message from which the account keys of the real-time sync are derived
+const ACCOUNT_SYNC_KEY_TAG: &[u8] = b"breez-sdk-liquid-sync-account";
+
+/// Scopes the real-time sync to a wallet account, so that each account of a seed syncs in its
+/// own namespace. Sync requests are signed and records are encrypted with a key derived from
+/// the account index instead of the master key.
+pub(crate) struct AccountScopedSigner {
+    signer: Arc<dyn AsyncSigner>,
+    account_index: u32,
+}
+
+impl AccountScopedSigner {
+    pub(crate) fn new(signer: Arc<dyn AsyncSigner>, account_index: u32) -> Self {
+        Self {
+            signer,
+            account_index,
+        }
+    }
+
+    async fn account_key(&self) -> Result<SecretKey, SignerError> {
+        let key_bytes = self
+            .signer
+            .hmac_sha256(
+                ACCOUNT_SYNC_KEY_TAG.to_vec(),
+                format!("m/{}'", self.account_index),
+            )
+            .await?;
+        SecretKey::from_slice(&key_bytes).map_err(|e| SignerError::Generic {
+            err: format!("Invalid account key: {e}"),
+        })
+    }
+}
+
+#[sdk_macros::async_trait]
+impl AsyncSigner for AccountScopedSigner {
+    async fn xpub(&self) -> Result<Vec<u8>, SignerError> {
+        self.signer.xpub().await
+    }
+
+    async fn derive_xpub(&self, derivation_path: String) -> Result<Vec<u8>, SignerError> {
+        self.signer.derive_xpub(derivation_path).await
+    }
+
+    async fn sign_ecdsa(
+        &self,
+        msg: Vec<u8>,
+        derivation_path: String,
+    ) -> Result<Vec<u8>, SignerError> {
+        self.signer.sign_ecdsa(msg, derivation_path).await
+    }
+
+    async fn sign_ecdsa_recoverable(&self, msg: Vec<u8>) -> Result<Vec<u8>, SignerError> {
+        let secret_key = self.account_key().await?;
+        let msg: Message = Message::from_digest_slice(msg.as_slice())
+            .map_err(|e| SignerError::Generic { err: e.to_string() })?;
+        let recoverable_sig = Secp256k1::new().sign_ecdsa_recoverable(&msg, &secret_key);
+        let (recovery_id, sig) = recoverable_sig.serialize_compact();
+        let mut complete_signature = vec![31 + recovery_id.to_i32() as u8];
+        complete_signature.extend_from_slice(&sig);
+        Ok(complete_signature)
+    }
+
+    async fn slip77_master_blinding_key(&self) -> Result<Vec<u8>, SignerError> {
+        self.signer.slip77_master_blinding_key().await
+    }
+
+    async fn hmac_sha256(
+        &self,
+        msg: Vec<u8>,
+        derivation_path: String,
+    ) -> Result<Vec<u8>, SignerError> {
+        self.signer.hmac_sha256(msg, derivation_path).await
+    }
+
+    async fn ecies_encrypt(&self, msg: Vec<u8>) -> Result<Vec<u8>, SignerError> {
+        let public_key = self.account_key().await?.public_key(&Secp256k1::new());
+        ecies::encrypt(&public_key.serialize(), &msg).map_err(|err| SignerError::Generic {
+            err: format!("Could not encrypt data: {err}"),
+        })
+    }
+
+    async fn ecies_decrypt(&self, msg: Vec<u8>) -> Result<Vec<u8>, SignerError> {
+        let secret_key = self.account_key().await?;
+        ecies::decrypt(&secret_key.secret_bytes(), &msg).map_err(|err| SignerError::Generic {
+            err: format!("Could not decrypt data: {err}"),
+        })
+    }
+}
+
+/// Signs the wallet txs with the SDK [AsyncSigner].
+///
+/// The master xpub and the SLIP77 master blinding key are fetched once when created, so that
+/// they are available without waiting on the signer.
 pub struct SdkLwkSigner {
-    sdk_signer: Arc<Box<dyn Signer>>,
+    sdk_signer: Arc<dyn AsyncSigner>,
+    xpub: Xpub,
+    slip77_master_blinding_key: MasterBlindingKey,
 }
 
 impl SdkLwkSigner {
-    /// Creates a new software signer from the given mnemonic.
-    ///
-    /// Takes also a flag if the network is mainnet so that generated extended keys are in the
-    /// correct form xpub/tpub (there is no need to discriminate between regtest and testnet)
-    pub fn new(sdk_signer: Arc<Box<dyn Signer>>) -> Result<Self, NewError> {
-        Ok(Self { sdk_signer })
+    pub async fn new(sdk_signer: Arc<dyn AsyncSigner>) -> Result<Self, NewError> {
+        let xpub = Xpub::decode(&sdk_signer.xpub().await?)?;
+        let slip77_master_blinding_key: [u8; 32] = sdk_signer
+            .slip77_master_blinding_key()
+            .await?
+            .try_into()
+            .map_err(|_| anyhow!("Wrong slip77 master blinding key length"))?;
+        Ok(Self {
+            sdk_signer,
+            xpub,
+            slip77_master_blinding_key: slip77_master_blinding_key.into(),
+        })
     }
 
     pub(crate) fn xpub(&self) -> Result<Xpub, SignError> {
-        let xpub = self.sdk_signer.xpub()?;
-        Ok(Xpub::decode(&xpub)?)
+        Ok(self.xpub)
     }
 
     pub fn fingerprint(&self) -> Result<Fingerprint, SignError> {
@@ -89,18 +250,37 @@ impl SdkLwkSigner {
         Ok(f)
     }
 
-    pub(crate) fn sign_ecdsa_recoverable(&self, msg: &Message) -> Result<Vec<u8>, SignError> {
+    pub fn slip77_master_blinding_key(&self) -> Result<MasterBlindingKey, SignError> {
+        Ok(self.slip77_master_blinding_key)
+    }
+
+    /// Whether the keys are for mainnet, as opposed to testnet or regtest
+    pub fn is_mainnet(&self) -> bool {
+        // The version bytes of a mainnet xpub
+        self.xpub.encode()[0..4] == [0x04, 0x88, 0xB2, 0x1E]
+    }
+
+    pub async fn derive_xpub(&self, path: &DerivationPath) -> Result<Xpub, SignError> {
+        let pubkey_bytes = if path.is_empty() {
+            self.sdk_signer.xpub().await?
+        } else {
+            self.sdk_signer.derive_xpub(path.to_string()).await?
+        };
+        let xpub = Xpub::decode(pubkey_bytes.as_slice())?;
+        Ok(xpub)
+    }
+
+    pub(crate) async fn sign_ecdsa_recoverable(&self, msg: &Message) -> Result<Vec<u8>, SignError> {
         let sig_bytes = self
             .sdk_signer
-            .sign_ecdsa_recoverable(msg.as_ref().to_vec())?;
+            .sign_ecdsa_recoverable(msg.as_ref().to_vec())
+            .await?;
         Ok(sig_bytes)
     }
-}
-
-impl LwkSigner for SdkLwkSigner {
-    type Error = SignError;
 
-    fn sign(&self, pset: &mut PartiallySignedTransaction) -> Result<u32, Self::Error> {
+    /// Signs the PSET inputs derived from the signer keys, returning the number of added
+    /// signatures
+    pub async fn sign(&self, pset: &mut PartiallySignedTransaction) -> Result<u32, SignError> {
         let tx = pset.extract_tx()?;
         let mut sighash_cache = SighashCache::new(&tx);
         let mut signature_added = 0;
@@ -122,52 +302,34 @@ impl LwkSigner for SdkLwkSigner {
 
         let signer_fingerprint = self.fingerprint()?;
         for (input, msg) in pset.inputs_mut().iter_mut().zip(messages) {
+            let mut signatures = vec![];
             for (want_public_key, (fingerprint, derivation_path)) in input.bip32_derivation.iter() {
                 if &signer_fingerprint == fingerprint {
-                    let xpub = self.derive_xpub(derivation_path)?;
+                    let xpub = self.derive_xpub(derivation_path).await?;
                     let public_key: PublicKey = xpub.public_key.into();
                     if want_public_key == &public_key {
                         // fixme: for taproot use schnorr
                         let sig_bytes = self
                             .sdk_signer
-                            .sign_ecdsa(msg.as_ref().to_vec(), derivation_path.to_string())?;
+                            .sign_ecdsa(msg.as_ref().to_vec(), derivation_path.to_string())
+                            .await?;
                         let sig = Signature::from_der(&sig_bytes).map_err(|_| {
                             SignError::Generic(anyhow::anyhow!("Invalid esda signature"))
                         })?;
-                        let sig = elementssig_to_rawsig(&(sig, hash_ty));
-
-                        let inserted = input.partial_sigs.insert(public_key, sig);
-                        if inserted.is_none() {
-                            signature_added += 1;
-                        }
+                        signatures.push((public_key, elementssig_to_rawsig(&(sig, hash_ty))));
                     }
                 }
             }
+            for (public_key, sig) in signatures {
+                let inserted = input.partial_sigs.insert(public_key, sig);
+                if inserted.is_none() {
+                    signature_added += 1;
+                }
+            }
         }
 
         Ok(signature_added)
     }
-
-    fn slip77_master_blinding_key(&self) -> Result<MasterBlindingKey, Self::Error> {
-        let bytes: [u8; 32] = self
-            .sdk_signer
-            .slip77_master_blinding_key()?
-            .try_into()
-            .map_err(|_| {
-                SignError::Generic(anyhow::anyhow!("Wrong slip77 master blinding key length"))
-            })?;
-        Ok(bytes.into())
-    }
-
-    fn derive_xpub(&self, path: &DerivationPath) -> Result<Xpub, Self::Error> {
-        let pubkey_bytes = if path.is_empty() {
-            self.sdk_signer.xpub()?
-        } else {
-            self.sdk_signer.derive_xpub(path.to_string())?
-        };
-        let xpub = Xpub::decode(pubkey_bytes.as_slice())?;
-        Ok(xpub)
-    }
 }
 
 pub struct SdkSigner {
@@ -281,6 +443,118 @@ impl Signer for SdkSigner {
     }
 }
 
+/// Returns the distinct xpubs of a CT descriptor, in order, and its SLIP77 master blinding key
+pub(crate) fn descriptor_keys(
+    descriptor: &WolletDescriptor,
+) -> Result<(Vec<Xpub>, MasterBlindingKey), NewError> {
+    let ct_descriptor: &ConfidentialDescriptor<DescriptorPublicKey> = descriptor.as_ref();
+    let Key::Slip77(slip77_master_blinding_key) = &ct_descriptor.key else {
+        return Err(NewError::Descriptor(
+            "only SLIP77 blinding keys are supported".to_string(),
+        ));
+    };
+
+    let mut xpubs: Vec<Xpub> = vec![];
+    let all_xpubs = ct_descriptor.descriptor.for_each_key(|key| {
+        let xpub = match key {
+            DescriptorPublicKey::XPub(xkey) => xkey.xkey,
+            DescriptorPublicKey::MultiXPub(xkey) => xkey.xkey,
+            DescriptorPublicKey::Single(_) => return false,
+        };
+        if !xpubs.contains(&xpub) {
+            xpubs.push(xpub);
+        }
+        true
+    });
+    if !all_xpubs {
+        return Err(NewError::Descriptor(
+            "only xpub descriptor keys are supported".to_string(),
+        ));
+    }
+    Ok((xpubs, *slip77_master_blinding_key))
+}
+
+/// A signer for a watch-only wallet, built from its CT descriptor.
+///
+/// It only exposes the account xpub and the SLIP77 master blinding key of the descriptor,
+/// which is enough to derive addresses and unblind the wallet transactions. As it holds no
+/// private keys, every signing operation fails.
+pub struct WatchOnlySigner {
+    xpub: Xpub,
+    slip77_master_blinding_key: MasterBlindingKey,
+}
+
+impl WatchOnlySigner {
+    pub fn new(descriptor: &WolletDescriptor) -> Result<Self, NewError> {
+        let (xpubs, slip77_master_blinding_key) = descriptor_keys(descriptor)?;
+        let [xpub] = xpubs[..] else {
+            return Err(NewError::Descriptor(
+                "only single-key xpub descriptors are supported".to_string(),
+            ));
+        };
+
+        Ok(Self {
+            xpub,
+            slip77_master_blinding_key,
+        })
+    }
+
+    /// Creates a signer for a multisig descriptor, identified by its first cosigner xpub
+    pub fn new_multisig(descriptor: &WolletDescriptor) -> Result<Self, NewError> {
+        let (xpubs, slip77_master_blinding_key) = descriptor_keys(descriptor)?;
+        let [xpub, _, ..] = xpubs[..] else {
+            return Err(NewError::Descriptor(
+                "multisig descriptors need at least two cosigner xpubs".to_string(),
+            ));
+        };
+
+        Ok(Self {
+            xpub,
+            slip77_master_blinding_key,
+        })
+    }
+
+    fn watch_only_error() -> SignerError {
+        SignerError::Generic {
+            err: "Cannot sign with a watch-only wallet".to_string(),
+        }
+    }
+}
+
+impl Signer for WatchOnlySigner {
+    fn xpub(&self) -> Result<Vec<u8>, SignerError> {
+        Ok(self.xpub.encode().to_vec())
+    }
+
+    fn derive_xpub(&self, _derivation_path: String) -> Result<Vec<u8>, SignerError> {
+        Err(Self::watch_only_error())
+    }
+
+    fn sign_ecdsa(&self, _msg: Vec<u8>, _derivation_path: String) -> Result<Vec<u8>, SignerError> {
+        Err(Self::watch_only_error())
+    }
+
+    fn slip77_master_blinding_key(&self) -> Result<Vec<u8>, SignerError> {
+        Ok(self.slip77_master_blinding_key.as_bytes().to_vec())
+    }
+
+    fn sign_ecdsa_recoverable(&self, _msg: Vec<u8>) -> Result<Vec<u8>, SignerError> {
+        Err(Self::watch_only_error())
+    }
+
+    fn hmac_sha256(&self, _msg: Vec<u8>, _derivation_path: String) -> Result<Vec<u8>, SignerError> {
+        Err(Self::watch_only_error())
+    }
+
+    fn ecies_encrypt(&self, _msg: Vec<u8>) -> Result<Vec<u8>, SignerError> {
+        Err(Self::watch_only_error())
+    }
+
+    fn ecies_decrypt(&self, _msg: Vec<u8>) -> Result<Vec<u8>, SignerError> {
+        Err(Self::watch_only_error())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -291,10 +565,10 @@ mod tests {
         pset::{Input, Output, PartiallySignedTransaction},
         AssetId, TxOut, Txid,
     };
-    use lwk_common::{singlesig_desc, Singlesig};
+    use lwk_common::{singlesig_desc, Signer as LwkSigner, Singlesig};
     use lwk_signer::SwSigner;
     use lwk_wollet::{
-        elements::{self, Script},
+        elements::{self, hex::ToHex, Script},
         ElementsNetwork, NoPersist, Wollet, WolletDescriptor,
     };
     use std::collections::BTreeMap;
@@ -312,14 +586,16 @@ mod tests {
         Ok(descriptor_str.parse()?)
     }
 
-    fn create_signers(mnemonic: &str) -> (SwSigner, SdkLwkSigner) {
+    async fn create_signers(mnemonic: &str) -> (SwSigner, SdkLwkSigner) {
         let sw_signer = SwSigner::new(mnemonic, false).unwrap();
         let sdk_signer: Box<dyn Signer> = Box::new(SdkSigner::new(mnemonic, "", false).unwrap());
-        let sdk_signer = SdkLwkSigner::new(Arc::new(sdk_signer)).unwrap();
+        let sdk_signer = SdkLwkSigner::new(Arc::new(SyncSignerAdapter::new(Arc::new(sdk_signer))))
+            .await
+            .unwrap();
         (sw_signer, sdk_signer)
     }
 
-    fn create_pset<S: LwkSigner>(signer: &S) -> PartiallySignedTransaction {
+    fn create_pset(fingerprint: Fingerprint, xpub: Xpub) -> PartiallySignedTransaction {
         // Create a PartiallySignedTransaction
         let mut pset = PartiallySignedTransaction::new_v2();
 
@@ -328,12 +604,8 @@ mod tests {
         let prev_vout = 0;
 
         let derivation_path: DerivationPath = "m/84'/0'/0'/0/0".parse().unwrap();
-        let xpub = signer.derive_xpub(&derivation_path).unwrap();
         let mut bip32_derivation_map: BTreeMap<PublicKey, KeySource> = BTreeMap::new();
-        bip32_derivation_map.insert(
-            xpub.public_key.into(),
-            (signer.fingerprint().unwrap(), derivation_path),
-        );
+        bip32_derivation_map.insert(xpub.public_key.into(), (fingerprint, derivation_path));
         let input = Input {
             non_witness_utxo: None,
             witness_utxo: Some(TxOut::new_fee(
@@ -381,21 +653,28 @@ mod tests {
         assert!(SdkSigner::new_with_seed(seed2.to_vec(), false).is_ok());
     }
 
-    #[sdk_macros::test_all]
-    fn test_sign() {
+    #[sdk_macros::async_test_all]
+    async fn test_sign() {
         let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
-        let (sw_signer, sdk_signer) = create_signers(mnemonic);
+        let (sw_signer, sdk_signer) = create_signers(mnemonic).await;
 
         // Clone the PSET for each signer
-        let mut pset_sw = create_pset(&sw_signer);
-        let mut pset_sdk = create_pset(&sdk_signer);
+        let derivation_path: DerivationPath = "m/84'/0'/0'/0/0".parse().unwrap();
+        let mut pset_sw = create_pset(
+            sw_signer.fingerprint(),
+            sw_signer.derive_xpub(&derivation_path).unwrap(),
+        );
+        let mut pset_sdk = create_pset(
+            sdk_signer.fingerprint().unwrap(),
+            sdk_signer.derive_xpub(&derivation_path).await.unwrap(),
+        );
 
         // Sign with SwSigner
         let sw_sig_count = sw_signer.sign(&mut pset_sw).unwrap();
         assert_eq!(sw_sig_count, 1);
 
         // Sign with SdkLwkSigner
-        let sdk_sig_count = sdk_signer.sign(&mut pset_sdk).unwrap();
+        let sdk_sig_count = sdk_signer.sign(&mut pset_sdk).await.unwrap();
         assert_eq!(sdk_sig_count, 1);
 
         // Compare the sign results
@@ -407,10 +686,10 @@ mod tests {
         assert_eq!(tx_sw, tx_sdk);
     }
 
-    #[sdk_macros::test_all]
-    fn test_slip77_master_blinding_key() {
+    #[sdk_macros::async_test_all]
+    async fn test_slip77_master_blinding_key() {
         let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
-        let (sw_signer, sdk_signer) = create_signers(mnemonic);
+        let (sw_signer, sdk_signer) = create_signers(mnemonic).await;
 
         let sw_key = sw_signer.slip77_master_blinding_key().unwrap();
         let sdk_key = sdk_signer.slip77_master_blinding_key().unwrap();
@@ -421,22 +700,22 @@ mod tests {
         );
     }
 
-    #[sdk_macros::test_all]
-    fn test_derive_xpub() {
+    #[sdk_macros::async_test_all]
+    async fn test_derive_xpub() {
         let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
-        let (sw_signer, sdk_signer) = create_signers(mnemonic);
+        let (sw_signer, sdk_signer) = create_signers(mnemonic).await;
 
         let path = "m/84'/0'/0'/0/0".parse().unwrap();
         let sw_xpub = sw_signer.derive_xpub(&path).unwrap();
-        let sdk_xpub = sdk_signer.derive_xpub(&path).unwrap();
+        let sdk_xpub = sdk_signer.derive_xpub(&path).await.unwrap();
 
         assert_eq!(sw_xpub, sdk_xpub, "Derived xpubs should be identical");
     }
 
-    #[sdk_macros::test_all]
-    fn test_identifier() {
+    #[sdk_macros::async_test_all]
+    async fn test_identifier() {
         let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
-        let (sw_signer, sdk_signer) = create_signers(mnemonic);
+        let (sw_signer, sdk_signer) = create_signers(mnemonic).await;
 
         let sw_identifier = sw_signer.xpub().identifier();
         let sdk_identifier = sdk_signer.xpub().unwrap().identifier();
@@ -447,10 +726,10 @@ mod tests {
         );
     }
 
-    #[sdk_macros::test_all]
-    fn test_fingerprint() {
+    #[sdk_macros::async_test_all]
+    async fn test_fingerprint() {
         let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
-        let (sw_signer, sdk_signer) = create_signers(mnemonic);
+        let (sw_signer, sdk_signer) = create_signers(mnemonic).await;
 
         let sw_fingerprint = sw_signer.fingerprint();
         let sdk_fingerprint = sdk_signer.fingerprint().unwrap();
@@ -468,8 +747,8 @@ mod tests {
         );
     }
 
-    #[sdk_macros::test_all]
-    fn test_sdk_signer_vs_sw_signer() {
+    #[sdk_macros::async_test_all]
+    async fn test_sdk_signer_vs_sw_signer() {
         // Use a test mnemonic (don't use this in production!)
         let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
         let network = ElementsNetwork::LiquidTestnet;
@@ -484,12 +763,12 @@ mod tests {
         .unwrap();
 
         // 2. Create a wallet using SdkLwkSigner
-        let sdk_signer: Box<dyn Signer> = Box::new(SdkSigner::new(mnemonic, "", false).unwrap());
-        let sdk_signer = SdkLwkSigner::new(Arc::new(sdk_signer)).unwrap();
+        let (_, sdk_signer) = create_signers(mnemonic).await;
+        assert!(!sdk_signer.is_mainnet());
         let sdk_wallet = Wollet::new(
             network,
             NoPersist::new(),
-            get_descriptor(&sdk_signer).unwrap(),
+            crate::wallet::get_descriptor(&sdk_signer, 0).await.unwrap(),
         )
         .unwrap();
 
@@ -503,4 +782,144 @@ mod tests {
             "Addresses should be identical"
         );
     }
+
+    #[sdk_macros::async_test_all]
+    async fn test_watch_only_signer() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let (sw_signer, sdk_signer) = create_signers(mnemonic).await;
+        let descriptor = get_descriptor(&sw_signer).unwrap();
+
+        let watch_only_signer = WatchOnlySigner::new(&descriptor).unwrap();
+        assert_eq!(
+            watch_only_signer.slip77_master_blinding_key().unwrap(),
+            sdk_signer.slip77_master_blinding_key().unwrap().as_bytes(),
+        );
+        let account_xpub = Xpub::decode(&watch_only_signer.xpub().unwrap()).unwrap();
+        assert_eq!(
+            account_xpub,
+            sdk_signer
+                .derive_xpub(&"m/84'/1'/0'".parse().unwrap())
+                .await
+                .unwrap()
+        );
+        assert!(watch_only_signer
+            .sign_ecdsa_recoverable(vec![0; 32])
+            .is_err());
+        assert!(watch_only_signer.ecies_encrypt(vec![0; 32]).is_err());
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_watch_only_multisig_signer() {
+        let (sw_signer, sdk_signer) = create_signers("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about").await;
+        let (_, cosigner) = create_signers(
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+        )
+        .await;
+        let path: DerivationPath = "m/87'/1'/0'".parse().unwrap();
+        let xpubs: Vec<Xpub> = vec![
+            sdk_signer.derive_xpub(&path).await.unwrap(),
+            cosigner.derive_xpub(&path).await.unwrap(),
+        ];
+        let descriptor: WolletDescriptor = format!(
+            "ct(slip77({}),elwsh(multi(2,{}/<0;1>/*,{}/<0;1>/*)))",
+            sdk_signer
+                .slip77_master_blinding_key()
+                .unwrap()
+                .as_bytes()
+                .to_hex(),
+            xpubs[0],
+            xpubs[1]
+        )
+        .parse()
+        .unwrap();
+
+        // A multisig descriptor is not a single-key one
+        assert!(WatchOnlySigner::new(&descriptor).is_err());
+
+        let multisig_signer = WatchOnlySigner::new_multisig(&descriptor).unwrap();
+        assert_eq!(
+            Xpub::decode(&multisig_signer.xpub().unwrap()).unwrap(),
+            xpubs[0]
+        );
+        assert!(multisig_signer.sign_ecdsa_recoverable(vec![0; 32]).is_err());
+
+        // A single-key descriptor is not a multisig one
+        let singlesig_descriptor = get_descriptor(&sw_signer).unwrap();
+        assert!(WatchOnlySigner::new_multisig(&singlesig_descriptor).is_err());
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_sync_signer_adapter() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let sdk_signer = SdkSigner::new(mnemonic, "", false).unwrap();
+        let sync_signer: Box<dyn Signer> = Box::new(SdkSigner::new(mnemonic, "", false).unwrap());
+        let async_signer = SyncSignerAdapter::new(Arc::new(sync_signer));
+
+        assert_eq!(
+            async_signer.xpub().await.unwrap(),
+            sdk_signer.xpub().unwrap()
+        );
+        let msg = vec![1; 32];
+        assert_eq!(
+            async_signer
+                .sign_ecdsa(msg.clone(), "m/84'/1'/0'/0/0".to_string())
+                .await
+                .unwrap(),
+            sdk_signer
+                .sign_ecdsa(msg.clone(), "m/84'/1'/0'/0/0".to_string())
+                .unwrap()
+        );
+        let encrypted = async_signer.ecies_encrypt(msg.clone()).await.unwrap();
+        assert_eq!(sdk_signer.ecies_decrypt(encrypted).unwrap(), msg);
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_account_scoped_signer() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let sync_signer: Box<dyn Signer> = Box::new(SdkSigner::new(mnemonic, "", false).unwrap());
+        let async_signer: Arc<dyn AsyncSigner> =
+            Arc::new(SyncSignerAdapter::new(Arc::new(sync_signer)));
+        let account_1_signer = AccountScopedSigner::new(async_signer.clone(), 1);
+        let account_2_signer = AccountScopedSigner::new(async_signer.clone(), 2);
+
+        // Only the same account can decrypt its records
+        let msg = vec![1; 32];
+        let encrypted = account_1_signer.ecies_encrypt(msg.clone()).await.unwrap();
+        assert_eq!(
+            account_1_signer
+                .ecies_decrypt(encrypted.clone())
+                .await
+                .unwrap(),
+            msg
+        );
+        assert!(account_2_signer
+            .ecies_decrypt(encrypted.clone())
+            .await
+            .is_err());
+        assert!(async_signer.ecies_decrypt(encrypted).await.is_err());
+
+        // Each account signs with its own key
+        let account_1_sig = account_1_signer
+            .sign_ecdsa_recoverable(msg.clone())
+            .await
+            .unwrap();
+        assert_eq!(account_1_sig.len(), 65);
+        assert_ne!(
+            account_1_sig,
+            account_2_signer
+                .sign_ecdsa_recoverable(msg.clone())
+                .await
+                .unwrap()
+        );
+        assert_ne!(
+            account_1_sig,
+            async_signer.sign_ecdsa_recoverable(msg).await.unwrap()
+        );
+
+        // The wallet keys are untouched
+        assert_eq!(
+            account_1_signer.xpub().await.unwrap(),
+            async_signer.xpub().await.unwrap()
+        );
+    }
 }
@@ -1,7 +1,14 @@
 use std::sync::Arc;
 use std::{sync::OnceLock, time::Duration};
 
-use super::{ProxyUrlFetcher, Swapper};
+use super::{
+    model::{
+        CreateBolt12OfferRequest, CreateChainRequest, CreateReverseRequest, CreateSubmarineRequest,
+        GetBolt12FetchRequest, GetBolt12FetchResponse, GetBolt12ParamsResponse, GetNodesResponse,
+        InvoiceRequest, SubmarineClaimTxResponse, SwapStatus, UpdateBolt12OfferRequest,
+    },
+    ProxyUrlFetcher, Swapper, STATUS_STREAM_CHANNEL_CAPACITY,
+};
 use crate::bitcoin::secp256k1::rand;
 use crate::model::BREEZ_SWAP_PROXY_URL;
 use crate::{
@@ -13,11 +20,8 @@ use anyhow::{anyhow, bail, Result};
 use boltz_client::reqwest::header::HeaderMap;
 use boltz_client::{
     boltz::{
-        self, BoltzApiClientV2, ChainPair, Cooperative, CreateBolt12OfferRequest,
-        CreateChainRequest, CreateChainResponse, CreateReverseRequest, CreateReverseResponse,
-        CreateSubmarineRequest, CreateSubmarineResponse, GetBolt12FetchRequest,
-        GetBolt12FetchResponse, GetBolt12ParamsResponse, GetNodesResponse, ReversePair,
-        SubmarineClaimTxResponse, SubmarinePair, UpdateBolt12OfferRequest, WsRequest,
+        self, BoltzApiClientV2, ChainPair, Cooperative, CreateChainResponse, CreateReverseResponse,
+        CreateSubmarineResponse, ReversePair, SubmarinePair, WsRequest,
     },
     elements::secp256k1_zkp::{MusigPartialSignature, MusigPubNonce},
     network::Chain,
@@ -34,6 +38,7 @@ use tokio_with_wasm::alias as tokio;
 pub(crate) mod bitcoin;
 mod client;
 pub(crate) mod liquid;
+mod model;
 pub(crate) mod proxy;
 pub mod status_stream;
 
@@ -44,30 +49,57 @@ const MAX_RETRY_DELAY_SECS: u64 = 10;
 
 pub(crate) struct BoltzClient {
     referral_id: Option<String>,
-    inner: BoltzApiClientV2,
+    pub(crate) inner: BoltzApiClientV2,
     ws_auth_api_key: Option<String>,
+    url: String,
+    http_client: boltz_client::reqwest::Client,
+}
+
+impl BoltzClient {
+    /// Fetches the current status of a swap from the REST API, which is used when the
+    /// websocket status stream cannot be reached
+    pub(crate) async fn get_swap_status(&self, swap_id: &str) -> Result<SwapStatus> {
+        let body = self
+            .http_client
+            .get(format!("{}/swap/{swap_id}", self.url))
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let mut status: serde_json::Value = serde_json::from_str(&body)?;
+        let Some(fields) = status.as_object_mut() else {
+            bail!("Unexpected swap status response: {body}");
+        };
+        fields.insert("id".to_string(), swap_id.into());
+        Ok(serde_json::from_value::<boltz::SwapStatus>(status)?.into())
+    }
 }
 
 pub struct BoltzSwapper<P: ProxyUrlFetcher> {
     config: Config,
+    /// The API URL of the instance, if it should be used instead of the default one
+    boltz_url: Option<String>,
     boltz_client: OnceLock<BoltzClient>,
     liquid_client: OnceLock<LiquidClient>,
     bitcoin_client: OnceLock<BitcoinClient>,
     proxy_url: Arc<P>,
     request_notifier: broadcast::Sender<WsRequest>,
-    update_notifier: broadcast::Sender<boltz::SwapStatus>,
-    invoice_request_notifier: broadcast::Sender<boltz::InvoiceRequest>,
+    update_notifier: broadcast::Sender<SwapStatus>,
+    invoice_request_notifier: broadcast::Sender<InvoiceRequest>,
 }
 
 impl<P: ProxyUrlFetcher> BoltzSwapper<P> {
     pub fn new(config: Config, proxy_url: Arc<P>) -> Result<Self, SdkError> {
-        let (request_notifier, _) = broadcast::channel::<WsRequest>(30);
-        let (update_notifier, _) = broadcast::channel::<boltz::SwapStatus>(30);
-        let (invoice_request_notifier, _) = broadcast::channel::<boltz::InvoiceRequest>(30);
+        let (request_notifier, _) = broadcast::channel::<WsRequest>(STATUS_STREAM_CHANNEL_CAPACITY);
+        let (update_notifier, _) = broadcast::channel::<SwapStatus>(STATUS_STREAM_CHANNEL_CAPACITY);
+        let (invoice_request_notifier, _) =
+            broadcast::channel::<InvoiceRequest>(STATUS_STREAM_CHANNEL_CAPACITY);
 
         Ok(Self {
             proxy_url,
             config: config.clone(),
+            boltz_url: None,
             boltz_client: OnceLock::new(),
             liquid_client: OnceLock::new(),
             bitcoin_client: OnceLock::new(),
@@ -77,14 +109,21 @@ impl<P: ProxyUrlFetcher> BoltzSwapper<P> {
         })
     }
 
-    async fn get_boltz_client(&self) -> Result<&BoltzClient> {
+    /// Sets the API URL of the Boltz instance to use instead of the default one
+    pub fn with_boltz_url(mut self, boltz_url: String) -> Self {
+        self.boltz_url = Some(boltz_url);
+        self
+    }
+
+    pub(crate) async fn get_boltz_client(&self) -> Result<&BoltzClient> {
         if let Some(client) = self.boltz_client.get() {
             return Ok(client);
         }
 
-        let (boltz_api_base_url, referral_id) = match &self.config.network {
-            LiquidNetwork::Testnet | LiquidNetwork::Regtest => (None, None),
-            LiquidNetwork::Mainnet => match self.proxy_url.fetch().await {
+        let (boltz_api_base_url, referral_id) = match (&self.boltz_url, &self.config.network) {
+            (Some(boltz_url), _) => (Some(boltz_url.clone()), None),
+            (None, LiquidNetwork::Testnet | LiquidNetwork::Regtest) => (None, None),
+            (None, LiquidNetwork::Mainnet) => match self.proxy_url.fetch().await {
                 Ok(Some(boltz_swapper_urls)) => {
                     if self.config.breez_api_key.is_some() {
                         split_boltz_url(&boltz_swapper_urls.proxy_url)
@@ -112,17 +151,20 @@ impl<P: ProxyUrlFetcher> BoltzSwapper<P> {
             }
         }
 
+        let http_client = boltz_client::reqwest::Client::builder()
+            .default_headers(headers)
+            .build()?;
         let inner = BoltzApiClientV2::with_client(
-            boltz_url,
-            boltz_client::reqwest::Client::builder()
-                .default_headers(headers)
-                .build()?,
+            boltz_url.clone(),
+            http_client.clone(),
             Some(CONNECTION_TIMEOUT),
         );
         let client = self.boltz_client.get_or_init(|| BoltzClient {
             inner,
             referral_id,
             ws_auth_api_key,
+            url: boltz_url.trim_end_matches('/').to_string(),
+            http_client,
         });
         Ok(client)
     }
@@ -278,9 +320,9 @@ impl<P: ProxyUrlFetcher> Swapper for BoltzSwapper<P> {
         let client = self.get_boltz_client().await?;
         let modified_req = CreateChainRequest {
             referral_id: client.referral_id.clone(),
-            ..req.clone()
+            ..req
         };
-        Ok(client.inner.post_chain_req(modified_req).await?)
+        Ok(client.inner.post_chain_req(modified_req.into()).await?)
     }
 
     /// Create a new send swap
@@ -291,9 +333,9 @@ impl<P: ProxyUrlFetcher> Swapper for BoltzSwapper<P> {
         let client = self.get_boltz_client().await?;
         let modified_req = CreateSubmarineRequest {
             referral_id: client.referral_id.clone(),
-            ..req.clone()
+            ..req
         };
-        Ok(client.inner.post_swap_req(&modified_req).await?)
+        Ok(client.inner.post_swap_req(&modified_req.into()).await?)
     }
 
     async fn get_chain_pair(
@@ -388,7 +430,7 @@ impl<P: ProxyUrlFetcher> Swapper for BoltzSwapper<P> {
         info!("Received claim tx details: {:?}", &claim_tx_response);
 
         self.validate_send_swap_preimage(&swap.id, &swap.invoice, &claim_tx_response.preimage)?;
-        Ok(claim_tx_response)
+        Ok(claim_tx_response.into())
     }
 
     /// Claim send swap cooperatively. Here the remote swapper is the one that claims.
@@ -428,9 +470,9 @@ impl<P: ProxyUrlFetcher> Swapper for BoltzSwapper<P> {
         let client = self.get_boltz_client().await?;
         let modified_req = CreateReverseRequest {
             referral_id: client.referral_id.clone(),
-            ..req.clone()
+            ..req
         };
-        Ok(client.inner.post_reverse_req(modified_req).await?)
+        Ok(client.inner.post_reverse_req(modified_req.into()).await?)
     }
 
     // Get a reverse pair information
@@ -607,17 +649,17 @@ impl<P: ProxyUrlFetcher> Swapper for BoltzSwapper<P> {
             .get_boltz_client()
             .await?
             .inner
-            .get_bolt12_invoice(req)
+            .get_bolt12_invoice(req.into())
             .await?;
         info!("Received BOLT12 invoice response: {invoice_res:?}");
-        Ok(invoice_res)
+        Ok(invoice_res.into())
     }
 
     async fn create_bolt12_offer(&self, req: CreateBolt12OfferRequest) -> Result<(), SdkError> {
         self.get_boltz_client()
             .await?
             .inner
-            .post_bolt12_offer(req)
+            .post_bolt12_offer(req.into())
             .await?;
         Ok(())
     }
@@ -626,7 +668,7 @@ impl<P: ProxyUrlFetcher> Swapper for BoltzSwapper<P> {
         self.get_boltz_client()
             .await?
             .inner
-            .patch_bolt12_offer(req)
+            .patch_bolt12_offer(req.into())
             .await?;
         Ok(())
     }
@@ -647,12 +689,12 @@ impl<P: ProxyUrlFetcher> Swapper for BoltzSwapper<P> {
             .inner
             .get_bolt12_params()
             .await?;
-        Ok(res)
+        Ok(res.into())
     }
 
     async fn get_nodes(&self) -> Result<GetNodesResponse, PaymentError> {
         let res = self.get_boltz_client().await?.inner.get_nodes().await?;
-        Ok(res)
+        Ok(res.into())
     }
 }
 
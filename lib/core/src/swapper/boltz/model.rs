@@ -0,0 +1,284 @@
+//! Conversions between the [swapper model](crate::swapper::model) and the Boltz API types
+
+use boltz_client::boltz;
+
+use crate::swapper::model::{
+    CreateBolt12OfferRequest, CreateChainRequest, CreateReverseRequest, CreateSubmarineRequest,
+    GetBolt12FetchRequest, GetBolt12FetchResponse, GetBolt12ParamsResponse, GetNodesResponse,
+    InvoiceRequest, MagicRoutingHint, Node, SubmarineClaimTxResponse, SwapStatus, TransactionInfo,
+    UpdateBolt12OfferRequest, Webhook,
+};
+
+impl<T> From<Webhook<T>> for boltz::Webhook<T> {
+    fn from(webhook: Webhook<T>) -> Self {
+        boltz::Webhook {
+            url: webhook.url,
+            hash_swap_id: webhook.hash_swap_id,
+            status: webhook.status,
+        }
+    }
+}
+
+impl From<CreateSubmarineRequest> for boltz::CreateSubmarineRequest {
+    fn from(req: CreateSubmarineRequest) -> Self {
+        boltz::CreateSubmarineRequest {
+            from: req.from,
+            to: req.to,
+            invoice: req.invoice,
+            refund_public_key: req.refund_public_key,
+            pair_hash: req.pair_hash,
+            referral_id: req.referral_id,
+            webhook: req.webhook.map(Into::into),
+        }
+    }
+}
+
+impl From<CreateReverseRequest> for boltz::CreateReverseRequest {
+    fn from(req: CreateReverseRequest) -> Self {
+        boltz::CreateReverseRequest {
+            from: req.from,
+            to: req.to,
+            invoice: req.invoice,
+            invoice_amount: req.invoice_amount,
+            preimage_hash: req.preimage_hash,
+            claim_public_key: req.claim_public_key,
+            description: req.description,
+            description_hash: req.description_hash,
+            address: req.address,
+            address_signature: req.address_signature,
+            referral_id: req.referral_id,
+            webhook: req.webhook.map(Into::into),
+        }
+    }
+}
+
+impl From<CreateChainRequest> for boltz::CreateChainRequest {
+    fn from(req: CreateChainRequest) -> Self {
+        boltz::CreateChainRequest {
+            from: req.from,
+            to: req.to,
+            preimage_hash: req.preimage_hash,
+            claim_public_key: req.claim_public_key,
+            refund_public_key: req.refund_public_key,
+            user_lock_amount: req.user_lock_amount,
+            server_lock_amount: req.server_lock_amount,
+            pair_hash: req.pair_hash,
+            referral_id: req.referral_id,
+            webhook: req.webhook.map(Into::into),
+        }
+    }
+}
+
+impl From<boltz::SubmarineClaimTxResponse> for SubmarineClaimTxResponse {
+    fn from(res: boltz::SubmarineClaimTxResponse) -> Self {
+        SubmarineClaimTxResponse {
+            preimage: res.preimage,
+            pub_nonce: res.pub_nonce,
+            public_key: res.public_key,
+            transaction_hash: res.transaction_hash,
+        }
+    }
+}
+
+impl From<GetBolt12FetchRequest> for boltz::GetBolt12FetchRequest {
+    fn from(req: GetBolt12FetchRequest) -> Self {
+        boltz::GetBolt12FetchRequest {
+            offer: req.offer,
+            amount: req.amount,
+            note: req.note,
+        }
+    }
+}
+
+impl From<boltz::GetBolt12FetchResponse> for GetBolt12FetchResponse {
+    fn from(res: boltz::GetBolt12FetchResponse) -> Self {
+        GetBolt12FetchResponse {
+            invoice: res.invoice,
+            magic_routing_hint: res.magic_routing_hint.map(|mrh| MagicRoutingHint {
+                bip21: mrh.bip21,
+                signature: mrh.signature,
+            }),
+        }
+    }
+}
+
+impl From<CreateBolt12OfferRequest> for boltz::CreateBolt12OfferRequest {
+    fn from(req: CreateBolt12OfferRequest) -> Self {
+        boltz::CreateBolt12OfferRequest {
+            offer: req.offer,
+            url: req.url,
+        }
+    }
+}
+
+impl From<UpdateBolt12OfferRequest> for boltz::UpdateBolt12OfferRequest {
+    fn from(req: UpdateBolt12OfferRequest) -> Self {
+        boltz::UpdateBolt12OfferRequest {
+            offer: req.offer,
+            url: req.url,
+            signature: req.signature,
+        }
+    }
+}
+
+impl From<boltz::GetBolt12ParamsResponse> for GetBolt12ParamsResponse {
+    fn from(res: boltz::GetBolt12ParamsResponse) -> Self {
+        GetBolt12ParamsResponse {
+            min_cltv: res.min_cltv,
+        }
+    }
+}
+
+impl From<boltz::GetNodesResponse> for GetNodesResponse {
+    fn from(res: boltz::GetNodesResponse) -> Self {
+        GetNodesResponse {
+            btc: res
+                .btc
+                .into_iter()
+                .map(|(implementation, node)| {
+                    (
+                        implementation,
+                        Node {
+                            public_key: node.public_key,
+                            uris: node.uris,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<boltz::SwapStatus> for SwapStatus {
+    fn from(update: boltz::SwapStatus) -> Self {
+        SwapStatus {
+            id: update.id,
+            status: update.status,
+            transaction: update.transaction.map(|tx| TransactionInfo {
+                id: tx.id,
+                hex: tx.hex,
+            }),
+            zero_conf_rejected: update.zero_conf_rejected,
+        }
+    }
+}
+
+impl From<boltz::InvoiceRequest> for InvoiceRequest {
+    fn from(request: boltz::InvoiceRequest) -> Self {
+        InvoiceRequest {
+            id: request.id,
+            offer: request.offer,
+            invoice_request: request.invoice_request,
+        }
+    }
+}
+
+impl<T> From<boltz::Webhook<T>> for Webhook<T> {
+    fn from(webhook: boltz::Webhook<T>) -> Self {
+        Webhook {
+            url: webhook.url,
+            hash_swap_id: webhook.hash_swap_id,
+            status: webhook.status,
+        }
+    }
+}
+
+impl From<boltz::CreateSubmarineRequest> for CreateSubmarineRequest {
+    fn from(req: boltz::CreateSubmarineRequest) -> Self {
+        CreateSubmarineRequest {
+            from: req.from,
+            to: req.to,
+            invoice: req.invoice,
+            refund_public_key: req.refund_public_key,
+            pair_hash: req.pair_hash,
+            referral_id: req.referral_id,
+            webhook: req.webhook.map(Into::into),
+        }
+    }
+}
+
+impl From<boltz::CreateReverseRequest> for CreateReverseRequest {
+    fn from(req: boltz::CreateReverseRequest) -> Self {
+        CreateReverseRequest {
+            from: req.from,
+            to: req.to,
+            invoice: req.invoice,
+            invoice_amount: req.invoice_amount,
+            preimage_hash: req.preimage_hash,
+            claim_public_key: req.claim_public_key,
+            description: req.description,
+            description_hash: req.description_hash,
+            address: req.address,
+            address_signature: req.address_signature,
+            referral_id: req.referral_id,
+            webhook: req.webhook.map(Into::into),
+        }
+    }
+}
+
+impl From<boltz::CreateChainRequest> for CreateChainRequest {
+    fn from(req: boltz::CreateChainRequest) -> Self {
+        CreateChainRequest {
+            from: req.from,
+            to: req.to,
+            preimage_hash: req.preimage_hash,
+            claim_public_key: req.claim_public_key,
+            refund_public_key: req.refund_public_key,
+            user_lock_amount: req.user_lock_amount,
+            server_lock_amount: req.server_lock_amount,
+            pair_hash: req.pair_hash,
+            referral_id: req.referral_id,
+            webhook: req.webhook.map(Into::into),
+        }
+    }
+}
+
+impl From<boltz::GetBolt12FetchRequest> for GetBolt12FetchRequest {
+    fn from(req: boltz::GetBolt12FetchRequest) -> Self {
+        GetBolt12FetchRequest {
+            offer: req.offer,
+            amount: req.amount,
+            note: req.note,
+        }
+    }
+}
+
+impl From<GetBolt12FetchResponse> for boltz::GetBolt12FetchResponse {
+    fn from(res: GetBolt12FetchResponse) -> Self {
+        boltz::GetBolt12FetchResponse {
+            invoice: res.invoice,
+            magic_routing_hint: res.magic_routing_hint.map(|mrh| boltz::MagicRoutingHint {
+                bip21: mrh.bip21,
+                signature: mrh.signature,
+            }),
+        }
+    }
+}
+
+impl From<GetBolt12ParamsResponse> for boltz::GetBolt12ParamsResponse {
+    fn from(res: GetBolt12ParamsResponse) -> Self {
+        boltz::GetBolt12ParamsResponse {
+            min_cltv: res.min_cltv,
+        }
+    }
+}
+
+impl From<GetNodesResponse> for boltz::GetNodesResponse {
+    fn from(res: GetNodesResponse) -> Self {
+        boltz::GetNodesResponse {
+            btc: res
+                .btc
+                .into_iter()
+                .map(|(implementation, node)| {
+                    (
+                        implementation,
+                        boltz::Node {
+                            public_key: node.public_key,
+                            uris: node.uris,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
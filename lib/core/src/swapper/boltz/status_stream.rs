@@ -1,8 +1,16 @@
 use std::time::Duration;
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    pin::pin,
+    sync::Arc,
+};
 
 use crate::{
-    swapper::{boltz::BoltzSwapper, ProxyUrlFetcher, SubscriptionHandler, SwapperStatusStream},
+    swapper::{
+        boltz::{BoltzClient, BoltzSwapper},
+        model::{InvoiceRequest, SwapStatus},
+        ProxyUrlFetcher, SubscriptionHandler, SwapperStatusStream, TERMINAL_SWAP_STATUSES,
+    },
     utils::run_with_shutdown,
 };
 use anyhow::{anyhow, Result};
@@ -12,11 +20,21 @@ use boltz_client::boltz::{
     InvoiceRequestParams, WsRequest, WsResponse,
 };
 use futures_util::{stream::SplitSink, SinkExt, StreamExt};
-use log::{debug, error, warn};
+use log::{debug, error, info, warn};
 use serde::Serialize;
 use tokio::sync::{broadcast, watch};
 use tokio_with_wasm::alias as tokio;
 
+/// The number of consecutive failed websocket connections after which swap updates are polled
+/// over HTTP
+const WS_FAILURES_BEFORE_POLLING: u32 = 3;
+/// How long a websocket connection has to stay open to not count as a failed connection
+const WS_MIN_CONNECTION_DURATION: Duration = Duration::from_secs(30);
+/// How long swap updates are polled before trying to connect to the websocket again
+const POLLING_PERIOD: Duration = Duration::from_secs(60);
+/// The delay between polling the status of each tracked swap
+const POLLING_INTERVAL: Duration = Duration::from_secs(10);
+
 #[derive(Debug, Serialize)]
 struct ApiKeyMessage {
     #[serde(rename = "apikey")]
@@ -38,6 +56,65 @@ impl<P: ProxyUrlFetcher> BoltzSwapper<P> {
             Err(e) => error!("Error encoding request: {e:?}"),
         }
     }
+
+    /// Polls the status of the tracked swaps over HTTP for [POLLING_PERIOD], feeding any status
+    /// changes to the same channel as the websocket updates.
+    ///
+    /// Swaps are no longer polled once they reach a terminal status. Invoice requests cannot be
+    /// polled, so BOLT12 offers are only served over the websocket.
+    async fn poll_swap_updates(
+        &self,
+        client: &BoltzClient,
+        callback: &dyn SubscriptionHandler,
+        request_stream: &mut broadcast::Receiver<WsRequest>,
+    ) {
+        // The last known status of each tracked swap
+        let mut polled_swaps: HashMap<String, Option<String>> = HashMap::new();
+        callback.track_subscriptions().await;
+
+        let mut polling_end = pin!(tokio::time::sleep(POLLING_PERIOD));
+        let mut polling_interval = tokio::time::interval(POLLING_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = &mut polling_end => {
+                    debug!("Polling period ended, retrying websocket connection");
+                    break;
+                },
+
+                ws_request_res = request_stream.recv() => match ws_request_res {
+                    Ok(WsRequest::Subscribe(boltz::SubscribeRequest::SwapUpdate { args })) => {
+                        for id in args {
+                            polled_swaps.entry(id).or_default();
+                        }
+                    },
+                    Ok(ws_request) => warn!("Cannot send request while polling: {ws_request:?}"),
+                    Err(e) => error!("Received error on request stream: {e:?}"),
+                },
+
+                _ = polling_interval.tick() => {
+                    let mut terminal_swaps = vec![];
+                    for (id, last_status) in polled_swaps.iter_mut() {
+                        match client.get_swap_status(id).await {
+                            Ok(update) => {
+                                if TERMINAL_SWAP_STATUSES.contains(&update.status.as_str()) {
+                                    terminal_swaps.push(id.clone());
+                                }
+                                if last_status.as_ref() != Some(&update.status) {
+                                    *last_status = Some(update.status.clone());
+                                    let _ = self.update_notifier.send(update);
+                                }
+                            }
+                            Err(e) => warn!("Failed to poll status of swap {id}: {e:?}"),
+                        }
+                    }
+                    for id in terminal_swaps {
+                        debug!("Swap {id} reached a terminal status, no longer polling it");
+                        polled_swaps.remove(&id);
+                    }
+                },
+            }
+        }
+    }
 }
 
 impl<P: ProxyUrlFetcher> SwapperStatusStream for BoltzSwapper<P> {
@@ -51,6 +128,7 @@ impl<P: ProxyUrlFetcher> SwapperStatusStream for BoltzSwapper<P> {
 
         let swapper = Arc::clone(&self);
         let status_stream_future = async move {
+            let mut ws_failures = 0;
             loop {
                 debug!("Start of ws stream loop");
                 let mut request_stream = self.request_notifier.subscribe();
@@ -62,6 +140,15 @@ impl<P: ProxyUrlFetcher> SwapperStatusStream for BoltzSwapper<P> {
                         continue;
                     }
                 };
+                if ws_failures >= WS_FAILURES_BEFORE_POLLING {
+                    info!("Websocket is unavailable, polling swap updates");
+                    swapper
+                        .poll_swap_updates(client, callback.as_ref(), &mut request_stream)
+                        .await;
+                    // Go back to polling if the next connection attempt fails as well
+                    ws_failures = WS_FAILURES_BEFORE_POLLING - 1;
+                    continue;
+                }
                 match client.inner.connect_ws().await {
                     Ok(ws_stream) => {
                         let (mut sender, mut receiver) = ws_stream.split();
@@ -88,8 +175,18 @@ impl<P: ProxyUrlFetcher> SwapperStatusStream for BoltzSwapper<P> {
 
                         callback.track_subscriptions().await;
 
+                        // Connections closed before this are counted as failed connections,
+                        // so a websocket that keeps dropping falls back to polling as well
+                        let mut min_connection_duration =
+                            pin!(tokio::time::sleep(WS_MIN_CONNECTION_DURATION));
+                        let mut is_connection_stable = false;
                         loop {
                             tokio::select! {
+                                _ = &mut min_connection_duration, if !is_connection_stable => {
+                                    is_connection_stable = true;
+                                    ws_failures = 0;
+                                },
+
                                 _ = tokio::time::sleep(keep_alive_ping_interval) => {
                                     match serde_json::to_string(&WsRequest::Ping) {
                                         Ok(ping_msg) => {
@@ -136,14 +233,14 @@ impl<P: ProxyUrlFetcher> SwapperStatusStream for BoltzSwapper<P> {
                                                 // Swap status update(s)
                                                 Ok(WsResponse::Update(update)) => {
                                                     for update in update.args {
-                                                        let _ = self.update_notifier.send(update);
+                                                        let _ = self.update_notifier.send(update.into());
                                                     }
                                                 }
 
                                                 // Invoice requests(s)
                                                 Ok(WsResponse::InvoiceRequest(invoice_request)) => {
                                                     for invoice_request in invoice_request.args {
-                                                        let _ = self.invoice_request_notifier.send(invoice_request);
+                                                        let _ = self.invoice_request_notifier.send(invoice_request.into());
                                                     }
                                                 }
 
@@ -176,9 +273,14 @@ impl<P: ProxyUrlFetcher> SwapperStatusStream for BoltzSwapper<P> {
                                 }
                             }
                         }
+                        if !is_connection_stable {
+                            ws_failures += 1;
+                            warn!("Websocket connection closed after less than {WS_MIN_CONNECTION_DURATION:?} ({ws_failures} consecutive failures)");
+                        }
                     }
                     Err(e) => {
                         warn!("Error connecting to stream: {e:?}");
+                        ws_failures += 1;
                         tokio::time::sleep(reconnect_delay).await;
                     }
                 }
@@ -257,11 +359,156 @@ impl<P: ProxyUrlFetcher> SwapperStatusStream for BoltzSwapper<P> {
         }
     }
 
-    fn subscribe_swap_updates(&self) -> broadcast::Receiver<boltz::SwapStatus> {
+    fn subscribe_swap_updates(&self) -> broadcast::Receiver<SwapStatus> {
         self.update_notifier.subscribe()
     }
 
-    fn subscribe_invoice_requests(&self) -> broadcast::Receiver<boltz::InvoiceRequest> {
+    fn subscribe_invoice_requests(&self) -> broadcast::Receiver<InvoiceRequest> {
         self.invoice_request_notifier.subscribe()
     }
 }
+
+#[cfg(all(test, not(all(target_family = "wasm", target_os = "unknown"))))]
+mod tests {
+    use std::sync::Arc;
+
+    use anyhow::Result;
+    use boltz_client::boltz;
+    use tokio::sync::watch;
+    use tokio_with_wasm::alias as tokio;
+
+    use crate::{
+        model::Config,
+        swapper::{
+            boltz::BoltzSwapper, model::SwapStatus, SubscriptionHandler, SwapperStatusStream,
+        },
+        test_utils::{boltz_server::MockBoltzServer, swapper::MockProxyUrlFetcher},
+    };
+
+    struct TrackingSubscriptionHandler {
+        swapper: Arc<BoltzSwapper<MockProxyUrlFetcher>>,
+        swap_id: String,
+    }
+
+    #[sdk_macros::async_trait]
+    impl SubscriptionHandler for TrackingSubscriptionHandler {
+        async fn track_subscriptions(&self) {
+            self.swapper.track_swap_id(&self.swap_id).unwrap();
+        }
+    }
+
+    fn new_swapper(server: &MockBoltzServer) -> Result<Arc<BoltzSwapper<MockProxyUrlFetcher>>> {
+        Ok(Arc::new(
+            BoltzSwapper::new(
+                Config::regtest_esplora(),
+                Arc::new(MockProxyUrlFetcher::new()),
+            )?
+            .with_boltz_url(server.url()),
+        ))
+    }
+
+    fn set_status(server: &MockBoltzServer, status: &str) {
+        server.set_swap_status(boltz::SwapStatus {
+            id: "swap-id".to_string(),
+            status: status.to_string(),
+            ..Default::default()
+        });
+    }
+
+    /// Starts the status stream of `swapper` tracking the swap, then waits for the first update
+    async fn start_and_receive_update(
+        swapper: Arc<BoltzSwapper<MockProxyUrlFetcher>>,
+        shutdown: watch::Receiver<()>,
+    ) -> Result<SwapStatus> {
+        let mut updates = swapper.subscribe_swap_updates();
+        swapper.clone().start(
+            Box::new(TrackingSubscriptionHandler {
+                swapper: swapper.clone(),
+                swap_id: "swap-id".to_string(),
+            }),
+            shutdown,
+        );
+        Ok(tokio::time::timeout(super::POLLING_PERIOD, updates.recv()).await??)
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_poll_swap_updates() -> Result<()> {
+        // The polling intervals elapse as soon as the test is idle
+        ::tokio::time::pause();
+        let server = MockBoltzServer::start().await?;
+        let swapper = new_swapper(&server)?;
+        set_status(&server, "swap.created");
+
+        let mut updates = swapper.subscribe_swap_updates();
+        let mut request_stream = swapper.request_notifier.subscribe();
+        let cloned = swapper.clone();
+        let polling = tokio::spawn(async move {
+            let handler = TrackingSubscriptionHandler {
+                swapper: cloned.clone(),
+                swap_id: "swap-id".to_string(),
+            };
+            let client = cloned.get_boltz_client().await.unwrap();
+            cloned
+                .poll_swap_updates(client, &handler, &mut request_stream)
+                .await;
+        });
+
+        // The tracked swap is polled right away
+        let update = tokio::time::timeout(super::POLLING_INTERVAL, updates.recv()).await??;
+        assert_eq!(update.id, "swap-id");
+        assert_eq!(update.status, "swap.created");
+
+        // Only status changes are sent, so the next update is the new status
+        set_status(&server, "transaction.mempool");
+        let update = tokio::time::timeout(super::POLLING_INTERVAL * 2, updates.recv()).await??;
+        assert_eq!(update.status, "transaction.mempool");
+
+        // Once the swap reaches a terminal status, it is no longer polled
+        set_status(&server, "transaction.claimed");
+        let update = tokio::time::timeout(super::POLLING_INTERVAL * 2, updates.recv()).await??;
+        assert_eq!(update.status, "transaction.claimed");
+        let status_request_count = server.status_request_count("swap-id");
+        tokio::time::sleep(super::POLLING_INTERVAL * 3).await;
+        assert_eq!(server.status_request_count("swap-id"), status_request_count);
+
+        polling.abort();
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_falls_back_to_polling() -> Result<()> {
+        ::tokio::time::pause();
+        let server = MockBoltzServer::start().await?;
+        let swapper = new_swapper(&server)?;
+        let (shutdown_sender, shutdown_receiver) = watch::channel(());
+        server.set_ws_unavailable(true);
+        set_status(&server, "transaction.mempool");
+
+        // The update is only received once the websocket failed enough times to start polling
+        let update = start_and_receive_update(swapper, shutdown_receiver).await?;
+        assert_eq!(update.id, "swap-id");
+        assert_eq!(update.status, "transaction.mempool");
+        assert!(server.status_request_count("swap-id") > 0);
+
+        shutdown_sender.send(())?;
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_short_lived_connections_fall_back_to_polling() -> Result<()> {
+        ::tokio::time::pause();
+        let server = MockBoltzServer::start().await?;
+        let swapper = new_swapper(&server)?;
+        let (shutdown_sender, shutdown_receiver) = watch::channel(());
+        server.set_ws_drops_connections(true);
+        set_status(&server, "transaction.mempool");
+
+        // The connections succeed, but are closed before any update is sent over them
+        let update = start_and_receive_update(swapper, shutdown_receiver).await?;
+        assert_eq!(update.status, "transaction.mempool");
+        assert!(server.status_request_count("swap-id") > 0);
+
+        shutdown_sender.send(())?;
+        Ok(())
+    }
+}
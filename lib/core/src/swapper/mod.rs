@@ -1,18 +1,14 @@
 use std::sync::Arc;
 
 use anyhow::Result;
-use boltz_client::{
-    boltz::{
-        ChainPair, CreateBolt12OfferRequest, CreateChainRequest, CreateChainResponse,
-        CreateReverseRequest, CreateReverseResponse, CreateSubmarineRequest,
-        CreateSubmarineResponse, GetBolt12FetchRequest, GetBolt12FetchResponse,
-        GetBolt12ParamsResponse, GetNodesResponse, ReversePair, SubmarineClaimTxResponse,
-        SubmarinePair, UpdateBolt12OfferRequest,
-    },
-    network::Chain,
-    Amount,
-};
 use mockall::automock;
+use model::{
+    Amount, Chain, ChainPair, CreateBolt12OfferRequest, CreateChainRequest, CreateChainResponse,
+    CreateReverseRequest, CreateReverseResponse, CreateSubmarineRequest, CreateSubmarineResponse,
+    GetBolt12FetchRequest, GetBolt12FetchResponse, GetBolt12ParamsResponse, GetNodesResponse,
+    InvoiceRequest, ReversePair, SubmarineClaimTxResponse, SubmarinePair, SwapStatus,
+    UpdateBolt12OfferRequest,
+};
 use sdk_common::prelude::BoltzSwapperUrls;
 use tokio::sync::{broadcast, watch};
 
@@ -20,10 +16,34 @@ use crate::{
     error::{PaymentError, SdkError},
     prelude::{Direction, SendSwap, Swap, Utxo},
 };
+pub(crate) use registry::SwapperRegistry;
+pub use registry::{SwapProvider, DEFAULT_SWAP_PROVIDER};
+pub use subscription_handler::SubscriptionHandler;
 pub(crate) use subscription_handler::*;
 pub(crate) mod boltz;
+pub mod model;
+pub(crate) mod registry;
+pub(crate) mod router;
 pub(crate) mod subscription_handler;
 
+/// The capacity of the channels notifying swap updates and invoice requests. A status stream
+/// may be shared by many wallets, so its updates can arrive in bursts, e.g. when re-tracking
+/// all ongoing swaps after reconnecting.
+pub(crate) const STATUS_STREAM_CHANNEL_CAPACITY: usize = 4096;
+
+/// The swap statuses after which the swapper sends no further updates for a swap
+pub(crate) const TERMINAL_SWAP_STATUSES: [&str; 6] = [
+    "invoice.expired",
+    "invoice.settled",
+    "swap.expired",
+    "transaction.claimed",
+    "transaction.failed",
+    "transaction.refunded",
+];
+
+/// A swap provider's API, used to create and settle swaps.
+///
+/// See [model] for the request and response types.
 #[automock]
 #[sdk_macros::async_trait]
 pub trait Swapper: Send + Sync {
@@ -157,10 +177,8 @@ pub trait SwapperStatusStream: Send + Sync {
     fn send_invoice_created(&self, id: &str, invoice: &str) -> Result<()>;
     fn send_invoice_error(&self, id: &str, error: &str) -> Result<()>;
 
-    fn subscribe_swap_updates(&self) -> broadcast::Receiver<boltz_client::boltz::SwapStatus>;
-    fn subscribe_invoice_requests(
-        &self,
-    ) -> broadcast::Receiver<boltz_client::boltz::InvoiceRequest>;
+    fn subscribe_swap_updates(&self) -> broadcast::Receiver<SwapStatus>;
+    fn subscribe_invoice_requests(&self) -> broadcast::Receiver<InvoiceRequest>;
 }
 
 #[sdk_macros::async_trait]
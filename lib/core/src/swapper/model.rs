@@ -0,0 +1,160 @@
+//! The swap model used by the [Swapper](super::Swapper) trait.
+//!
+//! The requests, the claim and BOLT12 responses and the status stream messages are owned by
+//! the SDK. Providers other than Boltz can be plugged in by implementing
+//! [Swapper](super::Swapper) as an adapter that converts their API types to and from these.
+//!
+//! The pairs and the swap creation responses follow the Boltz v2 API, as they are persisted
+//! with the swaps and used to build the swap scripts.
+
+use std::collections::HashMap;
+
+use boltz_client::bitcoin::hashes::sha256;
+use lwk_wollet::secp256k1;
+
+pub use boltz_client::{
+    boltz::{
+        ChainPair, ChainSwapStates, CreateChainResponse, CreateReverseResponse,
+        CreateSubmarineResponse, RevSwapStates, ReversePair, SubSwapStates, SubmarinePair,
+    },
+    network::Chain,
+    Amount, PublicKey,
+};
+
+/// A webhook the provider calls when a swap reaches one of the given `status` states
+#[derive(Clone, Debug)]
+pub struct Webhook<T> {
+    pub url: String,
+    pub hash_swap_id: Option<bool>,
+    pub status: Option<Vec<T>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateSubmarineRequest {
+    pub from: String,
+    pub to: String,
+    pub invoice: String,
+    pub refund_public_key: PublicKey,
+    pub pair_hash: Option<String>,
+    pub referral_id: Option<String>,
+    pub webhook: Option<Webhook<SubSwapStates>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateReverseRequest {
+    pub from: String,
+    pub to: String,
+    pub invoice: Option<String>,
+    pub invoice_amount: Option<u64>,
+    pub preimage_hash: Option<sha256::Hash>,
+    pub claim_public_key: PublicKey,
+    pub description: Option<String>,
+    pub description_hash: Option<String>,
+    pub address: Option<String>,
+    pub address_signature: Option<String>,
+    pub referral_id: Option<String>,
+    pub webhook: Option<Webhook<RevSwapStates>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateChainRequest {
+    pub from: String,
+    pub to: String,
+    pub preimage_hash: sha256::Hash,
+    pub claim_public_key: Option<PublicKey>,
+    pub refund_public_key: Option<PublicKey>,
+    pub user_lock_amount: Option<u64>,
+    pub server_lock_amount: Option<u64>,
+    pub pair_hash: Option<String>,
+    pub referral_id: Option<String>,
+    pub webhook: Option<Webhook<ChainSwapStates>>,
+}
+
+/// The details needed to cooperatively let the provider claim a send swap
+#[derive(Clone, Debug)]
+pub struct SubmarineClaimTxResponse {
+    pub preimage: String,
+    pub pub_nonce: String,
+    pub public_key: PublicKey,
+    pub transaction_hash: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct GetBolt12FetchRequest {
+    pub offer: String,
+    pub amount: u64,
+    pub note: Option<String>,
+}
+
+/// A BIP21 URI signed by the receiver, which can be paid directly instead of through a swap
+#[derive(Clone, Debug)]
+pub struct MagicRoutingHint {
+    pub bip21: String,
+    pub signature: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct GetBolt12FetchResponse {
+    pub invoice: String,
+    pub magic_routing_hint: Option<MagicRoutingHint>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateBolt12OfferRequest {
+    pub offer: String,
+    pub url: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct UpdateBolt12OfferRequest {
+    pub offer: String,
+    pub url: Option<String>,
+    pub signature: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct GetBolt12ParamsResponse {
+    pub min_cltv: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct Node {
+    pub public_key: secp256k1::PublicKey,
+    pub uris: Vec<String>,
+}
+
+/// The Lightning nodes of the provider, by implementation
+#[derive(Clone, Debug)]
+pub struct GetNodesResponse {
+    pub btc: HashMap<String, Node>,
+}
+
+impl GetNodesResponse {
+    pub fn get_btc_cln_node(&self) -> Option<Node> {
+        self.btc.get("CLN").cloned()
+    }
+}
+
+/// A transaction reported in a [SwapStatus]
+#[derive(Clone, Debug, Default)]
+pub struct TransactionInfo {
+    pub id: String,
+    pub hex: Option<String>,
+}
+
+/// A swap status update
+#[derive(Clone, Debug, Default)]
+pub struct SwapStatus {
+    pub id: String,
+    pub status: String,
+    pub transaction: Option<TransactionInfo>,
+    pub zero_conf_rejected: Option<bool>,
+}
+
+/// A request to create an invoice for a BOLT12 offer
+#[derive(Clone, Debug)]
+pub struct InvoiceRequest {
+    pub id: String,
+    pub offer: String,
+    pub invoice_request: String,
+}
@@ -0,0 +1,732 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use log::warn;
+use tokio::sync::{broadcast, watch};
+use tokio_with_wasm::alias as tokio;
+
+use super::{
+    model::{
+        Amount, Chain, ChainPair, CreateBolt12OfferRequest, CreateChainRequest,
+        CreateChainResponse, CreateReverseRequest, CreateReverseResponse, CreateSubmarineRequest,
+        CreateSubmarineResponse, GetBolt12FetchRequest, GetBolt12FetchResponse,
+        GetBolt12ParamsResponse, GetNodesResponse, InvoiceRequest, ReversePair,
+        SubmarineClaimTxResponse, SubmarinePair, SwapStatus, UpdateBolt12OfferRequest,
+    },
+    SubscriptionHandler, Swapper, SwapperStatusStream, STATUS_STREAM_CHANNEL_CAPACITY,
+};
+use crate::{
+    error::{PaymentError, SdkError},
+    model::{Limits, SwapQuote},
+    persist::Persister,
+    prelude::{Direction, SendSwap, Swap, Transaction, Utxo},
+};
+
+/// The id of the swap provider the SDK is built with, which is Boltz unless a custom
+/// [Swapper] is set on the [LiquidSdkBuilder](crate::sdk::LiquidSdkBuilder)
+pub const DEFAULT_SWAP_PROVIDER: &str = "boltz";
+
+/// A swap provider, consisting of the [Swapper] used to create and settle its swaps and the
+/// [SwapperStatusStream] reporting their updates
+#[derive(Clone)]
+pub struct SwapProvider {
+    pub swapper: Arc<dyn Swapper>,
+    pub status_stream: Arc<dyn SwapperStatusStream>,
+}
+
+/// Holds the registered swap providers.
+///
+/// The registry itself acts as a [Swapper] and [SwapperStatusStream]: calls concerning an
+/// existing swap are routed to the provider the swap was created with, while all other calls
+/// are handled by the default provider. Swaps are created with a specific provider by
+/// selecting it via [SwapperRegistry::swapper].
+///
+/// When no provider is selected, new swaps are quoted by the default provider, falling back to
+/// the registered fallback instances in order if it cannot be reached.
+pub(crate) struct SwapperRegistry {
+    providers: BTreeMap<String, SwapProvider>,
+    /// The ids of the providers tried in order when no provider is selected
+    failover_order: Vec<String>,
+    persister: Arc<Persister>,
+    update_notifier: broadcast::Sender<SwapStatus>,
+    invoice_request_notifier: broadcast::Sender<InvoiceRequest>,
+}
+
+impl SwapperRegistry {
+    pub(crate) fn new(persister: Arc<Persister>, default_provider: SwapProvider) -> Self {
+        let (update_notifier, _) = broadcast::channel::<SwapStatus>(STATUS_STREAM_CHANNEL_CAPACITY);
+        let (invoice_request_notifier, _) =
+            broadcast::channel::<InvoiceRequest>(STATUS_STREAM_CHANNEL_CAPACITY);
+        Self {
+            providers: BTreeMap::from([(DEFAULT_SWAP_PROVIDER.to_string(), default_provider)]),
+            failover_order: vec![DEFAULT_SWAP_PROVIDER.to_string()],
+            persister,
+            update_notifier,
+            invoice_request_notifier,
+        }
+    }
+
+    pub(crate) fn register(&mut self, id: &str, provider: SwapProvider) -> Result<()> {
+        if self.providers.contains_key(id) {
+            bail!("Swap provider {id} is already registered");
+        }
+        self.providers.insert(id.to_string(), provider);
+        Ok(())
+    }
+
+    /// Registers a fallback instance of the default provider, which is used for new swaps
+    /// when the default provider and all previously registered fallbacks cannot be reached.
+    ///
+    /// The instance is registered under its API URL, which is persisted as the provider of
+    /// the swaps it creates. This keeps them pinned to it if the fallback list is reordered.
+    pub(crate) fn register_fallback(&mut self, url: &str, provider: SwapProvider) -> Result<()> {
+        let id = Self::fallback_id(url);
+        if self.failover_order.contains(&id) {
+            warn!("Swapper fallback URL {url} is configured more than once");
+            return Ok(());
+        }
+        self.register(&id, provider)?;
+        self.failover_order.push(id);
+        Ok(())
+    }
+
+    /// The provider id of the fallback instance with the given API URL
+    pub(crate) fn fallback_id(url: &str) -> String {
+        url.trim_end_matches('/').to_string()
+    }
+
+    fn default_provider(&self) -> &SwapProvider {
+        &self.providers[DEFAULT_SWAP_PROVIDER]
+    }
+
+    fn provider(&self, id: Option<&str>) -> Result<&SwapProvider, PaymentError> {
+        let id = id.unwrap_or(DEFAULT_SWAP_PROVIDER);
+        self.providers
+            .get(id)
+            .ok_or_else(|| PaymentError::generic(format!("Unknown swap provider: {id}")))
+    }
+
+    /// Returns the provider a swap was created with, falling back to the default provider
+    fn provider_of(&self, swap: &Swap) -> &SwapProvider {
+        self.provider(swap.swap_provider()).unwrap_or_else(|_| {
+            warn!(
+                "Swap provider of swap {} is not registered, using the default provider",
+                swap.id()
+            );
+            self.default_provider()
+        })
+    }
+
+    fn provider_of_swap_id(&self, swap_id: &str) -> &SwapProvider {
+        match self.persister.fetch_swap_by_id(swap_id) {
+            Ok(swap) => self.provider_of(&swap),
+            Err(_) => self.default_provider(),
+        }
+    }
+
+    /// Resolves the id of a swap provider, which defaults to [DEFAULT_SWAP_PROVIDER]
+    pub(crate) fn resolve_id(&self, id: Option<&str>) -> Result<String, PaymentError> {
+        let id = id.unwrap_or(DEFAULT_SWAP_PROVIDER);
+        self.provider(Some(id))?;
+        Ok(id.to_string())
+    }
+
+    /// Returns the [Swapper] of a registered provider, or of the default one if `id` is not set
+    pub(crate) fn swapper(&self, id: Option<&str>) -> Result<Arc<dyn Swapper>, PaymentError> {
+        Ok(self.provider(id)?.swapper.clone())
+    }
+
+    /// Runs `f` against the selected provider. If no provider is selected, the providers in the
+    /// failover order are tried until one of them succeeds.
+    ///
+    /// Returns the id of the provider that succeeded along with the result.
+    async fn with_failover<T, F, Fut>(
+        &self,
+        id: Option<&str>,
+        f: F,
+    ) -> Result<(String, T), PaymentError>
+    where
+        F: Fn(Arc<dyn Swapper>) -> Fut,
+        Fut: Future<Output = Result<Option<T>, PaymentError>>,
+    {
+        let ids = match id {
+            Some(id) => vec![self.resolve_id(Some(id))?],
+            None => self.failover_order.clone(),
+        };
+        let mut last_err = PaymentError::PairsNotFound;
+        for id in ids {
+            match f(self.providers[&id].swapper.clone()).await {
+                Ok(Some(res)) => return Ok((id, res)),
+                Ok(None) => {
+                    warn!("Swap provider {id} returned no pair");
+                    last_err = PaymentError::PairsNotFound;
+                }
+                Err(e) => {
+                    warn!("Swap provider {id} is unavailable: {e}");
+                    last_err = e;
+                }
+            }
+        }
+        Err(last_err)
+    }
+
+    /// Fetches the submarine pair of the selected provider, or of the first available one
+    pub(crate) async fn get_submarine_pair(
+        &self,
+        id: Option<&str>,
+    ) -> Result<(String, SubmarinePair), PaymentError> {
+        self.with_failover(
+            id,
+            |swapper| async move { swapper.get_submarine_pairs().await },
+        )
+        .await
+    }
+
+    /// Fetches the reverse pair of the selected provider, or of the first available one
+    pub(crate) async fn get_reverse_pair(
+        &self,
+        id: Option<&str>,
+    ) -> Result<(String, ReversePair), PaymentError> {
+        self.with_failover(id, |swapper| async move {
+            swapper.get_reverse_swap_pairs().await
+        })
+        .await
+    }
+
+    /// Fetches the chain pair of the selected provider, or of the first available one
+    pub(crate) async fn get_chain_pair(
+        &self,
+        id: Option<&str>,
+        direction: Direction,
+    ) -> Result<(String, ChainPair), PaymentError> {
+        self.with_failover(id, |swapper| async move {
+            swapper.get_chain_pair(direction).await
+        })
+        .await
+    }
+
+    /// Fetches the quotes of all providers for Lightning sends. Providers that fail to quote
+    /// are left out.
+    pub(crate) async fn fetch_submarine_quotes(&self, amount_sat: Option<u64>) -> Vec<SwapQuote> {
+        let mut quotes = vec![];
+        for (id, provider) in &self.providers {
+            match provider.swapper.get_submarine_pairs().await {
+                Ok(Some(pair)) => quotes.push(submarine_quote(id, &pair, amount_sat)),
+                Ok(None) => {}
+                Err(e) => warn!("Failed to fetch submarine pairs from swap provider {id}: {e}"),
+            }
+        }
+        quotes
+    }
+
+    /// Fetches the quotes of all providers for Lightning receives. Providers that fail to quote
+    /// are left out.
+    pub(crate) async fn fetch_reverse_quotes(
+        &self,
+        amount_sat: Option<u64>,
+        max_zero_conf_sat: u64,
+    ) -> Vec<SwapQuote> {
+        let mut quotes = vec![];
+        for (id, provider) in &self.providers {
+            match provider.swapper.get_reverse_swap_pairs().await {
+                Ok(Some(pair)) => {
+                    quotes.push(reverse_quote(id, &pair, amount_sat, max_zero_conf_sat))
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Failed to fetch reverse pairs from swap provider {id}: {e}"),
+            }
+        }
+        quotes
+    }
+
+    /// Fetches the quotes of all providers for chain swaps in the given direction. Providers
+    /// that fail to quote are left out.
+    pub(crate) async fn fetch_chain_quotes(
+        &self,
+        direction: Direction,
+        amount_sat: Option<u64>,
+    ) -> Vec<SwapQuote> {
+        let mut quotes = vec![];
+        for (id, provider) in &self.providers {
+            match provider.swapper.get_chain_pair(direction).await {
+                Ok(Some(pair)) => quotes.push(chain_quote(id, &pair, amount_sat)),
+                Ok(None) => {}
+                Err(e) => warn!("Failed to fetch chain pairs from swap provider {id}: {e}"),
+            }
+        }
+        quotes
+    }
+
+    /// Forwards the updates and invoice requests of a provider. If updates were skipped because
+    /// the registry lagged behind, the ongoing swaps are tracked again with `callback`, which
+    /// makes the providers resend their latest status.
+    fn forward_updates(
+        &self,
+        provider: &SwapProvider,
+        callback: Arc<dyn SubscriptionHandler>,
+        mut shutdown: watch::Receiver<()>,
+    ) {
+        let mut updates = provider.status_stream.subscribe_swap_updates();
+        let mut invoice_requests = provider.status_stream.subscribe_invoice_requests();
+        let update_notifier = self.update_notifier.clone();
+        let invoice_request_notifier = self.invoice_request_notifier.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    update = updates.recv() => match update {
+                        Ok(update) => {
+                            let _ = update_notifier.send(update);
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            warn!("Skipped {skipped} swap updates of a swap provider, tracking the swaps again");
+                            callback.track_subscriptions().await;
+                        }
+                        Err(broadcast::error::RecvError::Closed) => return,
+                    },
+                    request = invoice_requests.recv() => match request {
+                        Ok(request) => {
+                            let _ = invoice_request_notifier.send(request);
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            warn!("Skipped {skipped} invoice requests of a swap provider");
+                        }
+                        Err(broadcast::error::RecvError::Closed) => return,
+                    },
+                    _ = shutdown.changed() => return,
+                }
+            }
+        });
+    }
+}
+
+fn submarine_quote(id: &str, pair: &SubmarinePair, amount_sat: Option<u64>) -> SwapQuote {
+    SwapQuote {
+        swap_provider: id.to_string(),
+        limits: Limits {
+            min_sat: pair.limits.minimal_batched.unwrap_or(pair.limits.minimal),
+            max_sat: pair.limits.maximal,
+            max_zero_conf_sat: pair.limits.maximal_zero_conf,
+        },
+        fees_percentage: pair.fees.percentage,
+        fixed_fees_sat: pair.fees.total(0),
+        fees_sat: amount_sat
+            .filter(|amount_sat| pair.limits.within(*amount_sat).is_ok())
+            .map(|amount_sat| pair.fees.total(amount_sat)),
+    }
+}
+
+fn reverse_quote(
+    id: &str,
+    pair: &ReversePair,
+    amount_sat: Option<u64>,
+    max_zero_conf_sat: u64,
+) -> SwapQuote {
+    SwapQuote {
+        swap_provider: id.to_string(),
+        limits: Limits {
+            min_sat: pair.limits.minimal,
+            max_sat: pair.limits.maximal,
+            max_zero_conf_sat,
+        },
+        fees_percentage: pair.fees.percentage,
+        fixed_fees_sat: pair.fees.total(0),
+        fees_sat: amount_sat
+            .filter(|amount_sat| pair.limits.within(*amount_sat).is_ok())
+            .map(|amount_sat| pair.fees.total(amount_sat)),
+    }
+}
+
+fn chain_quote(id: &str, pair: &ChainPair, amount_sat: Option<u64>) -> SwapQuote {
+    let fixed_fees_sat = pair.fees.server() + pair.fees.claim_estimate();
+    SwapQuote {
+        swap_provider: id.to_string(),
+        limits: Limits {
+            min_sat: pair.limits.minimal,
+            max_sat: pair.limits.maximal,
+            max_zero_conf_sat: pair.limits.maximal_zero_conf,
+        },
+        fees_percentage: pair.fees.percentage,
+        fixed_fees_sat,
+        fees_sat: amount_sat
+            .filter(|amount_sat| pair.limits.within(*amount_sat).is_ok())
+            .map(|amount_sat| pair.fees.boltz(amount_sat) + fixed_fees_sat),
+    }
+}
+
+/// Lets the subscription handler be shared by the status streams of all providers
+struct SharedSubscriptionHandler(Arc<dyn SubscriptionHandler>);
+
+#[sdk_macros::async_trait]
+impl SubscriptionHandler for SharedSubscriptionHandler {
+    async fn track_subscriptions(&self) {
+        self.0.track_subscriptions().await
+    }
+}
+
+#[sdk_macros::async_trait]
+impl Swapper for SwapperRegistry {
+    async fn create_chain_swap(
+        &self,
+        req: CreateChainRequest,
+    ) -> Result<CreateChainResponse, PaymentError> {
+        self.default_provider().swapper.create_chain_swap(req).await
+    }
+
+    async fn create_send_swap(
+        &self,
+        req: CreateSubmarineRequest,
+    ) -> Result<CreateSubmarineResponse, PaymentError> {
+        self.default_provider().swapper.create_send_swap(req).await
+    }
+
+    async fn get_chain_pair(
+        &self,
+        direction: Direction,
+    ) -> Result<Option<ChainPair>, PaymentError> {
+        self.default_provider()
+            .swapper
+            .get_chain_pair(direction)
+            .await
+    }
+
+    async fn get_chain_pairs(
+        &self,
+    ) -> Result<(Option<ChainPair>, Option<ChainPair>), PaymentError> {
+        self.default_provider().swapper.get_chain_pairs().await
+    }
+
+    async fn get_zero_amount_chain_swap_quote(&self, swap_id: &str) -> Result<Amount, SdkError> {
+        self.provider_of_swap_id(swap_id)
+            .swapper
+            .get_zero_amount_chain_swap_quote(swap_id)
+            .await
+    }
+
+    async fn accept_zero_amount_chain_swap_quote(
+        &self,
+        swap_id: &str,
+        server_lockup_sat: u64,
+    ) -> Result<(), PaymentError> {
+        self.provider_of_swap_id(swap_id)
+            .swapper
+            .accept_zero_amount_chain_swap_quote(swap_id, server_lockup_sat)
+            .await
+    }
+
+    async fn get_submarine_pairs(&self) -> Result<Option<SubmarinePair>, PaymentError> {
+        self.default_provider().swapper.get_submarine_pairs().await
+    }
+
+    async fn get_submarine_preimage(&self, swap_id: &str) -> Result<String, PaymentError> {
+        self.provider_of_swap_id(swap_id)
+            .swapper
+            .get_submarine_preimage(swap_id)
+            .await
+    }
+
+    async fn get_send_claim_tx_details(
+        &self,
+        swap: &SendSwap,
+    ) -> Result<SubmarineClaimTxResponse, PaymentError> {
+        self.provider_of(&Swap::Send(swap.clone()))
+            .swapper
+            .get_send_claim_tx_details(swap)
+            .await
+    }
+
+    async fn claim_send_swap_cooperative(
+        &self,
+        swap: &SendSwap,
+        claim_tx_response: SubmarineClaimTxResponse,
+        refund_address: &str,
+    ) -> Result<(), PaymentError> {
+        self.provider_of(&Swap::Send(swap.clone()))
+            .swapper
+            .claim_send_swap_cooperative(swap, claim_tx_response, refund_address)
+            .await
+    }
+
+    async fn create_receive_swap(
+        &self,
+        req: CreateReverseRequest,
+    ) -> Result<CreateReverseResponse, PaymentError> {
+        self.default_provider()
+            .swapper
+            .create_receive_swap(req)
+            .await
+    }
+
+    async fn get_reverse_swap_pairs(&self) -> Result<Option<ReversePair>, PaymentError> {
+        self.default_provider()
+            .swapper
+            .get_reverse_swap_pairs()
+            .await
+    }
+
+    async fn create_claim_tx(
+        &self,
+        swap: Swap,
+        claim_address: Option<String>,
+        is_cooperative: bool,
+    ) -> Result<Transaction, PaymentError> {
+        self.provider_of(&swap)
+            .swapper
+            .create_claim_tx(swap, claim_address, is_cooperative)
+            .await
+    }
+
+    async fn estimate_refund_broadcast(
+        &self,
+        swap: Swap,
+        refund_address: &str,
+        fee_rate_sat_per_vb: Option<f64>,
+        is_cooperative: bool,
+    ) -> Result<(u32, u64), SdkError> {
+        self.provider_of(&swap)
+            .swapper
+            .estimate_refund_broadcast(swap, refund_address, fee_rate_sat_per_vb, is_cooperative)
+            .await
+    }
+
+    async fn create_refund_tx(
+        &self,
+        swap: Swap,
+        refund_address: &str,
+        utxos: Vec<Utxo>,
+        broadcast_fee_rate_sat_per_vb: Option<f64>,
+        is_cooperative: bool,
+    ) -> Result<Transaction, PaymentError> {
+        self.provider_of(&swap)
+            .swapper
+            .create_refund_tx(
+                swap,
+                refund_address,
+                utxos,
+                broadcast_fee_rate_sat_per_vb,
+                is_cooperative,
+            )
+            .await
+    }
+
+    async fn broadcast_tx(&self, chain: Chain, tx_hex: &str) -> Result<String, PaymentError> {
+        self.default_provider()
+            .swapper
+            .broadcast_tx(chain, tx_hex)
+            .await
+    }
+
+    async fn check_for_mrh(
+        &self,
+        invoice: &str,
+    ) -> Result<Option<(String, boltz_client::bitcoin::Amount)>, PaymentError> {
+        self.default_provider().swapper.check_for_mrh(invoice).await
+    }
+
+    async fn get_bolt12_info(
+        &self,
+        req: GetBolt12FetchRequest,
+    ) -> Result<GetBolt12FetchResponse, PaymentError> {
+        self.default_provider().swapper.get_bolt12_info(req).await
+    }
+
+    async fn create_bolt12_offer(&self, req: CreateBolt12OfferRequest) -> Result<(), SdkError> {
+        self.default_provider()
+            .swapper
+            .create_bolt12_offer(req)
+            .await
+    }
+
+    async fn update_bolt12_offer(&self, req: UpdateBolt12OfferRequest) -> Result<(), SdkError> {
+        self.default_provider()
+            .swapper
+            .update_bolt12_offer(req)
+            .await
+    }
+
+    async fn delete_bolt12_offer(&self, offer: &str, signature: &str) -> Result<(), SdkError> {
+        self.default_provider()
+            .swapper
+            .delete_bolt12_offer(offer, signature)
+            .await
+    }
+
+    async fn get_bolt12_params(&self) -> Result<GetBolt12ParamsResponse, PaymentError> {
+        self.default_provider().swapper.get_bolt12_params().await
+    }
+
+    async fn get_nodes(&self) -> Result<GetNodesResponse, PaymentError> {
+        self.default_provider().swapper.get_nodes().await
+    }
+}
+
+impl SwapperStatusStream for SwapperRegistry {
+    fn start(
+        self: Arc<Self>,
+        callback: Box<dyn SubscriptionHandler>,
+        shutdown: watch::Receiver<()>,
+    ) {
+        // With a single provider, its updates are subscribed to directly
+        if self.providers.len() == 1 {
+            self.default_provider()
+                .status_stream
+                .clone()
+                .start(callback, shutdown);
+            return;
+        }
+
+        let callback: Arc<dyn SubscriptionHandler> = Arc::from(callback);
+        for provider in self.providers.values() {
+            self.forward_updates(provider, callback.clone(), shutdown.clone());
+            provider.status_stream.clone().start(
+                Box::new(SharedSubscriptionHandler(callback.clone())),
+                shutdown.clone(),
+            );
+        }
+    }
+
+    fn track_swap_id(&self, swap_id: &str) -> Result<()> {
+        self.provider_of_swap_id(swap_id)
+            .status_stream
+            .track_swap_id(swap_id)
+    }
+
+    fn track_offer(&self, offer: &str, signature: &str) -> Result<()> {
+        self.default_provider()
+            .status_stream
+            .track_offer(offer, signature)
+    }
+
+    fn send_invoice_created(&self, id: &str, invoice: &str) -> Result<()> {
+        self.default_provider()
+            .status_stream
+            .send_invoice_created(id, invoice)
+    }
+
+    fn send_invoice_error(&self, id: &str, error: &str) -> Result<()> {
+        self.default_provider()
+            .status_stream
+            .send_invoice_error(id, error)
+    }
+
+    fn subscribe_swap_updates(&self) -> broadcast::Receiver<SwapStatus> {
+        match self.providers.len() {
+            1 => self
+                .default_provider()
+                .status_stream
+                .subscribe_swap_updates(),
+            _ => self.update_notifier.subscribe(),
+        }
+    }
+
+    fn subscribe_invoice_requests(&self) -> broadcast::Receiver<InvoiceRequest> {
+        match self.providers.len() {
+            1 => self
+                .default_provider()
+                .status_stream
+                .subscribe_invoice_requests(),
+            _ => self.invoice_request_notifier.subscribe(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use anyhow::Result;
+
+    use super::{SwapProvider, SwapperRegistry, DEFAULT_SWAP_PROVIDER};
+    use crate::{
+        model::Direction,
+        test_utils::{
+            persist::create_persister, status_stream::MockStatusStream, swapper::MockSwapper,
+        },
+    };
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    fn mock_provider() -> SwapProvider {
+        SwapProvider {
+            swapper: Arc::new(MockSwapper::default()),
+            status_stream: Arc::new(MockStatusStream::new()),
+        }
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_registry_quotes_every_provider() -> Result<()> {
+        create_persister!(persister);
+        let mut registry = SwapperRegistry::new(persister, mock_provider());
+        registry.register("other", mock_provider())?;
+        assert!(registry.register("other", mock_provider()).is_err());
+
+        assert_eq!(registry.resolve_id(None)?, DEFAULT_SWAP_PROVIDER);
+        assert_eq!(registry.resolve_id(Some("other"))?, "other");
+        assert!(registry.resolve_id(Some("unknown")).is_err());
+
+        let quotes = registry.fetch_submarine_quotes(Some(50_000)).await;
+        assert_eq!(
+            quotes
+                .iter()
+                .map(|q| q.swap_provider.as_str())
+                .collect::<Vec<_>>(),
+            vec![DEFAULT_SWAP_PROVIDER, "other"]
+        );
+        assert!(quotes.iter().all(|q| q.fees_sat.is_some()));
+
+        let quotes = registry.fetch_reverse_quotes(None, 1_000).await;
+        assert_eq!(quotes.len(), 2);
+        assert!(quotes.iter().all(|q| q.fees_sat.is_none()));
+
+        let quotes = registry
+            .fetch_chain_quotes(Direction::Outgoing, Some(50_000))
+            .await;
+        assert_eq!(quotes.len(), 2);
+
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_registry_fails_over_to_fallbacks() -> Result<()> {
+        create_persister!(persister);
+        let default_swapper = Arc::new(MockSwapper::default());
+        let mut registry = SwapperRegistry::new(
+            persister,
+            SwapProvider {
+                swapper: default_swapper.clone(),
+                status_stream: Arc::new(MockStatusStream::new()),
+            },
+        );
+        registry.register("other", mock_provider())?;
+        registry.register_fallback("https://boltz.example/v2/", mock_provider())?;
+        registry.register_fallback("https://boltz.example/v2", mock_provider())?;
+        let fallback_id = SwapperRegistry::fallback_id("https://boltz.example/v2");
+        assert_eq!(fallback_id, "https://boltz.example/v2");
+
+        let (id, _) = registry.get_submarine_pair(None).await?;
+        assert_eq!(id, DEFAULT_SWAP_PROVIDER);
+
+        default_swapper.set_unavailable(true);
+        let (id, _) = registry.get_submarine_pair(None).await?;
+        assert_eq!(id, fallback_id);
+        let (id, _) = registry.get_reverse_pair(None).await?;
+        assert_eq!(id, fallback_id);
+        let (id, _) = registry.get_chain_pair(None, Direction::Incoming).await?;
+        assert_eq!(id, fallback_id);
+
+        // Swaps created by the fallback are routed back to it by URL
+        assert!(registry.swapper(Some(&fallback_id)).is_ok());
+
+        // An explicitly selected provider is never failed over
+        assert!(registry
+            .get_submarine_pair(Some(DEFAULT_SWAP_PROVIDER))
+            .await
+            .is_err());
+        let (id, _) = registry.get_submarine_pair(Some("other")).await?;
+        assert_eq!(id, "other");
+
+        Ok(())
+    }
+}
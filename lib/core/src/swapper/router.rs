@@ -0,0 +1,436 @@
+use std::collections::HashMap;
+use std::sync::{
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    Arc, Mutex, Weak,
+};
+
+use anyhow::Result;
+use log::{debug, info, warn};
+use tokio::sync::{broadcast, watch};
+use tokio_with_wasm::alias as tokio;
+
+use super::{
+    model::{InvoiceRequest, SwapStatus},
+    SubscriptionHandler, SwapperStatusStream, STATUS_STREAM_CHANNEL_CAPACITY,
+    TERMINAL_SWAP_STATUSES,
+};
+
+/// Shares one [SwapperStatusStream] connection between the SDKs of a
+/// [SdkRuntime](crate::runtime::SdkRuntime).
+///
+/// Each SDK gets its own [WalletStatusStream], which remembers the swaps and offers it tracks.
+/// The updates and invoice requests received on the shared connection are then only routed
+/// to the SDK that tracked them.
+pub(crate) struct StatusStreamRouter {
+    inner: Arc<dyn SwapperStatusStream>,
+    routes: Mutex<Routes>,
+    next_wallet_id: AtomicU64,
+    is_started: AtomicBool,
+    shutdown: watch::Receiver<()>,
+}
+
+#[derive(Default)]
+struct Routes {
+    wallets: HashMap<u64, WalletRoute>,
+    /// The wallet tracking each swap id
+    swap_owners: HashMap<String, u64>,
+    /// The wallet tracking each BOLT12 offer
+    offer_owners: HashMap<String, u64>,
+}
+
+struct WalletRoute {
+    callback: Arc<dyn SubscriptionHandler>,
+    update_notifier: broadcast::Sender<SwapStatus>,
+    invoice_request_notifier: broadcast::Sender<InvoiceRequest>,
+}
+
+impl StatusStreamRouter {
+    pub(crate) fn new(inner: Arc<dyn SwapperStatusStream>, shutdown: watch::Receiver<()>) -> Self {
+        Self {
+            inner,
+            routes: Mutex::new(Routes::default()),
+            next_wallet_id: AtomicU64::new(0),
+            is_started: AtomicBool::new(false),
+            shutdown,
+        }
+    }
+
+    /// Creates the status stream of a new wallet, which is routed to once started
+    pub(crate) fn wallet_stream(self: &Arc<Self>) -> WalletStatusStream {
+        let wallet_id = self.next_wallet_id.fetch_add(1, Ordering::Relaxed);
+        let (update_notifier, _) = broadcast::channel::<SwapStatus>(STATUS_STREAM_CHANNEL_CAPACITY);
+        let (invoice_request_notifier, _) =
+            broadcast::channel::<InvoiceRequest>(STATUS_STREAM_CHANNEL_CAPACITY);
+        WalletStatusStream {
+            router: self.clone(),
+            wallet_id,
+            update_notifier,
+            invoice_request_notifier,
+        }
+    }
+
+    /// The number of started wallets currently using the shared stream
+    pub(crate) fn wallet_count(&self) -> usize {
+        self.lock_routes().wallets.len()
+    }
+
+    fn lock_routes(&self) -> std::sync::MutexGuard<'_, Routes> {
+        self.routes.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Starts the shared stream, unless a previous wallet already did. Returns whether it was
+    /// already running.
+    fn start_once(self: &Arc<Self>) -> bool {
+        if self.is_started.swap(true, Ordering::SeqCst) {
+            return true;
+        }
+
+        self.forward_updates();
+        self.inner.clone().start(
+            Box::new(RoutedSubscriptionHandler(Arc::downgrade(self))),
+            self.shutdown.clone(),
+        );
+        false
+    }
+
+    /// Routes the updates and invoice requests of the shared stream. If updates were skipped
+    /// because the router lagged behind, all wallets track their swaps again, which makes the
+    /// swapper resend their latest status.
+    fn forward_updates(self: &Arc<Self>) {
+        let mut updates = self.inner.subscribe_swap_updates();
+        let mut invoice_requests = self.inner.subscribe_invoice_requests();
+        let mut shutdown = self.shutdown.clone();
+        let router = Arc::downgrade(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    update = updates.recv() => match (update, router.upgrade()) {
+                        (Ok(update), Some(router)) => router.route_update(update),
+                        (Err(broadcast::error::RecvError::Lagged(skipped)), Some(router)) => {
+                            warn!("Skipped {skipped} swap updates of the shared status stream, tracking the swaps again");
+                            router.track_subscriptions().await;
+                        }
+                        _ => return,
+                    },
+                    request = invoice_requests.recv() => match (request, router.upgrade()) {
+                        (Ok(request), Some(router)) => router.route_invoice_request(request),
+                        (Err(broadcast::error::RecvError::Lagged(skipped)), Some(_)) => {
+                            warn!("Skipped {skipped} invoice requests of the shared status stream");
+                        }
+                        _ => return,
+                    },
+                    _ = shutdown.changed() => return,
+                }
+            }
+        });
+    }
+
+    fn route_update(&self, update: SwapStatus) {
+        let mut routes = self.lock_routes();
+        let Some(route) = routes
+            .swap_owners
+            .get(&update.id)
+            .and_then(|wallet_id| routes.wallets.get(wallet_id))
+        else {
+            debug!("No wallet is tracking swap {}", update.id);
+            return;
+        };
+        let _ = route.update_notifier.send(update.clone());
+
+        if TERMINAL_SWAP_STATUSES.contains(&update.status.as_str()) {
+            routes.swap_owners.remove(&update.id);
+        }
+    }
+
+    fn route_invoice_request(&self, request: InvoiceRequest) {
+        let routes = self.lock_routes();
+        let Some(route) = routes
+            .offer_owners
+            .get(&request.offer)
+            .and_then(|wallet_id| routes.wallets.get(wallet_id))
+        else {
+            debug!("No wallet is tracking offer {}", request.offer);
+            return;
+        };
+        let _ = route.invoice_request_notifier.send(request);
+    }
+
+    /// Calls the subscription handlers of all started wallets, after the shared stream
+    /// (re)connected. The owners of the swaps and offers are kept, and overwritten as the
+    /// wallets track them again.
+    async fn track_subscriptions(&self) {
+        let callbacks: Vec<Arc<dyn SubscriptionHandler>> = self
+            .lock_routes()
+            .wallets
+            .values()
+            .map(|route| route.callback.clone())
+            .collect();
+        for callback in callbacks {
+            callback.track_subscriptions().await;
+        }
+    }
+
+    fn remove_wallet(&self, wallet_id: u64) {
+        let mut routes = self.lock_routes();
+        routes.wallets.remove(&wallet_id);
+        routes.swap_owners.retain(|_, owner| *owner != wallet_id);
+        routes.offer_owners.retain(|_, owner| *owner != wallet_id);
+        info!("Removed wallet {wallet_id} from the shared status stream");
+    }
+}
+
+struct RoutedSubscriptionHandler(Weak<StatusStreamRouter>);
+
+#[sdk_macros::async_trait]
+impl SubscriptionHandler for RoutedSubscriptionHandler {
+    async fn track_subscriptions(&self) {
+        if let Some(router) = self.0.upgrade() {
+            router.track_subscriptions().await
+        }
+    }
+}
+
+/// The status stream of a single wallet on a shared [StatusStreamRouter]
+pub(crate) struct WalletStatusStream {
+    router: Arc<StatusStreamRouter>,
+    wallet_id: u64,
+    update_notifier: broadcast::Sender<SwapStatus>,
+    invoice_request_notifier: broadcast::Sender<InvoiceRequest>,
+}
+
+impl SwapperStatusStream for WalletStatusStream {
+    fn start(
+        self: Arc<Self>,
+        callback: Box<dyn SubscriptionHandler>,
+        mut shutdown: watch::Receiver<()>,
+    ) {
+        let callback: Arc<dyn SubscriptionHandler> = Arc::from(callback);
+        self.router.lock_routes().wallets.insert(
+            self.wallet_id,
+            WalletRoute {
+                callback: callback.clone(),
+                update_notifier: self.update_notifier.clone(),
+                invoice_request_notifier: self.invoice_request_notifier.clone(),
+            },
+        );
+
+        // If the shared stream is already connected, the wallet's subscriptions are tracked
+        // right away instead of on the next reconnection
+        if self.router.start_once() {
+            tokio::spawn(async move { callback.track_subscriptions().await });
+        }
+
+        tokio::spawn(async move {
+            let _ = shutdown.changed().await;
+            self.router.remove_wallet(self.wallet_id);
+        });
+    }
+
+    fn track_swap_id(&self, swap_id: &str) -> Result<()> {
+        self.router
+            .lock_routes()
+            .swap_owners
+            .insert(swap_id.to_string(), self.wallet_id);
+        self.router.inner.track_swap_id(swap_id)
+    }
+
+    fn track_offer(&self, offer: &str, signature: &str) -> Result<()> {
+        self.router
+            .lock_routes()
+            .offer_owners
+            .insert(offer.to_string(), self.wallet_id);
+        self.router.inner.track_offer(offer, signature)
+    }
+
+    fn send_invoice_created(&self, id: &str, invoice: &str) -> Result<()> {
+        self.router.inner.send_invoice_created(id, invoice)
+    }
+
+    fn send_invoice_error(&self, id: &str, error: &str) -> Result<()> {
+        self.router.inner.send_invoice_error(id, error)
+    }
+
+    fn subscribe_swap_updates(&self) -> broadcast::Receiver<SwapStatus> {
+        self.update_notifier.subscribe()
+    }
+
+    fn subscribe_invoice_requests(&self) -> broadcast::Receiver<InvoiceRequest> {
+        self.invoice_request_notifier.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use anyhow::Result;
+    use tokio::sync::watch;
+    use tokio_with_wasm::alias as tokio;
+
+    use super::StatusStreamRouter;
+    use crate::{
+        swapper::{model::SwapStatus, SubscriptionHandler, SwapperStatusStream},
+        test_utils::status_stream::MockStatusStream,
+    };
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    struct NoopSubscriptionHandler;
+
+    #[sdk_macros::async_trait]
+    impl SubscriptionHandler for NoopSubscriptionHandler {
+        async fn track_subscriptions(&self) {}
+    }
+
+    struct CountingSubscriptionHandler(Arc<AtomicUsize>);
+
+    #[sdk_macros::async_trait]
+    impl SubscriptionHandler for CountingSubscriptionHandler {
+        async fn track_subscriptions(&self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_router_routes_updates_per_wallet() -> Result<()> {
+        let inner = Arc::new(MockStatusStream::new());
+        let (_shutdown_sender, shutdown_receiver) = watch::channel(());
+        let router = Arc::new(StatusStreamRouter::new(
+            inner.clone(),
+            shutdown_receiver.clone(),
+        ));
+
+        let (wallet_shutdown_sender, wallet_shutdown_receiver) = watch::channel(());
+        let wallet_a = Arc::new(router.wallet_stream());
+        let wallet_b = Arc::new(router.wallet_stream());
+        // Wallets are only routed to once started
+        assert_eq!(router.wallet_count(), 0);
+        wallet_a
+            .clone()
+            .start(Box::new(NoopSubscriptionHandler), wallet_shutdown_receiver);
+        wallet_b
+            .clone()
+            .start(Box::new(NoopSubscriptionHandler), shutdown_receiver);
+        assert_eq!(router.wallet_count(), 2);
+
+        let mut updates_a = wallet_a.subscribe_swap_updates();
+        let mut updates_b = wallet_b.subscribe_swap_updates();
+        wallet_a.track_swap_id("swap-a")?;
+        wallet_b.track_swap_id("swap-b")?;
+
+        for id in ["swap-a", "swap-b", "unknown"] {
+            inner
+                .clone()
+                .send_mock_update(SwapStatus {
+                    id: id.to_string(),
+                    ..Default::default()
+                })
+                .await?;
+        }
+
+        let update = tokio::time::timeout(Duration::from_secs(1), updates_a.recv()).await??;
+        assert_eq!(update.id, "swap-a");
+        let update = tokio::time::timeout(Duration::from_secs(1), updates_b.recv()).await??;
+        assert_eq!(update.id, "swap-b");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(updates_a.try_recv().is_err());
+        assert!(updates_b.try_recv().is_err());
+
+        // A wallet that shut down is no longer routed to
+        wallet_shutdown_sender.send(())?;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(router.wallet_count(), 1);
+
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_router_keeps_swap_owners_until_terminal() -> Result<()> {
+        let inner = Arc::new(MockStatusStream::new());
+        let (_shutdown_sender, shutdown_receiver) = watch::channel(());
+        let router = Arc::new(StatusStreamRouter::new(
+            inner.clone(),
+            shutdown_receiver.clone(),
+        ));
+
+        let wallet = Arc::new(router.wallet_stream());
+        wallet
+            .clone()
+            .start(Box::new(NoopSubscriptionHandler), shutdown_receiver);
+        let mut updates = wallet.subscribe_swap_updates();
+        wallet.track_swap_id("swap")?;
+
+        // The swaps are still routed after the shared stream reconnected
+        router.track_subscriptions().await;
+        for status in [
+            "transaction.mempool",
+            "transaction.claimed",
+            "invoice.settled",
+        ] {
+            inner
+                .clone()
+                .send_mock_update(SwapStatus {
+                    id: "swap".to_string(),
+                    status: status.to_string(),
+                    ..Default::default()
+                })
+                .await?;
+        }
+
+        let update = tokio::time::timeout(Duration::from_secs(1), updates.recv()).await??;
+        assert_eq!(update.status, "transaction.mempool");
+        let update = tokio::time::timeout(Duration::from_secs(1), updates.recv()).await??;
+        assert_eq!(update.status, "transaction.claimed");
+        // The swap is no longer routed once it reached a terminal state
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(updates.try_recv().is_err());
+        assert!(router.lock_routes().swap_owners.is_empty());
+
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_router_tracks_swaps_again_after_lagging() -> Result<()> {
+        let inner = Arc::new(MockStatusStream::new());
+        let (_shutdown_sender, shutdown_receiver) = watch::channel(());
+        let router = Arc::new(StatusStreamRouter::new(
+            inner.clone(),
+            shutdown_receiver.clone(),
+        ));
+
+        let track_count = Arc::new(AtomicUsize::new(0));
+        let wallet = Arc::new(router.wallet_stream());
+        wallet.clone().start(
+            Box::new(CountingSubscriptionHandler(track_count.clone())),
+            shutdown_receiver,
+        );
+        wallet.track_swap_id("swap")?;
+
+        // More updates than the shared stream buffers arrive before they are routed
+        for _ in 0..100 {
+            let _ = inner.update_notifier.send(SwapStatus {
+                id: "swap".to_string(),
+                status: "transaction.mempool".to_string(),
+                ..Default::default()
+            });
+        }
+
+        // The skipped updates are recovered by tracking the swaps again
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while track_count.load(Ordering::SeqCst) == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await?;
+        assert_eq!(track_count.load(Ordering::SeqCst), 1);
+
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use anyhow::{anyhow, Error, Result};
 
 use log::debug;
@@ -104,6 +106,63 @@ impl SyncerClient for BreezSyncerClient {
     }
 }
 
+/// A [SyncerClient] sharing one connection between the SDKs of a
+/// [SdkRuntime](crate::runtime::SdkRuntime).
+///
+/// The connection is opened by the first SDK that connects and stays open when an SDK
+/// disconnects, until the runtime itself shuts down.
+#[derive(Clone)]
+pub(crate) struct SharedSyncerClient {
+    inner: Arc<dyn SyncerClient>,
+    connected_url: Arc<Mutex<Option<String>>>,
+}
+
+impl SharedSyncerClient {
+    pub(crate) fn new(inner: Arc<dyn SyncerClient>) -> Self {
+        Self {
+            inner,
+            connected_url: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Closes the shared connection
+    pub(crate) async fn shutdown(&self) -> Result<()> {
+        let mut connected_url = self.connected_url.lock().await;
+        *connected_url = None;
+        self.inner.disconnect().await
+    }
+}
+
+#[sdk_macros::async_trait]
+impl SyncerClient for SharedSyncerClient {
+    async fn connect(&self, connect_url: String) -> Result<()> {
+        let mut connected_url = self.connected_url.lock().await;
+        if connected_url.as_ref() == Some(&connect_url) {
+            return Ok(());
+        }
+        self.inner.connect(connect_url.clone()).await?;
+        *connected_url = Some(connect_url);
+        Ok(())
+    }
+
+    async fn push(&self, req: SetRecordRequest) -> Result<SetRecordReply> {
+        self.inner.push(req).await
+    }
+
+    async fn pull(&self, req: ListChangesRequest) -> Result<ListChangesReply> {
+        self.inner.pull(req).await
+    }
+
+    async fn listen(&self, req: ListenChangesRequest) -> Result<Streaming<Notification>> {
+        self.inner.listen(req).await
+    }
+
+    async fn disconnect(&self) -> Result<()> {
+        // The connection is still used by the other SDKs of the runtime
+        Ok(())
+    }
+}
+
 #[derive(Clone)]
 pub struct ApiKeyInterceptor {
     api_key_metadata: Option<MetadataValue<Ascii>>,
@@ -17,13 +17,14 @@ use self::model::{ListenChangesRequest, Notification, SyncOutgoingChanges};
 use crate::prelude::Swap;
 use crate::recover::recoverer::Recoverer;
 use crate::sync::model::data::{
-    Bolt12OfferSyncData, ChainSyncData, PaymentDetailsSyncData, ReceiveSyncData, SendSyncData,
+    Bolt12OfferSyncData, ChainSyncData, PaymentDetailsSyncData, PegSyncData, ReceiveSyncData,
+    SendSyncData,
 };
 use crate::sync::model::{DecryptionInfo, Record, SetRecordRequest, SetRecordStatus};
 use crate::utils;
 use crate::{
     persist::{cache::KEY_LAST_DERIVATION_INDEX, Persister},
-    prelude::Signer,
+    prelude::AsyncSigner,
 };
 
 pub(crate) mod client;
@@ -47,7 +48,7 @@ pub struct SyncService {
     client_id: String,
     persister: std::sync::Arc<Persister>,
     recoverer: Arc<Recoverer>,
-    signer: Arc<Box<dyn Signer>>,
+    signer: Arc<dyn AsyncSigner>,
     client: Box<dyn SyncerClient>,
     subscription_notifier: broadcast::Sender<Event>,
 }
@@ -57,7 +58,7 @@ impl SyncService {
         remote_url: String,
         persister: std::sync::Arc<Persister>,
         recoverer: Arc<Recoverer>,
-        signer: Arc<Box<dyn Signer>>,
+        signer: Arc<dyn AsyncSigner>,
         client: Box<dyn SyncerClient>,
     ) -> Self {
         let client_id = uuid::Uuid::new_v4().to_string();
@@ -133,7 +134,7 @@ impl SyncService {
     }
 
     async fn new_listener(&self) -> Result<Streaming<Notification>> {
-        let req = ListenChangesRequest::new(self.signer.clone())?;
+        let req = ListenChangesRequest::new(self.signer.clone()).await?;
         self.client.listen(req).await
     }
 
@@ -264,6 +265,11 @@ impl SyncService {
                     *last_commit_time,
                 )
             }
+            SyncData::Peg(peg_data) => self.persister.commit_incoming_peg(
+                peg_data.into(),
+                new_sync_state,
+                *last_commit_time,
+            ),
         }
     }
 
@@ -315,6 +321,14 @@ impl SyncService {
                     .into();
                 SyncData::Bolt12Offer(bolt12_offer_data)
             }
+            RecordType::Peg => {
+                let peg_data: PegSyncData = self
+                    .persister
+                    .fetch_peg_by_id(data_id)?
+                    .ok_or(anyhow!("Could not find Peg {data_id}"))?
+                    .into();
+                SyncData::Peg(peg_data)
+            }
         };
         Ok(data)
     }
@@ -329,6 +343,7 @@ impl SyncService {
             .latest_revision
             .unwrap_or(0);
         let req = ListChangesRequest::new(local_latest_revision, self.signer.clone())
+            .await
             .map_err(PullError::signing)?;
         let incoming_records = self
             .client
@@ -384,7 +399,7 @@ impl SyncService {
         }
 
         // Step 5: Decrypt the incoming record
-        let mut decrypted_record = new_record.decrypt(self.signer.clone())?;
+        let mut decrypted_record = new_record.decrypt(self.signer.clone()).await?;
 
         // Step 6: Merge with outgoing records, if present
         let maybe_outgoing_changes = self
@@ -586,7 +601,7 @@ impl SyncService {
         trace!("realtime-sync: Got sync data: {sync_data:?}");
 
         // Step 3: Create the record to push outwards
-        let record = Record::new(sync_data, record_revision, self.signer.clone())?;
+        let record = Record::new(sync_data, record_revision, self.signer.clone()).await?;
         trace!("realtime-sync: Got record: {record:?}");
 
         // Step 4: Push the record
@@ -596,6 +611,7 @@ impl SyncService {
             self.signer.clone(),
             self.client_id.clone(),
         )
+        .await
         .map_err(PushError::signing)?;
         trace!("realtime-sync: Got set record request: {req:?}");
         let reply = self.client.push(req).await.map_err(PushError::network)?;
@@ -680,7 +696,8 @@ mod tests {
 
     use crate::{
         persist::{cache::KEY_LAST_DERIVATION_INDEX, Persister},
-        prelude::{Direction, PaymentState, Signer},
+        prelude::{AsyncSigner, Direction, PaymentState, Signer},
+        signer::SyncSignerAdapter,
         sync::model::{data::LAST_DERIVATION_INDEX_DATA_ID, SyncState},
         test_utils::{
             chain_swap::new_chain_swap,
@@ -703,6 +720,7 @@ mod tests {
     async fn test_incoming_sync_create_and_update() -> Result<()> {
         create_persister!(persister);
         let signer: Arc<Box<dyn Signer>> = Arc::new(Box::new(MockSigner::new()?));
+        let async_signer: Arc<dyn AsyncSigner> = Arc::new(SyncSignerAdapter::new(signer.clone()));
         let swapper = Arc::new(MockSwapper::new());
         let onchain_wallet = Arc::new(MockWallet::new(signer.clone())?);
         let recoverer = Arc::new(new_recoverer(
@@ -719,9 +737,9 @@ mod tests {
             SyncData::Chain(new_chain_sync_data(None)),
         ];
         let incoming_records = vec![
-            Record::new(sync_data[0].clone(), 1, signer.clone())?,
-            Record::new(sync_data[1].clone(), 2, signer.clone())?,
-            Record::new(sync_data[2].clone(), 3, signer.clone())?,
+            Record::new(sync_data[0].clone(), 1, async_signer.clone()).await?,
+            Record::new(sync_data[1].clone(), 2, async_signer.clone()).await?,
+            Record::new(sync_data[2].clone(), 3, async_signer.clone()).await?,
         ];
 
         let (incoming_tx, _outgoing_records, sync_service) =
@@ -760,8 +778,8 @@ mod tests {
             SyncData::Chain(new_chain_sync_data(Some(new_accept_zero_conf))),
         ];
         let incoming_records = [
-            Record::new(sync_data[0].clone(), 4, signer.clone())?,
-            Record::new(sync_data[1].clone(), 5, signer.clone())?,
+            Record::new(sync_data[0].clone(), 4, async_signer.clone()).await?,
+            Record::new(sync_data[1].clone(), 5, async_signer.clone()).await?,
         ];
 
         for record in incoming_records {
@@ -805,6 +823,7 @@ mod tests {
     async fn test_outgoing_sync() -> Result<()> {
         create_persister!(persister);
         let signer: Arc<Box<dyn Signer>> = Arc::new(Box::new(MockSigner::new()?));
+        let async_signer: Arc<dyn AsyncSigner> = Arc::new(SyncSignerAdapter::new(signer.clone()));
         let swapper = Arc::new(MockSwapper::new());
         let onchain_wallet = Arc::new(MockWallet::new(signer.clone())?);
         let recoverer = Arc::new(new_recoverer(
@@ -878,7 +897,7 @@ mod tests {
         let outgoing = outgoing_records.lock().await;
 
         let record = get_outgoing_record(persister.clone(), &outgoing, &swap.id, RecordType::Send)?;
-        let decrypted_record = record.clone().decrypt(signer.clone())?;
+        let decrypted_record = record.clone().decrypt(async_signer.clone()).await?;
         assert_eq!(decrypted_record.data.id(), &swap.id);
         match decrypted_record.data {
             SyncData::Send(data) => {
@@ -909,7 +928,7 @@ mod tests {
 
         let outgoing = outgoing_records.lock().await;
         let record = get_outgoing_record(persister.clone(), &outgoing, &swap.id, RecordType::Send)?;
-        let decrypted_record = record.clone().decrypt(signer.clone())?;
+        let decrypted_record = record.clone().decrypt(async_signer.clone()).await?;
         assert_eq!(decrypted_record.data.id(), &swap.id);
         match decrypted_record.data {
             SyncData::Send(data) => {
@@ -927,6 +946,7 @@ mod tests {
     async fn test_sync_clean() -> Result<()> {
         create_persister!(persister);
         let signer: Arc<Box<dyn Signer>> = Arc::new(Box::new(MockSigner::new()?));
+        let async_signer: Arc<dyn AsyncSigner> = Arc::new(SyncSignerAdapter::new(signer.clone()));
         let swapper = Arc::new(MockSwapper::new());
         let onchain_wallet = Arc::new(MockWallet::new(signer.clone())?);
         let recoverer = Arc::new(new_recoverer(
@@ -944,8 +964,9 @@ mod tests {
         let record = Record::new(
             SyncData::Receive(new_receive_sync_data()),
             1,
-            signer.clone(),
-        )?;
+            async_signer.clone(),
+        )
+        .await?;
         incoming_tx.send(record).await?;
         sync_service.pull().await?;
 
@@ -955,8 +976,9 @@ mod tests {
         let mut inapplicable_record = Record::new(
             SyncData::Receive(new_receive_sync_data()),
             2,
-            signer.clone(),
-        )?;
+            async_signer.clone(),
+        )
+        .await?;
         inapplicable_record.schema_version = "9.9.9".to_string();
         incoming_tx.send(inapplicable_record).await?;
         sync_service.pull().await?;
@@ -996,6 +1018,7 @@ mod tests {
     async fn test_last_derivation_index_update() -> Result<()> {
         create_persister!(persister);
         let signer: Arc<Box<dyn Signer>> = Arc::new(Box::new(MockSigner::new()?));
+        let async_signer: Arc<dyn AsyncSigner> = Arc::new(SyncSignerAdapter::new(signer.clone()));
         let swapper = Arc::new(MockSwapper::new());
         let onchain_wallet = Arc::new(MockWallet::new(signer.clone())?);
         let recoverer = Arc::new(new_recoverer(
@@ -1015,7 +1038,7 @@ mod tests {
         let new_last_derivation_index = 10;
         let data = SyncData::LastDerivationIndex(new_last_derivation_index);
         incoming_tx
-            .send(Record::new(data, 0, signer.clone())?)
+            .send(Record::new(data, 0, async_signer.clone()).await?)
             .await?;
 
         sync_service.pull().await?;
@@ -1038,7 +1061,7 @@ mod tests {
             LAST_DERIVATION_INDEX_DATA_ID,
             RecordType::LastDerivationIndex,
         )?;
-        let decrypted_record = record.clone().decrypt(signer.clone())?;
+        let decrypted_record = record.clone().decrypt(async_signer.clone()).await?;
         match decrypted_record.data {
             SyncData::LastDerivationIndex(last_derivation_index) => {
                 assert_eq!(last_derivation_index, new_last_derivation_index);
@@ -1055,7 +1078,7 @@ mod tests {
         let new_remote_last_derivation_index = 25;
         let data = SyncData::LastDerivationIndex(new_remote_last_derivation_index);
         incoming_tx
-            .send(Record::new(data, 0, signer.clone())?)
+            .send(Record::new(data, 0, async_signer.clone()).await?)
             .await?;
 
         sync_service.pull().await?;
@@ -1072,7 +1095,7 @@ mod tests {
         let new_remote_last_derivation_index = 40;
         let data = SyncData::LastDerivationIndex(new_remote_last_derivation_index);
         incoming_tx
-            .send(Record::new(data, 2, signer.clone())?)
+            .send(Record::new(data, 2, async_signer.clone()).await?)
             .await?;
 
         sync_service.pull().await?;
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use crate::{
-    prelude::{Signer, SignerError},
+    prelude::{AsyncSigner, SignerError},
     utils,
 };
 use anyhow::Result;
@@ -14,24 +14,25 @@ use super::{
     MESSAGE_PREFIX,
 };
 
-fn sign_message(msg: &[u8], signer: Arc<Box<dyn Signer>>) -> Result<String, SignerError> {
+async fn sign_message(msg: &[u8], signer: Arc<dyn AsyncSigner>) -> Result<String, SignerError> {
     let msg = [MESSAGE_PREFIX, msg].concat();
     trace!("About to compute sha256 hash of msg: {msg:?}");
     let digest = sha256::Hash::hash(&sha256::Hash::hash(&msg));
     trace!("About to sign digest: {digest:?}");
     signer
         .sign_ecdsa_recoverable(digest.to_vec())
+        .await
         .map(|bytes| zbase32::encode_full_bytes(&bytes))
 }
 
 impl ListChangesRequest {
-    pub(crate) fn new(
+    pub(crate) async fn new(
         since_revision: u64,
-        signer: Arc<Box<dyn Signer>>,
+        signer: Arc<dyn AsyncSigner>,
     ) -> Result<Self, SignerError> {
         let request_time = utils::now();
         let msg = format!("{since_revision}-{request_time}");
-        let signature = sign_message(msg.as_bytes(), signer)?;
+        let signature = sign_message(msg.as_bytes(), signer).await?;
         Ok(Self {
             since_revision,
             request_time,
@@ -40,10 +41,10 @@ impl ListChangesRequest {
     }
 }
 impl SetRecordRequest {
-    pub(crate) fn new(
+    pub(crate) async fn new(
         record: Record,
         request_time: u32,
-        signer: Arc<Box<dyn Signer>>,
+        signer: Arc<dyn AsyncSigner>,
         client_id: String,
     ) -> Result<Self, SignerError> {
         let msg = format!(
@@ -55,7 +56,7 @@ impl SetRecordRequest {
             request_time,
         );
         trace!("About to sign message: {msg}");
-        let signature = sign_message(msg.as_bytes(), signer)?;
+        let signature = sign_message(msg.as_bytes(), signer).await?;
         trace!("Got signature: {signature}");
         Ok(Self {
             record: Some(record),
@@ -66,10 +67,10 @@ impl SetRecordRequest {
     }
 }
 impl ListenChangesRequest {
-    pub(crate) fn new(signer: Arc<Box<dyn Signer>>) -> Result<Self, SignerError> {
+    pub(crate) async fn new(signer: Arc<dyn AsyncSigner>) -> Result<Self, SignerError> {
         let request_time = utils::now();
         let msg = format!("{request_time}");
-        let signature = sign_message(msg.as_bytes(), signer)?;
+        let signature = sign_message(msg.as_bytes(), signer).await?;
         Ok(Self {
             request_time,
             signature,
@@ -2,7 +2,7 @@ use anyhow::bail;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    model::Bolt12Offer,
+    model::{Bolt12Offer, Peg},
     persist::model::PaymentTxDetails,
     prelude::{ChainSwap, Direction, LnUrlInfo, PaymentState, ReceiveSwap, SendSwap, Swap},
 };
@@ -30,6 +30,8 @@ pub(crate) struct ChainSyncData {
     pub(crate) description: Option<String>,
     #[serde(default)]
     pub(crate) auto_accepted_fees: bool,
+    #[serde(default)]
+    pub(crate) swap_provider: Option<String>,
 }
 
 impl ChainSyncData {
@@ -92,6 +94,7 @@ impl From<ChainSwap> for ChainSyncData {
             created_at: value.created_at,
             description: value.description,
             auto_accepted_fees: value.auto_accepted_fees,
+            swap_provider: value.swap_provider,
         }
     }
 }
@@ -129,6 +132,7 @@ impl From<ChainSyncData> for ChainSwap {
             refund_tx_id: None,
             auto_accepted_fees: val.auto_accepted_fees,
             user_lockup_spent: false,
+            swap_provider: val.swap_provider,
             metadata: Default::default(),
         }
     }
@@ -160,6 +164,8 @@ pub(crate) struct SendSyncData {
     pub(crate) payment_hash: Option<String>,
     pub(crate) description: Option<String>,
     pub(crate) destination_pubkey: Option<String>,
+    #[serde(default)]
+    pub(crate) swap_provider: Option<String>,
 }
 
 impl SendSyncData {
@@ -203,6 +209,7 @@ impl From<SendSwap> for SendSyncData {
             description: value.description,
             bolt12_offer: value.bolt12_offer,
             destination_pubkey: value.destination_pubkey,
+            swap_provider: value.swap_provider,
         }
     }
 }
@@ -228,6 +235,7 @@ impl From<SendSyncData> for SendSwap {
             lockup_tx_id: None,
             refund_address: None,
             refund_tx_id: None,
+            swap_provider: val.swap_provider,
             metadata: Default::default(),
         }
     }
@@ -253,6 +261,8 @@ pub(crate) struct ReceiveSyncData {
     pub(crate) description: Option<String>,
     pub(crate) payer_note: Option<String>,
     pub(crate) destination_pubkey: Option<String>,
+    #[serde(default)]
+    pub(crate) swap_provider: Option<String>,
 }
 
 impl ReceiveSyncData {
@@ -290,6 +300,7 @@ impl From<ReceiveSwap> for ReceiveSyncData {
             description: value.description,
             payer_note: value.payer_note,
             destination_pubkey: value.destination_pubkey,
+            swap_provider: value.swap_provider,
         }
     }
 }
@@ -319,6 +330,7 @@ impl From<ReceiveSyncData> for ReceiveSwap {
             claim_tx_id: None,
             lockup_tx_id: None,
             mrh_tx_id: None,
+            swap_provider: val.swap_provider,
             metadata: Default::default(),
         }
     }
@@ -443,6 +455,64 @@ impl From<Bolt12OfferSyncData> for Bolt12Offer {
     }
 }
 
+/// The creation data of a peg. Its deposit and claim are recovered from the chains.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct PegSyncData {
+    pub(crate) id: String,
+    pub(crate) direction: Direction,
+    pub(crate) bitcoin_address: String,
+    pub(crate) claim_script: Option<String>,
+    pub(crate) key_index: Option<u32>,
+    pub(crate) amount_sat: u64,
+    pub(crate) liquid_tx_id: Option<String>,
+    pub(crate) description: Option<String>,
+    pub(crate) created_at: u32,
+}
+
+impl From<Peg> for PegSyncData {
+    fn from(value: Peg) -> Self {
+        // The tx of a peg-out is known when it's created, while the claim of a peg-in is not
+        let (amount_sat, liquid_tx_id) = match value.direction {
+            Direction::Incoming => (0, None),
+            Direction::Outgoing => (value.amount_sat, value.liquid_tx_id),
+        };
+        Self {
+            id: value.id,
+            direction: value.direction,
+            bitcoin_address: value.bitcoin_address,
+            claim_script: value.claim_script,
+            key_index: value.key_index,
+            amount_sat,
+            liquid_tx_id,
+            description: value.description,
+            created_at: value.created_at,
+        }
+    }
+}
+
+impl From<PegSyncData> for Peg {
+    fn from(val: PegSyncData) -> Self {
+        let state = match val.direction {
+            Direction::Incoming => PaymentState::Created,
+            Direction::Outgoing => PaymentState::Pending,
+        };
+        Peg {
+            id: val.id,
+            direction: val.direction,
+            bitcoin_address: val.bitcoin_address,
+            claim_script: val.claim_script,
+            key_index: val.key_index,
+            amount_sat: val.amount_sat,
+            bitcoin_tx_id: None,
+            bitcoin_vout: None,
+            liquid_tx_id: val.liquid_tx_id,
+            description: val.description,
+            state,
+            created_at: val.created_at,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(tag = "data_type", content = "data")]
 pub(crate) enum SyncData {
@@ -452,6 +522,7 @@ pub(crate) enum SyncData {
     LastDerivationIndex(u32),
     PaymentDetails(PaymentDetailsSyncData),
     Bolt12Offer(Bolt12OfferSyncData),
+    Peg(PegSyncData),
 }
 
 impl SyncData {
@@ -463,6 +534,7 @@ impl SyncData {
             SyncData::LastDerivationIndex(_) => LAST_DERIVATION_INDEX_DATA_ID,
             SyncData::PaymentDetails(payment_details) => &payment_details.tx_id,
             SyncData::Bolt12Offer(bolt12_offer_data) => &bolt12_offer_data.id,
+            SyncData::Peg(peg_data) => &peg_data.id,
         }
     }
 
@@ -475,7 +547,8 @@ impl SyncData {
         match self {
             SyncData::Bolt12Offer(_)
             | SyncData::LastDerivationIndex(_)
-            | SyncData::PaymentDetails(_) => false,
+            | SyncData::PaymentDetails(_)
+            | SyncData::Peg(_) => false,
             SyncData::Chain(_) | SyncData::Send(_) | SyncData::Receive(_) => true,
         }
     }
@@ -503,6 +576,9 @@ impl SyncData {
             (SyncData::Bolt12Offer(ref mut base), SyncData::Bolt12Offer(other)) => {
                 base.merge(other, updated_fields)
             }
+            (SyncData::Peg(ref mut _base), SyncData::Peg(_other)) => {
+                bail!("Merge not supported for sync data of type Peg")
+            }
             _ => return Err(anyhow::anyhow!("Cannot merge data from two separate types")),
         };
         Ok(())
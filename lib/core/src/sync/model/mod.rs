@@ -3,7 +3,7 @@ tonic::include_proto!("sync");
 use std::sync::Arc;
 
 use self::data::SyncData;
-use crate::prelude::{Signer, SignerError};
+use crate::prelude::{AsyncSigner, SignerError};
 use anyhow::Result;
 use lazy_static::lazy_static;
 use log::trace;
@@ -20,7 +20,7 @@ pub(crate) mod data;
 
 const MESSAGE_PREFIX: &[u8; 13] = b"realtimesync:";
 lazy_static! {
-    static ref CURRENT_SCHEMA_VERSION: Version = Version::parse("0.7.0").unwrap();
+    static ref CURRENT_SCHEMA_VERSION: Version = Version::parse("0.8.0").unwrap();
 }
 
 #[derive(Copy, Clone)]
@@ -31,6 +31,7 @@ pub(crate) enum RecordType {
     LastDerivationIndex = 3,
     PaymentDetails = 4,
     Bolt12Offer = 5,
+    Peg = 6,
 }
 
 impl ToSql for RecordType {
@@ -49,6 +50,7 @@ impl FromSql for RecordType {
                 3 => Ok(Self::LastDerivationIndex),
                 4 => Ok(Self::PaymentDetails),
                 5 => Ok(Self::Bolt12Offer),
+                6 => Ok(Self::Peg),
                 _ => Err(FromSqlError::OutOfRange(i)),
             },
             _ => Err(FromSqlError::InvalidType),
@@ -234,15 +236,18 @@ impl PushError {
 }
 
 impl Record {
-    pub(crate) fn new(
+    pub(crate) async fn new(
         data: SyncData,
         revision: u64,
-        signer: Arc<Box<dyn Signer>>,
+        signer: Arc<dyn AsyncSigner>,
     ) -> Result<Self, PushError> {
         let id = Self::get_id_from_sync_data(&data);
         let data = data.to_bytes().map_err(PushError::serialization)?;
         trace!("About to encrypt sync data: {data:?}");
-        let data = signer.ecies_encrypt(data).map_err(PushError::encryption)?;
+        let data = signer
+            .ecies_encrypt(data)
+            .await
+            .map_err(PushError::encryption)?;
         trace!("Got encrypted sync data: {data:?}");
         let schema_version = CURRENT_SCHEMA_VERSION.to_string();
         Ok(Self {
@@ -265,6 +270,7 @@ impl Record {
             SyncData::LastDerivationIndex(_) => "derivation-index",
             SyncData::PaymentDetails(_) => "payment-details",
             SyncData::Bolt12Offer(_) => "bolt12-offer",
+            SyncData::Peg(_) => "peg",
         }
         .to_string();
         Self::id(prefix, data.id())
@@ -278,6 +284,7 @@ impl Record {
             RecordType::LastDerivationIndex => "derivation-index",
             RecordType::PaymentDetails => "payment-details",
             RecordType::Bolt12Offer => "bolt12-offer",
+            RecordType::Peg => "peg",
         }
         .to_string();
         Self::id(prefix, data_id)
@@ -288,12 +295,13 @@ impl Record {
         Ok(CURRENT_SCHEMA_VERSION.major >= record_version.major)
     }
 
-    pub(crate) fn decrypt(
+    pub(crate) async fn decrypt(
         self,
-        signer: Arc<Box<dyn Signer>>,
+        signer: Arc<dyn AsyncSigner>,
     ) -> Result<DecryptedRecord, PullError> {
         let dec_data = signer
             .ecies_decrypt(self.data)
+            .await
             .map_err(PullError::decryption)?;
         let data = serde_json::from_slice(&dec_data).map_err(PullError::deserialization)?;
         Ok(DecryptedRecord {
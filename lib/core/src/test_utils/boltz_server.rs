@@ -0,0 +1,520 @@
+//! An in-process server implementing the Boltz v2 HTTP and websocket endpoints used by the SDK,
+//! so the [BoltzSwapper](crate::swapper::boltz::BoltzSwapper) client code can be exercised
+//! without network access.
+//!
+//! Responses are generated by a [MockSwapper], while swap statuses are scripted by the test.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Result};
+use boltz_client::boltz::{self, SubmarineClaimTxResponse};
+use futures_util::{SinkExt, StreamExt};
+use log::{debug, warn};
+use serde_json::{json, Value};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::broadcast,
+    task::JoinHandle,
+};
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::{
+    model::Direction,
+    swapper::Swapper,
+    test_utils::{
+        http::{self, IncomingRequest},
+        swapper::MockSwapper,
+    },
+    utils,
+};
+
+struct ServerState {
+    swapper: MockSwapper,
+    /// The last status of each swap, as returned by the REST API and the websocket
+    statuses: Mutex<HashMap<String, boltz::SwapStatus>>,
+    claim_tx_details: Mutex<HashMap<String, SubmarineClaimTxResponse>>,
+    update_notifier: broadcast::Sender<boltz::SwapStatus>,
+    /// Whether websocket connections are refused, so clients fall back to the REST API
+    ws_unavailable: Mutex<bool>,
+    /// Whether websocket connections are closed right after being accepted
+    ws_drops_connections: Mutex<bool>,
+    /// The number of REST status requests received for each swap
+    status_requests: Mutex<HashMap<String, usize>>,
+}
+
+pub(crate) struct MockBoltzServer {
+    url: String,
+    state: Arc<ServerState>,
+    handle: JoinHandle<()>,
+}
+
+impl MockBoltzServer {
+    /// Starts the server on a random local port
+    pub(crate) async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}/v2", listener.local_addr()?);
+        let (update_notifier, _) = broadcast::channel::<boltz::SwapStatus>(30);
+        let state = Arc::new(ServerState {
+            swapper: MockSwapper::new(),
+            statuses: Mutex::new(HashMap::new()),
+            claim_tx_details: Mutex::new(HashMap::new()),
+            update_notifier,
+            ws_unavailable: Mutex::new(false),
+            ws_drops_connections: Mutex::new(false),
+            status_requests: Mutex::new(HashMap::new()),
+        });
+
+        let cloned_state = state.clone();
+        let handle = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        let state = cloned_state.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = handle_connection(state, stream).await {
+                                warn!("Mock Boltz server connection failed: {e:?}");
+                            }
+                        });
+                    }
+                    Err(e) => warn!("Mock Boltz server failed to accept connection: {e:?}"),
+                }
+            }
+        });
+
+        Ok(Self { url, state, handle })
+    }
+
+    /// The API URL of the server, to be set on the swapper via
+    /// [BoltzSwapper::with_boltz_url](crate::swapper::boltz::BoltzSwapper::with_boltz_url)
+    pub(crate) fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    /// The [MockSwapper] generating the responses, which can be used to configure them
+    pub(crate) fn swapper(&self) -> &MockSwapper {
+        &self.state.swapper
+    }
+
+    /// Refuses or accepts websocket connections. The REST API stays available.
+    pub(crate) fn set_ws_unavailable(&self, unavailable: bool) {
+        *self.state.ws_unavailable.lock().unwrap() = unavailable;
+    }
+
+    /// Closes websocket connections right after accepting them, or keeps them open
+    pub(crate) fn set_ws_drops_connections(&self, drops_connections: bool) {
+        *self.state.ws_drops_connections.lock().unwrap() = drops_connections;
+    }
+
+    /// The number of times the status of a swap was requested over the REST API
+    pub(crate) fn status_request_count(&self, swap_id: &str) -> usize {
+        self.state
+            .status_requests
+            .lock()
+            .unwrap()
+            .get(swap_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Sets the status of a swap, notifying the websocket clients subscribed to it
+    pub(crate) fn set_swap_status(&self, update: boltz::SwapStatus) {
+        self.state.set_swap_status(update)
+    }
+
+    /// Applies the given statuses to a swap one after the other, waiting `interval` before each
+    pub(crate) fn script_swap_statuses(
+        &self,
+        swap_id: &str,
+        statuses: Vec<&str>,
+        interval: Duration,
+    ) -> JoinHandle<()> {
+        let state = self.state.clone();
+        let swap_id = swap_id.to_string();
+        let statuses: Vec<String> = statuses.into_iter().map(Into::into).collect();
+        tokio::spawn(async move {
+            for status in statuses {
+                tokio::time::sleep(interval).await;
+                state.set_swap_status(boltz::SwapStatus {
+                    id: swap_id.clone(),
+                    status,
+                    ..Default::default()
+                });
+            }
+        })
+    }
+
+    /// Sets the claim details returned for a send swap once its invoice is paid
+    pub(crate) fn set_send_claim_tx_details(
+        &self,
+        swap_id: &str,
+        claim_tx_details: SubmarineClaimTxResponse,
+    ) {
+        self.state
+            .claim_tx_details
+            .lock()
+            .unwrap()
+            .insert(swap_id.to_string(), claim_tx_details);
+    }
+}
+
+impl Drop for MockBoltzServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+impl ServerState {
+    fn set_swap_status(&self, update: boltz::SwapStatus) {
+        debug!("Mock Boltz server setting swap status: {update:?}");
+        self.statuses
+            .lock()
+            .unwrap()
+            .insert(update.id.clone(), update.clone());
+        let _ = self.update_notifier.send(update);
+    }
+
+    fn swap_status(&self, swap_id: &str) -> Option<boltz::SwapStatus> {
+        self.statuses.lock().unwrap().get(swap_id).cloned()
+    }
+
+    fn init_swap_status(&self, response: &Value, status: &str) {
+        if let Some(id) = response.get("id").and_then(Value::as_str) {
+            self.set_swap_status(boltz::SwapStatus {
+                id: id.to_string(),
+                status: status.to_string(),
+                ..Default::default()
+            });
+        }
+    }
+
+    async fn handle_request(&self, method: &str, path: &str, body: &str) -> Result<Value> {
+        let path = path.split('?').next().unwrap_or_default();
+        let segments: Vec<&str> = path
+            .trim_start_matches("/v2")
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        let res = match (method, segments.as_slice()) {
+            ("GET", ["swap", "submarine"]) => {
+                let pair = self.swapper.get_submarine_pairs().await?;
+                json!({ "L-BTC": { "BTC": pair } })
+            }
+            ("POST", ["swap", "submarine"]) => {
+                let res = json!(
+                    self.swapper
+                        .create_send_swap(
+                            serde_json::from_str::<boltz::CreateSubmarineRequest>(body)?.into()
+                        )
+                        .await?
+                );
+                self.init_swap_status(&res, "invoice.set");
+                res
+            }
+            ("GET", ["swap", "submarine", id, "preimage"]) => {
+                json!({ "preimage": self.swapper.get_submarine_preimage(id).await? })
+            }
+            ("GET", ["swap", "submarine", id, "claim"]) => json!(self
+                .claim_tx_details
+                .lock()
+                .unwrap()
+                .get(*id)
+                .ok_or(anyhow!("Swap {id} cannot be claimed yet"))?),
+            ("POST", ["swap", "submarine", _, "claim"]) => json!({}),
+            ("GET", ["swap", "reverse"]) => {
+                let pair = self.swapper.get_reverse_swap_pairs().await?;
+                json!({ "BTC": { "L-BTC": pair } })
+            }
+            ("POST", ["swap", "reverse"]) => {
+                let res = json!(
+                    self.swapper
+                        .create_receive_swap(
+                            serde_json::from_str::<boltz::CreateReverseRequest>(body)?.into()
+                        )
+                        .await?
+                );
+                self.init_swap_status(&res, "swap.created");
+                res
+            }
+            ("GET", ["swap", "chain"]) => {
+                let incoming = self.swapper.get_chain_pair(Direction::Incoming).await?;
+                let outgoing = self.swapper.get_chain_pair(Direction::Outgoing).await?;
+                json!({ "BTC": { "L-BTC": incoming }, "L-BTC": { "BTC": outgoing } })
+            }
+            ("POST", ["swap", "chain"]) => {
+                let res = json!(
+                    self.swapper
+                        .create_chain_swap(
+                            serde_json::from_str::<boltz::CreateChainRequest>(body)?.into()
+                        )
+                        .await?
+                );
+                self.init_swap_status(&res, "swap.created");
+                res
+            }
+            ("GET", ["swap", "chain", id, "quote"]) => {
+                let amount = self.swapper.get_zero_amount_chain_swap_quote(id).await?;
+                json!({ "amount": amount.to_sat() })
+            }
+            ("POST", ["swap", "chain", _, "quote"]) => json!({}),
+            ("GET", ["swap", "chain", id, "claim"]) => {
+                bail!("Swap {id} has no claim details")
+            }
+            ("POST", ["swap", "chain", _, "claim"]) => json!({}),
+            ("GET", ["swap", id]) => {
+                *self
+                    .status_requests
+                    .lock()
+                    .unwrap()
+                    .entry(id.to_string())
+                    .or_default() += 1;
+                let status = self
+                    .swap_status(id)
+                    .ok_or(anyhow!("Could not find swap with id: {id}"))?;
+                json!({
+                    "status": status.status,
+                    "zeroConfRejected": status.zero_conf_rejected,
+                    "transaction": status.transaction,
+                })
+            }
+            ("POST", ["chain", _, "transaction"]) => {
+                let req: Value = serde_json::from_str(body)?;
+                let tx_hex = req
+                    .get("hex")
+                    .and_then(Value::as_str)
+                    .ok_or(anyhow!("Missing transaction hex"))?;
+                json!({ "id": utils::deserialize_tx_hex(tx_hex)?.txid().to_string() })
+            }
+            ("GET", ["nodes"]) => {
+                json!(boltz::GetNodesResponse::from(
+                    self.swapper.get_nodes().await?
+                ))
+            }
+            ("POST", ["bolt12", "fetch"]) => {
+                let req = serde_json::from_str::<boltz::GetBolt12FetchRequest>(body)?;
+                json!(boltz::GetBolt12FetchResponse::from(
+                    self.swapper.get_bolt12_info(req.into()).await?
+                ))
+            }
+            ("GET", ["bolt12", ..]) => json!(boltz::GetBolt12ParamsResponse::from(
+                self.swapper.get_bolt12_params().await?
+            )),
+            ("POST" | "PATCH" | "DELETE", ["bolt12", ..]) => json!({}),
+            _ => bail!("Unknown endpoint: {method} {path}"),
+        };
+        Ok(res)
+    }
+}
+
+async fn handle_connection(state: Arc<ServerState>, mut stream: TcpStream) -> Result<()> {
+    match http::read_request(&mut stream).await? {
+        IncomingRequest::Closed => Ok(()),
+        IncomingRequest::WebSocket if *state.ws_unavailable.lock().unwrap() => {
+            debug!("Mock Boltz server refusing websocket connection");
+            Ok(())
+        }
+        IncomingRequest::WebSocket if *state.ws_drops_connections.lock().unwrap() => {
+            debug!("Mock Boltz server dropping websocket connection");
+            let mut ws_stream = tokio_tungstenite::accept_async(stream).await?;
+            ws_stream.close(None).await?;
+            Ok(())
+        }
+        IncomingRequest::WebSocket => handle_ws(state, stream).await,
+        IncomingRequest::Http { method, path, body } => {
+            debug!("Mock Boltz server received request: {method} {path} {body}");
+            let res = state.handle_request(&method, &path, &body).await;
+            http::write_response(&mut stream, res).await
+        }
+    }
+}
+
+async fn handle_ws(state: Arc<ServerState>, stream: TcpStream) -> Result<()> {
+    let ws_stream = tokio_tungstenite::accept_async(stream).await?;
+    let (mut sender, mut receiver) = ws_stream.split();
+    let mut updates = state.update_notifier.subscribe();
+    let mut subscribed_ids: HashSet<String> = HashSet::new();
+
+    loop {
+        tokio::select! {
+            update = updates.recv() => match update {
+                Ok(update) if subscribed_ids.contains(&update.id) => {
+                    sender.send(Message::Text(update_msg(vec![update]).into())).await?;
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Mock Boltz server failed to receive update: {e:?}"),
+            },
+
+            msg = receiver.next() => match msg {
+                Some(Ok(Message::Text(payload))) => {
+                    let req: Value = serde_json::from_str(payload.as_str())?;
+                    let op = req.get("op").and_then(Value::as_str).unwrap_or_default();
+                    match op {
+                        "ping" => {
+                            sender.send(Message::Text(json!({ "event": "pong" }).to_string().into())).await?;
+                        }
+                        "subscribe" => {
+                            let channel = req.get("channel").cloned().unwrap_or_default();
+                            let args = req.get("args").cloned().unwrap_or(json!([]));
+                            let ack = json!({
+                                "event": "subscribe",
+                                "channel": channel,
+                                "args": args,
+                                "timestamp": utils::now().to_string(),
+                            });
+                            sender.send(Message::Text(ack.to_string().into())).await?;
+
+                            if channel == "swap.update" {
+                                let ids: Vec<String> = serde_json::from_value(args)?;
+                                // Like Boltz, send the current status of the swaps right away
+                                let current: Vec<boltz::SwapStatus> = ids
+                                    .iter()
+                                    .filter_map(|id| state.swap_status(id))
+                                    .collect();
+                                subscribed_ids.extend(ids);
+                                if !current.is_empty() {
+                                    sender.send(Message::Text(update_msg(current).into())).await?;
+                                }
+                            }
+                        }
+                        _ => debug!("Mock Boltz server ignoring ws request: {req}"),
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => return Ok(()),
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(anyhow!("Websocket stream failed: {e:?}")),
+            },
+        }
+    }
+}
+
+fn update_msg(updates: Vec<boltz::SwapStatus>) -> String {
+    json!({
+        "event": "update",
+        "channel": "swap.update",
+        "args": updates,
+        "timestamp": utils::now().to_string(),
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use anyhow::Result;
+    use boltz_client::boltz::{self, SubmarineClaimTxResponse};
+    use tokio::sync::watch;
+
+    use super::MockBoltzServer;
+    use crate::{
+        model::{Config, Direction},
+        swapper::{boltz::BoltzSwapper, SubscriptionHandler, Swapper, SwapperStatusStream},
+        test_utils::swapper::MockProxyUrlFetcher,
+        utils,
+    };
+
+    struct TrackingSubscriptionHandler {
+        swapper: Arc<BoltzSwapper<MockProxyUrlFetcher>>,
+        swap_id: String,
+    }
+
+    #[sdk_macros::async_trait]
+    impl SubscriptionHandler for TrackingSubscriptionHandler {
+        async fn track_subscriptions(&self) {
+            self.swapper.track_swap_id(&self.swap_id).unwrap();
+        }
+    }
+
+    fn new_swapper(server: &MockBoltzServer) -> Result<Arc<BoltzSwapper<MockProxyUrlFetcher>>> {
+        Ok(Arc::new(
+            BoltzSwapper::new(
+                Config::regtest_esplora(),
+                Arc::new(MockProxyUrlFetcher::new()),
+            )?
+            .with_boltz_url(server.url()),
+        ))
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_mock_server_endpoints() -> Result<()> {
+        let server = MockBoltzServer::start().await?;
+        let swapper = new_swapper(&server)?;
+        let id = "swap-id".to_string();
+
+        assert!(swapper.get_submarine_pairs().await?.is_some());
+        assert!(swapper.get_reverse_swap_pairs().await?.is_some());
+        assert!(swapper.get_chain_pair(Direction::Incoming).await?.is_some());
+        assert!(swapper.get_chain_pair(Direction::Outgoing).await?.is_some());
+        assert_eq!(swapper.get_bolt12_params().await?.min_cltv, 180);
+
+        let client = &swapper.get_boltz_client().await?.inner;
+        assert!(client.get_submarine_claim_tx_details(&id).await.is_err());
+        server.set_send_claim_tx_details(
+            &id,
+            SubmarineClaimTxResponse {
+                preimage: "preimage".to_string(),
+                pub_nonce: "".to_string(),
+                public_key: utils::generate_keypair().public_key().into(),
+                transaction_hash: "".to_string(),
+            },
+        );
+        let claim_tx_details = client.get_submarine_claim_tx_details(&id).await.unwrap();
+        assert_eq!(claim_tx_details.preimage, "preimage");
+
+        server.swapper().set_unavailable(true);
+        assert!(swapper.get_submarine_pairs().await.is_err());
+
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_mock_server_swap_updates() -> Result<()> {
+        let server = MockBoltzServer::start().await?;
+        let swapper = new_swapper(&server)?;
+        let (shutdown_sender, shutdown_receiver) = watch::channel(());
+
+        server.set_swap_status(boltz::SwapStatus {
+            id: "swap-id".to_string(),
+            status: "swap.created".to_string(),
+            ..Default::default()
+        });
+
+        let mut updates = swapper.subscribe_swap_updates();
+        swapper.clone().start(
+            Box::new(TrackingSubscriptionHandler {
+                swapper: swapper.clone(),
+                swap_id: "swap-id".to_string(),
+            }),
+            shutdown_receiver,
+        );
+
+        // The current status is sent on subscription, then each scripted transition
+        let update = tokio::time::timeout(Duration::from_secs(10), updates.recv()).await??;
+        assert_eq!(update.id, "swap-id");
+        assert_eq!(update.status, "swap.created");
+
+        server
+            .script_swap_statuses(
+                "swap-id",
+                vec!["transaction.mempool"],
+                Duration::from_millis(100),
+            )
+            .await?;
+        let update = tokio::time::timeout(Duration::from_secs(10), updates.recv()).await??;
+        assert_eq!(update.status, "transaction.mempool");
+
+        let status = swapper
+            .get_boltz_client()
+            .await?
+            .get_swap_status("swap-id")
+            .await?;
+        assert_eq!(status.status, "transaction.mempool");
+
+        shutdown_sender.send(())?;
+        Ok(())
+    }
+}
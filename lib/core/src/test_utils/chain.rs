@@ -1,11 +1,14 @@
-use std::sync::Mutex;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
 use crate::{
     bitcoin, elements,
     model::{BtcHistory, BtcScriptBalance, LBtcHistory},
 };
 use anyhow::Result;
-use bitcoin::{consensus::deserialize, OutPoint, Script, TxOut};
+use bitcoin::{consensus::deserialize, hashes::Hash as _, OutPoint, Script, TxOut};
 use boltz_client::Amount;
 use elements::{
     hex::FromHex, OutPoint as ElementsOutPoint, Script as ElementsScript, TxOut as ElementsTxOut,
@@ -14,12 +17,19 @@ use elements::{
 use crate::{
     chain::{bitcoin::BitcoinChainService, liquid::LiquidChainService},
     prelude::{RecommendedFees, Utxo},
+    test_utils::faults::{FaultInjector, FaultTarget},
     utils,
 };
 
 #[derive(Default)]
 pub(crate) struct MockLiquidChainService {
     history: Mutex<Vec<LBtcHistory>>,
+    script_histories: Mutex<HashMap<ElementsScript, Vec<LBtcHistory>>>,
+    block_hashes: Mutex<HashMap<u32, elements::BlockHash>>,
+    txs: Mutex<Vec<elements::Transaction>>,
+    broadcast_txs: Mutex<Vec<elements::Transaction>>,
+    tip: Mutex<u32>,
+    faults: Arc<FaultInjector>,
 }
 
 impl MockLiquidChainService {
@@ -27,6 +37,11 @@ impl MockLiquidChainService {
         MockLiquidChainService::default()
     }
 
+    pub(crate) fn with_faults(mut self, faults: Arc<FaultInjector>) -> Self {
+        self.faults = faults;
+        self
+    }
+
     pub(crate) fn set_history(&self, history: Vec<LBtcHistory>) -> &Self {
         *self.history.lock().unwrap() = history;
         self
@@ -35,30 +50,108 @@ impl MockLiquidChainService {
     pub(crate) fn get_history(&self) -> Vec<LBtcHistory> {
         self.history.lock().unwrap().clone()
     }
+
+    pub(crate) fn set_script_history(
+        &self,
+        script: ElementsScript,
+        history: Vec<LBtcHistory>,
+    ) -> &Self {
+        self.script_histories
+            .lock()
+            .unwrap()
+            .insert(script, history);
+        self
+    }
+
+    pub(crate) fn set_block_hash(&self, height: u32, hash: elements::BlockHash) -> &Self {
+        self.block_hashes.lock().unwrap().insert(height, hash);
+        self
+    }
+
+    pub(crate) fn set_transactions(&self, txs: Vec<elements::Transaction>) -> &Self {
+        *self.txs.lock().unwrap() = txs;
+        self
+    }
+
+    /// The txs broadcast through the service, in broadcast order
+    pub(crate) fn get_broadcast_txs(&self) -> Vec<elements::Transaction> {
+        self.broadcast_txs.lock().unwrap().clone()
+    }
+
+    fn get_script_history_for(&self, script: &ElementsScript) -> Vec<LBtcHistory> {
+        self.script_histories
+            .lock()
+            .unwrap()
+            .get(script)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn set_tip(&self, tip: u32) -> &Self {
+        *self.tip.lock().unwrap() = tip;
+        self
+    }
 }
 
 #[sdk_macros::async_trait]
 impl LiquidChainService for MockLiquidChainService {
     async fn tip(&self) -> Result<u32> {
-        Ok(0)
+        self.faults.check(FaultTarget::ChainService, "tip").await?;
+        Ok(*self.tip.lock().unwrap())
+    }
+
+    async fn get_block_hash(&self, height: u32) -> Result<elements::BlockHash> {
+        self.faults
+            .check(FaultTarget::ChainService, "get_block_hash")
+            .await?;
+        Ok(self
+            .block_hashes
+            .lock()
+            .unwrap()
+            .get(&height)
+            .cloned()
+            .unwrap_or(elements::BlockHash::all_zeros()))
     }
 
     async fn broadcast(&self, tx: &elements::Transaction) -> Result<elements::Txid> {
+        self.faults
+            .check(FaultTarget::ChainService, "broadcast")
+            .await?;
+        self.broadcast_txs.lock().unwrap().push(tx.clone());
         Ok(tx.txid())
     }
 
     async fn get_transaction_hex(
         &self,
-        _txid: &elements::Txid,
+        txid: &elements::Txid,
     ) -> Result<Option<elements::Transaction>> {
-        unimplemented!()
+        self.faults
+            .check(FaultTarget::ChainService, "get_transaction_hex")
+            .await?;
+        Ok(self
+            .txs
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|tx| &tx.txid() == txid)
+            .cloned())
     }
 
     async fn get_transactions(
         &self,
-        _txids: &[elements::Txid],
+        txids: &[elements::Txid],
     ) -> Result<Vec<elements::Transaction>> {
-        Ok(vec![])
+        self.faults
+            .check(FaultTarget::ChainService, "get_transactions")
+            .await?;
+        Ok(self
+            .txs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|tx| txids.contains(&tx.txid()))
+            .cloned()
+            .collect())
     }
 
     async fn get_script_history_with_retry(
@@ -66,22 +159,37 @@ impl LiquidChainService for MockLiquidChainService {
         _script: &ElementsScript,
         _retries: u64,
     ) -> Result<Vec<LBtcHistory>> {
+        self.faults
+            .check(FaultTarget::ChainService, "get_script_history_with_retry")
+            .await?;
         Ok(self.get_history().into_iter().collect())
     }
 
-    async fn get_script_history(&self, _script: &ElementsScript) -> Result<Vec<LBtcHistory>> {
-        Ok(vec![])
+    async fn get_script_history(&self, script: &ElementsScript) -> Result<Vec<LBtcHistory>> {
+        self.faults
+            .check(FaultTarget::ChainService, "get_script_history")
+            .await?;
+        Ok(self.get_script_history_for(script))
     }
 
     async fn get_scripts_history_with_retry(
         &self,
-        _scripts: &[ElementsScript],
+        scripts: &[ElementsScript],
         _retries: u64,
     ) -> Result<Vec<Vec<LBtcHistory>>> {
-        Ok(vec![])
+        self.faults
+            .check(FaultTarget::ChainService, "get_scripts_history_with_retry")
+            .await?;
+        Ok(scripts
+            .iter()
+            .map(|script| self.get_script_history_for(script))
+            .collect())
     }
 
     async fn get_script_utxos(&self, _script: &ElementsScript) -> Result<Vec<Utxo>> {
+        self.faults
+            .check(FaultTarget::ChainService, "get_script_utxos")
+            .await?;
         Ok(vec![Utxo::Liquid(Box::new((
             ElementsOutPoint::default(),
             ElementsTxOut::default(),
@@ -95,30 +203,51 @@ impl LiquidChainService for MockLiquidChainService {
         tx_hex: &str,
         _verify_confirmation: bool,
     ) -> Result<elements::Transaction> {
+        self.faults
+            .check(FaultTarget::ChainService, "verify_tx")
+            .await?;
         utils::deserialize_tx_hex(tx_hex)
     }
 }
 
 pub(crate) struct MockBitcoinChainService {
     history: Mutex<Vec<BtcHistory>>,
+    block_hashes: Mutex<HashMap<u32, bitcoin::BlockHash>>,
     txs: Mutex<Vec<bitcoin::Transaction>>,
+    txout_proof: Mutex<Option<bitcoin::merkle_tree::MerkleBlock>>,
     script_balance_sat: Mutex<u64>,
+    tip: Mutex<u32>,
+    faults: Arc<FaultInjector>,
 }
 
 impl MockBitcoinChainService {
     pub(crate) fn new() -> Self {
         MockBitcoinChainService {
             history: Mutex::new(vec![]),
+            block_hashes: Default::default(),
             txs: Mutex::new(vec![]),
+            txout_proof: Mutex::new(None),
             script_balance_sat: Mutex::new(0),
+            tip: Mutex::new(0),
+            faults: Default::default(),
         }
     }
 
+    pub(crate) fn with_faults(mut self, faults: Arc<FaultInjector>) -> Self {
+        self.faults = faults;
+        self
+    }
+
     pub(crate) fn set_history(&self, history: Vec<BtcHistory>) -> &Self {
         *self.history.lock().unwrap() = history;
         self
     }
 
+    pub(crate) fn set_block_hash(&self, height: u32, hash: bitcoin::BlockHash) -> &Self {
+        self.block_hashes.lock().unwrap().insert(height, hash);
+        self
+    }
+
     pub(crate) fn set_transactions(&self, txs: &[&str]) -> &Self {
         *self.txs.lock().unwrap() = txs
             .iter()
@@ -127,19 +256,47 @@ impl MockBitcoinChainService {
         self
     }
 
+    /// Sets the proof returned for any tx, as the merkle block of the tx's block
+    pub(crate) fn set_txout_proof(&self, txout_proof: bitcoin::merkle_tree::MerkleBlock) -> &Self {
+        *self.txout_proof.lock().unwrap() = Some(txout_proof);
+        self
+    }
+
     pub(crate) fn set_script_balance_sat(&self, script_balance_sat: u64) -> &Self {
         *self.script_balance_sat.lock().unwrap() = script_balance_sat;
         self
     }
+
+    pub(crate) fn set_tip(&self, tip: u32) -> &Self {
+        *self.tip.lock().unwrap() = tip;
+        self
+    }
 }
 
 #[sdk_macros::async_trait]
 impl BitcoinChainService for MockBitcoinChainService {
     async fn tip(&self) -> Result<u32> {
-        Ok(0)
+        self.faults.check(FaultTarget::ChainService, "tip").await?;
+        Ok(*self.tip.lock().unwrap())
+    }
+
+    async fn get_block_hash(&self, height: u32) -> Result<bitcoin::BlockHash> {
+        self.faults
+            .check(FaultTarget::ChainService, "get_block_hash")
+            .await?;
+        Ok(self
+            .block_hashes
+            .lock()
+            .unwrap()
+            .get(&height)
+            .cloned()
+            .unwrap_or(bitcoin::BlockHash::all_zeros()))
     }
 
     async fn broadcast(&self, tx: &bitcoin::Transaction) -> Result<bitcoin::Txid, anyhow::Error> {
+        self.faults
+            .check(FaultTarget::ChainService, "broadcast")
+            .await?;
         Ok(tx.compute_txid())
     }
 
@@ -148,6 +305,9 @@ impl BitcoinChainService for MockBitcoinChainService {
         _txids: &[bitcoin::Txid],
         _retries: u64,
     ) -> Result<Vec<bitcoin::Transaction>> {
+        self.faults
+            .check(FaultTarget::ChainService, "get_transactions_with_retry")
+            .await?;
         Ok(self.txs.lock().unwrap().clone())
     }
 
@@ -156,11 +316,17 @@ impl BitcoinChainService for MockBitcoinChainService {
         _script: &Script,
         _retries: u64,
     ) -> Result<Vec<BtcHistory>> {
+        self.faults
+            .check(FaultTarget::ChainService, "get_script_history_with_retry")
+            .await?;
         Ok(self.history.lock().unwrap().clone().into_iter().collect())
     }
 
     async fn get_script_history(&self, _scripts: &Script) -> Result<Vec<BtcHistory>> {
-        Ok(vec![])
+        self.faults
+            .check(FaultTarget::ChainService, "get_script_history")
+            .await?;
+        Ok(self.history.lock().unwrap().clone())
     }
 
     async fn get_scripts_history_with_retry(
@@ -168,10 +334,16 @@ impl BitcoinChainService for MockBitcoinChainService {
         _scripts: &[&Script],
         _retries: u64,
     ) -> Result<Vec<Vec<BtcHistory>>> {
+        self.faults
+            .check(FaultTarget::ChainService, "get_scripts_history_with_retry")
+            .await?;
         Ok(vec![])
     }
 
     async fn get_script_utxos(&self, script: &Script) -> Result<Vec<Utxo>> {
+        self.faults
+            .check(FaultTarget::ChainService, "get_script_utxos")
+            .await?;
         Ok(self
             .get_scripts_utxos(&[script])
             .await?
@@ -181,6 +353,9 @@ impl BitcoinChainService for MockBitcoinChainService {
     }
 
     async fn get_scripts_utxos(&self, scripts: &[&Script]) -> Result<Vec<Vec<Utxo>>> {
+        self.faults
+            .check(FaultTarget::ChainService, "get_scripts_utxos")
+            .await?;
         let scripts_utxos = scripts
             .iter()
             .map(|s| {
@@ -200,6 +375,9 @@ impl BitcoinChainService for MockBitcoinChainService {
         &self,
         _script: &boltz_client::bitcoin::Script,
     ) -> Result<BtcScriptBalance> {
+        self.faults
+            .check(FaultTarget::ChainService, "script_get_balance")
+            .await?;
         Ok(BtcScriptBalance {
             confirmed: 0,
             unconfirmed: 0,
@@ -207,6 +385,9 @@ impl BitcoinChainService for MockBitcoinChainService {
     }
 
     async fn scripts_get_balance(&self, _scripts: &[&Script]) -> Result<Vec<BtcScriptBalance>> {
+        self.faults
+            .check(FaultTarget::ChainService, "scripts_get_balance")
+            .await?;
         Ok(vec![])
     }
 
@@ -215,6 +396,9 @@ impl BitcoinChainService for MockBitcoinChainService {
         _script: &boltz_client::bitcoin::Script,
         _retries: u64,
     ) -> Result<BtcScriptBalance> {
+        self.faults
+            .check(FaultTarget::ChainService, "script_get_balance_with_retry")
+            .await?;
         Ok(BtcScriptBalance {
             confirmed: *self.script_balance_sat.lock().unwrap(),
             unconfirmed: 0,
@@ -228,12 +412,33 @@ impl BitcoinChainService for MockBitcoinChainService {
         tx_hex: &str,
         _verify_confirmation: bool,
     ) -> Result<boltz_client::bitcoin::Transaction> {
+        self.faults
+            .check(FaultTarget::ChainService, "verify_tx")
+            .await?;
         Ok(deserialize(&Vec::<u8>::from_hex(tx_hex).map_err(
             |err| anyhow::anyhow!("Could not deserialize transaction: {err:?}"),
         )?)?)
     }
 
+    async fn get_txout_proof(
+        &self,
+        _txid: &bitcoin::Txid,
+        _height: u32,
+    ) -> Result<bitcoin::merkle_tree::MerkleBlock> {
+        self.faults
+            .check(FaultTarget::ChainService, "get_txout_proof")
+            .await?;
+        self.txout_proof
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or(anyhow::anyhow!("No txout proof is set"))
+    }
+
     async fn recommended_fees(&self) -> Result<RecommendedFees> {
+        self.faults
+            .check(FaultTarget::ChainService, "recommended_fees")
+            .await?;
         unimplemented!()
     }
 }
@@ -152,6 +152,7 @@ pub(crate) fn new_chain_swap(
             .to_string(),
             auto_accepted_fees: false,
             user_lockup_spent: false,
+            swap_provider: None,
             metadata: Default::default(),
         };
     }
@@ -240,6 +241,7 @@ pub(crate) fn new_chain_swap(
             }"#.to_string(),
             auto_accepted_fees: false,
             user_lockup_spent: false,
+            swap_provider: None,
             metadata: Default::default(),
         },
         Direction::Outgoing => ChainSwap {
@@ -326,6 +328,7 @@ pub(crate) fn new_chain_swap(
             }"#.to_string(),
             auto_accepted_fees: false,
             user_lockup_spent: false,
+            swap_provider: None,
             metadata: Default::default(),
         }
     }
@@ -0,0 +1,82 @@
+use std::{cell::RefCell, sync::Arc, sync::Mutex, time::Duration};
+
+use web_time::{SystemTime, UNIX_EPOCH};
+
+thread_local! {
+    static CLOCK: RefCell<Option<Arc<MockClock>>> = const { RefCell::new(None) };
+}
+
+/// A manually driven clock that replaces the system time returned by [crate::utils::now].
+///
+/// The clock is installed for the current thread only, so tests running in parallel don't
+/// affect each other. Tasks spawned on a single-threaded runtime share the installed clock,
+/// while code running on other threads keeps using the system time.
+///
+/// Only the timestamps taken through `utils::now` and `utils::system_time` are controlled.
+/// Durations measured with `Instant::now` and the timers of the async runtime (sleeps,
+/// intervals, timeouts) are not, so the latter need the runtime's own paused time instead.
+pub struct MockClock {
+    now: Mutex<SystemTime>,
+}
+
+impl MockClock {
+    /// Installs a clock starting at the given UNIX timestamp, replacing any installed clock
+    pub fn install(start_ts: u64) -> Arc<Self> {
+        let clock = Arc::new(Self {
+            now: Mutex::new(UNIX_EPOCH + Duration::from_secs(start_ts)),
+        });
+        CLOCK.with(|c| *c.borrow_mut() = Some(clock.clone()));
+        clock
+    }
+
+    /// Removes the installed clock, restoring the system time
+    pub fn uninstall() {
+        CLOCK.with(|c| *c.borrow_mut() = None);
+    }
+
+    /// Moves the clock forward by the given duration
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+
+    /// Sets the clock to the given UNIX timestamp
+    pub fn set(&self, ts: u64) {
+        *self.now.lock().unwrap() = UNIX_EPOCH + Duration::from_secs(ts);
+    }
+
+    pub fn now(&self) -> SystemTime {
+        *self.now.lock().unwrap()
+    }
+}
+
+/// The time of the clock installed on the current thread, if any
+pub fn installed_now() -> Option<SystemTime> {
+    CLOCK.with(|c| c.borrow().as_ref().map(|clock| clock.now()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::utils;
+
+    use super::MockClock;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[sdk_macros::test_all]
+    fn test_mock_clock() {
+        let clock = MockClock::install(1_700_000_000);
+        assert_eq!(utils::now(), 1_700_000_000);
+
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(utils::now(), 1_700_000_060);
+
+        clock.set(1_600_000_000);
+        assert_eq!(utils::now(), 1_600_000_000);
+
+        MockClock::uninstall();
+        assert!(utils::now() > 1_700_000_000);
+    }
+}
@@ -0,0 +1,272 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use anyhow::{anyhow, Result};
+use tokio_with_wasm::alias as tokio;
+
+/// Matches every method of a [FaultTarget]
+pub const ANY_METHOD: &str = "*";
+
+/// The services a [FaultInjector] can interfere with
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FaultTarget {
+    /// Both the Liquid and the Bitcoin chain service
+    ChainService,
+    Swapper,
+    SyncClient,
+    /// The database, matched by the `get_connection` method for every access, or by the name
+    /// of the swap and payment inserts and updates, e.g. `insert_or_update_send_swap` or
+    /// `try_handle_receive_swap_update`
+    Persister,
+}
+
+#[derive(Clone, Debug)]
+pub enum Fault {
+    /// The call fails with the given error message
+    Error(String),
+    /// The call is delayed by the given duration before proceeding. The synchronous
+    /// [FaultTarget::Persister] call sites block the calling thread, like a slow disk would.
+    Delay(Duration),
+}
+
+struct InjectedFault {
+    fault: Fault,
+    /// The number of calls left to affect, or `None` to affect every call
+    remaining: Option<usize>,
+}
+
+/// Forces errors or delays at chosen call sites of the mocked services.
+///
+/// Faults are keyed by [FaultTarget] and method name, or [ANY_METHOD] to match every method of
+/// the target. The same injector can be shared by all the mocks used in a test.
+#[derive(Default)]
+pub struct FaultInjector {
+    faults: Mutex<HashMap<(FaultTarget, String), InjectedFault>>,
+}
+
+impl FaultInjector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Injects a fault affecting every call to `method` until [FaultInjector::clear] is called
+    pub fn inject(&self, target: FaultTarget, method: &str, fault: Fault) -> &Self {
+        self.insert(target, method, fault, None)
+    }
+
+    /// Injects a fault affecting only the next `times` calls to `method`.
+    /// Injecting a fault for 0 calls has no effect.
+    pub fn inject_times(
+        &self,
+        target: FaultTarget,
+        method: &str,
+        fault: Fault,
+        times: usize,
+    ) -> &Self {
+        if times == 0 {
+            return self;
+        }
+        self.insert(target, method, fault, Some(times))
+    }
+
+    /// Removes all injected faults
+    pub fn clear(&self) {
+        self.faults.lock().unwrap().clear();
+    }
+
+    fn insert(
+        &self,
+        target: FaultTarget,
+        method: &str,
+        fault: Fault,
+        remaining: Option<usize>,
+    ) -> &Self {
+        self.faults.lock().unwrap().insert(
+            (target, method.to_string()),
+            InjectedFault { fault, remaining },
+        );
+        self
+    }
+
+    /// Takes the fault matching the call site, if any, consuming one of its remaining calls
+    fn take(&self, target: FaultTarget, method: &str) -> Option<Fault> {
+        let mut faults = self.faults.lock().unwrap();
+        let key = [method, ANY_METHOD]
+            .into_iter()
+            .map(|m| (target, m.to_string()))
+            .find(|key| faults.contains_key(key))?;
+        let injected = faults.get_mut(&key)?;
+        let fault = injected.fault.clone();
+        if let Some(remaining) = injected.remaining.as_mut() {
+            *remaining = remaining.saturating_sub(1);
+            if *remaining == 0 {
+                faults.remove(&key);
+            }
+        }
+        Some(fault)
+    }
+
+    /// Applies the fault injected for the call site, if any
+    pub(crate) async fn check(&self, target: FaultTarget, method: &str) -> Result<()> {
+        match self.take(target, method) {
+            Some(Fault::Error(err)) => Err(anyhow!("Injected {target:?} fault: {err}")),
+            Some(Fault::Delay(duration)) => {
+                tokio::time::sleep(duration).await;
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Applies the fault injected for the synchronous call site, if any
+    pub(crate) fn check_sync(&self, target: FaultTarget, method: &str) -> Result<()> {
+        match self.take(target, method) {
+            Some(Fault::Error(err)) => Err(anyhow!("Injected {target:?} fault: {err}")),
+            Some(Fault::Delay(duration)) => {
+                block_for(duration);
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+fn block_for(duration: Duration) {
+    std::thread::sleep(duration);
+}
+
+/// The browser main thread cannot sleep, so the delay is spent busy-waiting
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+fn block_for(duration: Duration) {
+    let start = web_time::Instant::now();
+    while start.elapsed() < duration {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use anyhow::Result;
+
+    use crate::{
+        model::{ListPaymentsRequest, PaymentState},
+        swapper::Swapper,
+        sync::client::SyncerClient,
+        test_utils::{
+            persist::{create_persister, new_send_swap},
+            swapper::MockSwapper,
+            sync::MockSyncerClient,
+        },
+    };
+
+    use super::*;
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[sdk_macros::async_test_all]
+    async fn test_swapper_faults() -> Result<()> {
+        let faults = Arc::new(FaultInjector::new());
+        let swapper = MockSwapper::new().with_faults(faults.clone());
+
+        faults.inject_times(
+            FaultTarget::Swapper,
+            "get_submarine_pairs",
+            Fault::Error("unreachable".to_string()),
+            1,
+        );
+        assert!(swapper.get_submarine_pairs().await.is_err());
+        assert!(swapper.get_submarine_pairs().await.is_ok());
+
+        faults.inject_times(
+            FaultTarget::Swapper,
+            "get_submarine_pairs",
+            Fault::Error("unreachable".to_string()),
+            0,
+        );
+        assert!(swapper.get_submarine_pairs().await.is_ok());
+
+        faults.inject(
+            FaultTarget::Swapper,
+            ANY_METHOD,
+            Fault::Error("unreachable".to_string()),
+        );
+        assert!(swapper.get_reverse_swap_pairs().await.is_err());
+        assert!(swapper.get_nodes().await.is_err());
+
+        faults.clear();
+        assert!(swapper.get_reverse_swap_pairs().await.is_ok());
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_sync_client_faults() -> Result<()> {
+        let faults = Arc::new(FaultInjector::new());
+        let (_incoming_tx, incoming_rx) = tokio::sync::mpsc::channel(10);
+        let client =
+            MockSyncerClient::new(incoming_rx, Default::default()).with_faults(faults.clone());
+
+        faults.inject_times(
+            FaultTarget::SyncClient,
+            "connect",
+            Fault::Error("unreachable".to_string()),
+            2,
+        );
+        assert!(client.connect("".to_string()).await.is_err());
+        assert!(client.connect("".to_string()).await.is_err());
+        assert!(client.connect("".to_string()).await.is_ok());
+        Ok(())
+    }
+
+    #[sdk_macros::test_all]
+    fn test_persister_faults() -> Result<()> {
+        create_persister!(persister);
+        let faults = Arc::new(FaultInjector::new());
+        persister.set_fault_injector(faults.clone());
+
+        faults.inject(
+            FaultTarget::Persister,
+            ANY_METHOD,
+            Fault::Error("disk full".to_string()),
+        );
+        assert!(persister
+            .get_payments(&ListPaymentsRequest::default())
+            .is_err());
+
+        faults.clear();
+        assert!(persister
+            .get_payments(&ListPaymentsRequest::default())
+            .is_ok());
+
+        // Named operations fail without affecting the other database accesses
+        let send_swap = new_send_swap(None, None);
+        faults.inject_times(
+            FaultTarget::Persister,
+            "insert_or_update_send_swap",
+            Fault::Error("disk full".to_string()),
+            1,
+        );
+        assert!(persister.insert_or_update_send_swap(&send_swap).is_err());
+        assert!(persister.fetch_send_swap_by_id(&send_swap.id)?.is_none());
+        persister.insert_or_update_send_swap(&send_swap)?;
+
+        // Delays block the operation
+        let delay = Duration::from_millis(50);
+        faults.inject_times(
+            FaultTarget::Persister,
+            "try_handle_send_swap_update",
+            Fault::Delay(delay),
+            1,
+        );
+        let start = web_time::Instant::now();
+        persister.try_handle_send_swap_update(
+            &send_swap.id,
+            PaymentState::Pending,
+            None,
+            None,
+            None,
+        )?;
+        assert!(start.elapsed() >= delay);
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+//! Minimal HTTP/1.1 handling shared by the in-process mock servers
+
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+const MAX_HEADER_SIZE: usize = 8 * 1024;
+
+pub(crate) enum IncomingRequest {
+    /// The client closed the connection before sending a request
+    Closed,
+    /// A websocket upgrade request, left unread on the stream
+    WebSocket,
+    Http {
+        method: String,
+        path: String,
+        body: String,
+    },
+}
+
+/// Reads the next request from the stream.
+///
+/// The request head is peeked at first, so websocket upgrades can be handed over untouched.
+pub(crate) async fn read_request(stream: &mut TcpStream) -> Result<IncomingRequest> {
+    let mut buf = vec![0; MAX_HEADER_SIZE];
+    let head = loop {
+        let len = stream.peek(&mut buf).await?;
+        if len == 0 {
+            return Ok(IncomingRequest::Closed);
+        }
+        let peeked = String::from_utf8_lossy(&buf[..len]).to_string();
+        if let Some(head_end) = peeked.find("\r\n\r\n") {
+            break peeked[..head_end + 4].to_string();
+        }
+        if len >= MAX_HEADER_SIZE {
+            bail!("Request header is too large");
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    };
+
+    let mut lines = head.lines();
+    let request_line = lines.next().unwrap_or_default().to_string();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+        .collect();
+
+    if headers
+        .get("upgrade")
+        .is_some_and(|upgrade| upgrade.eq_ignore_ascii_case("websocket"))
+    {
+        return Ok(IncomingRequest::WebSocket);
+    }
+
+    let mut request_parts = request_line.split_whitespace();
+    let method = request_parts.next().unwrap_or_default().to_string();
+    let path = request_parts.next().unwrap_or_default().to_string();
+    let content_length: usize = headers
+        .get("content-length")
+        .map(|len| len.parse())
+        .transpose()?
+        .unwrap_or_default();
+
+    let mut request = vec![0; head.len() + content_length];
+    stream.read_exact(&mut request).await?;
+    let body = String::from_utf8_lossy(&request[head.len()..]).to_string();
+    Ok(IncomingRequest::Http { method, path, body })
+}
+
+/// Writes the result of a request as a JSON response, then closes the stream
+pub(crate) async fn write_response(stream: &mut TcpStream, res: Result<Value>) -> Result<()> {
+    let (status_line, body) = match res {
+        Ok(res) => ("200 OK", res),
+        Err(e) => ("400 Bad Request", json!({ "error": e.to_string() })),
+    };
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {status_line}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
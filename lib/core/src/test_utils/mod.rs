@@ -3,13 +3,24 @@
 use bip39::rand::{self, distributions::Alphanumeric, Rng};
 
 pub(crate) mod bolt12_offer;
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub(crate) mod boltz_server;
 pub(crate) mod chain;
 pub(crate) mod chain_swap;
+pub mod clock;
+pub mod faults;
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub(crate) mod http;
+pub(crate) mod payjoin;
 pub mod persist;
 pub(crate) mod receive_swap;
 pub(crate) mod recover;
 pub(crate) mod sdk;
 pub(crate) mod send_swap;
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub mod sideswap_server;
+#[cfg(any(test, feature = "simulation"))]
+pub mod simulation;
 pub(crate) mod status_stream;
 pub(crate) mod swapper;
 pub(crate) mod sync;
@@ -0,0 +1,144 @@
+use std::sync::Mutex;
+
+use lwk_wollet::elements::Transaction;
+
+use crate::payjoin::{
+    error::{PayjoinError, PayjoinResult},
+    model::AcceptedAsset,
+    PayjoinService,
+};
+
+use super::wallet::TEST_LIQUID_TX;
+
+/// A call made to the [MockPayjoinService]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum PayjoinCall {
+    EstimateTxFee {
+        asset_id: String,
+        amount_sat: u64,
+    },
+    EstimateDrainTxFee {
+        asset_id: String,
+    },
+    EstimateLbtcTxFee {
+        fee_asset_id: String,
+        amount_sat: u64,
+    },
+    BuildTx {
+        recipient_address: String,
+        asset_id: String,
+        amount_sat: u64,
+    },
+    BuildLbtcTx {
+        recipient_address: String,
+        fee_asset_id: String,
+        amount_sat: u64,
+    },
+}
+
+/// A payjoin service accepting a single asset, which estimates a fixed fee and builds
+/// [TEST_LIQUID_TX] for every payjoin
+pub(crate) struct MockPayjoinService {
+    asset_id: String,
+    fee: f64,
+    fee_sat: u64,
+    calls: Mutex<Vec<PayjoinCall>>,
+}
+
+impl MockPayjoinService {
+    /// Creates a service accepting `asset_id`, with a fee of `fee` in the asset amount and of
+    /// `fee_sat` in its satoshi units
+    pub(crate) fn new(asset_id: &str, fee: f64, fee_sat: u64) -> Self {
+        Self {
+            asset_id: asset_id.to_string(),
+            fee,
+            fee_sat,
+            calls: Mutex::new(vec![]),
+        }
+    }
+
+    /// The calls made to the service, in order
+    pub(crate) fn calls(&self) -> Vec<PayjoinCall> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn record(&self, call: PayjoinCall) {
+        self.calls.lock().unwrap().push(call);
+    }
+
+    fn ensure_accepted_asset(&self, asset_id: &str) -> PayjoinResult<()> {
+        match asset_id == self.asset_id {
+            true => Ok(()),
+            false => Err(PayjoinError::generic("Asset not accepted by SideSwap")),
+        }
+    }
+}
+
+#[sdk_macros::async_trait]
+impl PayjoinService for MockPayjoinService {
+    async fn fetch_accepted_assets(&self) -> PayjoinResult<Vec<AcceptedAsset>> {
+        Ok(vec![AcceptedAsset {
+            asset_id: self.asset_id.clone(),
+        }])
+    }
+
+    async fn estimate_payjoin_tx_fee(&self, asset_id: &str, amount_sat: u64) -> PayjoinResult<f64> {
+        self.record(PayjoinCall::EstimateTxFee {
+            asset_id: asset_id.to_string(),
+            amount_sat,
+        });
+        self.ensure_accepted_asset(asset_id)?;
+        Ok(self.fee)
+    }
+
+    async fn estimate_payjoin_drain_tx_fee(&self, asset_id: &str) -> PayjoinResult<f64> {
+        self.record(PayjoinCall::EstimateDrainTxFee {
+            asset_id: asset_id.to_string(),
+        });
+        self.ensure_accepted_asset(asset_id)?;
+        Ok(self.fee)
+    }
+
+    async fn estimate_payjoin_lbtc_tx_fee(
+        &self,
+        fee_asset_id: &str,
+        amount_sat: u64,
+    ) -> PayjoinResult<f64> {
+        self.record(PayjoinCall::EstimateLbtcTxFee {
+            fee_asset_id: fee_asset_id.to_string(),
+            amount_sat,
+        });
+        self.ensure_accepted_asset(fee_asset_id)?;
+        Ok(self.fee)
+    }
+
+    async fn build_payjoin_tx(
+        &self,
+        recipient_address: &str,
+        asset_id: &str,
+        amount_sat: u64,
+    ) -> PayjoinResult<(Transaction, u64)> {
+        self.record(PayjoinCall::BuildTx {
+            recipient_address: recipient_address.to_string(),
+            asset_id: asset_id.to_string(),
+            amount_sat,
+        });
+        self.ensure_accepted_asset(asset_id)?;
+        Ok((TEST_LIQUID_TX.clone(), self.fee_sat))
+    }
+
+    async fn build_payjoin_lbtc_tx(
+        &self,
+        recipient_address: &str,
+        fee_asset_id: &str,
+        amount_sat: u64,
+    ) -> PayjoinResult<(Transaction, u64)> {
+        self.record(PayjoinCall::BuildLbtcTx {
+            recipient_address: recipient_address.to_string(),
+            fee_asset_id: fee_asset_id.to_string(),
+            amount_sat,
+        });
+        self.ensure_accepted_asset(fee_asset_id)?;
+        Ok((TEST_LIQUID_TX.clone(), self.fee_sat))
+    }
+}
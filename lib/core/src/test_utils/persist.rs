@@ -91,6 +91,7 @@ pub fn new_send_swap(
         created_at: utils::now(),
         state: payment_state.unwrap_or(PaymentState::Created),
         refund_private_key: "945affeef55f12227f1d4a3f80a17062a05b229ddc5a01591eb5ddf882df92e3".to_string(),
+        swap_provider: None,
         metadata: Default::default(),
     }
 }
@@ -122,6 +123,7 @@ pub fn new_receive_swap(
         mrh_tx_id: None,
         created_at: utils::now(),
         state: payment_state.unwrap_or(PaymentState::Created),
+        swap_provider: None,
         metadata: Default::default(),
     }
 }
@@ -177,6 +179,7 @@ pub(crate) fn new_payment_tx_data(
             fees_sat: 0,
             is_confirmed: false,
             unblinding_data: None,
+            blocks_remaining: None,
         },
         PaymentTxBalance {
             payment_type,
@@ -9,6 +9,7 @@ use crate::{
     persist::Persister,
     recover::recoverer::Recoverer,
     sdk::{LiquidSdk, LiquidSdkBuilder},
+    swapper::{Swapper, SwapperStatusStream},
 };
 
 use super::{
@@ -40,8 +41,8 @@ pub(crate) async fn new_liquid_sdk(
 
 pub(crate) async fn new_liquid_sdk_with_chain_services(
     persister: std::sync::Arc<Persister>,
-    swapper: Arc<MockSwapper>,
-    status_stream: Arc<MockStatusStream>,
+    swapper: Arc<dyn Swapper>,
+    status_stream: Arc<dyn SwapperStatusStream>,
     liquid_chain_service: Arc<MockLiquidChainService>,
     bitcoin_chain_service: Arc<MockBitcoinChainService>,
     onchain_fee_rate_leeway_sat: Option<u64>,
@@ -0,0 +1,751 @@
+//! An in-process server implementing the SideSwap payjoin and asset swap endpoints used by the
+//! SDK, so the [SideSwapPayjoinService](crate::payjoin::side_swap::SideSwapPayjoinService) and
+//! [SideSwapService](crate::side_swap::api::SideSwapService) client code can be exercised
+//! without network access, or against a regtest chain.
+//!
+//! The server spends from a single-key wallet whose utxos are added by the test, see
+//! [MockSideSwapServer::add_utxo].
+
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use boltz_client::{Keypair, Secp256k1};
+use futures_util::{SinkExt, StreamExt};
+use log::{debug, warn};
+use lwk_wollet::{
+    bitcoin::base64::{self, Engine as _},
+    secp256k1::Message,
+};
+use sdk_common::bitcoin::hashes::hex::ToHex as _;
+use serde_json::{json, Value};
+use sideswap_api::{http_rpc::SwapSignRequest, http_rpc::SwapStartRequest, StartSwapWebRequest};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+use crate::{
+    bitcoin::{self, hashes::Hash},
+    elements::{
+        self,
+        confidential::{self, AssetBlindingFactor, ValueBlindingFactor},
+        pset::PartiallySignedTransaction,
+        secp256k1_zkp::Generator,
+        Address, AddressParams, AssetId, Script, Transaction, TxOutSecrets, Txid,
+    },
+    model::LiquidNetwork,
+    payjoin::{
+        model::{
+            AcceptedAsset, AcceptedAssetsResponse, Request as PayjoinRequest,
+            Response as PayjoinResponse, SignResponse, StartResponse, Utxo,
+        },
+        pset::{construct_pset, ConstructPsetRequest, PsetInput, PsetOutput},
+    },
+    side_swap::api::model::{HttpRequest, Request, WrappedRequest},
+    test_utils::http::{self, IncomingRequest},
+    utils,
+    wallet::network_fee::TxFee,
+};
+
+/// The exchange rate used by default, in asset units per L-BTC
+const DEFAULT_PRICE: f64 = 1.0;
+const DEFAULT_FIXED_FEE: u64 = 1_000;
+
+struct SwapOrder {
+    send_asset: AssetId,
+    send_amount: u64,
+    recv_asset: AssetId,
+    recv_amount: u64,
+}
+
+struct ServerState {
+    url: String,
+    policy_asset: AssetId,
+    keypair: Keypair,
+    blinding_keypair: Keypair,
+    /// The unspent outputs of the server wallet
+    utxos: Mutex<Vec<Utxo>>,
+    accepted_assets: Mutex<Vec<AssetId>>,
+    price: Mutex<f64>,
+    fixed_fee: Mutex<u64>,
+    swap_orders: Mutex<HashMap<String, SwapOrder>>,
+    /// The transactions of the asset swaps signed by the server
+    swap_txs: Mutex<Vec<Transaction>>,
+}
+
+pub struct MockSideSwapServer {
+    state: Arc<ServerState>,
+    handle: JoinHandle<()>,
+}
+
+impl MockSideSwapServer {
+    /// Starts the server on a random local port
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let state = Arc::new(ServerState {
+            url: format!("http://{}", listener.local_addr()?),
+            policy_asset: utils::lbtc_asset_id(LiquidNetwork::Regtest),
+            keypair: utils::generate_keypair(),
+            blinding_keypair: utils::generate_keypair(),
+            utxos: Mutex::new(vec![]),
+            accepted_assets: Mutex::new(vec![]),
+            price: Mutex::new(DEFAULT_PRICE),
+            fixed_fee: Mutex::new(DEFAULT_FIXED_FEE),
+            swap_orders: Mutex::new(HashMap::new()),
+            swap_txs: Mutex::new(vec![]),
+        });
+
+        let cloned_state = state.clone();
+        let handle = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        let state = cloned_state.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = handle_connection(state, stream).await {
+                                warn!("Mock SideSwap server connection failed: {e:?}");
+                            }
+                        });
+                    }
+                    Err(e) => warn!("Mock SideSwap server failed to accept connection: {e:?}"),
+                }
+            }
+        });
+
+        Ok(Self { state, handle })
+    }
+
+    /// The base url of the server, to be set as [Config::sideswap_url](crate::model::Config::sideswap_url)
+    pub fn url(&self) -> String {
+        self.state.url.clone()
+    }
+
+    /// The unconfidential address of the server wallet, which can be funded on regtest
+    pub fn address(&self) -> Address {
+        self.state.address()
+    }
+
+    /// Adds an unblinded output of the server wallet address to the spendable utxos
+    pub fn add_utxo(&self, txid: Txid, vout: u32, asset_id: AssetId, value: u64) {
+        self.state.utxos.lock().unwrap().push(Utxo {
+            txid,
+            vout,
+            script_pub_key: self.address().script_pubkey(),
+            asset_id,
+            value,
+            asset_bf: AssetBlindingFactor::zero(),
+            value_bf: ValueBlindingFactor::zero(),
+        });
+    }
+
+    /// Sets the assets accepted to pay payjoin fees
+    pub fn set_accepted_assets(&self, accepted_assets: Vec<AssetId>) {
+        *self.state.accepted_assets.lock().unwrap() = accepted_assets;
+    }
+
+    /// Sets the exchange rate, in asset units per L-BTC
+    pub fn set_price(&self, price: f64) {
+        *self.state.price.lock().unwrap() = price;
+    }
+
+    /// Sets the fixed fee, in satoshi units of the fee asset for payjoins and of L-BTC for swaps
+    pub fn set_fixed_fee(&self, fixed_fee: u64) {
+        *self.state.fixed_fee.lock().unwrap() = fixed_fee;
+    }
+
+    /// The transactions of the asset swaps completed by the server. Unlike SideSwap, the server
+    /// does not broadcast them.
+    pub fn swap_txs(&self) -> Vec<Transaction> {
+        self.state.swap_txs.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockSideSwapServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+impl ServerState {
+    fn public_key(&self) -> bitcoin::PublicKey {
+        bitcoin::PublicKey::new(self.keypair.public_key())
+    }
+
+    fn address(&self) -> Address {
+        Address::p2wpkh(&self.public_key(), None, &AddressParams::ELEMENTS)
+    }
+
+    fn blinded_address(&self) -> Address {
+        Address::p2wpkh(
+            &self.public_key(),
+            Some(self.blinding_keypair.public_key()),
+            &AddressParams::ELEMENTS,
+        )
+    }
+
+    fn price(&self) -> f64 {
+        *self.price.lock().unwrap()
+    }
+
+    fn fixed_fee(&self) -> u64 {
+        *self.fixed_fee.lock().unwrap()
+    }
+
+    /// Selects server utxos of the asset until their value covers the target
+    fn select_utxos(&self, asset_id: AssetId, target: u64) -> Result<Vec<Utxo>> {
+        let mut selected = vec![];
+        let mut value = 0;
+        for utxo in self.utxos.lock().unwrap().iter() {
+            if value >= target {
+                break;
+            }
+            if utxo.asset_id == asset_id {
+                value += utxo.value;
+                selected.push(utxo.clone());
+            }
+        }
+        ensure!(
+            value >= target,
+            "Server has insufficient funds of {asset_id}"
+        );
+        Ok(selected)
+    }
+
+    /// Signs the inputs spending server utxos, removing them from the wallet
+    fn sign_pset(&self, pset: &mut PartiallySignedTransaction) -> Result<()> {
+        let tx = pset
+            .extract_tx()
+            .map_err(|e| anyhow!("Failed to extract transaction: {e:?}"))?;
+        let public_key = self.public_key();
+        let script_pubkey = self.address().script_pubkey();
+        let script_code = Script::new_p2pkh(&elements::PubkeyHash::hash(&public_key.to_bytes()));
+        let mut sighash_cache = elements::sighash::SighashCache::new(&tx);
+        let mut utxos = self.utxos.lock().unwrap();
+        for (index, input) in pset.inputs_mut().iter_mut().enumerate() {
+            let Some(witness_utxo) = input
+                .witness_utxo
+                .as_ref()
+                .filter(|txout| txout.script_pubkey == script_pubkey)
+            else {
+                continue;
+            };
+            let sighash = sighash_cache.segwitv0_sighash(
+                index,
+                &script_code,
+                witness_utxo.value,
+                elements::EcdsaSighashType::All,
+            );
+            let signature = Secp256k1::new().sign_ecdsa(
+                &Message::from_digest(sighash.to_byte_array()),
+                &self.keypair.secret_key(),
+            );
+            let mut signature = signature.serialize_der().to_vec();
+            signature.push(elements::EcdsaSighashType::All as u8);
+            input.final_script_witness = Some(vec![signature, public_key.to_bytes()]);
+            utxos.retain(|utxo| {
+                utxo.txid != input.previous_txid || utxo.vout != input.previous_output_index
+            });
+        }
+        Ok(())
+    }
+
+    async fn handle_request(&self, method: &str, path: &str, body: &str) -> Result<Value> {
+        match (method, path) {
+            ("POST", "/payjoin") => self.handle_payjoin_request(serde_json::from_str(body)?),
+            ("POST", "/swap") => self.handle_swap_request(serde_json::from_str(body)?),
+            _ => bail!("Unknown endpoint: {method} {path}"),
+        }
+    }
+
+    fn handle_payjoin_request(&self, req: PayjoinRequest) -> Result<Value> {
+        let res = match req {
+            PayjoinRequest::AcceptedAssets(_) => {
+                PayjoinResponse::AcceptedAssets(AcceptedAssetsResponse {
+                    accepted_asset: self
+                        .accepted_assets
+                        .lock()
+                        .unwrap()
+                        .iter()
+                        .map(|asset_id| AcceptedAsset {
+                            asset_id: asset_id.to_string(),
+                        })
+                        .collect(),
+                })
+            }
+            PayjoinRequest::Start(req) => {
+                let fee_asset = AssetId::from_str(&req.asset_id)?;
+                ensure!(
+                    self.accepted_assets.lock().unwrap().contains(&fee_asset),
+                    "Asset {fee_asset} is not accepted"
+                );
+                let utxos: Vec<Utxo> = self
+                    .utxos
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|utxo| utxo.asset_id == self.policy_asset)
+                    .cloned()
+                    .collect();
+                PayjoinResponse::Start(StartResponse {
+                    order_id: utils::generate_entropy().to_hex(),
+                    expires_at: utils::now() as u64 + 60,
+                    price: self.price(),
+                    fixed_fee: self.fixed_fee(),
+                    fee_address: self.blinded_address(),
+                    change_address: self.blinded_address(),
+                    utxos,
+                })
+            }
+            PayjoinRequest::Sign(req) => {
+                let mut pset = elements::encode::deserialize::<PartiallySignedTransaction>(
+                    &base64::engine::general_purpose::STANDARD.decode(&req.pset)?,
+                )?;
+                self.sign_pset(&mut pset)?;
+                PayjoinResponse::Sign(SignResponse {
+                    pset: base64::engine::general_purpose::STANDARD
+                        .encode(elements::encode::serialize(&pset)),
+                })
+            }
+        };
+        Ok(json!(res))
+    }
+
+    fn handle_ws_request(&self, req: Request) -> Result<Value> {
+        let res = match req {
+            Request::Ping(_) => Value::Null,
+            Request::LoginClient(_) => json!({ "token": utils::generate_entropy().to_hex() }),
+            Request::SubscribePriceStream(req) => {
+                let price = self.price();
+                let fixed_fee = self.fixed_fee();
+                let (send_amount, recv_amount) = match (req.send_amount, req.recv_amount) {
+                    (Some(send_amount), None) => {
+                        let recv_amount = match req.send_bitcoins {
+                            true => ((send_amount as u64).saturating_sub(fixed_fee)) as f64 * price,
+                            false => (send_amount as f64 / price) - fixed_fee as f64,
+                        };
+                        (send_amount, recv_amount.floor() as i64)
+                    }
+                    (None, Some(recv_amount)) => {
+                        let send_amount = match req.send_bitcoins {
+                            true => (recv_amount as f64 / price).ceil() + fixed_fee as f64,
+                            false => (recv_amount as u64 + fixed_fee) as f64 * price,
+                        };
+                        (send_amount.ceil() as i64, recv_amount)
+                    }
+                    _ => bail!("Expected either the send or the receive amount"),
+                };
+                let error_msg = (send_amount <= 0 || recv_amount <= 0)
+                    .then_some("Amount is too low".to_string());
+                json!({
+                    "subscribe_id": req.subscribe_id,
+                    "asset": req.asset,
+                    "send_bitcoins": req.send_bitcoins,
+                    "send_amount": send_amount,
+                    "recv_amount": recv_amount,
+                    "fixed_fee": fixed_fee,
+                    "price": price,
+                    "error_msg": error_msg,
+                })
+            }
+            Request::StartSwapWeb(req) => self.start_swap(req)?,
+            Request::UnsubscribePriceStream(_) => json!({}),
+        };
+        Ok(res)
+    }
+
+    fn start_swap(&self, req: StartSwapWebRequest) -> Result<Value> {
+        let (send_asset, recv_asset) = match req.send_bitcoins {
+            true => (self.policy_asset, req.asset),
+            false => (req.asset, self.policy_asset),
+        };
+        let order_id = utils::generate_entropy().to_hex();
+        self.swap_orders.lock().unwrap().insert(
+            order_id.clone(),
+            SwapOrder {
+                send_asset,
+                send_amount: req.send_amount as u64,
+                recv_asset,
+                recv_amount: req.recv_amount as u64,
+            },
+        );
+        Ok(json!({
+            "order_id": order_id,
+            "send_asset": send_asset,
+            "send_amount": req.send_amount,
+            "recv_asset": recv_asset,
+            "recv_amount": req.recv_amount,
+            "upload_url": format!("{}/swap", self.url),
+        }))
+    }
+
+    fn handle_swap_request(&self, req: HttpRequest) -> Result<Value> {
+        let req = match req {
+            HttpRequest::SwapStart(req) => return self.swap_start(req),
+            req => req,
+        };
+        let HttpRequest::SwapSign(req) = req else {
+            bail!("Unsupported swap request");
+        };
+        self.swap_sign(req)
+    }
+
+    fn swap_start(&self, req: SwapStartRequest) -> Result<Value> {
+        let order_id = req.order_id.to_string();
+        let swap_orders = self.swap_orders.lock().unwrap();
+        let order = swap_orders
+            .get(&order_id)
+            .ok_or(anyhow!("Could not find order {order_id}"))?;
+        ensure!(
+            order.send_asset == req.send_asset
+                && order.send_amount == req.send_amount as u64
+                && order.recv_asset == req.recv_asset
+                && order.recv_amount == req.recv_amount as u64,
+            "Swap request does not match order {order_id}"
+        );
+
+        let secp = Secp256k1::new();
+        let mut inputs = vec![];
+        let mut user_value = 0;
+        for utxo in &req.inputs {
+            ensure!(utxo.asset == order.send_asset, "Unexpected input asset");
+            user_value += utxo.value;
+            let (asset_commitment, value_commitment) = if utxo.asset_bf
+                == AssetBlindingFactor::zero()
+                || utxo.value_bf == ValueBlindingFactor::zero()
+            {
+                (
+                    confidential::Asset::Explicit(utxo.asset),
+                    confidential::Value::Explicit(utxo.value),
+                )
+            } else {
+                let gen = Generator::new_blinded(
+                    &secp,
+                    utxo.asset.into_tag(),
+                    utxo.asset_bf.into_inner(),
+                );
+                (
+                    confidential::Asset::Confidential(gen),
+                    confidential::Value::new_confidential(&secp, utxo.value, gen, utxo.value_bf),
+                )
+            };
+            inputs.push(PsetInput {
+                txid: utxo.txid,
+                vout: utxo.vout,
+                script_pub_key: Script::new(),
+                asset_commitment,
+                value_commitment,
+                tx_out_sec: TxOutSecrets {
+                    asset: utxo.asset,
+                    asset_bf: utxo.asset_bf,
+                    value: utxo.value,
+                    value_bf: utxo.value_bf,
+                },
+            });
+        }
+        ensure!(
+            user_value >= order.send_amount,
+            "Inputs do not cover the send amount"
+        );
+
+        // The server pays the network fees in L-BTC
+        let server_utxo_count = self.utxos.lock().unwrap().len();
+        let network_fee = TxFee {
+            native_inputs: server_utxo_count,
+            nested_inputs: inputs.len(),
+            outputs: 6,
+        }
+        .fee(None);
+        let mut server_targets = HashMap::from([(order.recv_asset, order.recv_amount)]);
+        *server_targets.entry(self.policy_asset).or_default() += network_fee;
+
+        let mut outputs = vec![
+            PsetOutput {
+                address: req.recv_addr.clone(),
+                asset_id: order.recv_asset,
+                amount: order.recv_amount,
+            },
+            PsetOutput {
+                address: self.blinded_address(),
+                asset_id: order.send_asset,
+                amount: order.send_amount,
+            },
+        ];
+        if user_value > order.send_amount {
+            outputs.push(PsetOutput {
+                address: req.change_addr.clone(),
+                asset_id: order.send_asset,
+                amount: user_value - order.send_amount,
+            });
+        }
+        for (asset_id, target) in server_targets {
+            let utxos = self.select_utxos(asset_id, target)?;
+            let value: u64 = utxos.iter().map(|utxo| utxo.value).sum();
+            if value > target {
+                outputs.push(PsetOutput {
+                    address: self.blinded_address(),
+                    asset_id,
+                    amount: value - target,
+                });
+            }
+            inputs.extend(utxos.into_iter().map(|utxo| PsetInput {
+                txid: utxo.txid,
+                vout: utxo.vout,
+                script_pub_key: utxo.script_pub_key,
+                asset_commitment: confidential::Asset::Explicit(utxo.asset_id),
+                value_commitment: confidential::Value::Explicit(utxo.value),
+                tx_out_sec: TxOutSecrets {
+                    asset: utxo.asset_id,
+                    asset_bf: utxo.asset_bf,
+                    value: utxo.value,
+                    value_bf: utxo.value_bf,
+                },
+            }));
+        }
+
+        let pset = construct_pset(ConstructPsetRequest {
+            policy_asset: self.policy_asset,
+            inputs,
+            outputs,
+            network_fee,
+        })?;
+        Ok(json!({
+            "swap_start": {
+                "submit_id": utils::generate_entropy().to_hex(),
+                "pset": base64::engine::general_purpose::STANDARD
+                    .encode(elements::encode::serialize(&pset)),
+            }
+        }))
+    }
+
+    fn swap_sign(&self, req: SwapSignRequest) -> Result<Value> {
+        let order_id = req.order_id.to_string();
+        ensure!(
+            self.swap_orders.lock().unwrap().remove(&order_id).is_some(),
+            "Could not find order {order_id}"
+        );
+        let mut pset = PartiallySignedTransaction::from_str(&req.pset)?;
+        self.sign_pset(&mut pset)?;
+        let tx = pset
+            .extract_tx()
+            .map_err(|e| anyhow!("Failed to extract transaction: {e:?}"))?;
+        let txid = tx.txid();
+        self.swap_txs.lock().unwrap().push(tx);
+        Ok(json!({ "swap_sign": { "txid": txid } }))
+    }
+}
+
+async fn handle_connection(state: Arc<ServerState>, mut stream: TcpStream) -> Result<()> {
+    match http::read_request(&mut stream).await? {
+        IncomingRequest::Closed => Ok(()),
+        IncomingRequest::WebSocket => handle_ws(state, stream).await,
+        IncomingRequest::Http { method, path, body } => {
+            debug!("Mock SideSwap server received request: {method} {path} {body}");
+            let res = state.handle_request(&method, &path, &body).await;
+            http::write_response(&mut stream, res).await
+        }
+    }
+}
+
+async fn handle_ws(state: Arc<ServerState>, stream: TcpStream) -> Result<()> {
+    let ws_stream = tokio_tungstenite::accept_async(stream).await?;
+    let (mut sender, mut receiver) = ws_stream.split();
+
+    loop {
+        match receiver.next().await {
+            Some(Ok(WsMessage::Text(payload))) => {
+                debug!("Mock SideSwap server received ws request: {payload}");
+                let WrappedRequest { id, request } = serde_json::from_str(payload.as_str())?;
+                let method = serde_json::to_value(&request)?
+                    .get("method")
+                    .cloned()
+                    .unwrap_or_default();
+                let res = match state.handle_ws_request(request) {
+                    Ok(result) => json!({ "id": id, "method": method, "result": result }),
+                    Err(e) => json!({
+                        "id": id,
+                        "error": { "code": "ServerError", "message": e.to_string() },
+                    }),
+                };
+                sender.send(WsMessage::Text(res.to_string().into())).await?;
+            }
+            Some(Ok(WsMessage::Close(_))) | None => return Ok(()),
+            Some(Ok(_)) => {}
+            Some(Err(e)) => return Err(anyhow!("Websocket stream failed: {e:?}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use anyhow::Result;
+    use lwk_wollet::{Chain, WalletTxOut};
+    use sdk_common::prelude::{BreezServer, ReqwestRestClient, STAGING_BREEZSERVER_URL};
+    use tokio::sync::watch;
+
+    use super::MockSideSwapServer;
+    use crate::{
+        elements::{
+            confidential::{AssetBlindingFactor, ValueBlindingFactor},
+            Address, AddressParams, AssetId, OutPoint, Script, TxOutSecrets, Txid,
+        },
+        model::{Config, LiquidNetwork, Signer},
+        payjoin::{side_swap::SideSwapPayjoinService, PayjoinService},
+        side_swap::api::SideSwapService,
+        test_utils::{
+            persist::create_persister,
+            wallet::{MockSigner, MockWallet},
+        },
+        utils,
+    };
+
+    fn new_wallet(asset_id: AssetId, value: u64) -> Result<Arc<MockWallet>> {
+        let signer: Arc<Box<dyn Signer>> = Arc::new(Box::new(MockSigner::new()?));
+        let wallet = Arc::new(MockWallet::new(signer)?);
+        wallet.set_utxos(vec![WalletTxOut {
+            outpoint: OutPoint::new(
+                Txid::from_str("0000000000000000000000000000000000000000000000000000000000000001")?,
+                0,
+            ),
+            script_pubkey: Script::new(),
+            height: Some(10),
+            unblinded: TxOutSecrets {
+                asset: asset_id,
+                value,
+                asset_bf: AssetBlindingFactor::zero(),
+                value_bf: ValueBlindingFactor::zero(),
+            },
+            wildcard_index: 0,
+            ext_int: Chain::Internal,
+            is_spent: false,
+            address: recipient_address(),
+        }]);
+        Ok(wallet)
+    }
+
+    fn new_config(server: &MockSideSwapServer) -> Config {
+        Config {
+            sideswap_url: Some(server.url()),
+            ..Config::regtest_esplora()
+        }
+    }
+
+    fn recipient_address() -> Address {
+        let keypair = utils::generate_keypair();
+        Address::p2wpkh(
+            &crate::bitcoin::PublicKey::new(keypair.public_key()),
+            Some(utils::generate_keypair().public_key()),
+            &AddressParams::ELEMENTS,
+        )
+    }
+
+    fn server_txid() -> Result<Txid> {
+        Ok(Txid::from_str(
+            "0000000000000000000000000000000000000000000000000000000000000002",
+        )?)
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_mock_server_payjoin() -> Result<()> {
+        create_persister!(persister);
+        let server = MockSideSwapServer::start().await?;
+        let asset_id = AssetId::from_slice(&[2; 32])?;
+        let lbtc_asset_id = utils::lbtc_asset_id(LiquidNetwork::Regtest);
+        server.set_accepted_assets(vec![asset_id]);
+        server.add_utxo(server_txid()?, 0, lbtc_asset_id, 10_000);
+
+        let wallet = new_wallet(asset_id, 1_000_000)?;
+        let payjoin_service = SideSwapPayjoinService::new(
+            new_config(&server),
+            Arc::new(BreezServer::new(STAGING_BREEZSERVER_URL.to_string(), None)?),
+            persister,
+            wallet,
+            Arc::new(ReqwestRestClient::new()?),
+        );
+
+        let accepted_assets = payjoin_service.fetch_accepted_assets().await?;
+        assert_eq!(accepted_assets.len(), 1);
+        assert_eq!(accepted_assets[0].asset_id, asset_id.to_string());
+
+        let (tx, fee_sat) = payjoin_service
+            .build_payjoin_tx(
+                &recipient_address().to_string(),
+                &asset_id.to_string(),
+                100_000,
+            )
+            .await?;
+        assert!(fee_sat > 0);
+        // Only the server input is signed, by the server
+        let server_input = tx
+            .input
+            .iter()
+            .find(|input| input.previous_output.txid == server_txid().unwrap())
+            .unwrap();
+        assert_eq!(server_input.witness.script_witness.len(), 2);
+
+        // Spent server utxos cannot be used for further payjoins
+        assert!(payjoin_service
+            .build_payjoin_tx(
+                &recipient_address().to_string(),
+                &asset_id.to_string(),
+                100_000,
+            )
+            .await
+            .is_err());
+
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_mock_server_asset_swap() -> Result<()> {
+        let server = MockSideSwapServer::start().await?;
+        let asset_id = AssetId::from_slice(&[2; 32])?;
+        let lbtc_asset_id = utils::lbtc_asset_id(LiquidNetwork::Regtest);
+        server.set_price(0.5);
+        server.add_utxo(server_txid()?, 0, asset_id, 100_000);
+        server.add_utxo(server_txid()?, 1, lbtc_asset_id, 10_000);
+
+        let (_shutdown_sender, shutdown_receiver) = watch::channel(());
+        let wallet = new_wallet(lbtc_asset_id, 1_000_000)?;
+        let sideswap_service = SideSwapService::new(
+            new_config(&server),
+            Arc::new(ReqwestRestClient::new()?),
+            wallet,
+            shutdown_receiver,
+        )
+        .await;
+
+        let swap = sideswap_service
+            .get_asset_swap(lbtc_asset_id, asset_id, 50_000)
+            .await?;
+        assert_eq!(swap.receiver_amount_sat, 50_000);
+        assert_eq!(swap.payer_amount_sat, 101_000);
+        assert_eq!(swap.fees_sat, 1_000);
+
+        let txid = sideswap_service
+            .execute_swap(recipient_address(), &swap)
+            .await?;
+        let swap_txs = server.swap_txs();
+        assert_eq!(swap_txs.len(), 1);
+        assert_eq!(swap_txs[0].txid().to_string(), txid);
+
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_sideswap_url() -> Result<()> {
+        let server = MockSideSwapServer::start().await?;
+        assert!(Config::regtest_esplora().sideswap_ws_url().is_err());
+        assert_eq!(
+            new_config(&server).sideswap_ws_url()?,
+            format!("{}/json-rpc-ws", server.url().replacen("http", "ws", 1))
+        );
+        Ok(())
+    }
+}
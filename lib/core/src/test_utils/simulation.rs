@@ -0,0 +1,488 @@
+use std::{
+    collections::BTreeMap,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+use bip39::rand;
+use lwk_wollet::{
+    elements::{
+        self,
+        confidential::{AssetBlindingFactor, ValueBlindingFactor},
+        hashes::Hash as _,
+        secp256k1_zkp::Secp256k1,
+        OutPoint, Sequence, Transaction, TxIn, TxOut, TxOutSecrets, Txid,
+    },
+    hashes::hex::DisplayHex,
+    Chain, WalletTx, WalletTxOut,
+};
+use sdk_common::prelude::{
+    CurrencyInfo, FiatAPI, FiatCurrency, Rate, ServiceConnectivityError, Symbol,
+};
+
+use super::{
+    chain::{MockBitcoinChainService, MockLiquidChainService},
+    faults::FaultInjector,
+    status_stream::MockStatusStream,
+    swapper::MockSwapper,
+    wallet::MockWallet,
+};
+use tokio_with_wasm::alias as tokio;
+
+use crate::{
+    model::{Config, LBtcHistory, PaymentState, ReceiveSwap, Signer},
+    sdk::LiquidSdk,
+    swapper::model::{RevSwapStates, SubSwapStates, SwapStatus, TransactionInfo},
+    utils,
+    wallet::OnchainWallet as _,
+};
+
+const UPDATE_POLL_ATTEMPTS: usize = 50;
+const UPDATE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Controls the in-memory network of an SDK built with
+/// [LiquidSdkBuilder::simulated](crate::sdk::LiquidSdkBuilder::simulated).
+///
+/// Lightning invoices created by the SDK can be paid with [Simulator::pay_invoice], while
+/// outgoing payments are settled with [Simulator::settle_pending_sends]. Blocks are only produced
+/// when calling [Simulator::mine_block].
+pub struct Simulator {
+    config: Config,
+    tip: Mutex<u32>,
+    pub(crate) swapper: Arc<MockSwapper>,
+    pub(crate) status_stream: Arc<MockStatusStream>,
+    pub(crate) liquid_chain_service: Arc<MockLiquidChainService>,
+    pub(crate) bitcoin_chain_service: Arc<MockBitcoinChainService>,
+    pub(crate) onchain_wallet: Arc<MockWallet>,
+    pub(crate) fiat_api: Arc<SimulatedFiatAPI>,
+    faults: Arc<FaultInjector>,
+}
+
+impl Simulator {
+    pub(crate) fn new(config: Config, signer: Arc<Box<dyn Signer>>) -> Result<Self> {
+        let faults = Arc::new(FaultInjector::new());
+        Ok(Self {
+            config,
+            tip: Mutex::new(0),
+            swapper: Arc::new(MockSwapper::new().with_faults(faults.clone())),
+            status_stream: Arc::new(MockStatusStream::new()),
+            liquid_chain_service: Arc::new(
+                MockLiquidChainService::new().with_faults(faults.clone()),
+            ),
+            bitcoin_chain_service: Arc::new(
+                MockBitcoinChainService::new().with_faults(faults.clone()),
+            ),
+            onchain_wallet: Arc::new(MockWallet::new(signer)?),
+            fiat_api: Arc::new(SimulatedFiatAPI {}),
+            faults,
+        })
+    }
+
+    /// The fault injector shared by the simulated swapper and chain services
+    pub fn faults(&self) -> Arc<FaultInjector> {
+        self.faults.clone()
+    }
+
+    /// The height of the last mined block
+    pub fn tip(&self) -> u32 {
+        *self.tip.lock().unwrap()
+    }
+
+    /// Mines a block on both the Liquid and the Bitcoin chain, returning the new height
+    pub fn mine_block(&self) -> u32 {
+        let mut tip = self.tip.lock().unwrap();
+        *tip += 1;
+        self.liquid_chain_service.set_tip(*tip);
+        self.bitcoin_chain_service.set_tip(*tip);
+        *tip
+    }
+
+    /// Credits the wallet with a confirmed L-BTC transaction of the given amount
+    pub async fn fund_wallet(&self, sdk: &LiquidSdk, amount_sat: u64) -> Result<String> {
+        let tx_id = self.add_wallet_tx(amount_sat as i64);
+        sdk.sync(false).await?;
+        Ok(tx_id.to_string())
+    }
+
+    /// Credits the wallet with a confirmed transaction of the given amount of an asset, whose
+    /// output is added to the wallet utxos
+    pub async fn fund_wallet_with_asset(
+        &self,
+        sdk: &LiquidSdk,
+        asset_id: &str,
+        amount_sat: u64,
+    ) -> Result<String> {
+        let tx_id = random_txid();
+        let asset = elements::AssetId::from_str(asset_id)?;
+        let mut tx = self.wallet_tx(tx_id, 0);
+        tx.balance = BTreeMap::from([(asset, amount_sat as i64)]);
+        self.onchain_wallet.add_transaction(tx);
+
+        let address = self.onchain_wallet.next_unused_address().await?;
+        self.onchain_wallet.add_utxo(WalletTxOut {
+            outpoint: OutPoint::new(tx_id, 0),
+            script_pubkey: address.script_pubkey(),
+            height: Some(self.tip()),
+            unblinded: TxOutSecrets::new(
+                asset,
+                AssetBlindingFactor::zero(),
+                amount_sat,
+                ValueBlindingFactor::zero(),
+            ),
+            wildcard_index: 0,
+            ext_int: Chain::External,
+            is_spent: false,
+            address,
+        });
+        sdk.sync(false).await?;
+        Ok(tx_id.to_string())
+    }
+
+    /// Pays into the wallet with an L-BTC transaction confirmed in the last mined block. Unlike
+    /// [Simulator::fund_wallet], the tx has an unblinded wallet output, so the SDK persists it as
+    /// a Liquid payment.
+    pub async fn receive_liquid_payment(&self, sdk: &LiquidSdk, amount_sat: u64) -> Result<String> {
+        let tx_id = random_txid();
+        let mut tx = self.wallet_tx(tx_id, amount_sat as i64);
+        let address = self.onchain_wallet.next_unused_address().await?;
+        tx.outputs = vec![Some(WalletTxOut {
+            outpoint: OutPoint::new(tx_id, 0),
+            script_pubkey: address.script_pubkey(),
+            height: tx.height,
+            unblinded: TxOutSecrets::new(
+                utils::lbtc_asset_id(self.config.network),
+                AssetBlindingFactor::zero(),
+                amount_sat,
+                ValueBlindingFactor::zero(),
+            ),
+            wildcard_index: 0,
+            ext_int: Chain::External,
+            is_spent: false,
+            address,
+        })];
+        self.onchain_wallet.add_transaction(tx);
+        sdk.sync(false).await?;
+        Ok(tx_id.to_string())
+    }
+
+    /// Pays a Lightning invoice created by [LiquidSdk::receive_payment] from outside the wallet.
+    ///
+    /// The swapper locks up the funds and reports it with a status update, which the SDK handles
+    /// by claiming them. The lockup and the claim are then confirmed in a new block. The SDK has
+    /// to be started for the status update to be handled.
+    pub async fn pay_invoice(&self, sdk: &LiquidSdk, invoice: &str) -> Result<()> {
+        let swap = sdk
+            .persister
+            .fetch_receive_swap_by_invoice(invoice)?
+            .ok_or(anyhow!("No receive payment found for invoice"))?;
+        let lockup_tx = self.receive_lockup_tx(&swap)?;
+        let lockup_tx_id = lockup_tx.txid();
+        let lockup_tx_info = TransactionInfo {
+            id: lockup_tx_id.to_string(),
+            hex: Some(elements::encode::serialize(&lockup_tx).to_lower_hex_string()),
+        };
+
+        // Amounts above the zero-conf limit are only claimed once the lockup is confirmed
+        self.send_swap_update(&swap.id, RevSwapStates::TransactionMempool, &lockup_tx_info)
+            .await?;
+        let claim_tx_id = match self.wait_for_receive_claim(sdk, &swap.id).await? {
+            Some(claim_tx_id) => claim_tx_id,
+            None => {
+                self.mine_block();
+                self.send_swap_update(
+                    &swap.id,
+                    RevSwapStates::TransactionConfirmed,
+                    &lockup_tx_info,
+                )
+                .await?;
+                self.wait_for_receive_claim(sdk, &swap.id)
+                    .await?
+                    .ok_or(anyhow!("Receive swap {} was not claimed", swap.id))?
+            }
+        };
+
+        let claim_tx_id = Txid::from_str(&claim_tx_id)?;
+        let height = self.mine_block() as i32;
+        self.liquid_chain_service.set_script_history(
+            swap.claim_script()?,
+            vec![
+                LBtcHistory {
+                    txid: lockup_tx_id,
+                    height,
+                },
+                LBtcHistory {
+                    txid: claim_tx_id,
+                    height,
+                },
+            ],
+        );
+        self.onchain_wallet
+            .add_transaction(self.wallet_tx(claim_tx_id, swap.receiver_amount_sat as i64));
+        sdk.sync(false).await?;
+        Ok(())
+    }
+
+    /// Settles all outgoing Lightning payments awaiting the recipient, returning their number.
+    ///
+    /// The swapper reports each claim with a status update, which the SDK handles by completing
+    /// the payment. The SDK has to be started for the status updates to be handled.
+    pub async fn settle_pending_sends(&self, sdk: &LiquidSdk) -> Result<usize> {
+        let swaps = sdk.persister.list_pending_send_swaps()?;
+        for swap in &swaps {
+            let lockup_tx_id = match &swap.lockup_tx_id {
+                Some(lockup_tx_id) => Txid::from_str(lockup_tx_id)?,
+                None => random_txid(),
+            };
+            self.onchain_wallet
+                .add_transaction(self.wallet_tx(lockup_tx_id, -(swap.payer_amount_sat as i64)));
+
+            self.send_swap_update(
+                &swap.id,
+                SubSwapStates::TransactionClaimPending,
+                &TransactionInfo {
+                    id: lockup_tx_id.to_string(),
+                    hex: None,
+                },
+            )
+            .await?;
+            self.wait_for(|| {
+                Ok(sdk
+                    .persister
+                    .fetch_send_swap_by_id(&swap.id)?
+                    .filter(|swap| swap.state == PaymentState::Complete))
+            })
+            .await?
+            .ok_or(anyhow!("Send swap {} was not settled", swap.id))?;
+        }
+        sdk.sync(false).await?;
+        Ok(swaps.len())
+    }
+
+    async fn send_swap_update(
+        &self,
+        swap_id: &str,
+        status: impl ToString,
+        transaction: &TransactionInfo,
+    ) -> Result<()> {
+        self.status_stream
+            .clone()
+            .send_mock_update(SwapStatus {
+                id: swap_id.to_string(),
+                status: status.to_string(),
+                transaction: Some(transaction.clone()),
+                ..Default::default()
+            })
+            .await
+    }
+
+    async fn wait_for_receive_claim(
+        &self,
+        sdk: &LiquidSdk,
+        swap_id: &str,
+    ) -> Result<Option<String>> {
+        self.wait_for(|| {
+            Ok(sdk
+                .persister
+                .fetch_receive_swap_by_id(swap_id)?
+                .and_then(|swap| swap.claim_tx_id))
+        })
+        .await
+    }
+
+    /// Polls `check` until it returns a value, as status updates are handled asynchronously
+    async fn wait_for<T>(&self, check: impl Fn() -> Result<Option<T>>) -> Result<Option<T>> {
+        for _ in 0..UPDATE_POLL_ATTEMPTS {
+            if let Some(value) = check()? {
+                return Ok(Some(value));
+            }
+            tokio::time::sleep(UPDATE_POLL_INTERVAL).await;
+        }
+        Ok(None)
+    }
+
+    /// Builds a lockup tx paying the receive swap script, blinded to the swap blinding key
+    fn receive_lockup_tx(&self, swap: &ReceiveSwap) -> Result<Transaction> {
+        let address = swap
+            .get_swap_script()?
+            .to_address(self.config.network.into())
+            .map_err(|e| anyhow!("Failed to get swap script address {e:?}"))?;
+        let asset = utils::lbtc_asset_id(self.config.network);
+        let amount_sat = swap.receiver_amount_sat + swap.claim_fees_sat;
+        let spent_utxo_secrets = TxOutSecrets::new(
+            asset,
+            AssetBlindingFactor::zero(),
+            amount_sat,
+            ValueBlindingFactor::zero(),
+        );
+        let (output, ..) = TxOut::new_not_last_confidential(
+            &mut rand::thread_rng(),
+            &Secp256k1::new(),
+            amount_sat,
+            address,
+            asset,
+            &[spent_utxo_secrets],
+        )?;
+        Ok(Transaction {
+            version: 2,
+            lock_time: elements::LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::new(random_txid(), 0),
+                sequence: Sequence::MAX,
+                ..Default::default()
+            }],
+            output: vec![output],
+        })
+    }
+
+    fn add_wallet_tx(&self, amount_sat: i64) -> Txid {
+        let tx_id = random_txid();
+        self.onchain_wallet
+            .add_transaction(self.wallet_tx(tx_id, amount_sat));
+        tx_id
+    }
+
+    fn wallet_tx(&self, tx_id: Txid, amount_sat: i64) -> WalletTx {
+        WalletTx {
+            txid: tx_id,
+            tx: Transaction {
+                version: 2,
+                lock_time: elements::LockTime::ZERO,
+                input: vec![],
+                output: vec![],
+            },
+            height: Some(self.tip()),
+            fee: 0,
+            timestamp: Some(utils::now()),
+            balance: BTreeMap::from([(utils::lbtc_asset_id(self.config.network), amount_sat)]),
+            outputs: vec![],
+            inputs: vec![],
+            type_: "".to_string(),
+        }
+    }
+}
+
+fn random_txid() -> Txid {
+    Txid::from_byte_array(bip39::rand::random())
+}
+
+/// Serves fixed fiat rates
+pub(crate) struct SimulatedFiatAPI {}
+
+#[sdk_macros::async_trait]
+impl FiatAPI for SimulatedFiatAPI {
+    async fn list_fiat_currencies(&self) -> Result<Vec<FiatCurrency>, ServiceConnectivityError> {
+        Ok(vec![
+            simulated_currency("EUR", "Euro", "€"),
+            simulated_currency("USD", "United States Dollar", "$"),
+        ])
+    }
+
+    async fn fetch_fiat_rates(&self) -> Result<Vec<Rate>, ServiceConnectivityError> {
+        Ok(vec![
+            Rate {
+                coin: "EUR".to_string(),
+                value: 90_000.0,
+            },
+            Rate {
+                coin: "USD".to_string(),
+                value: 100_000.0,
+            },
+        ])
+    }
+}
+
+fn simulated_currency(id: &str, name: &str, grapheme: &str) -> FiatCurrency {
+    FiatCurrency {
+        id: id.to_string(),
+        info: CurrencyInfo {
+            name: name.to_string(),
+            fraction_size: 2,
+            spacing: None,
+            symbol: Some(Symbol {
+                grapheme: Some(grapheme.to_string()),
+                template: None,
+                rtl: None,
+                position: None,
+            }),
+            uniq_symbol: None,
+            localized_name: vec![],
+            locale_overrides: vec![],
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use anyhow::{anyhow, Result};
+
+    use crate::{
+        model::{
+            Config, ListPaymentsRequest, PaymentMethod, PaymentState, PaymentType,
+            PrepareReceiveRequest, ReceiveAmount, ReceivePaymentRequest, Signer,
+        },
+        sdk::LiquidSdkBuilder,
+        test_utils::{persist::create_persister, wallet::MockSigner},
+    };
+
+    #[cfg(feature = "browser-tests")]
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    #[sdk_macros::async_test_all]
+    async fn test_simulated_receive() -> Result<()> {
+        create_persister!(persister);
+        let signer: Arc<Box<dyn Signer>> = Arc::new(Box::new(MockSigner::new()?));
+        let mut config = Config::regtest_esplora();
+        config.working_dir = persister
+            .get_database_dir()
+            .to_str()
+            .ok_or(anyhow!("An invalid SDK directory was specified"))?
+            .to_string();
+
+        let (mut builder, simulator) = LiquidSdkBuilder::simulated(config, signer)?;
+        builder.persister(persister);
+        let sdk = builder.build().await?;
+        sdk.start().await?;
+
+        assert_eq!(simulator.mine_block(), 1);
+        assert!(!sdk.fetch_fiat_rates().await?.is_empty());
+
+        simulator.fund_wallet(&sdk, 100_000).await?;
+        assert_eq!(sdk.get_info().await?.wallet_info.balance_sat, 100_000);
+
+        let prepare_response = sdk
+            .prepare_receive_payment(&PrepareReceiveRequest {
+                payment_method: PaymentMethod::Bolt11Invoice,
+                amount: Some(ReceiveAmount::Bitcoin {
+                    payer_amount_sat: 50_000,
+                }),
+                swap_provider: None,
+            })
+            .await?;
+        let receive_response = sdk
+            .receive_payment(&ReceivePaymentRequest {
+                prepare_response,
+                description: Some("Simulated".to_string()),
+                description_hash: None,
+                payer_note: None,
+                settle_as_asset: None,
+            })
+            .await?;
+        simulator
+            .pay_invoice(&sdk, &receive_response.destination)
+            .await?;
+
+        let payments = sdk.list_payments(&ListPaymentsRequest::default()).await?;
+        let received = payments
+            .iter()
+            .find(|p| p.payment_type == PaymentType::Receive && p.destination.is_some())
+            .ok_or(anyhow!("Expected a Lightning receive payment"))?;
+        assert_eq!(received.status, PaymentState::Complete);
+
+        sdk.disconnect().await?;
+        Ok(())
+    }
+}
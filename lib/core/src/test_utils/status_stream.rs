@@ -1,22 +1,23 @@
 use std::sync::Arc;
 
 use anyhow::Result;
-use boltz_client::boltz;
-
 use tokio::sync::{broadcast, watch};
 use tokio_with_wasm::alias as tokio;
 
-use crate::swapper::{SubscriptionHandler, SwapperStatusStream};
+use crate::swapper::{
+    model::{InvoiceRequest, SwapStatus},
+    SubscriptionHandler, SwapperStatusStream,
+};
 
 pub(crate) struct MockStatusStream {
-    pub update_notifier: broadcast::Sender<boltz::SwapStatus>,
-    pub invoice_request_notifier: broadcast::Sender<boltz::InvoiceRequest>,
+    pub update_notifier: broadcast::Sender<SwapStatus>,
+    pub invoice_request_notifier: broadcast::Sender<InvoiceRequest>,
 }
 
 impl MockStatusStream {
     pub(crate) fn new() -> Self {
-        let (update_notifier, _) = broadcast::channel::<boltz::SwapStatus>(30);
-        let (invoice_request_notifier, _) = broadcast::channel::<boltz::InvoiceRequest>(30);
+        let (update_notifier, _) = broadcast::channel::<SwapStatus>(30);
+        let (invoice_request_notifier, _) = broadcast::channel::<InvoiceRequest>(30);
 
         Self {
             update_notifier,
@@ -24,7 +25,7 @@ impl MockStatusStream {
         }
     }
 
-    pub(crate) async fn send_mock_update(self: Arc<Self>, update: boltz::SwapStatus) -> Result<()> {
+    pub(crate) async fn send_mock_update(self: Arc<Self>, update: SwapStatus) -> Result<()> {
         tokio::spawn(async move {
             self.update_notifier.send(update).unwrap();
         })
@@ -57,11 +58,11 @@ impl SwapperStatusStream for MockStatusStream {
         Ok(())
     }
 
-    fn subscribe_swap_updates(&self) -> broadcast::Receiver<boltz::SwapStatus> {
+    fn subscribe_swap_updates(&self) -> broadcast::Receiver<SwapStatus> {
         self.update_notifier.subscribe()
     }
 
-    fn subscribe_invoice_requests(&self) -> broadcast::Receiver<boltz::InvoiceRequest> {
+    fn subscribe_invoice_requests(&self) -> broadcast::Receiver<InvoiceRequest> {
         self.invoice_request_notifier.subscribe()
     }
 }
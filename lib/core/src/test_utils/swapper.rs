@@ -1,29 +1,63 @@
 use anyhow::Result;
+use bip39::rand::{self, RngCore};
 use boltz_client::{
     boltz::{
-        ChainFees, ChainMinerFees, ChainPair, ChainSwapDetails, CreateBolt12OfferRequest,
-        CreateChainResponse, CreateReverseResponse, CreateSubmarineResponse, GetBolt12FetchRequest,
-        GetBolt12FetchResponse, GetBolt12ParamsResponse, GetNodesResponse, Leaf, Node, PairLimits,
-        PairMinerFees, ReverseFees, ReverseLimits, ReversePair, SubmarineClaimTxResponse,
-        SubmarineFees, SubmarinePair, SubmarinePairLimits, SwapTree, UpdateBolt12OfferRequest,
+        ChainFees, ChainMinerFees, ChainPair, ChainSwapDetails, CreateChainResponse,
+        CreateReverseResponse, CreateSubmarineResponse, Leaf, PairLimits, PairMinerFees,
+        ReverseFees, ReverseLimits, ReversePair, SubmarineFees, SubmarinePair, SubmarinePairLimits,
+        SwapTree,
     },
+    elements::{
+        opcodes::all::{
+            OP_CHECKSIG, OP_CHECKSIGVERIFY, OP_CLTV, OP_EQUALVERIFY, OP_HASH160, OP_SIZE,
+        },
+        script::Builder,
+        OutPoint, Sequence, TxIn, Txid,
+    },
+    network::LiquidChain,
     util::secrets::Preimage,
-    Amount, PublicKey,
+    Amount, LBtcSwapScript, PublicKey,
 };
-use lwk_wollet::secp256k1;
-use sdk_common::invoice::parse_invoice;
+use lwk_wollet::{hashes::hex::DisplayHex, secp256k1};
 use sdk_common::prelude::BoltzSwapperUrls;
-use std::{collections::HashMap, str::FromStr, sync::Mutex};
+use sdk_common::{
+    bitcoin::{
+        hashes::{ripemd160, sha256, Hash},
+        secp256k1::{Secp256k1, SecretKey},
+    },
+    invoice::parse_invoice,
+    lightning::ln::PaymentSecret,
+    lightning_invoice::{Currency, InvoiceBuilder},
+};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
 
 use crate::{
     ensure_sdk,
     error::{PaymentError, SdkError},
     model::{Direction, SendSwap, Swap, Transaction as SdkTransaction, Utxo},
-    swapper::{ProxyUrlFetcher, Swapper},
-    test_utils::generate_random_string,
+    swapper::{
+        model::{
+            CreateBolt12OfferRequest, CreateChainRequest, CreateReverseRequest,
+            CreateSubmarineRequest, GetBolt12FetchRequest, GetBolt12FetchResponse,
+            GetBolt12ParamsResponse, GetNodesResponse, Node, SubmarineClaimTxResponse,
+            UpdateBolt12OfferRequest,
+        },
+        ProxyUrlFetcher, Swapper,
+    },
+    test_utils::{
+        faults::{FaultInjector, FaultTarget},
+        generate_random_string,
+    },
     utils,
 };
 
+/// The timeout of the reverse swaps created by the [MockSwapper]
+pub(crate) const MOCK_REVERSE_SWAP_TIMEOUT_BLOCK_HEIGHT: u32 = 1_000;
+
 #[derive(Default)]
 pub struct ZeroAmountSwapMockConfig {
     pub user_lockup_sat: u64,
@@ -33,6 +67,10 @@ pub struct ZeroAmountSwapMockConfig {
 #[derive(Default)]
 pub struct MockSwapper {
     zero_amount_swap_mock_config: Mutex<ZeroAmountSwapMockConfig>,
+    unavailable: Mutex<bool>,
+    /// The Magic Routing Hint address and amount of each generated invoice
+    mrh_by_invoice: Mutex<HashMap<String, (String, u64)>>,
+    faults: Arc<FaultInjector>,
 }
 
 impl MockSwapper {
@@ -40,6 +78,11 @@ impl MockSwapper {
         MockSwapper::default()
     }
 
+    pub(crate) fn with_faults(mut self, faults: Arc<FaultInjector>) -> Self {
+        self.faults = faults;
+        self
+    }
+
     fn mock_swap_tree() -> SwapTree {
         SwapTree {
             claim_leaf: Leaf {
@@ -53,6 +96,72 @@ impl MockSwapper {
         }
     }
 
+    /// Creates a reverse swap tree in the Boltz format, from which the swap script and its
+    /// lockup address can be derived
+    fn mock_reverse_swap_tree(
+        preimage_hash: &sha256::Hash,
+        claim_public_key: &PublicKey,
+        refund_public_key: &PublicKey,
+    ) -> SwapTree {
+        let hashlock = ripemd160::Hash::hash(preimage_hash.as_byte_array());
+        let claim_script = Builder::new()
+            .push_opcode(OP_SIZE)
+            .push_int(32)
+            .push_opcode(OP_EQUALVERIFY)
+            .push_opcode(OP_HASH160)
+            .push_slice(hashlock.as_byte_array())
+            .push_opcode(OP_EQUALVERIFY)
+            .push_slice(&claim_public_key.inner.x_only_public_key().0.serialize())
+            .push_opcode(OP_CHECKSIG)
+            .into_script();
+        let refund_script = Builder::new()
+            .push_slice(&refund_public_key.inner.x_only_public_key().0.serialize())
+            .push_opcode(OP_CHECKSIGVERIFY)
+            .push_int(MOCK_REVERSE_SWAP_TIMEOUT_BLOCK_HEIGHT as i64)
+            .push_opcode(OP_CLTV)
+            .into_script();
+        SwapTree {
+            claim_leaf: Leaf {
+                output: claim_script.as_bytes().to_lower_hex_string(),
+                version: 196,
+            },
+            refund_leaf: Leaf {
+                output: refund_script.as_bytes().to_lower_hex_string(),
+                version: 196,
+            },
+        }
+    }
+
+    /// Creates a regtest invoice for the given payment hash, signed by a random node
+    pub(crate) fn mock_invoice(
+        payment_hash: &str,
+        amount_sat: u64,
+        description: String,
+    ) -> Result<String, PaymentError> {
+        let payment_hash = sha256::Hash::from_str(payment_hash)
+            .map_err(|e| PaymentError::generic(format!("Invalid payment hash: {e}")))?;
+        let mut rng = rand::thread_rng();
+        let node_secret_key = loop {
+            let mut buf = [0u8; 32];
+            rng.fill_bytes(&mut buf);
+            if let Ok(secret_key) = SecretKey::from_slice(&buf) {
+                break secret_key;
+            }
+        };
+        let mut payment_secret = [0u8; 32];
+        rng.fill_bytes(&mut payment_secret);
+        let invoice = InvoiceBuilder::new(Currency::Regtest)
+            .description(description)
+            .payment_hash(payment_hash)
+            .payment_secret(PaymentSecret(payment_secret))
+            .current_timestamp()
+            .min_final_cltv_expiry_delta(144)
+            .amount_milli_satoshis(amount_sat * 1_000)
+            .build_signed(|hash| Secp256k1::new().sign_ecdsa_recoverable(hash, &node_secret_key))
+            .map_err(|e| PaymentError::generic(format!("Failed to create invoice: {e:?}")))?;
+        Ok(invoice.to_string())
+    }
+
     fn mock_public_key() -> PublicKey {
         utils::generate_keypair().public_key().into()
     }
@@ -75,6 +184,36 @@ impl MockSwapper {
         *self.zero_amount_swap_mock_config.lock().unwrap() = config;
     }
 
+    /// Makes the pair endpoints fail, as if the swapper could not be reached
+    pub(crate) fn set_unavailable(&self, unavailable: bool) {
+        *self.unavailable.lock().unwrap() = unavailable;
+    }
+
+    fn ensure_available(&self) -> Result<(), PaymentError> {
+        ensure_sdk!(
+            !*self.unavailable.lock().unwrap(),
+            PaymentError::generic("Swapper is unavailable")
+        );
+        Ok(())
+    }
+
+    fn new_submarine_pair() -> SubmarinePair {
+        SubmarinePair {
+            hash: generate_random_string(10),
+            rate: 0.0,
+            limits: SubmarinePairLimits {
+                maximal: 25_000_000,
+                minimal: 1_000,
+                maximal_zero_conf: 250_000,
+                minimal_batched: Some(21),
+            },
+            fees: SubmarineFees {
+                percentage: 0.1,
+                miner_fees: 14,
+            },
+        }
+    }
+
     fn new_chain_pair() -> ChainPair {
         ChainPair {
             hash: generate_random_string(10),
@@ -115,19 +254,33 @@ impl MockSwapper {
 impl Swapper for MockSwapper {
     async fn create_chain_swap(
         &self,
-        _req: boltz_client::swaps::boltz::CreateChainRequest,
+        req: CreateChainRequest,
     ) -> Result<CreateChainResponse, PaymentError> {
+        self.faults
+            .check(FaultTarget::Swapper, "create_chain_swap")
+            .await?;
+        let mut lockup_details = Self::mock_swap_details();
+        if let Some(server_lock_amount) = req.server_lock_amount {
+            // The user lockup covers the server lockup and the service fees
+            let fees = Self::new_chain_pair().fees;
+            lockup_details.amount = ((server_lock_amount + fees.server()) as f64 * 100.0
+                / (100.0 - fees.percentage))
+                .ceil() as u64;
+        }
         Ok(CreateChainResponse {
             id: generate_random_string(4),
             claim_details: Self::mock_swap_details(),
-            lockup_details: Self::mock_swap_details(),
+            lockup_details,
         })
     }
 
     async fn create_send_swap(
         &self,
-        req: boltz_client::swaps::boltz::CreateSubmarineRequest,
+        req: CreateSubmarineRequest,
     ) -> Result<CreateSubmarineResponse, PaymentError> {
+        self.faults
+            .check(FaultTarget::Swapper, "create_send_swap")
+            .await?;
         let invoice = parse_invoice(&req.invoice)
             .map_err(|err| PaymentError::invalid_invoice(err.to_string()))?;
         let Some(amount_msat) = invoice.amount_msat else {
@@ -141,7 +294,9 @@ impl Swapper for MockSwapper {
             address: "".to_string(),
             bip21: "".to_string(),
             claim_public_key: Self::mock_public_key(),
-            expected_amount: amount_msat / 1000,
+            // The lockup covers the invoice amount and the swap fees
+            expected_amount: amount_msat / 1000
+                + Self::new_submarine_pair().fees.total(amount_msat / 1000),
             id: generate_random_string(4),
             referral_id: None,
             swap_tree: Self::mock_swap_tree(),
@@ -154,12 +309,19 @@ impl Swapper for MockSwapper {
         &self,
         _direction: Direction,
     ) -> anyhow::Result<Option<ChainPair>, PaymentError> {
+        self.faults
+            .check(FaultTarget::Swapper, "get_chain_pair")
+            .await?;
+        self.ensure_available()?;
         Ok(Some(Self::new_chain_pair()))
     }
 
     async fn get_chain_pairs(
         &self,
     ) -> Result<(Option<ChainPair>, Option<ChainPair>), PaymentError> {
+        self.faults
+            .check(FaultTarget::Swapper, "get_chain_pairs")
+            .await?;
         let test_pair = Some(ChainPair {
             hash: generate_random_string(10),
             rate: 0.0,
@@ -183,30 +345,27 @@ impl Swapper for MockSwapper {
     }
 
     async fn get_submarine_preimage(&self, _swap_id: &str) -> Result<String, PaymentError> {
+        self.faults
+            .check(FaultTarget::Swapper, "get_submarine_preimage")
+            .await?;
         Ok(Preimage::new().to_string().unwrap())
     }
 
     async fn get_submarine_pairs(&self) -> Result<Option<SubmarinePair>, PaymentError> {
-        Ok(Some(SubmarinePair {
-            hash: generate_random_string(10),
-            rate: 0.0,
-            limits: SubmarinePairLimits {
-                maximal: 25_000_000,
-                minimal: 1_000,
-                maximal_zero_conf: 250_000,
-                minimal_batched: Some(21),
-            },
-            fees: SubmarineFees {
-                percentage: 0.1,
-                miner_fees: 14,
-            },
-        }))
+        self.faults
+            .check(FaultTarget::Swapper, "get_submarine_pairs")
+            .await?;
+        self.ensure_available()?;
+        Ok(Some(Self::new_submarine_pair()))
     }
 
     async fn get_send_claim_tx_details(
         &self,
         _swap: &SendSwap,
     ) -> Result<SubmarineClaimTxResponse, PaymentError> {
+        self.faults
+            .check(FaultTarget::Swapper, "get_send_claim_tx_details")
+            .await?;
         Ok(SubmarineClaimTxResponse {
             preimage: Preimage::new()
                 .to_string()
@@ -223,6 +382,9 @@ impl Swapper for MockSwapper {
         _claim_address: Option<String>,
         _is_cooperative: bool,
     ) -> Result<SdkTransaction, PaymentError> {
+        self.faults
+            .check(FaultTarget::Swapper, "create_claim_tx")
+            .await?;
         let btc_tx = SdkTransaction::Bitcoin(boltz_client::bitcoin::Transaction {
             version: lwk_wollet::bitcoin::transaction::Version::TWO,
             lock_time: boltz_client::LockTime::ZERO,
@@ -241,7 +403,24 @@ impl Swapper for MockSwapper {
                 Direction::Incoming => lbtc_tx,
                 Direction::Outgoing => btc_tx,
             },
-            Swap::Receive(_) => lbtc_tx,
+            // Spend the lockup, so each swap has its own claim tx
+            Swap::Receive(swap) => match swap.lockup_tx_id {
+                Some(lockup_tx_id) => {
+                    let lockup_tx_id = Txid::from_str(&lockup_tx_id)
+                        .map_err(|e| PaymentError::generic(format!("Invalid lockup txid: {e}")))?;
+                    SdkTransaction::Liquid(boltz_client::elements::Transaction {
+                        version: 2,
+                        lock_time: boltz_client::ElementsLockTime::ZERO,
+                        input: vec![TxIn {
+                            previous_output: OutPoint::new(lockup_tx_id, 0),
+                            sequence: Sequence::MAX,
+                            ..Default::default()
+                        }],
+                        output: vec![],
+                    })
+                }
+                None => lbtc_tx,
+            },
             Swap::Send(_) => unimplemented!(),
         })
     }
@@ -253,6 +432,9 @@ impl Swapper for MockSwapper {
         _fee_rate_sat_per_vb: Option<f64>,
         _is_cooperative: bool,
     ) -> Result<(u32, u64), SdkError> {
+        self.faults
+            .check(FaultTarget::Swapper, "estimate_refund_broadcast")
+            .await?;
         Ok((0, 0))
     }
 
@@ -264,6 +446,9 @@ impl Swapper for MockSwapper {
         _broadcast_fee_rate_sat_per_vb: Option<f64>,
         _is_cooperative: bool,
     ) -> Result<SdkTransaction, PaymentError> {
+        self.faults
+            .check(FaultTarget::Swapper, "create_refund_tx")
+            .await?;
         let btc_tx = SdkTransaction::Bitcoin(boltz_client::bitcoin::Transaction {
             version: lwk_wollet::bitcoin::transaction::Version::TWO,
             lock_time: boltz_client::LockTime::ZERO,
@@ -290,29 +475,80 @@ impl Swapper for MockSwapper {
     async fn claim_send_swap_cooperative(
         &self,
         _swap: &SendSwap,
-        _claim_tx_response: boltz_client::swaps::boltz::SubmarineClaimTxResponse,
+        _claim_tx_response: SubmarineClaimTxResponse,
         _refund_address: &str,
     ) -> Result<(), PaymentError> {
+        self.faults
+            .check(FaultTarget::Swapper, "claim_send_swap_cooperative")
+            .await?;
         Ok(())
     }
 
     async fn create_receive_swap(
         &self,
-        req: boltz_client::swaps::boltz::CreateReverseRequest,
+        req: CreateReverseRequest,
     ) -> Result<CreateReverseResponse, PaymentError> {
-        Ok(CreateReverseResponse {
+        self.faults
+            .check(FaultTarget::Swapper, "create_receive_swap")
+            .await?;
+        let invoice = match (&req.invoice, req.preimage_hash, req.invoice_amount) {
+            (Some(_), _, _) => None,
+            (None, Some(preimage_hash), Some(invoice_amount_sat)) => {
+                let invoice = Self::mock_invoice(
+                    &preimage_hash.to_string(),
+                    invoice_amount_sat,
+                    req.description.clone().unwrap_or_default(),
+                )?;
+                if let Some(address) = &req.address {
+                    self.mrh_by_invoice
+                        .lock()
+                        .unwrap()
+                        .insert(invoice.clone(), (address.clone(), invoice_amount_sat));
+                }
+                Some(invoice)
+            }
+            (None, _, _) => Some("".to_string()),
+        };
+        let refund_public_key = Self::mock_public_key();
+        let mut response = CreateReverseResponse {
             id: generate_random_string(4),
-            invoice: req.invoice.map_or(Some("".to_string()), |_| None),
+            invoice,
             swap_tree: Self::mock_swap_tree(),
             lockup_address: "".to_string(),
-            refund_public_key: Self::mock_public_key(),
+            refund_public_key,
             timeout_block_height: 0,
             onchain_amount: 0,
             blinding_key: None,
-        })
+        };
+
+        // Use a valid swap script when we generate the invoice, so lockups to it can be verified
+        if let (Some(preimage_hash), Some(address)) = (req.preimage_hash, req.address) {
+            response.swap_tree = Self::mock_reverse_swap_tree(
+                &preimage_hash,
+                &req.claim_public_key,
+                &refund_public_key,
+            );
+            response.timeout_block_height = MOCK_REVERSE_SWAP_TIMEOUT_BLOCK_HEIGHT;
+            response.blinding_key = Some(utils::generate_keypair().display_secret().to_string());
+            // The swap script is parsed along with the lockup address, so the MRH address is
+            // used until the lockup address is derived from it
+            response.lockup_address = address;
+            response.lockup_address =
+                LBtcSwapScript::reverse_from_swap_resp(&response, req.claim_public_key)
+                    .and_then(|script| script.to_address(LiquidChain::LiquidRegtest))
+                    .map_err(|e| {
+                        PaymentError::generic(format!("Failed to derive lockup address: {e:?}"))
+                    })?
+                    .to_string();
+        }
+        Ok(response)
     }
 
     async fn get_reverse_swap_pairs(&self) -> Result<Option<ReversePair>, PaymentError> {
+        self.faults
+            .check(FaultTarget::Swapper, "get_reverse_swap_pairs")
+            .await?;
+        self.ensure_available()?;
         Ok(Some(ReversePair {
             hash: "".to_string(),
             rate: 0.0,
@@ -335,42 +571,73 @@ impl Swapper for MockSwapper {
         _chain: boltz_client::network::Chain,
         tx_hex: &str,
     ) -> Result<String, PaymentError> {
+        self.faults
+            .check(FaultTarget::Swapper, "broadcast_tx")
+            .await?;
         let tx = utils::deserialize_tx_hex(tx_hex)?;
         Ok(tx.txid().to_string())
     }
 
     async fn check_for_mrh(
         &self,
-        _invoice: &str,
+        invoice: &str,
     ) -> Result<Option<(String, boltz_client::bitcoin::Amount)>, PaymentError> {
-        // Ok(Some(("".to_string(), 0.0)))
-        unimplemented!()
+        self.faults
+            .check(FaultTarget::Swapper, "check_for_mrh")
+            .await?;
+        Ok(self
+            .mrh_by_invoice
+            .lock()
+            .unwrap()
+            .get(invoice)
+            .map(|(address, amount_sat)| {
+                (
+                    address.clone(),
+                    boltz_client::bitcoin::Amount::from_sat(*amount_sat),
+                )
+            }))
     }
 
     async fn get_bolt12_info(
         &self,
         _req: GetBolt12FetchRequest,
     ) -> Result<GetBolt12FetchResponse, PaymentError> {
+        self.faults
+            .check(FaultTarget::Swapper, "get_bolt12_info")
+            .await?;
         unimplemented!()
     }
 
     async fn create_bolt12_offer(&self, _req: CreateBolt12OfferRequest) -> Result<(), SdkError> {
+        self.faults
+            .check(FaultTarget::Swapper, "create_bolt12_offer")
+            .await?;
         Ok(())
     }
 
     async fn update_bolt12_offer(&self, _req: UpdateBolt12OfferRequest) -> Result<(), SdkError> {
+        self.faults
+            .check(FaultTarget::Swapper, "update_bolt12_offer")
+            .await?;
         Ok(())
     }
 
     async fn delete_bolt12_offer(&self, _offer: &str, _signature: &str) -> Result<(), SdkError> {
+        self.faults
+            .check(FaultTarget::Swapper, "delete_bolt12_offer")
+            .await?;
         Ok(())
     }
 
     async fn get_bolt12_params(&self) -> Result<GetBolt12ParamsResponse, PaymentError> {
+        self.faults
+            .check(FaultTarget::Swapper, "get_bolt12_params")
+            .await?;
         Ok(GetBolt12ParamsResponse { min_cltv: 180 })
     }
 
     async fn get_nodes(&self) -> Result<GetNodesResponse, PaymentError> {
+        self.faults.check(FaultTarget::Swapper, "get_nodes").await?;
         Ok(GetNodesResponse {
             btc: HashMap::from([(
                 "CLN".to_string(),
@@ -388,6 +655,9 @@ impl Swapper for MockSwapper {
     }
 
     async fn get_zero_amount_chain_swap_quote(&self, _swap_id: &str) -> Result<Amount, SdkError> {
+        self.faults
+            .check(FaultTarget::Swapper, "get_zero_amount_chain_swap_quote")
+            .await?;
         let server_lockup_amount_sat = self.get_zero_amount_swap_server_lockup_sat().await;
         Ok(Amount::from_sat(server_lockup_amount_sat))
     }
@@ -397,6 +667,9 @@ impl Swapper for MockSwapper {
         _swap_id: &str,
         server_lockup_sat: u64,
     ) -> Result<(), PaymentError> {
+        self.faults
+            .check(FaultTarget::Swapper, "accept_zero_amount_chain_swap_quote")
+            .await?;
         ensure_sdk!(
             server_lockup_sat == self.get_zero_amount_swap_server_lockup_sat().await,
             PaymentError::InvalidOrExpiredFees
@@ -4,6 +4,7 @@ use crate::{
     persist::Persister,
     prelude::{Direction, Signer},
     recover::recoverer::Recoverer,
+    signer::SyncSignerAdapter,
     sync::{
         client::SyncerClient,
         model::{
@@ -13,6 +14,7 @@ use crate::{
         },
         SyncService,
     },
+    test_utils::faults::{FaultInjector, FaultTarget},
 };
 use anyhow::Result;
 use tokio::sync::{
@@ -24,6 +26,7 @@ use tonic::Streaming;
 pub(crate) struct MockSyncerClient {
     pub(crate) incoming_rx: Mutex<Receiver<Record>>,
     pub(crate) outgoing_records: Arc<Mutex<HashMap<String, Record>>>,
+    faults: Arc<FaultInjector>,
 }
 
 impl MockSyncerClient {
@@ -34,17 +37,27 @@ impl MockSyncerClient {
         Self {
             incoming_rx: Mutex::new(incoming_rx),
             outgoing_records,
+            faults: Default::default(),
         }
     }
+
+    pub(crate) fn with_faults(mut self, faults: Arc<FaultInjector>) -> Self {
+        self.faults = faults;
+        self
+    }
 }
 
 #[sdk_macros::async_trait]
 impl SyncerClient for MockSyncerClient {
     async fn connect(&self, _connect_url: String) -> Result<()> {
+        self.faults
+            .check(FaultTarget::SyncClient, "connect")
+            .await?;
         Ok(())
     }
 
     async fn push(&self, req: SetRecordRequest) -> Result<SetRecordReply> {
+        self.faults.check(FaultTarget::SyncClient, "push").await?;
         if let Some(mut record) = req.record {
             let mut outgoing_records = self.outgoing_records.lock().await;
 
@@ -71,6 +84,7 @@ impl SyncerClient for MockSyncerClient {
     }
 
     async fn pull(&self, _req: ListChangesRequest) -> Result<ListChangesReply> {
+        self.faults.check(FaultTarget::SyncClient, "pull").await?;
         let mut rx = self.incoming_rx.lock().await;
         let mut changes = Vec::with_capacity(3);
         rx.recv_many(&mut changes, 3).await;
@@ -78,10 +92,14 @@ impl SyncerClient for MockSyncerClient {
     }
 
     async fn listen(&self, _req: ListenChangesRequest) -> Result<Streaming<Notification>> {
+        self.faults.check(FaultTarget::SyncClient, "listen").await?;
         Err(anyhow::anyhow!("Not implemented"))
     }
 
     async fn disconnect(&self) -> Result<()> {
+        self.faults
+            .check(FaultTarget::SyncClient, "disconnect")
+            .await?;
         Ok(())
     }
 }
@@ -103,7 +121,7 @@ pub(crate) fn new_sync_service(
         "".to_string(),
         persister.clone(),
         recoverer,
-        signer.clone(),
+        Arc::new(SyncSignerAdapter::new(signer)),
         client,
     );
 
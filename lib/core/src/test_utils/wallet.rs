@@ -7,7 +7,7 @@ use std::{
 use crate::{
     error::PaymentError,
     model::{Signer, SignerError},
-    signer::{NewError, SdkLwkSigner},
+    signer::NewError,
     utils,
     wallet::OnchainWallet,
 };
@@ -20,15 +20,20 @@ use lwk_wollet::{
         self,
         bip32::{DerivationPath, Xpriv, Xpub},
     },
-    elements::{hex::ToHex, pset::PartiallySignedTransaction, Address, AssetId, Transaction, Txid},
+    elements::{
+        confidential, hex::ToHex, pset::PartiallySignedTransaction, Address, AssetId, Script,
+        Transaction, TxOut, TxOutWitness, Txid,
+    },
     elements_miniscript::{slip77::MasterBlindingKey, ToPublicKey as _},
     secp256k1::{All, Message},
-    WalletTx, WalletTxOut,
+    Contract, WalletTx, WalletTxOut,
 };
 
 pub(crate) struct MockWallet {
-    signer: SdkLwkSigner,
+    xpub: Xpub,
     utxos: Mutex<Vec<WalletTxOut>>,
+    transactions: Mutex<Vec<WalletTx>>,
+    insufficient_funds: Mutex<bool>,
 }
 
 lazy_static! {
@@ -39,10 +44,12 @@ lazy_static! {
 
 impl MockWallet {
     pub(crate) fn new(user_signer: Arc<Box<dyn Signer>>) -> Result<Self> {
-        let signer = crate::signer::SdkLwkSigner::new(user_signer.clone())?;
+        let xpub = Xpub::decode(&user_signer.xpub()?)?;
         Ok(Self {
-            signer,
+            xpub,
             utxos: Mutex::new(vec![]),
+            transactions: Mutex::new(vec![]),
+            insufficient_funds: Mutex::new(false),
         })
     }
 
@@ -50,16 +57,38 @@ impl MockWallet {
         *self.utxos.lock().unwrap() = utxos;
         self
     }
+
+    pub(crate) fn add_utxo(&self, utxo: WalletTxOut) -> &Self {
+        self.utxos.lock().unwrap().push(utxo);
+        self
+    }
+
+    pub(crate) fn add_transaction(&self, tx: WalletTx) -> &Self {
+        self.transactions.lock().unwrap().push(tx);
+        self
+    }
+
+    /// Makes [OnchainWallet::build_tx] fail as if there was no L-BTC to pay the tx fees
+    pub(crate) fn set_insufficient_funds(&self, insufficient_funds: bool) -> &Self {
+        *self.insufficient_funds.lock().unwrap() = insufficient_funds;
+        self
+    }
 }
 
 #[sdk_macros::async_trait]
 impl OnchainWallet for MockWallet {
     async fn transactions(&self) -> Result<Vec<WalletTx>, PaymentError> {
-        Ok(vec![])
+        Ok(self.transactions.lock().unwrap().clone())
     }
 
     async fn transactions_by_tx_id(&self) -> Result<HashMap<Txid, WalletTx>, PaymentError> {
-        Ok(Default::default())
+        Ok(self
+            .transactions
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|tx| (tx.txid, tx.clone()))
+            .collect())
     }
 
     async fn asset_utxos(&self, _asset_id: &AssetId) -> Result<Vec<WalletTxOut>, PaymentError> {
@@ -72,6 +101,83 @@ impl OnchainWallet for MockWallet {
         _recipient_address: &str,
         _asset_id: &str,
         _amount_sat: u64,
+    ) -> Result<Transaction, PaymentError> {
+        if *self.insufficient_funds.lock().unwrap() {
+            return Err(PaymentError::InsufficientFunds);
+        }
+        Ok(TEST_LIQUID_TX.clone())
+    }
+
+    async fn build_unsigned_tx(
+        &self,
+        _fee_rate: Option<f32>,
+        recipient_address: &str,
+        asset_id: &str,
+        amount_sat: u64,
+    ) -> Result<PartiallySignedTransaction, PaymentError> {
+        // Pays the recipient from the inputs of TEST_LIQUID_TX, with the same fees
+        let mut tx = TEST_LIQUID_TX.clone();
+        let address = Address::from_str(recipient_address)
+            .map_err(|e| PaymentError::generic(format!("Invalid address: {e}")))?;
+        let (fee_asset, fees_sat) = tx
+            .all_fees()
+            .into_iter()
+            .next()
+            .ok_or(PaymentError::generic("Test tx has no fees"))?;
+        tx.output = vec![
+            TxOut {
+                asset: confidential::Asset::Explicit(AssetId::from_str(asset_id)?),
+                value: confidential::Value::Explicit(amount_sat),
+                nonce: confidential::Nonce::Null,
+                script_pubkey: address.script_pubkey(),
+                witness: TxOutWitness::default(),
+            },
+            TxOut::new_fee(fees_sat, fee_asset),
+        ];
+        Ok(PartiallySignedTransaction::from_tx(tx))
+    }
+
+    async fn finalize_pset(
+        &self,
+        pset: &mut PartiallySignedTransaction,
+    ) -> Result<Transaction, PaymentError> {
+        pset.extract_tx()
+            .map_err(|e| PaymentError::generic(format!("Failed to extract tx: {e}")))
+    }
+
+    async fn build_peg_out_tx(
+        &self,
+        _fee_rate_sats_per_kvb: Option<f32>,
+        _peg_out_script: &Script,
+        _amount_sat: u64,
+    ) -> Result<Transaction, PaymentError> {
+        Ok(TEST_LIQUID_TX.clone())
+    }
+
+    async fn build_issuance_tx(
+        &self,
+        _fee_rate_sats_per_kvb: Option<f32>,
+        _asset_sat: u64,
+        _token_sat: u64,
+        _contract: Option<Contract>,
+    ) -> Result<Transaction, PaymentError> {
+        Ok(TEST_LIQUID_TX.clone())
+    }
+
+    async fn build_reissuance_tx(
+        &self,
+        _fee_rate_sats_per_kvb: Option<f32>,
+        _asset_id: &AssetId,
+        _amount_sat: u64,
+    ) -> Result<Transaction, PaymentError> {
+        Ok(TEST_LIQUID_TX.clone())
+    }
+
+    async fn build_burn_tx(
+        &self,
+        _fee_rate_sats_per_kvb: Option<f32>,
+        _asset_id: &AssetId,
+        _amount_sat: u64,
     ) -> Result<Transaction, PaymentError> {
         Ok(TEST_LIQUID_TX.clone())
     }
@@ -99,6 +205,13 @@ impl OnchainWallet for MockWallet {
         Ok(())
     }
 
+    async fn cosign_pset(
+        &self,
+        _pset: &mut PartiallySignedTransaction,
+    ) -> Result<(), PaymentError> {
+        Ok(())
+    }
+
     async fn next_unused_address(&self) -> Result<Address, PaymentError> {
         Ok(TEST_P2TR_ADDR.clone())
     }
@@ -112,14 +225,14 @@ impl OnchainWallet for MockWallet {
     }
 
     fn pubkey(&self) -> Result<String> {
-        Ok(self.signer.xpub()?.public_key.to_string())
+        Ok(self.xpub.public_key.to_string())
     }
 
     fn fingerprint(&self) -> Result<String> {
-        Ok(self.signer.fingerprint()?.to_hex())
+        Ok(self.xpub.fingerprint().to_hex())
     }
 
-    fn sign_message(&self, _message: &str) -> Result<String> {
+    async fn sign_message(&self, _message: &str) -> Result<String> {
         unimplemented!()
     }
 
@@ -15,11 +15,13 @@ use boltz_client::util::secrets::Preimage;
 use boltz_client::{Keypair, ToHex};
 use lazy_static::lazy_static;
 use log::warn;
+use lwk_wollet::bitcoin::base64::{self, Engine as _};
 use lwk_wollet::bitcoin::secp256k1::Message;
-use lwk_wollet::elements::encode::deserialize;
+use lwk_wollet::elements::encode::{deserialize, serialize};
 use lwk_wollet::elements::hex::FromHex;
 use lwk_wollet::elements::AssetId;
 use lwk_wollet::elements::{
+    pset::PartiallySignedTransaction,
     LockTime::{self, *},
     Transaction,
 };
@@ -38,10 +40,17 @@ lazy_static! {
 }
 
 pub(crate) fn now() -> u32 {
+    system_time().duration_since(UNIX_EPOCH).unwrap().as_secs() as u32
+}
+
+/// The current system time, or the time of the [MockClock](crate::test_utils::clock::MockClock)
+/// installed by a test
+pub(crate) fn system_time() -> SystemTime {
+    #[cfg(any(test, feature = "test-utils"))]
+    if let Some(now) = crate::test_utils::clock::installed_now() {
+        return now;
+    }
     SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs() as u32
 }
 
 pub(crate) fn json_to_pubkey(json: &str) -> Result<boltz_client::PublicKey, PaymentError> {
@@ -84,6 +93,19 @@ pub(crate) fn deserialize_tx_hex(tx_hex: &str) -> Result<Transaction> {
     )?)?)
 }
 
+/// Encodes a PSET as base64
+pub(crate) fn encode_pset(pset: &PartiallySignedTransaction) -> String {
+    base64::engine::general_purpose::STANDARD.encode(serialize(pset))
+}
+
+/// Decodes a base64 encoded PSET
+pub(crate) fn decode_pset(pset_base64: &str) -> Result<PartiallySignedTransaction> {
+    let pset_bytes = base64::engine::general_purpose::STANDARD
+        .decode(pset_base64)
+        .map_err(|err| anyhow!("Could not base64 decode the PSET: {err:?}"))?;
+    Ok(deserialize(&pset_bytes)?)
+}
+
 pub(crate) fn sign_message_hash<S: AsRef<str>>(msg: S, keypair: &Keypair) -> Result<Signature> {
     let msg_hash = sha256::Hash::hash(msg.as_ref().as_bytes());
     Ok(keypair.sign_schnorr(Message::from_digest_slice(msg_hash.as_byte_array())?))
@@ -308,8 +330,11 @@ pub(crate) fn update_invoice_settled_at(
 
 #[cfg(test)]
 mod tests {
+    use lwk_wollet::elements::pset::PartiallySignedTransaction;
+
     use crate::error::PaymentError;
-    use crate::utils::verify_payment_hash;
+    use crate::test_utils::wallet::TEST_LIQUID_TX;
+    use crate::utils::{decode_pset, encode_pset, verify_payment_hash};
 
     #[cfg(feature = "browser-tests")]
     wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
@@ -343,4 +368,16 @@ mod tests {
 
         Ok(())
     }
+
+    #[sdk_macros::test_all]
+    fn test_pset_encoding() -> anyhow::Result<()> {
+        let pset = PartiallySignedTransaction::from_tx(TEST_LIQUID_TX.clone());
+
+        let decoded = decode_pset(&encode_pset(&pset))?;
+        assert_eq!(decoded.extract_tx()?.txid(), TEST_LIQUID_TX.txid());
+
+        assert!(decode_pset("not a pset").is_err());
+
+        Ok(())
+    }
 }
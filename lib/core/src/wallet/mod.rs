@@ -10,14 +10,12 @@ use std::sync::Arc;
 use anyhow::{anyhow, bail, Result};
 use boltz_client::ElementsAddress;
 use log::{debug, error, info, warn};
-use lwk_common::Signer as LwkSigner;
-use lwk_common::{singlesig_desc, Singlesig};
 use lwk_wollet::asyncr::{EsploraClient, EsploraClientBuilder};
 use lwk_wollet::elements::hex::ToHex;
 use lwk_wollet::elements::pset::PartiallySignedTransaction;
-use lwk_wollet::elements::{Address, AssetId, OutPoint, Transaction, TxOut, Txid};
+use lwk_wollet::elements::{Address, AssetId, OutPoint, Script, Transaction, TxOut, Txid};
 use lwk_wollet::secp256k1::Message;
-use lwk_wollet::{ElementsNetwork, WalletTx, WalletTxOut, Wollet, WolletDescriptor};
+use lwk_wollet::{Contract, ElementsNetwork, WalletTx, WalletTxOut, Wollet, WolletDescriptor};
 use persister::SqliteWalletCachePersister;
 use sdk_common::bitcoin::hashes::{sha256, Hash};
 use sdk_common::bitcoin::secp256k1::PublicKey;
@@ -26,9 +24,12 @@ use tokio::sync::Mutex;
 use utxo_select::{InOut, WalletUtxoSelectRequest};
 use web_time::Instant;
 
-use crate::model::{BlockchainExplorer, Signer, BREEZ_LIQUID_ESPLORA_URL};
+use crate::model::{
+    AsyncSigner, BlockchainExplorer, Signer, BREEZ_LIQUID_ESPLORA_URL,
+    LIQUID_FEE_RATE_MSAT_PER_VBYTE,
+};
 use crate::persist::Persister;
-use crate::signer::SdkLwkSigner;
+use crate::signer::{descriptor_keys, SdkLwkSigner, SyncSignerAdapter, WatchOnlySigner};
 use crate::{ensure_sdk, error::PaymentError, model::Config};
 
 use crate::wallet::persister::WalletCachePersister;
@@ -57,6 +58,56 @@ pub trait OnchainWallet: Send + Sync {
         amount_sat: u64,
     ) -> Result<Transaction, PaymentError>;
 
+    /// Build an unsigned transaction to send funds to a recipient, to be signed externally
+    async fn build_unsigned_tx(
+        &self,
+        fee_rate_sats_per_kvb: Option<f32>,
+        recipient_address: &str,
+        asset_id: &str,
+        amount_sat: u64,
+    ) -> Result<PartiallySignedTransaction, PaymentError>;
+
+    /// Finalize a signed partially signed transaction spending wallet inputs
+    async fn finalize_pset(
+        &self,
+        pset: &mut PartiallySignedTransaction,
+    ) -> Result<Transaction, PaymentError>;
+
+    /// Build a peg-out transaction, burning `amount_sat` of L-BTC to the given peg-out script
+    async fn build_peg_out_tx(
+        &self,
+        fee_rate_sats_per_kvb: Option<f32>,
+        peg_out_script: &Script,
+        amount_sat: u64,
+    ) -> Result<Transaction, PaymentError>;
+
+    /// Build a transaction issuing `asset_sat` of a new asset and `token_sat` of its reissuance
+    /// token, both received by the wallet. No reissuance token is issued if `token_sat` is 0.
+    async fn build_issuance_tx(
+        &self,
+        fee_rate_sats_per_kvb: Option<f32>,
+        asset_sat: u64,
+        token_sat: u64,
+        contract: Option<Contract>,
+    ) -> Result<Transaction, PaymentError>;
+
+    /// Build a transaction reissuing `amount_sat` of an asset whose reissuance token is held
+    /// by the wallet
+    async fn build_reissuance_tx(
+        &self,
+        fee_rate_sats_per_kvb: Option<f32>,
+        asset_id: &AssetId,
+        amount_sat: u64,
+    ) -> Result<Transaction, PaymentError>;
+
+    /// Build a transaction provably burning `amount_sat` of the given asset
+    async fn build_burn_tx(
+        &self,
+        fee_rate_sats_per_kvb: Option<f32>,
+        asset_id: &AssetId,
+        amount_sat: u64,
+    ) -> Result<Transaction, PaymentError>;
+
     /// Builds a drain tx.
     ///
     /// ### Arguments
@@ -85,6 +136,10 @@ pub trait OnchainWallet: Send + Sync {
     /// Sign a partially signed transaction
     async fn sign_pset(&self, pset: &mut PartiallySignedTransaction) -> Result<(), PaymentError>;
 
+    /// Add the signatures of the SDK signer to the wallet inputs of a multisig PSET, without
+    /// finalizing it, so that it can be passed on to the other cosigners
+    async fn cosign_pset(&self, pset: &mut PartiallySignedTransaction) -> Result<(), PaymentError>;
+
     /// Get the next unused address in the wallet
     async fn next_unused_address(&self) -> Result<Address, PaymentError>;
 
@@ -102,7 +157,7 @@ pub trait OnchainWallet: Send + Sync {
 
     /// Sign given message with the wallet private key. Returns a zbase
     /// encoded signature.
-    fn sign_message(&self, msg: &str) -> Result<String>;
+    async fn sign_message(&self, msg: &str) -> Result<String>;
 
     /// Check whether given message was signed by the given
     /// pubkey and the signature (zbase encoded) is valid.
@@ -189,25 +244,79 @@ pub struct LiquidOnchainWallet {
     wallet: Arc<Mutex<Wollet>>,
     client: Mutex<Option<WalletClient>>,
     pub(crate) signer: SdkLwkSigner,
+    descriptor: WolletDescriptor,
+    watch_only: bool,
+    multisig: bool,
     wallet_cache_persister: Arc<dyn WalletCachePersister>,
 }
 
 impl LiquidOnchainWallet {
-    /// Creates a new LiquidOnchainWallet that caches data on the provided `working_dir`.
+    /// Creates a new LiquidOnchainWallet for the given BIP44 `account_index` of the signer.
     pub(crate) async fn new(
         config: Config,
         persister: std::sync::Arc<Persister>,
-        user_signer: Arc<Box<dyn Signer>>,
+        user_signer: Arc<dyn AsyncSigner>,
+        account_index: u32,
     ) -> Result<Self> {
-        let signer = SdkLwkSigner::new(user_signer.clone())?;
+        let signer = SdkLwkSigner::new(user_signer).await?;
+        let descriptor = get_descriptor(&signer, account_index).await?;
+        Self::new_inner(config, persister, signer, descriptor, false, false).await
+    }
 
-        let wallet_cache_persister: Arc<dyn WalletCachePersister> =
-            Arc::new(SqliteWalletCachePersister::new(
-                std::sync::Arc::clone(&persister),
-                get_descriptor(&signer)?,
-            )?);
+    /// Creates a new watch-only LiquidOnchainWallet from the provided CT `descriptor`.
+    /// Any attempt to sign with it fails with [PaymentError::WatchOnly].
+    pub(crate) async fn new_watch_only(
+        config: Config,
+        persister: std::sync::Arc<Persister>,
+        descriptor: WolletDescriptor,
+    ) -> Result<Self> {
+        let user_signer: Box<dyn Signer> = Box::new(WatchOnlySigner::new(&descriptor)?);
+        let signer =
+            SdkLwkSigner::new(Arc::new(SyncSignerAdapter::new(Arc::new(user_signer)))).await?;
+        Self::new_inner(config, persister, signer, descriptor, true, false).await
+    }
 
-        let wollet = Self::create_wallet(&config, &signer, wallet_cache_persister.clone()).await?;
+    /// Creates a new LiquidOnchainWallet from the provided k-of-n multisig CT `descriptor`.
+    /// Wallet txs are only ever partially signed, by the `hot_signer` if it is one of the
+    /// cosigners, and have to be completed by the other cosigners before being finalized.
+    /// Without a `hot_signer`, the wallet is watch-only.
+    pub(crate) async fn new_multisig(
+        config: Config,
+        persister: std::sync::Arc<Persister>,
+        hot_signer: Option<Arc<dyn AsyncSigner>>,
+        descriptor: WolletDescriptor,
+    ) -> Result<Self> {
+        let watch_only = hot_signer.is_none();
+        let user_signer = match hot_signer {
+            Some(hot_signer) => hot_signer,
+            None => {
+                let watch_only_signer: Box<dyn Signer> =
+                    Box::new(WatchOnlySigner::new_multisig(&descriptor)?);
+                Arc::new(SyncSignerAdapter::new(Arc::new(watch_only_signer)))
+            }
+        };
+        ensure_sdk!(
+            descriptor_keys(&descriptor)?.0.len() > 1,
+            anyhow!("A multisig descriptor needs at least two cosigner xpubs")
+        );
+        let signer = SdkLwkSigner::new(user_signer).await?;
+        Self::new_inner(config, persister, signer, descriptor, watch_only, true).await
+    }
+
+    async fn new_inner(
+        config: Config,
+        persister: std::sync::Arc<Persister>,
+        signer: SdkLwkSigner,
+        descriptor: WolletDescriptor,
+        watch_only: bool,
+        multisig: bool,
+    ) -> Result<Self> {
+        let wallet_cache_persister: Arc<dyn WalletCachePersister> = Arc::new(
+            SqliteWalletCachePersister::new(std::sync::Arc::clone(&persister), descriptor.clone())?,
+        );
+
+        let wollet =
+            Self::create_wallet(&config, &descriptor, wallet_cache_persister.clone()).await?;
 
         Ok(Self {
             config,
@@ -215,17 +324,19 @@ impl LiquidOnchainWallet {
             wallet: Arc::new(Mutex::new(wollet)),
             client: Mutex::new(None),
             signer,
+            descriptor,
+            watch_only,
+            multisig,
             wallet_cache_persister,
         })
     }
 
     async fn create_wallet(
         config: &Config,
-        signer: &SdkLwkSigner,
+        descriptor: &WolletDescriptor,
         wallet_cache_persister: Arc<dyn WalletCachePersister>,
     ) -> Result<Wollet> {
         let elements_network: ElementsNetwork = config.network.into();
-        let descriptor = get_descriptor(signer)?;
         let wollet_res = Wollet::new(
             elements_network,
             wallet_cache_persister.get_lwk_persister()?,
@@ -250,6 +361,26 @@ impl LiquidOnchainWallet {
         }
     }
 
+    async fn sign_wallet_inputs(
+        &self,
+        pset: &mut PartiallySignedTransaction,
+    ) -> Result<(), PaymentError> {
+        ensure_sdk!(!self.watch_only, PaymentError::WatchOnly);
+        ensure_sdk!(
+            !self.multisig,
+            PaymentError::generic(
+                "Multisig wallet txs need the cosigner signatures: export the payment PSET instead"
+            )
+        );
+        self.signer
+            .sign(pset)
+            .await
+            .map_err(|e| PaymentError::Generic {
+                err: format!("Failed to sign transaction: {e:?}"),
+            })?;
+        Ok(())
+    }
+
     async fn get_txout(&self, wallet: &Wollet, outpoint: &OutPoint) -> Result<TxOut> {
         let wallet_tx = wallet
             .transaction(&outpoint.txid)?
@@ -316,16 +447,50 @@ impl LiquidOnchainWallet {
     }
 }
 
-pub fn get_descriptor(signer: &SdkLwkSigner) -> Result<WolletDescriptor, PaymentError> {
-    let descriptor_str = singlesig_desc(
-        signer,
-        Singlesig::Wpkh,
-        lwk_common::DescriptorBlindingKey::Slip77,
-    )
-    .map_err(|e| anyhow!("Invalid descriptor: {e}"))?;
+/// Builds the singlesig P2WPKH CT descriptor of the given BIP44 account of the signer, blinded
+/// with its SLIP77 key
+pub async fn get_descriptor(
+    signer: &SdkLwkSigner,
+    account_index: u32,
+) -> Result<WolletDescriptor, PaymentError> {
+    let coin_type = if signer.is_mainnet() { 1776 } else { 1 };
+    let path = format!("84h/{coin_type}h/{account_index}h");
+    let xpub = signer
+        .derive_xpub(&format!("m/{path}").parse().map_err(anyhow::Error::new)?)
+        .await
+        .map_err(|e| anyhow!("Invalid descriptor: {e}"))?;
+    let fingerprint = signer
+        .fingerprint()
+        .map_err(|e| anyhow!("Invalid descriptor: {e}"))?;
+    let blinding_key = signer
+        .slip77_master_blinding_key()
+        .map_err(|e| anyhow!("Invalid descriptor: {e}"))?
+        .as_bytes()
+        .to_hex();
+    let descriptor_str =
+        format!("ct(slip77({blinding_key}),elwpkh([{fingerprint}/{path}]{xpub}/<0;1>/*))");
     Ok(descriptor_str.parse()?)
 }
 
+/// Returns the number of txs in the history of the given BIP44 account of the signer, which
+/// is scanned from scratch without being cached
+pub(crate) async fn account_tx_count(
+    config: &Config,
+    signer: &SdkLwkSigner,
+    account_index: u32,
+) -> Result<usize, PaymentError> {
+    let descriptor = get_descriptor(signer, account_index).await?;
+    let mut wallet = Wollet::new(
+        config.network.into(),
+        lwk_wollet::NoPersist::new(),
+        descriptor,
+    )?;
+    WalletClient::from_config(config)?
+        .full_scan_to_index(&mut wallet, 0)
+        .await?;
+    Ok(wallet.transactions()?.len())
+}
+
 #[sdk_macros::async_trait]
 impl OnchainWallet for LiquidOnchainWallet {
     /// List all transactions in the wallet
@@ -366,6 +531,25 @@ impl OnchainWallet for LiquidOnchainWallet {
         asset_id: &str,
         amount_sat: u64,
     ) -> Result<Transaction, PaymentError> {
+        let mut pset = self
+            .build_unsigned_tx(
+                fee_rate_sats_per_kvb,
+                recipient_address,
+                asset_id,
+                amount_sat,
+            )
+            .await?;
+        self.sign_wallet_inputs(&mut pset).await?;
+        self.finalize_pset(&mut pset).await
+    }
+
+    async fn build_unsigned_tx(
+        &self,
+        fee_rate_sats_per_kvb: Option<f32>,
+        recipient_address: &str,
+        asset_id: &str,
+        amount_sat: u64,
+    ) -> Result<PartiallySignedTransaction, PaymentError> {
         let lwk_wollet = self.wallet.lock().await;
         let address =
             ElementsAddress::from_str(recipient_address).map_err(|e| PaymentError::Generic {
@@ -404,12 +588,111 @@ impl OnchainWallet for LiquidOnchainWallet {
             let asset = AssetId::from_str(asset_id)?;
             tx_builder = tx_builder.add_recipient(&address, amount_sat, asset)?;
         }
-        let mut pset = tx_builder.finish(&lwk_wollet)?;
-        self.signer
-            .sign(&mut pset)
-            .map_err(|e| PaymentError::Generic {
-                err: format!("Failed to sign transaction: {e:?}"),
-            })?;
+        Ok(tx_builder.finish(&lwk_wollet)?)
+    }
+
+    async fn finalize_pset(
+        &self,
+        pset: &mut PartiallySignedTransaction,
+    ) -> Result<Transaction, PaymentError> {
+        let lwk_wollet = self.wallet.lock().await;
+        Ok(lwk_wollet.finalize(pset)?)
+    }
+
+    async fn build_peg_out_tx(
+        &self,
+        fee_rate_sats_per_kvb: Option<f32>,
+        peg_out_script: &Script,
+        amount_sat: u64,
+    ) -> Result<Transaction, PaymentError> {
+        let lwk_wollet = self.wallet.lock().await;
+        let policy_asset = lwk_wollet.policy_asset();
+        let target_fee_rate = fee_rate_sats_per_kvb.unwrap_or(LIQUID_FEE_RATE_MSAT_PER_VBYTE);
+
+        // LWK can only build burn outputs, so the peg-out script replaces the burn output
+        // script once the tx is built. As the fees are estimated without the peg-out script,
+        // the tx is rebuilt with a higher fee rate if it doesn't meet the target fee rate.
+        let mut fee_rate = target_fee_rate;
+        for _ in 0..2 {
+            let mut pset = lwk_wollet::TxBuilder::new(self.config.network.into())
+                .fee_rate(Some(fee_rate))
+                .enable_ct_discount()
+                .add_burn(amount_sat, policy_asset)?
+                .finish(&lwk_wollet)?;
+            let burn_output = pset
+                .outputs_mut()
+                .iter_mut()
+                .find(|output| {
+                    output.script_pubkey.is_op_return()
+                        && output.amount == Some(amount_sat)
+                        && output.asset == Some(policy_asset)
+                })
+                .ok_or(PaymentError::generic("Peg-out output not found"))?;
+            burn_output.script_pubkey = peg_out_script.clone();
+
+            self.sign_wallet_inputs(&mut pset).await?;
+            let tx = lwk_wollet.finalize(&mut pset)?;
+
+            let fee_sat = tx.all_fees().values().sum::<u64>();
+            let required_fee_sat =
+                (tx.discount_vsize() as f64 * target_fee_rate as f64 / 1000.0).ceil() as u64;
+            if fee_sat >= required_fee_sat {
+                return Ok(tx);
+            }
+            debug!("Peg-out tx fee {fee_sat} sat is below {required_fee_sat} sat, rebuilding");
+            fee_rate *= (required_fee_sat + 1) as f32 / fee_sat.max(1) as f32;
+        }
+        Err(PaymentError::generic(
+            "Failed to build a peg-out tx meeting the fee rate",
+        ))
+    }
+
+    async fn build_issuance_tx(
+        &self,
+        fee_rate_sats_per_kvb: Option<f32>,
+        asset_sat: u64,
+        token_sat: u64,
+        contract: Option<Contract>,
+    ) -> Result<Transaction, PaymentError> {
+        let lwk_wollet = self.wallet.lock().await;
+        let mut pset = lwk_wollet::TxBuilder::new(self.config.network.into())
+            .fee_rate(fee_rate_sats_per_kvb)
+            .enable_ct_discount()
+            .issue_asset(asset_sat, None, token_sat, None, contract)?
+            .finish(&lwk_wollet)?;
+        self.sign_wallet_inputs(&mut pset).await?;
+        Ok(lwk_wollet.finalize(&mut pset)?)
+    }
+
+    async fn build_reissuance_tx(
+        &self,
+        fee_rate_sats_per_kvb: Option<f32>,
+        asset_id: &AssetId,
+        amount_sat: u64,
+    ) -> Result<Transaction, PaymentError> {
+        let lwk_wollet = self.wallet.lock().await;
+        let mut pset = lwk_wollet::TxBuilder::new(self.config.network.into())
+            .fee_rate(fee_rate_sats_per_kvb)
+            .enable_ct_discount()
+            .reissue_asset(*asset_id, amount_sat, None, None)?
+            .finish(&lwk_wollet)?;
+        self.sign_wallet_inputs(&mut pset).await?;
+        Ok(lwk_wollet.finalize(&mut pset)?)
+    }
+
+    async fn build_burn_tx(
+        &self,
+        fee_rate_sats_per_kvb: Option<f32>,
+        asset_id: &AssetId,
+        amount_sat: u64,
+    ) -> Result<Transaction, PaymentError> {
+        let lwk_wollet = self.wallet.lock().await;
+        let mut pset = lwk_wollet::TxBuilder::new(self.config.network.into())
+            .fee_rate(fee_rate_sats_per_kvb)
+            .enable_ct_discount()
+            .add_burn(amount_sat, *asset_id)?
+            .finish(&lwk_wollet)?;
+        self.sign_wallet_inputs(&mut pset).await?;
         Ok(lwk_wollet.finalize(&mut pset)?)
     }
 
@@ -452,11 +735,7 @@ impl OnchainWallet for LiquidOnchainWallet {
             );
         }
 
-        self.signer
-            .sign(&mut pset)
-            .map_err(|e| PaymentError::Generic {
-                err: format!("Failed to sign transaction: {e:?}"),
-            })?;
+        self.sign_wallet_inputs(&mut pset).await?;
         Ok(lwk_wollet.finalize(&mut pset)?)
     }
 
@@ -508,9 +787,7 @@ impl OnchainWallet for LiquidOnchainWallet {
 
         lwk_wollet.add_details(pset)?;
 
-        self.signer.sign(pset).map_err(|e| PaymentError::Generic {
-            err: format!("Failed to sign transaction: {e:?}"),
-        })?;
+        self.sign_wallet_inputs(pset).await?;
 
         // Set the final script witness for each input adding the signature and any missing public key
         for input in pset.inputs_mut() {
@@ -522,6 +799,27 @@ impl OnchainWallet for LiquidOnchainWallet {
         Ok(())
     }
 
+    async fn cosign_pset(&self, pset: &mut PartiallySignedTransaction) -> Result<(), PaymentError> {
+        ensure_sdk!(!self.watch_only, PaymentError::WatchOnly);
+        ensure_sdk!(
+            self.multisig,
+            PaymentError::generic("Only multisig wallet txs can be cosigned")
+        );
+        self.wallet.lock().await.add_details(pset)?;
+        let signatures = self
+            .signer
+            .sign(pset)
+            .await
+            .map_err(|e| PaymentError::Generic {
+                err: format!("Failed to sign transaction: {e:?}"),
+            })?;
+        ensure_sdk!(
+            signatures > 0,
+            PaymentError::generic("The SDK signer is not a cosigner of any of the PSET inputs")
+        );
+        Ok(())
+    }
+
     /// Get the next unused address in the wallet
     async fn next_unused_address(&self) -> Result<Address, PaymentError> {
         let tip = self.tip().await;
@@ -620,7 +918,7 @@ impl OnchainWallet for LiquidOnchainWallet {
                 warn!("Full scan failed due to {e}, reloading wallet and retrying");
                 let mut new_wallet = Self::create_wallet(
                     &self.config,
-                    &self.signer,
+                    &self.descriptor,
                     self.wallet_cache_persister.clone(),
                 )
                 .await?;
@@ -642,7 +940,8 @@ impl OnchainWallet for LiquidOnchainWallet {
         res
     }
 
-    fn sign_message(&self, message: &str) -> Result<String> {
+    async fn sign_message(&self, message: &str) -> Result<String> {
+        ensure_sdk!(!self.watch_only, PaymentError::WatchOnly.into());
         // Prefix and double hash message
         let mut engine = sha256::HashEngine::default();
         engine.write_all(LN_MESSAGE_PREFIX)?;
@@ -650,7 +949,10 @@ impl OnchainWallet for LiquidOnchainWallet {
         let hashed_msg = sha256::Hash::from_engine(engine);
         let double_hashed_msg = Message::from_digest(sha256::Hash::hash(&hashed_msg).into_inner());
         // Get message signature and encode to zbase32
-        let recoverable_sig = self.signer.sign_ecdsa_recoverable(&double_hashed_msg)?;
+        let recoverable_sig = self
+            .signer
+            .sign_ecdsa_recoverable(&double_hashed_msg)
+            .await?;
         Ok(zbase32::encode_full_bytes(recoverable_sig.as_slice()))
     }
 
@@ -676,14 +978,15 @@ mod tests {
     async fn test_sign_and_check_message() -> Result<()> {
         let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
         let sdk_signer: Box<dyn Signer> = Box::new(SdkSigner::new(mnemonic, "", false).unwrap());
-        let sdk_signer = Arc::new(sdk_signer);
+        let sdk_signer: Arc<dyn AsyncSigner> =
+            Arc::new(SyncSignerAdapter::new(Arc::new(sdk_signer)));
 
         let config = Config::regtest_esplora();
 
         create_persister!(storage);
 
         let wallet: Arc<dyn OnchainWallet> = Arc::new(
-            LiquidOnchainWallet::new(config, storage, sdk_signer.clone())
+            LiquidOnchainWallet::new(config, storage, sdk_signer.clone(), 0)
                 .await
                 .unwrap(),
         );
@@ -692,7 +995,7 @@ mod tests {
         let message = "Hello, Liquid!";
 
         // Sign the message
-        let signature = wallet.sign_message(message).unwrap();
+        let signature = wallet.sign_message(message).await.unwrap();
 
         // Get the public key
         let pubkey = wallet.pubkey().unwrap();
@@ -734,4 +1037,142 @@ mod tests {
         // The temporary directory will be automatically deleted when temp_dir goes out of scope
         Ok(())
     }
+
+    #[sdk_macros::async_test_all]
+    async fn test_watch_only_wallet() -> Result<()> {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let sdk_signer: Box<dyn Signer> = Box::new(SdkSigner::new(mnemonic, "", false).unwrap());
+        let descriptor = get_descriptor(&lwk_signer(sdk_signer).await?, 0).await?;
+
+        let config = Config::regtest_esplora();
+        let network: ElementsNetwork = config.network.into();
+
+        create_persister!(storage);
+
+        let wallet: Arc<dyn OnchainWallet> = Arc::new(
+            LiquidOnchainWallet::new_watch_only(config, storage, descriptor.clone()).await?,
+        );
+
+        // Addresses are derived from the descriptor alone
+        let expected_address = Wollet::new(network, lwk_wollet::NoPersist::new(), descriptor)?
+            .address(Some(0))?
+            .address()
+            .clone();
+        assert_eq!(wallet.next_unused_address().await?, expected_address);
+
+        // Signing is refused
+        assert!(wallet.sign_message("Hello, Liquid!").await.is_err());
+        let mut pset = PartiallySignedTransaction::new_v2();
+        assert!(matches!(
+            wallet.sign_pset(&mut pset).await,
+            Err(PaymentError::WatchOnly)
+        ));
+
+        Ok(())
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_account_descriptors() -> Result<()> {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let sdk_signer: Box<dyn Signer> = Box::new(SdkSigner::new(mnemonic, "", false).unwrap());
+        let signer = lwk_signer(sdk_signer).await?;
+
+        let first_account = get_descriptor(&signer, 0).await?;
+        let second_account = get_descriptor(&signer, 1).await?;
+        let account_path =
+            |descriptor: &WolletDescriptor| descriptor.to_string().replace('\'', "h");
+        assert!(account_path(&first_account).contains("/84h/1h/0h]"));
+        assert!(account_path(&second_account).contains("/84h/1h/1h]"));
+
+        // Each account derives its own addresses from the same seed
+        let network: ElementsNetwork = Config::regtest_esplora().network.into();
+        let first_address = Wollet::new(network, lwk_wollet::NoPersist::new(), first_account)?
+            .address(Some(0))?
+            .address()
+            .clone();
+        let second_address = Wollet::new(network, lwk_wollet::NoPersist::new(), second_account)?
+            .address(Some(0))?
+            .address()
+            .clone();
+        assert_ne!(first_address, second_address);
+
+        Ok(())
+    }
+
+    async fn lwk_signer(signer: Box<dyn Signer>) -> Result<SdkLwkSigner> {
+        Ok(SdkLwkSigner::new(Arc::new(SyncSignerAdapter::new(Arc::new(signer)))).await?)
+    }
+
+    async fn multisig_descriptor(
+        threshold: usize,
+        signers: &[&SdkLwkSigner],
+    ) -> Result<WolletDescriptor> {
+        let path: lwk_wollet::bitcoin::bip32::DerivationPath = "m/87'/1'/0'".parse()?;
+        let mut keys = vec![];
+        for signer in signers {
+            keys.push(format!(
+                "[{}/87'/1'/0']{}/<0;1>/*",
+                signer.fingerprint()?,
+                signer.derive_xpub(&path).await?
+            ));
+        }
+        let blinding_key = signers[0].slip77_master_blinding_key()?.as_bytes().to_hex();
+        Ok(format!(
+            "ct(slip77({blinding_key}),elwsh(multi({threshold},{})))",
+            keys.join(",")
+        )
+        .parse()?)
+    }
+
+    #[sdk_macros::async_test_all]
+    async fn test_multisig_wallet() -> Result<()> {
+        let hot_signer: Box<dyn Signer> = Box::new(SdkSigner::new(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "",
+            false,
+        )?);
+        let hot_signer: Arc<dyn AsyncSigner> =
+            Arc::new(SyncSignerAdapter::new(Arc::new(hot_signer)));
+        let cosigner: Box<dyn Signer> = Box::new(SdkSigner::new(
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            "",
+            false,
+        )?);
+        let hot_lwk_signer = SdkLwkSigner::new(hot_signer.clone()).await?;
+        let descriptor =
+            multisig_descriptor(2, &[&hot_lwk_signer, &lwk_signer(cosigner).await?]).await?;
+
+        let config = Config::regtest_esplora();
+
+        // A singlesig descriptor is not a multisig one
+        create_persister!(singlesig_storage);
+        let singlesig_descriptor = get_descriptor(&hot_lwk_signer, 0).await?;
+        assert!(LiquidOnchainWallet::new_multisig(
+            config.clone(),
+            singlesig_storage,
+            Some(hot_signer.clone()),
+            singlesig_descriptor,
+        )
+        .await
+        .is_err());
+
+        create_persister!(storage);
+        let wallet: Arc<dyn OnchainWallet> = Arc::new(
+            LiquidOnchainWallet::new_multisig(config, storage, Some(hot_signer), descriptor)
+                .await?,
+        );
+
+        // Wallet txs cannot be fully signed by the hot key
+        let mut pset = PartiallySignedTransaction::new_v2();
+        assert!(matches!(
+            wallet.sign_pset(&mut pset).await,
+            Err(PaymentError::Generic { .. })
+        ));
+        // A PSET without any wallet inputs cannot be cosigned
+        assert!(wallet.cosign_pset(&mut pset).await.is_err());
+        // The hot key still signs messages
+        assert!(wallet.sign_message("Hello, Liquid!").await.is_ok());
+
+        Ok(())
+    }
 }
@@ -44,6 +44,7 @@ async fn bitcoin(mut handle: SdkNodeHandle) {
         .receive_payment(&PrepareReceiveRequest {
             payment_method: PaymentMethod::BitcoinAddress,
             amount: Some(ReceiveAmount::Bitcoin { payer_amount_sat }),
+            swap_provider: None,
         })
         .await
         .unwrap();
@@ -121,6 +122,8 @@ async fn bitcoin(mut handle: SdkNodeHandle) {
                     receiver_amount_sat,
                 },
                 fee_rate_sat_per_vbyte: None,
+                swap_provider: None,
+                fee_asset: None,
             },
             address.clone(),
         )
@@ -183,6 +186,7 @@ async fn bitcoin(mut handle: SdkNodeHandle) {
         .receive_payment(&PrepareReceiveRequest {
             payment_method: PaymentMethod::BitcoinAddress,
             amount: Some(ReceiveAmount::Bitcoin { payer_amount_sat }),
+            swap_provider: None,
         })
         .await
         .unwrap();
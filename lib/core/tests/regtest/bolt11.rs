@@ -56,6 +56,7 @@ async fn bolt11(mut handle_alice: SdkNodeHandle, mut handle_bob: SdkNodeHandle)
         .receive_payment(&PrepareReceiveRequest {
             payment_method: breez_sdk_liquid::model::PaymentMethod::Bolt11Invoice,
             amount: Some(breez_sdk_liquid::model::ReceiveAmount::Bitcoin { payer_amount_sat }),
+            swap_provider: None,
         })
         .await
         .unwrap();
@@ -119,6 +120,10 @@ async fn bolt11(mut handle_alice: SdkNodeHandle, mut handle_bob: SdkNodeHandle)
             amount: None,
             disable_mrh: None,
             payment_timeout_sec: None,
+            swap_provider: None,
+            from_asset: None,
+            fee_asset: None,
+            export_pset: None,
         })
         .await
         .unwrap();
@@ -175,6 +180,7 @@ async fn bolt11(mut handle_alice: SdkNodeHandle, mut handle_bob: SdkNodeHandle)
             amount: Some(breez_sdk_liquid::model::ReceiveAmount::Bitcoin {
                 payer_amount_sat: receiver_amount_sat,
             }),
+            swap_provider: None,
         })
         .await
         .unwrap();
@@ -186,6 +192,10 @@ async fn bolt11(mut handle_alice: SdkNodeHandle, mut handle_bob: SdkNodeHandle)
             amount: None,
             disable_mrh: None,
             payment_timeout_sec: None,
+            swap_provider: None,
+            from_asset: None,
+            fee_asset: None,
+            export_pset: None,
         })
         .await
         .unwrap();
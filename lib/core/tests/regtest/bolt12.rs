@@ -56,6 +56,7 @@ async fn bolt12(mut handle_alice: SdkNodeHandle, mut handle_bob: SdkNodeHandle)
         .receive_payment(&PrepareReceiveRequest {
             payment_method: PaymentMethod::LiquidAddress,
             amount: None,
+            swap_provider: None,
         })
         .await
         .unwrap();
@@ -87,6 +88,7 @@ async fn bolt12(mut handle_alice: SdkNodeHandle, mut handle_bob: SdkNodeHandle)
         .receive_payment(&PrepareReceiveRequest {
             payment_method: PaymentMethod::Bolt12Offer,
             amount: None,
+            swap_provider: None,
         })
         .await
         .unwrap();
@@ -106,6 +108,10 @@ async fn bolt12(mut handle_alice: SdkNodeHandle, mut handle_bob: SdkNodeHandle)
             }),
             disable_mrh: None,
             payment_timeout_sec: None,
+            swap_provider: None,
+            from_asset: None,
+            fee_asset: None,
+            export_pset: None,
         })
         .await
         .unwrap();
@@ -39,6 +39,7 @@ async fn liquid(mut handle: SdkNodeHandle) {
         .receive_payment(&PrepareReceiveRequest {
             payment_method: PaymentMethod::LiquidAddress,
             amount: None,
+            swap_provider: None,
         })
         .await
         .unwrap();
@@ -97,6 +98,10 @@ async fn liquid(mut handle: SdkNodeHandle) {
             }),
             disable_mrh: None,
             payment_timeout_sec: None,
+            swap_provider: None,
+            from_asset: None,
+            fee_asset: None,
+            export_pset: None,
         })
         .await
         .unwrap();
@@ -4,6 +4,9 @@ mod bitcoin;
 mod bolt11;
 mod bolt12;
 mod liquid;
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+mod multisig;
+mod peg;
 mod utils;
 
 use std::{fs, path::PathBuf, sync::Arc, time::Duration};
@@ -12,10 +15,11 @@ use anyhow::Result;
 use breez_sdk_liquid::model::Config;
 use breez_sdk_liquid::{
     model::{
-        ConnectRequest, EventListener, ListPaymentsRequest, PayOnchainRequest, Payment,
-        PreparePayOnchainRequest, PreparePayOnchainResponse, PrepareReceiveRequest,
-        PrepareReceiveResponse, PrepareSendRequest, PrepareSendResponse, ReceivePaymentRequest,
-        ReceivePaymentResponse, SdkEvent, SendPaymentRequest, SendPaymentResponse,
+        ConnectMultisigRequest, ConnectRequest, EventListener, ListPaymentsRequest,
+        PayOnchainRequest, Payment, PreparePayOnchainRequest, PreparePayOnchainResponse,
+        PrepareReceiveRequest, PrepareReceiveResponse, PrepareSendRequest, PrepareSendResponse,
+        ReceivePaymentRequest, ReceivePaymentResponse, SdkEvent, SendPaymentRequest,
+        SendPaymentResponse,
     },
     sdk::LiquidSdk,
 };
@@ -48,6 +52,13 @@ pub enum ChainBackend {
 
 impl SdkNodeHandle {
     pub async fn init_node(backend: ChainBackend) -> Result<Self> {
+        Self::init_node_with_config(backend, |_| {}).await
+    }
+
+    pub async fn init_node_with_config(
+        backend: ChainBackend,
+        configure: impl FnOnce(&mut Config),
+    ) -> Result<Self> {
         #[cfg(all(target_family = "wasm", target_os = "unknown"))]
         let _ = console_log::init_with_level(log::Level::Debug);
 
@@ -64,6 +75,7 @@ impl SdkNodeHandle {
             ChainBackend::Esplora => Config::regtest_esplora(),
         };
         config.working_dir = data_dir.to_str().unwrap().to_string();
+        configure(&mut config);
 
         #[cfg(all(target_family = "wasm", target_os = "unknown"))]
         let sdk = {
@@ -72,6 +84,7 @@ impl SdkNodeHandle {
                 mnemonic: Some(mnemonic.to_string()),
                 passphrase: None,
                 seed: None,
+                account_index: None,
             };
             let signer: Arc<Box<dyn breez_sdk_liquid::model::Signer>> =
                 Arc::new(Box::new(LiquidSdk::default_signer(&connect_req)?));
@@ -101,9 +114,33 @@ impl SdkNodeHandle {
             mnemonic: Some(mnemonic.to_string()),
             passphrase: None,
             seed: None,
+            account_index: None,
         })
         .await?;
 
+        Self::with_event_listener(sdk).await
+    }
+
+    /// Connects an SDK using the k-of-n multisig wallet of `ct_descriptor`, with the key of
+    /// `mnemonic` as hot key
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    pub async fn init_multisig_node(ct_descriptor: &str, mnemonic: &str) -> Result<Self> {
+        let data_dir = PathBuf::from(format!("/tmp/{}", uuid::Uuid::new_v4()));
+        let mut config = Config::regtest_esplora();
+        config.working_dir = data_dir.to_str().unwrap().to_string();
+
+        let sdk = LiquidSdk::connect_multisig(ConnectMultisigRequest {
+            config,
+            ct_descriptor: ct_descriptor.to_string(),
+            mnemonic: Some(mnemonic.to_string()),
+            passphrase: None,
+        })
+        .await?;
+
+        Self::with_event_listener(sdk).await
+    }
+
+    async fn with_event_listener(sdk: Arc<LiquidSdk>) -> Result<Self> {
         let (sender, receiver) = mpsc::channel(50);
         let listener = ForwardingEventListener { sender };
         sdk.add_event_listener(Box::new(listener)).await?;
@@ -142,6 +179,7 @@ impl SdkNodeHandle {
                 description: None,
                 description_hash: None,
                 payer_note: None,
+                settle_as_asset: None,
             })
             .await?;
         Ok((prepare_response, receive_response))
@@ -158,6 +196,7 @@ impl SdkNodeHandle {
                 prepare_response: prepare_response.clone(),
                 use_asset_fees: None,
                 payer_note: None,
+                sub_account_id: None,
             })
             .await?;
         Ok((prepare_response, send_response))
@@ -174,6 +213,7 @@ impl SdkNodeHandle {
             .pay_onchain(&PayOnchainRequest {
                 address,
                 prepare_response: prepare_response.clone(),
+                use_asset_fees: None,
             })
             .await?;
         Ok((prepare_response, send_response))
@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use breez_sdk_liquid::{
+    bitcoin::bip32::DerivationPath,
+    model::{
+        CombinePsetsRequest, FinalizeSendPaymentRequest, PayAmount, PaymentMethod, PaymentState,
+        PaymentType, PrepareReceiveRequest, PrepareSendRequest, SdkEvent, SignPsetRequest, Signer,
+    },
+    signer::{SdkLwkSigner, SdkSigner, SyncSignerAdapter},
+};
+use serial_test::serial;
+
+use crate::regtest::{utils, SdkNodeHandle, TIMEOUT};
+
+async fn lwk_signer(mnemonic: &str) -> Result<SdkLwkSigner> {
+    let signer: Box<dyn Signer> = Box::new(SdkSigner::new(mnemonic, "", false)?);
+    Ok(SdkLwkSigner::new(Arc::new(SyncSignerAdapter::new(Arc::new(signer)))).await?)
+}
+
+/// The 2-of-2 multisig CT descriptor of the cosigner mnemonics, blinded with the key of the
+/// first one
+async fn multisig_descriptor(mnemonics: &[&str]) -> Result<String> {
+    let path: DerivationPath = "m/87'/1'/0'".parse()?;
+    let mut keys = vec![];
+    let mut blinding_keys = vec![];
+    for mnemonic in mnemonics {
+        let signer = lwk_signer(mnemonic).await?;
+        keys.push(format!(
+            "[{}/87'/1'/0']{}/<0;1>/*",
+            signer.fingerprint()?,
+            signer.derive_xpub(&path).await?
+        ));
+        blinding_keys.push(signer.slip77_master_blinding_key()?);
+    }
+    let blinding_key: String = blinding_keys[0]
+        .as_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    Ok(format!(
+        "ct(slip77({blinding_key}),elwsh(multi(2,{})))",
+        keys.join(",")
+    ))
+}
+
+#[sdk_macros::async_test_not_wasm]
+#[serial]
+async fn multisig() {
+    let hot_mnemonic = bip39::Mnemonic::generate_in(bip39::Language::English, 12)
+        .unwrap()
+        .to_string();
+    let cosigner_mnemonic = bip39::Mnemonic::generate_in(bip39::Language::English, 12)
+        .unwrap()
+        .to_string();
+    let descriptor = multisig_descriptor(&[&hot_mnemonic, &cosigner_mnemonic])
+        .await
+        .unwrap();
+
+    // Each cosigner runs its own SDK on the same wallet
+    let mut handle = SdkNodeHandle::init_multisig_node(&descriptor, &hot_mnemonic)
+        .await
+        .unwrap();
+    let mut cosigner_handle = SdkNodeHandle::init_multisig_node(&descriptor, &cosigner_mnemonic)
+        .await
+        .unwrap();
+    for handle in [&mut handle, &mut cosigner_handle] {
+        handle
+            .wait_for_event(|e| matches!(e, SdkEvent::Synced { .. }), TIMEOUT)
+            .await
+            .unwrap();
+    }
+
+    // --------------RECEIVE--------------
+
+    let (_, receive_response) = handle
+        .receive_payment(&PrepareReceiveRequest {
+            payment_method: PaymentMethod::LiquidAddress,
+            amount: None,
+            swap_provider: None,
+        })
+        .await
+        .unwrap();
+    let amount_sat = 100_000;
+
+    utils::send_to_address_elementsd(&receive_response.destination, amount_sat)
+        .await
+        .unwrap();
+
+    handle
+        .wait_for_event(
+            |e| matches!(e, SdkEvent::PaymentWaitingConfirmation { .. }),
+            TIMEOUT,
+        )
+        .await
+        .unwrap();
+
+    utils::mine_blocks(1).await.unwrap();
+
+    for handle in [&mut handle, &mut cosigner_handle] {
+        handle
+            .wait_for_event(|e| matches!(e, SdkEvent::PaymentSucceeded { .. }), TIMEOUT)
+            .await
+            .unwrap();
+        assert_eq!(handle.get_balance_sat().await.unwrap(), amount_sat);
+    }
+
+    // --------------SEND--------------
+
+    let address = utils::generate_address_elementsd().await.unwrap();
+    let receiver_amount_sat = 50_000;
+
+    let prepare_response = handle
+        .sdk
+        .prepare_send_payment(&PrepareSendRequest {
+            destination: address,
+            amount: Some(PayAmount::Bitcoin {
+                receiver_amount_sat,
+            }),
+            disable_mrh: None,
+            payment_timeout_sec: None,
+            swap_provider: None,
+            from_asset: None,
+            fee_asset: None,
+            export_pset: Some(true),
+        })
+        .await
+        .unwrap();
+    let fees_sat = prepare_response.fees_sat.unwrap();
+    let unsigned_pset = prepare_response.unsigned_pset.clone().unwrap();
+    let finalize_request = |signed_pset: String| FinalizeSendPaymentRequest {
+        prepare_response: prepare_response.clone(),
+        signed_pset,
+        sub_account_id: None,
+    };
+
+    // The signature of the hot key alone does not reach the threshold
+    let hot_pset = handle
+        .sdk
+        .sign_pset(&SignPsetRequest {
+            pset: unsigned_pset.clone(),
+        })
+        .await
+        .unwrap()
+        .pset;
+    assert!(handle
+        .sdk
+        .finalize_send_payment(&finalize_request(hot_pset.clone()))
+        .await
+        .is_err());
+
+    // Once combined with the signature of the cosigner, the payment is broadcast
+    let cosigner_pset = cosigner_handle
+        .sdk
+        .sign_pset(&SignPsetRequest {
+            pset: unsigned_pset,
+        })
+        .await
+        .unwrap()
+        .pset;
+    let combined_pset = handle
+        .sdk
+        .combine_psets(&CombinePsetsRequest {
+            psets: vec![hot_pset, cosigner_pset],
+        })
+        .unwrap()
+        .pset;
+    handle
+        .sdk
+        .finalize_send_payment(&finalize_request(combined_pset))
+        .await
+        .unwrap();
+
+    utils::mine_blocks(1).await.unwrap();
+
+    handle
+        .wait_for_event(|e| matches!(e, SdkEvent::PaymentSucceeded { .. }), TIMEOUT)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        handle.get_balance_sat().await.unwrap(),
+        amount_sat - receiver_amount_sat - fees_sat
+    );
+
+    let payments = handle.get_payments().await.unwrap();
+    assert_eq!(payments.len(), 2);
+    let payment = &payments[0];
+    assert_eq!(payment.amount_sat, receiver_amount_sat);
+    assert_eq!(payment.payment_type, PaymentType::Send);
+    assert_eq!(payment.status, PaymentState::Complete);
+
+    handle.sdk.disconnect().await.unwrap();
+    cosigner_handle.sdk.disconnect().await.unwrap();
+}
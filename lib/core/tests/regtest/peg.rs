@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use breez_sdk_liquid::model::{
+    FederationConfig, PaymentDetails, PaymentState, PaymentType, PegOutRequest,
+    PreparePegOutRequest, ReceivePegInRequest, SdkEvent,
+};
+use serial_test::serial;
+use tokio_with_wasm::alias as tokio;
+
+use crate::regtest::{utils, ChainBackend, SdkNodeHandle, TIMEOUT};
+
+#[cfg(feature = "browser-tests")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[sdk_macros::async_test_all]
+#[serial]
+async fn peg_esplora() {
+    let (fedpeg_script, pegin_confirmations) = utils::get_sidechain_info_elementsd().await.unwrap();
+    let mut handle = SdkNodeHandle::init_node_with_config(ChainBackend::Esplora, |config| {
+        config.federation_config = Some(FederationConfig {
+            fedpeg_script,
+            pegin_confirmations,
+        })
+    })
+    .await
+    .unwrap();
+
+    handle
+        .wait_for_event(|e| matches!(e, SdkEvent::Synced { .. }), TIMEOUT)
+        .await
+        .unwrap();
+
+    // --------------PEG-IN--------------
+
+    let deposit_amount_sat = 100_000;
+    let receive_response = handle
+        .sdk
+        .receive_peg_in(&ReceivePegInRequest { description: None })
+        .await
+        .unwrap();
+    assert_eq!(receive_response.required_confirmations, pegin_confirmations);
+
+    utils::send_to_address_bitcoind(&receive_response.bitcoin_address, deposit_amount_sat)
+        .await
+        .unwrap();
+    utils::mine_blocks(1).await.unwrap();
+
+    handle
+        .wait_for_event(
+            |e| matches!(e, SdkEvent::PaymentWaitingConfirmation { .. }),
+            TIMEOUT,
+        )
+        .await
+        .unwrap();
+
+    // Reach the peg-in confirmation depth
+    utils::mine_blocks(pegin_confirmations as u64)
+        .await
+        .unwrap();
+
+    // Wait for the claim tx to be broadcast
+    tokio::time::sleep(Duration::from_secs(5)).await;
+
+    // Confirm claim tx
+    utils::mine_blocks(1).await.unwrap();
+
+    handle
+        .wait_for_event(|e| matches!(e, SdkEvent::PaymentSucceeded { .. }), TIMEOUT)
+        .await
+        .unwrap();
+
+    let payments = handle.get_payments().await.unwrap();
+    assert_eq!(payments.len(), 1);
+    let payment = &payments[0];
+    assert_eq!(payment.payment_type, PaymentType::Receive);
+    assert_eq!(payment.status, PaymentState::Complete);
+    assert!(payment.amount_sat > 0 && payment.amount_sat < deposit_amount_sat);
+    assert!(
+        matches!(&payment.details, PaymentDetails::Peg { peg_id, .. } if *peg_id == receive_response.peg_id)
+    );
+    assert_eq!(handle.get_balance_sat().await.unwrap(), payment.amount_sat);
+
+    // --------------PEG-OUT--------------
+
+    let initial_balance_sat = handle.get_balance_sat().await.unwrap();
+    let receiver_amount_sat = 50_000;
+    let bitcoin_address = utils::generate_address_bitcoind().await.unwrap();
+
+    let prepare_response = handle
+        .sdk
+        .prepare_peg_out(&PreparePegOutRequest {
+            receiver_amount_sat,
+        })
+        .await
+        .unwrap();
+    let fees_sat = prepare_response.fees_sat;
+
+    handle
+        .sdk
+        .peg_out(&PegOutRequest {
+            bitcoin_address,
+            prepare_response,
+        })
+        .await
+        .unwrap();
+
+    handle
+        .wait_for_event(
+            |e| matches!(e, SdkEvent::PaymentWaitingConfirmation { .. }),
+            TIMEOUT,
+        )
+        .await
+        .unwrap();
+    utils::mine_blocks(1).await.unwrap();
+
+    handle
+        .wait_for_event(|e| matches!(e, SdkEvent::PaymentSucceeded { .. }), TIMEOUT)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        handle.get_balance_sat().await.unwrap(),
+        initial_balance_sat - receiver_amount_sat - fees_sat
+    );
+}
@@ -123,6 +123,25 @@ pub async fn send_to_address_elementsd(
     .ok_or_else(|| "Invalid response".into())
 }
 
+/// Returns the federation peg script and the peg-in confirmation depth of elementsd
+pub async fn get_sidechain_info_elementsd() -> Result<(String, u32), Box<dyn Error>> {
+    let response = json_rpc_request(
+        ELEMENTSD_URL,
+        ELEMENTSD_COOKIE,
+        "getsidechaininfo",
+        json!([]),
+    )
+    .await?;
+    let fedpeg_script = response["fedpegscript"]
+        .as_str()
+        .ok_or("Missing fedpegscript field")?
+        .to_string();
+    let pegin_confirmations = response["pegin_confirmation_depth"]
+        .as_u64()
+        .ok_or("Missing pegin_confirmation_depth field")?;
+    Ok((fedpeg_script, pegin_confirmations as u32))
+}
+
 pub async fn generate_invoice_lnd(amount_sat: u64) -> Result<String, Box<dyn Error>> {
     let response = lnd_request("v1/invoices", json!({ "value": amount_sat })).await?;
     response["payment_request"]
@@ -147,6 +147,7 @@ impl RelayMessageHandler for SdkRelayMessageHandler {
             amount: Some(ReceiveAmount::Bitcoin {
                 payer_amount_sat: req.amount.div_ceil(1000),
             }),
+            swap_provider: None,
         };
 
         let prepare_response = self.sdk.prepare_receive_payment(&prepare_req).await?;
@@ -160,6 +161,7 @@ impl RelayMessageHandler for SdkRelayMessageHandler {
                     .clone()
                     .map(|hash| DescriptionHash::Custom { hash }),
                 payer_note: None,
+                settle_as_asset: None,
             })
             .await?;
 
@@ -197,6 +199,10 @@ impl RelayMessageHandler for SdkRelayMessageHandler {
             }),
             disable_mrh: Some(true),
             payment_timeout_sec: Some(180), // 3 minutes timeout
+            swap_provider: None,
+            from_asset: None,
+            fee_asset: None,
+            export_pset: None,
         };
 
         // Prepare the payment
@@ -207,6 +213,7 @@ impl RelayMessageHandler for SdkRelayMessageHandler {
             prepare_response: prepare_resp,
             use_asset_fees: None,
             payer_note: None,
+            sub_account_id: None,
         };
 
         // Send the payment
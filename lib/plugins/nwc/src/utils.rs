@@ -1,10 +1,11 @@
 use web_time::{SystemTime, UNIX_EPOCH};
 
 pub(crate) fn now() -> u32 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs() as u32
+    #[cfg(feature = "test-utils")]
+    let now = breez_sdk_liquid::test_utils::clock::installed_now().unwrap_or_else(SystemTime::now);
+    #[cfg(not(feature = "test-utils"))]
+    let now = SystemTime::now();
+    now.duration_since(UNIX_EPOCH).unwrap().as_secs() as u32
 }
 
 pub(crate) fn mins_to_seconds(mins: u32) -> u32 {
@@ -15,10 +15,8 @@ use crate::model::*;
 use crate::plugin::nwc::{BindingNwcService, NwcConfig};
 
 use anyhow::anyhow;
-use breez_sdk_liquid::elements::hex::ToHex;
 use breez_sdk_liquid::persist::Persister;
 use breez_sdk_liquid::sdk::{LiquidSdk, LiquidSdkBuilder};
-use breez_sdk_liquid::signer::SdkLwkSigner;
 use breez_sdk_liquid::PRODUCTION_BREEZSERVER_URL;
 use log::LevelFilter;
 use logger::{Logger, WasmLogger};
@@ -35,7 +33,7 @@ pub struct BindingLiquidSdk {
 #[wasm_bindgen(js_name = "connect")]
 pub async fn connect(req: ConnectRequest) -> WasmResult<BindingLiquidSdk> {
     let signer = Box::new(LiquidSdk::default_signer(&req.clone().into())?);
-    connect_inner(req.config, signer).await
+    connect_with_boxed_signer(req.config, req.account_index, signer).await
 }
 
 #[wasm_bindgen(js_name = "connectWithSigner")]
@@ -44,25 +42,67 @@ pub async fn connect_with_signer(
     signer: Signer,
 ) -> WasmResult<BindingLiquidSdk> {
     let signer: Box<dyn breez_sdk_liquid::model::Signer> = Box::new(WasmSigner { signer });
-    connect_inner(req.config, signer).await
+    connect_with_boxed_signer(req.config, req.account_index, signer).await
 }
 
-async fn connect_inner(
+#[wasm_bindgen(js_name = "connectWatchOnly")]
+pub async fn connect_watch_only(req: ConnectWatchOnlyRequest) -> WasmResult<BindingLiquidSdk> {
+    let config: breez_sdk_liquid::model::Config = req.config.into();
+    let sdk_builder = LiquidSdkBuilder::new_watch_only(
+        config.clone(),
+        PRODUCTION_BREEZSERVER_URL.to_string(),
+        &req.ct_descriptor,
+    )?;
+    connect_inner(config, sdk_builder).await
+}
+
+#[wasm_bindgen(js_name = "connectMultisig")]
+pub async fn connect_multisig(req: ConnectMultisigRequest) -> WasmResult<BindingLiquidSdk> {
+    let config: breez_sdk_liquid::model::Config = req.config.into();
+    let hot_signer = match req.mnemonic {
+        Some(mnemonic) => {
+            let signer: Box<dyn breez_sdk_liquid::model::Signer> = Box::new(
+                LiquidSdk::default_signer(&breez_sdk_liquid::model::ConnectRequest {
+                    config: config.clone(),
+                    mnemonic: Some(mnemonic),
+                    passphrase: req.passphrase,
+                    seed: None,
+                    account_index: None,
+                })?,
+            );
+            Some(Arc::new(signer))
+        }
+        None => None,
+    };
+    let sdk_builder = LiquidSdkBuilder::new_multisig(
+        config.clone(),
+        PRODUCTION_BREEZSERVER_URL.to_string(),
+        &req.ct_descriptor,
+        hot_signer,
+    )?;
+    connect_inner(config, sdk_builder).await
+}
+
+async fn connect_with_boxed_signer(
     config: Config,
+    account_index: Option<u32>,
     signer: Box<dyn breez_sdk_liquid::model::Signer>,
 ) -> WasmResult<BindingLiquidSdk> {
     let config: breez_sdk_liquid::model::Config = config.into();
-    let signer = Arc::new(signer);
-
     let mut sdk_builder = LiquidSdkBuilder::new(
         config.clone(),
         PRODUCTION_BREEZSERVER_URL.to_string(),
-        Arc::clone(&signer),
+        Arc::new(signer),
     )?;
+    sdk_builder.account_index(account_index.unwrap_or_default());
+    connect_inner(config, sdk_builder).await
+}
 
-    let sdk_lwk_signer = SdkLwkSigner::new(Arc::clone(&signer))?;
-    let fingerprint = sdk_lwk_signer.fingerprint()?;
-    let fingerprint = fingerprint.to_hex();
+async fn connect_inner(
+    config: breez_sdk_liquid::model::Config,
+    mut sdk_builder: LiquidSdkBuilder,
+) -> WasmResult<BindingLiquidSdk> {
+    let fingerprint = sdk_builder.fingerprint().await?;
 
     let wallet_dir = PathBuf::from_str(&config.get_wallet_dir(&config.working_dir, &fingerprint)?)
         .map_err(|e| anyhow!(e.to_string()))?;
@@ -138,9 +178,20 @@ impl BindingLiquidSdk {
         Ok(self.sdk.get_info().await?.into())
     }
 
+    #[wasm_bindgen(js_name = "listAccounts")]
+    pub async fn list_accounts(&self) -> WasmResult<Vec<AccountInfo>> {
+        Ok(self
+            .sdk
+            .list_accounts()
+            .await?
+            .into_iter()
+            .map(|a| a.into())
+            .collect())
+    }
+
     #[wasm_bindgen(js_name = "signMessage")]
-    pub fn sign_message(&self, req: SignMessageRequest) -> WasmResult<SignMessageResponse> {
-        Ok(self.sdk.sign_message(&req.into())?.into())
+    pub async fn sign_message(&self, req: SignMessageRequest) -> WasmResult<SignMessageResponse> {
+        Ok(self.sdk.sign_message(&req.into()).await?.into())
     }
 
     #[wasm_bindgen(js_name = "checkMessage")]
@@ -180,6 +231,24 @@ impl BindingLiquidSdk {
         Ok(self.sdk.send_payment(&req.into()).await?.into())
     }
 
+    #[wasm_bindgen(js_name = "finalizeSendPayment")]
+    pub async fn finalize_send_payment(
+        &self,
+        req: FinalizeSendPaymentRequest,
+    ) -> WasmResult<SendPaymentResponse> {
+        Ok(self.sdk.finalize_send_payment(&req.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "signPset")]
+    pub async fn sign_pset(&self, req: SignPsetRequest) -> WasmResult<SignPsetResponse> {
+        Ok(self.sdk.sign_pset(&req.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "combinePsets")]
+    pub fn combine_psets(&self, req: CombinePsetsRequest) -> WasmResult<CombinePsetsResponse> {
+        Ok(self.sdk.combine_psets(&req.into())?.into())
+    }
+
     #[wasm_bindgen(js_name = "prepareReceivePayment")]
     pub async fn prepare_receive_payment(
         &self,
@@ -227,6 +296,58 @@ impl BindingLiquidSdk {
         Ok(self.sdk.pay_onchain(&req.into()).await?.into())
     }
 
+    #[wasm_bindgen(js_name = "receivePegIn")]
+    pub async fn receive_peg_in(
+        &self,
+        req: ReceivePegInRequest,
+    ) -> WasmResult<ReceivePegInResponse> {
+        Ok(self.sdk.receive_peg_in(&req.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "preparePegOut")]
+    pub async fn prepare_peg_out(
+        &self,
+        req: PreparePegOutRequest,
+    ) -> WasmResult<PreparePegOutResponse> {
+        Ok(self.sdk.prepare_peg_out(&req.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "pegOut")]
+    pub async fn peg_out(&self, req: PegOutRequest) -> WasmResult<SendPaymentResponse> {
+        Ok(self.sdk.peg_out(&req.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "issueAsset")]
+    pub async fn issue_asset(&self, req: IssueAssetRequest) -> WasmResult<IssueAssetResponse> {
+        Ok(self.sdk.issue_asset(&req.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "reissueAsset")]
+    pub async fn reissue_asset(
+        &self,
+        req: ReissueAssetRequest,
+    ) -> WasmResult<AssetOperationResponse> {
+        Ok(self.sdk.reissue_asset(&req.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "burnAsset")]
+    pub async fn burn_asset(&self, req: BurnAssetRequest) -> WasmResult<AssetOperationResponse> {
+        Ok(self.sdk.burn_asset(&req.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "prepareAssetSwap")]
+    pub async fn prepare_asset_swap(
+        &self,
+        req: PrepareAssetSwapRequest,
+    ) -> WasmResult<PrepareAssetSwapResponse> {
+        Ok(self.sdk.prepare_asset_swap(&req.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "assetSwap")]
+    pub async fn asset_swap(&self, req: AssetSwapRequest) -> WasmResult<AssetOperationResponse> {
+        Ok(self.sdk.asset_swap(&req.into()).await?.into())
+    }
+
     #[wasm_bindgen(js_name = "prepareBuyBitcoin")]
     pub async fn prepare_buy_bitcoin(
         &self,
@@ -256,6 +377,64 @@ impl BindingLiquidSdk {
         Ok(self.sdk.get_payment(&req.into()).await?.map(|r| r.into()))
     }
 
+    #[wasm_bindgen(js_name = "createSubAccount")]
+    pub async fn create_sub_account(&self, req: CreateSubAccountRequest) -> WasmResult<SubAccount> {
+        Ok(self.sdk.create_sub_account(&req.into()).await?.into())
+    }
+
+    #[wasm_bindgen(js_name = "listSubAccounts")]
+    pub async fn list_sub_accounts(&self) -> WasmResult<Vec<SubAccount>> {
+        Ok(self
+            .sdk
+            .list_sub_accounts()
+            .await?
+            .into_iter()
+            .map(|a| a.into())
+            .collect())
+    }
+
+    #[wasm_bindgen(js_name = "attributeToSubAccount")]
+    pub async fn attribute_to_sub_account(
+        &self,
+        req: AttributeToSubAccountRequest,
+    ) -> WasmResult<()> {
+        self.sdk.attribute_to_sub_account(&req.into()).await?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = "transferSubAccountFunds")]
+    pub async fn transfer_sub_account_funds(
+        &self,
+        req: TransferSubAccountFundsRequest,
+    ) -> WasmResult<Vec<LedgerEntry>> {
+        Ok(self
+            .sdk
+            .transfer_sub_account_funds(&req.into())
+            .await?
+            .into_iter()
+            .map(|e| e.into())
+            .collect())
+    }
+
+    #[wasm_bindgen(js_name = "listLedgerEntries")]
+    pub async fn list_ledger_entries(
+        &self,
+        req: ListLedgerEntriesRequest,
+    ) -> WasmResult<Vec<LedgerEntry>> {
+        Ok(self
+            .sdk
+            .list_ledger_entries(&req.into())
+            .await?
+            .into_iter()
+            .map(|e| e.into())
+            .collect())
+    }
+
+    #[wasm_bindgen(js_name = "getLedgerInfo")]
+    pub async fn get_ledger_info(&self) -> WasmResult<LedgerInfo> {
+        Ok(self.sdk.get_ledger_info().await?.into())
+    }
+
     #[wasm_bindgen(js_name = "fetchPaymentProposedFees")]
     pub async fn fetch_payment_proposed_fees(
         &self,
@@ -326,9 +326,38 @@ pub struct Config {
     pub onchain_fee_rate_leeway_sat: Option<u64>,
     pub asset_metadata: Option<Vec<AssetMetadata>>,
     pub sideswap_api_key: Option<String>,
+    pub sideswap_url: Option<String>,
     pub use_magic_routing_hints: bool,
     pub onchain_sync_period_sec: u32,
     pub onchain_sync_request_timeout_sec: u32,
+    pub confirmation_policy: Option<ConfirmationPolicy>,
+    pub swapper_fallback_urls: Option<Vec<String>>,
+    pub federation_config: Option<FederationConfig>,
+    pub asset_registry_url: Option<String>,
+    pub breez_server_url: Option<String>,
+}
+
+#[derive(Clone)]
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::ConfirmationPolicy)]
+pub struct ConfirmationPolicy {
+    pub liquid_confirmations: u32,
+    pub bitcoin_confirmations: u32,
+    pub tiers: Vec<ConfirmationTier>,
+}
+
+#[derive(Clone)]
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::ConfirmationTier)]
+pub struct ConfirmationTier {
+    pub min_amount_sat: u64,
+    pub liquid_confirmations: u32,
+    pub bitcoin_confirmations: u32,
+}
+
+#[derive(Clone)]
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::FederationConfig)]
+pub struct FederationConfig {
+    pub fedpeg_script: String,
+    pub pegin_confirmations: u32,
 }
 
 #[derive(Clone)]
@@ -339,6 +368,13 @@ pub enum LiquidNetwork {
     Regtest,
 }
 
+#[derive(Clone)]
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::Blockchain)]
+pub enum Blockchain {
+    Liquid,
+    Bitcoin,
+}
+
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::SdkEvent)]
 pub enum SdkEvent {
     PaymentFailed { details: Payment },
@@ -349,9 +385,11 @@ pub enum SdkEvent {
     PaymentSucceeded { details: Payment },
     PaymentWaitingConfirmation { details: Payment },
     PaymentWaitingFeeAcceptance { details: Payment },
+    PaymentDoubleSpent { details: Payment },
     Synced,
     SyncFailed { error: String },
     DataSynced { did_pull_new_records: bool },
+    Reorg { chain: Blockchain, depth: u32 },
 }
 
 #[derive(Clone)]
@@ -361,11 +399,27 @@ pub struct ConnectRequest {
     pub mnemonic: Option<String>,
     pub passphrase: Option<String>,
     pub seed: Option<Vec<u8>>,
+    pub account_index: Option<u32>,
 }
 
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::ConnectWithSignerRequest)]
 pub struct ConnectWithSignerRequest {
     pub config: Config,
+    pub account_index: Option<u32>,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::ConnectWatchOnlyRequest)]
+pub struct ConnectWatchOnlyRequest {
+    pub config: Config,
+    pub ct_descriptor: String,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::ConnectMultisigRequest)]
+pub struct ConnectMultisigRequest {
+    pub config: Config,
+    pub ct_descriptor: String,
+    pub mnemonic: Option<String>,
+    pub passphrase: Option<String>,
 }
 
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::PaymentMethod)]
@@ -391,6 +445,7 @@ pub enum ReceiveAmount {
 pub struct PrepareReceiveRequest {
     pub payment_method: PaymentMethod,
     pub amount: Option<ReceiveAmount>,
+    pub swap_provider: Option<String>,
 }
 
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::PrepareReceiveResponse)]
@@ -401,6 +456,8 @@ pub struct PrepareReceiveResponse {
     pub min_payer_amount_sat: Option<u64>,
     pub max_payer_amount_sat: Option<u64>,
     pub swapper_feerate: Option<f64>,
+    pub swap_provider: Option<String>,
+    pub swap_quotes: Vec<SwapQuote>,
 }
 
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::DescriptionHash)]
@@ -415,6 +472,13 @@ pub struct ReceivePaymentRequest {
     pub description: Option<String>,
     pub description_hash: Option<DescriptionHash>,
     pub payer_note: Option<String>,
+    pub settle_as_asset: Option<SettleAsAsset>,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::SettleAsAsset)]
+pub struct SettleAsAsset {
+    pub asset_id: String,
+    pub max_slippage_bps: Option<u32>,
 }
 
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::ReceivePaymentResponse)]
@@ -442,16 +506,29 @@ pub struct Limits {
     pub max_zero_conf_sat: u64,
 }
 
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::SwapQuote)]
+pub struct SwapQuote {
+    pub swap_provider: String,
+    pub limits: Limits,
+    pub fees_percentage: f64,
+    pub fixed_fees_sat: u64,
+    pub fees_sat: Option<u64>,
+}
+
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::LightningPaymentLimitsResponse)]
 pub struct LightningPaymentLimitsResponse {
     pub send: Limits,
     pub receive: Limits,
+    pub send_quotes: Vec<SwapQuote>,
+    pub receive_quotes: Vec<SwapQuote>,
 }
 
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::OnchainPaymentLimitsResponse)]
 pub struct OnchainPaymentLimitsResponse {
     pub send: Limits,
     pub receive: Limits,
+    pub send_quotes: Vec<SwapQuote>,
+    pub receive_quotes: Vec<SwapQuote>,
 }
 
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::PrepareSendRequest)]
@@ -460,6 +537,10 @@ pub struct PrepareSendRequest {
     pub amount: Option<PayAmount>,
     pub disable_mrh: Option<bool>,
     pub payment_timeout_sec: Option<u64>,
+    pub swap_provider: Option<String>,
+    pub from_asset: Option<String>,
+    pub fee_asset: Option<String>,
+    pub export_pset: Option<bool>,
 }
 
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::SendDestination)]
@@ -486,8 +567,13 @@ pub struct PrepareSendResponse {
     pub fees_sat: Option<u64>,
     pub estimated_asset_fees: Option<f64>,
     pub exchange_amount_sat: Option<u64>,
+    pub from_asset: Option<String>,
+    pub fee_asset: Option<String>,
     pub disable_mrh: Option<bool>,
     pub payment_timeout_sec: Option<u64>,
+    pub swap_provider: Option<String>,
+    pub swap_quotes: Vec<SwapQuote>,
+    pub unsigned_pset: Option<String>,
 }
 
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::SendPaymentRequest)]
@@ -495,6 +581,14 @@ pub struct SendPaymentRequest {
     pub prepare_response: PrepareSendResponse,
     pub use_asset_fees: Option<bool>,
     pub payer_note: Option<String>,
+    pub sub_account_id: Option<String>,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::FinalizeSendPaymentRequest)]
+pub struct FinalizeSendPaymentRequest {
+    pub prepare_response: PrepareSendResponse,
+    pub signed_pset: String,
+    pub sub_account_id: Option<String>,
 }
 
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::SendPaymentResponse)]
@@ -514,12 +608,17 @@ pub enum PayAmount {
         from_asset: Option<String>,
     },
     Drain,
+    DrainAsset {
+        asset_id: String,
+    },
 }
 
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::PreparePayOnchainRequest)]
 pub struct PreparePayOnchainRequest {
     pub amount: PayAmount,
     pub fee_rate_sat_per_vbyte: Option<u32>,
+    pub swap_provider: Option<String>,
+    pub fee_asset: Option<String>,
 }
 
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::PreparePayOnchainResponse)]
@@ -527,12 +626,171 @@ pub struct PreparePayOnchainResponse {
     pub receiver_amount_sat: u64,
     pub claim_fees_sat: u64,
     pub total_fees_sat: u64,
+    pub swap_provider: Option<String>,
+    pub swap_quotes: Vec<SwapQuote>,
+    pub fee_asset: Option<String>,
+    pub estimated_asset_fees: Option<f64>,
 }
 
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::PayOnchainRequest)]
 pub struct PayOnchainRequest {
     pub address: String,
     pub prepare_response: PreparePayOnchainResponse,
+    pub use_asset_fees: Option<bool>,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::ReceivePegInRequest)]
+pub struct ReceivePegInRequest {
+    pub description: Option<String>,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::ReceivePegInResponse)]
+pub struct ReceivePegInResponse {
+    pub peg_id: String,
+    pub bitcoin_address: String,
+    pub required_confirmations: u32,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::PreparePegOutRequest)]
+pub struct PreparePegOutRequest {
+    pub receiver_amount_sat: u64,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::PreparePegOutResponse)]
+pub struct PreparePegOutResponse {
+    pub receiver_amount_sat: u64,
+    pub fees_sat: u64,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::PegOutRequest)]
+pub struct PegOutRequest {
+    pub bitcoin_address: String,
+    pub prepare_response: PreparePegOutResponse,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::AssetContract)]
+pub struct AssetContract {
+    pub domain: String,
+    pub name: String,
+    pub ticker: String,
+    pub precision: u8,
+    pub issuer_pubkey: Option<String>,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::IssueAssetRequest)]
+pub struct IssueAssetRequest {
+    pub amount: f64,
+    pub reissuance_token_amount_sat: Option<u64>,
+    pub contract: AssetContract,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::IssueAssetResponse)]
+pub struct IssueAssetResponse {
+    pub asset_id: String,
+    pub reissuance_token_id: Option<String>,
+    pub contract_hash: String,
+    pub payment: Payment,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::ReissueAssetRequest)]
+pub struct ReissueAssetRequest {
+    pub asset_id: String,
+    pub amount: f64,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::BurnAssetRequest)]
+pub struct BurnAssetRequest {
+    pub asset_id: String,
+    pub amount: f64,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::AssetOperationResponse)]
+pub struct AssetOperationResponse {
+    pub payment: Payment,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::CreateSubAccountRequest)]
+pub struct CreateSubAccountRequest {
+    pub name: String,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::SubAccount)]
+pub struct SubAccount {
+    pub id: String,
+    pub name: String,
+    pub balance_sat: u64,
+    pub created_at: u32,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::AttributeToSubAccountRequest)]
+pub struct AttributeToSubAccountRequest {
+    pub sub_account_id: String,
+    pub destination: String,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::TransferSubAccountFundsRequest)]
+pub struct TransferSubAccountFundsRequest {
+    pub from_sub_account_id: Option<String>,
+    pub to_sub_account_id: Option<String>,
+    pub amount_sat: u64,
+    pub description: Option<String>,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::LedgerEntryType)]
+pub enum LedgerEntryType {
+    Receive = 0,
+    Send = 1,
+    Refund = 2,
+    Transfer = 3,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::LedgerEntry)]
+pub struct LedgerEntry {
+    pub id: i64,
+    pub sub_account_id: String,
+    pub entry_type: LedgerEntryType,
+    pub amount_sat: i64,
+    pub balance_sat: u64,
+    pub payment_id: Option<String>,
+    pub transfer_id: Option<String>,
+    pub description: Option<String>,
+    pub created_at: u32,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::ListLedgerEntriesRequest)]
+pub struct ListLedgerEntriesRequest {
+    pub sub_account_id: Option<String>,
+    pub offset: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::LedgerInfo)]
+pub struct LedgerInfo {
+    pub allocated_sat: u64,
+    pub unallocated_sat: u64,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::PrepareAssetSwapRequest)]
+pub struct PrepareAssetSwapRequest {
+    pub from_asset: String,
+    pub to_asset: String,
+    pub receiver_amount: f64,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::PrepareAssetSwapResponse)]
+pub struct PrepareAssetSwapResponse {
+    pub from_asset: String,
+    pub to_asset: String,
+    pub payer_amount_sat: u64,
+    pub receiver_amount_sat: u64,
+    pub fees_sat: u64,
+    pub exchange_rate: f64,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::AssetSwapRequest)]
+pub struct AssetSwapRequest {
+    pub prepare_response: PrepareAssetSwapResponse,
+    pub description: Option<String>,
 }
 
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::PrepareRefundRequest)]
@@ -592,6 +850,12 @@ pub struct GetInfoResponse {
     pub blockchain_info: BlockchainInfo,
 }
 
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::AccountInfo)]
+pub struct AccountInfo {
+    pub account_index: u32,
+    pub tx_count: u32,
+}
+
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::SignMessageRequest)]
 pub struct SignMessageRequest {
     pub message: String,
@@ -602,6 +866,26 @@ pub struct SignMessageResponse {
     pub signature: String,
 }
 
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::SignPsetRequest)]
+pub struct SignPsetRequest {
+    pub pset: String,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::SignPsetResponse)]
+pub struct SignPsetResponse {
+    pub pset: String,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::CombinePsetsRequest)]
+pub struct CombinePsetsRequest {
+    pub psets: Vec<String>,
+}
+
+#[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::CombinePsetsResponse)]
+pub struct CombinePsetsResponse {
+    pub pset: String,
+}
+
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::CheckMessageRequest)]
 pub struct CheckMessageRequest {
     pub message: String,
@@ -733,6 +1017,7 @@ pub enum PaymentDetails {
         refund_tx_id: Option<String>,
         refund_tx_amount_sat: Option<u64>,
         settled_at: Option<u32>,
+        asset_swap_id: Option<String>,
     },
     Liquid {
         destination: String,
@@ -755,6 +1040,24 @@ pub enum PaymentDetails {
         refund_tx_id: Option<String>,
         refund_tx_amount_sat: Option<u64>,
     },
+    Peg {
+        peg_id: String,
+        bitcoin_address: String,
+        description: String,
+        bitcoin_tx_id: Option<String>,
+        claim_tx_id: Option<String>,
+    },
+    AssetSwap {
+        swap_id: String,
+        description: String,
+        from_asset_id: String,
+        to_asset_id: String,
+        payer_amount_sat: u64,
+        receiver_amount_sat: u64,
+        from_asset_info: Option<AssetInfo>,
+        to_asset_info: Option<AssetInfo>,
+        receive_swap_id: Option<String>,
+    },
 }
 
 #[sdk_macros::extern_wasm_bindgen(breez_sdk_liquid::prelude::Payment)]
@@ -768,6 +1071,7 @@ pub struct Payment {
     pub swapper_fees_sat: Option<u64>,
     pub payment_type: PaymentType,
     pub status: PaymentState,
+    pub blocks_remaining: Option<u32>,
     pub details: PaymentDetails,
 }
 
@@ -26,6 +26,7 @@ pub enum _PaymentError {
     SelfTransferNotSupported,
     SendError { err: String },
     SignerError { err: String },
+    WatchOnly,
 }
 
 #[frb(mirror(SdkError))]
@@ -35,6 +36,7 @@ pub enum _SdkError {
     NetworkNotSupported { network: String },
     NotStarted,
     ServiceConnectivity { err: String },
+    WatchOnly,
 }
 
 #[frb(mirror(PluginStorageError))]
@@ -1,7 +1,7 @@
 use crate::frb_generated::StreamSink;
 use flutter_rust_bridge::frb;
 
-pub use breez_sdk_liquid::model::{Payment, SdkEvent};
+pub use breez_sdk_liquid::model::{Blockchain, Payment, SdkEvent};
 pub use breez_sdk_liquid::prelude::EventListener as _EventListener;
 
 #[frb(mirror(SdkEvent))]
@@ -30,6 +30,9 @@ pub enum _SdkEvent {
     PaymentWaitingFeeAcceptance {
         details: Payment,
     },
+    PaymentDoubleSpent {
+        details: Payment,
+    },
     /// Synced with mempool and onchain data
     Synced,
     /// Failed to sync with mempool and onchain data
@@ -41,6 +44,17 @@ pub enum _SdkEvent {
         /// Indicates new data was pulled from other instances.
         did_pull_new_records: bool,
     },
+    /// A chain reorganization was detected
+    Reorg {
+        chain: Blockchain,
+        depth: u32,
+    },
+}
+
+#[frb(mirror(Blockchain))]
+pub enum _Blockchain {
+    Liquid,
+    Bitcoin,
 }
 
 pub struct BreezEventListener {
@@ -301,22 +301,30 @@ pub struct _Symbol {
 
 pub use breez_sdk_liquid::{
     model::{
-        AcceptPaymentProposedFeesRequest, AssetBalance, AssetInfo, AssetMetadata, BackupRequest,
-        BlockchainExplorer, BlockchainInfo, BuyBitcoinProvider, BuyBitcoinRequest,
-        CheckMessageRequest, CheckMessageResponse, Config, ConnectRequest,
-        CreateBolt12InvoiceRequest, CreateBolt12InvoiceResponse, DescriptionHash,
-        FetchPaymentProposedFeesRequest, FetchPaymentProposedFeesResponse, GetInfoResponse,
-        GetPaymentRequest, LightningPaymentLimitsResponse, Limits, LiquidNetwork,
+        AcceptPaymentProposedFeesRequest, AccountInfo, AssetBalance, AssetContract, AssetInfo,
+        AssetMetadata, AssetOperationResponse, AssetSwapRequest, AttributeToSubAccountRequest,
+        BackupRequest, BlockchainExplorer, BlockchainInfo, BurnAssetRequest, BuyBitcoinProvider,
+        BuyBitcoinRequest, CheckMessageRequest, CheckMessageResponse, CombinePsetsRequest,
+        CombinePsetsResponse, Config, ConfirmationPolicy, ConfirmationTier, ConnectMultisigRequest,
+        ConnectRequest, ConnectWatchOnlyRequest, CreateBolt12InvoiceRequest,
+        CreateBolt12InvoiceResponse, CreateSubAccountRequest, DescriptionHash, FederationConfig,
+        FetchPaymentProposedFeesRequest, FetchPaymentProposedFeesResponse,
+        FinalizeSendPaymentRequest, GetInfoResponse, GetPaymentRequest, IssueAssetRequest,
+        IssueAssetResponse, LedgerEntry, LedgerEntryType, LedgerInfo,
+        LightningPaymentLimitsResponse, Limits, LiquidNetwork, ListLedgerEntriesRequest,
         ListPaymentDetails, ListPaymentsRequest, LnUrlInfo, LnUrlPayRequest, LnUrlPayResult,
         LnUrlPaySuccessData, OnchainPaymentLimitsResponse, PayAmount, PayOnchainRequest, Payment,
-        PaymentDetails, PaymentMethod, PaymentState, PaymentType, PrepareBuyBitcoinRequest,
+        PaymentDetails, PaymentMethod, PaymentState, PaymentType, PegOutRequest,
+        PrepareAssetSwapRequest, PrepareAssetSwapResponse, PrepareBuyBitcoinRequest,
         PrepareBuyBitcoinResponse, PrepareLnUrlPayRequest, PrepareLnUrlPayResponse,
-        PreparePayOnchainRequest, PreparePayOnchainResponse, PrepareReceiveRequest,
-        PrepareReceiveResponse, PrepareRefundRequest, PrepareRefundResponse, PrepareSendRequest,
-        PrepareSendResponse, ReceiveAmount, ReceivePaymentRequest, ReceivePaymentResponse,
-        RecommendedFees, RefundRequest, RefundResponse, RefundableSwap, RestoreRequest, SdkEvent,
-        SendDestination, SendPaymentRequest, SendPaymentResponse, SignMessageRequest,
-        SignMessageResponse, WalletInfo,
+        PreparePayOnchainRequest, PreparePayOnchainResponse, PreparePegOutRequest,
+        PreparePegOutResponse, PrepareReceiveRequest, PrepareReceiveResponse, PrepareRefundRequest,
+        PrepareRefundResponse, PrepareSendRequest, PrepareSendResponse, ReceiveAmount,
+        ReceivePaymentRequest, ReceivePaymentResponse, ReceivePegInRequest, ReceivePegInResponse,
+        RecommendedFees, RefundRequest, RefundResponse, RefundableSwap, ReissueAssetRequest,
+        RestoreRequest, SdkEvent, SendDestination, SendPaymentRequest, SendPaymentResponse,
+        SettleAsAsset, SignMessageRequest, SignMessageResponse, SignPsetRequest, SignPsetResponse,
+        SubAccount, SwapQuote, TransferSubAccountFundsRequest, WalletInfo,
     },
     sdk::LiquidSdk,
 };
@@ -364,9 +372,35 @@ pub struct _Config {
     pub onchain_fee_rate_leeway_sat: Option<u64>,
     pub asset_metadata: Option<Vec<AssetMetadata>>,
     pub sideswap_api_key: Option<String>,
+    pub sideswap_url: Option<String>,
     pub use_magic_routing_hints: bool,
     pub onchain_sync_period_sec: u32,
     pub onchain_sync_request_timeout_sec: u32,
+    pub confirmation_policy: Option<ConfirmationPolicy>,
+    pub swapper_fallback_urls: Option<Vec<String>>,
+    pub federation_config: Option<FederationConfig>,
+    pub asset_registry_url: Option<String>,
+    pub breez_server_url: Option<String>,
+}
+
+#[frb(mirror(ConfirmationPolicy))]
+pub struct _ConfirmationPolicy {
+    pub liquid_confirmations: u32,
+    pub bitcoin_confirmations: u32,
+    pub tiers: Vec<ConfirmationTier>,
+}
+
+#[frb(mirror(ConfirmationTier))]
+pub struct _ConfirmationTier {
+    pub min_amount_sat: u64,
+    pub liquid_confirmations: u32,
+    pub bitcoin_confirmations: u32,
+}
+
+#[frb(mirror(FederationConfig))]
+pub struct _FederationConfig {
+    pub fedpeg_script: String,
+    pub pegin_confirmations: u32,
 }
 
 #[frb(mirror(ConnectRequest))]
@@ -375,6 +409,21 @@ pub struct _ConnectRequest {
     pub mnemonic: Option<String>,
     pub passphrase: Option<String>,
     pub seed: Option<Vec<u8>>,
+    pub account_index: Option<u32>,
+}
+
+#[frb(mirror(ConnectWatchOnlyRequest))]
+pub struct _ConnectWatchOnlyRequest {
+    pub config: Config,
+    pub ct_descriptor: String,
+}
+
+#[frb(mirror(ConnectMultisigRequest))]
+pub struct _ConnectMultisigRequest {
+    pub config: Config,
+    pub ct_descriptor: String,
+    pub mnemonic: Option<String>,
+    pub passphrase: Option<String>,
 }
 
 #[frb(mirror(CreateBolt12InvoiceRequest))]
@@ -407,6 +456,12 @@ pub struct _GetInfoResponse {
     pub blockchain_info: BlockchainInfo,
 }
 
+#[frb(mirror(AccountInfo))]
+pub struct _AccountInfo {
+    pub account_index: u32,
+    pub tx_count: u32,
+}
+
 #[frb(mirror(GetPaymentRequest))]
 pub enum _GetPaymentRequest {
     PaymentHash { payment_hash: String },
@@ -417,6 +472,8 @@ pub enum _GetPaymentRequest {
 pub struct _LightningPaymentLimitsResponse {
     pub send: Limits,
     pub receive: Limits,
+    pub send_quotes: Vec<SwapQuote>,
+    pub receive_quotes: Vec<SwapQuote>,
 }
 
 #[frb(mirror(LiquidNetwork))]
@@ -454,12 +511,15 @@ pub enum _LnUrlPayResult {
 pub struct _OnchainPaymentLimitsResponse {
     pub send: Limits,
     pub receive: Limits,
+    pub send_quotes: Vec<SwapQuote>,
+    pub receive_quotes: Vec<SwapQuote>,
 }
 
 #[frb(mirror(PayOnchainRequest))]
 pub struct _PayOnchainRequest {
     pub address: String,
     pub prepare_response: PreparePayOnchainResponse,
+    pub use_asset_fees: Option<bool>,
 }
 
 #[frb(mirror(Payment))]
@@ -473,6 +533,7 @@ pub struct _Payment {
     pub swapper_fees_sat: Option<u64>,
     pub payment_type: PaymentType,
     pub status: PaymentState,
+    pub blocks_remaining: Option<u32>,
     pub details: PaymentDetails,
 }
 
@@ -512,6 +573,8 @@ pub struct _PrepareLnUrlPayResponse {
 pub struct _PreparePayOnchainRequest {
     pub amount: PayAmount,
     pub fee_rate_sat_per_vbyte: Option<u32>,
+    pub swap_provider: Option<String>,
+    pub fee_asset: Option<String>,
 }
 
 #[frb(mirror(PreparePayOnchainResponse))]
@@ -519,12 +582,17 @@ pub struct _PreparePayOnchainResponse {
     pub receiver_amount_sat: u64,
     pub claim_fees_sat: u64,
     pub total_fees_sat: u64,
+    pub swap_provider: Option<String>,
+    pub swap_quotes: Vec<SwapQuote>,
+    pub fee_asset: Option<String>,
+    pub estimated_asset_fees: Option<f64>,
 }
 
 #[frb(mirror(PrepareReceiveRequest))]
 pub struct _PrepareReceiveRequest {
     pub payment_method: PaymentMethod,
     pub amount: Option<ReceiveAmount>,
+    pub swap_provider: Option<String>,
 }
 
 #[frb(mirror(PrepareReceiveResponse))]
@@ -535,6 +603,162 @@ pub struct _PrepareReceiveResponse {
     pub min_payer_amount_sat: Option<u64>,
     pub max_payer_amount_sat: Option<u64>,
     pub swapper_feerate: Option<f64>,
+    pub swap_provider: Option<String>,
+    pub swap_quotes: Vec<SwapQuote>,
+}
+
+#[frb(mirror(ReceivePegInRequest))]
+pub struct _ReceivePegInRequest {
+    pub description: Option<String>,
+}
+
+#[frb(mirror(ReceivePegInResponse))]
+pub struct _ReceivePegInResponse {
+    pub peg_id: String,
+    pub bitcoin_address: String,
+    pub required_confirmations: u32,
+}
+
+#[frb(mirror(PreparePegOutRequest))]
+pub struct _PreparePegOutRequest {
+    pub receiver_amount_sat: u64,
+}
+
+#[frb(mirror(PreparePegOutResponse))]
+pub struct _PreparePegOutResponse {
+    pub receiver_amount_sat: u64,
+    pub fees_sat: u64,
+}
+
+#[frb(mirror(PegOutRequest))]
+pub struct _PegOutRequest {
+    pub bitcoin_address: String,
+    pub prepare_response: PreparePegOutResponse,
+}
+
+#[frb(mirror(AssetContract))]
+pub struct _AssetContract {
+    pub domain: String,
+    pub name: String,
+    pub ticker: String,
+    pub precision: u8,
+    pub issuer_pubkey: Option<String>,
+}
+
+#[frb(mirror(IssueAssetRequest))]
+pub struct _IssueAssetRequest {
+    pub amount: f64,
+    pub reissuance_token_amount_sat: Option<u64>,
+    pub contract: AssetContract,
+}
+
+#[frb(mirror(IssueAssetResponse))]
+pub struct _IssueAssetResponse {
+    pub asset_id: String,
+    pub reissuance_token_id: Option<String>,
+    pub contract_hash: String,
+    pub payment: Payment,
+}
+
+#[frb(mirror(ReissueAssetRequest))]
+pub struct _ReissueAssetRequest {
+    pub asset_id: String,
+    pub amount: f64,
+}
+
+#[frb(mirror(BurnAssetRequest))]
+pub struct _BurnAssetRequest {
+    pub asset_id: String,
+    pub amount: f64,
+}
+
+#[frb(mirror(AssetOperationResponse))]
+pub struct _AssetOperationResponse {
+    pub payment: Payment,
+}
+
+#[frb(mirror(CreateSubAccountRequest))]
+pub struct _CreateSubAccountRequest {
+    pub name: String,
+}
+
+#[frb(mirror(SubAccount))]
+pub struct _SubAccount {
+    pub id: String,
+    pub name: String,
+    pub balance_sat: u64,
+    pub created_at: u32,
+}
+
+#[frb(mirror(AttributeToSubAccountRequest))]
+pub struct _AttributeToSubAccountRequest {
+    pub sub_account_id: String,
+    pub destination: String,
+}
+
+#[frb(mirror(TransferSubAccountFundsRequest))]
+pub struct _TransferSubAccountFundsRequest {
+    pub from_sub_account_id: Option<String>,
+    pub to_sub_account_id: Option<String>,
+    pub amount_sat: u64,
+    pub description: Option<String>,
+}
+
+#[frb(mirror(LedgerEntryType))]
+pub enum _LedgerEntryType {
+    Receive,
+    Send,
+    Refund,
+    Transfer,
+}
+
+#[frb(mirror(LedgerEntry))]
+pub struct _LedgerEntry {
+    pub id: i64,
+    pub sub_account_id: String,
+    pub entry_type: LedgerEntryType,
+    pub amount_sat: i64,
+    pub balance_sat: u64,
+    pub payment_id: Option<String>,
+    pub transfer_id: Option<String>,
+    pub description: Option<String>,
+    pub created_at: u32,
+}
+
+#[frb(mirror(ListLedgerEntriesRequest))]
+pub struct _ListLedgerEntriesRequest {
+    pub sub_account_id: Option<String>,
+    pub offset: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+#[frb(mirror(LedgerInfo))]
+pub struct _LedgerInfo {
+    pub allocated_sat: u64,
+    pub unallocated_sat: u64,
+}
+
+#[frb(mirror(PrepareAssetSwapRequest))]
+pub struct _PrepareAssetSwapRequest {
+    pub from_asset: String,
+    pub to_asset: String,
+    pub receiver_amount: f64,
+}
+
+#[frb(mirror(PrepareAssetSwapResponse))]
+pub struct _PrepareAssetSwapResponse {
+    pub from_asset: String,
+    pub to_asset: String,
+    pub payer_amount_sat: u64,
+    pub receiver_amount_sat: u64,
+    pub fees_sat: u64,
+    pub exchange_rate: f64,
+}
+
+#[frb(mirror(AssetSwapRequest))]
+pub struct _AssetSwapRequest {
+    pub prepare_response: PrepareAssetSwapResponse,
+    pub description: Option<String>,
 }
 
 #[frb(mirror(PrepareRefundRequest))]
@@ -557,6 +781,10 @@ pub struct _PrepareSendRequest {
     pub amount: Option<PayAmount>,
     pub disable_mrh: Option<bool>,
     pub payment_timeout_sec: Option<u64>,
+    pub swap_provider: Option<String>,
+    pub from_asset: Option<String>,
+    pub fee_asset: Option<String>,
+    pub export_pset: Option<bool>,
 }
 
 #[frb(mirror(PrepareSendResponse))]
@@ -566,8 +794,13 @@ pub struct _PrepareSendResponse {
     pub fees_sat: Option<u64>,
     pub estimated_asset_fees: Option<f64>,
     pub exchange_amount_sat: Option<u64>,
+    pub from_asset: Option<String>,
+    pub fee_asset: Option<String>,
     pub disable_mrh: Option<bool>,
     pub payment_timeout_sec: Option<u64>,
+    pub swap_provider: Option<String>,
+    pub swap_quotes: Vec<SwapQuote>,
+    pub unsigned_pset: Option<String>,
 }
 
 #[frb(mirror(DescriptionHash))]
@@ -582,6 +815,13 @@ pub struct _ReceivePaymentRequest {
     pub description: Option<String>,
     pub description_hash: Option<DescriptionHash>,
     pub payer_note: Option<String>,
+    pub settle_as_asset: Option<SettleAsAsset>,
+}
+
+#[frb(mirror(SettleAsAsset))]
+pub struct _SettleAsAsset {
+    pub asset_id: String,
+    pub max_slippage_bps: Option<u32>,
 }
 
 #[frb(mirror(ReceivePaymentResponse))]
@@ -630,6 +870,14 @@ pub struct _SendPaymentRequest {
     pub prepare_response: PrepareSendResponse,
     pub use_asset_fees: Option<bool>,
     pub payer_note: Option<String>,
+    pub sub_account_id: Option<String>,
+}
+
+#[frb(mirror(FinalizeSendPaymentRequest))]
+pub struct _FinalizeSendPaymentRequest {
+    pub prepare_response: PrepareSendResponse,
+    pub signed_pset: String,
+    pub sub_account_id: Option<String>,
 }
 
 #[frb(mirror(SendPaymentResponse))]
@@ -647,6 +895,26 @@ pub struct _SignMessageResponse {
     pub signature: String,
 }
 
+#[frb(mirror(SignPsetRequest))]
+pub struct _SignPsetRequest {
+    pub pset: String,
+}
+
+#[frb(mirror(SignPsetResponse))]
+pub struct _SignPsetResponse {
+    pub pset: String,
+}
+
+#[frb(mirror(CombinePsetsRequest))]
+pub struct _CombinePsetsRequest {
+    pub psets: Vec<String>,
+}
+
+#[frb(mirror(CombinePsetsResponse))]
+pub struct _CombinePsetsResponse {
+    pub pset: String,
+}
+
 #[frb(mirror(AssetMetadata))]
 pub struct _AssetMetadata {
     pub asset_id: String,
@@ -680,6 +948,15 @@ pub struct _Limits {
     pub max_zero_conf_sat: u64,
 }
 
+#[frb(mirror(SwapQuote))]
+pub struct _SwapQuote {
+    pub swap_provider: String,
+    pub limits: Limits,
+    pub fees_percentage: f64,
+    pub fixed_fees_sat: u64,
+    pub fees_sat: Option<u64>,
+}
+
 #[frb(mirror(ListPaymentDetails))]
 pub enum _ListPaymentDetails {
     Liquid {
@@ -703,6 +980,9 @@ pub enum _PayAmount {
         from_asset: Option<String>,
     },
     Drain,
+    DrainAsset {
+        asset_id: String,
+    },
 }
 
 #[frb(mirror(PaymentDetails))]
@@ -723,6 +1003,7 @@ pub enum _PaymentDetails {
         refund_tx_id: Option<String>,
         refund_tx_amount_sat: Option<u64>,
         settled_at: Option<u32>,
+        asset_swap_id: Option<String>,
     },
     Liquid {
         destination: String,
@@ -745,6 +1026,24 @@ pub enum _PaymentDetails {
         refund_tx_id: Option<String>,
         refund_tx_amount_sat: Option<u64>,
     },
+    Peg {
+        peg_id: String,
+        bitcoin_address: String,
+        description: String,
+        bitcoin_tx_id: Option<String>,
+        claim_tx_id: Option<String>,
+    },
+    AssetSwap {
+        swap_id: String,
+        description: String,
+        from_asset_id: String,
+        to_asset_id: String,
+        payer_amount_sat: u64,
+        receiver_amount_sat: u64,
+        from_asset_info: Option<AssetInfo>,
+        to_asset_info: Option<AssetInfo>,
+        receive_swap_id: Option<String>,
+    },
 }
 
 #[frb(mirror(PaymentMethod))]
@@ -15,6 +15,16 @@ pub async fn connect(req: ConnectRequest) -> Result<BreezSdkLiquid, SdkError> {
     Ok(BreezSdkLiquid { sdk: ln_sdk })
 }
 
+pub async fn connect_watch_only(req: ConnectWatchOnlyRequest) -> Result<BreezSdkLiquid, SdkError> {
+    let ln_sdk = LiquidSdk::connect_watch_only(req).await?;
+    Ok(BreezSdkLiquid { sdk: ln_sdk })
+}
+
+pub async fn connect_multisig(req: ConnectMultisigRequest) -> Result<BreezSdkLiquid, SdkError> {
+    let ln_sdk = LiquidSdk::connect_multisig(req).await?;
+    Ok(BreezSdkLiquid { sdk: ln_sdk })
+}
+
 #[frb(sync)]
 pub fn default_config(
     network: LiquidNetwork,
@@ -38,9 +48,15 @@ impl BreezSdkLiquid {
         self.sdk.get_info().await
     }
 
-    #[frb(sync)]
-    pub fn sign_message(&self, req: SignMessageRequest) -> Result<SignMessageResponse, SdkError> {
-        self.sdk.sign_message(&req)
+    pub async fn list_accounts(&self) -> Result<Vec<AccountInfo>, SdkError> {
+        self.sdk.list_accounts().await
+    }
+
+    pub async fn sign_message(
+        &self,
+        req: SignMessageRequest,
+    ) -> Result<SignMessageResponse, SdkError> {
+        self.sdk.sign_message(&req).await
     }
 
     #[frb(sync)]
@@ -78,6 +94,25 @@ impl BreezSdkLiquid {
         self.sdk.send_payment(&req).await
     }
 
+    pub async fn finalize_send_payment(
+        &self,
+        req: FinalizeSendPaymentRequest,
+    ) -> Result<SendPaymentResponse, PaymentError> {
+        self.sdk.finalize_send_payment(&req).await
+    }
+
+    pub async fn sign_pset(&self, req: SignPsetRequest) -> Result<SignPsetResponse, PaymentError> {
+        self.sdk.sign_pset(&req).await
+    }
+
+    #[frb(sync)]
+    pub fn combine_psets(
+        &self,
+        req: CombinePsetsRequest,
+    ) -> Result<CombinePsetsResponse, PaymentError> {
+        self.sdk.combine_psets(&req)
+    }
+
     pub async fn prepare_receive_payment(
         &self,
         req: PrepareReceiveRequest,
@@ -123,6 +158,59 @@ impl BreezSdkLiquid {
         self.sdk.pay_onchain(&req).await
     }
 
+    pub async fn receive_peg_in(
+        &self,
+        req: ReceivePegInRequest,
+    ) -> Result<ReceivePegInResponse, PaymentError> {
+        self.sdk.receive_peg_in(&req).await
+    }
+
+    pub async fn prepare_peg_out(
+        &self,
+        req: PreparePegOutRequest,
+    ) -> Result<PreparePegOutResponse, PaymentError> {
+        self.sdk.prepare_peg_out(&req).await
+    }
+
+    pub async fn peg_out(&self, req: PegOutRequest) -> Result<SendPaymentResponse, PaymentError> {
+        self.sdk.peg_out(&req).await
+    }
+
+    pub async fn issue_asset(
+        &self,
+        req: IssueAssetRequest,
+    ) -> Result<IssueAssetResponse, PaymentError> {
+        self.sdk.issue_asset(&req).await
+    }
+
+    pub async fn reissue_asset(
+        &self,
+        req: ReissueAssetRequest,
+    ) -> Result<AssetOperationResponse, PaymentError> {
+        self.sdk.reissue_asset(&req).await
+    }
+
+    pub async fn burn_asset(
+        &self,
+        req: BurnAssetRequest,
+    ) -> Result<AssetOperationResponse, PaymentError> {
+        self.sdk.burn_asset(&req).await
+    }
+
+    pub async fn prepare_asset_swap(
+        &self,
+        req: PrepareAssetSwapRequest,
+    ) -> Result<PrepareAssetSwapResponse, PaymentError> {
+        self.sdk.prepare_asset_swap(&req).await
+    }
+
+    pub async fn asset_swap(
+        &self,
+        req: AssetSwapRequest,
+    ) -> Result<AssetOperationResponse, PaymentError> {
+        self.sdk.asset_swap(&req).await
+    }
+
     pub async fn prepare_buy_bitcoin(
         &self,
         req: PrepareBuyBitcoinRequest,
@@ -148,6 +236,42 @@ impl BreezSdkLiquid {
         self.sdk.get_payment(&req).await
     }
 
+    pub async fn create_sub_account(
+        &self,
+        req: CreateSubAccountRequest,
+    ) -> Result<SubAccount, SdkError> {
+        self.sdk.create_sub_account(&req).await
+    }
+
+    pub async fn list_sub_accounts(&self) -> Result<Vec<SubAccount>, SdkError> {
+        self.sdk.list_sub_accounts().await
+    }
+
+    pub async fn attribute_to_sub_account(
+        &self,
+        req: AttributeToSubAccountRequest,
+    ) -> Result<(), SdkError> {
+        self.sdk.attribute_to_sub_account(&req).await
+    }
+
+    pub async fn transfer_sub_account_funds(
+        &self,
+        req: TransferSubAccountFundsRequest,
+    ) -> Result<Vec<LedgerEntry>, SdkError> {
+        self.sdk.transfer_sub_account_funds(&req).await
+    }
+
+    pub async fn list_ledger_entries(
+        &self,
+        req: ListLedgerEntriesRequest,
+    ) -> Result<Vec<LedgerEntry>, SdkError> {
+        self.sdk.list_ledger_entries(&req).await
+    }
+
+    pub async fn get_ledger_info(&self) -> Result<LedgerInfo, SdkError> {
+        self.sdk.get_ledger_info().await
+    }
+
     pub async fn fetch_payment_proposed_fees(
         &self,
         req: FetchPaymentProposedFeesRequest,